@@ -42,14 +42,16 @@ impl SocketClient {
     fn start_websocket_listener(
         config: &Config,
         client_input: ClientInput,
-        client_output: ClientOutput,
+        mut client_output: ClientOutput,
         client_state: ClientState,
         self_address: &Recipient,
         shutdown: nym_task::TaskClient,
         packet_type: PacketType,
-    ) {
+    ) -> Result<(), ClientError> {
         info!("Starting websocket listener...");
 
+        let delivery_status_receiver = client_output.register_delivery_status_receiver()?;
+
         let ClientInput {
             connection_command_sender,
             input_sender,
@@ -57,6 +59,7 @@ impl SocketClient {
 
         let ClientOutput {
             received_buffer_request_sender,
+            ..
         } = client_output;
 
         let ClientState {
@@ -65,10 +68,18 @@ impl SocketClient {
             ..
         } = client_state;
 
+        let (message_router, reconstructed_receiver) =
+            websocket::MessageRouter::new(received_buffer_request_sender);
+        message_router.start(
+            reconstructed_receiver,
+            delivery_status_receiver,
+            shutdown.fork("websocket_message_router"),
+        );
+
         let websocket_handler = websocket::HandlerBuilder::new(
             input_sender,
             connection_command_sender,
-            received_buffer_request_sender,
+            message_router,
             self_address,
             shared_lane_queue_lengths,
             reply_controller_sender,
@@ -77,6 +88,8 @@ impl SocketClient {
 
         websocket::Listener::new(config.socket.host, config.socket.listening_port)
             .start(websocket_handler, shutdown);
+
+        Ok(())
     }
 
     /// blocking version of `start_socket` method. Will run forever (or until SIGINT is sent)
@@ -139,7 +152,7 @@ impl SocketClient {
             &self_address,
             started_client.task_handle.get_handle(),
             packet_type,
-        );
+        )?;
 
         info!("Client startup finished!");
         info!("The address of this client is: {self_address}");
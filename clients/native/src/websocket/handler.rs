@@ -1,17 +1,20 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use futures::channel::mpsc;
+use super::router::{
+    ClientTrackingId, DeliveryStatusUpdatesReceiver, MessageRouter, SubscriptionId, WsConnectionId,
+};
 use futures::{SinkExt, StreamExt};
 use log::*;
 use nym_client_core::client::replies::reply_controller::requests::ReplyControllerSender;
 use nym_client_core::client::{
+    delivery_status::{DeliveryStatus, MessageTrackingId},
     inbound_messages::{InputMessage, InputMessageSender},
-    received_buffer::{
-        ReceivedBufferMessage, ReceivedBufferRequestSender, ReconstructedMessagesReceiver,
-    },
+    received_buffer::ReconstructedMessagesReceiver,
+};
+use nym_client_websocket_requests::{
+    filter::MessageFilter, requests::ClientRequest, responses, responses::ServerResponse,
 };
-use nym_client_websocket_requests::{requests::ClientRequest, responses::ServerResponse};
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
 use nym_sphinx::params::PacketType;
@@ -38,7 +41,7 @@ enum ReceivedResponseType {
 pub(crate) struct HandlerBuilder {
     msg_input: InputMessageSender,
     client_connection_tx: ConnectionCommandSender,
-    buffer_requester: ReceivedBufferRequestSender,
+    router: MessageRouter,
     self_full_address: Recipient,
     lane_queue_lengths: LaneQueueLengths,
     reply_controller_sender: ReplyControllerSender,
@@ -49,7 +52,7 @@ impl HandlerBuilder {
     pub(crate) fn new(
         msg_input: InputMessageSender,
         client_connection_tx: ConnectionCommandSender,
-        buffer_requester: ReceivedBufferRequestSender,
+        router: MessageRouter,
         self_full_address: &Recipient,
         lane_queue_lengths: LaneQueueLengths,
         reply_controller_sender: ReplyControllerSender,
@@ -58,7 +61,7 @@ impl HandlerBuilder {
         Self {
             msg_input,
             client_connection_tx,
-            buffer_requester,
+            router,
             self_full_address: *self_full_address,
             lane_queue_lengths,
             reply_controller_sender,
@@ -66,12 +69,12 @@ impl HandlerBuilder {
         }
     }
 
-    // TODO: make sure we only ever have one active handler
     pub fn create_active_handler(&self) -> Handler {
         Handler {
             msg_input: self.msg_input.clone(),
             client_connection_tx: self.client_connection_tx.clone(),
-            buffer_requester: self.buffer_requester.clone(),
+            router: self.router.clone(),
+            ws_connection_id: None,
             self_full_address: self.self_full_address,
             socket: None,
             received_response_type: Default::default(),
@@ -85,7 +88,8 @@ impl HandlerBuilder {
 pub(crate) struct Handler {
     msg_input: InputMessageSender,
     client_connection_tx: ConnectionCommandSender,
-    buffer_requester: ReceivedBufferRequestSender,
+    router: MessageRouter,
+    ws_connection_id: Option<WsConnectionId>,
     self_full_address: Recipient,
     socket: Option<WebSocketStream<TcpStream>>,
     received_response_type: ReceivedResponseType,
@@ -96,12 +100,8 @@ pub(crate) struct Handler {
 
 impl Drop for Handler {
     fn drop(&mut self) {
-        if self
-            .buffer_requester
-            .unbounded_send(ReceivedBufferMessage::ReceiverDisconnect)
-            .is_err()
-        {
-            error!("we failed to disconnect the receiver from the buffer! presumably the shutdown procedure has been initiated!")
+        if let Some(ws_connection_id) = self.ws_connection_id {
+            self.router.unregister_connection(ws_connection_id)
         }
     }
 }
@@ -155,6 +155,7 @@ impl Handler {
         recipient: Recipient,
         message: Vec<u8>,
         connection_id: Option<u64>,
+        tracking_id: Option<MessageTrackingId>,
     ) -> Option<ServerResponse> {
         info!(
             "Attempting to send {:.2} kiB message to {recipient} on connection_id {connection_id:?}",
@@ -169,7 +170,7 @@ impl Handler {
         // the ack control is now responsible for chunking, etc.
         let input_msg = InputMessage::new_regular(recipient, message, lane, self.packet_type);
         self.msg_input
-            .send(input_msg)
+            .send(with_tracking_id(input_msg, tracking_id))
             .await
             .expect("InputMessageReceiver has stopped receiving!");
 
@@ -187,6 +188,7 @@ impl Handler {
         message: Vec<u8>,
         reply_surbs: u32,
         connection_id: Option<u64>,
+        tracking_id: Option<MessageTrackingId>,
     ) -> Option<ServerResponse> {
         info!(
             "Attempting to anonymously send {:.2} kiB message to {recipient} on connection_id {connection_id:?} while attaching {reply_surbs} replySURBs.",
@@ -201,7 +203,7 @@ impl Handler {
         let input_msg =
             InputMessage::new_anonymous(recipient, message, reply_surbs, lane, self.packet_type);
         self.msg_input
-            .send(input_msg)
+            .send(with_tracking_id(input_msg, tracking_id))
             .await
             .expect("InputMessageReceiver has stopped receiving!");
 
@@ -218,6 +220,7 @@ impl Handler {
         recipient_tag: AnonymousSenderTag,
         message: Vec<u8>,
         connection_id: Option<u64>,
+        tracking_id: Option<MessageTrackingId>,
     ) -> Option<ServerResponse> {
         info!("Attempting to send {:.2} kiB reply message to {recipient_tag} on connection_id {connection_id:?}", message.len() as f64 / 1024.0);
 
@@ -228,7 +231,7 @@ impl Handler {
 
         let input_msg = InputMessage::new_reply(recipient_tag, message, lane, self.packet_type);
         self.msg_input
-            .send(input_msg)
+            .send(with_tracking_id(input_msg, tracking_id))
             .await
             .expect("InputMessageReceiver has stopped receiving!");
 
@@ -255,13 +258,42 @@ impl Handler {
         self.get_lane_queue_length(connection_id).await
     }
 
-    async fn handle_request(&mut self, request: ClientRequest) -> Option<ServerResponse> {
+    fn handle_subscribe(&self, filter: MessageFilter, exclusive: bool) -> ServerResponse {
+        let Some(ws_connection_id) = self.ws_connection_id else {
+            return ServerResponse::new_error("the websocket connection is not registered");
+        };
+
+        match self.router.subscribe(ws_connection_id, filter, exclusive) {
+            Ok(subscription_id) => ServerResponse::Subscribed { subscription_id },
+            Err(err) => ServerResponse::new_error(err.to_string()),
+        }
+    }
+
+    fn handle_unsubscribe(&self, subscription_id: SubscriptionId) -> ServerResponse {
+        let Some(ws_connection_id) = self.ws_connection_id else {
+            return ServerResponse::new_error("the websocket connection is not registered");
+        };
+
+        match self.router.unsubscribe(ws_connection_id, subscription_id) {
+            Ok(_) => ServerResponse::Unsubscribed { subscription_id },
+            Err(err) => ServerResponse::new_error(err.to_string()),
+        }
+    }
+
+    async fn handle_send_request(
+        &mut self,
+        request: ClientRequest,
+        tracking_id: Option<MessageTrackingId>,
+    ) -> Option<ServerResponse> {
         match request {
             ClientRequest::Send {
                 recipient,
                 message,
                 connection_id,
-            } => self.handle_send(recipient, message, connection_id).await,
+            } => {
+                self.handle_send(recipient, message, connection_id, tracking_id)
+                    .await
+            }
 
             ClientRequest::SendAnonymous {
                 recipient,
@@ -269,19 +301,75 @@ impl Handler {
                 reply_surbs,
                 connection_id,
             } => {
-                self.handle_send_anonymous(recipient, message, reply_surbs, connection_id)
-                    .await
+                self.handle_send_anonymous(
+                    recipient,
+                    message,
+                    reply_surbs,
+                    connection_id,
+                    tracking_id,
+                )
+                .await
             }
 
             ClientRequest::Reply {
                 message,
                 sender_tag,
                 connection_id,
-            } => self.handle_reply(sender_tag, message, connection_id).await,
+            } => {
+                self.handle_reply(sender_tag, message, connection_id, tracking_id)
+                    .await
+            }
+
+            _ => Some(ServerResponse::new_error(
+                "only 'send', 'sendAnonymous' and 'reply' requests can be tracked",
+            )),
+        }
+    }
+
+    async fn handle_tracked(
+        &mut self,
+        client_tracking_id: ClientTrackingId,
+        request: ClientRequest,
+    ) -> Option<ServerResponse> {
+        if !request.is_trackable() {
+            // don't bother tracking anything, just produce the appropriate error response
+            return self.handle_send_request(request, None).await;
+        }
+        let Some(ws_connection_id) = self.ws_connection_id else {
+            return Some(ServerResponse::new_error(
+                "the websocket connection is not registered",
+            ));
+        };
+
+        match self
+            .router
+            .track_message(ws_connection_id, client_tracking_id)
+        {
+            Ok(tracking_id) => self.handle_send_request(request, Some(tracking_id)).await,
+            Err(err) => Some(ServerResponse::new_error(err.to_string())),
+        }
+    }
+
+    async fn handle_request(&mut self, request: ClientRequest) -> Option<ServerResponse> {
+        match request {
+            request @ (ClientRequest::Send { .. }
+            | ClientRequest::SendAnonymous { .. }
+            | ClientRequest::Reply { .. }) => self.handle_send_request(request, None).await,
+
+            ClientRequest::Tracked {
+                tracking_id,
+                request,
+            } => self.handle_tracked(tracking_id, *request).await,
 
             ClientRequest::SelfAddress => Some(self.handle_self_address()),
             ClientRequest::ClosedConnection(id) => self.handle_closed_connection(id),
             ClientRequest::GetLaneQueueLength(id) => self.handle_get_lane_queue_length(id).await,
+            ClientRequest::Subscribe { filter, exclusive } => {
+                Some(self.handle_subscribe(filter, exclusive))
+            }
+            ClientRequest::Unsubscribe { subscription_id } => {
+                Some(self.handle_unsubscribe(subscription_id))
+            }
         }
     }
 
@@ -362,9 +450,34 @@ impl Handler {
         }
     }
 
+    async fn push_websocket_delivery_status(
+        &mut self,
+        tracking_id: ClientTrackingId,
+        status: DeliveryStatus,
+    ) -> Result<(), WsError> {
+        let status = match status {
+            DeliveryStatus::Delivered => responses::DeliveryStatus::Delivered,
+            DeliveryStatus::Retransmitting(retransmissions) => {
+                responses::DeliveryStatus::Retransmitting { retransmissions }
+            }
+            DeliveryStatus::Failed => responses::DeliveryStatus::Failed,
+        };
+        let response = ServerResponse::DeliveryStatus {
+            tracking_id,
+            status,
+        };
+
+        let response_message = match self.received_response_type {
+            ReceivedResponseType::Binary => WsMessage::Binary(response.into_binary()),
+            ReceivedResponseType::Text => WsMessage::Text(response.into_text()),
+        };
+        self.send_websocket_response(response_message).await
+    }
+
     async fn listen_for_requests(
         &mut self,
         mut msg_receiver: ReconstructedMessagesReceiver,
+        mut status_receiver: DeliveryStatusUpdatesReceiver,
         mut task_client: nym_task::TaskClient,
     ) {
         while !task_client.is_shutdown() {
@@ -406,6 +519,13 @@ impl Handler {
                         break;
                     }
                 }
+                // or a change in delivery status of a message sent by this connection
+                Some((tracking_id, status)) = status_receiver.next() => {
+                    if let Err(err) = self.push_websocket_delivery_status(tracking_id, status).await {
+                        warn!("failed to send delivery status back to the client - {err}, assuming the connection is dead");
+                        break;
+                    }
+                }
                 _ = task_client.recv() => {
                     log::trace!("Websocket handler: Received shutdown");
                 }
@@ -433,20 +553,23 @@ impl Handler {
         };
         self.socket = Some(ws_stream);
 
-        let (reconstructed_sender, reconstructed_receiver) = mpsc::unbounded();
-
-        // tell the buffer to start sending stuff to us
-        self.buffer_requester
-            .unbounded_send(ReceivedBufferMessage::ReceiverAnnounce(
-                reconstructed_sender,
-            ))
-            .expect("the buffer request failed!");
+        // tell the router to start sending stuff to us
+        let (ws_connection_id, reconstructed_receiver, status_receiver) =
+            self.router.register_connection();
+        self.ws_connection_id = Some(ws_connection_id);
 
-        self.listen_for_requests(reconstructed_receiver, task_client)
+        self.listen_for_requests(reconstructed_receiver, status_receiver, task_client)
             .await;
     }
 }
 
+fn with_tracking_id(message: InputMessage, tracking_id: Option<MessageTrackingId>) -> InputMessage {
+    match tracking_id {
+        Some(tracking_id) => message.with_tracking_id(tracking_id),
+        None => message,
+    }
+}
+
 // I'm still not entirely sure why `send_all` requires `TryStream` rather than `Stream`, but
 // let's just play along for now
 fn prepare_reconstructed_binary(
@@ -3,6 +3,8 @@
 
 pub(crate) use handler::HandlerBuilder;
 pub(crate) use listener::Listener;
+pub(crate) use router::MessageRouter;
 
 pub(crate) mod handler;
 pub(crate) mod listener;
+pub(crate) mod router;
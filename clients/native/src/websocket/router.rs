@@ -0,0 +1,560 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::channel::mpsc;
+use futures::StreamExt;
+use log::*;
+use nym_client_core::client::delivery_status::{
+    DeliveryStatus, DeliveryStatusEvent, DeliveryStatusReceiver, MessageTrackingId,
+};
+use nym_client_core::client::received_buffer::{
+    ReceivedBufferMessage, ReceivedBufferRequestSender, ReconstructedMessagesReceiver,
+    ReconstructedMessagesSender,
+};
+use nym_client_websocket_requests::filter::MessageFilter;
+use nym_sphinx::receiver::ReconstructedMessage;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+
+pub(crate) type WsConnectionId = u64;
+pub(crate) type SubscriptionId = u64;
+
+/// Tracking id of a message, as provided by the websocket client.
+pub(crate) type ClientTrackingId = u64;
+
+pub(crate) type DeliveryStatusUpdatesSender =
+    mpsc::UnboundedSender<(ClientTrackingId, DeliveryStatus)>;
+pub(crate) type DeliveryStatusUpdatesReceiver =
+    mpsc::UnboundedReceiver<(ClientTrackingId, DeliveryStatus)>;
+
+#[derive(Debug, Error)]
+pub(crate) enum SubscriptionError {
+    #[error("websocket connection {connection_id} is not registered")]
+    UnknownConnection { connection_id: WsConnectionId },
+
+    #[error("subscription {subscription_id} does not exist")]
+    UnknownSubscription { subscription_id: SubscriptionId },
+
+    #[error("messages matching the provided filter are already exclusively delivered to another connection")]
+    ExclusiveFilterTaken,
+}
+
+struct Subscription {
+    filter: MessageFilter,
+    exclusive: bool,
+}
+
+struct Connection {
+    sender: ReconstructedMessagesSender,
+    status_sender: DeliveryStatusUpdatesSender,
+    subscriptions: BTreeMap<SubscriptionId, Subscription>,
+}
+
+#[derive(Default)]
+struct Connections {
+    // used for connection, subscription and message tracking ids
+    next_id: u64,
+    connections: BTreeMap<WsConnectionId, Connection>,
+
+    // tracking ids given to the client core are unique across all connections,
+    // so we have to remember who should be notified and with what id
+    tracked_messages: HashMap<MessageTrackingId, (WsConnectionId, ClientTrackingId)>,
+
+    // messages received while there were no connections present, i.e. in the middle of
+    // the last connection going away and the buffer being told to stop sending us anything
+    pending: Vec<ReconstructedMessage>,
+}
+
+impl Connections {
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    /// Determines which connections should receive the provided message:
+    /// - if it matches any exclusive subscription, it's only going to the owner of the oldest one,
+    /// - otherwise it goes to all connections with a matching shared subscription,
+    /// - finally, if nobody has claimed it, it goes to all connections without any subscriptions.
+    fn route(&self, message: &ReconstructedMessage) -> Vec<WsConnectionId> {
+        let exclusive_owner = self
+            .connections
+            .iter()
+            .flat_map(|(connection_id, connection)| {
+                connection
+                    .subscriptions
+                    .iter()
+                    .map(move |(subscription_id, sub)| (*subscription_id, *connection_id, sub))
+            })
+            .filter(|(_, _, sub)| sub.exclusive && sub.filter.matches(message))
+            .min_by_key(|(subscription_id, _, _)| *subscription_id);
+
+        if let Some((_, connection_id, _)) = exclusive_owner {
+            return vec![connection_id];
+        }
+
+        let shared: Vec<_> = self
+            .connections
+            .iter()
+            .filter(|(_, connection)| {
+                connection
+                    .subscriptions
+                    .values()
+                    .any(|sub| !sub.exclusive && sub.filter.matches(message))
+            })
+            .map(|(connection_id, _)| *connection_id)
+            .collect();
+
+        if !shared.is_empty() {
+            return shared;
+        }
+
+        self.connections
+            .iter()
+            .filter(|(_, connection)| connection.subscriptions.is_empty())
+            .map(|(connection_id, _)| *connection_id)
+            .collect()
+    }
+}
+
+/// Fans out the reconstructed messages to all websocket connections according to their subscriptions.
+/// It acts as the single receiver registered with the received messages buffer for as long as
+/// there is at least one connection present.
+#[derive(Clone)]
+pub(crate) struct MessageRouter {
+    connections: Arc<Mutex<Connections>>,
+    buffer_requester: ReceivedBufferRequestSender,
+    reconstructed_sender: ReconstructedMessagesSender,
+}
+
+impl MessageRouter {
+    pub(crate) fn new(
+        buffer_requester: ReceivedBufferRequestSender,
+    ) -> (Self, ReconstructedMessagesReceiver) {
+        let (reconstructed_sender, reconstructed_receiver) = mpsc::unbounded();
+        (
+            MessageRouter {
+                connections: Default::default(),
+                buffer_requester,
+                reconstructed_sender,
+            },
+            reconstructed_receiver,
+        )
+    }
+
+    fn lock_connections(&self) -> MutexGuard<'_, Connections> {
+        // the lock can only be poisoned if another thread panicked while holding it,
+        // at which point there's nothing sensible we could do anyway
+        self.connections
+            .lock()
+            .expect("websocket connections lock got poisoned")
+    }
+
+    pub(crate) fn register_connection(
+        &self,
+    ) -> (
+        WsConnectionId,
+        ReconstructedMessagesReceiver,
+        DeliveryStatusUpdatesReceiver,
+    ) {
+        let (sender, receiver) = mpsc::unbounded();
+        let (status_sender, status_receiver) = mpsc::unbounded();
+
+        let mut guard = self.lock_connections();
+        let connection_id = guard.next_id();
+
+        if guard.connections.is_empty() {
+            let pending = std::mem::take(&mut guard.pending);
+            if !pending.is_empty() && sender.unbounded_send(pending).is_err() {
+                // this can't happen as we're holding the receiver
+                error!("failed to forward pending messages to a fresh websocket connection")
+            }
+
+            // tell the buffer to start sending stuff to us
+            if self
+                .buffer_requester
+                .unbounded_send(ReceivedBufferMessage::ReceiverAnnounce(
+                    self.reconstructed_sender.clone(),
+                ))
+                .is_err()
+            {
+                error!("we failed to announce the receiver to the buffer! presumably the shutdown procedure has been initiated!")
+            }
+        }
+
+        guard.connections.insert(
+            connection_id,
+            Connection {
+                sender,
+                status_sender,
+                subscriptions: BTreeMap::new(),
+            },
+        );
+        debug!("registered websocket connection {connection_id}");
+
+        (connection_id, receiver, status_receiver)
+    }
+
+    pub(crate) fn unregister_connection(&self, connection_id: WsConnectionId) {
+        let mut guard = self.lock_connections();
+        if guard.connections.remove(&connection_id).is_none() {
+            return;
+        }
+        guard
+            .tracked_messages
+            .retain(|_, (owner, _)| *owner != connection_id);
+        debug!("unregistered websocket connection {connection_id}");
+
+        if guard.connections.is_empty()
+            && self
+                .buffer_requester
+                .unbounded_send(ReceivedBufferMessage::ReceiverDisconnect)
+                .is_err()
+        {
+            error!("we failed to disconnect the receiver from the buffer! presumably the shutdown procedure has been initiated!")
+        }
+    }
+
+    pub(crate) fn subscribe(
+        &self,
+        connection_id: WsConnectionId,
+        filter: MessageFilter,
+        exclusive: bool,
+    ) -> Result<SubscriptionId, SubscriptionError> {
+        let mut guard = self.lock_connections();
+        if !guard.connections.contains_key(&connection_id) {
+            return Err(SubscriptionError::UnknownConnection { connection_id });
+        }
+
+        if exclusive {
+            let taken = guard
+                .connections
+                .iter()
+                .filter(|(id, _)| **id != connection_id)
+                .flat_map(|(_, connection)| connection.subscriptions.values())
+                .any(|sub| sub.exclusive && sub.filter == filter);
+            if taken {
+                return Err(SubscriptionError::ExclusiveFilterTaken);
+            }
+        }
+
+        let subscription_id = guard.next_id();
+        // the unwrap is fine as we've just checked the entry exists and we're holding the lock
+        guard
+            .connections
+            .get_mut(&connection_id)
+            .unwrap()
+            .subscriptions
+            .insert(subscription_id, Subscription { filter, exclusive });
+
+        debug!("websocket connection {connection_id} created subscription {subscription_id} (exclusive: {exclusive})");
+        Ok(subscription_id)
+    }
+
+    pub(crate) fn unsubscribe(
+        &self,
+        connection_id: WsConnectionId,
+        subscription_id: SubscriptionId,
+    ) -> Result<(), SubscriptionError> {
+        let mut guard = self.lock_connections();
+        let connection = guard
+            .connections
+            .get_mut(&connection_id)
+            .ok_or(SubscriptionError::UnknownConnection { connection_id })?;
+
+        connection
+            .subscriptions
+            .remove(&subscription_id)
+            .map(|_| ())
+            .ok_or(SubscriptionError::UnknownSubscription { subscription_id })
+    }
+
+    /// Assigns a unique tracking id to the message the connection is about to send, so that any
+    /// changes to its delivery status would be reported back under the client-provided id.
+    pub(crate) fn track_message(
+        &self,
+        connection_id: WsConnectionId,
+        client_tracking_id: ClientTrackingId,
+    ) -> Result<MessageTrackingId, SubscriptionError> {
+        let mut guard = self.lock_connections();
+        if !guard.connections.contains_key(&connection_id) {
+            return Err(SubscriptionError::UnknownConnection { connection_id });
+        }
+
+        let tracking_id = MessageTrackingId::new(guard.next_id());
+        guard
+            .tracked_messages
+            .insert(tracking_id, (connection_id, client_tracking_id));
+        Ok(tracking_id)
+    }
+
+    fn dispatch_delivery_status(&self, event: DeliveryStatusEvent) {
+        let mut guard = self.lock_connections();
+
+        // once the final status is known, we won't hear about this message anymore
+        let owner = match event.status {
+            DeliveryStatus::Retransmitting(_) => {
+                guard.tracked_messages.get(&event.tracking_id).copied()
+            }
+            DeliveryStatus::Delivered | DeliveryStatus::Failed => {
+                guard.tracked_messages.remove(&event.tracking_id)
+            }
+        };
+        let Some((connection_id, client_tracking_id)) = owner else {
+            trace!(
+                "received delivery status of untracked message {}",
+                event.tracking_id
+            );
+            return;
+        };
+
+        if let Some(connection) = guard.connections.get(&connection_id) {
+            if connection
+                .status_sender
+                .unbounded_send((client_tracking_id, event.status))
+                .is_err()
+            {
+                debug!("websocket connection {connection_id} has already stopped receiving");
+            }
+        }
+    }
+
+    fn dispatch(&self, messages: Vec<ReconstructedMessage>) {
+        let mut guard = self.lock_connections();
+        if guard.connections.is_empty() {
+            guard.pending.extend(messages);
+            return;
+        }
+
+        let mut batches: HashMap<WsConnectionId, Vec<ReconstructedMessage>> = HashMap::new();
+        for message in messages {
+            let targets = guard.route(&message);
+            let Some((last, rest)) = targets.split_last() else {
+                debug!("none of the websocket connections is interested in the received message - dropping it");
+                continue;
+            };
+            for connection_id in rest {
+                batches
+                    .entry(*connection_id)
+                    .or_default()
+                    .push(message.clone());
+            }
+            batches.entry(*last).or_default().push(message);
+        }
+
+        for (connection_id, batch) in batches {
+            if let Some(connection) = guard.connections.get(&connection_id) {
+                if connection.sender.unbounded_send(batch).is_err() {
+                    debug!("websocket connection {connection_id} has already stopped receiving");
+                }
+            }
+        }
+    }
+
+    async fn run(
+        &self,
+        mut reconstructed_receiver: ReconstructedMessagesReceiver,
+        mut delivery_status_receiver: DeliveryStatusReceiver,
+        mut shutdown: nym_task::TaskClient,
+    ) {
+        debug!("Started MessageRouter with graceful shutdown support");
+        while !shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("MessageRouter: Received shutdown");
+                }
+                messages = reconstructed_receiver.next() => {
+                    let Some(messages) = messages else {
+                        trace!("MessageRouter: Stopping since channel closed");
+                        break;
+                    };
+                    self.dispatch(messages)
+                }
+                event = delivery_status_receiver.next() => {
+                    let Some(event) = event else {
+                        trace!("MessageRouter: Stopping since delivery status channel closed");
+                        break;
+                    };
+                    self.dispatch_delivery_status(event)
+                }
+            }
+        }
+        debug!("MessageRouter: Exiting");
+    }
+
+    pub(crate) fn start(
+        &self,
+        reconstructed_receiver: ReconstructedMessagesReceiver,
+        delivery_status_receiver: DeliveryStatusReceiver,
+        shutdown: nym_task::TaskClient,
+    ) -> JoinHandle<()> {
+        let router = self.clone();
+        tokio::spawn(async move {
+            router
+                .run(reconstructed_receiver, delivery_status_receiver, shutdown)
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_sphinx::anonymous_replies::requests::{AnonymousSenderTag, SENDER_TAG_SIZE};
+
+    fn test_router() -> MessageRouter {
+        let (buffer_requester, _) = mpsc::unbounded();
+        MessageRouter::new(buffer_requester).0
+    }
+
+    fn message(content: &[u8], sender_tag: Option<AnonymousSenderTag>) -> ReconstructedMessage {
+        ReconstructedMessage {
+            message: content.to_vec(),
+            sender_tag,
+        }
+    }
+
+    fn prefix_filter(prefix: &[u8]) -> MessageFilter {
+        MessageFilter {
+            sender_tag: None,
+            prefix: prefix.to_vec(),
+        }
+    }
+
+    fn received(receiver: &mut ReconstructedMessagesReceiver) -> Vec<Vec<u8>> {
+        let mut messages = Vec::new();
+        while let Ok(Some(batch)) = receiver.try_next() {
+            messages.extend(batch.into_iter().map(|msg| msg.message))
+        }
+        messages
+    }
+
+    #[test]
+    fn connections_without_subscriptions_receive_unclaimed_messages() {
+        let router = test_router();
+        let (_, mut first, _) = router.register_connection();
+        let (_, mut second, _) = router.register_connection();
+        let (third_id, mut third, _) = router.register_connection();
+        router
+            .subscribe(third_id, prefix_filter(b"foo"), false)
+            .unwrap();
+
+        router.dispatch(vec![message(b"foomp", None), message(b"bar", None)]);
+
+        assert_eq!(received(&mut first), vec![b"bar".to_vec()]);
+        assert_eq!(received(&mut second), vec![b"bar".to_vec()]);
+        assert_eq!(received(&mut third), vec![b"foomp".to_vec()]);
+    }
+
+    #[test]
+    fn shared_subscriptions_fan_out_messages() {
+        let router = test_router();
+        let tag: AnonymousSenderTag = [1u8; SENDER_TAG_SIZE].into();
+
+        let (first_id, mut first, _) = router.register_connection();
+        let (second_id, mut second, _) = router.register_connection();
+        router
+            .subscribe(first_id, prefix_filter(b"foo"), false)
+            .unwrap();
+        router
+            .subscribe(
+                second_id,
+                MessageFilter {
+                    sender_tag: Some(tag),
+                    prefix: Vec::new(),
+                },
+                false,
+            )
+            .unwrap();
+
+        router.dispatch(vec![message(b"foomp", Some(tag)), message(b"foomp", None)]);
+
+        assert_eq!(
+            received(&mut first),
+            vec![b"foomp".to_vec(), b"foomp".to_vec()]
+        );
+        assert_eq!(received(&mut second), vec![b"foomp".to_vec()]);
+    }
+
+    #[test]
+    fn exclusive_subscriptions_take_precedence() {
+        let router = test_router();
+        let (first_id, mut first, _) = router.register_connection();
+        let (second_id, mut second, _) = router.register_connection();
+        let (_, mut third, _) = router.register_connection();
+
+        router
+            .subscribe(first_id, prefix_filter(b"foo"), false)
+            .unwrap();
+        let exclusive = router
+            .subscribe(second_id, prefix_filter(b"foo"), true)
+            .unwrap();
+        assert!(matches!(
+            router.subscribe(first_id, prefix_filter(b"foo"), true),
+            Err(SubscriptionError::ExclusiveFilterTaken)
+        ));
+
+        router.dispatch(vec![message(b"foomp", None)]);
+        assert!(received(&mut first).is_empty());
+        assert_eq!(received(&mut second), vec![b"foomp".to_vec()]);
+        assert!(received(&mut third).is_empty());
+
+        router.unsubscribe(second_id, exclusive).unwrap();
+        router.dispatch(vec![message(b"foomp", None)]);
+        assert_eq!(received(&mut first), vec![b"foomp".to_vec()]);
+        assert!(received(&mut second).is_empty());
+    }
+
+    #[test]
+    fn messages_are_retained_until_a_connection_is_present() {
+        let router = test_router();
+        let (connection_id, _, _) = router.register_connection();
+        router.unregister_connection(connection_id);
+
+        router.dispatch(vec![message(b"foomp", None)]);
+
+        let (_, mut receiver, _) = router.register_connection();
+        assert_eq!(received(&mut receiver), vec![b"foomp".to_vec()]);
+    }
+
+    #[test]
+    fn delivery_statuses_are_reported_to_the_sending_connection() {
+        let router = test_router();
+        let (first_id, _, mut first) = router.register_connection();
+        let (second_id, _, mut second) = router.register_connection();
+
+        // both connections are free to use the same ids
+        let first_tracking = router.track_message(first_id, 1).unwrap();
+        let second_tracking = router.track_message(second_id, 1).unwrap();
+        assert_ne!(first_tracking, second_tracking);
+
+        let event = |tracking_id, status| DeliveryStatusEvent {
+            tracking_id,
+            status,
+        };
+        router.dispatch_delivery_status(event(first_tracking, DeliveryStatus::Retransmitting(1)));
+        router.dispatch_delivery_status(event(first_tracking, DeliveryStatus::Delivered));
+        router.dispatch_delivery_status(event(second_tracking, DeliveryStatus::Failed));
+
+        // nothing is reported once the final status is known
+        router.dispatch_delivery_status(event(first_tracking, DeliveryStatus::Delivered));
+
+        let statuses = |receiver: &mut DeliveryStatusUpdatesReceiver| {
+            let mut statuses = Vec::new();
+            while let Ok(Some(status)) = receiver.try_next() {
+                statuses.push(status)
+            }
+            statuses
+        };
+        assert_eq!(
+            statuses(&mut first),
+            vec![
+                (1, DeliveryStatus::Retransmitting(1)),
+                (1, DeliveryStatus::Delivered)
+            ]
+        );
+        assert_eq!(statuses(&mut second), vec![(1, DeliveryStatus::Failed)]);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+// all variable size data is always prefixed with u64 length
+
+use crate::error::{self, ErrorKind};
+use nym_sphinx::anonymous_replies::requests::{AnonymousSenderTag, SENDER_TAG_SIZE};
+use nym_sphinx::receiver::ReconstructedMessage;
+use std::mem::size_of;
+
+/// Filter over received messages used by websocket subscriptions.
+/// A message is matched only if it satisfies all the specified conditions,
+/// so an empty filter matches every received message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageFilter {
+    /// If specified, only messages sent with this particular sender tag are matched.
+    pub sender_tag: Option<AnonymousSenderTag>,
+
+    /// Only messages whose content starts with those bytes are matched.
+    pub prefix: Vec<u8>,
+}
+
+impl MessageFilter {
+    pub fn matches(&self, message: &ReconstructedMessage) -> bool {
+        if let Some(sender_tag) = self.sender_tag {
+            if message.sender_tag != Some(sender_tag) {
+                return false;
+            }
+        }
+        message.message.starts_with(&self.prefix)
+    }
+
+    // 1 | 0 indicating sender_tag || Option<sender_tag> || prefix_len || prefix
+    pub(crate) fn serialize(self) -> Vec<u8> {
+        let prefix_len_bytes = (self.prefix.len() as u64).to_be_bytes();
+
+        if let Some(sender_tag) = self.sender_tag {
+            std::iter::once(true as u8)
+                .chain(sender_tag.to_bytes())
+                .chain(prefix_len_bytes)
+                .chain(self.prefix)
+                .collect()
+        } else {
+            std::iter::once(false as u8)
+                .chain(prefix_len_bytes)
+                .chain(self.prefix)
+                .collect()
+        }
+    }
+
+    // 1 | 0 indicating sender_tag || Option<sender_tag> || prefix_len || prefix
+    pub(crate) fn deserialize(b: &[u8]) -> Result<Self, error::Error> {
+        if b.len() < 1 + size_of::<u64>() {
+            return Err(error::Error::new(
+                ErrorKind::TooShortRequest,
+                "not enough data provided to recover message filter".to_string(),
+            ));
+        }
+
+        let mut i = 1;
+        let sender_tag = match b[0] {
+            0 => None,
+            1 => {
+                if b.len() < 1 + SENDER_TAG_SIZE + size_of::<u64>() {
+                    return Err(error::Error::new(
+                        ErrorKind::TooShortRequest,
+                        "not enough data provided to recover message filter".to_string(),
+                    ));
+                }
+                i += SENDER_TAG_SIZE;
+                // the unwrap here is fine as we're definitely using exactly SENDER_TAG_SIZE bytes
+                Some(AnonymousSenderTag::from_bytes(
+                    b[1..1 + SENDER_TAG_SIZE].try_into().unwrap(),
+                ))
+            }
+            n => {
+                return Err(error::Error::new(
+                    ErrorKind::MalformedRequest,
+                    format!("invalid sender tag flag {n}"),
+                ))
+            }
+        };
+
+        let prefix_len = u64::from_be_bytes(b[i..i + size_of::<u64>()].try_into().unwrap());
+        let prefix = &b[i + size_of::<u64>()..];
+        if prefix.len() as u64 != prefix_len {
+            return Err(error::Error::new(
+                ErrorKind::MalformedRequest,
+                format!(
+                    "prefix len has inconsistent length. specified: {} got: {}",
+                    prefix_len,
+                    prefix.len()
+                ),
+            ));
+        }
+
+        Ok(MessageFilter {
+            sender_tag,
+            prefix: prefix.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(content: &[u8], sender_tag: Option<AnonymousSenderTag>) -> ReconstructedMessage {
+        ReconstructedMessage {
+            message: content.to_vec(),
+            sender_tag,
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        let filter = MessageFilter::default();
+        assert!(filter.matches(&message(b"foomp", None)));
+        assert!(filter.matches(&message(b"", Some([1u8; SENDER_TAG_SIZE].into()))));
+    }
+
+    #[test]
+    fn all_conditions_must_be_satisfied() {
+        let tag: AnonymousSenderTag = [1u8; SENDER_TAG_SIZE].into();
+        let filter = MessageFilter {
+            sender_tag: Some(tag),
+            prefix: b"foo".to_vec(),
+        };
+
+        assert!(filter.matches(&message(b"foomp", Some(tag))));
+        assert!(!filter.matches(&message(b"foomp", None)));
+        assert!(!filter.matches(&message(b"foomp", Some([2u8; SENDER_TAG_SIZE].into()))));
+        assert!(!filter.matches(&message(b"bar", Some(tag))));
+        assert!(!filter.matches(&message(b"fo", Some(tag))));
+    }
+
+    #[test]
+    fn filter_serialization_works() {
+        let filters = vec![
+            MessageFilter::default(),
+            MessageFilter {
+                sender_tag: Some([42u8; SENDER_TAG_SIZE].into()),
+                prefix: Vec::new(),
+            },
+            MessageFilter {
+                sender_tag: None,
+                prefix: b"foomp".to_vec(),
+            },
+            MessageFilter {
+                sender_tag: Some([42u8; SENDER_TAG_SIZE].into()),
+                prefix: b"foomp".to_vec(),
+            },
+        ];
+
+        for filter in filters {
+            let bytes = filter.clone().serialize();
+            assert_eq!(filter, MessageFilter::deserialize(&bytes).unwrap());
+        }
+    }
+}
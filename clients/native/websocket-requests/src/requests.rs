@@ -5,6 +5,7 @@
 // tags are u8
 
 use crate::error::{self, ErrorKind};
+use crate::filter::MessageFilter;
 use crate::text::ClientRequestText;
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::{AnonymousSenderTag, SENDER_TAG_SIZE};
@@ -30,6 +31,15 @@ enum ClientRequestTag {
 
     /// Value tag representing [`GetLaneQueueLength`] variant of the [`ClientRequest`]
     GetLaneQueueLength = 0x05,
+
+    /// Value tag representing [`Subscribe`] variant of the [`ClientRequest`]
+    Subscribe = 0x06,
+
+    /// Value tag representing [`Unsubscribe`] variant of the [`ClientRequest`]
+    Unsubscribe = 0x07,
+
+    /// Value tag representing [`Tracked`] variant of the [`ClientRequest`]
+    Tracked = 0x08,
 }
 
 impl TryFrom<u8> for ClientRequestTag {
@@ -43,6 +53,9 @@ impl TryFrom<u8> for ClientRequestTag {
             _ if value == (Self::SelfAddress as u8) => Ok(Self::SelfAddress),
             _ if value == (Self::ClosedConnection as u8) => Ok(Self::ClosedConnection),
             _ if value == (Self::GetLaneQueueLength as u8) => Ok(Self::GetLaneQueueLength),
+            _ if value == (Self::Subscribe as u8) => Ok(Self::Subscribe),
+            _ if value == (Self::Unsubscribe as u8) => Ok(Self::Unsubscribe),
+            _ if value == (Self::Tracked as u8) => Ok(Self::Tracked),
             n => Err(error::Error::new(
                 ErrorKind::UnknownRequest,
                 format!("{n} does not correspond to any valid request tag"),
@@ -94,11 +107,45 @@ pub enum ClientRequest {
     ClosedConnection(u64),
 
     GetLaneQueueLength(u64),
+
+    /// Subscribe this connection to received messages matching the provided filter.
+    /// If `exclusive` is set, matching messages are delivered only to this connection,
+    /// rather than being shared with all other connections with matching subscriptions.
+    ///
+    /// Note that until a connection creates its first subscription,
+    /// it receives all messages that are not claimed by any subscription.
+    Subscribe {
+        filter: MessageFilter,
+        exclusive: bool,
+    },
+
+    /// Remove the subscription with the provided id previously created by this connection.
+    Unsubscribe {
+        subscription_id: u64,
+    },
+
+    /// Send the wrapped `Send`, `SendAnonymous` or `Reply` request and report any changes to
+    /// its delivery status with `DeliveryStatus` responses carrying the provided `tracking_id`.
+    Tracked {
+        tracking_id: u64,
+        request: Box<ClientRequest>,
+    },
 }
 
 // we could have been parsing it directly TryFrom<WsMessage>, but we want to retain
 // information about whether it came from binary or text to send appropriate response back
 impl ClientRequest {
+    /// Specifies whether delivery status of this request can be tracked,
+    /// i.e. whether it's going to result in a message being sent to the mixnet.
+    pub fn is_trackable(&self) -> bool {
+        matches!(
+            self,
+            ClientRequest::Send { .. }
+                | ClientRequest::SendAnonymous { .. }
+                | ClientRequest::Reply { .. }
+        )
+    }
+
     // SEND_REQUEST_TAG || recipient || conn_id || data_len || data
     fn serialize_send(recipient: Recipient, data: Vec<u8>, connection_id: Option<u64>) -> Vec<u8> {
         let data_len_bytes = (data.len() as u64).to_be_bytes();
@@ -382,6 +429,105 @@ impl ClientRequest {
         Ok(ClientRequest::GetLaneQueueLength(connection_id))
     }
 
+    // SUBSCRIBE_REQUEST_TAG || exclusive || filter
+    fn serialize_subscribe(filter: MessageFilter, exclusive: bool) -> Vec<u8> {
+        std::iter::once(ClientRequestTag::Subscribe as u8)
+            .chain(std::iter::once(exclusive as u8))
+            .chain(filter.serialize())
+            .collect()
+    }
+
+    // SUBSCRIBE_REQUEST_TAG || exclusive || filter
+    fn deserialize_subscribe(b: &[u8]) -> Result<Self, error::Error> {
+        if b.len() < 2 {
+            return Err(error::Error::new(
+                ErrorKind::TooShortRequest,
+                "not enough data provided to recover 'subscribe'".to_string(),
+            ));
+        }
+
+        // this MUST match because it was called by 'deserialize'
+        debug_assert_eq!(b[0], ClientRequestTag::Subscribe as u8);
+
+        let exclusive = match b[1] {
+            0 => false,
+            1 => true,
+            n => {
+                return Err(error::Error::new(
+                    ErrorKind::MalformedRequest,
+                    format!("invalid exclusive flag {n}"),
+                ))
+            }
+        };
+        let filter = MessageFilter::deserialize(&b[2..])?;
+
+        Ok(ClientRequest::Subscribe { filter, exclusive })
+    }
+
+    // UNSUBSCRIBE_REQUEST_TAG || subscription_id
+    fn serialize_unsubscribe(subscription_id: u64) -> Vec<u8> {
+        std::iter::once(ClientRequestTag::Unsubscribe as u8)
+            .chain(subscription_id.to_be_bytes())
+            .collect()
+    }
+
+    // UNSUBSCRIBE_REQUEST_TAG || subscription_id
+    fn deserialize_unsubscribe(b: &[u8]) -> Result<Self, error::Error> {
+        if b.len() != 1 + size_of::<u64>() {
+            return Err(error::Error::new(
+                ErrorKind::MalformedRequest,
+                "The received unsubscribe has invalid length",
+            ));
+        }
+
+        // this MUST match because it was called by 'deserialize'
+        debug_assert_eq!(b[0], ClientRequestTag::Unsubscribe as u8);
+
+        let mut subscription_id_bytes = [0u8; size_of::<u64>()];
+        subscription_id_bytes.copy_from_slice(&b[1..=size_of::<u64>()]);
+        let subscription_id = u64::from_be_bytes(subscription_id_bytes);
+
+        Ok(ClientRequest::Unsubscribe { subscription_id })
+    }
+
+    // TRACKED_REQUEST_TAG || tracking_id || request
+    fn serialize_tracked(tracking_id: u64, request: ClientRequest) -> Vec<u8> {
+        std::iter::once(ClientRequestTag::Tracked as u8)
+            .chain(tracking_id.to_be_bytes())
+            .chain(request.serialize())
+            .collect()
+    }
+
+    // TRACKED_REQUEST_TAG || tracking_id || request
+    fn deserialize_tracked(b: &[u8]) -> Result<Self, error::Error> {
+        if b.len() < 1 + size_of::<u64>() + 1 {
+            return Err(error::Error::new(
+                ErrorKind::TooShortRequest,
+                "not enough data provided to recover 'tracked'".to_string(),
+            ));
+        }
+
+        // this MUST match because it was called by 'deserialize'
+        debug_assert_eq!(b[0], ClientRequestTag::Tracked as u8);
+
+        let mut tracking_id_bytes = [0u8; size_of::<u64>()];
+        tracking_id_bytes.copy_from_slice(&b[1..=size_of::<u64>()]);
+        let tracking_id = u64::from_be_bytes(tracking_id_bytes);
+
+        let request = Self::deserialize(&b[1 + size_of::<u64>()..])?;
+        if !request.is_trackable() {
+            return Err(error::Error::new(
+                ErrorKind::MalformedRequest,
+                "only 'send', 'sendAnonymous' and 'reply' requests can be tracked",
+            ));
+        }
+
+        Ok(ClientRequest::Tracked {
+            tracking_id,
+            request: Box::new(request),
+        })
+    }
+
     pub fn serialize(self) -> Vec<u8> {
         match self {
             ClientRequest::Send {
@@ -408,6 +554,19 @@ impl ClientRequest {
             ClientRequest::ClosedConnection(id) => Self::serialize_closed_connection(id),
 
             ClientRequest::GetLaneQueueLength(id) => Self::serialize_get_lane_queue_lengths(id),
+
+            ClientRequest::Subscribe { filter, exclusive } => {
+                Self::serialize_subscribe(filter, exclusive)
+            }
+
+            ClientRequest::Unsubscribe { subscription_id } => {
+                Self::serialize_unsubscribe(subscription_id)
+            }
+
+            ClientRequest::Tracked {
+                tracking_id,
+                request,
+            } => Self::serialize_tracked(tracking_id, *request),
         }
     }
 
@@ -431,6 +590,9 @@ impl ClientRequest {
             ClientRequestTag::SelfAddress => Self::deserialize_self_address(b),
             ClientRequestTag::ClosedConnection => Self::deserialize_closed_connection(b),
             ClientRequestTag::GetLaneQueueLength => Self::deserialize_get_lane_queue_length(b),
+            ClientRequestTag::Subscribe => Self::deserialize_subscribe(b),
+            ClientRequestTag::Unsubscribe => Self::deserialize_unsubscribe(b),
+            ClientRequestTag::Tracked => Self::deserialize_tracked(b),
         }
     }
 
@@ -565,4 +727,86 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn subscribe_request_serialization_works() {
+        let original_filter = MessageFilter {
+            sender_tag: Some([8u8; SENDER_TAG_SIZE].into()),
+            prefix: b"foomp".to_vec(),
+        };
+        let subscribe_request = ClientRequest::Subscribe {
+            filter: original_filter.clone(),
+            exclusive: true,
+        };
+        let bytes = subscribe_request.serialize();
+        let recovered = ClientRequest::deserialize(&bytes).unwrap();
+        match recovered {
+            ClientRequest::Subscribe { filter, exclusive } => {
+                assert_eq!(filter, original_filter);
+                assert!(exclusive)
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn unsubscribe_request_serialization_works() {
+        let unsubscribe_request = ClientRequest::Unsubscribe {
+            subscription_id: 42,
+        };
+        let bytes = unsubscribe_request.serialize();
+        let recovered = ClientRequest::deserialize(&bytes).unwrap();
+        match recovered {
+            ClientRequest::Unsubscribe { subscription_id } => assert_eq!(subscription_id, 42),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn tracked_request_serialization_works() {
+        let recipient = Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap();
+        let recipient_string = recipient.to_string();
+
+        let tracked_request = ClientRequest::Tracked {
+            tracking_id: 42,
+            request: Box::new(ClientRequest::Send {
+                recipient,
+                message: b"foomp".to_vec(),
+                connection_id: Some(123),
+            }),
+        };
+        let bytes = tracked_request.serialize();
+        let recovered = ClientRequest::deserialize(&bytes).unwrap();
+        match recovered {
+            ClientRequest::Tracked {
+                tracking_id,
+                request,
+            } => {
+                assert_eq!(tracking_id, 42);
+                match *request {
+                    ClientRequest::Send {
+                        recipient,
+                        message,
+                        connection_id,
+                    } => {
+                        assert_eq!(recipient.to_string(), recipient_string);
+                        assert_eq!(message, b"foomp".to_vec());
+                        assert_eq!(connection_id, Some(123))
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn only_sending_requests_can_be_tracked() {
+        let tracked_request = ClientRequest::Tracked {
+            tracking_id: 42,
+            request: Box::new(ClientRequest::SelfAddress),
+        };
+        let bytes = tracked_request.serialize();
+        assert!(ClientRequest::deserialize(&bytes).is_err());
+    }
 }
@@ -25,6 +25,15 @@ enum ServerResponseTag {
 
     /// Value tag representing [`LaneQueueLength`] variant of the [`ServerResponse`]
     LaneQueueLength = 0x03,
+
+    /// Value tag representing [`Subscribed`] variant of the [`ServerResponse`]
+    Subscribed = 0x04,
+
+    /// Value tag representing [`Unsubscribed`] variant of the [`ServerResponse`]
+    Unsubscribed = 0x05,
+
+    /// Value tag representing [`DeliveryStatus`] variant of the [`ServerResponse`]
+    DeliveryStatus = 0x06,
 }
 
 impl TryFrom<u8> for ServerResponseTag {
@@ -36,6 +45,9 @@ impl TryFrom<u8> for ServerResponseTag {
             _ if value == (Self::Received as u8) => Ok(Self::Received),
             _ if value == (Self::SelfAddress as u8) => Ok(Self::SelfAddress),
             _ if value == (Self::LaneQueueLength as u8) => Ok(Self::LaneQueueLength),
+            _ if value == (Self::Subscribed as u8) => Ok(Self::Subscribed),
+            _ if value == (Self::Unsubscribed as u8) => Ok(Self::Unsubscribed),
+            _ if value == (Self::DeliveryStatus as u8) => Ok(Self::DeliveryStatus),
             n => Err(error::Error::new(
                 ErrorKind::UnknownResponse,
                 format!("{n} does not correspond to any valid response tag"),
@@ -44,11 +56,80 @@ impl TryFrom<u8> for ServerResponseTag {
     }
 }
 
+/// Delivery status of a message sent with a `Tracked` request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Every fragment of the message has been acknowledged by the recipient's gateway.
+    Delivered,
+
+    /// Some fragments of the message had to be retransmitted.
+    Retransmitting { retransmissions: u32 },
+
+    /// The client gave up on delivering the message.
+    Failed,
+}
+
+impl DeliveryStatus {
+    const DELIVERED: u8 = 0;
+    const RETRANSMITTING: u8 = 1;
+    const FAILED: u8 = 2;
+
+    // kind || retransmissions
+    fn to_bytes(self) -> [u8; 1 + size_of::<u32>()] {
+        let (kind, retransmissions) = match self {
+            DeliveryStatus::Delivered => (Self::DELIVERED, 0u32),
+            DeliveryStatus::Retransmitting { retransmissions } => {
+                (Self::RETRANSMITTING, retransmissions)
+            }
+            DeliveryStatus::Failed => (Self::FAILED, 0),
+        };
+        let mut bytes = [0u8; 1 + size_of::<u32>()];
+        bytes[0] = kind;
+        bytes[1..].copy_from_slice(&retransmissions.to_be_bytes());
+        bytes
+    }
+
+    // kind || retransmissions
+    fn try_from_bytes(b: &[u8]) -> Result<Self, error::Error> {
+        if b.len() != 1 + size_of::<u32>() {
+            return Err(error::Error::new(
+                ErrorKind::MalformedResponse,
+                "the received delivery status has invalid length",
+            ));
+        }
+
+        match b[0] {
+            Self::DELIVERED => Ok(DeliveryStatus::Delivered),
+            Self::RETRANSMITTING => Ok(DeliveryStatus::Retransmitting {
+                retransmissions: u32::from_be_bytes(b[1..].try_into().unwrap()),
+            }),
+            Self::FAILED => Ok(DeliveryStatus::Failed),
+            n => Err(error::Error::new(
+                ErrorKind::MalformedResponse,
+                format!("{n} does not correspond to any valid delivery status"),
+            )),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ServerResponse {
     Received(ReconstructedMessage),
     SelfAddress(Box<Recipient>),
-    LaneQueueLength { lane: u64, queue_length: usize },
+    LaneQueueLength {
+        lane: u64,
+        queue_length: usize,
+    },
+    Subscribed {
+        subscription_id: u64,
+    },
+    Unsubscribed {
+        subscription_id: u64,
+    },
+    DeliveryStatus {
+        tracking_id: u64,
+        status: DeliveryStatus,
+    },
     Error(error::Error),
 }
 
@@ -206,6 +287,58 @@ impl ServerResponse {
         Ok(ServerResponse::LaneQueueLength { lane, queue_length })
     }
 
+    // SUBSCRIBED_RESPONSE_TAG | UNSUBSCRIBED_RESPONSE_TAG || subscription_id
+    fn serialize_subscription_id(tag: ServerResponseTag, subscription_id: u64) -> Vec<u8> {
+        std::iter::once(tag as u8)
+            .chain(subscription_id.to_be_bytes())
+            .collect()
+    }
+
+    // SUBSCRIBED_RESPONSE_TAG | UNSUBSCRIBED_RESPONSE_TAG || subscription_id
+    fn deserialize_subscription_id(b: &[u8]) -> Result<u64, error::Error> {
+        if b.len() != 1 + size_of::<u64>() {
+            return Err(error::Error::new(
+                ErrorKind::TooShortResponse,
+                "not enough data provided to recover subscription id".to_string(),
+            ));
+        }
+
+        let mut subscription_id_bytes = [0u8; size_of::<u64>()];
+        subscription_id_bytes.copy_from_slice(&b[1..=size_of::<u64>()]);
+        Ok(u64::from_be_bytes(subscription_id_bytes))
+    }
+
+    // DELIVERY_STATUS_RESPONSE_TAG || tracking_id || status
+    fn serialize_delivery_status(tracking_id: u64, status: DeliveryStatus) -> Vec<u8> {
+        std::iter::once(ServerResponseTag::DeliveryStatus as u8)
+            .chain(tracking_id.to_be_bytes())
+            .chain(status.to_bytes())
+            .collect()
+    }
+
+    // DELIVERY_STATUS_RESPONSE_TAG || tracking_id || status
+    fn deserialize_delivery_status(b: &[u8]) -> Result<Self, error::Error> {
+        if b.len() < 1 + size_of::<u64>() {
+            return Err(error::Error::new(
+                ErrorKind::TooShortResponse,
+                "not enough data provided to recover 'delivery status'".to_string(),
+            ));
+        }
+
+        // this MUST match because it was called by 'deserialize'
+        debug_assert_eq!(b[0], ServerResponseTag::DeliveryStatus as u8);
+
+        let mut tracking_id_bytes = [0u8; size_of::<u64>()];
+        tracking_id_bytes.copy_from_slice(&b[1..=size_of::<u64>()]);
+        let tracking_id = u64::from_be_bytes(tracking_id_bytes);
+        let status = DeliveryStatus::try_from_bytes(&b[1 + size_of::<u64>()..])?;
+
+        Ok(ServerResponse::DeliveryStatus {
+            tracking_id,
+            status,
+        })
+    }
+
     // ERROR_RESPONSE_TAG || err_code || msg_len || msg
     fn serialize_error(error: error::Error) -> Vec<u8> {
         let message_len_bytes = (error.message.len() as u64).to_be_bytes();
@@ -268,6 +401,16 @@ impl ServerResponse {
             ServerResponse::LaneQueueLength { lane, queue_length } => {
                 Self::serialize_lane_queue_length(lane, queue_length)
             }
+            ServerResponse::Subscribed { subscription_id } => {
+                Self::serialize_subscription_id(ServerResponseTag::Subscribed, subscription_id)
+            }
+            ServerResponse::Unsubscribed { subscription_id } => {
+                Self::serialize_subscription_id(ServerResponseTag::Unsubscribed, subscription_id)
+            }
+            ServerResponse::DeliveryStatus {
+                tracking_id,
+                status,
+            } => Self::serialize_delivery_status(tracking_id, status),
             ServerResponse::Error(err) => Self::serialize_error(err),
         }
     }
@@ -299,6 +442,13 @@ impl ServerResponse {
             ServerResponseTag::Received => Self::deserialize_received(b),
             ServerResponseTag::SelfAddress => Self::deserialize_self_address(b),
             ServerResponseTag::LaneQueueLength => Self::deserialize_lane_queue_length(b),
+            ServerResponseTag::Subscribed => Ok(ServerResponse::Subscribed {
+                subscription_id: Self::deserialize_subscription_id(b)?,
+            }),
+            ServerResponseTag::Unsubscribed => Ok(ServerResponse::Unsubscribed {
+                subscription_id: Self::deserialize_subscription_id(b)?,
+            }),
+            ServerResponseTag::DeliveryStatus => Self::deserialize_delivery_status(b),
             ServerResponseTag::Error => Self::deserialize_error(b),
         }
     }
@@ -386,6 +536,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn subscription_responses_serialization_works() {
+        let bytes = ServerResponse::Subscribed {
+            subscription_id: 42,
+        }
+        .serialize();
+        match ServerResponse::deserialize(&bytes).unwrap() {
+            ServerResponse::Subscribed { subscription_id } => assert_eq!(subscription_id, 42),
+            _ => unreachable!(),
+        }
+
+        let bytes = ServerResponse::Unsubscribed {
+            subscription_id: 42,
+        }
+        .serialize();
+        match ServerResponse::deserialize(&bytes).unwrap() {
+            ServerResponse::Unsubscribed { subscription_id } => assert_eq!(subscription_id, 42),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn delivery_status_response_serialization_works() {
+        let statuses = vec![
+            DeliveryStatus::Delivered,
+            DeliveryStatus::Retransmitting { retransmissions: 3 },
+            DeliveryStatus::Failed,
+        ];
+
+        for original_status in statuses {
+            let bytes = ServerResponse::DeliveryStatus {
+                tracking_id: 42,
+                status: original_status,
+            }
+            .serialize();
+            match ServerResponse::deserialize(&bytes).unwrap() {
+                ServerResponse::DeliveryStatus {
+                    tracking_id,
+                    status,
+                } => {
+                    assert_eq!(tracking_id, 42);
+                    assert_eq!(status, original_status)
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
     #[test]
     fn error_response_serialization_works() {
         let dummy_error = error::Error::new(ErrorKind::UnknownRequest, "foomp message".to_string());
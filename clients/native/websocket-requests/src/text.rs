@@ -2,8 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::ErrorKind;
+use crate::filter::MessageFilter;
 use crate::requests::ClientRequest;
-use crate::responses::ServerResponse;
+use crate::responses::{DeliveryStatus, ServerResponse};
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
 use serde::{Deserialize, Serialize};
@@ -34,6 +35,22 @@ pub(super) enum ClientRequestText {
         connection_id: Option<u64>,
     },
     SelfAddress,
+    #[serde(rename_all = "camelCase")]
+    Subscribe {
+        sender_tag: Option<String>,
+        prefix: Option<String>,
+        #[serde(default)]
+        exclusive: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Unsubscribe {
+        subscription_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Tracked {
+        tracking_id: u64,
+        request: Box<ClientRequestText>,
+    },
 }
 
 impl TryFrom<String> for ClientRequestText {
@@ -100,6 +117,46 @@ impl TryInto<ClientRequest> for ClientRequestText {
                     connection_id,
                 })
             }
+            ClientRequestText::Subscribe {
+                sender_tag,
+                prefix,
+                exclusive,
+            } => {
+                let sender_tag = sender_tag
+                    .map(AnonymousSenderTag::try_from_base58_string)
+                    .transpose()
+                    .map_err(|err| {
+                        Self::Error::new(ErrorKind::MalformedRequest, err.to_string())
+                    })?;
+
+                Ok(ClientRequest::Subscribe {
+                    filter: MessageFilter {
+                        sender_tag,
+                        prefix: prefix.map(String::into_bytes).unwrap_or_default(),
+                    },
+                    exclusive,
+                })
+            }
+            ClientRequestText::Unsubscribe { subscription_id } => {
+                Ok(ClientRequest::Unsubscribe { subscription_id })
+            }
+            ClientRequestText::Tracked {
+                tracking_id,
+                request,
+            } => {
+                let request: ClientRequest = (*request).try_into()?;
+                if !request.is_trackable() {
+                    return Err(Self::Error::new(
+                        ErrorKind::MalformedRequest,
+                        "only 'send', 'sendAnonymous' and 'reply' requests can be tracked",
+                    ));
+                }
+
+                Ok(ClientRequest::Tracked {
+                    tracking_id,
+                    request: Box::new(request),
+                })
+            }
         }
     }
 }
@@ -122,11 +179,34 @@ pub(super) enum ServerResponseText {
         lane: u64,
         queue_length: usize,
     },
+    #[serde(rename_all = "camelCase")]
+    Subscribed {
+        subscription_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Unsubscribed {
+        subscription_id: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    DeliveryStatus {
+        tracking_id: u64,
+        status: DeliveryStatusText,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        retransmissions: Option<u32>,
+    },
     Error {
         message: String,
     },
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(super) enum DeliveryStatusText {
+    Delivered,
+    Retransmitting,
+    Failed,
+}
+
 impl TryFrom<String> for ServerResponseText {
     type Error = serde_json::Error;
 
@@ -164,6 +244,29 @@ impl From<ServerResponse> for ServerResponseText {
             ServerResponse::LaneQueueLength { lane, queue_length } => {
                 ServerResponseText::LaneQueueLength { lane, queue_length }
             }
+            ServerResponse::Subscribed { subscription_id } => {
+                ServerResponseText::Subscribed { subscription_id }
+            }
+            ServerResponse::Unsubscribed { subscription_id } => {
+                ServerResponseText::Unsubscribed { subscription_id }
+            }
+            ServerResponse::DeliveryStatus {
+                tracking_id,
+                status,
+            } => {
+                let (status, retransmissions) = match status {
+                    DeliveryStatus::Delivered => (DeliveryStatusText::Delivered, None),
+                    DeliveryStatus::Retransmitting { retransmissions } => {
+                        (DeliveryStatusText::Retransmitting, Some(retransmissions))
+                    }
+                    DeliveryStatus::Failed => (DeliveryStatusText::Failed, None),
+                };
+                ServerResponseText::DeliveryStatus {
+                    tracking_id,
+                    status,
+                    retransmissions,
+                }
+            }
             ServerResponse::Error(err) => ServerResponseText::Error {
                 message: err.to_string(),
             },
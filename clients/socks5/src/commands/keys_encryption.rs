@@ -0,0 +1,24 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::commands::CliSocks5Client;
+use crate::error::Socks5ClientError;
+use nym_client_core::cli_helpers::client_keys_encryption::{
+    update_keys_encryption, CommonClientKeysEncryptionArgs,
+};
+
+#[derive(clap::Args, Clone, Debug)]
+pub struct Args {
+    #[command(flatten)]
+    common_args: CommonClientKeysEncryptionArgs,
+}
+
+impl AsRef<CommonClientKeysEncryptionArgs> for Args {
+    fn as_ref(&self) -> &CommonClientKeysEncryptionArgs {
+        &self.common_args
+    }
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), Socks5ClientError> {
+    update_keys_encryption::<CliSocks5Client, _>(args).await
+}
@@ -28,6 +28,7 @@ mod add_gateway;
 pub(crate) mod build_info;
 mod import_credential;
 pub mod init;
+mod keys_encryption;
 mod list_gateways;
 pub(crate) mod run;
 mod switch_gateway;
@@ -88,6 +89,9 @@ pub(crate) enum Commands {
     /// Change the currently active gateway. Note that you must have already registered with the new gateway!
     SwitchGateway(switch_gateway::Args),
 
+    /// Encrypt the existing private keys of this client with a passphrase or decrypt them again
+    KeysEncryption(keys_encryption::Args),
+
     /// Show build information of this binary
     BuildInfo(build_info::BuildInfo),
 
@@ -123,6 +127,7 @@ pub(crate) async fn execute(args: Cli) -> Result<(), Box<dyn Error + Send + Sync
         Commands::ListGateways(args) => list_gateways::execute(args).await?,
         Commands::AddGateway(args) => add_gateway::execute(args).await?,
         Commands::SwitchGateway(args) => switch_gateway::execute(args).await?,
+        Commands::KeysEncryption(args) => keys_encryption::execute(args).await?,
         Commands::BuildInfo(m) => build_info::execute(m),
         Commands::Completions(s) => s.generate(&mut Cli::command(), bin_name),
         Commands::GenerateFigSpec => fig_generate(&mut Cli::command(), bin_name),
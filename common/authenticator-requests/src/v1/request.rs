@@ -57,6 +57,22 @@ impl AuthenticatorRequest {
         )
     }
 
+    pub fn new_query_request(
+        query_message: QueryBandwidthMessage,
+        reply_to: Recipient,
+    ) -> (Self, u64) {
+        let request_id = generate_random();
+        (
+            Self {
+                version: VERSION,
+                data: AuthenticatorRequestData::QueryBandwidth(query_message),
+                reply_to,
+                request_id,
+            },
+            request_id,
+        )
+    }
+
     pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
         use bincode::Options;
         make_bincode_serializer().serialize(self)
@@ -67,4 +83,14 @@ impl AuthenticatorRequest {
 pub enum AuthenticatorRequestData {
     Initial(InitMessage),
     Final(GatewayClient),
+    QueryBandwidth(QueryBandwidthMessage),
+}
+
+/// Request for the remaining bandwidth of an already registered peer.
+/// The mac of the included `GatewayClient` has to be computed over the provided `nonce`,
+/// the same way as during the registration, proving the requester owns the peer key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryBandwidthMessage {
+    pub gateway_client: GatewayClient,
+    pub nonce: u64,
 }
@@ -44,6 +44,22 @@ impl AuthenticatorResponse {
         }
     }
 
+    pub fn new_remaining_bandwidth(
+        remaining_bandwidth_data: Option<RemainingBandwidthData>,
+        reply_to: Recipient,
+        request_id: u64,
+    ) -> Self {
+        Self {
+            version: VERSION,
+            data: AuthenticatorResponseData::RemainingBandwidth(RemainingBandwidthResponse {
+                reply: remaining_bandwidth_data,
+                reply_to,
+                request_id,
+            }),
+            reply_to,
+        }
+    }
+
     pub fn recipient(&self) -> Recipient {
         self.reply_to
     }
@@ -64,6 +80,7 @@ impl AuthenticatorResponse {
         match &self.data {
             AuthenticatorResponseData::PendingRegistration(response) => Some(response.request_id),
             AuthenticatorResponseData::Registered(response) => Some(response.request_id),
+            AuthenticatorResponseData::RemainingBandwidth(response) => Some(response.request_id),
         }
     }
 }
@@ -72,6 +89,7 @@ impl AuthenticatorResponse {
 pub enum AuthenticatorResponseData {
     PendingRegistration(PendingRegistrationResponse),
     Registered(RegisteredResponse),
+    RemainingBandwidth(RemainingBandwidthResponse),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -86,3 +104,17 @@ pub struct RegisteredResponse {
     pub request_id: u64,
     pub reply_to: Recipient,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RemainingBandwidthResponse {
+    pub request_id: u64,
+    pub reply_to: Recipient,
+    /// `None` if the peer is not registered with the gateway.
+    pub reply: Option<RemainingBandwidthData>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct RemainingBandwidthData {
+    /// Bandwidth, in bytes, the peer can still use before getting removed.
+    pub available_bandwidth: i64,
+}
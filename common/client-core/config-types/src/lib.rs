@@ -3,7 +3,7 @@
 
 use nym_config::defaults::NymNetworkDetails;
 use nym_sphinx_addressing::Recipient;
-use nym_sphinx_params::{PacketSize, PacketType};
+use nym_sphinx_params::{PacketSize, PacketType, SphinxKeyRotationSchedule};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use url::Url;
@@ -19,6 +19,7 @@ pub use error::ConfigUpgradeFailure;
 const DEFAULT_ACK_WAIT_MULTIPLIER: f64 = 1.5;
 
 const DEFAULT_ACK_WAIT_ADDITION: Duration = Duration::from_millis(1_500);
+const DEFAULT_MAXIMUM_TRACKED_MESSAGE_RETRANSMISSIONS: u32 = 10;
 const DEFAULT_LOOP_COVER_STREAM_AVERAGE_DELAY: Duration = Duration::from_millis(200);
 const DEFAULT_MESSAGE_STREAM_AVERAGE_DELAY: Duration = Duration::from_millis(20);
 const DEFAULT_AVERAGE_PACKET_DELAY: Duration = Duration::from_millis(50);
@@ -29,6 +30,8 @@ const DEFAULT_TOPOLOGY_RESOLUTION_TIMEOUT: Duration = Duration::from_millis(5_00
 const DEFAULT_MIN_MIXNODE_PERFORMANCE: u8 = 50;
 const DEFAULT_MIN_GATEWAY_PERFORMANCE: u8 = 50;
 
+pub const DEFAULT_MAXIMUM_SELECTION_BIAS: f64 = 4.0;
+
 const DEFAULT_MAX_STARTUP_GATEWAY_WAITING_PERIOD: Duration = Duration::from_secs(70 * 60); // 70min -> full epoch (1h) + a bit of overhead
 
 // Set this to a high value for now, so that we don't risk sporadic timeouts that might cause
@@ -36,6 +39,8 @@ const DEFAULT_MAX_STARTUP_GATEWAY_WAITING_PERIOD: Duration = Duration::from_secs
 // bandwidth bridging protocol, we can come back to a smaller timeout value
 const DEFAULT_GATEWAY_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
+const DEFAULT_FAILBACK_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 const DEFAULT_COVER_TRAFFIC_PRIMARY_SIZE_RATIO: f64 = 0.70;
 
 // reply-surbs related:
@@ -61,6 +66,12 @@ const DEFAULT_MAXIMUM_REPLY_SURB_AGE: Duration = Duration::from_secs(12 * 60 * 6
 // 24 hours
 const DEFAULT_MAXIMUM_REPLY_KEY_AGE: Duration = Duration::from_secs(24 * 60 * 60);
 
+// message reconstruction related:
+const DEFAULT_MAXIMUM_PARTIAL_MESSAGE_INACTIVITY: Duration = Duration::from_secs(10 * 60);
+// 128 MiB
+const DEFAULT_MAXIMUM_PARTIAL_MESSAGES_SIZE: usize = 128 * 1024 * 1024;
+const DEFAULT_RECONSTRUCTED_SETS_RETENTION: Duration = Duration::from_secs(60 * 60);
+
 use crate::error::InvalidTrafficModeFailure;
 pub use nym_country_group::CountryGroup;
 
@@ -391,6 +402,19 @@ pub struct Traffic {
     pub secondary_packet_size: Option<PacketSize>,
 
     pub packet_type: PacketType,
+
+    /// Specifies the number of repair fragments (used for forward error correction) that are going
+    /// to be attached to each set of fragments of sent messages. They allow the recipient to
+    /// reconstruct the message even if some of its packets got lost without having to wait
+    /// for retransmission, at the cost of increased bandwidth.
+    /// Recipients do not advertise whether they support this feature, so it should only be enabled
+    /// if all of them are known to run clients capable of using the repair fragments. Older clients
+    /// discard each of them (logging a warning), so for them it's nothing more than wasted bandwidth.
+    /// Note that the repair fragments are never attached to replies sent with reply SURBs
+    /// nor to full sets of 255 fragments (i.e. all but the last set of very long messages),
+    /// as there are no fragment positions left for them.
+    /// It is disabled (set to 0) by default.
+    pub repair_fragments_per_set: u8,
 }
 
 impl Traffic {
@@ -415,6 +439,7 @@ impl Default for Traffic {
             primary_packet_size: PacketSize::RegularPacket,
             secondary_packet_size: None,
             packet_type: PacketType::Mix,
+            repair_fragments_per_set: 0,
         }
     }
 }
@@ -453,16 +478,57 @@ pub struct GatewayConnection {
     /// before giving up on it.
     #[serde(with = "humantime_serde")]
     pub gateway_response_timeout: Duration,
+
+    /// Specifies whether the client should automatically switch to a different gateway
+    /// once the currently used one becomes unreachable.
+    /// Note that switching gateways changes the address of the client.
+    pub enable_failover: bool,
+
+    /// Specifies whether, if none of the already registered gateways is reachable,
+    /// the client is allowed to register with a new gateway chosen from the current network topology.
+    /// Note that such registrations are only kept in memory, so the client is going to have to
+    /// register again with that gateway if it's ever needed after a restart.
+    pub failover_to_new_gateways: bool,
+
+    /// Specifies whether the client should switch back to its original gateway
+    /// once it becomes reachable again.
+    pub failback_policy: FailbackPolicy,
+
+    /// Defines how often the client checks whether its original gateway has recovered.
+    /// This setting is only applicable if the failback policy allows switching back.
+    #[serde(with = "humantime_serde")]
+    pub failback_check_interval: Duration,
 }
 
 impl Default for GatewayConnection {
     fn default() -> Self {
         GatewayConnection {
             gateway_response_timeout: DEFAULT_GATEWAY_RESPONSE_TIMEOUT,
+            enable_failover: false,
+            failover_to_new_gateways: false,
+            failback_policy: FailbackPolicy::default(),
+            failback_check_interval: DEFAULT_FAILBACK_CHECK_INTERVAL,
         }
     }
 }
 
+#[derive(Default, Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailbackPolicy {
+    /// Keep using the gateway the client has failed over to.
+    #[default]
+    Never,
+
+    /// Switch back to the original gateway as soon as it becomes reachable again.
+    OnRecovery,
+}
+
+impl FailbackPolicy {
+    pub fn allows_failback(&self) -> bool {
+        matches!(self, FailbackPolicy::OnRecovery)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct Acknowledgements {
@@ -483,6 +549,11 @@ pub struct Acknowledgements {
     /// In an ideal network with 0 latency, this value would have been 0.
     #[serde(with = "humantime_serde")]
     pub ack_wait_addition: Duration,
+
+    /// Maximum number of times a fragment of a message sent with an explicit tracking id
+    /// is going to be retransmitted before the message is reported as failed.
+    /// Note that messages without tracking ids are retransmitted until they're acknowledged.
+    pub maximum_tracked_message_retransmissions: u32,
 }
 
 impl Default for Acknowledgements {
@@ -491,6 +562,8 @@ impl Default for Acknowledgements {
             average_ack_delay: DEFAULT_AVERAGE_PACKET_DELAY,
             ack_wait_multiplier: DEFAULT_ACK_WAIT_MULTIPLIER,
             ack_wait_addition: DEFAULT_ACK_WAIT_ADDITION,
+            maximum_tracked_message_retransmissions:
+                DEFAULT_MAXIMUM_TRACKED_MESSAGE_RETRANSMISSIONS,
         }
     }
 }
@@ -523,11 +596,11 @@ pub struct Topology {
     pub topology_structure: TopologyStructure,
 
     /// Specifies a minimum performance of a mixnode that is used on route construction.
-    /// This setting is only applicable when `NymApi` topology is used.
+    /// This setting is only applicable when `NymApi` or `LatencyAware` topology is used.
     pub minimum_mixnode_performance: u8,
 
     /// Specifies a minimum performance of a gateway that is used on route construction.
-    /// This setting is only applicable when `NymApi` topology is used.
+    /// This setting is only applicable when `NymApi` or `LatencyAware` topology is used.
     pub minimum_gateway_performance: u8,
 }
 
@@ -537,6 +610,22 @@ pub enum TopologyStructure {
     #[default]
     NymApi,
     GeoAware(GroupBy),
+
+    /// Uses the same nodes as `NymApi`, but prefers mixnodes with lower measured latency and higher performance
+    /// when constructing routes.
+    LatencyAware {
+        /// Specifies how many times more likely the most preferred mixnode on a layer can be to be chosen
+        /// compared to the least preferred one. Lower values preserve a larger anonymity set.
+        maximum_selection_bias: f64,
+    },
+}
+
+impl TopologyStructure {
+    pub fn latency_aware() -> Self {
+        TopologyStructure::LatencyAware {
+            maximum_selection_bias: DEFAULT_MAXIMUM_SELECTION_BIAS,
+        }
+    }
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -599,12 +688,12 @@ pub struct ReplySurbs {
     pub maximum_reply_surb_drop_waiting_period: Duration,
 
     /// Defines maximum amount of time given reply surb is going to be valid for.
-    /// This is going to be superseded by key rotation once implemented.
+    /// Note that reply surbs are also invalidated once the sphinx keys they were created with expire.
     #[serde(with = "humantime_serde")]
     pub maximum_reply_surb_age: Duration,
 
     /// Defines maximum amount of time given reply key is going to be valid for.
-    /// This is going to be superseded by key rotation once implemented.
+    /// Note that reply keys are also invalidated once the sphinx keys of the corresponding reply surbs expire.
     #[serde(with = "humantime_serde")]
     pub maximum_reply_key_age: Duration,
 
@@ -631,6 +720,34 @@ impl Default for ReplySurbs {
     }
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MessageReconstruction {
+    /// Defines maximum amount of time an incomplete message is going to be kept in memory
+    /// since receiving its most recent fragment.
+    #[serde(with = "humantime_serde")]
+    pub maximum_partial_message_inactivity: Duration,
+
+    /// Defines maximum number of bytes held by all incomplete messages.
+    /// Once exceeded, the least recently updated ones are going to be dropped.
+    pub maximum_partial_messages_size: usize,
+
+    /// Defines how long ids of already reconstructed messages are remembered for
+    /// in order to discard any of their late (for example retransmitted) fragments.
+    #[serde(with = "humantime_serde")]
+    pub reconstructed_sets_retention: Duration,
+}
+
+impl Default for MessageReconstruction {
+    fn default() -> Self {
+        MessageReconstruction {
+            maximum_partial_message_inactivity: DEFAULT_MAXIMUM_PARTIAL_MESSAGE_INACTIVITY,
+            maximum_partial_messages_size: DEFAULT_MAXIMUM_PARTIAL_MESSAGES_SIZE,
+            reconstructed_sets_retention: DEFAULT_RECONSTRUCTED_SETS_RETENTION,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DebugConfig {
@@ -651,6 +768,13 @@ pub struct DebugConfig {
 
     /// Defines all configuration options related to reply SURBs.
     pub reply_surbs: ReplySurbs,
+
+    /// Defines all configuration options related to reconstruction of received messages.
+    pub message_reconstruction: MessageReconstruction,
+
+    /// Defines the schedule of sphinx key rotations of the mix nodes.
+    /// It determines when the network topology has to be refreshed and when the reply surbs expire.
+    pub sphinx_key_rotation: SphinxKeyRotationSchedule,
 }
 
 impl DebugConfig {
@@ -672,6 +796,8 @@ impl Default for DebugConfig {
             acknowledgements: Default::default(),
             topology: Default::default(),
             reply_surbs: Default::default(),
+            message_reconstruction: Default::default(),
+            sphinx_key_rotation: Default::default(),
         }
     }
 }
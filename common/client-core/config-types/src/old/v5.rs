@@ -111,6 +111,7 @@ impl From<ConfigV5> for Config {
                     primary_packet_size: value.debug.traffic.primary_packet_size,
                     secondary_packet_size: value.debug.traffic.secondary_packet_size,
                     packet_type: value.debug.traffic.packet_type,
+                    repair_fragments_per_set: 0,
                 },
                 cover_traffic: CoverTraffic {
                     loop_cover_traffic_average_delay: value
@@ -131,11 +132,13 @@ impl From<ConfigV5> for Config {
                         .debug
                         .gateway_connection
                         .gateway_response_timeout,
+                    ..Default::default()
                 },
                 acknowledgements: Acknowledgements {
                     average_ack_delay: value.debug.acknowledgements.average_ack_delay,
                     ack_wait_multiplier: value.debug.acknowledgements.ack_wait_multiplier,
                     ack_wait_addition: value.debug.acknowledgements.ack_wait_addition,
+                    ..Default::default()
                 },
                 topology: Topology {
                     topology_refresh_rate: value.debug.topology.topology_refresh_rate,
@@ -181,6 +184,8 @@ impl From<ConfigV5> for Config {
                     maximum_reply_key_age: value.debug.reply_surbs.maximum_reply_key_age,
                     surb_mix_hops: value.debug.reply_surbs.surb_mix_hops,
                 },
+                message_reconstruction: Default::default(),
+                sphinx_key_rotation: Default::default(),
             },
         }
     }
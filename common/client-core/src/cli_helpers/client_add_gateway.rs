@@ -76,7 +76,11 @@ where
     let core = config.core_config();
     let paths = config.common_paths();
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
+    let key_store = OnDiskKeys::new(paths.keys.clone())
+        .with_passphrase_from_env()
+        .map_err(|source| ClientCoreError::KeyStoreError {
+            source: Box::new(source),
+        })?;
     let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
 
     // Attempt to use a user-provided gateway, if possible
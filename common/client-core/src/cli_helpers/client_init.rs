@@ -1,6 +1,7 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::cli_helpers::client_keys_encryption::KeysPassphraseArgs;
 use crate::cli_helpers::traits::{CliClient, CliClientConfig};
 use crate::error::ClientCoreError;
 use crate::{
@@ -8,7 +9,7 @@ use crate::{
         base_client::{
             non_wasm_helpers::setup_fs_gateways_storage, storage::helpers::set_active_gateway,
         },
-        key_manager::persistence::OnDiskKeys,
+        key_manager::persistence::{OnDiskKeys, OnDiskKeysError},
     },
     init::types::{GatewaySelectionSpecification, GatewaySetup, InitResults},
 };
@@ -88,6 +89,11 @@ pub struct CommonClientInitArgs {
     /// Disable loop cover traffic and the Poisson rate limiter (for debugging only)
     #[cfg_attr(feature = "cli", clap(long, hide = true))]
     pub no_cover: bool,
+
+    /// Specifies the passphrase used for encrypting the generated private keys at rest.
+    /// If none is provided, the keys are going to be stored in plaintext.
+    #[cfg_attr(feature = "cli", clap(flatten))]
+    pub keys_passphrase: KeysPassphraseArgs,
 }
 
 pub struct InitResultsWithConfig<T> {
@@ -147,7 +153,18 @@ where
             .join(",")
     );
 
-    let key_store = OnDiskKeys::new(paths.keys.clone());
+    let mut key_store = OnDiskKeys::new(paths.keys.clone());
+    let passphrase = common_args
+        .keys_passphrase
+        .read()
+        .map_err(|err| OnDiskKeysError::PassphraseReadFailure { err })
+        .map_err(|source| ClientCoreError::KeyStoreError {
+            source: Box::new(source),
+        })?;
+    if let Some(passphrase) = passphrase {
+        info!("the private keys are going to be encrypted at rest");
+        key_store = key_store.with_passphrase(passphrase);
+    }
     let details_store = setup_fs_gateways_storage(&paths.gateway_registrations).await?;
 
     let mut rng = OsRng;
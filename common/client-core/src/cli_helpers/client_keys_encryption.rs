@@ -0,0 +1,64 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_helpers::{CliClient, CliClientConfig};
+use crate::client::key_manager::persistence::{OnDiskKeys, OnDiskKeysError};
+use crate::error::ClientCoreError;
+use log::info;
+
+pub use nym_pemstore::KeysPassphraseArgs;
+
+#[cfg_attr(feature = "cli", derive(clap::Args))]
+#[derive(Debug, Clone)]
+pub struct CommonClientKeysEncryptionArgs {
+    /// Id of client whose keys we want to encrypt or decrypt.
+    #[cfg_attr(feature = "cli", clap(long))]
+    pub id: String,
+
+    /// Decrypt the existing keys, so that they would be stored in plaintext again,
+    /// instead of encrypting them.
+    #[cfg_attr(feature = "cli", clap(long))]
+    pub decrypt: bool,
+
+    #[cfg_attr(feature = "cli", clap(flatten))]
+    pub keys_passphrase: KeysPassphraseArgs,
+}
+
+pub async fn update_keys_encryption<C, A>(args: A) -> Result<(), C::Error>
+where
+    A: AsRef<CommonClientKeysEncryptionArgs>,
+    C: CliClient,
+{
+    let common_args = args.as_ref();
+    let id = &common_args.id;
+
+    let config = C::try_load_current_config(id).await?;
+    let key_store = OnDiskKeys::new(config.common_paths().keys.clone());
+
+    let res = common_args
+        .keys_passphrase
+        .read()
+        .map_err(|err| OnDiskKeysError::PassphraseReadFailure { err })
+        .and_then(|passphrase| passphrase.ok_or(OnDiskKeysError::MissingPassphrase))
+        .and_then(|passphrase| {
+            if common_args.decrypt {
+                key_store.decrypt_existing_keys(&passphrase)
+            } else {
+                key_store.encrypt_existing_keys(&passphrase)
+            }
+        });
+
+    if let Err(source) = res {
+        return Err(ClientCoreError::KeyStoreError {
+            source: Box::new(source),
+        }
+        .into());
+    }
+
+    if common_args.decrypt {
+        info!("decrypted the keys of {} client '{id}'", C::NAME);
+    } else {
+        info!("encrypted the keys of {} client '{id}'", C::NAME);
+    }
+    Ok(())
+}
@@ -4,6 +4,7 @@
 pub mod client_add_gateway;
 pub mod client_import_credential;
 pub mod client_init;
+pub mod client_keys_encryption;
 pub mod client_list_gateways;
 pub mod client_run;
 pub mod client_switch_gateway;
@@ -7,14 +7,20 @@ use super::topology_control::geo_aware_provider::GeoAwareTopologyProvider;
 use crate::client::base_client::storage::helpers::store_client_keys;
 use crate::client::base_client::storage::MixnetClientStorage;
 use crate::client::cover_traffic_stream::LoopCoverTrafficStream;
+use crate::client::delivery_status::DeliveryStatusReceiver;
 use crate::client::inbound_messages::{InputMessage, InputMessageReceiver, InputMessageSender};
 use crate::client::key_manager::persistence::KeyStore;
 use crate::client::key_manager::ClientKeys;
+use crate::client::mix_traffic::connector::GatewayConnector;
+use crate::client::mix_traffic::failover::{FailoverGateway, FailoverSetup};
+use crate::client::mix_traffic::multi_gateway::MultiGatewayTransceiver;
 use crate::client::mix_traffic::transceiver::{GatewayReceiver, GatewayTransceiver, RemoteGateway};
 use crate::client::mix_traffic::{BatchMixMessageSender, MixTrafficController};
 use crate::client::packet_statistics_control::PacketStatisticsControl;
 use crate::client::real_messages_control;
-use crate::client::real_messages_control::RealMessagesController;
+use crate::client::real_messages_control::{
+    AckActionReceiver, AckActionSender, Action, RealMessagesController,
+};
 use crate::client::received_buffer::{
     ReceivedBufferRequestReceiver, ReceivedBufferRequestSender, ReceivedMessagesBufferController,
 };
@@ -23,6 +29,7 @@ use crate::client::replies::reply_controller::{ReplyControllerReceiver, ReplyCon
 use crate::client::replies::reply_storage::{
     CombinedReplyStorage, PersistentReplyStorage, ReplyStorageBackend, SentReplyKeys,
 };
+use crate::client::self_address::{self_address_channel, SelfAddress};
 use crate::client::topology_control::nym_api_provider::NymApiTopologyProvider;
 use crate::client::topology_control::{
     nym_api_provider, TopologyAccessor, TopologyRefresher, TopologyRefresherConfig,
@@ -31,13 +38,15 @@ use crate::config::{Config, DebugConfig};
 use crate::error::ClientCoreError;
 use crate::init::{
     setup_gateway,
-    types::{GatewaySetup, InitialisationResult},
+    types::{GatewaySetup, InitialisationResult, SelectedGateway},
 };
 use crate::{config, spawn_future};
 use futures::channel::mpsc;
 use log::{debug, error, info, warn};
 use nym_bandwidth_controller::BandwidthController;
-use nym_client_core_gateways_storage::{GatewayDetails, GatewaysDetailsStore};
+use nym_client_core_gateways_storage::{
+    GatewayDetails, GatewaysDetailsStore, RemoteGatewayDetails,
+};
 use nym_credential_storage::storage::Storage as CredentialStorage;
 use nym_crypto::asymmetric::{encryption, identity};
 use nym_gateway_client::{
@@ -45,9 +54,9 @@ use nym_gateway_client::{
 };
 use nym_network_defaults::{DEFAULT_CLIENT_LISTENING_PORT, WG_TUN_DEVICE_ADDRESS};
 use nym_sphinx::acknowledgements::AckKey;
-use nym_sphinx::addressing::clients::Recipient;
+use nym_sphinx::addressing::clients::{MultiHomedRecipient, Recipient};
 use nym_sphinx::addressing::nodes::NodeIdentity;
-use nym_sphinx::params::PacketType;
+use nym_sphinx::params::{PacketType, SphinxKeyRotationSchedule};
 use nym_sphinx::receiver::{ReconstructedMessage, SphinxMessageReceiver};
 use nym_task::connections::{ConnectionCommandReceiver, ConnectionCommandSender, LaneQueueLengths};
 use nym_task::{TaskClient, TaskHandle};
@@ -89,6 +98,7 @@ impl ClientInput {
 #[derive(Clone)]
 pub struct ClientOutput {
     pub received_buffer_request_sender: ReceivedBufferRequestSender,
+    pub(crate) ack_action_sender: AckActionSender,
 }
 
 impl ClientOutput {
@@ -105,6 +115,20 @@ impl ClientOutput {
 
         Ok(reconstructed_receiver)
     }
+
+    /// Registers a receiver for delivery status changes of messages sent with an explicit
+    /// tracking id. Any previously registered receiver is going to stop receiving updates.
+    pub fn register_delivery_status_receiver(
+        &mut self,
+    ) -> Result<DeliveryStatusReceiver, ClientCoreError> {
+        let (status_sender, status_receiver) = mpsc::unbounded();
+
+        self.ack_action_sender
+            .unbounded_send(Action::new_register_delivery_status_listener(status_sender))
+            .map_err(|_| ClientCoreError::FailedToRegisterDeliveryStatusReceiver)?;
+
+        Ok(status_receiver)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -113,6 +137,10 @@ pub struct ClientState {
     pub reply_controller_sender: ReplyControllerSender,
     pub topology_accessor: TopologyAccessor,
     pub gateway_connection: GatewayConnection,
+
+    /// The current address of this client.
+    /// It might change if the client fails over to a different gateway.
+    pub self_address: SelfAddress,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -183,6 +211,7 @@ pub struct BaseClientBuilder<'a, C, S: MixnetClientStorage> {
     wireguard_connection: bool,
     custom_topology_provider: Option<Box<dyn TopologyProvider + Send + Sync>>,
     custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send>>,
+    standby_gateways: Vec<identity::PublicKey>,
     shutdown: Option<TaskClient>,
     user_agent: Option<UserAgent>,
 
@@ -207,6 +236,7 @@ where
             wireguard_connection: false,
             custom_topology_provider: None,
             custom_gateway_transceiver: None,
+            standby_gateways: Vec::new(),
             shutdown: None,
             user_agent: None,
             setup_method: GatewaySetup::MustLoad { gateway_id: None },
@@ -231,6 +261,14 @@ where
         self
     }
 
+    /// Specifies additional gateways the client is going to be simultaneously connected to,
+    /// so that it would remain reachable even if its primary gateway goes down.
+    #[must_use]
+    pub fn with_standby_gateways(mut self, standby_gateways: Vec<identity::PublicKey>) -> Self {
+        self.standby_gateways = standby_gateways;
+        self
+    }
+
     #[must_use]
     pub fn with_topology_provider(
         mut self,
@@ -278,7 +316,7 @@ where
     fn start_cover_traffic_stream(
         debug_config: &DebugConfig,
         ack_key: Arc<AckKey>,
-        self_address: Recipient,
+        self_address: SelfAddress,
         topology_accessor: TopologyAccessor,
         mix_tx: BatchMixMessageSender,
         stats_tx: PacketStatisticsReporter,
@@ -315,6 +353,8 @@ where
         shutdown: TaskClient,
         packet_type: PacketType,
         stats_tx: PacketStatisticsReporter,
+        ack_action_sender: AckActionSender,
+        ack_action_receiver: AckActionReceiver,
     ) {
         info!("Starting real traffic stream...");
 
@@ -330,13 +370,17 @@ where
             lane_queue_lengths,
             client_connection_rx,
             stats_tx,
+            ack_action_sender,
+            ack_action_receiver,
         )
         .start_with_shutdown(shutdown, packet_type);
     }
 
     // buffer controlling all messages fetched from provider
     // required so that other components would be able to use them (say the websocket)
+    #[allow(clippy::too_many_arguments)]
     fn start_received_messages_buffer_controller(
+        reconstruction_config: config::MessageReconstruction,
         local_encryption_keypair: Arc<encryption::KeyPair>,
         query_receiver: ReceivedBufferRequestReceiver,
         mixnet_receiver: MixnetMessageReceiver,
@@ -344,16 +388,19 @@ where
         reply_controller_sender: ReplyControllerSender,
         shutdown: TaskClient,
         packet_statistics_control: PacketStatisticsReporter,
+        deduplicate_fragments: bool,
     ) {
         info!("Starting received messages buffer controller...");
         let controller: ReceivedMessagesBufferController<SphinxMessageReceiver> =
             ReceivedMessagesBufferController::new(
+                reconstruction_config,
                 local_encryption_keypair,
                 query_receiver,
                 mixnet_receiver,
                 reply_key_storage,
                 reply_controller_sender,
                 packet_statistics_control,
+                deduplicate_fragments,
             );
         controller.start_with_shutdown(shutdown)
     }
@@ -428,13 +475,16 @@ where
         Ok(gateway_client)
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn setup_gateway_transceiver(
         custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send>>,
         config: &Config,
         wireguard_connection: bool,
         initialisation_result: InitialisationResult,
         bandwidth_controller: Option<BandwidthController<C, S::CredentialStore>>,
-        packet_router: PacketRouter,
+        mut packet_router: PacketRouter,
+        failover: Option<FailoverSetup>,
+        has_standby_gateways: bool,
         mut shutdown: TaskClient,
     ) -> Result<Box<dyn GatewayTransceiver + Send>, ClientCoreError>
     where
@@ -457,18 +507,176 @@ where
             };
         }
 
+        let failover = match failover {
+            Some(_) if wireguard_connection => {
+                warn!("gateway failover is not supported for wireguard connections");
+                None
+            }
+            Some(setup) => {
+                let GatewayDetails::Remote(original) =
+                    &initialisation_result.gateway_registration.details
+                else {
+                    return Err(ClientCoreError::UnexpectedPersistedCustomGatewayDetails);
+                };
+                Some((setup, original.clone()))
+            }
+            None => None,
+        };
+
         // otherwise, setup normal gateway client, etc
+        let Some((failover_setup, original_gateway)) = failover else {
+            // if we're also connected to standby gateways, the failure of this connection
+            // must not be treated as an unexpected halt of the whole client
+            if has_standby_gateways {
+                packet_router.mark_as_success();
+                shutdown.disarm();
+            }
+
+            let gateway_client = Self::start_gateway_client(
+                config,
+                wireguard_connection,
+                initialisation_result,
+                bandwidth_controller,
+                packet_router,
+                shutdown,
+            )
+            .await?;
+
+            return Ok(Box::new(RemoteGateway::new(gateway_client)));
+        };
+
+        // the connection is going to be managed by the failover gateway, so make sure
+        // its failure is not treated as an unexpected halt of the whole client
+        let mut gateway_shutdown = shutdown.fork("initial_gateway");
+        gateway_shutdown.disarm();
+        let mut gateway_packet_router = packet_router.clone();
+        gateway_packet_router.mark_as_success();
+
+        let connector = GatewayConnector::new(
+            initialisation_result.client_keys.identity_keypair(),
+            config.client.disabled_credentials_mode,
+            config.debug.gateway_connection.gateway_response_timeout,
+            packet_router,
+            shutdown.fork("failover"),
+        );
         let gateway_client = Self::start_gateway_client(
             config,
             wireguard_connection,
             initialisation_result,
             bandwidth_controller,
-            packet_router,
-            shutdown,
+            gateway_packet_router,
+            gateway_shutdown,
         )
         .await?;
 
-        Ok(Box::new(RemoteGateway::new(gateway_client)))
+        Ok(Box::new(FailoverGateway::new(
+            config.debug.gateway_connection,
+            connector,
+            gateway_client,
+            original_gateway,
+            failover_setup,
+            shutdown,
+        )))
+    }
+
+    // connect to the additional gateways this client is going to be simultaneously reachable through
+    async fn setup_standby_gateways(
+        standby_gateways: &[identity::PublicKey],
+        primary_gateway: identity::PublicKey,
+        must_use_tls: bool,
+        registered_gateways: &[RemoteGatewayDetails],
+        topology_accessor: &TopologyAccessor,
+        connector: GatewayConnector,
+    ) -> Vec<Box<dyn GatewayTransceiver + Send>>
+    where
+        <S::CredentialStore as CredentialStorage>::StorageError: Send + Sync + 'static,
+    {
+        let topology = topology_accessor.current_topology().await;
+        let mut connected: Vec<Box<dyn GatewayTransceiver + Send>> = Vec::new();
+
+        for gateway_id in standby_gateways {
+            if gateway_id == &primary_gateway
+                || connected
+                    .iter()
+                    .any(|g| &g.gateway_identity() == gateway_id)
+            {
+                warn!("gateway {gateway_id} is already used by this client");
+                continue;
+            }
+
+            let registered = registered_gateways
+                .iter()
+                .find(|registered| &registered.gateway_id == gateway_id);
+
+            let (gateway_listener, gateway_owner, shared_keys) = match registered {
+                Some(registered) => (
+                    registered.gateway_listener.clone(),
+                    registered.gateway_owner_address.clone(),
+                    Some(Arc::clone(&registered.derived_aes128_ctr_blake3_hmac_keys)),
+                ),
+                None => {
+                    let Some(node) = topology.as_ref().and_then(|t| t.get_gateway(gateway_id))
+                    else {
+                        warn!("standby gateway {gateway_id} does not exist in the current network topology");
+                        continue;
+                    };
+                    match SelectedGateway::from_topology_node(node.clone(), None, must_use_tls) {
+                        Ok(SelectedGateway::Remote {
+                            gateway_owner_address,
+                            gateway_listener,
+                            ..
+                        }) => (gateway_listener, gateway_owner_address, None),
+                        Ok(SelectedGateway::Custom { .. }) => continue,
+                        Err(err) => {
+                            warn!("standby gateway {gateway_id} can't be used: {err}");
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            // note: the bandwidth controller is owned by the primary gateway, so the standby gateways
+            // can only be used in disabled credentials mode or if they still have some bandwidth left
+            let mut bandwidth_controller: Option<BandwidthController<C, S::CredentialStore>> = None;
+            match connector
+                .connect(
+                    *gateway_id,
+                    &gateway_listener,
+                    gateway_owner.as_ref(),
+                    shared_keys,
+                    &mut bandwidth_controller,
+                )
+                .await
+            {
+                Ok((gateway_client, _)) => {
+                    info!("connected to standby gateway {gateway_id}");
+                    connected.push(Box::new(RemoteGateway::new(gateway_client)))
+                }
+                Err(err) => warn!("failed to connect to standby gateway {gateway_id}: {err}"),
+            }
+        }
+
+        connected
+    }
+
+    // retrieve details of all remote gateways we have registered with so that we could use them for failover
+    // or as standby gateways
+    async fn registered_remote_gateways(
+        details_store: &S::GatewaysDetailsStore,
+    ) -> Vec<RemoteGatewayDetails> {
+        match details_store.all_gateways().await {
+            Ok(registrations) => registrations
+                .into_iter()
+                .filter_map(|registration| match registration.details {
+                    GatewayDetails::Remote(details) => Some(details),
+                    GatewayDetails::Custom(_) => None,
+                })
+                .collect(),
+            Err(err) => {
+                warn!("failed to retrieve the registered gateways: {err}. Only new gateways are going to be used for failover");
+                Vec::new()
+            }
+        }
     }
 
     fn setup_topology_provider(
@@ -483,6 +691,19 @@ where
                 nym_api_provider::Config {
                     min_mixnode_performance: config_topology.minimum_mixnode_performance,
                     min_gateway_performance: config_topology.minimum_gateway_performance,
+                    maximum_selection_bias: None,
+                },
+                nym_api_urls,
+                env!("CARGO_PKG_VERSION").to_string(),
+                user_agent,
+            )),
+            config::TopologyStructure::LatencyAware {
+                maximum_selection_bias,
+            } => Box::new(NymApiTopologyProvider::new(
+                nym_api_provider::Config {
+                    min_mixnode_performance: config_topology.minimum_mixnode_performance,
+                    min_gateway_performance: config_topology.minimum_gateway_performance,
+                    maximum_selection_bias: Some(maximum_selection_bias),
                 },
                 nym_api_urls,
                 env!("CARGO_PKG_VERSION").to_string(),
@@ -503,13 +724,15 @@ where
     async fn start_topology_refresher(
         topology_provider: Box<dyn TopologyProvider + Send + Sync>,
         topology_config: config::Topology,
+        sphinx_key_rotation: SphinxKeyRotationSchedule,
         topology_accessor: TopologyAccessor,
         local_gateway: &NodeIdentity,
         wait_for_gateway: bool,
         mut shutdown: TaskClient,
     ) -> Result<(), ClientCoreError> {
         let topology_refresher_config =
-            TopologyRefresherConfig::new(topology_config.topology_refresh_rate);
+            TopologyRefresherConfig::new(topology_config.topology_refresh_rate)
+                .with_sphinx_key_rotation(sphinx_key_rotation);
 
         let mut topology_refresher = TopologyRefresher::new(
             topology_refresher_config,
@@ -578,11 +801,14 @@ where
 
     fn start_mix_traffic_controller(
         gateway_transceiver: Box<dyn GatewayTransceiver + Send>,
+        ack_action_sender: AckActionSender,
         shutdown: TaskClient,
     ) -> BatchMixMessageSender {
         info!("Starting mix traffic controller...");
         let (mix_traffic_controller, mix_tx) = MixTrafficController::new(gateway_transceiver);
-        mix_traffic_controller.start_with_shutdown(shutdown);
+        mix_traffic_controller
+            .with_ack_action_sender(ack_action_sender)
+            .start_with_shutdown(shutdown);
         mix_tx
     }
 
@@ -652,6 +878,27 @@ where
         )
         .await?;
 
+        // standby gateways are only supported for standard websocket connections
+        let has_standby_gateways = !self.standby_gateways.is_empty()
+            && self.custom_gateway_transceiver.is_none()
+            && !self.wireguard_connection;
+        if !self.standby_gateways.is_empty() && !has_standby_gateways {
+            warn!("standby gateways are not supported with custom gateway transceivers or wireguard connections");
+        }
+
+        let mut failover_enabled = self.config.debug.gateway_connection.enable_failover;
+        if failover_enabled && has_standby_gateways {
+            warn!("gateway failover is not used when the client is connected to standby gateways");
+            failover_enabled = false;
+        }
+
+        // note: any registrations made during a failover or with the standby gateways are not going to be persisted
+        let registered_gateways = if failover_enabled || has_standby_gateways {
+            Self::registered_remote_gateways(self.client_store.gateway_details_store()).await
+        } else {
+            Vec::new()
+        };
+
         let (reply_storage_backend, credential_store) = self.client_store.into_runtime_stores();
 
         // channels for inter-component communication
@@ -671,6 +918,10 @@ where
 
         // channels responsible for controlling ack messages
         let (ack_sender, ack_receiver) = mpsc::unbounded();
+
+        // channels used for requesting actions on pending acknowledgements,
+        // such as registering a listener for delivery status of tracked messages
+        let (ack_action_sender, ack_action_receiver) = mpsc::unbounded();
         let shared_topology_accessor = TopologyAccessor::new();
 
         // Shutdown notifier for signalling tasks to stop
@@ -689,6 +940,9 @@ where
         let encryption_keys = init_res.client_keys.encryption_keypair();
         let identity_keys = init_res.client_keys.identity_keypair();
 
+        // the address of this client changes if we fail over to a different gateway
+        let (self_address_updater, shared_self_address) = self_address_channel(self_address);
+
         // the components are started in very specific order. Unless you know what you are doing,
         // do not change that.
         let bandwidth_controller = self
@@ -706,6 +960,7 @@ where
         Self::start_topology_refresher(
             topology_provider,
             self.config.debug.topology,
+            self.config.debug.sphinx_key_rotation,
             shared_topology_accessor.clone(),
             self_address.gateway(),
             self.wait_for_gateway,
@@ -722,6 +977,30 @@ where
             shutdown.get_handle().named("gateway-packet-router"),
         );
 
+        let failover = failover_enabled.then(|| FailoverSetup {
+            registered: registered_gateways.clone(),
+            topology_access: shared_topology_accessor.clone(),
+            self_address: self_address_updater,
+            encryption_key: *encryption_keys.public_key(),
+        });
+
+        let standby_connector = has_standby_gateways.then(|| {
+            GatewayConnector::new(
+                Arc::clone(&identity_keys),
+                self.config.client.disabled_credentials_mode,
+                self.config
+                    .debug
+                    .gateway_connection
+                    .gateway_response_timeout,
+                gateway_packet_router.clone(),
+                shutdown.fork("standby_gateways"),
+            )
+        });
+        let primary_uses_tls = match &init_res.gateway_registration.details {
+            GatewayDetails::Remote(details) => details.gateway_listener.scheme() == "wss",
+            GatewayDetails::Custom(_) => false,
+        };
+
         let gateway_transceiver = Self::setup_gateway_transceiver(
             self.custom_gateway_transceiver,
             self.config,
@@ -729,9 +1008,38 @@ where
             init_res,
             bandwidth_controller,
             gateway_packet_router,
+            failover,
+            has_standby_gateways,
             shutdown.fork("gateway_transceiver"),
         )
         .await?;
+
+        let (gateway_transceiver, multi_homed_address) = match standby_connector {
+            Some(connector) => {
+                let standby_gateways = Self::setup_standby_gateways(
+                    &self.standby_gateways,
+                    gateway_transceiver.gateway_identity(),
+                    primary_uses_tls,
+                    &registered_gateways,
+                    &shared_topology_accessor,
+                    connector,
+                )
+                .await;
+
+                let multi_gateway =
+                    MultiGatewayTransceiver::new(gateway_transceiver, standby_gateways);
+                let multi_homed_address = MultiHomedRecipient::new(
+                    *identity_keys.public_key(),
+                    *encryption_keys.public_key(),
+                    multi_gateway.gateways(),
+                )
+                .expect("there's always at least the primary gateway");
+
+                let transceiver: Box<dyn GatewayTransceiver + Send> = Box::new(multi_gateway);
+                (transceiver, Some(multi_homed_address))
+            }
+            None => (gateway_transceiver, None),
+        };
         let gateway_ws_fd = gateway_transceiver.ws_fd();
 
         let reply_storage = Self::setup_persistent_reply_storage(
@@ -741,6 +1049,7 @@ where
         .await?;
 
         Self::start_received_messages_buffer_controller(
+            self.config.debug.message_reconstruction,
             encryption_keys,
             received_buffer_request_receiver,
             mixnet_messages_receiver,
@@ -748,6 +1057,7 @@ where
             reply_controller_sender.clone(),
             shutdown.fork("received_messages_buffer"),
             packet_stats_reporter.clone(),
+            has_standby_gateways,
         );
 
         // The message_sender is the transmitter for any component generating sphinx packets
@@ -756,6 +1066,7 @@ where
         // The MixTrafficController then sends the actual traffic
         let message_sender = Self::start_mix_traffic_controller(
             gateway_transceiver,
+            ack_action_sender.clone(),
             shutdown.fork("mix_traffic_controller"),
         );
 
@@ -770,7 +1081,7 @@ where
         let controller_config = real_messages_control::Config::new(
             &self.config.debug,
             Arc::clone(&ack_key),
-            self_address,
+            shared_self_address.clone(),
         );
 
         Self::start_real_traffic_controller(
@@ -787,6 +1098,8 @@ where
             shutdown.fork("real_traffic_controller"),
             self.config.debug.traffic.packet_type,
             packet_stats_reporter.clone(),
+            ack_action_sender.clone(),
+            ack_action_receiver,
         );
 
         if !self
@@ -798,7 +1111,7 @@ where
             Self::start_cover_traffic_stream(
                 &self.config.debug,
                 ack_key,
-                self_address,
+                shared_self_address.clone(),
                 shared_topology_accessor.clone(),
                 message_sender,
                 packet_stats_reporter,
@@ -808,9 +1121,13 @@ where
 
         debug!("Core client startup finished!");
         debug!("The address of this client is: {self_address}");
+        if let Some(multi_homed_address) = &multi_homed_address {
+            info!("The multi-homed address of this client is: {multi_homed_address}");
+        }
 
         Ok(BaseClient {
             address: self_address,
+            multi_homed_address,
             identity_keys,
             client_input: ClientInputStatus::AwaitingProducer {
                 client_input: ClientInput {
@@ -821,6 +1138,7 @@ where
             client_output: ClientOutputStatus::AwaitingConsumer {
                 client_output: ClientOutput {
                     received_buffer_request_sender,
+                    ack_action_sender,
                 },
             },
             client_state: ClientState {
@@ -828,6 +1146,7 @@ where
                 reply_controller_sender,
                 topology_accessor: shared_topology_accessor,
                 gateway_connection: GatewayConnection { gateway_ws_fd },
+                self_address: shared_self_address,
             },
             task_handle: shutdown,
         })
@@ -836,6 +1155,10 @@ where
 
 pub struct BaseClient {
     pub address: Recipient,
+
+    /// Address of this client going through all of its gateways.
+    /// Only available if the client is connected to standby gateways.
+    pub multi_homed_address: Option<MultiHomedRecipient>,
     pub identity_keys: Arc<identity::KeyPair>,
     pub client_input: ClientInputStatus,
     pub client_output: ClientOutputStatus,
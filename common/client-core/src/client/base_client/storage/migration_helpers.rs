@@ -75,8 +75,12 @@ pub mod v1_1_33 {
     }
 
     fn load_shared_key<P: AsRef<Path>>(path: P) -> Result<SharedKeys, ClientCoreError> {
-        // the shared key was a simple pem file
-        Ok(nym_pemstore::load_key(path)?)
+        // the shared key was a simple pem file (that might have been encrypted at rest)
+        let passphrase = nym_pemstore::default_passphrase()?;
+        Ok(nym_pemstore::load_key_with_passphrase(
+            path,
+            passphrase.as_ref(),
+        )?)
     }
 
     fn gateway_details_from_raw(
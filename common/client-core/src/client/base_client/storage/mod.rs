@@ -134,7 +134,11 @@ impl OnDiskPersistent {
         paths: CommonClientPaths,
         debug_config: &config::DebugConfig,
     ) -> Result<Self, ClientCoreError> {
-        let key_store = OnDiskKeys::new(paths.keys);
+        let key_store = OnDiskKeys::new(paths.keys)
+            .with_passphrase_from_env()
+            .map_err(|source| ClientCoreError::KeyStoreError {
+                source: Box::new(source),
+            })?;
 
         let reply_store = non_wasm_helpers::setup_fs_reply_surb_backend(
             paths.reply_surb_database,
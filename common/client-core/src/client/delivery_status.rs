@@ -0,0 +1,64 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use futures::channel::mpsc;
+use std::fmt::{self, Display, Formatter};
+
+pub type DeliveryStatusSender = mpsc::UnboundedSender<DeliveryStatusEvent>;
+pub type DeliveryStatusReceiver = mpsc::UnboundedReceiver<DeliveryStatusEvent>;
+
+/// User-provided identifier attached to a sent message in order to receive notifications
+/// about its delivery status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MessageTrackingId(u64);
+
+impl MessageTrackingId {
+    pub const fn new(id: u64) -> Self {
+        MessageTrackingId(id)
+    }
+
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for MessageTrackingId {
+    fn from(id: u64) -> Self {
+        MessageTrackingId(id)
+    }
+}
+
+impl Display for MessageTrackingId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    /// Every fragment of the message has been acknowledged by the recipient's gateway.
+    Delivered,
+
+    /// Some fragments of the message had to be retransmitted.
+    /// It contains the total number of retransmissions performed for the message so far.
+    Retransmitting(u32),
+
+    /// The message could not be delivered, i.e. the client gave up on at least one of its fragments
+    /// or it couldn't have been sent in the first place.
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliveryStatusEvent {
+    pub tracking_id: MessageTrackingId,
+    pub status: DeliveryStatus,
+}
+
+impl DeliveryStatusEvent {
+    pub fn new(tracking_id: MessageTrackingId, status: DeliveryStatus) -> Self {
+        DeliveryStatusEvent {
+            tracking_id,
+            status,
+        }
+    }
+}
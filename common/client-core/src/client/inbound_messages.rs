@@ -1,6 +1,7 @@
 // Copyright 2020-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::client::delivery_status::MessageTrackingId;
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
 use nym_sphinx::forwarding::packet::MixPacket;
@@ -61,6 +62,16 @@ pub enum InputMessage {
         message: Box<InputMessage>,
         packet_type: PacketType,
     },
+
+    /// Attaches the provided `tracking_id` to the underlying message so that the changes to its
+    /// delivery status would get reported on the delivery status channel.
+    ///
+    /// Note that for `Premade` messages only failures to send the packets to the gateway are reported,
+    /// as their acknowledgements are not handled by the client.
+    Tracked {
+        message: Box<InputMessage>,
+        tracking_id: MessageTrackingId,
+    },
 }
 
 impl InputMessage {
@@ -84,6 +95,21 @@ impl InputMessage {
         }
     }
 
+    pub fn with_tracking_id(self, tracking_id: MessageTrackingId) -> Self {
+        InputMessage::Tracked {
+            message: Box::new(self),
+            tracking_id,
+        }
+    }
+
+    pub fn tracking_id(&self) -> Option<MessageTrackingId> {
+        match self {
+            InputMessage::Tracked { tracking_id, .. } => Some(*tracking_id),
+            InputMessage::MessageWrapper { message, .. } => message.tracking_id(),
+            _ => None,
+        }
+    }
+
     pub fn new_regular(
         recipient: Recipient,
         data: Vec<u8>,
@@ -194,7 +220,8 @@ impl InputMessage {
             | InputMessage::Anonymous { lane, .. }
             | InputMessage::Reply { lane, .. }
             | InputMessage::Premade { lane, .. } => lane,
-            InputMessage::MessageWrapper { message, .. } => message.lane(),
+            InputMessage::MessageWrapper { message, .. }
+            | InputMessage::Tracked { message, .. } => message.lane(),
         }
     }
 }
@@ -13,7 +13,7 @@ use nym_crypto::asymmetric::{encryption, identity};
 #[cfg(not(target_arch = "wasm32"))]
 use nym_pemstore::traits::{PemStorableKey, PemStorableKeyPair};
 #[cfg(not(target_arch = "wasm32"))]
-use nym_pemstore::KeyPairPath;
+use nym_pemstore::{KeyPairPath, Passphrase};
 #[cfg(not(target_arch = "wasm32"))]
 use nym_sphinx::acknowledgements::AckKey;
 
@@ -62,24 +62,99 @@ pub enum OnDiskKeysError {
         #[source]
         err: std::io::Error,
     },
+
+    #[error("failed to read the keys passphrase: {err}")]
+    PassphraseReadFailure {
+        #[source]
+        err: std::io::Error,
+    },
+
+    #[error("no passphrase for the keys encryption has been provided")]
+    MissingPassphrase,
+
+    #[error("failed to change encryption of the key stored at {path}: {err}")]
+    KeyEncryptionChangeFailure {
+        path: String,
+        #[source]
+        err: std::io::Error,
+    },
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 pub struct OnDiskKeys {
     paths: ClientKeysPaths,
+
+    /// Optional passphrase used for encrypting the private keys at rest.
+    passphrase: Option<Passphrase>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl From<ClientKeysPaths> for OnDiskKeys {
     fn from(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys::new(paths)
     }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 impl OnDiskKeys {
     pub fn new(paths: ClientKeysPaths) -> Self {
-        OnDiskKeys { paths }
+        OnDiskKeys {
+            paths,
+            passphrase: None,
+        }
+    }
+
+    /// Makes the store encrypt any newly stored private keys with the provided passphrase
+    /// and use it for decrypting any existing encrypted keys.
+    #[must_use]
+    pub fn with_passphrase(mut self, passphrase: Passphrase) -> Self {
+        self.passphrase = Some(passphrase);
+        self
+    }
+
+    /// Attempts to read the keys passphrase from the location specified by the
+    /// `NYM_KEYS_PASSPHRASE` or `NYM_KEYS_PASSPHRASE_FILE` environment variables, if set.
+    pub fn with_passphrase_from_env(mut self) -> Result<Self, OnDiskKeysError> {
+        if let Some(passphrase) = nym_pemstore::default_passphrase()
+            .map_err(|err| OnDiskKeysError::PassphraseReadFailure { err })?
+        {
+            self.passphrase = Some(passphrase);
+        }
+        Ok(self)
+    }
+
+    fn secret_key_files(&self) -> [&std::path::Path; 3] {
+        [
+            self.paths.private_identity_key(),
+            self.paths.private_encryption_key(),
+            self.paths.ack_key(),
+        ]
+    }
+
+    /// Encrypts all existing plaintext private keys with the provided passphrase.
+    pub fn encrypt_existing_keys(&self, passphrase: &Passphrase) -> Result<(), OnDiskKeysError> {
+        for path in self.secret_key_files() {
+            nym_pemstore::encrypt_key_file(path, passphrase).map_err(|err| {
+                OnDiskKeysError::KeyEncryptionChangeFailure {
+                    path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
+                    err,
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Decrypts all existing encrypted private keys, so that they'd be stored in plaintext again.
+    pub fn decrypt_existing_keys(&self, passphrase: &Passphrase) -> Result<(), OnDiskKeysError> {
+        for path in self.secret_key_files() {
+            nym_pemstore::decrypt_key_file(path, passphrase).map_err(|err| {
+                OnDiskKeysError::KeyEncryptionChangeFailure {
+                    path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
+                    err,
+                }
+            })?;
+        }
+        Ok(())
     }
 
     #[doc(hidden)]
@@ -99,10 +174,12 @@ impl OnDiskKeys {
         path: &std::path::Path,
         name: impl Into<String>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_key(path).map_err(|err| OnDiskKeysError::KeyLoadFailure {
-            key: name.into(),
-            path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
-            err,
+        nym_pemstore::load_key_with_passphrase(path, self.passphrase.as_ref()).map_err(|err| {
+            OnDiskKeysError::KeyLoadFailure {
+                key: name.into(),
+                path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
+                err,
+            }
         })
     }
 
@@ -111,11 +188,13 @@ impl OnDiskKeys {
         paths: KeyPairPath,
         name: impl Into<String>,
     ) -> Result<T, OnDiskKeysError> {
-        nym_pemstore::load_keypair(&paths).map_err(|err| OnDiskKeysError::KeyPairLoadFailure {
-            keys: name.into(),
-            paths,
-            err,
-        })
+        nym_pemstore::load_keypair_with_passphrase(&paths, self.passphrase.as_ref()).map_err(
+            |err| OnDiskKeysError::KeyPairLoadFailure {
+                keys: name.into(),
+                paths,
+                err,
+            },
+        )
     }
 
     fn store_key<T: PemStorableKey>(
@@ -124,11 +203,13 @@ impl OnDiskKeys {
         path: &std::path::Path,
         name: impl Into<String>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_key(key, path).map_err(|err| OnDiskKeysError::KeyStoreFailure {
-            key: name.into(),
-            path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
-            err,
-        })
+        nym_pemstore::store_key_with_passphrase(key, path, self.passphrase.as_ref()).map_err(
+            |err| OnDiskKeysError::KeyStoreFailure {
+                key: name.into(),
+                path: path.to_str().map(|s| s.to_owned()).unwrap_or_default(),
+                err,
+            },
+        )
     }
 
     fn store_keypair<T: PemStorableKeyPair>(
@@ -137,13 +218,13 @@ impl OnDiskKeys {
         paths: KeyPairPath,
         name: impl Into<String>,
     ) -> Result<(), OnDiskKeysError> {
-        nym_pemstore::store_keypair(keys, &paths).map_err(|err| {
-            OnDiskKeysError::KeyPairStoreFailure {
+        nym_pemstore::store_keypair_with_passphrase(keys, &paths, self.passphrase.as_ref()).map_err(
+            |err| OnDiskKeysError::KeyPairStoreFailure {
                 keys: name.into(),
                 paths,
                 err,
-            }
-        })
+            },
+        )
     }
 
     fn load_keys(&self) -> Result<ClientKeys, OnDiskKeysError> {
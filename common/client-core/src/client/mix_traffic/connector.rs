@@ -0,0 +1,112 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_bandwidth_controller::BandwidthController;
+use nym_credential_storage::storage::Storage as CredentialStorage;
+use nym_crypto::asymmetric::identity;
+use nym_gateway_client::error::GatewayClientError;
+use nym_gateway_client::{GatewayClient, GatewayConfig, PacketRouter, SharedKeys};
+use nym_task::TaskClient;
+use nym_validator_client::nyxd::contract_traits::DkgQueryClient;
+use nym_validator_client::nyxd::AccountId;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Creates connections to gateways that are managed by the client itself (for example during a failover),
+/// i.e. whose failure must not be treated as an unexpected halt of the whole client.
+#[derive(Clone)]
+pub(crate) struct GatewayConnector {
+    identity_keys: Arc<identity::KeyPair>,
+    disabled_credentials_mode: bool,
+    response_timeout: Duration,
+    packet_router: PacketRouter,
+    task_client: TaskClient,
+}
+
+impl GatewayConnector {
+    pub(crate) fn new(
+        identity_keys: Arc<identity::KeyPair>,
+        disabled_credentials_mode: bool,
+        response_timeout: Duration,
+        mut packet_router: PacketRouter,
+        mut task_client: TaskClient,
+    ) -> Self {
+        // note: the disarmed mode is inherited by all clones
+        packet_router.mark_as_success();
+        task_client.disarm();
+
+        GatewayConnector {
+            identity_keys,
+            disabled_credentials_mode,
+            response_timeout,
+            packet_router,
+            task_client,
+        }
+    }
+
+    pub(crate) fn identity_keys(&self) -> &Arc<identity::KeyPair> {
+        &self.identity_keys
+    }
+
+    fn new_gateway_client<C, St>(
+        &self,
+        gateway_id: identity::PublicKey,
+        gateway_listener: &Url,
+        gateway_owner: Option<&AccountId>,
+        shared_keys: Option<Arc<SharedKeys>>,
+        bandwidth_controller: Option<BandwidthController<C, St>>,
+    ) -> GatewayClient<C, St> {
+        let task_client = self
+            .task_client
+            .fork(format!("gateway-{}", gateway_id.to_base58_string()));
+
+        let cfg = GatewayConfig::new(
+            gateway_id,
+            gateway_owner.map(|owner| owner.to_string()),
+            gateway_listener.to_string(),
+        );
+        GatewayClient::new(
+            cfg,
+            Arc::clone(&self.identity_keys),
+            shared_keys,
+            self.packet_router.clone(),
+            bandwidth_controller,
+            task_client,
+        )
+        .with_disabled_credentials_mode(self.disabled_credentials_mode)
+        .with_response_timeout(self.response_timeout)
+    }
+
+    /// Attempts to connect to the specified gateway. If no shared keys are provided, a fresh registration is performed.
+    /// On failure, the bandwidth controller is given back so that it could be used for the next attempt.
+    pub(crate) async fn connect<C, St>(
+        &self,
+        gateway_id: identity::PublicKey,
+        gateway_listener: &Url,
+        gateway_owner: Option<&AccountId>,
+        shared_keys: Option<Arc<SharedKeys>>,
+        bandwidth_controller: &mut Option<BandwidthController<C, St>>,
+    ) -> Result<(GatewayClient<C, St>, Arc<SharedKeys>), GatewayClientError>
+    where
+        C: DkgQueryClient + Send + Sync,
+        St: CredentialStorage,
+        <St as CredentialStorage>::StorageError: Send + Sync + 'static,
+    {
+        let mut gateway_client = self.new_gateway_client(
+            gateway_id,
+            gateway_listener,
+            gateway_owner,
+            shared_keys,
+            bandwidth_controller.take(),
+        );
+
+        match gateway_client.authenticate_and_start().await {
+            Ok(shared_keys) => Ok((gateway_client, shared_keys)),
+            Err(err) => {
+                *bandwidth_controller = gateway_client.take_bandwidth_controller();
+                Err(err)
+            }
+        }
+    }
+}
@@ -0,0 +1,654 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::helpers::{get_time_now, new_interval_stream, Instant};
+use crate::client::mix_traffic::connector::GatewayConnector;
+use crate::client::mix_traffic::transceiver::{
+    erase_err, ErasedGatewayError, GatewayReceiver, GatewaySender, GatewayTransceiver,
+};
+use crate::client::self_address::SelfAddressUpdater;
+use crate::client::topology_control::TopologyAccessor;
+use crate::config;
+use crate::error::ClientCoreStatusMessage;
+use crate::init::types::SelectedGateway;
+use crate::spawn_future;
+use async_trait::async_trait;
+use futures::StreamExt;
+use log::{debug, error, info, trace, warn};
+use nym_bandwidth_controller::BandwidthController;
+use nym_client_core_gateways_storage::RemoteGatewayDetails;
+use nym_credential_storage::storage::Storage as CredentialStorage;
+use nym_crypto::asymmetric::{encryption, identity};
+use nym_gateway_client::error::GatewayClientError;
+use nym_gateway_client::GatewayClient;
+use nym_sphinx::addressing::clients::Recipient;
+use nym_sphinx::forwarding::packet::MixPacket;
+use nym_task::TaskClient;
+use nym_topology::{gateway, NymTopology};
+use nym_validator_client::nyxd::contract_traits::DkgQueryClient;
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use std::os::raw::c_int as RawFd;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+
+// how many previously unknown gateways we're going to try to register with during a single failover
+const MAX_NEW_GATEWAY_ATTEMPTS: usize = 3;
+
+// minimum time between consecutive failover attempts so that we wouldn't try to reconnect
+// to every single gateway in the network for each packet we fail to send
+const FAILOVER_RETRY_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Remote gateway connection that, if the gateway becomes unreachable, automatically switches
+/// to a different gateway whilst keeping the same client identity.
+/// It tries the gateways the client has already registered with first and, if allowed,
+/// registers with a new one chosen from the current network topology.
+/// If the failback policy allows it, the original gateway is monitored in the background
+/// and the client switches back to it once it becomes reachable again.
+///
+/// Note: registrations with new gateways made during a failover are only kept in memory
+/// and are not persisted in the gateways storage.
+pub struct FailoverGateway<C, St> {
+    config: config::GatewayConnection,
+    connector: GatewayConnector,
+    encryption_key: encryption::PublicKey,
+
+    /// Connection to the gateway that's currently used for sending and receiving packets.
+    active: GatewayClient<C, St>,
+
+    /// Details of the gateway the client has been started with.
+    original: RemoteGatewayDetails,
+
+    /// All gateways the client has a shared key with, including the original one.
+    registered: Vec<RemoteGatewayDetails>,
+
+    topology_access: TopologyAccessor,
+    self_address: SelfAddressUpdater,
+
+    last_failover_attempt: Option<Instant>,
+
+    /// Identity of the currently used gateway as observed by the [`FailbackMonitor`].
+    active_gateway: watch::Sender<identity::PublicKey>,
+
+    /// Connections to the original gateway established by the [`FailbackMonitor`] once it has recovered.
+    recovered_gateways: Option<mpsc::Receiver<GatewayClient<C, St>>>,
+
+    task_client: TaskClient,
+}
+
+/// Background task periodically checking whether the original gateway has recovered whilst the client
+/// is using a different one. Once the gateway is reachable again, the established connection is handed over
+/// to the [`FailoverGateway`] so that sending packets never has to wait for the connection attempts.
+struct FailbackMonitor<C, St> {
+    connector: GatewayConnector,
+    original: RemoteGatewayDetails,
+    check_interval: Duration,
+    active_gateway: watch::Receiver<identity::PublicKey>,
+    recovered_sender: mpsc::Sender<GatewayClient<C, St>>,
+}
+
+/// Everything required for setting up a [`FailoverGateway`] that's not known until the base client has started.
+pub(crate) struct FailoverSetup {
+    pub(crate) registered: Vec<RemoteGatewayDetails>,
+    pub(crate) topology_access: TopologyAccessor,
+    pub(crate) self_address: SelfAddressUpdater,
+    pub(crate) encryption_key: encryption::PublicKey,
+}
+
+// errors indicating the gateway we're connected to is no longer reachable
+// (as opposed to, for example, running out of bandwidth)
+fn is_unreachable_gateway(err: &GatewayClientError) -> bool {
+    err.is_closed_connection()
+        || matches!(
+            err,
+            GatewayClientError::NotAuthenticated
+                | GatewayClientError::ConnectionNotEstablished
+                | GatewayClientError::ConnectionInInvalidState
+                | GatewayClientError::ConnectionAbruptlyClosed
+                | GatewayClientError::ConnectionClosedGatewayShutdown
+                | GatewayClientError::NetworkConnectionFailed { .. }
+                | GatewayClientError::Timeout
+        )
+}
+
+// registered gateways we could switch to, i.e. all but the failed one that are still part of the network
+fn registered_failover_candidates(
+    registered: &[RemoteGatewayDetails],
+    failed_gateway: identity::PublicKey,
+    topology: Option<&NymTopology>,
+) -> Vec<RemoteGatewayDetails> {
+    registered
+        .iter()
+        .filter(|gateway| gateway.gateway_id != failed_gateway)
+        .filter(|gateway| match topology {
+            Some(topology) => topology.get_gateway(&gateway.gateway_id).is_some(),
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+// gateways from the network topology we haven't registered with yet
+fn new_failover_candidates<'a>(
+    topology: &'a NymTopology,
+    failed_gateway: identity::PublicKey,
+    registered: &[RemoteGatewayDetails],
+) -> Vec<&'a gateway::Node> {
+    topology
+        .gateways()
+        .iter()
+        .filter(|node| node.identity_key != failed_gateway)
+        .filter(|node| {
+            !registered
+                .iter()
+                .any(|gateway| gateway.gateway_id == node.identity_key)
+        })
+        .collect()
+}
+
+// we only need to check the original gateway if we're not using it and we haven't already
+// got a recovered connection to it waiting to be picked up
+fn needs_failback_check(
+    active_gateway: identity::PublicKey,
+    original_gateway: identity::PublicKey,
+    recovered_pending: bool,
+) -> bool {
+    active_gateway != original_gateway && !recovered_pending
+}
+
+impl<C, St> FailbackMonitor<C, St>
+where
+    C: DkgQueryClient + Send + Sync,
+    St: CredentialStorage,
+    <St as CredentialStorage>::StorageError: Send + Sync + 'static,
+{
+    async fn check_original_gateway(&self) {
+        let original = &self.original;
+        debug!(
+            "checking whether the original gateway {} has recovered",
+            original.gateway_id
+        );
+
+        // the bandwidth controller is moved to the recovered connection once it gets picked up,
+        // so if the gateway requires more bandwidth straight away, we're going to fail here
+        let mut bandwidth_controller: Option<BandwidthController<C, St>> = None;
+        match self
+            .connector
+            .connect(
+                original.gateway_id,
+                &original.gateway_listener,
+                original.gateway_owner_address.as_ref(),
+                Some(Arc::clone(&original.derived_aes128_ctr_blake3_hmac_keys)),
+                &mut bandwidth_controller,
+            )
+            .await
+        {
+            Ok((gateway_client, _)) => {
+                info!(
+                    "the original gateway {} is reachable again",
+                    original.gateway_id
+                );
+                if self.recovered_sender.try_send(gateway_client).is_err() {
+                    debug!("the recovered connection is no longer needed");
+                }
+            }
+            Err(err) => debug!("the original gateway is still unavailable: {err}"),
+        }
+    }
+
+    async fn run(self, mut task_client: TaskClient) {
+        debug!("Started FailbackMonitor with graceful shutdown support");
+
+        let mut check_interval = new_interval_stream(self.check_interval);
+        while !task_client.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = task_client.recv() => {
+                    trace!("FailbackMonitor: Received shutdown");
+                }
+                _ = check_interval.next() => {
+                    let active_gateway = *self.active_gateway.borrow();
+                    let recovered_pending = self.recovered_sender.capacity() == 0;
+                    if needs_failback_check(active_gateway, self.original.gateway_id, recovered_pending) {
+                        self.check_original_gateway().await
+                    }
+                }
+            }
+        }
+        debug!("FailbackMonitor: Exiting");
+    }
+}
+
+impl<C, St> FailoverGateway<C, St>
+where
+    C: DkgQueryClient + Send + Sync,
+    St: CredentialStorage,
+    <St as CredentialStorage>::StorageError: Send + Sync + 'static,
+{
+    // note: the provided gateway client must have been created with disarmed packet router and task client,
+    // as otherwise the failure of the gateway connection would have resulted in the shutdown of the whole client
+    pub(crate) fn new(
+        config: config::GatewayConnection,
+        connector: GatewayConnector,
+        active: GatewayClient<C, St>,
+        original: RemoteGatewayDetails,
+        setup: FailoverSetup,
+        task_client: TaskClient,
+    ) -> Self
+    where
+        C: 'static,
+        St: 'static,
+    {
+        let mut registered = setup.registered;
+        if !registered
+            .iter()
+            .any(|gateway| gateway.gateway_id == original.gateway_id)
+        {
+            registered.push(original.clone());
+        }
+
+        let (active_gateway, active_gateway_receiver) = watch::channel(active.gateway_identity());
+
+        let recovered_gateways = if config.failback_policy.allows_failback() {
+            let (recovered_sender, recovered_receiver) = mpsc::channel(1);
+            let monitor = FailbackMonitor {
+                connector: connector.clone(),
+                original: original.clone(),
+                check_interval: config.failback_check_interval,
+                active_gateway: active_gateway_receiver,
+                recovered_sender,
+            };
+            let monitor_task_client = task_client.fork("failback_monitor");
+            spawn_future(async move { monitor.run(monitor_task_client).await });
+            Some(recovered_receiver)
+        } else {
+            None
+        };
+
+        FailoverGateway {
+            config,
+            connector,
+            encryption_key: setup.encryption_key,
+            active,
+            original,
+            registered,
+            topology_access: setup.topology_access,
+            self_address: setup.self_address,
+            last_failover_attempt: None,
+            active_gateway,
+            recovered_gateways,
+            task_client,
+        }
+    }
+
+    fn client_address(&self, gateway: identity::PublicKey) -> Recipient {
+        Recipient::new(
+            *self.connector.identity_keys().public_key(),
+            self.encryption_key,
+            gateway,
+        )
+    }
+
+    async fn switch_to(&mut self, new_client: GatewayClient<C, St>) {
+        let mut previous = std::mem::replace(&mut self.active, new_client);
+        let previous_gateway = previous.gateway_identity();
+
+        // the previous connection might still be alive if we're failing back
+        if let Err(err) = previous.close_connection().await {
+            debug!("failed to cleanly close the connection to gateway {previous_gateway}: {err}");
+        }
+
+        let new_gateway = self.active.gateway_identity();
+        let new_address = self.client_address(new_gateway);
+        self.self_address.update(new_address);
+        self.active_gateway.send_replace(new_gateway);
+
+        info!("switched from gateway {previous_gateway} to {new_gateway}. The new address of this client is {new_address}");
+        self.task_client
+            .send_status_msg(Box::new(ClientCoreStatusMessage::GatewaySwitched {
+                previous_gateway: previous_gateway.to_base58_string(),
+                new_gateway: new_gateway.to_base58_string(),
+                new_address,
+            }));
+    }
+
+    async fn try_registered_gateways(
+        &mut self,
+        failed_gateway: identity::PublicKey,
+        bandwidth_controller: &mut Option<BandwidthController<C, St>>,
+    ) -> bool {
+        let topology = self.topology_access.current_topology().await;
+        let candidates =
+            registered_failover_candidates(&self.registered, failed_gateway, topology.as_ref());
+
+        for candidate in candidates {
+            debug!(
+                "attempting to fail over to registered gateway {}",
+                candidate.gateway_id
+            );
+            match self
+                .connector
+                .connect(
+                    candidate.gateway_id,
+                    &candidate.gateway_listener,
+                    candidate.gateway_owner_address.as_ref(),
+                    Some(Arc::clone(&candidate.derived_aes128_ctr_blake3_hmac_keys)),
+                    bandwidth_controller,
+                )
+                .await
+            {
+                Ok((gateway_client, _)) => {
+                    self.switch_to(gateway_client).await;
+                    return true;
+                }
+                Err(err) => warn!(
+                    "failed to fail over to gateway {}: {err}",
+                    candidate.gateway_id
+                ),
+            }
+        }
+
+        false
+    }
+
+    async fn try_new_gateways(
+        &mut self,
+        failed_gateway: identity::PublicKey,
+        bandwidth_controller: &mut Option<BandwidthController<C, St>>,
+    ) -> bool {
+        let Some(topology) = self.topology_access.current_topology().await else {
+            warn!("no valid topology available - can't choose a new gateway");
+            return false;
+        };
+
+        // keep the same transport security as the original gateway
+        let must_use_tls = self.original.gateway_listener.scheme() == "wss";
+
+        let candidates = new_failover_candidates(&topology, failed_gateway, &self.registered);
+
+        for node in candidates.choose_multiple(&mut OsRng, MAX_NEW_GATEWAY_ATTEMPTS) {
+            let selected =
+                match SelectedGateway::from_topology_node((*node).clone(), None, must_use_tls) {
+                    Ok(selected) => selected,
+                    Err(err) => {
+                        debug!("gateway {} can't be used: {err}", node.identity_key);
+                        continue;
+                    }
+                };
+            let SelectedGateway::Remote {
+                gateway_id,
+                gateway_owner_address,
+                gateway_listener,
+                ..
+            } = selected
+            else {
+                continue;
+            };
+
+            debug!("attempting to register with new gateway {gateway_id}");
+            match self
+                .connector
+                .connect(
+                    gateway_id,
+                    &gateway_listener,
+                    gateway_owner_address.as_ref(),
+                    None,
+                    bandwidth_controller,
+                )
+                .await
+            {
+                Ok((gateway_client, shared_keys)) => {
+                    self.registered.push(RemoteGatewayDetails {
+                        gateway_id,
+                        derived_aes128_ctr_blake3_hmac_keys: shared_keys,
+                        gateway_owner_address,
+                        gateway_listener,
+                        wg_tun_address: None,
+                    });
+                    self.switch_to(gateway_client).await;
+                    return true;
+                }
+                Err(err) => warn!("failed to register with gateway {gateway_id}: {err}"),
+            }
+        }
+
+        false
+    }
+
+    async fn failover(&mut self) {
+        let now = get_time_now();
+        if let Some(last_attempt) = self.last_failover_attempt {
+            if now.duration_since(last_attempt) < FAILOVER_RETRY_BACKOFF {
+                return;
+            }
+        }
+        self.last_failover_attempt = Some(now);
+
+        let failed_gateway = self.active.gateway_identity();
+        warn!("gateway {failed_gateway} appears to be unreachable - attempting to fail over to a different one");
+
+        let mut bandwidth_controller = self.active.take_bandwidth_controller();
+
+        if self
+            .try_registered_gateways(failed_gateway, &mut bandwidth_controller)
+            .await
+        {
+            return;
+        }
+
+        if self.config.failover_to_new_gateways
+            && self
+                .try_new_gateways(failed_gateway, &mut bandwidth_controller)
+                .await
+        {
+            return;
+        }
+
+        // as a last resort, see if the gateway has come back in the meantime
+        self.active.set_bandwidth_controller(bandwidth_controller);
+        match self.active.try_reconnect().await {
+            Ok(_) => info!("managed to reconnect to gateway {failed_gateway}"),
+            Err(err) => error!("failed to fail over to any gateway. The last error was: {err}"),
+        }
+    }
+
+    // switches back to the original gateway if the failback monitor has managed to reconnect to it
+    async fn maybe_failback(&mut self) {
+        let Some(recovered_gateways) = self.recovered_gateways.as_mut() else {
+            return;
+        };
+        let Ok(mut recovered) = recovered_gateways.try_recv() else {
+            return;
+        };
+
+        // we might have already failed over back to it on our own
+        if self.active.gateway_identity() == self.original.gateway_id {
+            if let Err(err) = recovered.close_connection().await {
+                debug!("failed to cleanly close the redundant connection to the original gateway: {err}");
+            }
+            return;
+        }
+
+        info!(
+            "switching back to the original gateway {}",
+            self.original.gateway_id
+        );
+        recovered.set_bandwidth_controller(self.active.take_bandwidth_controller());
+        self.switch_to(recovered).await;
+    }
+
+    async fn handle_send_result(
+        &mut self,
+        result: Result<(), GatewayClientError>,
+    ) -> Result<(), ErasedGatewayError> {
+        let Err(err) = result else {
+            self.last_failover_attempt = None;
+            return Ok(());
+        };
+
+        if is_unreachable_gateway(&err) {
+            // the packets we failed to send are lost, but any real messages
+            // are going to get retransmitted once their acks time out
+            self.failover().await;
+        }
+
+        Err(erase_err(err))
+    }
+}
+
+impl<C, St> GatewayTransceiver for FailoverGateway<C, St>
+where
+    C: DkgQueryClient + Send + Sync,
+    St: CredentialStorage,
+    <St as CredentialStorage>::StorageError: Send + Sync + 'static,
+{
+    fn gateway_identity(&self) -> identity::PublicKey {
+        self.active.gateway_identity()
+    }
+
+    fn ws_fd(&self) -> Option<RawFd> {
+        self.active.ws_fd()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C, St> GatewaySender for FailoverGateway<C, St>
+where
+    C: DkgQueryClient + Send + Sync,
+    St: CredentialStorage,
+    <St as CredentialStorage>::StorageError: Send + Sync + 'static,
+{
+    async fn send_mix_packet(&mut self, packet: MixPacket) -> Result<(), ErasedGatewayError> {
+        self.maybe_failback().await;
+        let result = self.active.send_mix_packet(packet).await;
+        self.handle_send_result(result).await
+    }
+
+    async fn batch_send_mix_packets(
+        &mut self,
+        packets: Vec<MixPacket>,
+    ) -> Result<(), ErasedGatewayError> {
+        self.maybe_failback().await;
+        let result = self.active.batch_send_mix_packets(packets).await;
+        self.handle_send_result(result).await
+    }
+}
+
+impl<C, St> GatewayReceiver for FailoverGateway<C, St> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_gateway_client::SharedKeys;
+
+    fn random_identity() -> identity::PublicKey {
+        *identity::KeyPair::new(&mut OsRng).public_key()
+    }
+
+    fn registered_gateway(gateway_id: identity::PublicKey) -> RemoteGatewayDetails {
+        RemoteGatewayDetails {
+            gateway_id,
+            derived_aes128_ctr_blake3_hmac_keys: Arc::new(
+                SharedKeys::try_from_bytes(&[42u8; 32]).unwrap(),
+            ),
+            gateway_owner_address: None,
+            gateway_listener: "ws://1.2.3.4:9000".parse().unwrap(),
+            wg_tun_address: None,
+        }
+    }
+
+    fn topology_node(identity_key: identity::PublicKey) -> gateway::Node {
+        gateway::Node {
+            owner: None,
+            host: "1.2.3.4".parse().unwrap(),
+            mix_host: "1.2.3.4:1789".parse().unwrap(),
+            clients_ws_port: 9000,
+            clients_wss_port: None,
+            identity_key,
+            sphinx_key: *encryption::KeyPair::new(&mut OsRng).public_key(),
+            version: "1.1.0".into(),
+        }
+    }
+
+    fn topology(gateways: &[identity::PublicKey]) -> NymTopology {
+        NymTopology::new(
+            Default::default(),
+            gateways.iter().copied().map(topology_node).collect(),
+        )
+    }
+
+    fn ids(gateways: &[RemoteGatewayDetails]) -> Vec<identity::PublicKey> {
+        gateways.iter().map(|gateway| gateway.gateway_id).collect()
+    }
+
+    #[test]
+    fn failed_gateway_is_never_a_registered_candidate() {
+        let failed = random_identity();
+        let other = random_identity();
+        let registered = vec![registered_gateway(failed), registered_gateway(other)];
+
+        let candidates = registered_failover_candidates(&registered, failed, None);
+        assert_eq!(ids(&candidates), vec![other]);
+
+        let topology = topology(&[failed, other]);
+        let candidates = registered_failover_candidates(&registered, failed, Some(&topology));
+        assert_eq!(ids(&candidates), vec![other]);
+    }
+
+    #[test]
+    fn registered_candidates_must_be_in_the_topology_if_its_known() {
+        let failed = random_identity();
+        let gone = random_identity();
+        let present = random_identity();
+        let registered = vec![
+            registered_gateway(failed),
+            registered_gateway(gone),
+            registered_gateway(present),
+        ];
+
+        let topology = topology(&[failed, present]);
+        let candidates = registered_failover_candidates(&registered, failed, Some(&topology));
+        assert_eq!(ids(&candidates), vec![present]);
+
+        // without the topology we have no idea which gateways are gone, so try all of them
+        let candidates = registered_failover_candidates(&registered, failed, None);
+        assert_eq!(ids(&candidates), vec![gone, present]);
+    }
+
+    #[test]
+    fn new_candidates_exclude_failed_and_registered_gateways() {
+        let failed = random_identity();
+        let registered_id = random_identity();
+        let fresh1 = random_identity();
+        let fresh2 = random_identity();
+        let registered = vec![
+            registered_gateway(failed),
+            registered_gateway(registered_id),
+        ];
+
+        let topology = topology(&[failed, fresh1, registered_id, fresh2]);
+        let candidates = new_failover_candidates(&topology, failed, &registered)
+            .into_iter()
+            .map(|node| node.identity_key)
+            .collect::<Vec<_>>();
+        assert_eq!(candidates, vec![fresh1, fresh2]);
+
+        let topology = NymTopology::new(Default::default(), vec![topology_node(failed)]);
+        assert!(new_failover_candidates(&topology, failed, &registered).is_empty());
+    }
+
+    #[test]
+    fn original_gateway_is_only_checked_after_failing_over() {
+        let original = random_identity();
+        let other = random_identity();
+
+        assert!(!needs_failback_check(original, original, false));
+        assert!(needs_failback_check(other, original, false));
+
+        // a recovered connection is already waiting to be used
+        assert!(!needs_failback_check(other, original, true));
+        assert!(!needs_failback_check(original, original, true));
+    }
+}
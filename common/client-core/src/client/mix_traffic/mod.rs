@@ -1,14 +1,23 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::client::delivery_status::MessageTrackingId;
 use crate::client::mix_traffic::transceiver::GatewayTransceiver;
+use crate::client::real_messages_control::{AckActionSender, Action};
 use crate::spawn_future;
 use log::*;
 use nym_sphinx::forwarding::packet::MixPacket;
 
-pub type BatchMixMessageSender = tokio::sync::mpsc::Sender<Vec<MixPacket>>;
-pub type BatchMixMessageReceiver = tokio::sync::mpsc::Receiver<Vec<MixPacket>>;
+// the packets are (optionally) accompanied by the tracking id of the message they belong to,
+// so that a failure to send them could be reported
+pub type BatchMixMessageSender =
+    tokio::sync::mpsc::Sender<(Vec<MixPacket>, Option<MessageTrackingId>)>;
+pub type BatchMixMessageReceiver =
+    tokio::sync::mpsc::Receiver<(Vec<MixPacket>, Option<MessageTrackingId>)>;
 
+pub(crate) mod connector;
+pub mod failover;
+pub mod multi_gateway;
 pub mod transceiver;
 
 // We remind ourselves that 32 x 32kb = 1024kb, a reasonable size for a network buffer.
@@ -23,6 +32,9 @@ pub struct MixTrafficController {
 
     mix_rx: BatchMixMessageReceiver,
 
+    /// Channel used for reporting tracked messages that could not be sent to the gateway.
+    ack_action_sender: Option<AckActionSender>,
+
     // TODO: this is temporary work-around.
     // in long run `gateway_client` will be moved away from `MixTrafficController` anyway.
     consecutive_gateway_failure_count: usize,
@@ -39,6 +51,7 @@ impl MixTrafficController {
             MixTrafficController {
                 gateway_transceiver: Box::new(gateway_transceiver),
                 mix_rx: message_receiver,
+                ack_action_sender: None,
                 consecutive_gateway_failure_count: 0,
             },
             message_sender,
@@ -54,13 +67,36 @@ impl MixTrafficController {
             MixTrafficController {
                 gateway_transceiver,
                 mix_rx: message_receiver,
+                ack_action_sender: None,
                 consecutive_gateway_failure_count: 0,
             },
             message_sender,
         )
     }
 
-    async fn on_messages(&mut self, mut mix_packets: Vec<MixPacket>) {
+    #[must_use]
+    pub(crate) fn with_ack_action_sender(mut self, ack_action_sender: AckActionSender) -> Self {
+        self.ack_action_sender = Some(ack_action_sender);
+        self
+    }
+
+    fn report_failed_message(&self, tracking_id: MessageTrackingId) {
+        let Some(ack_action_sender) = &self.ack_action_sender else {
+            return;
+        };
+        if ack_action_sender
+            .unbounded_send(Action::new_message_failed(tracking_id))
+            .is_err()
+        {
+            debug!("failed to report message {tracking_id} as failed - the action controller has stopped")
+        }
+    }
+
+    async fn on_messages(
+        &mut self,
+        mut mix_packets: Vec<MixPacket>,
+        tracking_id: Option<MessageTrackingId>,
+    ) {
         debug_assert!(!mix_packets.is_empty());
 
         let result = if mix_packets.len() == 1 {
@@ -75,6 +111,9 @@ impl MixTrafficController {
         match result {
             Err(err) => {
                 error!("Failed to send sphinx packet(s) to the gateway: {err}");
+                if let Some(tracking_id) = tracking_id {
+                    self.report_failed_message(tracking_id)
+                }
                 self.consecutive_gateway_failure_count += 1;
                 if self.consecutive_gateway_failure_count == MAX_FAILURE_COUNT {
                     // todo: in the future this should initiate a 'graceful' shutdown or try
@@ -96,8 +135,8 @@ impl MixTrafficController {
             loop {
                 tokio::select! {
                     mix_packets = self.mix_rx.recv() => match mix_packets {
-                        Some(mix_packets) => {
-                            self.on_messages(mix_packets).await;
+                        Some((mix_packets, tracking_id)) => {
+                            self.on_messages(mix_packets, tracking_id).await;
                         },
                         None => {
                             log::trace!("MixTrafficController: Stopping since channel closed");
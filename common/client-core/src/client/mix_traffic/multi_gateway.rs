@@ -0,0 +1,264 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::helpers::{get_time_now, Instant};
+use crate::client::mix_traffic::transceiver::{
+    ErasedGatewayError, GatewayReceiver, GatewaySender, GatewayTransceiver, PacketRouter,
+};
+use async_trait::async_trait;
+use log::{info, warn};
+use nym_crypto::asymmetric::identity;
+use nym_sphinx::forwarding::packet::MixPacket;
+use std::os::raw::c_int as RawFd;
+use std::time::Duration;
+
+// how long a gateway that has failed to send packets is avoided for before it's given another chance
+const FAILED_GATEWAY_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+struct MonitoredGateway {
+    transceiver: Box<dyn GatewayTransceiver + Send>,
+    consecutive_failures: usize,
+    last_failure: Option<Instant>,
+}
+
+impl MonitoredGateway {
+    fn new(transceiver: Box<dyn GatewayTransceiver + Send>) -> Self {
+        MonitoredGateway {
+            transceiver,
+            consecutive_failures: 0,
+            last_failure: None,
+        }
+    }
+
+    // failing gateways are treated as healthy again once they haven't been used for a while,
+    // so that they'd get re-probed and used again if they have recovered
+    fn effective_failures(&self, now: Instant) -> usize {
+        match self.last_failure {
+            Some(last_failure)
+                if now.duration_since(last_failure) >= FAILED_GATEWAY_RETRY_INTERVAL =>
+            {
+                0
+            }
+            _ => self.consecutive_failures,
+        }
+    }
+
+    fn record_result<T>(&mut self, result: &Result<T, ErasedGatewayError>) {
+        match result {
+            Ok(_) => {
+                self.consecutive_failures = 0;
+                self.last_failure = None;
+            }
+            Err(err) => {
+                warn!(
+                    "failed to send packet(s) through gateway {}: {err}",
+                    self.transceiver.gateway_identity()
+                );
+                self.consecutive_failures += 1;
+                self.last_failure = Some(get_time_now());
+            }
+        }
+    }
+}
+
+/// Gateway transceiver for a client that is simultaneously connected to multiple gateways ("hot-standby").
+/// All of the gateways receive packets addressed to the client, while outgoing packets are sent
+/// through the healthiest one, i.e. the one with the fewest consecutive send failures,
+/// with ties being resolved in favour of the primary gateway.
+/// Gateways that have been failing are retried once they haven't been used for a while.
+pub struct MultiGatewayTransceiver {
+    // the first gateway is always the primary one
+    gateways: Vec<MonitoredGateway>,
+    current: usize,
+}
+
+impl MultiGatewayTransceiver {
+    pub fn new(
+        primary: Box<dyn GatewayTransceiver + Send>,
+        standby: Vec<Box<dyn GatewayTransceiver + Send>>,
+    ) -> Self {
+        let gateways = std::iter::once(primary)
+            .chain(standby)
+            .map(MonitoredGateway::new)
+            .collect();
+
+        MultiGatewayTransceiver {
+            gateways,
+            current: 0,
+        }
+    }
+
+    /// Identities of all gateways the client is connected to, starting with the primary one.
+    pub fn gateways(&self) -> Vec<identity::PublicKey> {
+        self.gateways
+            .iter()
+            .map(|gateway| gateway.transceiver.gateway_identity())
+            .collect()
+    }
+
+    fn healthiest(&mut self) -> &mut MonitoredGateway {
+        let now = get_time_now();
+
+        // note: `min_by_key` returns the first of the equally healthy gateways
+        let healthiest = self
+            .gateways
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, gateway)| gateway.effective_failures(now))
+            .map(|(index, _)| index)
+            .unwrap_or_default();
+
+        if healthiest != self.current {
+            info!(
+                "sending packets through gateway {} from now on",
+                self.gateways[healthiest].transceiver.gateway_identity()
+            );
+            self.current = healthiest;
+        }
+
+        &mut self.gateways[healthiest]
+    }
+}
+
+impl GatewayTransceiver for MultiGatewayTransceiver {
+    fn gateway_identity(&self) -> identity::PublicKey {
+        self.gateways[0].transceiver.gateway_identity()
+    }
+
+    fn ws_fd(&self) -> Option<RawFd> {
+        self.gateways[0].transceiver.ws_fd()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GatewaySender for MultiGatewayTransceiver {
+    async fn send_mix_packet(&mut self, packet: MixPacket) -> Result<(), ErasedGatewayError> {
+        let gateway = self.healthiest();
+        let result = gateway.transceiver.send_mix_packet(packet).await;
+        gateway.record_result(&result);
+        result
+    }
+
+    async fn batch_send_mix_packets(
+        &mut self,
+        packets: Vec<MixPacket>,
+    ) -> Result<(), ErasedGatewayError> {
+        let gateway = self.healthiest();
+        let result = gateway.transceiver.batch_send_mix_packets(packets).await;
+        gateway.record_result(&result);
+        result
+    }
+}
+
+impl GatewayReceiver for MultiGatewayTransceiver {
+    fn set_packet_router(&mut self, packet_router: PacketRouter) -> Result<(), ErasedGatewayError> {
+        for gateway in &mut self.gateways {
+            gateway
+                .transceiver
+                .set_packet_router(packet_router.clone())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mix_traffic::transceiver::erase_err;
+    use rand::rngs::OsRng;
+    use tokio::time::advance;
+
+    struct MockGateway(identity::PublicKey);
+
+    impl GatewayTransceiver for MockGateway {
+        fn gateway_identity(&self) -> identity::PublicKey {
+            self.0
+        }
+
+        fn ws_fd(&self) -> Option<RawFd> {
+            None
+        }
+    }
+
+    #[async_trait]
+    impl GatewaySender for MockGateway {
+        async fn send_mix_packet(&mut self, _: MixPacket) -> Result<(), ErasedGatewayError> {
+            Ok(())
+        }
+    }
+
+    impl GatewayReceiver for MockGateway {}
+
+    fn mock_gateway() -> Box<dyn GatewayTransceiver + Send> {
+        Box::new(MockGateway(
+            *identity::KeyPair::new(&mut OsRng).public_key(),
+        ))
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("gateway is down")]
+    struct GatewayDown;
+
+    fn failure() -> Result<(), ErasedGatewayError> {
+        Err(erase_err(GatewayDown))
+    }
+
+    fn healthiest_id(transceiver: &mut MultiGatewayTransceiver) -> identity::PublicKey {
+        transceiver.healthiest().transceiver.gateway_identity()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn primary_gateway_is_preferred_while_healthy() {
+        let mut transceiver = MultiGatewayTransceiver::new(mock_gateway(), vec![mock_gateway()]);
+        let gateways = transceiver.gateways();
+
+        assert_eq!(healthiest_id(&mut transceiver), gateways[0]);
+        transceiver.healthiest().record_result(&Ok(()));
+        assert_eq!(healthiest_id(&mut transceiver), gateways[0]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gateway_with_fewest_failures_is_used() {
+        let mut transceiver =
+            MultiGatewayTransceiver::new(mock_gateway(), vec![mock_gateway(), mock_gateway()]);
+        let gateways = transceiver.gateways();
+
+        transceiver.gateways[0].record_result(&failure());
+        transceiver.gateways[0].record_result(&failure());
+        transceiver.gateways[1].record_result(&failure());
+        transceiver.gateways[2].record_result(&failure());
+        transceiver.gateways[2].record_result(&failure());
+        assert_eq!(healthiest_id(&mut transceiver), gateways[1]);
+
+        // a single success is enough to clear the failures
+        transceiver.gateways[2].record_result(&Ok(()));
+        assert_eq!(healthiest_id(&mut transceiver), gateways[2]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_gateways_are_eventually_retried() {
+        let mut transceiver = MultiGatewayTransceiver::new(mock_gateway(), vec![mock_gateway()]);
+        let gateways = transceiver.gateways();
+
+        transceiver.healthiest().record_result(&failure());
+        assert_eq!(healthiest_id(&mut transceiver), gateways[1]);
+        transceiver.healthiest().record_result(&Ok(()));
+
+        advance(FAILED_GATEWAY_RETRY_INTERVAL / 2).await;
+        assert_eq!(healthiest_id(&mut transceiver), gateways[1]);
+
+        // the primary gateway gets another chance, but it's still down
+        advance(FAILED_GATEWAY_RETRY_INTERVAL / 2).await;
+        assert_eq!(healthiest_id(&mut transceiver), gateways[0]);
+        transceiver.healthiest().record_result(&failure());
+        assert_eq!(healthiest_id(&mut transceiver), gateways[1]);
+
+        // and once it has recovered, it's used again
+        advance(FAILED_GATEWAY_RETRY_INTERVAL).await;
+        assert_eq!(healthiest_id(&mut transceiver), gateways[0]);
+        transceiver.healthiest().record_result(&Ok(()));
+        advance(FAILED_GATEWAY_RETRY_INTERVAL).await;
+        assert_eq!(healthiest_id(&mut transceiver), gateways[0]);
+    }
+}
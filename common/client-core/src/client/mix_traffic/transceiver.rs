@@ -19,7 +19,9 @@ use futures::channel::{mpsc, oneshot};
 #[error(transparent)]
 pub struct ErasedGatewayError(Box<dyn std::error::Error + Send + Sync>);
 
-fn erase_err<E: std::error::Error + Send + Sync + 'static>(err: E) -> ErasedGatewayError {
+pub(crate) fn erase_err<E: std::error::Error + Send + Sync + 'static>(
+    err: E,
+) -> ErasedGatewayError {
     ErasedGatewayError(Box::new(err))
 }
 
@@ -3,6 +3,7 @@
 
 pub mod base_client;
 pub mod cover_traffic_stream;
+pub mod delivery_status;
 pub(crate) mod helpers;
 pub mod inbound_messages;
 pub mod key_manager;
@@ -11,5 +12,6 @@ pub(crate) mod packet_statistics_control;
 pub mod real_messages_control;
 pub mod received_buffer;
 pub mod replies;
+pub mod self_address;
 pub mod topology_control;
 pub(crate) mod transmission_buffer;
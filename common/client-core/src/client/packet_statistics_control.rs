@@ -69,6 +69,9 @@ struct PacketStatistics {
     retransmissions_queued: u64,
     reply_surbs_queued: u64,
     additional_reply_surbs_queued: u64,
+
+    // Reconstruction
+    incomplete_sets_dropped: u64,
 }
 
 impl PacketStatistics {
@@ -132,6 +135,10 @@ impl PacketStatistics {
                 self.additional_reply_surbs_queued += 1;
                 inc!("additional_reply_surbs_queued");
             }
+            PacketStatisticsEvent::IncompleteSetsDropped(dropped) => {
+                self.incomplete_sets_dropped += dropped as u64;
+                inc_by!("incomplete_sets_dropped", dropped);
+            }
         }
     }
 
@@ -185,6 +192,8 @@ impl std::ops::Sub for PacketStatistics {
             reply_surbs_queued: self.reply_surbs_queued - rhs.reply_surbs_queued,
             additional_reply_surbs_queued: self.additional_reply_surbs_queued
                 - rhs.additional_reply_surbs_queued,
+
+            incomplete_sets_dropped: self.incomplete_sets_dropped - rhs.incomplete_sets_dropped,
         }
     }
 }
@@ -355,6 +364,9 @@ pub(crate) enum PacketStatisticsEvent {
     RetransmissionQueued,
     ReplySurbRequestQueued,
     AdditionalReplySurbRequestQueued,
+
+    // Incomplete message sets dropped due to being stale or exceeding the buffer size limit
+    IncompleteSetsDropped(usize),
 }
 
 type PacketStatisticsReceiver = tokio::sync::mpsc::UnboundedReceiver<PacketStatisticsEvent>;
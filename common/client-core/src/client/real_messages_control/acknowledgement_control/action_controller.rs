@@ -1,7 +1,9 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use super::delivery_tracker::{DeliveryTracker, ExpiryDecision};
 use super::PendingAcknowledgement;
+use crate::client::delivery_status::{DeliveryStatusSender, MessageTrackingId};
 use crate::client::real_messages_control::acknowledgement_control::RetransmissionRequestSender;
 use futures::channel::mpsc;
 use futures::StreamExt;
@@ -24,6 +26,7 @@ type PendingAckEntry = (Arc<PendingAcknowledgement>, Option<QueueKey>);
 // - received an ack so we want to remove an entry
 // - start a retransmission timer for sending the packet into the network (on either first try or retransmission)
 // - update the internal sphinx delay of an expired packet
+// - start or stop tracking delivery status of a message
 pub(crate) enum Action {
     /// Inserts new `PendingAcknowledgement`s into the 'shared' state.
     /// Initiated by `InputMessageListener`
@@ -42,6 +45,19 @@ pub(crate) enum Action {
     /// Updates the expected delay of given `PendingAcknowledgement` with the new provided `SphinxDelay`.
     /// Initiated by `RetransmissionRequestListener`
     UpdateDelay(FragmentIdentifier, SphinxDelay),
+
+    /// Starts tracking delivery status of the message consisting of the provided fragments.
+    /// It must be sent before the associated `InsertPending`.
+    /// Initiated by `MessageHandler`
+    TrackMessage(MessageTrackingId, Vec<FragmentIdentifier>),
+
+    /// Indicates the tracked message could not have been sent at all.
+    /// Initiated by `MessageHandler` or `ReplyController`
+    MessageFailed(MessageTrackingId),
+
+    /// Sets the channel used for reporting delivery status changes of tracked messages.
+    /// Initiated by `ClientOutput`
+    RegisterDeliveryStatusListener(DeliveryStatusSender),
 }
 
 impl Action {
@@ -60,6 +76,21 @@ impl Action {
     pub(crate) fn new_update_delay(frag_id: FragmentIdentifier, delay: SphinxDelay) -> Self {
         Action::UpdateDelay(frag_id, delay)
     }
+
+    pub(crate) fn new_track_message(
+        tracking_id: MessageTrackingId,
+        frag_ids: Vec<FragmentIdentifier>,
+    ) -> Self {
+        Action::TrackMessage(tracking_id, frag_ids)
+    }
+
+    pub(crate) fn new_message_failed(tracking_id: MessageTrackingId) -> Self {
+        Action::MessageFailed(tracking_id)
+    }
+
+    pub(crate) fn new_register_delivery_status_listener(sender: DeliveryStatusSender) -> Self {
+        Action::RegisterDeliveryStatusListener(sender)
+    }
 }
 
 /// Configurable parameters of the `ActionController`
@@ -69,13 +100,21 @@ pub(super) struct Config {
 
     /// Given ack timeout in the form a * BASE_DELAY + b, it specifies the multiplier `a`
     ack_wait_multiplier: f64,
+
+    /// Maximum number of retransmissions of a fragment of a tracked message before it's given up on.
+    maximum_tracked_retransmissions: u32,
 }
 
 impl Config {
-    pub(super) fn new(ack_wait_addition: Duration, ack_wait_multiplier: f64) -> Self {
+    pub(super) fn new(
+        ack_wait_addition: Duration,
+        ack_wait_multiplier: f64,
+        maximum_tracked_retransmissions: u32,
+    ) -> Self {
         Config {
             ack_wait_addition,
             ack_wait_multiplier,
+            maximum_tracked_retransmissions,
         }
     }
 }
@@ -100,6 +139,9 @@ pub(super) struct ActionController {
 
     /// Channel for notifying `RetransmissionRequestListener` about expired acknowledgements.
     retransmission_sender: RetransmissionRequestSender,
+
+    /// Keeps track of delivery status of messages sent with an explicit tracking id.
+    delivery_tracker: DeliveryTracker,
 }
 
 impl ActionController {
@@ -109,6 +151,7 @@ impl ActionController {
         incoming_actions: AckActionReceiver,
     ) -> Self {
         ActionController {
+            delivery_tracker: DeliveryTracker::new(config.maximum_tracked_retransmissions),
             config,
             pending_acks_data: HashMap::new(),
             pending_acks_timers: NonExhaustiveDelayQueue::new(),
@@ -173,6 +216,7 @@ impl ActionController {
                 );
             }
             Some((_, queue_key)) => {
+                self.delivery_tracker.on_acknowledged(frag_id);
                 if let Some(queue_key) = queue_key {
                     // there are no possible checks here, we must GUARANTEE that we NEVER try
                     // to remove an entry that doesn't exist (and we MUST GUARANTEE that
@@ -227,6 +271,13 @@ impl ActionController {
 
         trace!("{} has expired", frag_id);
 
+        if self.delivery_tracker.on_expired(frag_id) == ExpiryDecision::GiveUp {
+            // the message this fragment belongs to has already been marked as failed,
+            // so there's no point in keeping it around any longer
+            self.pending_acks_data.remove(&frag_id);
+            return;
+        }
+
         if let Some((pending_ack_data, queue_key)) = self.pending_acks_data.get_mut(&frag_id) {
             if queue_key.is_none() {
                 // this branch should be IMPOSSIBLE under ANY condition. It would imply the timeout
@@ -259,6 +310,13 @@ impl ActionController {
             Action::RemovePending(frag_id) => self.handle_remove(frag_id),
             Action::StartTimer(frag_id) => self.handle_start_timer(frag_id),
             Action::UpdateDelay(frag_id, delay) => self.handle_update_delay(frag_id, delay),
+            Action::TrackMessage(tracking_id, frag_ids) => {
+                self.delivery_tracker.track(tracking_id, frag_ids)
+            }
+            Action::MessageFailed(tracking_id) => self.delivery_tracker.fail(tracking_id),
+            Action::RegisterDeliveryStatusListener(sender) => {
+                self.delivery_tracker.set_status_sender(sender)
+            }
         }
     }
 
@@ -0,0 +1,231 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::client::delivery_status::{
+    DeliveryStatus, DeliveryStatusEvent, DeliveryStatusSender, MessageTrackingId,
+};
+use log::*;
+use nym_sphinx::chunking::fragment::FragmentIdentifier;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+/// Decision on what should happen to a fragment whose acknowledgement timer has expired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ExpiryDecision {
+    Retransmit,
+    GiveUp,
+}
+
+#[derive(Default)]
+struct TrackedMessage {
+    pending_fragments: usize,
+    retransmissions: u32,
+    failed: bool,
+}
+
+struct TrackedFragment {
+    tracking_id: MessageTrackingId,
+    retransmissions: u32,
+}
+
+/// Keeps track of fragments of messages sent with an explicit `MessageTrackingId`
+/// and reports the delivery status of those messages once it changes.
+pub(super) struct DeliveryTracker {
+    /// Maximum number of retransmissions of a single fragment of a tracked message
+    /// before it is given up on.
+    maximum_retransmissions: u32,
+
+    messages: HashMap<MessageTrackingId, TrackedMessage>,
+    fragments: HashMap<FragmentIdentifier, TrackedFragment>,
+
+    /// Channel for reporting status changes, if anyone has registered for them.
+    status_sender: Option<DeliveryStatusSender>,
+}
+
+impl DeliveryTracker {
+    pub(super) fn new(maximum_retransmissions: u32) -> Self {
+        DeliveryTracker {
+            maximum_retransmissions,
+            messages: HashMap::new(),
+            fragments: HashMap::new(),
+            status_sender: None,
+        }
+    }
+
+    pub(super) fn set_status_sender(&mut self, status_sender: DeliveryStatusSender) {
+        if self.status_sender.replace(status_sender).is_some() {
+            debug!("replaced the existing delivery status listener")
+        }
+    }
+
+    fn report(&mut self, tracking_id: MessageTrackingId, status: DeliveryStatus) {
+        trace!("message {tracking_id} has changed its delivery status to {status:?}");
+        let Some(status_sender) = &self.status_sender else {
+            return;
+        };
+        if status_sender
+            .unbounded_send(DeliveryStatusEvent::new(tracking_id, status))
+            .is_err()
+        {
+            debug!("the delivery status receiver has been dropped");
+            self.status_sender = None;
+        }
+    }
+
+    pub(super) fn track(
+        &mut self,
+        tracking_id: MessageTrackingId,
+        fragments: Vec<FragmentIdentifier>,
+    ) {
+        let message = self.messages.entry(tracking_id).or_default();
+        for frag_id in fragments {
+            let tracked = TrackedFragment {
+                tracking_id,
+                retransmissions: 0,
+            };
+            if self.fragments.insert(frag_id, tracked).is_none() {
+                message.pending_fragments += 1;
+            } else {
+                warn!("{frag_id} is already being tracked")
+            }
+        }
+    }
+
+    pub(super) fn fail(&mut self, tracking_id: MessageTrackingId) {
+        self.report(tracking_id, DeliveryStatus::Failed)
+    }
+
+    pub(super) fn on_acknowledged(&mut self, frag_id: FragmentIdentifier) {
+        self.resolve(frag_id, false)
+    }
+
+    pub(super) fn on_expired(&mut self, frag_id: FragmentIdentifier) -> ExpiryDecision {
+        let Some(fragment) = self.fragments.get_mut(&frag_id) else {
+            // untracked fragments are retransmitted until they're eventually acknowledged
+            return ExpiryDecision::Retransmit;
+        };
+
+        if fragment.retransmissions >= self.maximum_retransmissions {
+            debug!(
+                "giving up on {frag_id} after {} retransmissions",
+                fragment.retransmissions
+            );
+            self.resolve(frag_id, true);
+            return ExpiryDecision::GiveUp;
+        }
+        fragment.retransmissions += 1;
+
+        let tracking_id = fragment.tracking_id;
+        if let Some(message) = self.messages.get_mut(&tracking_id) {
+            message.retransmissions += 1;
+            let retransmissions = message.retransmissions;
+            self.report(tracking_id, DeliveryStatus::Retransmitting(retransmissions))
+        }
+        ExpiryDecision::Retransmit
+    }
+
+    fn resolve(&mut self, frag_id: FragmentIdentifier, given_up: bool) {
+        let Some(fragment) = self.fragments.remove(&frag_id) else {
+            return;
+        };
+        let Entry::Occupied(mut entry) = self.messages.entry(fragment.tracking_id) else {
+            return;
+        };
+
+        let message = entry.get_mut();
+        message.failed |= given_up;
+        message.pending_fragments = message.pending_fragments.saturating_sub(1);
+        if message.pending_fragments > 0 {
+            return;
+        }
+
+        let status = if entry.remove().failed {
+            DeliveryStatus::Failed
+        } else {
+            DeliveryStatus::Delivered
+        };
+        self.report(fragment.tracking_id, status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::delivery_status::DeliveryStatusReceiver;
+    use futures::channel::mpsc;
+
+    fn frag_id(id: i32, position: u8) -> FragmentIdentifier {
+        let id = id.to_be_bytes();
+        FragmentIdentifier::try_from_bytes([id[0], id[1], id[2], id[3], position]).unwrap()
+    }
+
+    fn statuses(receiver: &mut DeliveryStatusReceiver) -> Vec<DeliveryStatus> {
+        let mut statuses = Vec::new();
+        while let Ok(Some(event)) = receiver.try_next() {
+            statuses.push(event.status)
+        }
+        statuses
+    }
+
+    #[test]
+    fn message_is_delivered_once_all_fragments_are_acknowledged() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let mut tracker = DeliveryTracker::new(3);
+        tracker.set_status_sender(sender);
+        let tracking_id = MessageTrackingId::new(42);
+
+        tracker.track(tracking_id, vec![frag_id(1, 1), frag_id(1, 2)]);
+        tracker.on_acknowledged(frag_id(1, 1));
+        assert!(statuses(&mut receiver).is_empty());
+
+        assert_eq!(
+            tracker.on_expired(frag_id(1, 2)),
+            ExpiryDecision::Retransmit
+        );
+        tracker.on_acknowledged(frag_id(1, 2));
+        assert_eq!(
+            statuses(&mut receiver),
+            vec![DeliveryStatus::Retransmitting(1), DeliveryStatus::Delivered]
+        );
+
+        // duplicate acks are ignored
+        tracker.on_acknowledged(frag_id(1, 2));
+        assert!(statuses(&mut receiver).is_empty());
+    }
+
+    #[test]
+    fn message_fails_if_any_fragment_is_given_up_on() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let mut tracker = DeliveryTracker::new(1);
+        tracker.set_status_sender(sender);
+        let tracking_id = MessageTrackingId::new(42);
+
+        tracker.track(tracking_id, vec![frag_id(1, 1), frag_id(1, 2)]);
+        assert_eq!(
+            tracker.on_expired(frag_id(1, 1)),
+            ExpiryDecision::Retransmit
+        );
+        assert_eq!(tracker.on_expired(frag_id(1, 1)), ExpiryDecision::GiveUp);
+        assert_eq!(
+            statuses(&mut receiver),
+            vec![DeliveryStatus::Retransmitting(1)]
+        );
+
+        tracker.on_acknowledged(frag_id(1, 2));
+        assert_eq!(statuses(&mut receiver), vec![DeliveryStatus::Failed]);
+    }
+
+    #[test]
+    fn untracked_fragments_are_always_retransmitted() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let mut tracker = DeliveryTracker::new(0);
+        tracker.set_status_sender(sender);
+
+        assert_eq!(
+            tracker.on_expired(frag_id(1, 1)),
+            ExpiryDecision::Retransmit
+        );
+        tracker.on_acknowledged(frag_id(1, 1));
+        assert!(statuses(&mut receiver).is_empty());
+    }
+}
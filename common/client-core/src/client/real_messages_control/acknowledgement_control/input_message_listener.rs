@@ -1,6 +1,7 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::client::delivery_status::MessageTrackingId;
 use crate::client::inbound_messages::{InputMessage, InputMessageReceiver};
 use crate::client::real_messages_control::message_handler::MessageHandler;
 use crate::client::real_messages_control::real_traffic_stream::RealMessage;
@@ -44,12 +45,19 @@ where
         }
     }
 
-    async fn handle_premade_packets(&mut self, packets: Vec<MixPacket>, lane: TransmissionLane) {
+    async fn handle_premade_packets(
+        &mut self,
+        packets: Vec<MixPacket>,
+        lane: TransmissionLane,
+        tracking_id: Option<MessageTrackingId>,
+    ) {
+        // we can't know whether premade packets have been delivered, but we can still report
+        // if they failed to be sent to the gateway
         self.message_handler
             .send_premade_mix_packets(
                 packets
                     .into_iter()
-                    .map(|p| RealMessage::new(p, None))
+                    .map(|p| RealMessage::new(p, None).with_tracking_id(tracking_id))
                     .collect(),
                 lane,
             )
@@ -61,10 +69,11 @@ where
         recipient_tag: AnonymousSenderTag,
         data: Vec<u8>,
         lane: TransmissionLane,
+        tracking_id: Option<MessageTrackingId>,
     ) {
         // offload reply handling to the dedicated task
         self.reply_controller_sender
-            .send_reply(recipient_tag, data, lane)
+            .send_reply(recipient_tag, data, lane, tracking_id)
     }
 
     async fn handle_plain_message(
@@ -74,16 +83,21 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        tracking_id: Option<MessageTrackingId>,
     ) {
         if let Err(err) = self
             .message_handler
-            .try_send_plain_message(recipient, content, lane, packet_type, mix_hops)
+            .try_send_plain_message(recipient, content, lane, packet_type, mix_hops, tracking_id)
             .await
         {
-            warn!("failed to send a plain message - {err}")
+            warn!("failed to send a plain message - {err}");
+            if let Some(tracking_id) = tracking_id {
+                self.message_handler.report_failed_message(tracking_id)
+            }
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn handle_repliable_message(
         &mut self,
         recipient: Recipient,
@@ -92,6 +106,7 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        tracking_id: Option<MessageTrackingId>,
     ) {
         if let Err(err) = self
             .message_handler
@@ -102,14 +117,41 @@ where
                 lane,
                 packet_type,
                 mix_hops,
+                tracking_id,
             )
             .await
         {
-            warn!("failed to send a repliable message - {err}")
+            warn!("failed to send a repliable message - {err}");
+            if let Some(tracking_id) = tracking_id {
+                self.message_handler.report_failed_message(tracking_id)
+            }
         }
     }
 
-    async fn on_input_message(&mut self, msg: InputMessage) {
+    async fn on_input_message(&mut self, mut msg: InputMessage) {
+        // strip all the wrappers, but keep the information they carried
+        let mut packet_type = PacketType::Mix;
+        let mut tracking_id = None;
+        loop {
+            match msg {
+                InputMessage::MessageWrapper {
+                    message,
+                    packet_type: wrapped_type,
+                } => {
+                    packet_type = wrapped_type;
+                    msg = *message;
+                }
+                InputMessage::Tracked {
+                    message,
+                    tracking_id: id,
+                } => {
+                    tracking_id = Some(id);
+                    msg = *message;
+                }
+                _ => break,
+            }
+        }
+
         match msg {
             InputMessage::Regular {
                 recipient,
@@ -117,7 +159,7 @@ where
                 lane,
                 mix_hops,
             } => {
-                self.handle_plain_message(recipient, data, lane, PacketType::Mix, mix_hops)
+                self.handle_plain_message(recipient, data, lane, packet_type, mix_hops, tracking_id)
                     .await
             }
             InputMessage::Anonymous {
@@ -132,8 +174,9 @@ where
                     data,
                     reply_surbs,
                     lane,
-                    PacketType::Mix,
+                    packet_type,
                     mix_hops,
+                    tracking_id,
                 )
                 .await
             }
@@ -142,52 +185,14 @@ where
                 data,
                 lane,
             } => {
-                self.handle_reply(recipient_tag, data, lane).await;
+                self.handle_reply(recipient_tag, data, lane, tracking_id)
+                    .await;
             }
-            InputMessage::Premade { msgs, lane } => self.handle_premade_packets(msgs, lane).await,
-            InputMessage::MessageWrapper {
-                message,
-                packet_type,
-            } => match *message {
-                InputMessage::Regular {
-                    recipient,
-                    data,
-                    lane,
-                    mix_hops,
-                } => {
-                    self.handle_plain_message(recipient, data, lane, packet_type, mix_hops)
-                        .await
-                }
-                InputMessage::Anonymous {
-                    recipient,
-                    data,
-                    reply_surbs,
-                    lane,
-                    mix_hops,
-                } => {
-                    self.handle_repliable_message(
-                        recipient,
-                        data,
-                        reply_surbs,
-                        lane,
-                        packet_type,
-                        mix_hops,
-                    )
-                    .await
-                }
-                InputMessage::Reply {
-                    recipient_tag,
-                    data,
-                    lane,
-                } => {
-                    self.handle_reply(recipient_tag, data, lane).await;
-                }
-                InputMessage::Premade { msgs, lane } => {
-                    self.handle_premade_packets(msgs, lane).await
-                }
-                // MessageWrappers can't be nested
-                InputMessage::MessageWrapper { .. } => unimplemented!(),
-            },
+            InputMessage::Premade { msgs, lane } => {
+                self.handle_premade_packets(msgs, lane, tracking_id).await
+            }
+            // all the wrappers have been removed above
+            InputMessage::MessageWrapper { .. } | InputMessage::Tracked { .. } => unreachable!(),
         };
     }
 
@@ -12,7 +12,6 @@ use crate::client::packet_statistics_control::PacketStatisticsReporter;
 use crate::client::real_messages_control::message_handler::MessageHandler;
 use crate::client::replies::reply_controller::ReplyControllerSender;
 use crate::spawn_future;
-use action_controller::AckActionReceiver;
 use futures::channel::mpsc;
 use log::*;
 use nym_gateway_client::AcknowledgementReceiver;
@@ -30,10 +29,11 @@ use std::{
     time::Duration,
 };
 
-pub(crate) use action_controller::{AckActionSender, Action};
+pub(crate) use action_controller::{AckActionReceiver, AckActionSender, Action};
 
 mod acknowledgement_listener;
 mod action_controller;
+mod delivery_tracker;
 mod input_message_listener;
 mod retransmission_request_listener;
 mod sent_notification_listener;
@@ -169,15 +169,23 @@ pub(super) struct Config {
     /// Given ack timeout in the form a * BASE_DELAY + b, it specifies the multiplier `a`
     ack_wait_multiplier: f64,
 
+    /// Maximum number of retransmissions of a fragment of a tracked message before it's given up on.
+    maximum_tracked_retransmissions: u32,
+
     /// Predefined packet size used for the encapsulated messages.
     packet_size: PacketSize,
 }
 
 impl Config {
-    pub(super) fn new(ack_wait_addition: Duration, ack_wait_multiplier: f64) -> Self {
+    pub(super) fn new(
+        ack_wait_addition: Duration,
+        ack_wait_multiplier: f64,
+        maximum_tracked_retransmissions: u32,
+    ) -> Self {
         Config {
             ack_wait_addition,
             ack_wait_multiplier,
+            maximum_tracked_retransmissions,
             packet_size: Default::default(),
         }
     }
@@ -213,8 +221,11 @@ where
     ) -> Self {
         let (retransmission_tx, retransmission_rx) = mpsc::unbounded();
 
-        let action_config =
-            action_controller::Config::new(config.ack_wait_addition, config.ack_wait_multiplier);
+        let action_config = action_controller::Config::new(
+            config.ack_wait_addition,
+            config.ack_wait_multiplier,
+            config.maximum_tracked_retransmissions,
+        );
         let action_controller = ActionController::new(
             action_config,
             retransmission_tx,
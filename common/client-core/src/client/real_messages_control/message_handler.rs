@@ -1,12 +1,14 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::client::delivery_status::MessageTrackingId;
 use crate::client::real_messages_control::acknowledgement_control::PendingAcknowledgement;
 use crate::client::real_messages_control::real_traffic_stream::{
     BatchRealMessageSender, RealMessage,
 };
 use crate::client::real_messages_control::{AckActionSender, Action};
 use crate::client::replies::reply_storage::{ReceivedReplySurbsMap, SentReplyKeys, UsedSenderTags};
+use crate::client::self_address::SelfAddress;
 use crate::client::topology_control::{TopologyAccessor, TopologyReadPermit};
 use log::{debug, error, info, trace, warn};
 use nym_sphinx::acknowledgements::AckKey;
@@ -89,7 +91,7 @@ pub(crate) struct Config {
 
     /// Address of this client which also represent an address to which all acknowledgements
     /// and surb-based are going to be sent.
-    sender_address: Recipient,
+    sender_address: SelfAddress,
 
     /// Average delay a data packet is going to get delay at a single mixnode.
     average_packet_delay: Duration,
@@ -106,12 +108,15 @@ pub(crate) struct Config {
 
     /// Optional secondary predefined packet size used for the encapsulated messages.
     secondary_packet_size: Option<PacketSize>,
+
+    /// Number of repair fragments attached to each set of fragments of non-reply messages.
+    repair_fragments_per_set: u8,
 }
 
 impl Config {
     pub fn new(
         ack_key: Arc<AckKey>,
-        sender_address: Recipient,
+        sender_address: SelfAddress,
         average_packet_delay: Duration,
         average_ack_delay: Duration,
     ) -> Self {
@@ -123,6 +128,7 @@ impl Config {
             num_mix_hops: DEFAULT_NUM_MIX_HOPS,
             primary_packet_size: PacketSize::default(),
             secondary_packet_size: None,
+            repair_fragments_per_set: 0,
         }
     }
 
@@ -144,6 +150,12 @@ impl Config {
         self.secondary_packet_size = packet_size;
         self
     }
+
+    /// Allows attaching repair fragments to sent messages for the purposes of forward error correction.
+    pub fn with_repair_fragments_per_set(mut self, repair_fragments_per_set: u8) -> Self {
+        self.repair_fragments_per_set = repair_fragments_per_set;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -176,7 +188,7 @@ where
     {
         let message_preparer = MessagePreparer::new(
             rng,
-            config.sender_address,
+            config.sender_address.current(),
             config.average_packet_delay,
             config.average_ack_delay,
         )
@@ -207,11 +219,18 @@ where
         }
     }
 
+    /// Makes sure the message preparer uses the current address of this client,
+    /// as it might have changed if we have failed over to a different gateway.
+    fn sync_sender_address(&mut self) {
+        self.message_preparer
+            .set_sender_address(self.config.sender_address.current());
+    }
+
     fn get_topology<'a>(
         &self,
         permit: &'a TopologyReadPermit<'a>,
     ) -> Result<&'a NymTopology, PreparationError> {
-        match permit.try_get_valid_topology_ref(&self.config.sender_address, None) {
+        match permit.try_get_valid_topology_ref(&self.config.sender_address.current(), None) {
             Ok(topology_ref) => Ok(topology_ref),
             Err(err) => {
                 warn!("Could not process the packet - the network topology is invalid - {err}");
@@ -246,6 +265,7 @@ where
         &mut self,
         amount: usize,
     ) -> Result<(Vec<ReplySurb>, Vec<SurbEncryptionKey>), PreparationError> {
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = self.get_topology(&topology_permit)?;
 
@@ -319,7 +339,7 @@ where
         debug!("requesting {amount} reply SURBs from {from}");
 
         let surbs_request =
-            ReplyMessage::new_surb_request_message(self.config.sender_address, amount);
+            ReplyMessage::new_surb_request_message(self.config.sender_address.current(), amount);
         self.try_send_single_surb_message(from, surbs_request, reply_surb, true)
             .await
     }
@@ -419,10 +439,18 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        tracking_id: Option<MessageTrackingId>,
     ) -> Result<(), PreparationError> {
         let message = NymMessage::new_plain(message);
-        self.try_split_and_send_non_reply_message(message, recipient, lane, packet_type, mix_hops)
-            .await
+        self.try_split_and_send_non_reply_message(
+            message,
+            recipient,
+            lane,
+            packet_type,
+            mix_hops,
+            tracking_id,
+        )
+        .await
     }
 
     pub(crate) async fn try_split_and_send_non_reply_message(
@@ -432,12 +460,14 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        tracking_id: Option<MessageTrackingId>,
     ) -> Result<(), PreparationError> {
         debug!("Sending non-reply message with packet type {packet_type}");
         // TODO: I really dislike existence of this assertion, it implies code has to be re-organised
         debug_assert!(!matches!(message, NymMessage::Reply(_)));
 
         // TODO2: it's really annoying we have to get topology permit again here due to borrow-checker
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = self.get_topology(&topology_permit)?;
 
@@ -447,9 +477,11 @@ where
             self.optimal_packet_size(&message)
         };
         debug!("Using {packet_size} packets for {message}");
-        let fragments = self
-            .message_preparer
-            .pad_and_split_message(message, packet_size);
+        let fragments = self.message_preparer.pad_and_split_message_with_repair(
+            message,
+            packet_size,
+            self.config.repair_fragments_per_set,
+        );
 
         let mut pending_acks = Vec::with_capacity(fragments.len());
         let mut real_messages = Vec::with_capacity(fragments.len());
@@ -478,6 +510,13 @@ where
             pending_acks.push(pending_ack);
         }
 
+        if let Some(tracking_id) = tracking_id {
+            let frag_ids = pending_acks
+                .iter()
+                .map(|pending_ack| pending_ack.inner_fragment_identifier())
+                .collect();
+            self.track_message(tracking_id, frag_ids);
+        }
         self.insert_pending_acks(pending_acks);
         self.forward_messages(real_messages, lane).await;
 
@@ -507,6 +546,7 @@ where
             TransmissionLane::AdditionalReplySurbs,
             packet_type,
             mix_hops,
+            None,
         )
         .await?;
 
@@ -524,6 +564,7 @@ where
         lane: TransmissionLane,
         packet_type: PacketType,
         mix_hops: Option<u8>,
+        tracking_id: Option<MessageTrackingId>,
     ) -> Result<(), SurbWrappedPreparationError> {
         debug!("Sending message with reply SURBs with packet type {packet_type}");
         let sender_tag = self.get_or_create_sender_tag(&recipient);
@@ -534,8 +575,15 @@ where
         let message =
             NymMessage::new_repliable(RepliableMessage::new_data(message, sender_tag, reply_surbs));
 
-        self.try_split_and_send_non_reply_message(message, recipient, lane, packet_type, mix_hops)
-            .await?;
+        self.try_split_and_send_non_reply_message(
+            message,
+            recipient,
+            lane,
+            packet_type,
+            mix_hops,
+            tracking_id,
+        )
+        .await?;
 
         log::trace!("storing {} reply keys", reply_keys.len());
         self.reply_key_storage.insert_multiple(reply_keys);
@@ -551,6 +599,7 @@ where
         mix_hops: Option<u8>,
     ) -> Result<PreparedFragment, PreparationError> {
         debug!("Sending single chunk with packet type {packet_type}");
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = self.get_topology(&topology_permit)?;
 
@@ -582,6 +631,7 @@ where
             reply_surbs.len()
         );
 
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = match self.get_topology(&topology_permit) {
             Ok(topology) => topology,
@@ -611,6 +661,7 @@ where
         reply_surb: ReplySurb,
         chunk: Fragment,
     ) -> Result<PreparedFragment, SurbWrappedPreparationError> {
+        self.sync_sender_address();
         let topology_permit = self.topology_access.get_read_permit().await;
         let topology = match self.get_topology(&topology_permit) {
             Ok(topology) => topology,
@@ -637,6 +688,22 @@ where
             .expect("action control task has died")
     }
 
+    pub(crate) fn track_message(
+        &self,
+        tracking_id: MessageTrackingId,
+        frag_ids: Vec<FragmentIdentifier>,
+    ) {
+        self.action_sender
+            .unbounded_send(Action::new_track_message(tracking_id, frag_ids))
+            .expect("action control task has died")
+    }
+
+    pub(crate) fn report_failed_message(&self, tracking_id: MessageTrackingId) {
+        self.action_sender
+            .unbounded_send(Action::new_message_failed(tracking_id))
+            .expect("action control task has died")
+    }
+
     pub(crate) fn insert_pending_acks(&self, pending_acks: Vec<PendingAcknowledgement>) {
         self.action_sender
             .unbounded_send(Action::new_insert(pending_acks))
@@ -13,6 +13,7 @@ use crate::client::replies::reply_controller::{
     ReplyController, ReplyControllerReceiver, ReplyControllerSender,
 };
 use crate::client::replies::reply_storage::CombinedReplyStorage;
+use crate::client::self_address::SelfAddress;
 use crate::{
     client::{
         inbound_messages::InputMessageReceiver, mix_traffic::BatchMixMessageSender,
@@ -25,15 +26,14 @@ use futures::channel::mpsc;
 use log::*;
 use nym_gateway_client::AcknowledgementReceiver;
 use nym_sphinx::acknowledgements::AckKey;
-use nym_sphinx::addressing::clients::Recipient;
-use nym_sphinx::params::PacketType;
+use nym_sphinx::params::{PacketType, SphinxKeyRotationSchedule};
 use nym_task::connections::{ConnectionCommandReceiver, LaneQueueLengths};
 use rand::{rngs::OsRng, CryptoRng, Rng};
 use std::sync::Arc;
 
 use crate::client::replies::reply_controller;
 use crate::config;
-pub(crate) use acknowledgement_control::{AckActionSender, Action};
+pub(crate) use acknowledgement_control::{AckActionReceiver, AckActionSender, Action};
 
 use super::packet_statistics_control::PacketStatisticsReporter;
 
@@ -47,7 +47,7 @@ pub struct Config {
     ack_key: Arc<AckKey>,
 
     /// Address of `this` client.
-    self_recipient: Recipient,
+    self_recipient: SelfAddress,
 
     /// Specifies all traffic related configuration options.
     traffic: config::Traffic,
@@ -60,6 +60,9 @@ pub struct Config {
 
     /// Specifies all reply SURBs related configuration options.
     reply_surbs: config::ReplySurbs,
+
+    /// Specifies the schedule of sphinx key rotations used for expiring reply SURBs.
+    sphinx_key_rotation: SphinxKeyRotationSchedule,
 }
 
 impl<'a> From<&'a Config> for acknowledgement_control::Config {
@@ -67,6 +70,7 @@ impl<'a> From<&'a Config> for acknowledgement_control::Config {
         acknowledgement_control::Config::new(
             cfg.acks.ack_wait_addition,
             cfg.acks.ack_wait_multiplier,
+            cfg.acks.maximum_tracked_message_retransmissions,
         )
         .with_custom_packet_size(cfg.traffic.primary_packet_size)
     }
@@ -76,7 +80,7 @@ impl<'a> From<&'a Config> for real_traffic_stream::Config {
     fn from(cfg: &'a Config) -> Self {
         real_traffic_stream::Config::new(
             Arc::clone(&cfg.ack_key),
-            cfg.self_recipient,
+            cfg.self_recipient.clone(),
             cfg.acks.average_ack_delay,
             cfg.traffic,
             cfg.cover_traffic.cover_traffic_primary_size_ratio,
@@ -86,7 +90,7 @@ impl<'a> From<&'a Config> for real_traffic_stream::Config {
 
 impl<'a> From<&'a Config> for reply_controller::Config {
     fn from(cfg: &'a Config) -> Self {
-        reply_controller::Config::new(cfg.reply_surbs)
+        reply_controller::Config::new(cfg.reply_surbs, cfg.sphinx_key_rotation)
     }
 }
 
@@ -94,12 +98,13 @@ impl<'a> From<&'a Config> for message_handler::Config {
     fn from(cfg: &'a Config) -> Self {
         message_handler::Config::new(
             Arc::clone(&cfg.ack_key),
-            cfg.self_recipient,
+            cfg.self_recipient.clone(),
             cfg.traffic.average_packet_delay,
             cfg.acks.average_ack_delay,
         )
         .with_custom_primary_packet_size(cfg.traffic.primary_packet_size)
         .with_custom_secondary_packet_size(cfg.traffic.secondary_packet_size)
+        .with_repair_fragments_per_set(cfg.traffic.repair_fragments_per_set)
     }
 }
 
@@ -107,7 +112,7 @@ impl Config {
     pub fn new(
         base_client_debug_config: &config::DebugConfig,
         ack_key: Arc<AckKey>,
-        self_recipient: Recipient,
+        self_recipient: SelfAddress,
     ) -> Self {
         Config {
             ack_key,
@@ -116,6 +121,7 @@ impl Config {
             cover_traffic: base_client_debug_config.cover_traffic,
             acks: base_client_debug_config.acknowledgements,
             reply_surbs: base_client_debug_config.reply_surbs,
+            sphinx_key_rotation: base_client_debug_config.sphinx_key_rotation,
         }
     }
 }
@@ -146,13 +152,14 @@ impl RealMessagesController<OsRng> {
         lane_queue_lengths: LaneQueueLengths,
         client_connection_rx: ConnectionCommandReceiver,
         stats_tx: PacketStatisticsReporter,
+        ack_action_tx: AckActionSender,
+        ack_action_rx: AckActionReceiver,
     ) -> Self {
         let rng = OsRng;
 
         // create channels for inter-task communication
         let (real_message_sender, real_message_receiver) = tokio::sync::mpsc::channel(1);
         let (sent_notifier_tx, sent_notifier_rx) = mpsc::unbounded();
-        let (ack_action_tx, ack_action_rx) = mpsc::unbounded();
         let ack_controller_connectors = AcknowledgementControllerConnectors::new(
             input_receiver,
             sent_notifier_rx,
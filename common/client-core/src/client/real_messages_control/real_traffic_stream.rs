@@ -2,9 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use self::sending_delay_controller::SendingDelayController;
+use crate::client::delivery_status::MessageTrackingId;
 use crate::client::mix_traffic::BatchMixMessageSender;
 use crate::client::packet_statistics_control::{PacketStatisticsEvent, PacketStatisticsReporter};
 use crate::client::real_messages_control::acknowledgement_control::SentPacketNotificationSender;
+use crate::client::self_address::SelfAddress;
 use crate::client::topology_control::TopologyAccessor;
 use crate::client::transmission_buffer::TransmissionBuffer;
 use crate::config;
@@ -12,7 +14,6 @@ use futures::task::{Context, Poll};
 use futures::{Future, Stream, StreamExt};
 use log::*;
 use nym_sphinx::acknowledgements::AckKey;
-use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::chunking::fragment::FragmentIdentifier;
 use nym_sphinx::cover::generate_loop_cover_packet;
 use nym_sphinx::forwarding::packet::MixPacket;
@@ -41,7 +42,7 @@ pub(crate) struct Config {
     ack_key: Arc<AckKey>,
 
     /// Represents full address of this client.
-    our_full_destination: Recipient,
+    our_full_destination: SelfAddress,
 
     /// Average delay an acknowledgement packet is going to get delay at a single mixnode.
     average_ack_delay: Duration,
@@ -57,7 +58,7 @@ pub(crate) struct Config {
 impl Config {
     pub(crate) fn new(
         ack_key: Arc<AckKey>,
-        our_full_destination: Recipient,
+        our_full_destination: SelfAddress,
         average_ack_delay: Duration,
         traffic: config::Traffic,
         cover_traffic_primary_size_ratio: f64,
@@ -123,6 +124,10 @@ where
 pub(crate) struct RealMessage {
     mix_packet: MixPacket,
     fragment_id: Option<FragmentIdentifier>,
+
+    /// Tracking id of a premade message this packet belongs to, if any.
+    /// Note that messages created by the client itself are tracked via their fragment ids instead.
+    tracking_id: Option<MessageTrackingId>,
     // TODO: add info about it being constructed with reply-surb
 }
 
@@ -131,6 +136,7 @@ impl From<PreparedFragment> for RealMessage {
         RealMessage {
             mix_packet: fragment.mix_packet,
             fragment_id: Some(fragment.fragment_identifier),
+            tracking_id: None,
         }
     }
 }
@@ -144,8 +150,15 @@ impl RealMessage {
         RealMessage {
             mix_packet,
             fragment_id,
+            tracking_id: None,
         }
     }
+
+    #[must_use]
+    pub(crate) fn with_tracking_id(mut self, tracking_id: Option<MessageTrackingId>) -> Self {
+        self.tracking_id = tracking_id;
+        self
+    }
 }
 
 // messages are already prepared, etc. the real point of it is to forward it to mix_traffic
@@ -220,7 +233,7 @@ where
     async fn on_message(&mut self, next_message: StreamMessage) {
         trace!("created new message");
 
-        let (next_message, fragment_id, packet_size) = match next_message {
+        let (next_message, fragment_id, tracking_id, packet_size) = match next_message {
             StreamMessage::Cover => {
                 let cover_traffic_packet_size = self.loop_cover_message_size();
                 trace!("the next loop cover message will be put in a {cover_traffic_packet_size} packet");
@@ -229,11 +242,11 @@ where
                 // to wait a really tiny bit before actually obtaining the permit hence messing with our
                 // poisson delay, but is it really a problem?
                 let topology_permit = self.topology_access.get_read_permit().await;
+                let our_address = self.config.our_full_destination.current();
                 // the ack is sent back to ourselves (and then ignored)
-                let topology_ref = match topology_permit.try_get_valid_topology_ref(
-                    &self.config.our_full_destination,
-                    Some(&self.config.our_full_destination),
-                ) {
+                let topology_ref = match topology_permit
+                    .try_get_valid_topology_ref(&our_address, Some(&our_address))
+                {
                     Ok(topology) => topology,
                     Err(err) => {
                         warn!("We're not going to send any loop cover message this time, as the current topology seem to be invalid - {err}");
@@ -246,7 +259,7 @@ where
                         &mut self.rng,
                         topology_ref,
                         &self.config.ack_key,
-                        &self.config.our_full_destination,
+                        &our_address,
                         self.config.average_ack_delay,
                         self.config.traffic.average_packet_delay,
                         cover_traffic_packet_size,
@@ -256,6 +269,7 @@ where
                         "Somehow failed to generate a loop cover message with a valid topology",
                     ),
                     None,
+                    None,
                     cover_traffic_packet_size.size(),
                 )
             }
@@ -264,15 +278,17 @@ where
                 (
                     real_message.mix_packet,
                     real_message.fragment_id,
+                    real_message.tracking_id,
                     packet_size,
                 )
             }
         };
 
-        if let Err(err) = self.mix_tx.send(vec![next_message]).await {
+        let is_real = fragment_id.is_some() || tracking_id.is_some();
+        if let Err(err) = self.mix_tx.send((vec![next_message], tracking_id)).await {
             log::error!("Failed to send: {err}");
         } else {
-            let event = if fragment_id.is_some() {
+            let event = if is_real {
                 PacketStatisticsEvent::RealPacketSent(packet_size)
             } else {
                 PacketStatisticsEvent::CoverPacketSent(packet_size)
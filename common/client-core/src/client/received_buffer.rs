@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::client::{
+    helpers::{get_time_now, Instant},
     packet_statistics_control::{PacketStatisticsEvent, PacketStatisticsReporter},
     replies::{reply_controller::ReplyControllerSender, reply_storage::SentReplyKeys},
 };
-use crate::spawn_future;
+use crate::{config, spawn_future};
 use futures::channel::mpsc;
 use futures::lock::Mutex;
 use futures::StreamExt;
@@ -17,11 +18,15 @@ use nym_sphinx::anonymous_replies::requests::{
     RepliableMessage, RepliableMessageContent, ReplyMessage, ReplyMessageContent,
 };
 use nym_sphinx::anonymous_replies::{encryption_key::EncryptionKeyDigest, SurbEncryptionKey};
+use nym_sphinx::chunking::fragment::Fragment;
+use nym_sphinx::chunking::reconstruction::ReconstructionLimits;
 use nym_sphinx::message::{NymMessage, PlainMessage};
 use nym_sphinx::params::ReplySurbKeyDigestAlgorithm;
 use nym_sphinx::receiver::{MessageReceiver, MessageRecoveryError, ReconstructedMessage};
-use std::collections::HashSet;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
 use std::sync::Arc;
+use std::time::Duration;
 
 // Buffer Requests to say "hey, send any reconstructed messages to this channel"
 // or to say "hey, I'm going offline, don't send anything more to me. Just buffer them instead"
@@ -32,6 +37,61 @@ pub type ReceivedBufferRequestReceiver = mpsc::UnboundedReceiver<ReceivedBufferM
 pub type ReconstructedMessagesSender = mpsc::UnboundedSender<Vec<ReconstructedMessage>>;
 pub type ReconstructedMessagesReceiver = mpsc::UnboundedReceiver<Vec<ReconstructedMessage>>;
 
+/// Ids of recently seen items, such as reconstructed sets, alongside the times they were seen at,
+/// that are remembered for at least the specified retention period.
+struct RecentlySeen<T> {
+    retention: Duration,
+    ids: HashMap<T, Instant>,
+    insertion_order: VecDeque<(Instant, T)>,
+}
+
+// (set id, position within the set, is repair fragment)
+type FragmentKey = (i32, u8, bool);
+
+fn fragment_key(fragment: &Fragment) -> FragmentKey {
+    (
+        fragment.id(),
+        fragment.current_fragment(),
+        fragment.is_repair(),
+    )
+}
+
+impl<T: Hash + Eq + Copy> RecentlySeen<T> {
+    fn new(retention: Duration) -> Self {
+        RecentlySeen {
+            retention,
+            ids: HashMap::new(),
+            insertion_order: VecDeque::new(),
+        }
+    }
+
+    fn remove_expired(&mut self, now: Instant) {
+        while let Some((inserted_at, id)) = self.insertion_order.front().copied() {
+            if now.duration_since(inserted_at) < self.retention {
+                break;
+            }
+            self.insertion_order.pop_front();
+            // make sure the id hasn't been re-inserted in the meantime
+            if self.ids.get(&id) == Some(&inserted_at) {
+                self.ids.remove(&id);
+            }
+        }
+    }
+
+    fn contains(&self, id: &T) -> bool {
+        self.ids.contains_key(id)
+    }
+
+    /// Inserts the id returning whether it has not been present before.
+    fn insert(&mut self, id: T) -> bool {
+        let now = get_time_now();
+        self.remove_expired(now);
+
+        self.insertion_order.push_back((now, id));
+        self.ids.insert(id, now).is_none()
+    }
+}
+
 struct ReceivedMessagesBufferInner<R: MessageReceiver> {
     messages: Vec<ReconstructedMessage>,
     local_encryption_keypair: Arc<encryption::KeyPair>,
@@ -41,10 +101,12 @@ struct ReceivedMessagesBufferInner<R: MessageReceiver> {
     message_receiver: R,
     message_sender: Option<ReconstructedMessagesSender>,
 
-    // TODO: this will get cleared upon re-running the client
-    // but perhaps it should be changed to include timestamps of when the message was reconstructed
-    // and every now and then remove ids older than X
-    recently_reconstructed: HashSet<i32>,
+    // note: this will get cleared upon re-running the client
+    recently_reconstructed: RecentlySeen<i32>,
+
+    // only used if the client is connected to multiple gateways, as then the same fragment
+    // might be delivered through more than a single one of them
+    recently_received_fragments: Option<RecentlySeen<FragmentKey>>,
 
     stats_tx: PacketStatisticsReporter,
 }
@@ -85,8 +147,24 @@ impl<R: MessageReceiver> ReceivedMessagesBufferInner<R> {
             return None;
         }
 
+        if let Some(received_fragments) = &mut self.recently_received_fragments {
+            if !received_fragments.insert(fragment_key(&fragment)) {
+                debug!("Received a duplicate of fragment {} of set {:?}! It probably got here through another gateway", fragment.current_fragment(), fragment.id());
+                return None;
+            }
+        }
+
         // if we returned an error the underlying message is malformed in some way
-        match self.message_receiver.insert_new_fragment(fragment) {
+        let reconstruction_result = self.message_receiver.insert_new_fragment(fragment);
+
+        let dropped_sets = self.message_receiver.reconstructor().take_dropped_sets();
+        if dropped_sets > 0 {
+            warn!("dropped {dropped_sets} incomplete message set(s) that were either stale or exceeded the reconstruction buffer limit");
+            self.stats_tx
+                .report(PacketStatisticsEvent::IncompleteSetsDropped(dropped_sets));
+        }
+
+        match reconstruction_result {
             Err(err) => match err {
                 MessageRecoveryError::MalformedReconstructedMessage { source, used_sets } => {
                     error!("message reconstruction failed - {source}. Attempting to re-use the message sets...");
@@ -160,18 +238,32 @@ struct ReceivedMessagesBuffer<R: MessageReceiver> {
 
 impl<R: MessageReceiver> ReceivedMessagesBuffer<R> {
     fn new(
+        reconstruction_config: config::MessageReconstruction,
         local_encryption_keypair: Arc<encryption::KeyPair>,
         reply_key_storage: SentReplyKeys,
         reply_controller_sender: ReplyControllerSender,
         stats_tx: PacketStatisticsReporter,
+        deduplicate_fragments: bool,
     ) -> Self {
+        let mut message_receiver = R::new();
+        message_receiver
+            .reconstructor()
+            .set_limits(ReconstructionLimits {
+                maximum_set_inactivity: reconstruction_config.maximum_partial_message_inactivity,
+                maximum_buffered_bytes: reconstruction_config.maximum_partial_messages_size,
+            });
+
         ReceivedMessagesBuffer {
             inner: Arc::new(Mutex::new(ReceivedMessagesBufferInner {
                 messages: Vec::new(),
                 local_encryption_keypair,
-                message_receiver: R::new(),
+                message_receiver,
                 message_sender: None,
-                recently_reconstructed: HashSet::new(),
+                recently_reconstructed: RecentlySeen::new(
+                    reconstruction_config.reconstructed_sets_retention,
+                ),
+                recently_received_fragments: deduplicate_fragments
+                    .then(|| RecentlySeen::new(reconstruction_config.reconstructed_sets_retention)),
                 stats_tx,
             })),
             reply_key_storage,
@@ -498,19 +590,24 @@ pub(crate) struct ReceivedMessagesBufferController<R: MessageReceiver> {
 }
 
 impl<R: MessageReceiver + Clone + Send + 'static> ReceivedMessagesBufferController<R> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
+        reconstruction_config: config::MessageReconstruction,
         local_encryption_keypair: Arc<encryption::KeyPair>,
         query_receiver: ReceivedBufferRequestReceiver,
         mixnet_packet_receiver: MixnetMessageReceiver,
         reply_key_storage: SentReplyKeys,
         reply_controller_sender: ReplyControllerSender,
         packet_statistics_reporter: PacketStatisticsReporter,
+        deduplicate_fragments: bool,
     ) -> Self {
         let received_buffer = ReceivedMessagesBuffer::new(
+            reconstruction_config,
             local_encryption_keypair,
             reply_key_storage,
             reply_controller_sender,
             packet_statistics_reporter,
+            deduplicate_fragments,
         );
 
         ReceivedMessagesBufferController {
@@ -543,3 +640,106 @@ impl<R: MessageReceiver + Clone + Send + 'static> ReceivedMessagesBufferControll
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_sphinx::chunking::split_into_sets;
+    use rand::rngs::OsRng;
+    use tokio::time::advance;
+
+    #[tokio::test(start_paused = true)]
+    async fn recently_reconstructed_sets_suppress_duplicates() {
+        let mut recently_reconstructed = RecentlySeen::new(Duration::from_secs(10));
+
+        assert!(!recently_reconstructed.contains(&1));
+        assert!(recently_reconstructed.insert(1));
+        assert!(recently_reconstructed.contains(&1));
+        assert!(!recently_reconstructed.contains(&2));
+
+        // the same set can't be reconstructed twice
+        assert!(!recently_reconstructed.insert(1));
+        assert!(recently_reconstructed.insert(2));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn recently_reconstructed_sets_expire() {
+        let mut recently_reconstructed = RecentlySeen::new(Duration::from_secs(10));
+
+        recently_reconstructed.insert(1);
+        advance(Duration::from_secs(5)).await;
+        recently_reconstructed.insert(2);
+
+        // expired entries are removed upon the next insertion
+        advance(Duration::from_secs(5)).await;
+        assert!(recently_reconstructed.insert(3));
+        assert!(!recently_reconstructed.contains(&1));
+        assert!(recently_reconstructed.contains(&2));
+        assert!(recently_reconstructed.contains(&3));
+
+        // and once forgotten, the id is treated as fresh
+        assert!(recently_reconstructed.insert(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reinserted_sets_are_retained_for_longer() {
+        let mut recently_reconstructed = RecentlySeen::new(Duration::from_secs(10));
+
+        recently_reconstructed.insert(1);
+        advance(Duration::from_secs(5)).await;
+        assert!(!recently_reconstructed.insert(1));
+
+        // the original insertion has expired, but not the more recent one
+        advance(Duration::from_secs(5)).await;
+        recently_reconstructed.insert(2);
+        assert!(recently_reconstructed.contains(&1));
+
+        advance(Duration::from_secs(5)).await;
+        recently_reconstructed.insert(3);
+        assert!(!recently_reconstructed.contains(&1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fragments_delivered_through_multiple_gateways_are_deduplicated() {
+        let mut received_fragments = RecentlySeen::new(Duration::from_secs(10));
+
+        let sets = split_into_sets(&mut OsRng, &[42u8; 5000], 1000);
+        let fragments = sets.into_iter().flatten().collect::<Vec<_>>();
+        assert!(fragments.len() > 1);
+
+        for fragment in &fragments {
+            assert!(received_fragments.insert(fragment_key(fragment)));
+        }
+
+        // the same fragments received through another gateway
+        for fragment in fragments.clone() {
+            let duplicate = Fragment::try_from_bytes(&fragment.into_bytes()).unwrap();
+            assert!(!received_fragments.insert(fragment_key(&duplicate)));
+        }
+
+        // while fragments of a different message are not affected
+        let other = split_into_sets(&mut OsRng, &[42u8; 5000], 1000);
+        for fragment in other.iter().flatten() {
+            assert!(received_fragments.insert(fragment_key(fragment)));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fragment_duplicates_are_only_remembered_for_the_retention_period() {
+        let mut received_fragments = RecentlySeen::new(Duration::from_secs(10));
+
+        let sets = split_into_sets(&mut OsRng, &[42u8; 100], 1000);
+        let fragment = &sets[0][0];
+
+        assert!(received_fragments.insert(fragment_key(fragment)));
+        advance(Duration::from_secs(5)).await;
+        assert!(!received_fragments.insert(fragment_key(fragment)));
+
+        // the most recent delivery keeps it around
+        advance(Duration::from_secs(9)).await;
+        assert!(!received_fragments.insert(fragment_key(fragment)));
+
+        advance(Duration::from_secs(11)).await;
+        assert!(received_fragments.insert(fragment_key(fragment)));
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::client::delivery_status::MessageTrackingId;
 use crate::client::real_messages_control::acknowledgement_control::PendingAcknowledgement;
 use crate::client::real_messages_control::message_handler::{MessageHandler, PreparationError};
 use crate::client::replies::reply_storage::CombinedReplyStorage;
@@ -11,13 +12,14 @@ use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
 use nym_sphinx::anonymous_replies::ReplySurb;
 use nym_sphinx::chunking::fragment::{Fragment, FragmentIdentifier};
+use nym_sphinx::params::SphinxKeyRotationSchedule;
 use nym_task::connections::{ConnectionId, TransmissionLane};
 use rand::{CryptoRng, Rng};
 use std::cmp::{max, min};
 use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Weak};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use time::OffsetDateTime;
 
 use crate::client::helpers::new_interval_stream;
@@ -31,12 +33,17 @@ pub mod requests;
 // plus its not unreasonable to think that we might need something outside config::ReplySurbs struct
 pub struct Config {
     reply_surbs: config::ReplySurbs,
+    sphinx_key_rotation: SphinxKeyRotationSchedule,
 }
 
 impl Config {
-    pub(crate) fn new(reply_surbs_cfg: config::ReplySurbs) -> Self {
+    pub(crate) fn new(
+        reply_surbs_cfg: config::ReplySurbs,
+        sphinx_key_rotation: SphinxKeyRotationSchedule,
+    ) -> Self {
         Self {
             reply_surbs: reply_surbs_cfg,
+            sphinx_key_rotation,
         }
     }
 }
@@ -185,6 +192,7 @@ where
         recipient_tag: AnonymousSenderTag,
         data: Vec<u8>,
         lane: TransmissionLane,
+        tracking_id: Option<MessageTrackingId>,
     ) {
         if !self
             .full_reply_storage
@@ -192,11 +200,18 @@ where
             .contains_surbs_for(&recipient_tag)
         {
             warn!("received reply request for {:?} but we don't have any surbs stored for that recipient!", recipient_tag);
+            if let Some(tracking_id) = tracking_id {
+                self.message_handler.report_failed_message(tracking_id)
+            }
             return;
         }
 
         trace!("handling reply to {:?}", recipient_tag);
         let mut fragments = self.message_handler.split_reply_message(data);
+        if let Some(tracking_id) = tracking_id {
+            let frag_ids = fragments.iter().map(|f| f.fragment_identifier()).collect();
+            self.message_handler.track_message(tracking_id, frag_ids);
+        }
         let total_size = fragments.len();
         trace!("This reply requires {:?} SURBs", total_size);
 
@@ -658,7 +673,11 @@ where
                 recipient,
                 message,
                 lane,
-            } => self.handle_send_reply(recipient, message, lane).await,
+                tracking_id,
+            } => {
+                self.handle_send_reply(recipient, message, lane, tracking_id)
+                    .await
+            }
             ReplyControllerMessage::AdditionalSurbs {
                 sender_tag,
                 reply_surbs,
@@ -778,6 +797,14 @@ where
         }
     }
 
+    /// Checks whether the sphinx keys that were used for constructing reply surbs at the provided time
+    /// have already expired, i.e. whether the surbs are no longer usable.
+    fn sphinx_keys_expired(&self, created_at: OffsetDateTime, now: OffsetDateTime) -> bool {
+        let schedule = &self.config.sphinx_key_rotation;
+        let rotation_id = schedule.rotation_id(created_at.into());
+        SystemTime::from(now) >= schedule.key_expiration(rotation_id)
+    }
+
     async fn invalidate_old_data(&self) {
         let now = OffsetDateTime::now_utc();
 
@@ -807,6 +834,10 @@ where
             if diff > self.config.reply_surbs.maximum_reply_surb_age {
                 info!("it's been {diff:?} since we last received any reply surb from {sender}. Going to remove all stored entries...");
 
+                to_remove_surbs.push(*sender);
+            } else if self.sphinx_keys_expired(last_received_time, now) {
+                info!("the sphinx keys used by the reply surbs from {sender} have expired. Going to remove all stored entries...");
+
                 to_remove_surbs.push(*sender);
             }
         }
@@ -828,6 +859,9 @@ where
             if diff > self.config.reply_surbs.maximum_reply_key_age {
                 debug!("it's been {diff:?} since we created this reply key. it's probably never going to get used, so we're going to purge it...");
                 to_remove_keys.push(*digest);
+            } else if self.sphinx_keys_expired(sent_at, now) {
+                debug!("the sphinx keys used by the reply surbs associated with this reply key have expired, so we're going to purge it...");
+                to_remove_keys.push(*digest);
             }
         }
 
@@ -853,9 +887,11 @@ where
         let polling_rate = Duration::from_secs(5);
         let mut stale_inspection = new_interval_stream(polling_rate);
 
-        // this is in the order of hours/days so we don't have to poll it that often
+        // this is in the order of hours/days so we don't have to poll it that often,
+        // but make sure we'd notice the sphinx keys expiring within their grace period
         let polling_rate =
-            Duration::from_secs(self.config.reply_surbs.maximum_reply_surb_age.as_secs() / 10);
+            Duration::from_secs(self.config.reply_surbs.maximum_reply_surb_age.as_secs() / 10)
+                .min(self.config.sphinx_key_rotation.grace_period);
         let mut invalidation_inspection = new_interval_stream(polling_rate);
 
         while !shutdown.is_shutdown() {
@@ -0,0 +1,42 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_sphinx::addressing::clients::Recipient;
+use tokio::sync::watch;
+
+pub(crate) fn self_address_channel(initial: Recipient) -> (SelfAddressUpdater, SelfAddress) {
+    let (tx, rx) = watch::channel(initial);
+    (SelfAddressUpdater { inner: tx }, SelfAddress { inner: rx })
+}
+
+/// Shared view of the current address of this client.
+/// The address might change during the lifetime of the client as a result of a gateway failover,
+/// since the gateway identity is part of the address.
+#[derive(Clone, Debug)]
+pub struct SelfAddress {
+    inner: watch::Receiver<Recipient>,
+}
+
+impl SelfAddress {
+    /// Returns the current address of this client.
+    pub fn current(&self) -> Recipient {
+        *self.inner.borrow()
+    }
+
+    /// Waits until the address of this client changes and returns the new value.
+    /// Returns `None` if the client has shut down and the address is never going to change again.
+    pub async fn changed(&mut self) -> Option<Recipient> {
+        self.inner.changed().await.ok()?;
+        Some(*self.inner.borrow_and_update())
+    }
+}
+
+pub(crate) struct SelfAddressUpdater {
+    inner: watch::Sender<Recipient>,
+}
+
+impl SelfAddressUpdater {
+    pub(crate) fn update(&self, new_address: Recipient) {
+        self.inner.send_replace(new_address);
+    }
+}
@@ -6,9 +6,11 @@ pub(crate) use accessor::{TopologyAccessor, TopologyReadPermit};
 use futures::StreamExt;
 use log::*;
 use nym_sphinx::addressing::nodes::NodeIdentity;
+use nym_sphinx::params::SphinxKeyRotationSchedule;
 use nym_topology::provider_trait::TopologyProvider;
 use nym_topology::NymTopologyError;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+use time::OffsetDateTime;
 
 #[cfg(not(target_arch = "wasm32"))]
 use tokio::time::sleep;
@@ -23,13 +25,29 @@ pub(crate) mod nym_api_provider;
 // TODO: move it to config later
 const MAX_FAILURE_COUNT: usize = 10;
 
+// give the nodes and the nym-api a moment to announce their new sphinx keys
+const KEY_ROTATION_REFRESH_DELAY: Duration = Duration::from_secs(30);
+
 pub struct TopologyRefresherConfig {
     refresh_rate: Duration,
+    sphinx_key_rotation: SphinxKeyRotationSchedule,
 }
 
 impl TopologyRefresherConfig {
     pub fn new(refresh_rate: Duration) -> Self {
-        TopologyRefresherConfig { refresh_rate }
+        TopologyRefresherConfig {
+            refresh_rate,
+            sphinx_key_rotation: Default::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_sphinx_key_rotation(
+        mut self,
+        sphinx_key_rotation: SphinxKeyRotationSchedule,
+    ) -> Self {
+        self.sphinx_key_rotation = sphinx_key_rotation;
+        self
     }
 }
 
@@ -38,6 +56,7 @@ pub struct TopologyRefresher {
     topology_accessor: TopologyAccessor,
 
     refresh_rate: Duration,
+    sphinx_key_rotation: SphinxKeyRotationSchedule,
     consecutive_failure_count: usize,
 }
 
@@ -51,6 +70,7 @@ impl TopologyRefresher {
             topology_provider,
             topology_accessor,
             refresh_rate: cfg.refresh_rate,
+            sphinx_key_rotation: cfg.sphinx_key_rotation,
             consecutive_failure_count: 0,
         }
     }
@@ -89,6 +109,13 @@ impl TopologyRefresher {
             .await;
     }
 
+    /// Returns the amount of time until the topology should get refreshed due to the nodes rotating their sphinx keys.
+    fn until_key_rotation_refresh(&self) -> Duration {
+        // note: `SystemTime::now()` is not available in wasm
+        let now = SystemTime::from(OffsetDateTime::now_utc());
+        self.sphinx_key_rotation.until_next_rotation(now) + KEY_ROTATION_REFRESH_DELAY
+    }
+
     pub async fn ensure_topology_is_routable(&self) -> Result<(), NymTopologyError> {
         self.topology_accessor.ensure_is_routable().await
     }
@@ -159,6 +186,10 @@ impl TopologyRefresher {
                     _ = interval.next() => {
                         self.try_refresh().await;
                     },
+                    _ = sleep(self.until_key_rotation_refresh()) => {
+                        debug!("the sphinx keys got rotated - refreshing the topology");
+                        self.try_refresh().await;
+                    },
                     _ = shutdown.recv() => {
                         log::trace!("TopologyRefresher: Received shutdown");
                     },
@@ -4,10 +4,14 @@
 use async_trait::async_trait;
 use log::{debug, error, warn};
 use nym_topology::provider_trait::TopologyProvider;
+use nym_topology::route_selection::RouteSelectionWeights;
 use nym_topology::{NymTopology, NymTopologyError};
+use nym_validator_client::nym_nodes::SkimmedNode;
 use nym_validator_client::UserAgent;
 use rand::prelude::SliceRandom;
 use rand::thread_rng;
+use std::collections::HashMap;
+use std::time::Duration;
 use url::Url;
 
 // the same values as our current (10.06.24) blacklist
@@ -17,6 +21,9 @@ pub const DEFAULT_MIN_GATEWAY_PERFORMANCE: u8 = 50;
 pub(crate) struct Config {
     pub(crate) min_mixnode_performance: u8,
     pub(crate) min_gateway_performance: u8,
+
+    // if specified, the mixnodes are weighted by their latency and performance when constructing routes
+    pub(crate) maximum_selection_bias: Option<f64>,
 }
 
 impl Default for Config {
@@ -25,6 +32,7 @@ impl Default for Config {
         Config {
             min_mixnode_performance: DEFAULT_MIN_MIXNODE_PERFORMANCE,
             min_gateway_performance: DEFAULT_MIN_GATEWAY_PERFORMANCE,
+            maximum_selection_bias: None,
         }
     }
 }
@@ -95,6 +103,48 @@ impl NymApiTopologyProvider {
         active_topology.ensure_even_layer_distribution(lower_threshold, upper_threshold)
     }
 
+    async fn get_route_selection_weights(
+        &self,
+        mixnodes: &[SkimmedNode],
+        maximum_selection_bias: f64,
+    ) -> Option<RouteSelectionWeights> {
+        let latency = match self.validator_client.get_mixnodes_latency().await {
+            Err(err) => {
+                warn!("failed to get network latency - {err}. Mixnodes are going to be chosen uniformly");
+                return None;
+            }
+            Ok(latency) => latency,
+        };
+
+        let mix_ids = mixnodes
+            .iter()
+            .map(|node| (node.ed25519_identity_pubkey.as_str(), node.node_id))
+            .collect::<HashMap<_, _>>();
+
+        let mut weights = RouteSelectionWeights::new(maximum_selection_bias);
+        for node in mixnodes {
+            weights.set_node_performance(
+                node.node_id,
+                node.performance.round_to_integer() as f64 / 100.,
+            );
+        }
+        for node in latency.nodes {
+            if let Some(&mix_id) = mix_ids.get(node.node_identity.as_str()) {
+                weights.set_node_latency(mix_id, Duration::from_micros(node.median_rtt_micros))
+            }
+        }
+        for link in latency.links {
+            if let (Some(&first), Some(&second)) = (
+                mix_ids.get(link.first_node_identity.as_str()),
+                mix_ids.get(link.second_node_identity.as_str()),
+            ) {
+                weights.set_link_latency(first, second, Duration::from_micros(link.mean_rtt_micros))
+            }
+        }
+
+        Some(weights)
+    }
+
     async fn get_current_compatible_topology(&mut self) -> Option<NymTopology> {
         let mixnodes = match self
             .validator_client
@@ -126,7 +176,7 @@ impl NymApiTopologyProvider {
             gateways.len()
         );
 
-        let topology = NymTopology::from_unordered(
+        let mut topology = NymTopology::from_unordered(
             mixnodes.iter().filter(|m| {
                 m.performance.round_to_integer() >= self.config.min_mixnode_performance
             }),
@@ -138,10 +188,19 @@ impl NymApiTopologyProvider {
         if let Err(err) = self.check_layer_distribution(&topology) {
             warn!("The current filtered active topology has extremely skewed layer distribution. It cannot be used: {err}");
             self.use_next_nym_api();
-            None
-        } else {
-            Some(topology)
+            return None;
         }
+
+        if let Some(maximum_selection_bias) = self.config.maximum_selection_bias {
+            if let Some(weights) = self
+                .get_route_selection_weights(&mixnodes, maximum_selection_bias)
+                .await
+            {
+                topology.set_route_selection_weights(weights)
+            }
+        }
+
+        Some(topology)
     }
 }
 
@@ -4,6 +4,7 @@
 use crate::client::mix_traffic::transceiver::ErasedGatewayError;
 use nym_crypto::asymmetric::identity::Ed25519RecoveryError;
 use nym_gateway_client::error::GatewayClientError;
+use nym_sphinx::addressing::clients::Recipient;
 use nym_topology::gateway::GatewayConversionError;
 use nym_topology::NymTopologyError;
 use nym_validator_client::ValidatorClientError;
@@ -94,6 +95,9 @@ pub enum ClientCoreError {
     #[error("failed to register receiver for reconstructed mixnet messages")]
     FailedToRegisterReceiver,
 
+    #[error("failed to register receiver for delivery status of tracked messages")]
+    FailedToRegisterDeliveryStatusReceiver,
+
     #[error("unexpected exit")]
     UnexpectedExit,
 
@@ -217,4 +221,11 @@ pub enum ClientCoreStatusMessage {
     // NOTE: The nym-connect frontend listens for these strings, so don't change them until we have a more robust mechanism in place
     #[error("The connected gateway is very slow, or the connection to it is very slow")]
     GatewayIsVerySlow,
+
+    #[error("Switched from gateway {previous_gateway} to {new_gateway}. The new address of this client is {new_address}")]
+    GatewaySwitched {
+        previous_gateway: String,
+        new_gateway: String,
+        new_address: Recipient,
+    },
 }
@@ -185,6 +185,19 @@ impl<C, St> GatewayClient<C, St> {
         self.bandwidth_remaining
     }
 
+    /// Removes the bandwidth controller from this client so that it could be reused
+    /// by a client connected to a different gateway.
+    pub fn take_bandwidth_controller(&mut self) -> Option<BandwidthController<C, St>> {
+        self.bandwidth_controller.take()
+    }
+
+    pub fn set_bandwidth_controller(
+        &mut self,
+        bandwidth_controller: Option<BandwidthController<C, St>>,
+    ) {
+        self.bandwidth_controller = bandwidth_controller;
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     async fn _close_connection(&mut self) -> Result<(), GatewayClientError> {
         match std::mem::replace(&mut self.connection, SocketState::NotConnected) {
@@ -10,17 +10,22 @@ use crate::{
 };
 use nym_api_requests::coconut::models::FreePassNonceResponse;
 use nym_api_requests::coconut::{
-    BlindSignRequestBody, BlindedSignatureResponse, FreePassRequest, VerifyCredentialBody,
-    VerifyCredentialResponse,
+    BlindSignRequestBody, BlindedSignatureResponse, FreePassRequest, SpentCredentialStatusResponse,
+    SpentCredentialsBatch, SpentCredentialsFilterResponse, VerifyCredentialBody,
+    VerifyCredentialResponse, VerifyCredentialsBatchBody,
 };
 use nym_api_requests::models::{DescribedGateway, MixNodeBondAnnotated};
 use nym_api_requests::models::{
     GatewayCoreStatusResponse, MixnodeCoreStatusResponse, MixnodeStatusResponse,
     RewardEstimationResponse, StakeSaturationResponse,
 };
-use nym_api_requests::nym_nodes::SkimmedNode;
+use nym_api_requests::nym_nodes::{
+    FullFatNode, NetworkLatency, PaginatedCachedNodesResponse, SemiSkimmedNode, SkimmedNode,
+};
+use nym_coconut_dkg_common::types::EpochId;
 use nym_http_api_client::UserAgent;
 use nym_network_defaults::NymNetworkDetails;
+use std::future::Future;
 use url::Url;
 
 pub use crate::nym_api::NymApiClientExt;
@@ -221,6 +226,77 @@ impl<C, S> Client<C, S> {
         Ok(self.nym_api.get_rewarded_mixnodes_detailed().await?)
     }
 
+    // keep retrieving subsequent pages until we get all the nodes
+    async fn get_all_nym_nodes_pages<T, F, Fut>(
+        mut get_page: F,
+    ) -> Result<Vec<T>, ValidatorClientError>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<PaginatedCachedNodesResponse<T>, nym_api::error::NymAPIError>>,
+    {
+        let mut page = 0;
+        let mut nodes = Vec::new();
+
+        loop {
+            let res = get_page(page).await?.nodes;
+            let received = res.data.len();
+            nodes.extend(res.data);
+
+            if received == 0 || nodes.len() >= res.pagination.total {
+                return Ok(nodes);
+            }
+            page += 1;
+        }
+    }
+
+    pub async fn get_all_semi_skimmed_mixnodes(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<Vec<SemiSkimmedNode>, ValidatorClientError> {
+        Self::get_all_nym_nodes_pages(|page| {
+            self.nym_api
+                .get_semi_skimmed_mixnodes(semver_compatibility.clone(), Some(page), None)
+        })
+        .await
+    }
+
+    pub async fn get_all_semi_skimmed_gateways(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<Vec<SemiSkimmedNode>, ValidatorClientError> {
+        Self::get_all_nym_nodes_pages(|page| {
+            self.nym_api
+                .get_semi_skimmed_gateways(semver_compatibility.clone(), Some(page), None)
+        })
+        .await
+    }
+
+    pub async fn get_all_full_fat_mixnodes(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<Vec<FullFatNode>, ValidatorClientError> {
+        Self::get_all_nym_nodes_pages(|page| {
+            self.nym_api
+                .get_full_fat_mixnodes(semver_compatibility.clone(), Some(page), None)
+        })
+        .await
+    }
+
+    pub async fn get_all_full_fat_gateways(
+        &self,
+        semver_compatibility: Option<String>,
+    ) -> Result<Vec<FullFatNode>, ValidatorClientError> {
+        Self::get_all_nym_nodes_pages(|page| {
+            self.nym_api
+                .get_full_fat_gateways(semver_compatibility.clone(), Some(page), None)
+        })
+        .await
+    }
+
+    pub async fn get_mixnodes_latency(&self) -> Result<NetworkLatency, ValidatorClientError> {
+        Ok(self.nym_api.get_mixnodes_latency().await?.latency)
+    }
+
     pub async fn get_cached_active_mixnodes(
         &self,
     ) -> Result<Vec<MixNodeDetails>, ValidatorClientError> {
@@ -299,6 +375,10 @@ impl NymApiClient {
             .nodes)
     }
 
+    pub async fn get_mixnodes_latency(&self) -> Result<NetworkLatency, ValidatorClientError> {
+        Ok(self.nym_api.get_mixnodes_latency().await?.latency)
+    }
+
     pub async fn get_cached_active_mixnodes(
         &self,
     ) -> Result<Vec<MixNodeDetails>, ValidatorClientError> {
@@ -385,6 +465,40 @@ impl NymApiClient {
             .await?)
     }
 
+    pub async fn verify_bandwidth_credentials_batch(
+        &self,
+        request_body: &VerifyCredentialsBatchBody,
+    ) -> Result<VerifyCredentialResponse, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .verify_bandwidth_credentials_batch(request_body)
+            .await?)
+    }
+
+    pub async fn spent_credentials_filter(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<SpentCredentialsFilterResponse, ValidatorClientError> {
+        Ok(self.nym_api.spent_credentials_filter(epoch_id).await?)
+    }
+
+    pub async fn spent_credential_status(
+        &self,
+        blinded_serial_number_bs58: &str,
+    ) -> Result<SpentCredentialStatusResponse, ValidatorClientError> {
+        Ok(self
+            .nym_api
+            .spent_credential_status(blinded_serial_number_bs58)
+            .await?)
+    }
+
+    pub async fn submit_spent_credentials(
+        &self,
+        batch: &SpentCredentialsBatch,
+    ) -> Result<(), ValidatorClientError> {
+        Ok(self.nym_api.submit_spent_credentials(batch).await?)
+    }
+
     pub async fn free_pass_nonce(&self) -> Result<FreePassNonceResponse, ValidatorClientError> {
         Ok(self.nym_api.free_pass_nonce().await?)
     }
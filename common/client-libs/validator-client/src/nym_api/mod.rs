@@ -11,7 +11,8 @@ pub use nym_api_requests::{
             IssuedCredentialResponse, IssuedCredentialsResponse,
         },
         BlindSignRequestBody, BlindedSignatureResponse, CredentialsRequestBody,
-        VerifyCredentialBody, VerifyCredentialResponse,
+        SpentCredentialStatusResponse, SpentCredentialsBatch, SpentCredentialsFilterResponse,
+        VerifyCredentialBody, VerifyCredentialResponse, VerifyCredentialsBatchBody,
     },
     models::{
         ComputeRewardEstParam, DescribedGateway, GatewayBondAnnotated, GatewayCoreStatusResponse,
@@ -25,13 +26,17 @@ pub use nym_coconut_dkg_common::types::EpochId;
 use nym_http_api_client::{ApiClient, NO_PARAMS};
 use nym_mixnet_contract_common::mixnode::MixNodeDetails;
 use nym_mixnet_contract_common::{GatewayBond, IdentityKeyRef, MixId};
+use serde::de::DeserializeOwned;
 
 pub mod error;
 pub mod routes;
 
 use nym_api_requests::coconut::models::FreePassNonceResponse;
 use nym_api_requests::coconut::FreePassRequest;
-use nym_api_requests::nym_nodes::{CachedNodesResponse, SkimmedNode};
+use nym_api_requests::nym_nodes::{
+    CachedNetworkLatencyResponse, CachedNodesResponse, FullFatNode, PaginatedCachedNodesResponse,
+    SemiSkimmedNode, SkimmedNode,
+};
 pub use nym_http_api_client::Client;
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -142,6 +147,110 @@ pub trait NymApiClientExt: ApiClient {
         .await
     }
 
+    async fn get_mixnodes_latency(&self) -> Result<CachedNetworkLatencyResponse, NymAPIError> {
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                "mixnodes",
+                "latency",
+            ],
+            NO_PARAMS,
+        )
+        .await
+    }
+
+    async fn get_nym_nodes_page<T>(
+        &self,
+        role_path: &str,
+        tier: &str,
+        semver_compatibility: Option<String>,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<PaginatedCachedNodesResponse<T>, NymAPIError>
+    where
+        T: DeserializeOwned,
+    {
+        let page = page.map(|page| page.to_string());
+        let per_page = per_page.map(|per_page| per_page.to_string());
+
+        let mut params = Vec::new();
+        if let Some(semver_compatibility) = &semver_compatibility {
+            params.push(("semver_compatibility", semver_compatibility.as_str()))
+        }
+        if let Some(page) = &page {
+            params.push(("page", page.as_str()))
+        }
+        if let Some(per_page) = &per_page {
+            params.push(("per_page", per_page.as_str()))
+        }
+
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                "unstable",
+                "nym-nodes",
+                role_path,
+                tier,
+            ],
+            &params,
+        )
+        .await
+    }
+
+    async fn get_semi_skimmed_mixnodes(
+        &self,
+        semver_compatibility: Option<String>,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<PaginatedCachedNodesResponse<SemiSkimmedNode>, NymAPIError> {
+        self.get_nym_nodes_page(
+            "mixnodes",
+            "semi-skimmed",
+            semver_compatibility,
+            page,
+            per_page,
+        )
+        .await
+    }
+
+    async fn get_semi_skimmed_gateways(
+        &self,
+        semver_compatibility: Option<String>,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<PaginatedCachedNodesResponse<SemiSkimmedNode>, NymAPIError> {
+        self.get_nym_nodes_page(
+            "gateways",
+            "semi-skimmed",
+            semver_compatibility,
+            page,
+            per_page,
+        )
+        .await
+    }
+
+    async fn get_full_fat_mixnodes(
+        &self,
+        semver_compatibility: Option<String>,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<PaginatedCachedNodesResponse<FullFatNode>, NymAPIError> {
+        self.get_nym_nodes_page("mixnodes", "full-fat", semver_compatibility, page, per_page)
+            .await
+    }
+
+    async fn get_full_fat_gateways(
+        &self,
+        semver_compatibility: Option<String>,
+        page: Option<u32>,
+        per_page: Option<u32>,
+    ) -> Result<PaginatedCachedNodesResponse<FullFatNode>, NymAPIError> {
+        self.get_nym_nodes_page("gateways", "full-fat", semver_compatibility, page, per_page)
+            .await
+    }
+
     async fn get_active_mixnodes(&self) -> Result<Vec<MixNodeDetails>, NymAPIError> {
         self.get_json(
             &[routes::API_VERSION, routes::MIXNODES, routes::ACTIVE],
@@ -484,6 +593,23 @@ pub trait NymApiClientExt: ApiClient {
         .await
     }
 
+    async fn verify_bandwidth_credentials_batch(
+        &self,
+        request_body: &VerifyCredentialsBatchBody,
+    ) -> Result<VerifyCredentialResponse, NymAPIError> {
+        self.post_json(
+            &[
+                routes::API_VERSION,
+                routes::COCONUT_ROUTES,
+                routes::BANDWIDTH,
+                routes::COCONUT_VERIFY_BANDWIDTH_CREDENTIALS_BATCH,
+            ],
+            NO_PARAMS,
+            request_body,
+        )
+        .await
+    }
+
     async fn epoch_credentials(
         &self,
         dkg_epoch: EpochId,
@@ -537,6 +663,57 @@ pub trait NymApiClientExt: ApiClient {
         )
         .await
     }
+
+    async fn spent_credentials_filter(
+        &self,
+        dkg_epoch: EpochId,
+    ) -> Result<SpentCredentialsFilterResponse, NymAPIError> {
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                routes::COCONUT_ROUTES,
+                routes::BANDWIDTH,
+                routes::COCONUT_SPENT_CREDENTIALS_FILTER,
+                &dkg_epoch.to_string(),
+            ],
+            NO_PARAMS,
+        )
+        .await
+    }
+
+    async fn spent_credential_status(
+        &self,
+        blinded_serial_number_bs58: &str,
+    ) -> Result<SpentCredentialStatusResponse, NymAPIError> {
+        self.get_json(
+            &[
+                routes::API_VERSION,
+                routes::COCONUT_ROUTES,
+                routes::BANDWIDTH,
+                routes::COCONUT_SPENT_CREDENTIAL,
+                blinded_serial_number_bs58,
+            ],
+            NO_PARAMS,
+        )
+        .await
+    }
+
+    async fn submit_spent_credentials(
+        &self,
+        batch: &SpentCredentialsBatch,
+    ) -> Result<(), NymAPIError> {
+        self.post_json(
+            &[
+                routes::API_VERSION,
+                routes::COCONUT_ROUTES,
+                routes::BANDWIDTH,
+                routes::COCONUT_SPENT_CREDENTIALS,
+            ],
+            NO_PARAMS,
+            batch,
+        )
+        .await
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -19,9 +19,13 @@ pub const COCONUT_FREE_PASS: &str = "free-pass";
 pub const COCONUT_FREE_PASS_NONCE: &str = "free-pass-nonce";
 pub const COCONUT_BLIND_SIGN: &str = "blind-sign";
 pub const COCONUT_VERIFY_BANDWIDTH_CREDENTIAL: &str = "verify-bandwidth-credential";
+pub const COCONUT_VERIFY_BANDWIDTH_CREDENTIALS_BATCH: &str = "verify-bandwidth-credentials-batch";
 pub const COCONUT_EPOCH_CREDENTIALS: &str = "epoch-credentials";
 pub const COCONUT_ISSUED_CREDENTIAL: &str = "issued-credential";
 pub const COCONUT_ISSUED_CREDENTIALS: &str = "issued-credentials";
+pub const COCONUT_SPENT_CREDENTIALS: &str = "spent-credentials";
+pub const COCONUT_SPENT_CREDENTIALS_FILTER: &str = "spent-credentials-filter";
+pub const COCONUT_SPENT_CREDENTIAL: &str = "spent-credential";
 
 pub const STATUS_ROUTES: &str = "status";
 pub const MIXNODE: &str = "mixnode";
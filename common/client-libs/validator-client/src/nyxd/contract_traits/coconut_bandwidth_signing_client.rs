@@ -7,7 +7,9 @@ use crate::nyxd::error::NyxdError;
 use crate::nyxd::{Coin, Fee, SigningCosmWasmClient};
 use crate::signing::signer::OfflineSigner;
 use async_trait::async_trait;
-use nym_coconut_bandwidth_contract_common::spend_credential::SpendCredentialData;
+use nym_coconut_bandwidth_contract_common::spend_credential::{
+    BatchedSpendCredential, SpendCredentialData, SpendCredentialsBatchData,
+};
 use nym_coconut_bandwidth_contract_common::{
     deposit::DepositData, msg::ExecuteMsg as CoconutBandwidthExecuteMsg,
 };
@@ -66,6 +68,24 @@ pub trait CoconutBandwidthSigningClient {
         .await
     }
 
+    async fn spend_credentials_batch(
+        &self,
+        credentials: Vec<BatchedSpendCredential>,
+        gateway_cosmos_address: String,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        let req = CoconutBandwidthExecuteMsg::SpendCredentialsBatch {
+            data: SpendCredentialsBatchData::new(credentials, gateway_cosmos_address),
+        };
+        self.execute_coconut_bandwidth_contract(
+            fee,
+            req,
+            "CoconutBandwidth::SpendCredentialsBatch".to_string(),
+            vec![],
+        )
+        .await
+    }
+
     async fn release_funds(
         &self,
         amount: Coin,
@@ -145,6 +165,13 @@ mod tests {
                     None,
                 )
                 .ignore(),
+            CoconutBandwidthExecuteMsg::SpendCredentialsBatch { data } => client
+                .spend_credentials_batch(
+                    data.credentials().to_vec(),
+                    data.gateway_cosmos_address().to_string(),
+                    None,
+                )
+                .ignore(),
             CoconutBandwidthExecuteMsg::ReleaseFunds { funds } => {
                 client.release_funds(funds.into(), None).ignore()
             }
@@ -17,7 +17,9 @@ use nym_mixnet_contract_common::{
         PagedUnbondedMixnodesResponse, StakeSaturationResponse, UnbondedMixnodeResponse,
     },
     reward_params::{Performance, RewardingParams},
-    rewarding::{EstimatedCurrentEpochRewardResponse, PendingRewardResponse},
+    rewarding::{
+        EstimatedCurrentEpochRewardResponse, PendingRewardResponse, RewardCompoundingResponse,
+    },
     ContractBuildInformation, ContractState, ContractStateParams, CurrentIntervalResponse,
     Delegation, EpochEventId, EpochStatus, FamilyByHeadResponse, FamilyByLabelResponse,
     FamilyMembersByHeadResponse, FamilyMembersByLabelResponse, GatewayBond, GatewayBondResponse,
@@ -367,6 +369,26 @@ pub trait MixnetQueryClient {
         .await
     }
 
+    async fn get_operator_reward_compounding(
+        &self,
+        mix_id: MixId,
+    ) -> Result<RewardCompoundingResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetOperatorRewardCompounding { mix_id })
+            .await
+    }
+
+    async fn get_delegator_reward_compounding(
+        &self,
+        delegator: &AccountId,
+        mix_id: MixId,
+    ) -> Result<RewardCompoundingResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetDelegatorRewardCompounding {
+            address: delegator.to_string(),
+            mix_id,
+        })
+        .await
+    }
+
     // given the provided performance, estimate the reward at the end of the current epoch
     async fn get_estimated_current_epoch_operator_reward(
         &self,
@@ -703,6 +725,12 @@ mod tests {
             } => client
                 .get_pending_delegator_reward(&address.parse().unwrap(), mix_id, proxy)
                 .ignore(),
+            MixnetQueryMsg::GetOperatorRewardCompounding { mix_id } => {
+                client.get_operator_reward_compounding(mix_id).ignore()
+            }
+            MixnetQueryMsg::GetDelegatorRewardCompounding { address, mix_id } => client
+                .get_delegator_reward_compounding(&address.parse().unwrap(), mix_id)
+                .ignore(),
             MixnetQueryMsg::GetEstimatedCurrentEpochOperatorReward {
                 mix_id,
                 estimated_performance,
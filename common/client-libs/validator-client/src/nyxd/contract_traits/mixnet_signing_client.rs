@@ -614,6 +614,46 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn redelegate_from_mixnode(
+        &self,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::RedelegateFromMixnode {
+                from_mix_id,
+                to_mix_id,
+                amount: amount.into(),
+            },
+            vec![],
+        )
+        .await
+    }
+
+    async fn redelegate_from_mixnode_on_behalf(
+        &self,
+        delegate: AccountId,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::RedelegateFromMixnodeOnBehalf {
+                from_mix_id,
+                to_mix_id,
+                amount: amount.into(),
+                delegate: delegate.to_string(),
+            },
+            vec![],
+        )
+        .await
+    }
+
     // reward-related
 
     async fn reward_mixnode(
@@ -683,6 +723,33 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn set_operator_reward_compounding(
+        &self,
+        enabled: bool,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::SetOperatorRewardCompounding { enabled },
+            vec![],
+        )
+        .await
+    }
+
+    async fn set_delegator_reward_compounding(
+        &self,
+        mix_id: MixId,
+        enabled: bool,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::SetDelegatorRewardCompounding { mix_id, enabled },
+            vec![],
+        )
+        .await
+    }
+
     #[cfg(feature = "contract-testing")]
     async fn testing_resolve_all_pending_events(
         &self,
@@ -912,6 +979,27 @@ mod tests {
             MixnetExecuteMsg::UndelegateFromMixnodeOnBehalf { mix_id, delegate } => client
                 .undelegate_to_mixnode_on_behalf(delegate.parse().unwrap(), mix_id, None)
                 .ignore(),
+            MixnetExecuteMsg::RedelegateFromMixnode {
+                from_mix_id,
+                to_mix_id,
+                amount,
+            } => client
+                .redelegate_from_mixnode(from_mix_id, to_mix_id, amount.into(), None)
+                .ignore(),
+            MixnetExecuteMsg::RedelegateFromMixnodeOnBehalf {
+                from_mix_id,
+                to_mix_id,
+                amount,
+                delegate,
+            } => client
+                .redelegate_from_mixnode_on_behalf(
+                    delegate.parse().unwrap(),
+                    from_mix_id,
+                    to_mix_id,
+                    amount.into(),
+                    None,
+                )
+                .ignore(),
             MixnetExecuteMsg::RewardMixnode {
                 mix_id,
                 performance,
@@ -928,6 +1016,12 @@ mod tests {
             MixnetExecuteMsg::WithdrawDelegatorRewardOnBehalf { mix_id, owner } => client
                 .withdraw_delegator_reward_on_behalf(owner.parse().unwrap(), mix_id, None)
                 .ignore(),
+            MixnetExecuteMsg::SetOperatorRewardCompounding { enabled } => client
+                .set_operator_reward_compounding(enabled, None)
+                .ignore(),
+            MixnetExecuteMsg::SetDelegatorRewardCompounding { mix_id, enabled } => client
+                .set_delegator_reward_compounding(mix_id, enabled, None)
+                .ignore(),
 
             #[cfg(feature = "contract-testing")]
             MixnetExecuteMsg::TestingResolveAllPendingEvents { .. } => {
@@ -220,6 +220,29 @@ pub trait VestingSigningClient {
         .await
     }
 
+    async fn vesting_track_redelegation(
+        &self,
+        address: &str,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        moved_entire_delegation: bool,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_vesting_contract(
+            fee,
+            VestingExecuteMsg::TrackRedelegation {
+                owner: address.to_string(),
+                from_mix_id,
+                to_mix_id,
+                amount: amount.into(),
+                moved_entire_delegation,
+            },
+            vec![],
+        )
+        .await
+    }
+
     async fn vesting_delegate_to_mixnode(
         &self,
         mix_id: MixId,
@@ -256,6 +279,27 @@ pub trait VestingSigningClient {
         .await
     }
 
+    async fn vesting_redelegate_from_mixnode(
+        &self,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        on_behalf_of: Option<String>,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_vesting_contract(
+            fee,
+            VestingExecuteMsg::RedelegateFromMixnode {
+                from_mix_id,
+                to_mix_id,
+                amount: amount.into(),
+                on_behalf_of,
+            },
+            vec![],
+        )
+        .await
+    }
+
     async fn create_periodic_vesting_account(
         &self,
         owner_address: &str,
@@ -491,6 +535,20 @@ mod tests {
             } => client
                 .vesting_undelegate_from_mixnode(mix_id, on_behalf_of, None)
                 .ignore(),
+            VestingExecuteMsg::RedelegateFromMixnode {
+                from_mix_id,
+                to_mix_id,
+                amount,
+                on_behalf_of,
+            } => client
+                .vesting_redelegate_from_mixnode(
+                    from_mix_id,
+                    to_mix_id,
+                    amount.into(),
+                    on_behalf_of,
+                    None,
+                )
+                .ignore(),
             VestingExecuteMsg::CreateAccount {
                 owner_address,
                 staking_address,
@@ -516,6 +574,22 @@ mod tests {
             } => client
                 .vesting_track_undelegation(&owner, mix_id, amount.into(), None)
                 .ignore(),
+            VestingExecuteMsg::TrackRedelegation {
+                owner,
+                from_mix_id,
+                to_mix_id,
+                amount,
+                moved_entire_delegation,
+            } => client
+                .vesting_track_redelegation(
+                    &owner,
+                    from_mix_id,
+                    to_mix_id,
+                    amount.into(),
+                    moved_entire_delegation,
+                    None,
+                )
+                .ignore(),
             VestingExecuteMsg::BondMixnode {
                 mix_node,
                 cost_params,
@@ -8,6 +8,7 @@ pub mod rewards;
 pub mod delegate_to_mixnode;
 pub mod delegate_to_multiple_mixnodes;
 pub mod query_for_delegations;
+pub mod redelegate_from_mixnode;
 pub mod undelegate_from_mixnode;
 pub mod vesting_delegate_to_mixnode;
 pub mod vesting_undelegate_from_mixnode;
@@ -31,6 +32,8 @@ pub enum MixnetDelegatorsCommands {
     DelegateMulti(delegate_to_multiple_mixnodes::Args),
     /// Undelegate from a mixnode
     Undelegate(undelegate_from_mixnode::Args),
+    /// Move (part of) a delegation, alongside its rewards, from one mixnode to another
+    Redelegate(redelegate_from_mixnode::Args),
     /// Delegate to a mixnode with locked tokens
     DelegateVesting(vesting_delegate_to_mixnode::Args),
     /// Undelegate from a mixnode (when originally using locked tokens)
@@ -0,0 +1,67 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::SigningClient;
+use clap::Parser;
+use log::info;
+use nym_mixnet_contract_common::{Coin, MixId};
+use nym_validator_client::nyxd::contract_traits::{MixnetQueryClient, MixnetSigningClient};
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(long)]
+    pub from_mix_id: Option<MixId>,
+
+    #[clap(long)]
+    pub from_identity_key: Option<String>,
+
+    #[clap(long)]
+    pub to_mix_id: Option<MixId>,
+
+    #[clap(long)]
+    pub to_identity_key: Option<String>,
+
+    /// Amount to move. If it exceeds the value of the delegation (including its rewards),
+    /// the entire delegation is moved instead.
+    #[clap(long)]
+    pub amount: u128,
+}
+
+async fn resolve_mix_id(
+    client: &SigningClient,
+    mix_id: Option<MixId>,
+    identity_key: Option<String>,
+) -> MixId {
+    match mix_id {
+        Some(mix_id) => mix_id,
+        None => {
+            let identity_key =
+                identity_key.expect("either mix_id or mix_identity has to be specified");
+            let node_details = client
+                .get_mixnode_details_by_identity(identity_key)
+                .await
+                .expect("contract query failed")
+                .mixnode_details
+                .expect("mixnode with the specified identity doesnt exist");
+            node_details.mix_id()
+        }
+    }
+}
+
+pub async fn redelegate_from_mixnode(args: Args, client: SigningClient) {
+    let denom = client.current_chain_details().mix_denom.base.as_str();
+
+    info!("Starting redelegation between mixnodes");
+
+    let from_mix_id = resolve_mix_id(&client, args.from_mix_id, args.from_identity_key).await;
+    let to_mix_id = resolve_mix_id(&client, args.to_mix_id, args.to_identity_key).await;
+
+    let coin = Coin::new(args.amount, denom);
+
+    let res = client
+        .redelegate_from_mixnode(from_mix_id, to_mix_id, coin.into(), None)
+        .await
+        .expect("failed to redelegate from mixnode!");
+
+    info!("redelegating from mixnode: {:?}", res);
+}
@@ -0,0 +1,69 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::SigningClient;
+use clap::Parser;
+use log::info;
+use nym_mixnet_contract_common::MixId;
+use nym_validator_client::nyxd::contract_traits::{MixnetQueryClient, MixnetSigningClient};
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(long)]
+    pub mix_id: Option<MixId>,
+
+    #[clap(long)]
+    pub identity_key: Option<String>,
+
+    /// Automatically add the rewards to the delegation at the end of every interval.
+    /// Note that this doesn't increase the yield as unclaimed rewards already earn rewards on their own
+    #[clap(long, conflicts_with = "disable")]
+    pub enable: bool,
+
+    /// Stop compounding the rewards automatically
+    #[clap(long)]
+    pub disable: bool,
+}
+
+pub async fn delegator_reward_compounding(args: Args, client: SigningClient) {
+    let mix_id = match args.mix_id {
+        Some(mix_id) => mix_id,
+        None => {
+            let identity_key = args
+                .identity_key
+                .expect("either mix_id or mix_identity has to be specified");
+            let node_details = client
+                .get_mixnode_details_by_identity(identity_key)
+                .await
+                .expect("contract query failed")
+                .mixnode_details
+                .expect("mixnode with the specified identity doesnt exist");
+            node_details.mix_id()
+        }
+    };
+
+    if !args.enable && !args.disable {
+        let res = client
+            .get_delegator_reward_compounding(&client.address(), mix_id)
+            .await
+            .expect("failed to query for delegator reward compounding");
+        info!(
+            "Automatic compounding of the rewards of the delegation towards mixnode {mix_id} is {}",
+            if res.compounding_enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        return;
+    }
+
+    info!("Setting delegator reward compounding");
+
+    let res = client
+        .set_delegator_reward_compounding(mix_id, args.enable, None)
+        .await
+        .expect("failed to set delegator reward compounding");
+
+    info!("Setting delegator reward compounding: {:?}", res)
+}
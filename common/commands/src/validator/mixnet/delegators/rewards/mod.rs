@@ -4,6 +4,7 @@
 use clap::{Args, Subcommand};
 
 pub mod claim_delegator_reward;
+pub mod delegator_reward_compounding;
 pub mod vesting_claim_delegator_reward;
 
 #[derive(Debug, Args)]
@@ -19,4 +20,6 @@ pub enum MixnetDelegatorsRewardCommands {
     Claim(claim_delegator_reward::Args),
     /// Claim rewards accumulated during the delegation of locked tokens
     VestingClaim(vesting_claim_delegator_reward::Args),
+    /// Show or change whether rewards of the delegation of unlocked tokens are automatically compounded
+    Compounding(delegator_reward_compounding::Args),
 }
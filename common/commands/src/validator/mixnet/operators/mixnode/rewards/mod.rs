@@ -4,6 +4,7 @@
 use clap::{Args, Subcommand};
 
 pub mod claim_operator_reward;
+pub mod operator_reward_compounding;
 pub mod vesting_claim_operator_reward;
 
 #[derive(Debug, Args)]
@@ -19,4 +20,6 @@ pub enum MixnetOperatorsMixnodeRewardsCommands {
     Claim(claim_operator_reward::Args),
     /// Claim rewards for a mixnode bonded with locked tokens
     VestingClaim(vesting_claim_operator_reward::Args),
+    /// Show or change whether rewards are automatically added to the pledge
+    Compounding(operator_reward_compounding::Args),
 }
@@ -0,0 +1,59 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::SigningClient;
+use clap::Parser;
+use log::info;
+use nym_validator_client::nyxd::contract_traits::{MixnetQueryClient, MixnetSigningClient};
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// Automatically add the rewards to the pledge at the end of every interval.
+    /// Note that this doesn't increase the yield as unclaimed rewards already earn rewards on their own
+    #[clap(long, conflicts_with = "disable")]
+    pub enable: bool,
+
+    /// Stop compounding the rewards automatically
+    #[clap(long)]
+    pub disable: bool,
+}
+
+pub async fn operator_reward_compounding(args: Args, client: SigningClient) {
+    if !args.enable && !args.disable {
+        let mix_id = match client
+            .get_owned_mixnode(&client.address())
+            .await
+            .expect("failed to query the chain for mixnode details")
+            .mixnode_details
+        {
+            Some(details) => details.mix_id(),
+            None => {
+                log::warn!("this operator does not own a mixnode");
+                return;
+            }
+        };
+
+        let res = client
+            .get_operator_reward_compounding(mix_id)
+            .await
+            .expect("failed to query for operator reward compounding");
+        info!(
+            "Automatic compounding of the operator rewards of mixnode {mix_id} is {}",
+            if res.compounding_enabled {
+                "enabled"
+            } else {
+                "disabled"
+            }
+        );
+        return;
+    }
+
+    info!("Setting operator reward compounding");
+
+    let res = client
+        .set_operator_reward_compounding(args.enable, None)
+        .await
+        .expect("failed to set operator reward compounding");
+
+    info!("Setting operator reward compounding: {:?}", res)
+}
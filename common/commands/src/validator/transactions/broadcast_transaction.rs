@@ -0,0 +1,56 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::QueryClient;
+use anyhow::{bail, Context};
+use clap::Parser;
+use log::info;
+use nym_validator_client::nyxd::CosmWasmClient;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(
+        value_parser,
+        help = "base64-encoded signed transaction, as produced by `tx sign`"
+    )]
+    pub signed_transaction: Option<String>,
+
+    #[clap(
+        long,
+        conflicts_with = "signed_transaction",
+        help = "Path to the file containing the signed transaction"
+    )]
+    pub input: Option<PathBuf>,
+}
+
+pub async fn broadcast(args: Args, client: &QueryClient) -> anyhow::Result<()> {
+    let encoded = match (args.signed_transaction, args.input) {
+        (Some(encoded), _) => encoded,
+        (None, Some(path)) => fs::read_to_string(&path).with_context(|| {
+            format!("failed to read signed transaction from {}", path.display())
+        })?,
+        (None, None) => bail!("either the signed transaction or the --input file must be provided"),
+    };
+
+    let tx_bytes =
+        base64::decode(encoded.trim()).context("the signed transaction is not valid base64")?;
+
+    info!("Broadcasting the transaction...");
+    let res = client.broadcast_tx(tx_bytes, None, None).await?;
+
+    info!("Broadcast result: {}", json!(res));
+
+    println!();
+    println!(
+        "Nodesguru: https://nym.explorers.guru/transaction/{}",
+        &res.hash
+    );
+    println!("Mintscan: https://www.mintscan.io/nyx/txs/{}", &res.hash);
+    println!("Transaction result code: {}", &res.tx_result.code.value());
+    println!("Transaction hash: {}", &res.hash);
+
+    Ok(())
+}
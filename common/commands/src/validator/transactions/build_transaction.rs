@@ -0,0 +1,310 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::create_query_client;
+use crate::validator::mixnet::delegators::rewards::{
+    claim_delegator_reward, vesting_claim_delegator_reward,
+};
+use crate::validator::mixnet::delegators::{
+    delegate_to_mixnode, undelegate_from_mixnode, vesting_delegate_to_mixnode,
+    vesting_undelegate_from_mixnode,
+};
+use crate::validator::mixnet::operators::mixnode::rewards::claim_operator_reward;
+use crate::validator::mixnet::operators::mixnode::{decrease_pledge, pledge_more, unbond_mixnode};
+use crate::validator::transactions::unsigned_transaction::{
+    write_output, TransactionFee, UnsignedTransaction,
+};
+use crate::validator::vesting::withdraw_vested;
+use anyhow::anyhow;
+use clap::{Parser, Subcommand};
+use cosmrs::bank::MsgSend;
+use cosmrs::cosmwasm::MsgExecuteContract;
+use cosmrs::tx::Msg;
+use nym_mixnet_contract_common::{Coin as CosmWasmCoin, ExecuteMsg as MixnetExecuteMsg, MixId};
+use nym_network_defaults::NymNetworkDetails;
+use nym_validator_client::nyxd::contract_traits::MixnetQueryClient;
+use nym_validator_client::nyxd::{AccountId, Any, Coin, CosmWasmClient, Gas, GasPrice};
+use nym_vesting_contract_common::ExecuteMsg as VestingExecuteMsg;
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(
+        long,
+        help = "Address of the account that is going to sign the transaction"
+    )]
+    pub from: AccountId,
+
+    #[clap(
+        long,
+        help = "Gas limit of the transaction. The fee is derived from it using the network gas price"
+    )]
+    pub gas_limit: Gas,
+
+    #[clap(
+        long,
+        help = "Chain id of the network. If not provided, it is queried from the nyxd endpoint"
+    )]
+    pub chain_id: Option<String>,
+
+    #[clap(long)]
+    pub memo: Option<String>,
+
+    #[clap(
+        long,
+        help = "File to write the unsigned transaction to. If not provided, it is printed to stdout"
+    )]
+    pub output: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    pub message: TransactionMessage,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum TransactionMessage {
+    /// Transfer tokens to another account
+    Send(SendArgs),
+    /// Execute a message on the mixnet contract
+    #[clap(subcommand)]
+    Mixnet(MixnetTransaction),
+    /// Execute a message on the vesting contract
+    #[clap(subcommand)]
+    Vesting(VestingTransaction),
+}
+
+#[derive(Debug, Parser)]
+pub struct SendArgs {
+    #[clap(value_parser, help = "The recipient account address")]
+    pub recipient: AccountId,
+
+    #[clap(
+        value_parser,
+        help = "Amount to transfer in micro denomination (e.g. unym or unyx)"
+    )]
+    pub amount: u128,
+
+    #[clap(long, help = "Override the denomination")]
+    pub denom: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MixnetTransaction {
+    /// Delegate to a mixnode
+    Delegate(delegate_to_mixnode::Args),
+    /// Remove a delegation from a mixnode
+    Undelegate(undelegate_from_mixnode::Args),
+    /// Claim rewards accumulated by a delegation
+    ClaimDelegatorReward(claim_delegator_reward::Args),
+    /// Claim rewards accumulated by the operated mixnode
+    ClaimOperatorReward(claim_operator_reward::Args),
+    /// Pledge more tokens to the operated mixnode
+    PledgeMore(pledge_more::Args),
+    /// Decrease the pledge of the operated mixnode
+    DecreasePledge(decrease_pledge::Args),
+    /// Unbond the operated mixnode
+    UnbondMixnode(unbond_mixnode::Args),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum VestingTransaction {
+    /// Delegate to a mixnode with locked tokens
+    Delegate(vesting_delegate_to_mixnode::Args),
+    /// Remove a delegation made with locked tokens from a mixnode
+    Undelegate(vesting_undelegate_from_mixnode::Args),
+    /// Claim rewards accumulated by a delegation made with locked tokens
+    ClaimDelegatorReward(vesting_claim_delegator_reward::Args),
+    /// Withdraw vested tokens to the main account
+    WithdrawVested(withdraw_vested::Args),
+}
+
+// note: the mixnode is only looked up on chain if its id wasn't provided explicitly
+async fn resolve_mix_id(
+    mix_id: Option<MixId>,
+    identity_key: Option<&String>,
+    network_details: &NymNetworkDetails,
+) -> anyhow::Result<MixId> {
+    if let Some(mix_id) = mix_id {
+        return Ok(mix_id);
+    }
+
+    let identity_key =
+        identity_key.ok_or_else(|| anyhow!("either mix_id or identity_key has to be specified"))?;
+    create_query_client(network_details)?
+        .get_mixnode_details_by_identity(identity_key.clone())
+        .await?
+        .mixnode_details
+        .map(|details| details.mix_id())
+        .ok_or_else(|| anyhow!("mixnode with identity {identity_key} doesn't exist"))
+}
+
+impl MixnetTransaction {
+    async fn execute_msg(
+        &self,
+        network_details: &NymNetworkDetails,
+    ) -> anyhow::Result<(MixnetExecuteMsg, Vec<Coin>)> {
+        let denom = &network_details.chain_details.mix_denom.base;
+
+        Ok(match self {
+            MixnetTransaction::Delegate(args) => {
+                let mix_id =
+                    resolve_mix_id(args.mix_id, args.identity_key.as_ref(), network_details)
+                        .await?;
+                (
+                    MixnetExecuteMsg::DelegateToMixnode { mix_id },
+                    vec![Coin::new(args.amount, denom)],
+                )
+            }
+            MixnetTransaction::Undelegate(args) => {
+                let mix_id =
+                    resolve_mix_id(args.mix_id, args.identity_key.as_ref(), network_details)
+                        .await?;
+                (
+                    MixnetExecuteMsg::UndelegateFromMixnode { mix_id },
+                    Vec::new(),
+                )
+            }
+            MixnetTransaction::ClaimDelegatorReward(args) => {
+                let mix_id =
+                    resolve_mix_id(args.mix_id, args.identity_key.as_ref(), network_details)
+                        .await?;
+                (
+                    MixnetExecuteMsg::WithdrawDelegatorReward { mix_id },
+                    Vec::new(),
+                )
+            }
+            MixnetTransaction::ClaimOperatorReward(_) => {
+                (MixnetExecuteMsg::WithdrawOperatorReward {}, Vec::new())
+            }
+            MixnetTransaction::PledgeMore(args) => (
+                MixnetExecuteMsg::PledgeMore {},
+                vec![Coin::new(args.amount, denom)],
+            ),
+            MixnetTransaction::DecreasePledge(args) => (
+                MixnetExecuteMsg::DecreasePledge {
+                    decrease_by: CosmWasmCoin::new(args.decrease_by, denom),
+                },
+                Vec::new(),
+            ),
+            MixnetTransaction::UnbondMixnode(_) => (MixnetExecuteMsg::UnbondMixnode {}, Vec::new()),
+        })
+    }
+}
+
+impl VestingTransaction {
+    async fn execute_msg(
+        &self,
+        network_details: &NymNetworkDetails,
+    ) -> anyhow::Result<VestingExecuteMsg> {
+        let denom = &network_details.chain_details.mix_denom.base;
+
+        Ok(match self {
+            VestingTransaction::Delegate(args) => VestingExecuteMsg::DelegateToMixnode {
+                mix_id: resolve_mix_id(args.mix_id, args.identity_key.as_ref(), network_details)
+                    .await?,
+                amount: CosmWasmCoin::new(args.amount, denom),
+                on_behalf_of: args.on_behalf_of.clone(),
+            },
+            VestingTransaction::Undelegate(args) => VestingExecuteMsg::UndelegateFromMixnode {
+                mix_id: resolve_mix_id(args.mix_id, args.identity_key.as_ref(), network_details)
+                    .await?,
+                on_behalf_of: args.on_behalf_of.clone(),
+            },
+            VestingTransaction::ClaimDelegatorReward(args) => {
+                VestingExecuteMsg::ClaimDelegatorReward {
+                    mix_id: resolve_mix_id(
+                        args.mix_id,
+                        args.identity_key.as_ref(),
+                        network_details,
+                    )
+                    .await?,
+                }
+            }
+            VestingTransaction::WithdrawVested(args) => VestingExecuteMsg::WithdrawVestedCoins {
+                amount: CosmWasmCoin::new(args.amount, denom),
+            },
+        })
+    }
+}
+
+fn contract_address(address: Option<&String>, contract: &str) -> anyhow::Result<AccountId> {
+    address
+        .ok_or_else(|| anyhow!("{contract} contract address is not set"))?
+        .parse()
+        .map_err(|err| anyhow!("invalid {contract} contract address: {err}"))
+}
+
+fn execute_contract_message<M: Serialize>(
+    sender: &AccountId,
+    contract: AccountId,
+    msg: &M,
+    funds: Vec<Coin>,
+) -> anyhow::Result<Any> {
+    MsgExecuteContract {
+        sender: sender.clone(),
+        contract,
+        msg: serde_json::to_vec(msg)?,
+        funds: funds.into_iter().map(Into::into).collect(),
+    }
+    .to_any()
+    .map_err(|err| anyhow!("failed to encode MsgExecuteContract: {err}"))
+}
+
+pub async fn build(args: Args, network_details: &NymNetworkDetails) -> anyhow::Result<()> {
+    let denom = &network_details.chain_details.mix_denom.base;
+
+    let message = match &args.message {
+        TransactionMessage::Send(send) => {
+            let denom = send.denom.as_deref().unwrap_or(denom);
+            MsgSend {
+                from_address: args.from.clone(),
+                to_address: send.recipient.clone(),
+                amount: vec![Coin::new(send.amount, denom).into()],
+            }
+            .to_any()
+            .map_err(|err| anyhow!("failed to encode MsgSend: {err}"))?
+        }
+        TransactionMessage::Mixnet(transaction) => {
+            let contract = contract_address(
+                network_details.contracts.mixnet_contract_address.as_ref(),
+                "mixnet",
+            )?;
+            let (msg, funds) = transaction.execute_msg(network_details).await?;
+            execute_contract_message(&args.from, contract, &msg, funds)?
+        }
+        TransactionMessage::Vesting(transaction) => {
+            let contract = contract_address(
+                network_details.contracts.vesting_contract_address.as_ref(),
+                "vesting",
+            )?;
+            let msg = transaction.execute_msg(network_details).await?;
+            execute_contract_message(&args.from, contract, &msg, Vec::new())?
+        }
+    };
+
+    let chain_id = match args.chain_id {
+        Some(chain_id) => chain_id,
+        None => create_query_client(network_details)?
+            .get_chain_id()
+            .await?
+            .to_string(),
+    };
+
+    let gas_price = GasPrice::try_from(network_details)?;
+    let fee = TransactionFee {
+        amount: vec![(&gas_price * args.gas_limit).into()],
+        gas_limit: args.gas_limit,
+    };
+
+    let unsigned = UnsignedTransaction {
+        signer: args.from.to_string(),
+        chain_id,
+        messages: vec![message.into()],
+        memo: args
+            .memo
+            .unwrap_or_else(|| "Transaction built with nym-cli".to_owned()),
+        fee,
+    };
+
+    write_output(&serde_json::to_string_pretty(&unsigned)?, args.output)
+}
@@ -3,8 +3,12 @@
 
 use clap::{Args, Subcommand};
 
+pub mod broadcast_transaction;
+pub mod build_transaction;
 pub mod get_transaction;
 pub mod query_transactions;
+pub mod sign_transaction;
+pub mod unsigned_transaction;
 
 #[derive(Debug, Args)]
 #[clap(args_conflicts_with_subcommands = true, subcommand_required = true)]
@@ -19,4 +23,10 @@ pub enum TransactionsCommands {
     Get(crate::validator::transactions::get_transaction::Args),
     /// Query for transactions
     Query(crate::validator::transactions::query_transactions::Args),
+    /// Build an unsigned transaction that can be signed on another machine
+    Build(crate::validator::transactions::build_transaction::Args),
+    /// Sign a transaction created with `build` without connecting to the network
+    Sign(crate::validator::transactions::sign_transaction::Args),
+    /// Broadcast a transaction signed with `sign`
+    Broadcast(crate::validator::transactions::broadcast_transaction::Args),
 }
@@ -0,0 +1,145 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator::transactions::unsigned_transaction::{write_output, UnsignedTransaction};
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use cosmrs::tx::{AccountNumber, SequenceNumber};
+use nym_validator_client::signing::direct_wallet::DirectSecp256k1HdWallet;
+use nym_validator_client::signing::tx_signer::TxSigner;
+use nym_validator_client::signing::SignerData;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+pub struct Args {
+    #[clap(
+        value_parser,
+        help = "Path to the unsigned transaction created with `tx build`"
+    )]
+    pub unsigned_transaction: PathBuf,
+
+    #[clap(long, help = "Account number of the signer")]
+    pub account_number: AccountNumber,
+
+    #[clap(long, help = "Current sequence number of the signer")]
+    pub sequence: SequenceNumber,
+
+    #[clap(
+        long,
+        help = "File to write the signed transaction to. If not provided, it is printed to stdout"
+    )]
+    pub output: Option<PathBuf>,
+}
+
+// note: this function does not perform any network requests so that it could be used on an air-gapped machine
+pub fn sign(args: Args, prefix: &str, mnemonic: Option<bip39::Mnemonic>) -> anyhow::Result<()> {
+    let Some(mnemonic) = mnemonic else {
+        bail!(
+            "Please provide the mnemonic as an argument or using the MNEMONIC environment variable"
+        )
+    };
+
+    let unsigned = UnsignedTransaction::load(&args.unsigned_transaction)?;
+    let wallet = DirectSecp256k1HdWallet::from_mnemonic(prefix, mnemonic);
+    let tx_bytes = sign_transaction(&unsigned, &wallet, args.account_number, args.sequence)?;
+
+    write_output(&base64::encode(tx_bytes), args.output)
+}
+
+/// Signs the provided transaction with the account of the wallet matching its signer
+/// and returns the raw bytes of the signed transaction, ready to get broadcast.
+pub fn sign_transaction(
+    unsigned: &UnsignedTransaction,
+    wallet: &DirectSecp256k1HdWallet,
+    account_number: AccountNumber,
+    sequence: SequenceNumber,
+) -> anyhow::Result<Vec<u8>> {
+    let signer = unsigned.signer()?;
+    let chain_id = unsigned
+        .chain_id
+        .parse()
+        .map_err(|err| anyhow!("invalid chain id '{}': {err}", unsigned.chain_id))?;
+    let signer_data = SignerData::new(account_number, sequence, chain_id);
+
+    let signed = wallet
+        .sign_direct(
+            &signer,
+            unsigned.decoded_messages()?,
+            (&unsigned.fee).into(),
+            unsigned.memo.clone(),
+            signer_data,
+        )
+        .map_err(|err| anyhow!("failed to sign the transaction: {err}"))?;
+
+    signed
+        .to_bytes()
+        .map_err(|err| anyhow!("failed to serialize the signed transaction: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::transactions::unsigned_transaction::TransactionFee;
+    use cosmrs::bank::MsgSend;
+    use cosmrs::tx::{Msg, SignerPublicKey, Tx};
+    use nym_validator_client::nyxd::Coin;
+
+    const TEST_MNEMONIC: &str = "crush minute paddle tobacco message debate cabin peace bar jacket execute twenty winner view sure mask popular couch penalty fragile demise fresh pizza stove";
+
+    #[test]
+    fn signed_transaction_decodes_to_the_built_one() {
+        let wallet = DirectSecp256k1HdWallet::from_mnemonic("n", TEST_MNEMONIC.parse().unwrap());
+        let account = wallet.try_derive_accounts().unwrap().pop().unwrap();
+
+        let message = MsgSend {
+            from_address: account.address().clone(),
+            to_address: "n1q85lscptz860j3dx92f8phaeaw08j2l5dt7adq".parse().unwrap(),
+            amount: vec![Coin::new(1234, "unym").into()],
+        }
+        .to_any()
+        .unwrap();
+
+        let unsigned = UnsignedTransaction {
+            signer: account.address().to_string(),
+            chain_id: "nyx".to_string(),
+            messages: vec![message.clone().into()],
+            memo: "offline".to_string(),
+            fee: TransactionFee {
+                amount: vec![Coin::new(5000, "unym")],
+                gas_limit: 200_000,
+            },
+        };
+
+        let tx_bytes = sign_transaction(&unsigned, &wallet, 42, 7).unwrap();
+        let decoded = Tx::from_bytes(&tx_bytes).unwrap();
+
+        assert_eq!(decoded.body.messages, vec![message]);
+        assert_eq!(decoded.body.memo, "offline");
+        assert_eq!(decoded.auth_info.fee, (&unsigned.fee).into());
+        assert_eq!(decoded.signatures.len(), 1);
+
+        let signer_info = &decoded.auth_info.signer_infos[0];
+        assert_eq!(signer_info.sequence, 7);
+        assert_eq!(
+            signer_info.public_key,
+            Some(SignerPublicKey::Single(account.public_key()))
+        );
+    }
+
+    #[test]
+    fn signing_with_a_different_account_fails() {
+        let wallet = DirectSecp256k1HdWallet::from_mnemonic("n", TEST_MNEMONIC.parse().unwrap());
+        let unsigned = UnsignedTransaction {
+            signer: "n1q85lscptz860j3dx92f8phaeaw08j2l5dt7adq".to_string(),
+            chain_id: "nyx".to_string(),
+            messages: vec![],
+            memo: String::new(),
+            fee: TransactionFee {
+                amount: vec![],
+                gas_limit: 200_000,
+            },
+        };
+
+        assert!(sign_transaction(&unsigned, &wallet, 42, 7).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Context;
+use cosmrs::tx;
+use nym_validator_client::nyxd::{AccountId, Any, Coin, Gas};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Everything required to construct the `SignDoc` of a transaction apart from the data
+/// that depends on the state of the signer's account, i.e. its account number, sequence
+/// and public key. Those are only provided at signing time, which allows the document
+/// to be created on an online machine and signed on an offline one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub signer: String,
+    pub chain_id: String,
+    pub messages: Vec<EncodedMessage>,
+    pub memo: String,
+    pub fee: TransactionFee,
+}
+
+/// Protobuf-encoded transaction message, i.e. a serializable representation of `Any`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodedMessage {
+    pub type_url: String,
+
+    /// base64-encoded protobuf message
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionFee {
+    pub amount: Vec<Coin>,
+    pub gas_limit: Gas,
+}
+
+impl From<Any> for EncodedMessage {
+    fn from(message: Any) -> Self {
+        EncodedMessage {
+            type_url: message.type_url,
+            value: base64::encode(message.value),
+        }
+    }
+}
+
+impl TryFrom<&EncodedMessage> for Any {
+    type Error = anyhow::Error;
+
+    fn try_from(message: &EncodedMessage) -> Result<Self, Self::Error> {
+        Ok(Any {
+            type_url: message.type_url.clone(),
+            value: base64::decode(&message.value)
+                .with_context(|| format!("malformed '{}' message", message.type_url))?,
+        })
+    }
+}
+
+impl From<&TransactionFee> for tx::Fee {
+    fn from(fee: &TransactionFee) -> Self {
+        tx::Fee {
+            amount: fee.amount.iter().cloned().map(Into::into).collect(),
+            gas_limit: fee.gas_limit,
+            payer: None,
+            granter: None,
+        }
+    }
+}
+
+impl UnsignedTransaction {
+    pub fn signer(&self) -> anyhow::Result<AccountId> {
+        self.signer
+            .parse()
+            .map_err(|err| anyhow::anyhow!("invalid signer address '{}': {err}", self.signer))
+    }
+
+    pub fn decoded_messages(&self) -> anyhow::Result<Vec<Any>> {
+        self.messages.iter().map(TryInto::try_into).collect()
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let raw = fs::read_to_string(path).with_context(|| {
+            format!(
+                "failed to read unsigned transaction from {}",
+                path.display()
+            )
+        })?;
+        serde_json::from_str(&raw).context("failed to deserialize the unsigned transaction")
+    }
+}
+
+/// Writes the provided content to the specified file or to stdout if no file was given.
+pub(crate) fn write_output<P: AsRef<Path>>(content: &str, output: Option<P>) -> anyhow::Result<()> {
+    match output {
+        Some(path) => {
+            let path = path.as_ref();
+            fs::write(path, content)
+                .with_context(|| format!("failed to write output to {}", path.display()))?;
+            eprintln!("Output written to {}", path.display());
+        }
+        None => println!("{content}"),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmrs::bank::MsgSend;
+    use cosmrs::tx::Msg;
+
+    #[test]
+    fn unsigned_transaction_survives_serde_roundtrip() {
+        let message = MsgSend {
+            from_address: "n1q85lscptz860j3dx92f8phaeaw08j2l5dt7adq".parse().unwrap(),
+            to_address: "n1q85lscptz860j3dx92f8phaeaw08j2l5dt7adq".parse().unwrap(),
+            amount: vec![Coin::new(1234, "unym").into()],
+        }
+        .to_any()
+        .unwrap();
+
+        let unsigned = UnsignedTransaction {
+            signer: "n1q85lscptz860j3dx92f8phaeaw08j2l5dt7adq".to_string(),
+            chain_id: "nyx".to_string(),
+            messages: vec![message.clone().into()],
+            memo: "offline".to_string(),
+            fee: TransactionFee {
+                amount: vec![Coin::new(5000, "unym")],
+                gas_limit: 200_000,
+            },
+        };
+
+        let serialized = serde_json::to_string(&unsigned).unwrap();
+        let deserialized: UnsignedTransaction = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized.signer().unwrap(), unsigned.signer().unwrap());
+        assert_eq!(deserialized.chain_id, unsigned.chain_id);
+        assert_eq!(deserialized.memo, unsigned.memo);
+        assert_eq!(deserialized.fee.amount, unsigned.fee.amount);
+        assert_eq!(deserialized.fee.gas_limit, unsigned.fee.gas_limit);
+        assert_eq!(deserialized.decoded_messages().unwrap(), vec![message]);
+    }
+
+    #[test]
+    fn malformed_messages_are_rejected() {
+        let message = EncodedMessage {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+            value: "definitely not base64!".to_string(),
+        };
+
+        assert!(Any::try_from(&message).is_err());
+    }
+}
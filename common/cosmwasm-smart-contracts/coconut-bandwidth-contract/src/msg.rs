@@ -1,7 +1,8 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{deposit::DepositData, spend_credential::SpendCredentialData};
+use crate::deposit::DepositData;
+use crate::spend_credential::{SpendCredentialData, SpendCredentialsBatchData};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Coin;
 
@@ -21,6 +22,7 @@ pub struct InstantiateMsg {
 pub enum ExecuteMsg {
     DepositFunds { data: DepositData },
     SpendCredential { data: SpendCredentialData },
+    SpendCredentialsBatch { data: SpendCredentialsBatchData },
     ReleaseFunds { funds: Coin },
 }
 
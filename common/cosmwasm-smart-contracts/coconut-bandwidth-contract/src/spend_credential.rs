@@ -7,6 +7,15 @@ use nym_multisig_contract_common::msg::ExecuteMsg as MultisigExecuteMsg;
 
 use crate::msg::ExecuteMsg;
 
+/// Maximum number of credentials that can be included in a single batch spend request.
+pub const MAX_SPEND_CREDENTIALS_BATCH_SIZE: usize = 100;
+
+/// Prefix of the description of proposals created for batches of spent credentials,
+/// so that they could be told apart from the proposals created for individual credentials.
+pub const BATCH_PROPOSAL_DESCRIPTION_PREFIX: &str = "batch:";
+
+const BATCH_PROPOSAL_SERIAL_NUMBERS_SEPARATOR: char = ',';
+
 #[cw_serde]
 pub struct SpendCredentialData {
     funds: Coin,
@@ -36,6 +45,52 @@ impl SpendCredentialData {
     }
 }
 
+#[cw_serde]
+pub struct BatchedSpendCredential {
+    funds: Coin,
+    blinded_serial_number: String,
+}
+
+impl BatchedSpendCredential {
+    pub fn new(funds: Coin, blinded_serial_number: String) -> Self {
+        BatchedSpendCredential {
+            funds,
+            blinded_serial_number,
+        }
+    }
+
+    pub fn funds(&self) -> &Coin {
+        &self.funds
+    }
+
+    pub fn blinded_serial_number(&self) -> &str {
+        &self.blinded_serial_number
+    }
+}
+
+#[cw_serde]
+pub struct SpendCredentialsBatchData {
+    credentials: Vec<BatchedSpendCredential>,
+    gateway_cosmos_address: String,
+}
+
+impl SpendCredentialsBatchData {
+    pub fn new(credentials: Vec<BatchedSpendCredential>, gateway_cosmos_address: String) -> Self {
+        SpendCredentialsBatchData {
+            credentials,
+            gateway_cosmos_address,
+        }
+    }
+
+    pub fn credentials(&self) -> &[BatchedSpendCredential] {
+        &self.credentials
+    }
+
+    pub fn gateway_cosmos_address(&self) -> &str {
+        &self.gateway_cosmos_address
+    }
+}
+
 #[cw_serde]
 #[derive(Copy)]
 pub enum SpendCredentialStatus {
@@ -137,6 +192,60 @@ pub fn to_cosmos_msg(
     Ok(msg)
 }
 
+/// Creates the description of the proposal releasing the funds of the provided batch of credentials.
+pub fn batch_proposal_description<S: AsRef<str>>(blinded_serial_numbers: &[S]) -> String {
+    let serials = blinded_serial_numbers
+        .iter()
+        .map(|serial| serial.as_ref())
+        .collect::<Vec<_>>()
+        .join(&BATCH_PROPOSAL_SERIAL_NUMBERS_SEPARATOR.to_string());
+    format!("{BATCH_PROPOSAL_DESCRIPTION_PREFIX}{serials}")
+}
+
+/// Attempts to extract the blinded serial numbers out of the description of the proposal
+/// releasing the funds of a batch of credentials.
+pub fn serial_numbers_from_batch_proposal_description(description: &str) -> Option<Vec<String>> {
+    let serials = description.strip_prefix(BATCH_PROPOSAL_DESCRIPTION_PREFIX)?;
+    if serials.is_empty() {
+        return None;
+    }
+    Some(
+        serials
+            .split(BATCH_PROPOSAL_SERIAL_NUMBERS_SEPARATOR)
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+pub fn to_batch_cosmos_msg(
+    total_funds: Coin,
+    blinded_serial_numbers: &[String],
+    coconut_bandwidth_addr: String,
+    multisig_addr: String,
+) -> StdResult<CosmosMsg> {
+    let release_funds_req = ExecuteMsg::ReleaseFunds { funds: total_funds };
+    let release_funds_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: coconut_bandwidth_addr,
+        msg: to_binary(&release_funds_req)?,
+        funds: vec![],
+    });
+    let req = MultisigExecuteMsg::Propose {
+        title: String::from(
+            "Release funds of a batch of credentials, as ordered by Coconut Bandwidth Contract",
+        ),
+        description: batch_proposal_description(blinded_serial_numbers),
+        msgs: vec![release_funds_msg],
+        latest: None,
+    };
+    let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: multisig_addr,
+        msg: to_binary(&req)?,
+        funds: vec![],
+    });
+
+    Ok(msg)
+}
+
 pub fn funds_from_cosmos_msgs(msgs: Vec<CosmosMsg>) -> Option<Coin> {
     if let Some(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: _,
@@ -138,6 +138,15 @@ pub enum MixnetContractError {
         proxy: Option<String>,
     },
 
+    #[error("Attempted to redelegate tokens from mixnode {mix_id} back to itself")]
+    RedelegationToSameMixnode { mix_id: MixId },
+
+    #[error("Rewards of mixnode {mix_id} can't be compounded automatically as it has been bonded with vesting tokens")]
+    CompoundingVestingPledge { mix_id: MixId },
+
+    #[error("Mixnode {mix_id} already has the maximum number of delegations ({limit}) with automatic reward compounding enabled")]
+    TooManyCompoundingDelegations { mix_id: MixId, limit: u32 },
+
     #[error("Provided message to update rewarding params did not contain any updates")]
     EmptyParamsChangeMsg,
 
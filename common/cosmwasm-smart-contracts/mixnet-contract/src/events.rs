@@ -28,15 +28,22 @@ pub enum MixnetEventType {
     MixnodeRewarding,
     WithdrawDelegatorReward,
     WithdrawOperatorReward,
+    OperatorRewardCompoundingUpdate,
+    DelegatorRewardCompoundingUpdate,
+    CompoundOperatorReward,
+    CompoundDelegatorReward,
     PendingActiveSetUpdate,
     ActiveSetUpdate,
     PendingIntervalRewardingParamsUpdate,
     IntervalRewardingParamsUpdate,
     PendingDelegation,
     PendingUndelegation,
+    PendingRedelegation,
     Delegation,
     DelegationOnUnbonding,
     Undelegation,
+    Redelegation,
+    RedelegationOnUnbonding,
     ContractSettingsUpdate,
     RewardingValidatorUpdate,
     BeginEpochTransition,
@@ -73,6 +80,14 @@ impl Display for MixnetEventType {
             MixnetEventType::MixnodeRewarding => "mix_rewarding",
             MixnetEventType::WithdrawDelegatorReward => "withdraw_delegator_reward",
             MixnetEventType::WithdrawOperatorReward => "withdraw_operator_reward",
+            MixnetEventType::OperatorRewardCompoundingUpdate => {
+                "operator_reward_compounding_update"
+            }
+            MixnetEventType::DelegatorRewardCompoundingUpdate => {
+                "delegator_reward_compounding_update"
+            }
+            MixnetEventType::CompoundOperatorReward => "compound_operator_reward",
+            MixnetEventType::CompoundDelegatorReward => "compound_delegator_reward",
             MixnetEventType::PendingActiveSetUpdate => "pending_active_set_update",
             MixnetEventType::ActiveSetUpdate => "active_set_update",
             MixnetEventType::PendingIntervalRewardingParamsUpdate => {
@@ -81,8 +96,11 @@ impl Display for MixnetEventType {
             MixnetEventType::IntervalRewardingParamsUpdate => "interval_rewarding_params_update",
             MixnetEventType::PendingDelegation => "pending_delegation",
             MixnetEventType::PendingUndelegation => "pending_undelegation",
+            MixnetEventType::PendingRedelegation => "pending_redelegation",
             MixnetEventType::Delegation => "delegation",
             MixnetEventType::Undelegation => "undelegation",
+            MixnetEventType::Redelegation => "redelegation",
+            MixnetEventType::RedelegationOnUnbonding => "redelegation_on_unbonding_node",
             MixnetEventType::ContractSettingsUpdate => "settings_update",
             MixnetEventType::RewardingValidatorUpdate => "rewarding_validator_address_update",
             MixnetEventType::BeginEpochTransition => "beginning_epoch_transition",
@@ -110,6 +128,7 @@ pub const PROXY_KEY: &str = "proxy";
 // delegation/undelegation
 pub const DELEGATOR_KEY: &str = "delegator";
 pub const DELEGATION_TARGET_KEY: &str = "delegation_target";
+pub const DELEGATION_SOURCE_KEY: &str = "delegation_source";
 pub const UNIT_REWARD_KEY: &str = "unit_reward";
 
 // bonding/unbonding
@@ -142,6 +161,7 @@ pub const INTERVAL_REWARDING_PARAMS_UPDATE_KEY: &str = "interval_rewarding_param
 pub const UPDATED_INTERVAL_REWARDING_PARAMS_KEY: &str = "updated_interval_rewarding_params";
 pub const PRIOR_DELEGATES_KEY: &str = "prior_delegates";
 pub const PRIOR_UNIT_REWARD_KEY: &str = "prior_unit_reward";
+pub const COMPOUNDING_ENABLED_KEY: &str = "compounding_enabled";
 
 pub const NO_REWARD_REASON_KEY: &str = "no_reward_reason";
 pub const BOND_NOT_FOUND_VALUE: &str = "bond_not_found";
@@ -227,6 +247,42 @@ pub fn new_withdraw_delegator_reward_event(
         .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
 }
 
+pub fn new_operator_reward_compounding_update_event(
+    owner: &Addr,
+    mix_id: MixId,
+    enabled: bool,
+) -> Event {
+    Event::new(MixnetEventType::OperatorRewardCompoundingUpdate)
+        .add_attribute(OWNER_KEY, owner.as_str())
+        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+        .add_attribute(COMPOUNDING_ENABLED_KEY, enabled.to_string())
+}
+
+pub fn new_delegator_reward_compounding_update_event(
+    delegator: &Addr,
+    mix_id: MixId,
+    enabled: bool,
+) -> Event {
+    Event::new(MixnetEventType::DelegatorRewardCompoundingUpdate)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
+        .add_attribute(COMPOUNDING_ENABLED_KEY, enabled.to_string())
+}
+
+pub fn new_compound_operator_reward_event(owner: &Addr, amount: Coin, mix_id: MixId) -> Event {
+    Event::new(MixnetEventType::CompoundOperatorReward)
+        .add_attribute(OWNER_KEY, owner.as_str())
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+}
+
+pub fn new_compound_delegator_reward_event(delegator: &Addr, amount: Coin, mix_id: MixId) -> Event {
+    Event::new(MixnetEventType::CompoundDelegatorReward)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
+}
+
 pub fn new_active_set_update_event(created_at: BlockHeight, new_size: u32) -> Event {
     Event::new(MixnetEventType::ActiveSetUpdate)
         .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
@@ -302,6 +358,53 @@ pub fn new_pending_undelegation_event(
         .add_attribute(MIX_ID_KEY, mix_id.to_string())
 }
 
+pub fn new_redelegation_event(
+    created_at: BlockHeight,
+    delegator: &Addr,
+    proxy: &Option<Addr>,
+    amount: &Coin,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+    unit_reward: Decimal,
+) -> Event {
+    Event::new(MixnetEventType::Redelegation)
+        .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_optional_attribute(PROXY_KEY, proxy.as_ref())
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(DELEGATION_SOURCE_KEY, from_mix_id.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, to_mix_id.to_string())
+        .add_attribute(UNIT_REWARD_KEY, unit_reward.to_string())
+}
+
+pub fn new_redelegation_on_unbonded_node_event(
+    delegator: &Addr,
+    proxy: &Option<Addr>,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+) -> Event {
+    Event::new(MixnetEventType::RedelegationOnUnbonding)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_optional_attribute(PROXY_KEY, proxy.as_ref())
+        .add_attribute(DELEGATION_SOURCE_KEY, from_mix_id.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, to_mix_id.to_string())
+}
+
+pub fn new_pending_redelegation_event(
+    delegator: &Addr,
+    proxy: &Option<Addr>,
+    amount: &Coin,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+) -> Event {
+    Event::new(MixnetEventType::PendingRedelegation)
+        .add_attribute(DELEGATOR_KEY, delegator)
+        .add_optional_attribute(PROXY_KEY, proxy.as_ref())
+        .add_attribute(AMOUNT_KEY, amount.to_string())
+        .add_attribute(DELEGATION_SOURCE_KEY, from_mix_id.to_string())
+        .add_attribute(DELEGATION_TARGET_KEY, to_mix_id.to_string())
+}
+
 pub fn new_gateway_bonding_event(
     owner: &Addr,
     proxy: &Option<Addr>,
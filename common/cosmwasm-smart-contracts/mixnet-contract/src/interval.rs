@@ -436,6 +436,11 @@ impl Interval {
         self.epochs_in_interval - self.current_epoch_id
     }
 
+    /// Checks whether the current epoch is the final epoch of the current interval.
+    pub fn is_current_epoch_last_in_interval(&self) -> bool {
+        self.epochs_until_interval_end() == 1
+    }
+
     /// Returns the ending datetime of the current interval.
     pub fn current_interval_end(&self) -> OffsetDateTime {
         self.current_epoch_start + self.epochs_until_interval_end() * self.epoch_length
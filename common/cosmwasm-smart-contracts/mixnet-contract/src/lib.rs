@@ -52,6 +52,7 @@ pub use pending_events::{
 pub use reward_params::{IntervalRewardParams, IntervalRewardingParamsUpdate, RewardingParams};
 pub use rewarding::{
     EstimatedCurrentEpochRewardResponse, PagedRewardedSetResponse, PendingRewardResponse,
+    RewardCompoundingResponse,
 };
 pub use signing_types::*;
 pub use types::*;
@@ -40,6 +40,7 @@ use crate::{
     },
     rewarding::{
         EstimatedCurrentEpochRewardResponse, PagedRewardedSetResponse, PendingRewardResponse,
+        RewardCompoundingResponse,
     },
     types::{ContractState, LayerDistribution},
 };
@@ -251,6 +252,17 @@ pub enum ExecuteMsg {
         mix_id: MixId,
         delegate: String,
     },
+    RedelegateFromMixnode {
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+    },
+    RedelegateFromMixnodeOnBehalf {
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        delegate: String,
+    },
 
     // reward-related
     RewardMixnode {
@@ -268,6 +280,13 @@ pub enum ExecuteMsg {
         mix_id: MixId,
         owner: String,
     },
+    SetOperatorRewardCompounding {
+        enabled: bool,
+    },
+    SetDelegatorRewardCompounding {
+        mix_id: MixId,
+        enabled: bool,
+    },
 
     // testing-only
     #[cfg(feature = "contract-testing")]
@@ -367,6 +386,18 @@ impl ExecuteMsg {
             ExecuteMsg::UndelegateFromMixnodeOnBehalf { mix_id, .. } => {
                 format!("removing delegation from mixnode {mix_id} on behalf")
             }
+            ExecuteMsg::RedelegateFromMixnode {
+                from_mix_id,
+                to_mix_id,
+                ..
+            } => format!("moving delegation from mixnode {from_mix_id} to mixnode {to_mix_id}"),
+            ExecuteMsg::RedelegateFromMixnodeOnBehalf {
+                from_mix_id,
+                to_mix_id,
+                ..
+            } => format!(
+                "moving delegation from mixnode {from_mix_id} to mixnode {to_mix_id} on behalf"
+            ),
             ExecuteMsg::RewardMixnode {
                 mix_id,
                 performance,
@@ -381,6 +412,12 @@ impl ExecuteMsg {
             ExecuteMsg::WithdrawDelegatorRewardOnBehalf { mix_id, .. } => {
                 format!("withdrawing delegator reward from mixnode {mix_id} on behalf")
             }
+            ExecuteMsg::SetOperatorRewardCompounding { enabled } => {
+                format!("setting operator reward compounding to {enabled}")
+            }
+            ExecuteMsg::SetDelegatorRewardCompounding { mix_id, enabled } => {
+                format!("setting delegator reward compounding for mixnode {mix_id} to {enabled}")
+            }
             #[cfg(feature = "contract-testing")]
             ExecuteMsg::TestingResolveAllPendingEvents { .. } => {
                 "resolving all pending events".into()
@@ -683,6 +720,23 @@ pub enum QueryMsg {
         proxy: Option<String>,
     },
 
+    /// Checks whether the rewards of the particular mixnode operator are automatically compounded.
+    #[cfg_attr(feature = "schema", returns(RewardCompoundingResponse))]
+    GetOperatorRewardCompounding {
+        /// Id of the node to query.
+        mix_id: MixId,
+    },
+
+    /// Checks whether the rewards of the particular delegator are automatically compounded.
+    #[cfg_attr(feature = "schema", returns(RewardCompoundingResponse))]
+    GetDelegatorRewardCompounding {
+        /// Address of the delegator to use for the query.
+        address: String,
+
+        /// Id of the node to query.
+        mix_id: MixId,
+    },
+
     /// Given the provided node performance, attempt to estimate the operator reward for the current epoch.
     #[cfg_attr(feature = "schema", returns(EstimatedCurrentEpochRewardResponse))]
     GetEstimatedCurrentEpochOperatorReward {
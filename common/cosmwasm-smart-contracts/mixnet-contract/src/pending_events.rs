@@ -67,6 +67,29 @@ pub enum PendingEpochEventKind {
         proxy: Option<Addr>,
     },
 
+    /// Request to move (part of) the delegation, including any rewards it has accrued,
+    /// from one mixnode to another.
+    /// Note that if a delegation towards the target mixnode already exists, it will get updated with the moved tokens.
+    #[serde(alias = "Redelegate")]
+    Redelegate {
+        /// The address of the owner of the delegation.
+        owner: Addr,
+
+        /// The id of the mixnode the delegation is moved from.
+        from_mix_id: MixId,
+
+        /// The id of the mixnode the delegation is moved to.
+        to_mix_id: MixId,
+
+        /// The amount of tokens to move. If it exceeds the value of the delegation (including its rewards),
+        /// the entire delegation is moved instead.
+        amount: Coin,
+
+        /// Entity who made the delegation on behalf of the owner.
+        /// If present, it's most likely the address of the vesting contract.
+        proxy: Option<Addr>,
+    },
+
     /// Request to pledge more tokens (by the node operator) towards its node.
     #[serde(alias = "PledgeMore")]
     PledgeMore {
@@ -100,6 +100,17 @@ impl EstimatedCurrentEpochRewardResponse {
     }
 }
 
+/// Response containing information about automatic compounding of the rewards.
+#[cw_serde]
+#[derive(Copy)]
+pub struct RewardCompoundingResponse {
+    /// Id of the mixnode associated with the stake.
+    pub mix_id: MixId,
+
+    /// Indicates whether the accrued rewards are going to get added to the stake at the end of every interval.
+    pub compounding_enabled: bool,
+}
+
 /// Response containing paged list of all mixnodes in the rewarded set.
 #[cw_serde]
 pub struct PagedRewardedSetResponse {
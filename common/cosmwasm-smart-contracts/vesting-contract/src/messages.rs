@@ -78,6 +78,12 @@ pub enum ExecuteMsg {
         mix_id: MixId,
         on_behalf_of: Option<String>,
     },
+    RedelegateFromMixnode {
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        on_behalf_of: Option<String>,
+    },
     CreateAccount {
         owner_address: String,
         staking_address: Option<String>,
@@ -92,6 +98,13 @@ pub enum ExecuteMsg {
         mix_id: MixId,
         amount: Coin,
     },
+    TrackRedelegation {
+        owner: String,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        moved_entire_delegation: bool,
+    },
     BondMixnode {
         mix_node: MixNode,
         cost_params: MixNodeCostParams,
@@ -155,9 +168,11 @@ impl ExecuteMsg {
             ExecuteMsg::UpdateMixnetAddress { .. } => "VestingExecuteMsg::UpdateMixnetAddress",
             ExecuteMsg::DelegateToMixnode { .. } => "VestingExecuteMsg::DelegateToMixnode",
             ExecuteMsg::UndelegateFromMixnode { .. } => "VestingExecuteMsg::UndelegateFromMixnode",
+            ExecuteMsg::RedelegateFromMixnode { .. } => "VestingExecuteMsg::RedelegateFromMixnode",
             ExecuteMsg::CreateAccount { .. } => "VestingExecuteMsg::CreateAccount",
             ExecuteMsg::WithdrawVestedCoins { .. } => "VestingExecuteMsg::WithdrawVestedCoins",
             ExecuteMsg::TrackUndelegation { .. } => "VestingExecuteMsg::TrackUndelegation",
+            ExecuteMsg::TrackRedelegation { .. } => "VestingExecuteMsg::TrackRedelegation",
             ExecuteMsg::BondMixnode { .. } => "VestingExecuteMsg::BondMixnode",
             ExecuteMsg::PledgeMore { .. } => "VestingExecuteMsg::PledgeMore",
             ExecuteMsg::DecreasePledge { .. } => "VestingExecuteMsg::DecreasePledge",
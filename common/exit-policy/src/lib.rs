@@ -35,6 +35,56 @@ pub fn parse_exit_policy<S: AsRef<str>>(exit_policy: S) -> Result<ExitPolicy, Po
     Ok(AddressPolicy { rules })
 }
 
+/// Rule-level difference between two versions of an exit policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExitPolicyDiff {
+    /// Rules present in the new policy, but not in the old one.
+    pub added: Vec<AddressPolicyRule>,
+
+    /// Rules present in the old policy, but not in the new one.
+    pub removed: Vec<AddressPolicyRule>,
+
+    /// Flag indicating whether the rules common to both policies got reordered,
+    /// which might also change the outcome of the evaluation.
+    pub reordered: bool,
+}
+
+impl ExitPolicyDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && !self.reordered
+    }
+}
+
+/// Computes the rule-level difference between the two provided exit policies.
+pub fn diff_exit_policies(old: &ExitPolicy, new: &ExitPolicy) -> ExitPolicyDiff {
+    // note: rules might be duplicated within a policy, so each matched rule is only consumed once
+    let mut unmatched_old = old.rules.iter().collect::<Vec<_>>();
+    let mut added = Vec::new();
+    let mut common_new = Vec::new();
+
+    for rule in &new.rules {
+        if let Some(position) = unmatched_old.iter().position(|old_rule| *old_rule == rule) {
+            unmatched_old.remove(position);
+            common_new.push(rule);
+        } else {
+            added.push(rule.clone());
+        }
+    }
+
+    let removed = unmatched_old.into_iter().cloned().collect::<Vec<_>>();
+    let common_old = old
+        .rules
+        .iter()
+        .filter(|rule| !removed.contains(rule))
+        .collect::<Vec<_>>();
+
+    ExitPolicyDiff {
+        added,
+        removed,
+        reordered: common_old != common_new,
+    }
+}
+
 pub fn format_exit_policy(policy: &ExitPolicy) -> String {
     policy
         .rules
@@ -293,4 +343,49 @@ ExitPolicy reject *:*
 
         assert_eq!(res, expected)
     }
+
+    #[test]
+    fn diffing_policies() {
+        let old = parse_exit_policy(
+            r#"
+ExitPolicy reject 1.2.3.4/32:*
+ExitPolicy accept *:53
+ExitPolicy accept *:80
+ExitPolicy reject *:*
+        "#,
+        )
+        .unwrap();
+
+        assert!(diff_exit_policies(&old, &old).is_empty());
+
+        let new = parse_exit_policy(
+            r#"
+ExitPolicy reject 1.2.3.4/32:*
+ExitPolicy accept *:53
+ExitPolicy accept *:443
+ExitPolicy reject *:*
+        "#,
+        )
+        .unwrap();
+
+        let diff = diff_exit_policies(&old, &new);
+        assert_eq!(diff.added, vec!["accept *:443".parse().unwrap()]);
+        assert_eq!(diff.removed, vec!["accept *:80".parse().unwrap()]);
+        assert!(!diff.reordered);
+
+        let reordered = parse_exit_policy(
+            r#"
+ExitPolicy accept *:53
+ExitPolicy reject 1.2.3.4/32:*
+ExitPolicy accept *:80
+ExitPolicy reject *:*
+        "#,
+        )
+        .unwrap();
+
+        let diff = diff_exit_policies(&old, &reordered);
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert!(diff.reordered);
+    }
 }
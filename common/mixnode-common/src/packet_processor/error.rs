@@ -28,4 +28,10 @@ pub enum MixProcessingError {
 
     #[error("failed to process received outfox packet: {0}")]
     OutfoxProcessingError(#[from] OutfoxError),
+
+    #[error("the received packet has already been processed before")]
+    ReplayedPacket,
+
+    #[error("there are no sphinx keys available for processing the received packet")]
+    NoSphinxKeys,
 }
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::packet_processor::error::MixProcessingError;
+use crate::packet_processor::replay_detection::{ReplayDetector, ReplayTag};
+use crate::packet_processor::sphinx_keys::ActiveSphinxKeys;
 use log::*;
 use nym_metrics::nanos;
 use nym_sphinx_acknowledgements::surb_ack::SurbAck;
@@ -36,25 +38,62 @@ pub enum MixProcessingResult {
 
 #[derive(Clone)]
 pub struct SphinxPacketProcessor {
-    /// Private sphinx key of this node required to unwrap received sphinx packet.
-    sphinx_key: Arc<PrivateKey>,
+    /// Private sphinx keys of this node required to unwrap received sphinx packet.
+    sphinx_keys: ActiveSphinxKeys,
+
+    /// Optional detector of packets that have already been processed by this node.
+    replay_detector: Option<ReplayDetector>,
 }
 
 impl SphinxPacketProcessor {
-    /// Creates new instance of `CachedPacketProcessor`
+    /// Creates new instance of `SphinxPacketProcessor` using a single, static, sphinx key.
     pub fn new(sphinx_key: PrivateKey) -> Self {
+        Self::new_with_rotating_keys(sphinx_key.into())
+    }
+
+    /// Creates new instance of `SphinxPacketProcessor` that's going to attempt to unwrap
+    /// received packets with any of the currently active keys.
+    pub fn new_with_rotating_keys(sphinx_keys: ActiveSphinxKeys) -> Self {
         SphinxPacketProcessor {
-            sphinx_key: Arc::new(sphinx_key),
+            sphinx_keys,
+            replay_detector: None,
         }
     }
 
-    /// Performs a fresh sphinx unwrapping using no cache.
+    /// Makes this processor reject any packet that has already been successfully processed before.
+    #[must_use]
+    pub fn with_replay_detection(mut self, replay_detector: ReplayDetector) -> Self {
+        self.replay_detector = Some(replay_detector);
+        self
+    }
+
+    /// Marks the packet with the provided shared key as processed, returning an error
+    /// if it has already been seen before.
+    fn check_for_replay(&self, shared_key: &[u8; 32]) -> Result<(), MixProcessingError> {
+        let Some(replay_detector) = &self.replay_detector else {
+            return Ok(());
+        };
+
+        if replay_detector.check_and_insert(&ReplayTag::from_shared_key(shared_key)) {
+            debug!("received a replayed packet");
+            return Err(MixProcessingError::ReplayedPacket);
+        }
+        Ok(())
+    }
+
+    /// Performs a fresh sphinx unwrapping using no cache, attempting each of the provided keys in order.
+    /// Alongside the processed packet, returns the key shared with the packet creator.
     fn perform_initial_packet_processing(
         &self,
         packet: NymPacket,
-    ) -> Result<NymProcessedPacket, MixProcessingError> {
+        sphinx_keys: &[Arc<PrivateKey>],
+    ) -> Result<(NymProcessedPacket, [u8; 32]), MixProcessingError> {
+        if sphinx_keys.is_empty() {
+            return Err(MixProcessingError::NoSphinxKeys);
+        }
+
         nanos!("perform_initial_packet_processing", {
-            packet.process(&self.sphinx_key).map_err(|err| {
+            packet.process_with_any_key(sphinx_keys).map_err(|err| {
                 debug!("Failed to unwrap NymPacket packet: {err}");
                 MixProcessingError::NymPacketProcessingError(err)
             })
@@ -68,7 +107,14 @@ impl SphinxPacketProcessor {
     ) -> Result<NymProcessedPacket, MixProcessingError> {
         nanos!("perform_initial_unwrapping", {
             let packet = received.into_inner();
-            self.perform_initial_packet_processing(packet)
+            let sphinx_keys = self.sphinx_keys.keys();
+            let (processed, shared_key) =
+                self.perform_initial_packet_processing(packet, &sphinx_keys)?;
+
+            // only remember packets that we managed to unwrap so that any garbage
+            // wouldn't be polluting the filters
+            self.check_for_replay(&shared_key)?;
+            Ok(processed)
         })
     }
 
@@ -221,7 +267,7 @@ impl SphinxPacketProcessor {
             let packet_size = received.packet_size();
             let packet_type = received.packet_type();
 
-            // unwrap the sphinx packet and if possible and appropriate, cache keys
+            // unwrap the sphinx packet and make sure we haven't seen it before
             let processed_packet = self.perform_initial_unwrapping(received)?;
 
             // for forward packets, extract next hop and set delay (but do NOT delay here)
@@ -242,13 +288,211 @@ impl SphinxPacketProcessor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::packet_processor::replay_detection::ReplayProtectionConfig;
     use nym_sphinx_types::crypto::keygen;
+    use nym_sphinx_types::{
+        Destination, Node, PublicKey, DESTINATION_ADDRESS_LENGTH, IDENTIFIER_LENGTH,
+    };
+    use std::net::SocketAddr;
 
     fn fixture() -> SphinxPacketProcessor {
         let local_keys = keygen();
         SphinxPacketProcessor::new(local_keys.0)
     }
 
+    fn replay_protected_fixture() -> (SphinxPacketProcessor, PublicKey) {
+        let (private_key, public_key) = keygen();
+        let detector = ReplayDetector::new(&ReplayProtectionConfig {
+            expected_packets_per_rotation: 1000,
+            ..Default::default()
+        });
+        let processor = SphinxPacketProcessor::new(private_key).with_replay_detection(detector);
+        (processor, public_key)
+    }
+
+    fn test_route(first_hop: PublicKey, hops: usize) -> Vec<Node> {
+        let mut route = Vec::with_capacity(hops);
+        for i in 0..hops {
+            let socket_address: SocketAddr = format!("1.2.3.{i}:1789").parse().unwrap();
+            let address = NymNodeRoutingAddress::from(socket_address)
+                .try_into()
+                .unwrap();
+            let public_key = if i == 0 { first_hop } else { keygen().1 };
+            route.push(Node::new(address, public_key))
+        }
+        route
+    }
+
+    fn test_destination() -> Destination {
+        Destination::new(
+            DestinationAddressBytes::from_bytes([3u8; DESTINATION_ADDRESS_LENGTH]),
+            [4u8; IDENTIFIER_LENGTH],
+        )
+    }
+
+    fn framed_sphinx_packet(first_hop: PublicKey) -> Vec<u8> {
+        let route = test_route(first_hop, 3);
+        let delays = vec![SphinxDelay::new_from_nanos(42); 3];
+        NymPacket::sphinx_build(
+            PacketSize::RegularPacket.payload_size(),
+            b"foomp",
+            &route,
+            &test_destination(),
+            &delays,
+        )
+        .unwrap()
+        .to_bytes()
+        .unwrap()
+    }
+
+    fn framed_outfox_packet(first_hop: PublicKey) -> Vec<u8> {
+        let route = test_route(first_hop, 4);
+        NymPacket::outfox_build(
+            b"foomp",
+            &route,
+            &test_destination(),
+            Some(PacketSize::OutfoxRegularPacket.plaintext_size()),
+        )
+        .unwrap()
+        .to_bytes()
+        .unwrap()
+    }
+
+    #[test]
+    fn replayed_sphinx_packet_is_rejected() {
+        let (processor, public_key) = replay_protected_fixture();
+        let packet_bytes = framed_sphinx_packet(public_key);
+
+        let framed = |bytes: &[u8]| {
+            FramedNymPacket::new(
+                NymPacket::sphinx_from_bytes(bytes).unwrap(),
+                PacketType::Mix,
+                false,
+            )
+        };
+
+        let first = processor.process_received(framed(&packet_bytes));
+        assert!(matches!(first, Ok(MixProcessingResult::ForwardHop(..))));
+
+        let replayed = processor.process_received(framed(&packet_bytes));
+        assert!(matches!(replayed, Err(MixProcessingError::ReplayedPacket)));
+
+        // but a different packet is still processed just fine
+        let another = processor.process_received(framed(&framed_sphinx_packet(public_key)));
+        assert!(matches!(another, Ok(MixProcessingResult::ForwardHop(..))));
+    }
+
+    #[test]
+    fn replayed_outfox_packet_is_rejected() {
+        let (processor, public_key) = replay_protected_fixture();
+        let packet_bytes = framed_outfox_packet(public_key);
+
+        let framed = |bytes: &[u8]| {
+            FramedNymPacket::new(
+                NymPacket::outfox_from_bytes(bytes).unwrap(),
+                PacketType::Outfox,
+                false,
+            )
+        };
+
+        let first = processor.process_received(framed(&packet_bytes));
+        assert!(matches!(first, Ok(MixProcessingResult::ForwardHop(..))));
+
+        let replayed = processor.process_received(framed(&packet_bytes));
+        assert!(matches!(replayed, Err(MixProcessingError::ReplayedPacket)));
+
+        let another = processor.process_received(framed(&framed_outfox_packet(public_key)));
+        assert!(matches!(another, Ok(MixProcessingResult::ForwardHop(..))));
+    }
+
+    #[test]
+    fn replays_are_not_detected_without_replay_detector() {
+        let (private_key, public_key) = keygen();
+        let processor = SphinxPacketProcessor::new(private_key);
+        let packet_bytes = framed_sphinx_packet(public_key);
+
+        for _ in 0..2 {
+            let framed = FramedNymPacket::new(
+                NymPacket::sphinx_from_bytes(&packet_bytes).unwrap(),
+                PacketType::Mix,
+                false,
+            );
+            assert!(processor.process_received(framed).is_ok());
+        }
+    }
+
+    #[test]
+    fn packets_are_processed_with_any_active_key() {
+        let (old_private, old_public) = keygen();
+        let (new_private, new_public) = keygen();
+        let keys = ActiveSphinxKeys::new(1, old_private);
+        keys.insert(2, new_private);
+
+        let processor = SphinxPacketProcessor::new_with_rotating_keys(keys.clone());
+        let framed = |public_key| {
+            FramedNymPacket::new(
+                NymPacket::sphinx_from_bytes(&framed_sphinx_packet(public_key)).unwrap(),
+                PacketType::Mix,
+                false,
+            )
+        };
+
+        assert!(processor.process_received(framed(old_public)).is_ok());
+        assert!(processor.process_received(framed(new_public)).is_ok());
+
+        // once the old key is no longer valid, packets created for it get rejected
+        assert!(keys.set_primary(2));
+        assert!(keys.remove(1));
+        assert!(processor.process_received(framed(old_public)).is_err());
+        assert!(processor.process_received(framed(new_public)).is_ok());
+    }
+
+    #[test]
+    fn replays_are_detected_for_packets_created_for_any_active_key() {
+        let (old_private, old_public) = keygen();
+        let (new_private, new_public) = keygen();
+        let keys = ActiveSphinxKeys::new(1, old_private);
+        keys.insert(2, new_private);
+
+        let detector = ReplayDetector::new(&ReplayProtectionConfig {
+            expected_packets_per_rotation: 1000,
+            ..Default::default()
+        });
+        let processor =
+            SphinxPacketProcessor::new_with_rotating_keys(keys).with_replay_detection(detector);
+
+        for public_key in [old_public, new_public] {
+            let sphinx_bytes = framed_sphinx_packet(public_key);
+            let outfox_bytes = framed_outfox_packet(public_key);
+            let sphinx = || {
+                FramedNymPacket::new(
+                    NymPacket::sphinx_from_bytes(&sphinx_bytes).unwrap(),
+                    PacketType::Mix,
+                    false,
+                )
+            };
+            let outfox = || {
+                FramedNymPacket::new(
+                    NymPacket::outfox_from_bytes(&outfox_bytes).unwrap(),
+                    PacketType::Outfox,
+                    false,
+                )
+            };
+
+            assert!(processor.process_received(sphinx()).is_ok());
+            assert!(matches!(
+                processor.process_received(sphinx()),
+                Err(MixProcessingError::ReplayedPacket)
+            ));
+
+            assert!(processor.process_received(outfox()).is_ok());
+            assert!(matches!(
+                processor.process_received(outfox()),
+                Err(MixProcessingError::ReplayedPacket)
+            ));
+        }
+    }
+
     #[tokio::test]
     async fn splitting_hop_data_works_for_sufficiently_long_payload() {
         let processor = fixture();
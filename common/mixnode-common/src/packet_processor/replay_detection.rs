@@ -0,0 +1,411 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use log::{debug, info, trace};
+use nym_task::TaskClient;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+
+const REPLAY_TAG_CONTEXT: &str = "NYM-MIXNODE-REPLAY-TAG-V1";
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ReplayProtectionConfig {
+    /// Specifies whether this node should **NOT** reject packets it has already processed.
+    pub unsafe_disabled: bool,
+
+    /// Expected number of packets the node is going to process within a single rotation interval.
+    /// It is used for sizing the underlying bloom filters. If more packets are received before the
+    /// interval elapses, the filters are rotated early so that the false positive rate would not degrade.
+    pub expected_packets_per_rotation: usize,
+
+    /// Desired false positive rate of the underlying bloom filters,
+    /// i.e. the probability of a fresh packet being incorrectly rejected as a replay.
+    pub false_positive_rate: f64,
+
+    /// Specifies how often the bloom filters should get rotated.
+    /// Note that, unless the filters fill up earlier, a packet is guaranteed to be remembered
+    /// for at least this long (and at most for twice that amount).
+    #[serde(with = "humantime_serde")]
+    pub rotation_interval: Duration,
+}
+
+impl ReplayProtectionConfig {
+    pub const DEFAULT_EXPECTED_PACKETS_PER_ROTATION: usize = 5_000_000;
+    pub const DEFAULT_FALSE_POSITIVE_RATE: f64 = 1e-5;
+    pub const DEFAULT_ROTATION_INTERVAL: Duration = Duration::from_secs(30 * 60);
+}
+
+impl Default for ReplayProtectionConfig {
+    fn default() -> Self {
+        ReplayProtectionConfig {
+            unsafe_disabled: false,
+            expected_packets_per_rotation: Self::DEFAULT_EXPECTED_PACKETS_PER_ROTATION,
+            false_positive_rate: Self::DEFAULT_FALSE_POSITIVE_RATE,
+            rotation_interval: Self::DEFAULT_ROTATION_INTERVAL,
+        }
+    }
+}
+
+/// Per-packet tag derived from the key shared between the packet creator and this node.
+/// Since the shared key is bound to both the packet and the node key,
+/// the same tag is going to be produced if (and only if) the same packet is replayed to this node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayTag([u8; 32]);
+
+impl ReplayTag {
+    /// Derives the tag from the shared key established while unwrapping the packet layer.
+    pub fn from_shared_key(shared_key: &[u8; 32]) -> Self {
+        ReplayTag(blake3::derive_key(REPLAY_TAG_CONTEXT, shared_key))
+    }
+
+    // the tag is an output of a hash function, so its bytes are uniformly distributed
+    // and can be directly used for deriving the bloom filter indices
+    fn filter_hashes(&self) -> (u64, u64) {
+        let mut first = [0u8; 8];
+        let mut second = [0u8; 8];
+        first.copy_from_slice(&self.0[..8]);
+        second.copy_from_slice(&self.0[8..16]);
+
+        // make sure the step is odd so that we'd never get the same index repeated
+        (u64::from_le_bytes(first), u64::from_le_bytes(second) | 1)
+    }
+}
+
+struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    num_hashes: u32,
+}
+
+impl BloomFilter {
+    fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let expected_items = expected_items.max(1) as f64;
+        let false_positive_rate = false_positive_rate.clamp(f64::MIN_POSITIVE, 0.5);
+        let ln2 = std::f64::consts::LN_2;
+
+        let num_bits = (-(expected_items * false_positive_rate.ln()) / (ln2 * ln2)).ceil() as u64;
+        let num_bits = num_bits.max(64);
+        let num_hashes = ((num_bits as f64 / expected_items) * ln2).round().max(1.) as u32;
+
+        BloomFilter {
+            bits: vec![0; num_bits.div_ceil(64) as usize],
+            num_bits,
+            num_hashes,
+        }
+    }
+
+    fn bit_position(&self, (h1, h2): (u64, u64), i: u64) -> (usize, u64) {
+        let index = h1.wrapping_add(i.wrapping_mul(h2)) % self.num_bits;
+        ((index / 64) as usize, 1 << (index % 64))
+    }
+
+    fn contains(&self, tag: &ReplayTag) -> bool {
+        let hashes = tag.filter_hashes();
+        (0..self.num_hashes as u64).all(|i| {
+            let (word, mask) = self.bit_position(hashes, i);
+            self.bits[word] & mask != 0
+        })
+    }
+
+    /// Inserts the tag into the filter returning whether it had already been present.
+    fn insert(&mut self, tag: &ReplayTag) -> bool {
+        let hashes = tag.filter_hashes();
+        let mut already_present = true;
+        for i in 0..self.num_hashes as u64 {
+            let (word, mask) = self.bit_position(hashes, i);
+            if self.bits[word] & mask == 0 {
+                already_present = false;
+                self.bits[word] |= mask;
+            }
+        }
+        already_present
+    }
+
+    fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|word| *word = 0)
+    }
+}
+
+struct ReplayFilters {
+    current: BloomFilter,
+    previous: BloomFilter,
+
+    /// Number of distinct tags inserted into the current filter.
+    current_insertions: usize,
+    last_rotation: Instant,
+}
+
+impl ReplayFilters {
+    fn rotate(&mut self) {
+        std::mem::swap(&mut self.current, &mut self.previous);
+        self.current.clear();
+        self.current_insertions = 0;
+        self.last_rotation = Instant::now();
+    }
+}
+
+struct ReplayDetectorInner {
+    filters: Mutex<ReplayFilters>,
+
+    /// Maximum number of tags inserted into a filter before it's rotated regardless of its age.
+    filter_capacity: usize,
+    replayed_packets: AtomicU64,
+}
+
+/// Memory-bounded detector of replayed packets.
+///
+/// It's composed of two bloom filters: new tags are always inserted into the current one,
+/// whilst lookups are performed against both of them. Upon rotation the previous filter
+/// gets cleared and becomes the current one, so that the memory usage stays constant.
+/// The filters are rotated either once they get too old or once the current one
+/// contains as many tags as it was sized for.
+#[derive(Clone)]
+pub struct ReplayDetector {
+    inner: Arc<ReplayDetectorInner>,
+}
+
+impl ReplayDetector {
+    pub fn new(config: &ReplayProtectionConfig) -> Self {
+        ReplayDetector {
+            inner: Arc::new(ReplayDetectorInner {
+                filters: Mutex::new(ReplayFilters {
+                    current: BloomFilter::new(
+                        config.expected_packets_per_rotation,
+                        config.false_positive_rate,
+                    ),
+                    previous: BloomFilter::new(
+                        config.expected_packets_per_rotation,
+                        config.false_positive_rate,
+                    ),
+                    current_insertions: 0,
+                    last_rotation: Instant::now(),
+                }),
+                filter_capacity: config.expected_packets_per_rotation.max(1),
+                replayed_packets: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    fn lock_filters(&self) -> MutexGuard<'_, ReplayFilters> {
+        // the lock can only be poisoned if another thread panicked while holding it,
+        // in which case the whole node is in an undefined state anyway
+        self.inner
+            .filters
+            .lock()
+            .expect("the replay detection filters lock got poisoned")
+    }
+
+    /// Checks whether the provided tag has already been seen and marks it as such.
+    /// Returns `true` if the packet is a replay.
+    pub fn check_and_insert(&self, tag: &ReplayTag) -> bool {
+        let mut filters = self.lock_filters();
+        let seen_before_rotation = filters.previous.contains(tag);
+
+        // always insert the tag into the current filter so that it'd survive the next rotation
+        let seen_since_rotation = filters.current.insert(tag);
+        if !seen_since_rotation {
+            filters.current_insertions += 1;
+            if filters.current_insertions >= self.inner.filter_capacity {
+                debug!("the replay detection filter has reached its capacity - rotating early");
+                filters.rotate()
+            }
+        }
+
+        let replayed = seen_before_rotation || seen_since_rotation;
+        if replayed {
+            self.inner.replayed_packets.fetch_add(1, Ordering::Relaxed);
+        }
+        replayed
+    }
+
+    /// Rotates the underlying filters if they haven't been rotated for at least the specified duration.
+    /// Returns whether the rotation has happened.
+    pub fn rotate_if_older_than(&self, max_age: Duration) -> bool {
+        let mut filters = self.lock_filters();
+        if filters.last_rotation.elapsed() >= max_age {
+            filters.rotate();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the time of the most recent rotation of the filters.
+    pub fn last_rotation(&self) -> Instant {
+        self.lock_filters().last_rotation
+    }
+
+    /// Total number of replayed packets detected since startup.
+    pub fn replayed_packets(&self) -> u64 {
+        self.inner.replayed_packets.load(Ordering::Relaxed)
+    }
+}
+
+/// Task responsible for rotating the filters of the associated `ReplayDetector` once they get too old.
+pub struct ReplayDetectorRotator {
+    detector: ReplayDetector,
+    rotation_interval: Duration,
+    shutdown: TaskClient,
+}
+
+impl ReplayDetectorRotator {
+    pub fn new(
+        detector: ReplayDetector,
+        rotation_interval: Duration,
+        shutdown: TaskClient,
+    ) -> Self {
+        ReplayDetectorRotator {
+            detector,
+            rotation_interval,
+            shutdown,
+        }
+    }
+
+    pub async fn run(&mut self) {
+        debug!("Started ReplayDetectorRotator with graceful shutdown support");
+
+        while !self.shutdown.is_shutdown() {
+            // the filters might have been rotated in the meantime due to reaching their capacity,
+            // so the deadline has to be recomputed every time
+            let deadline = self.detector.last_rotation() + self.rotation_interval;
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("ReplayDetectorRotator: Received shutdown");
+                }
+                _ = sleep_until(deadline) => {
+                    if self.detector.rotate_if_older_than(self.rotation_interval) {
+                        debug!("rotated the replay detection filters");
+                        let replayed = self.detector.replayed_packets();
+                        if replayed > 0 {
+                            info!("Since startup rejected {replayed} replayed packets");
+                        }
+                    }
+                }
+            }
+        }
+        trace!("ReplayDetectorRotator: Exiting");
+    }
+
+    pub fn start(mut self) {
+        tokio::spawn(async move { self.run().await });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::RngCore;
+
+    fn test_config() -> ReplayProtectionConfig {
+        ReplayProtectionConfig {
+            expected_packets_per_rotation: 1000,
+            ..Default::default()
+        }
+    }
+
+    fn random_tag() -> ReplayTag {
+        let mut shared_key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut shared_key);
+        ReplayTag::from_shared_key(&shared_key)
+    }
+
+    #[test]
+    fn replay_tag_is_deterministic() {
+        assert_eq!(
+            ReplayTag::from_shared_key(&[42; 32]),
+            ReplayTag::from_shared_key(&[42; 32])
+        );
+        assert_ne!(
+            ReplayTag::from_shared_key(&[42; 32]),
+            ReplayTag::from_shared_key(&[43; 32])
+        );
+    }
+
+    #[test]
+    fn detects_repeated_tags() {
+        let detector = ReplayDetector::new(&test_config());
+        let tag = random_tag();
+
+        assert!(!detector.check_and_insert(&tag));
+        assert!(detector.check_and_insert(&tag));
+        assert!(detector.check_and_insert(&tag));
+
+        assert!(!detector.check_and_insert(&random_tag()));
+    }
+
+    #[test]
+    fn remembers_tags_for_a_single_rotation() {
+        let detector = ReplayDetector::new(&test_config());
+        let tag = random_tag();
+
+        assert!(!detector.check_and_insert(&tag));
+        assert!(detector.rotate_if_older_than(Duration::ZERO));
+        assert!(detector.check_and_insert(&tag));
+
+        assert!(detector.rotate_if_older_than(Duration::ZERO));
+        assert!(detector.rotate_if_older_than(Duration::ZERO));
+        assert!(!detector.check_and_insert(&tag));
+    }
+
+    #[test]
+    fn counts_replayed_packets() {
+        let detector = ReplayDetector::new(&test_config());
+        let tag = random_tag();
+
+        assert!(!detector.check_and_insert(&tag));
+        assert_eq!(detector.replayed_packets(), 0);
+
+        assert!(detector.check_and_insert(&tag));
+        assert!(detector.check_and_insert(&tag));
+        assert_eq!(detector.replayed_packets(), 2);
+
+        // the counter is shared between all clones of the detector
+        let cloned = detector.clone();
+        assert!(cloned.check_and_insert(&tag));
+        assert_eq!(detector.replayed_packets(), 3);
+    }
+
+    #[test]
+    fn rotates_once_filter_is_full() {
+        let config = test_config();
+        let detector = ReplayDetector::new(&config);
+        let tag = random_tag();
+        assert!(!detector.check_and_insert(&tag));
+
+        for _ in 1..config.expected_packets_per_rotation {
+            detector.check_and_insert(&random_tag());
+        }
+
+        // the filter got rotated, but the tag is still remembered by the previous one
+        assert!(detector.check_and_insert(&tag));
+
+        // the replay got re-inserted into the current filter, so it takes two more rotations to forget it
+        for _ in 0..2 * config.expected_packets_per_rotation {
+            detector.check_and_insert(&random_tag());
+        }
+        assert!(!detector.check_and_insert(&tag));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rotates_based_on_age() {
+        let detector = ReplayDetector::new(&test_config());
+        let max_age = Duration::from_secs(60);
+        let tag = random_tag();
+        assert!(!detector.check_and_insert(&tag));
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert!(!detector.rotate_if_older_than(max_age));
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert!(detector.rotate_if_older_than(max_age));
+        assert!(!detector.rotate_if_older_than(max_age));
+
+        // remembered for at least a single rotation
+        assert!(detector.check_and_insert(&tag));
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_sphinx_types::PrivateKey;
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+struct SphinxKeysInner {
+    primary: u32,
+    keys: BTreeMap<u32, Arc<PrivateKey>>,
+
+    /// The key this node has been bonded with. It's not subject to rotation as clients
+    /// that learn about the node from the contract (rather than from the self-described data) rely on it.
+    bonded: Option<Arc<PrivateKey>>,
+}
+
+/// Set of sphinx keys, indexed by their rotation ids, this node is currently willing to process packets with.
+///
+/// It always contains the primary key that is expected to be used by the majority of the received packets,
+/// but it might also hold the pre-announced key of the following rotation
+/// as well as the keys of the previous rotations that are still within their grace period
+/// and the original key the node has been bonded with.
+#[derive(Clone)]
+pub struct ActiveSphinxKeys {
+    inner: Arc<RwLock<SphinxKeysInner>>,
+}
+
+impl ActiveSphinxKeys {
+    pub fn new(rotation_id: u32, primary_key: PrivateKey) -> Self {
+        let mut keys = BTreeMap::new();
+        keys.insert(rotation_id, Arc::new(primary_key));
+
+        ActiveSphinxKeys {
+            inner: Arc::new(RwLock::new(SphinxKeysInner {
+                primary: rotation_id,
+                keys,
+                bonded: None,
+            })),
+        }
+    }
+
+    /// Returns rotation id of the current primary key.
+    pub fn primary_rotation_id(&self) -> u32 {
+        self.inner
+            .read()
+            .expect("sphinx keys lock got poisoned")
+            .primary
+    }
+
+    /// Returns rotation ids of all the keys currently held.
+    pub fn rotation_ids(&self) -> Vec<u32> {
+        let guard = self.inner.read().expect("sphinx keys lock got poisoned");
+        guard.keys.keys().copied().collect()
+    }
+
+    /// Returns all currently valid keys, with the primary key always being the first one
+    /// followed by the bonded key (if any) and the remaining keys from the newest to the oldest.
+    pub fn keys(&self) -> Vec<Arc<PrivateKey>> {
+        let guard = self.inner.read().expect("sphinx keys lock got poisoned");
+        let mut keys = Vec::with_capacity(guard.keys.len() + 1);
+        if let Some(primary) = guard.keys.get(&guard.primary) {
+            keys.push(Arc::clone(primary));
+        }
+        if let Some(bonded) = &guard.bonded {
+            keys.push(Arc::clone(bonded));
+        }
+        keys.extend(
+            guard
+                .keys
+                .iter()
+                .rev()
+                .filter(|(id, _)| **id != guard.primary)
+                .map(|(_, key)| Arc::clone(key)),
+        );
+        keys
+    }
+
+    /// Makes the node keep accepting packets created with the key it has been bonded with,
+    /// regardless of the rotations.
+    pub fn set_bonded_key(&self, key: PrivateKey) {
+        let mut guard = self.inner.write().expect("sphinx keys lock got poisoned");
+        guard.bonded = Some(Arc::new(key));
+    }
+
+    /// Makes the node accept packets created with the provided key in addition to the existing ones.
+    pub fn insert(&self, rotation_id: u32, key: PrivateKey) {
+        let mut guard = self.inner.write().expect("sphinx keys lock got poisoned");
+        guard.keys.insert(rotation_id, Arc::new(key));
+    }
+
+    /// Makes the already held key of the provided rotation the primary one.
+    /// Returns `false` if no such key exists.
+    pub fn set_primary(&self, rotation_id: u32) -> bool {
+        let mut guard = self.inner.write().expect("sphinx keys lock got poisoned");
+        if !guard.keys.contains_key(&rotation_id) {
+            return false;
+        }
+        guard.primary = rotation_id;
+        true
+    }
+
+    /// Stops accepting packets created with the key of the provided rotation.
+    /// Note that the primary key can't be removed.
+    pub fn remove(&self, rotation_id: u32) -> bool {
+        let mut guard = self.inner.write().expect("sphinx keys lock got poisoned");
+        if guard.primary == rotation_id {
+            return false;
+        }
+        guard.keys.remove(&rotation_id).is_some()
+    }
+}
+
+impl From<PrivateKey> for ActiveSphinxKeys {
+    fn from(key: PrivateKey) -> Self {
+        ActiveSphinxKeys::new(0, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_sphinx_types::crypto::keygen;
+
+    #[test]
+    fn primary_key_is_always_first() {
+        let (first, _) = keygen();
+        let (second, _) = keygen();
+        let (third, _) = keygen();
+        let first_bytes = first.to_bytes();
+        let second_bytes = second.to_bytes();
+        let third_bytes = third.to_bytes();
+
+        let keys = ActiveSphinxKeys::new(1, first);
+        keys.insert(2, second);
+        keys.insert(3, third);
+
+        let ordered: Vec<_> = keys.keys().iter().map(|k| k.to_bytes()).collect();
+        assert_eq!(ordered, vec![first_bytes, third_bytes, second_bytes]);
+
+        assert!(keys.set_primary(2));
+        let ordered: Vec<_> = keys.keys().iter().map(|k| k.to_bytes()).collect();
+        assert_eq!(ordered, vec![second_bytes, third_bytes, first_bytes]);
+
+        assert!(!keys.remove(2));
+        assert!(keys.remove(1));
+        assert!(!keys.set_primary(1));
+        assert_eq!(keys.rotation_ids(), vec![2, 3]);
+    }
+
+    #[test]
+    fn bonded_key_is_kept_through_rotations() {
+        let (bonded, _) = keygen();
+        let (first, _) = keygen();
+        let (second, _) = keygen();
+        let bonded_bytes = bonded.to_bytes();
+        let first_bytes = first.to_bytes();
+        let second_bytes = second.to_bytes();
+
+        let keys = ActiveSphinxKeys::new(1, first);
+        keys.set_bonded_key(bonded);
+        keys.insert(2, second);
+
+        let ordered: Vec<_> = keys.keys().iter().map(|k| k.to_bytes()).collect();
+        assert_eq!(ordered, vec![first_bytes, bonded_bytes, second_bytes]);
+
+        assert!(keys.set_primary(2));
+        assert!(keys.remove(1));
+        let ordered: Vec<_> = keys.keys().iter().map(|k| k.to_bytes()).collect();
+        assert_eq!(ordered, vec![second_bytes, bonded_bytes]);
+
+        // the bonded key is not one of the rotations
+        assert_eq!(keys.rotation_ids(), vec![2]);
+    }
+}
@@ -36,6 +36,9 @@ pub enum RecipientFormattingError {
 
     #[error("recipient gateway's identity key is malformed: {0}")]
     MalformedGatewayError(identity::Ed25519RecoveryError),
+
+    #[error("multi-homed recipient does not specify any gateways")]
+    NoGateways,
 }
 
 // TODO: this should a different home... somewhere, but where?
@@ -241,6 +244,152 @@ impl FromStr for Recipient {
     }
 }
 
+/// Address of a client that is simultaneously connected to multiple gateways.
+/// Senders can deliver messages to the client through any of the specified gateways,
+/// so the client remains reachable even if some of them go down.
+///
+/// It is formatted as `ADDRESS.ENCRYPTION@GATEWAY_1,GATEWAY_2,...`, with the first gateway being the primary one.
+/// Note that a standard single-gateway address is also a valid multi-homed address.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MultiHomedRecipient {
+    client_identity: ClientIdentity,
+    client_encryption_key: ClientEncryptionKey,
+    gateways: Vec<NodeIdentity>,
+}
+
+impl MultiHomedRecipient {
+    pub fn new(
+        client_identity: ClientIdentity,
+        client_encryption_key: ClientEncryptionKey,
+        gateways: Vec<NodeIdentity>,
+    ) -> Result<Self, RecipientFormattingError> {
+        if gateways.is_empty() {
+            return Err(RecipientFormattingError::NoGateways);
+        }
+
+        Ok(MultiHomedRecipient {
+            client_identity,
+            client_encryption_key,
+            gateways,
+        })
+    }
+
+    pub fn identity(&self) -> &ClientIdentity {
+        &self.client_identity
+    }
+
+    pub fn encryption_key(&self) -> &ClientEncryptionKey {
+        &self.client_encryption_key
+    }
+
+    pub fn gateways(&self) -> &[NodeIdentity] {
+        &self.gateways
+    }
+
+    /// Returns the address of the client going through its primary gateway.
+    pub fn primary(&self) -> Recipient {
+        // the constructor ensures there's always at least a single gateway
+        self.recipient_via(self.gateways[0])
+    }
+
+    /// Returns all single-gateway addresses of the client in the order of preference.
+    pub fn recipients(&self) -> impl Iterator<Item = Recipient> + '_ {
+        self.gateways
+            .iter()
+            .map(|gateway| self.recipient_via(*gateway))
+    }
+
+    /// Returns the most preferred address of the client going through a gateway deemed to be available.
+    pub fn select<F>(&self, mut is_available: F) -> Option<Recipient>
+    where
+        F: FnMut(&NodeIdentity) -> bool,
+    {
+        self.gateways
+            .iter()
+            .find(|gateway| is_available(gateway))
+            .map(|gateway| self.recipient_via(*gateway))
+    }
+
+    fn recipient_via(&self, gateway: NodeIdentity) -> Recipient {
+        Recipient::new(self.client_identity, self.client_encryption_key, gateway)
+    }
+
+    pub fn try_from_base58_string<S: Into<String>>(
+        full_address: S,
+    ) -> Result<Self, RecipientFormattingError> {
+        let string_address = full_address.into();
+        let Some((client_half, gateways_half)) = string_address.split_once('@') else {
+            return Err(RecipientFormattingError::MalformedRecipientError {
+                reason: "the string address does not contain a '@' character".to_string(),
+            });
+        };
+
+        let gateways = gateways_half
+            .split(',')
+            .map(|gateway| {
+                NodeIdentity::from_base58_string(gateway)
+                    .map_err(RecipientFormattingError::MalformedGatewayError)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // parse the client half the same way as for the single-gateway address
+        let primary = Recipient::try_from_base58_string(format!(
+            "{client_half}@{}",
+            gateways[0].to_base58_string()
+        ))?;
+
+        MultiHomedRecipient::new(
+            primary.client_identity,
+            primary.client_encryption_key,
+            gateways,
+        )
+    }
+}
+
+impl From<Recipient> for MultiHomedRecipient {
+    fn from(recipient: Recipient) -> Self {
+        MultiHomedRecipient {
+            client_identity: recipient.client_identity,
+            client_encryption_key: recipient.client_encryption_key,
+            gateways: vec![recipient.gateway],
+        }
+    }
+}
+
+// ADDRESS . ENCRYPTION @ GATEWAY_ID_1 , GATEWAY_ID_2 , ...
+impl std::fmt::Display for MultiHomedRecipient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}@",
+            self.client_identity.to_base58_string(),
+            self.client_encryption_key.to_base58_string(),
+        )?;
+        for (i, gateway) in self.gateways.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", gateway.to_base58_string())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for MultiHomedRecipient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // use the Display implementation
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
+
+impl FromStr for MultiHomedRecipient {
+    type Err = RecipientFormattingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        MultiHomedRecipient::try_from_base58_string(s)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -308,4 +457,39 @@ mod tests {
             recovered_recipient.gateway.to_bytes()
         );
     }
+
+    #[test]
+    fn multi_homed_string_conversion_works() {
+        let mut rng = rand::thread_rng();
+
+        let client_id_pair = identity::KeyPair::new(&mut rng);
+        let client_enc_pair = encryption::KeyPair::new(&mut rng);
+        let gateways = (0..3)
+            .map(|_| *identity::KeyPair::new(&mut rng).public_key())
+            .collect::<Vec<_>>();
+
+        let recipient = MultiHomedRecipient::new(
+            *client_id_pair.public_key(),
+            *client_enc_pair.public_key(),
+            gateways.clone(),
+        )
+        .unwrap();
+
+        let str_recipient = recipient.to_string();
+        let recovered_recipient =
+            MultiHomedRecipient::try_from_base58_string(str_recipient).unwrap();
+        assert_eq!(recipient, recovered_recipient);
+
+        // the primary address is a valid standard address
+        let primary = recipient.primary();
+        assert_eq!(primary.gateway(), &gateways[0]);
+        assert_eq!(
+            MultiHomedRecipient::from(primary),
+            MultiHomedRecipient::try_from_base58_string(primary.to_string()).unwrap()
+        );
+
+        let selected = recipient.select(|gateway| gateway != &gateways[0]).unwrap();
+        assert_eq!(selected.gateway(), &gateways[1]);
+        assert!(recipient.select(|_| false).is_none());
+    }
 }
@@ -0,0 +1,286 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::fragment::{Fragment, REPAIR_FRAGMENT_HEADER_LEN};
+use crate::ChunkingError;
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use std::collections::BTreeMap;
+
+// Each data fragment is turned into a shard by stripping the first 6 bytes of its header,
+// i.e. the flagged set id, total number of fragments and its position in the set,
+// as all of those can be trivially recomputed from the position of the shard itself.
+// What remains is the optional linking information and the actual payload.
+//
+// Note that this relies on all data fragments in the set being full-sized. This is always
+// the case for messages that were padded with `pad_to_full_packet_lengths` before being split.
+const DATA_SHARD_OFFSET: usize = REPAIR_FRAGMENT_HEADER_LEN;
+
+/// Length of each shard used for forward error correction of `FragmentSet`s,
+/// which is equivalent to the length of payload of each repair fragment.
+pub const fn repair_shard_len(max_plaintext_size: usize) -> usize {
+    max_plaintext_size - REPAIR_FRAGMENT_HEADER_LEN
+}
+
+fn data_shard(fragment: &Fragment, shard_len: usize) -> Result<Vec<u8>, ChunkingError> {
+    let mut shard = fragment.clone().into_bytes();
+    if shard.len() != shard_len + DATA_SHARD_OFFSET {
+        return Err(ChunkingError::NonFullFragmentForRepair {
+            position: fragment.current_fragment(),
+        });
+    }
+    shard.drain(..DATA_SHARD_OFFSET);
+    Ok(shard)
+}
+
+fn erasure_coder(data_shards: usize, parity_shards: usize) -> Result<ReedSolomon, ChunkingError> {
+    ReedSolomon::new(data_shards, parity_shards)
+        .map_err(|err| ChunkingError::ErasureCodingFailure(format!("{err:?}")))
+}
+
+/// Generates `num_repair` repair fragments for the provided `FragmentSet`,
+/// such that the receiver is going to be able to reconstruct the set using any
+/// `set.len()` fragments out of the total of `set.len() + num_repair`.
+///
+/// Since the positions of fragments in a set are represented with a single byte,
+/// the number of repair fragments is capped at `u8::MAX - set.len()`.
+pub fn generate_repair_fragments(
+    set: &[Fragment],
+    num_repair: u8,
+    max_plaintext_size: usize,
+) -> Result<Vec<Fragment>, ChunkingError> {
+    let Some(first) = set.first() else {
+        return Ok(Vec::new());
+    };
+    let set_id = first.id();
+    let total_fragments = first.total_fragments();
+    debug_assert_eq!(total_fragments as usize, set.len());
+
+    let num_repair = num_repair.min(u8::MAX - total_fragments);
+    if num_repair == 0 {
+        return Ok(Vec::new());
+    }
+
+    let shard_len = repair_shard_len(max_plaintext_size);
+    let mut shards = Vec::with_capacity(set.len() + num_repair as usize);
+    for fragment in set {
+        shards.push(data_shard(fragment, shard_len)?);
+    }
+    shards.resize(set.len() + num_repair as usize, vec![0u8; shard_len]);
+
+    erasure_coder(set.len(), num_repair as usize)?
+        .encode(&mut shards)
+        .map_err(|err| ChunkingError::ErasureCodingFailure(format!("{err:?}")))?;
+
+    shards
+        .into_iter()
+        .skip(set.len())
+        .zip(total_fragments + 1..=total_fragments + num_repair)
+        .map(|(shard, position)| Fragment::try_new_repair(shard, set_id, total_fragments, position))
+        .collect()
+}
+
+/// Attempts to recover all missing data fragments of a set using the received repair fragments.
+/// The caller must ensure the total number of received fragments is at least equal to the number
+/// of data fragments in the set.
+pub(crate) fn recover_missing_fragments(
+    fragments: &[Option<Fragment>],
+    repair_fragments: &BTreeMap<u8, Fragment>,
+) -> Result<Vec<Fragment>, ChunkingError> {
+    let Some(first_repair) = repair_fragments.values().next() else {
+        return Err(ChunkingError::ErasureCodingFailure(
+            "no repair fragments available".to_string(),
+        ));
+    };
+    let set_id = first_repair.id();
+    let total_fragments = first_repair.total_fragments();
+    let shard_len = first_repair.payload_size();
+    if total_fragments as usize != fragments.len() {
+        return Err(ChunkingError::MalformedHeaderError);
+    }
+
+    // the parity rows of the (systematic) vandermonde-based encoding matrix only depend on their
+    // own index, so we don't need to know how many repair fragments were actually produced,
+    // it's sufficient to account for the highest one we have received
+    let highest_repair = repair_fragments
+        .keys()
+        .next_back()
+        .copied()
+        .unwrap_or_default();
+    let num_repair = (highest_repair - total_fragments) as usize;
+
+    let mut shards = Vec::with_capacity(fragments.len() + num_repair);
+    for fragment in fragments {
+        shards.push(
+            fragment
+                .as_ref()
+                .map(|fragment| data_shard(fragment, shard_len))
+                .transpose()?,
+        )
+    }
+    shards.resize(fragments.len() + num_repair, None);
+    for (position, repair_fragment) in repair_fragments {
+        if repair_fragment.id() != set_id
+            || repair_fragment.total_fragments() != total_fragments
+            || repair_fragment.payload_size() != shard_len
+        {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+        shards[*position as usize - 1] = Some(repair_fragment.payload().to_vec());
+    }
+
+    erasure_coder(fragments.len(), num_repair)?
+        .reconstruct_data(&mut shards)
+        .map_err(|err| ChunkingError::ErasureCodingFailure(format!("{err:?}")))?;
+
+    let flagged_id = (set_id | (1 << 31)).to_be_bytes();
+    fragments
+        .iter()
+        .zip(shards)
+        .zip(1..=total_fragments)
+        .filter(|((fragment, _), _)| fragment.is_none())
+        .map(|((_, shard), position)| {
+            // reconstruct_data guarantees all data shards are present on success
+            let shard = shard.unwrap_or_default();
+            let raw_fragment: Vec<_> = flagged_id
+                .into_iter()
+                .chain(std::iter::once(total_fragments))
+                .chain(std::iter::once(position))
+                .chain(shard)
+                .collect();
+            Fragment::try_from_bytes(&raw_fragment)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split_into_sets;
+    use rand::rngs::OsRng;
+    use rand::RngCore;
+
+    // just some arbitrary value to use in tests
+    const AVAILABLE_PLAINTEXT_SIZE: usize = 1024;
+
+    fn full_set(num_fragments: usize) -> Vec<Fragment> {
+        let len = num_fragments
+            * crate::fragment::unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE);
+        let mut message = vec![0u8; len];
+        OsRng.fill_bytes(&mut message);
+        split_into_sets(&mut OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn generates_requested_number_of_full_sized_repair_fragments() {
+        let set = full_set(10);
+        let repair = generate_repair_fragments(&set, 4, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+
+        assert_eq!(4, repair.len());
+        for (i, fragment) in repair.iter().enumerate() {
+            assert!(fragment.is_repair());
+            assert_eq!(set[0].id(), fragment.id());
+            assert_eq!(11 + i as u8, fragment.current_fragment());
+            assert_eq!(AVAILABLE_PLAINTEXT_SIZE, fragment.serialized_size());
+        }
+    }
+
+    #[test]
+    fn number_of_repair_fragments_is_capped() {
+        let set = full_set(250);
+        let repair = generate_repair_fragments(&set, 10, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+        assert_eq!(5, repair.len());
+
+        let set = full_set(255);
+        let repair = generate_repair_fragments(&set, 10, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+        assert!(repair.is_empty());
+    }
+
+    #[test]
+    fn repair_fragments_cannot_be_generated_for_non_full_sets() {
+        let message = vec![42u8; 100];
+        let set = split_into_sets(&mut OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+            .pop()
+            .unwrap();
+        assert!(generate_repair_fragments(&set, 2, AVAILABLE_PLAINTEXT_SIZE).is_err());
+    }
+
+    #[test]
+    fn missing_fragments_can_be_recovered_from_any_subset_of_sufficient_size() {
+        let set = full_set(10);
+        let repair = generate_repair_fragments(&set, 5, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+
+        let mut received: Vec<_> = set.iter().cloned().map(Some).collect();
+        received[0] = None;
+        received[4] = None;
+        received[9] = None;
+
+        // only use some of the repair fragments (including the last one)
+        let repair_fragments: BTreeMap<_, _> = repair
+            .into_iter()
+            .filter(|f| f.current_fragment() != 12)
+            .map(|f| (f.current_fragment(), f))
+            .collect();
+
+        let recovered = recover_missing_fragments(&received, &repair_fragments).unwrap();
+        assert_eq!(
+            vec![set[0].clone(), set[4].clone(), set[9].clone()],
+            recovered
+        );
+    }
+
+    #[test]
+    fn linked_fragments_can_be_recovered() {
+        let len = crate::set::max_one_way_linked_set_payload_length(AVAILABLE_PLAINTEXT_SIZE)
+            + crate::fragment::linked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE)
+            + 9 * crate::fragment::unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE);
+        let mut message = vec![0u8; len];
+        OsRng.fill_bytes(&mut message);
+        let sets = split_into_sets(&mut OsRng, &message, AVAILABLE_PLAINTEXT_SIZE);
+
+        // the first, post-linked, set is full so there's no space left for any repair fragments
+        assert!(
+            generate_repair_fragments(&sets[0], 2, AVAILABLE_PLAINTEXT_SIZE)
+                .unwrap()
+                .is_empty()
+        );
+
+        // but the last one, pre-linked, can be protected
+        let set = &sets[1];
+        let repair = generate_repair_fragments(set, 2, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+
+        let mut received: Vec<_> = set.iter().cloned().map(Some).collect();
+        received[0] = None;
+
+        let repair_fragments: BTreeMap<_, _> = repair
+            .into_iter()
+            .take(1)
+            .map(|f| (f.current_fragment(), f))
+            .collect();
+
+        let recovered = recover_missing_fragments(&received, &repair_fragments).unwrap();
+        assert_eq!(vec![set[0].clone()], recovered);
+        assert_eq!(
+            Some(sets[0][0].id()),
+            recovered[0].previous_fragments_set_id()
+        );
+    }
+
+    #[test]
+    fn recovery_fails_with_insufficient_number_of_fragments() {
+        let set = full_set(10);
+        let repair = generate_repair_fragments(&set, 1, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+
+        let mut received: Vec<_> = set.iter().cloned().map(Some).collect();
+        received[0] = None;
+        received[1] = None;
+
+        let repair_fragments: BTreeMap<_, _> = repair
+            .into_iter()
+            .map(|f| (f.current_fragment(), f))
+            .collect();
+
+        assert!(recover_missing_fragments(&received, &repair_fragments).is_err());
+    }
+}
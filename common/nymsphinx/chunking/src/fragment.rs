@@ -34,6 +34,12 @@ pub const UNLINKED_FRAGMENTED_HEADER_LEN: usize = 7;
 /// `Fragment` in a `FragmentSet`.
 pub const LINKED_FRAGMENTED_HEADER_LEN: usize = 10;
 
+/// Repair fragments, used for forward error correction, do not need any linking information
+/// as they're always associated with a single `FragmentSet`. Their header only consists of
+/// the 4 byte set id (with the cleared flag bit), 1 byte to represent total number of data fragments
+/// in the set and 1 byte to represent position of the current fragment.
+pub const REPAIR_FRAGMENT_HEADER_LEN: usize = 6;
+
 /// Maximum size of payload of each fragment is always the maximum amount of plaintext data
 /// we can put into a sphinx packet minus length of respective fragment header.
 pub const fn unlinked_fragment_payload_max_len(max_plaintext_size: usize) -> usize {
@@ -216,11 +222,36 @@ impl Fragment {
         }
     }
 
+    /// Tries to encapsulate provided repair shard into a `Fragment`.
+    /// It can fail if the metadata is malformed, for example if current_fragment <= total_fragments.
+    pub(crate) fn try_new_repair(
+        shard: Vec<u8>,
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+    ) -> Result<Self, ChunkingError> {
+        Ok(Fragment {
+            header: FragmentHeader::try_new_repair(id, total_fragments, current_fragment)?,
+            payload: shard,
+        })
+    }
+
     /// Gets the size of payload contained in this `Fragment`.
     pub fn payload_size(&self) -> usize {
         self.payload.len()
     }
 
+    /// Gets reference to the payload contained in this `Fragment`.
+    pub(crate) fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Determines whether this `Fragment` contains a repair shard rather than part of the actual
+    /// message, i.e. whether it was produced for the purposes of forward error correction.
+    pub fn is_repair(&self) -> bool {
+        self.header.is_repair()
+    }
+
     /// Extracts id of this `Fragment`.
     pub fn id(&self) -> i32 {
         self.header.id
@@ -292,6 +323,13 @@ impl Fragment {
 /// where the set is linked to either preceding data (TF == 1) or proceeding data (TF == CF == 255)
 /// '1'bit || 31-bit ID || 1-byte TF || 1 byte CF || '1'bit || 31-bit LID
 ///
+/// Finally, if the sender opted into forward error correction, each set might be followed by
+/// a number of repair fragments, whose header is represented by the following 6 byte sequence:
+/// '0'bit || 31-bit ID || 1-byte TF || 1 byte CF
+/// where CF > TF, i.e. the repair fragments are positioned after all data fragments in the set.
+/// Note that the cleared flag bit guarantees older receivers are going to reject such fragments
+/// rather than misinterpreting them as part of the message.
+///
 /// And hence for messages larger than `max_plaintext_size` but small enough
 /// to avoid set division (which happens if message has to be fragmented into more than 255 fragments)
 /// there is 7 bytes of overhead inside each sphinx packet sent
@@ -366,11 +404,37 @@ impl FragmentHeader {
         })
     }
 
+    /// Tries to create a new `FragmentHeader` of a repair fragment using provided metadata.
+    /// As opposed to the data fragments, current_fragment must be higher than total_fragments.
+    fn try_new_repair(
+        id: i32,
+        total_fragments: u8,
+        current_fragment: u8,
+    ) -> Result<Self, ChunkingError> {
+        if id <= 0 || total_fragments == 0 || current_fragment <= total_fragments {
+            return Err(ChunkingError::MalformedHeaderError);
+        }
+
+        Ok(FragmentHeader {
+            id,
+            total_fragments,
+            current_fragment,
+            previous_fragments_set_id: None,
+            next_fragments_set_id: None,
+        })
+    }
+
+    /// Determines whether this header belongs to a repair fragment.
+    fn is_repair(&self) -> bool {
+        self.current_fragment > self.total_fragments
+    }
+
     /// Tries to recover `FragmentHeader` from slice of bytes extracted from received sphinx packet.
     /// If successful, returns `Self` and number of bytes used, as those can differ based on the
     /// type of header (unlinked or linked).
     fn try_from_bytes(b: &[u8]) -> Result<(Self, usize), ChunkingError> {
         // header needs to be at least 7 bytes long
+        // (repair fragments have shorter headers, but they're always followed by non-empty shards)
         if b.len() < UNLINKED_FRAGMENTED_HEADER_LEN {
             return Err(ChunkingError::TooShortFragmentHeader {
                 received: b.len(),
@@ -378,9 +442,12 @@ impl FragmentHeader {
             });
         }
         let frag_id = i32::from_be_bytes(b[0..4].try_into().unwrap());
-        // sanity check for the fragmentation flag
+        // cleared fragmentation flag indicates a repair fragment
         if ((frag_id >> 31) & 1) == 0 {
-            return Err(ChunkingError::MalformedHeaderError);
+            return Ok((
+                Self::try_new_repair(frag_id, b[4], b[5])?,
+                REPAIR_FRAGMENT_HEADER_LEN,
+            ));
         }
 
         let id = frag_id & !(1 << 31); // make sure to clear the flag bit to parse id correctly
@@ -439,6 +506,16 @@ impl FragmentHeader {
 
     /// Marshal this `FragmentHeader` into vector of bytes which can be put into a sphinx packet.
     fn to_bytes(&self) -> Vec<u8> {
+        if self.is_repair() {
+            return self
+                .id
+                .to_be_bytes()
+                .into_iter()
+                .chain(std::iter::once(self.total_fragments))
+                .chain(std::iter::once(self.current_fragment))
+                .collect();
+        }
+
         let frag_id = self.id | (1 << 31);
         let frag_id_bytes = frag_id.to_be_bytes();
         let bytes_prefix_iter = frag_id_bytes
@@ -606,6 +683,24 @@ mod fragment_tests {
         assert_eq!(fragment, Fragment::try_from_bytes(&packet_bytes).unwrap());
     }
 
+    #[test]
+    fn can_be_converted_to_and_from_bytes_for_repair_payload() {
+        let mut rng = thread_rng();
+
+        let mut shard = vec![0u8; max_plaintext_size() - REPAIR_FRAGMENT_HEADER_LEN];
+        rng.fill_bytes(&mut shard);
+
+        let repair_fragment = Fragment::try_new_repair(shard, 12345, 10, 13).unwrap();
+        assert!(repair_fragment.is_repair());
+
+        let packet_bytes = repair_fragment.clone().into_bytes();
+        assert_eq!(max_plaintext_size(), packet_bytes.len());
+        assert_eq!(
+            repair_fragment,
+            Fragment::try_from_bytes(&packet_bytes).unwrap()
+        );
+    }
+
     #[test]
     fn unlinked_fragment_can_be_created_with_payload_of_valid_length() {
         let id = 12345;
@@ -1026,6 +1121,37 @@ mod fragment_header {
         }
     }
 
+    #[cfg(test)]
+    mod repair_payload {
+        use super::*;
+
+        #[test]
+        fn can_be_converted_to_and_from_bytes_for_more_than_required_number_of_bytes() {
+            let repair_header = FragmentHeader::try_new_repair(12345, 10, 11).unwrap();
+
+            let mut header_bytes = repair_header.to_bytes();
+            header_bytes.append(vec![1, 2, 3, 4, 5].as_mut());
+
+            let (recovered_header, bytes_used) =
+                FragmentHeader::try_from_bytes(&header_bytes).unwrap();
+            assert_eq!(repair_header, recovered_header);
+            assert_eq!(REPAIR_FRAGMENT_HEADER_LEN, bytes_used);
+        }
+
+        #[test]
+        fn creation_of_header_fails_if_current_fragment_is_not_higher_than_total() {
+            assert!(FragmentHeader::try_new_repair(12345, 10, 10).is_err());
+            assert!(FragmentHeader::try_new_repair(12345, 10, 5).is_err());
+        }
+
+        #[test]
+        fn creation_of_header_fails_for_invalid_id_or_total() {
+            assert!(FragmentHeader::try_new_repair(0, 10, 11).is_err());
+            assert!(FragmentHeader::try_new_repair(-10, 10, 11).is_err());
+            assert!(FragmentHeader::try_new_repair(12345, 0, 1).is_err());
+        }
+    }
+
     #[cfg(test)]
     mod linked_fragmented_payload {
         use super::*;
@@ -18,6 +18,7 @@ pub const MIN_PADDING_OVERHEAD: usize = 1;
 // they should definitely be revisited.
 // For instance there are not tests for the cases when we are padding the message
 
+pub mod fec;
 pub mod fragment;
 pub mod reconstruction;
 pub mod set;
@@ -68,6 +69,12 @@ pub enum ChunkingError {
 
     #[error("Received fragment identifier ({received}) is not a valid value!")]
     MalformedFragmentIdentifier { received: i32 },
+
+    #[error("Fragment at position {position} is not full-sized and thus can't be used for forward error correction")]
+    NonFullFragmentForRepair { position: u8 },
+
+    #[error("Failed to perform erasure coding of the fragment set: {0}")]
+    ErasureCodingFailure(String),
 }
 
 /// Returns number of fragments the message will be split to as well as number of available
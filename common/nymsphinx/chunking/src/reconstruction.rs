@@ -1,9 +1,20 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
+use crate::fec;
 use crate::fragment::Fragment;
 use crate::ChunkingError;
 use log::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+/// Specifies how often the `MessageReconstructor` checks for any stale sets.
+const STALE_SETS_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 
 // TODO: perhaps a more sophisticated approach with writing to disk periodically in case
 // we're receiving fast & furious in uncompressed 4K - we don't want to keep that in memory;
@@ -31,6 +42,18 @@ struct ReconstructionBuffer {
     /// appropriately resized and all missing fragments are set to a `None`, thus keeping
     /// everything in order the whole time, allowing for O(1) insertions and O(n) reconstruction.
     fragments: Vec<Option<Fragment>>,
+
+    /// Repair `Fragment`s, indexed by their position, received for this set if the sender
+    /// used forward error correction. They're used for recovering any missing data fragments
+    /// once sufficient number of fragments has been received.
+    repair_fragments: BTreeMap<u8, Fragment>,
+
+    /// Indicates the received repair `Fragment`s turned out to be unusable, in which case
+    /// any further ones are ignored and the set has to be completed with the data fragments alone.
+    repair_failed: bool,
+
+    /// Time at which the most recent `Fragment` of this set has been received.
+    last_activity: Instant,
 }
 
 /// Type alias representing fully reconstructed message - its original data and list of all
@@ -56,6 +79,9 @@ impl ReconstructionBuffer {
             previous_fragments_set_id: None,
             next_fragments_set_id: None,
             fragments: fragments_buffer,
+            repair_fragments: BTreeMap::new(),
+            repair_failed: false,
+            last_activity: Instant::now(),
         }
     }
 
@@ -82,17 +108,65 @@ impl ReconstructionBuffer {
         !self.fragments.contains(&None)
     }
 
+    /// Returns the total number of payload bytes (including any repair data) held by this buffer.
+    fn buffered_bytes(&self) -> usize {
+        self.fragments
+            .iter()
+            .flatten()
+            .chain(self.repair_fragments.values())
+            .map(|fragment| fragment.payload_size())
+            .sum()
+    }
+
+    /// If we have received any repair `Fragment`s and the total number of received fragments
+    /// is sufficient, attempts to recover all the missing data fragments.
+    fn try_recover_missing_fragments(&mut self) {
+        if self.repair_fragments.is_empty() {
+            return;
+        }
+
+        let received = self.fragments.iter().filter(|frag| frag.is_some()).count();
+        if received + self.repair_fragments.len() < self.fragments.len() {
+            return;
+        }
+
+        match fec::recover_missing_fragments(&self.fragments, &self.repair_fragments) {
+            Ok(recovered) => {
+                debug!(
+                    "recovered {} missing fragments using forward error correction",
+                    recovered.len()
+                );
+                for fragment in recovered {
+                    let fragment_index = fragment.current_fragment() as usize - 1;
+                    self.fragments[fragment_index] = Some(fragment);
+                }
+            }
+            Err(err) => {
+                // the failure is not going to go away with more fragments,
+                // so make sure we don't attempt (and complain about) it again for this set
+                warn!("failed to recover missing fragments using the repair data: {err}. Any further repair fragments of this set are going to be ignored");
+                self.repair_fragments.clear();
+                self.repair_failed = true;
+            }
+        }
+    }
+
     /// Inserts new `Fragment` data into an appropriate position in the buffer.
     ///
     /// (Note: currently there is no defined behaviour for dealing with duplicate
     /// fragments for the same position in the set. This might potentially corrupt
     /// entire message until resolved)
     ///
+    /// If the `Fragment` is a repair fragment, it is stored separately and, alongside the received
+    /// data fragments, used for recovering any missing ones once enough of them were received.
+    ///
     /// After new `Fragment` is inserted, it is checked whether the buffer should be
     /// done receiving and if so, the auxiliary data fields, i.e. `is_complete`,
     /// `previous_fragments_set_id` and `next_fragments_set_id` are set for the ease
     /// of access.
     fn insert_fragment(&mut self, fragment: Fragment) {
+        self.last_activity = Instant::now();
+
         // all fragments in the buffer should always have the same id as before inserting an element,
         // the correct buffer instance is looked up based on the fragment to be inserted.
         debug_assert!({
@@ -104,7 +178,26 @@ impl ReconstructionBuffer {
             }
         });
 
+        if fragment.is_repair() {
+            if !self.is_complete && !self.repair_failed {
+                self.repair_fragments
+                    .insert(fragment.current_fragment(), fragment);
+                self.try_recover_missing_fragments();
+                self.check_completion();
+            }
+            return;
+        }
+
         let fragment_index = fragment.current_fragment() as usize - 1;
+        if self.fragments[fragment_index].is_some() && !self.repair_fragments.is_empty() {
+            // the fragment has already been recovered using the repair data
+            debug!(
+                "received already recovered fragment - frag - {} (set id: {})",
+                fragment.current_fragment(),
+                fragment.id()
+            );
+            return;
+        }
         if self.fragments[fragment_index].is_some() {
             // TODO: what to do in that case? give up on the message? overwrite it? panic?
             // it *might* be due to lock ack-packet, but let's keep the `warn` level in case
@@ -116,6 +209,14 @@ impl ReconstructionBuffer {
             );
         }
         self.fragments[fragment_index] = Some(fragment);
+        if !self.is_done_receiving() {
+            self.try_recover_missing_fragments();
+        }
+        self.check_completion();
+    }
+
+    /// Checks if the buffer is done receiving and if so, sets the auxiliary data fields.
+    fn check_completion(&mut self) {
         if self.is_done_receiving() {
             self.is_complete = true;
             self.previous_fragments_set_id = self.fragments[0]
@@ -134,19 +235,50 @@ impl ReconstructionBuffer {
     }
 }
 
+/// Limits on the resources the `MessageReconstructor` is allowed to use for holding
+/// incomplete messages. Without them, anybody could keep on sending maximum sized sets
+/// without one of the required fragments, making the receiver keep all of that data indefinitely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconstructionLimits {
+    /// Maximum amount of time an incomplete set is kept for since receiving its most recent fragment.
+    pub maximum_set_inactivity: Duration,
+
+    /// Maximum number of bytes held by all incomplete sets.
+    /// Once exceeded, the least recently updated sets are dropped.
+    pub maximum_buffered_bytes: usize,
+}
+
+impl ReconstructionLimits {
+    pub const DEFAULT_MAXIMUM_SET_INACTIVITY: Duration = Duration::from_secs(10 * 60);
+    pub const DEFAULT_MAXIMUM_BUFFERED_BYTES: usize = 128 * 1024 * 1024;
+}
+
+impl Default for ReconstructionLimits {
+    fn default() -> Self {
+        ReconstructionLimits {
+            maximum_set_inactivity: Self::DEFAULT_MAXIMUM_SET_INACTIVITY,
+            maximum_buffered_bytes: Self::DEFAULT_MAXIMUM_BUFFERED_BYTES,
+        }
+    }
+}
+
 /// High level public structure used to buffer all received data `Fragment`s and eventually
 /// returning original messages that they encapsulate.
-#[derive(Default, PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Default)]
 pub struct MessageReconstructor {
-    // TODO: some cleaner thread/routine that if message is incomplete and
-    // we haven't received any fragments in X time, we assume they
-    // were lost and message can't be restored.
-    // Perhaps add 'latest_fragment_timestamp' to each buffer
-    // and after N fragments received globally, check all of buffer timestamps.
-    // otherwise we are vulnerable to heap overflow attacks -> somebody can keep on sending
-    // maximum sized sets but without one of required fragments. All of the received
-    // data will be kept on the heap indefinitely in the current implementation.
     reconstructed_sets: HashMap<i32, ReconstructionBuffer>,
+
+    /// Limits imposed on the incomplete sets held by this `MessageReconstructor`.
+    limits: ReconstructionLimits,
+
+    /// Total number of bytes currently held by all `ReconstructionBuffer`s.
+    buffered_bytes: usize,
+
+    /// Time at which we last checked for any stale sets.
+    last_stale_check: Option<Instant>,
+
+    /// Number of incomplete sets dropped since the last call to `take_dropped_sets`.
+    dropped_sets: usize,
 }
 
 impl MessageReconstructor {
@@ -155,6 +287,63 @@ impl MessageReconstructor {
         Default::default()
     }
 
+    /// Changes the limits imposed on the incomplete sets held by this `MessageReconstructor`.
+    pub fn set_limits(&mut self, limits: ReconstructionLimits) {
+        self.limits = limits;
+        self.enforce_size_limit();
+    }
+
+    /// Returns the number of incomplete sets that got dropped, due to either being stale
+    /// or exceeding the size limit, since the last call to this method.
+    pub fn take_dropped_sets(&mut self) -> usize {
+        std::mem::take(&mut self.dropped_sets)
+    }
+
+    /// Removes the set of given `id` alongside all of its buffered data.
+    fn drop_set(&mut self, id: i32) {
+        if let Some(buf) = self.reconstructed_sets.remove(&id) {
+            self.buffered_bytes = self.buffered_bytes.saturating_sub(buf.buffered_bytes());
+            self.dropped_sets += 1;
+        }
+    }
+
+    /// Drops all sets that have not received any new fragments within the allowed time.
+    fn remove_stale_sets(&mut self, now: Instant) {
+        let stale: Vec<_> = self
+            .reconstructed_sets
+            .iter()
+            .filter(|(_, buf)| {
+                now.duration_since(buf.last_activity) > self.limits.maximum_set_inactivity
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        if !stale.is_empty() {
+            debug!("dropping {} stale incomplete sets", stale.len());
+        }
+        for id in stale {
+            self.drop_set(id)
+        }
+    }
+
+    /// Drops the least recently updated sets until the total buffered size is within the limit.
+    fn enforce_size_limit(&mut self) {
+        while self.buffered_bytes > self.limits.maximum_buffered_bytes {
+            let Some(oldest) = self
+                .reconstructed_sets
+                .iter()
+                .min_by_key(|(_, buf)| buf.last_activity)
+                .map(|(id, _)| *id)
+            else {
+                return;
+            };
+            warn!(
+                "exceeded the maximum size of buffered incomplete messages. dropping set {oldest}"
+            );
+            self.drop_set(oldest)
+        }
+    }
+
     /// Given fully received set of given `id`, if it has any post-linked sets, recursively
     /// checks if all of them were also fully received.
     fn check_front_chain(&self, id: i32) -> bool {
@@ -237,10 +426,9 @@ impl MessageReconstructor {
     /// Note, before you call this method, you *must* ensure set was fully received
     fn extract_set_payload(&mut self, set_id: i32) -> Vec<u8> {
         debug_assert!(self.is_set_fully_received(set_id));
-        self.reconstructed_sets
-            .remove(&set_id)
-            .unwrap()
-            .reconstruct_set_data()
+        let buf = self.reconstructed_sets.remove(&set_id).unwrap();
+        self.buffered_bytes = self.buffered_bytes.saturating_sub(buf.buffered_bytes());
+        buf.reconstruct_set_data()
     }
 
     // Future consideration: perhaps for long messages, rather than return whole data allocated
@@ -267,7 +455,19 @@ impl MessageReconstructor {
     /// If a buffer does not exist, a new instance is created.
     /// If it was last remaining `Fragment` for the original message, the message is reconstructed
     /// and returned alongside all (if applicable) set ids used in the message.
+    /// Furthermore, it periodically drops any stale sets and, if the total size of the buffered
+    /// data exceeds the limit, the least recently updated sets.
     pub fn insert_new_fragment(&mut self, fragment: Fragment) -> Option<ReconstructedMessage> {
+        let now = Instant::now();
+        let should_check = self
+            .last_stale_check
+            .map(|last_check| now.duration_since(last_check) >= STALE_SETS_CHECK_INTERVAL)
+            .unwrap_or(true);
+        if should_check {
+            self.remove_stale_sets(now);
+            self.last_stale_check = Some(now);
+        }
+
         let set_id = fragment.id();
         let set_len = fragment.total_fragments();
 
@@ -276,10 +476,15 @@ impl MessageReconstructor {
             .entry(set_id)
             .or_insert_with(|| ReconstructionBuffer::new(set_len));
 
+        let size_before = buf.buffered_bytes();
         buf.insert_fragment(fragment);
+        let size_after = buf.buffered_bytes();
+        self.buffered_bytes = self.buffered_bytes.saturating_sub(size_before) + size_after;
+
         if self.is_message_fully_received(set_id) {
             Some(self.reconstruct_message(set_id))
         } else {
+            self.enforce_size_limit();
             None
         }
     }
@@ -861,6 +1066,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: None,
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
 
@@ -871,6 +1077,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: None,
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
 
@@ -998,6 +1205,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: Some(1234),
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
 
@@ -1008,6 +1216,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(12345),
                 next_fragments_set_id: Some(123),
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
 
@@ -1018,6 +1227,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(1234),
                 next_fragments_set_id: Some(12),
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
 
@@ -1028,6 +1238,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(123),
                 next_fragments_set_id: None,
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
 
@@ -1075,6 +1286,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: Some(1234),
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
         reconstructor.reconstructed_sets.insert(
@@ -1084,6 +1296,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(12345),
                 next_fragments_set_id: None,
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
         assert_eq!(reconstructor.previous_linked_set_id(12345), None);
@@ -1131,6 +1344,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: None,
                 next_fragments_set_id: Some(1234),
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
         reconstructor.reconstructed_sets.insert(
@@ -1140,6 +1354,7 @@ mod message_reconstructor {
                 previous_fragments_set_id: Some(12345),
                 next_fragments_set_id: None,
                 fragments: vec![],
+                ..ReconstructionBuffer::new(1)
             },
         );
         assert_eq!(reconstructor.next_linked_set_id(12345), Some(1234));
@@ -1330,6 +1545,96 @@ mod message_reconstructor {
     }
 }
 
+#[cfg(test)]
+mod reconstruction_limits {
+    use super::*;
+    use crate::fragment::unlinked_fragment_payload_max_len;
+
+    // just some arbitrary value to use in tests
+    const AVAILABLE_PLAINTEXT_SIZE: usize = 1024;
+
+    fn incomplete_set_fragment() -> Fragment {
+        let message = vec![42u8; unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE) * 3];
+        crate::split_into_sets(&mut rand::rngs::OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+            .pop()
+            .unwrap()
+            .pop()
+            .unwrap()
+    }
+
+    #[test]
+    fn stale_sets_are_dropped() {
+        let mut reconstructor = MessageReconstructor::default();
+        let fragment = incomplete_set_fragment();
+        let set_id = fragment.id();
+
+        assert!(reconstructor.insert_new_fragment(fragment).is_none());
+        assert!(reconstructor.buffered_bytes > 0);
+
+        // nothing should happen if the set is still fresh
+        reconstructor.remove_stale_sets(Instant::now());
+        assert!(reconstructor.reconstructed_sets.contains_key(&set_id));
+        assert_eq!(0, reconstructor.take_dropped_sets());
+
+        let future = Instant::now()
+            + ReconstructionLimits::DEFAULT_MAXIMUM_SET_INACTIVITY
+            + Duration::from_secs(1);
+        reconstructor.remove_stale_sets(future);
+        assert!(!reconstructor.reconstructed_sets.contains_key(&set_id));
+        assert_eq!(0, reconstructor.buffered_bytes);
+        assert_eq!(1, reconstructor.take_dropped_sets());
+        assert_eq!(0, reconstructor.take_dropped_sets());
+    }
+
+    #[test]
+    fn least_recently_updated_sets_are_dropped_when_exceeding_size_limit() {
+        let mut reconstructor = MessageReconstructor::default();
+        reconstructor.set_limits(ReconstructionLimits {
+            maximum_buffered_bytes: 2 * unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE),
+            ..Default::default()
+        });
+
+        let fragment1 = incomplete_set_fragment();
+        let fragment2 = incomplete_set_fragment();
+        let fragment3 = incomplete_set_fragment();
+        let (id1, id2, id3) = (fragment1.id(), fragment2.id(), fragment3.id());
+
+        reconstructor.insert_new_fragment(fragment1);
+        reconstructor.insert_new_fragment(fragment2);
+
+        // make sure the first set is the least recently updated one
+        reconstructor
+            .reconstructed_sets
+            .get_mut(&id1)
+            .unwrap()
+            .last_activity -= Duration::from_secs(10);
+
+        reconstructor.insert_new_fragment(fragment3);
+        assert!(!reconstructor.reconstructed_sets.contains_key(&id1));
+        assert!(reconstructor.reconstructed_sets.contains_key(&id2));
+        assert!(reconstructor.reconstructed_sets.contains_key(&id3));
+        assert_eq!(1, reconstructor.take_dropped_sets());
+    }
+
+    #[test]
+    fn reconstructed_sets_are_no_longer_accounted_for() {
+        let mut reconstructor = MessageReconstructor::default();
+        let message = vec![42u8; unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE) * 3];
+        let fragments =
+            crate::split_into_sets(&mut rand::rngs::OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+                .pop()
+                .unwrap();
+
+        let mut reconstructed = None;
+        for fragment in fragments {
+            reconstructed = reconstructor.insert_new_fragment(fragment);
+        }
+        assert_eq!(message, reconstructed.unwrap().0);
+        assert_eq!(0, reconstructor.buffered_bytes);
+        assert_eq!(0, reconstructor.take_dropped_sets());
+    }
+}
+
 #[cfg(test)]
 mod message_reconstruction {
     use super::*;
@@ -1843,4 +2148,121 @@ mod message_reconstruction {
             }
         }
     }
+
+    #[cfg(test)]
+    mod forward_error_correction {
+        use super::*;
+        use crate::fec::generate_repair_fragments;
+        use crate::fragment::{linked_fragment_payload_max_len, unlinked_fragment_payload_max_len};
+        use crate::set::max_one_way_linked_set_payload_length;
+
+        #[test]
+        fn it_reconstructs_message_with_lost_fragments_using_repair_fragments() {
+            let mut rng = thread_rng();
+
+            // a full set, which can't have any repair fragments, followed by a short, pre-linked, one
+            let mut message =
+                vec![
+                    0u8;
+                    max_one_way_linked_set_payload_length(AVAILABLE_PLAINTEXT_SIZE)
+                        + linked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE)
+                        + 9 * unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE)
+                ];
+            rng.fill_bytes(&mut message);
+
+            let mut sets =
+                crate::split_into_sets(&mut rand::rngs::OsRng, &message, AVAILABLE_PLAINTEXT_SIZE);
+            assert_eq!(sets.len(), 2);
+            let mut last_set = sets.pop().unwrap();
+            let mut fragments = sets.pop().unwrap();
+
+            let repair = generate_repair_fragments(&last_set, 3, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+            // lose some of the data fragments, including the linked one
+            last_set.remove(0);
+            last_set.shuffle(&mut rng);
+            last_set.truncate(last_set.len() - 2);
+            fragments.append(&mut last_set);
+            fragments.extend(repair);
+            fragments.shuffle(&mut rng);
+
+            let mut message_reconstructor = MessageReconstructor::default();
+            let mut reconstructed = None;
+            for fragment in fragments.into_iter() {
+                if let Some(msg) = message_reconstructor.insert_new_fragment(
+                    message_reconstructor
+                        .recover_fragment(fragment.into_bytes())
+                        .unwrap(),
+                ) {
+                    assert!(reconstructed.is_none());
+                    reconstructed = Some(msg);
+                }
+            }
+
+            let reconstructed = reconstructed.unwrap();
+            assert_eq!(reconstructed.0, message);
+            assert_eq!(reconstructed.1.len(), 2);
+        }
+
+        #[test]
+        fn it_ignores_repair_fragments_after_failing_to_use_them() {
+            let mut rng = thread_rng();
+
+            let mut message =
+                vec![0u8; 10 * unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE)];
+            rng.fill_bytes(&mut message);
+
+            let mut set =
+                crate::split_into_sets(&mut rand::rngs::OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+                    .pop()
+                    .unwrap();
+            let id = set[0].id();
+            let mut repair = generate_repair_fragments(&set, 2, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+            let last_fragment = set.pop().unwrap();
+
+            let mut buffer = ReconstructionBuffer::new(10);
+            for fragment in set {
+                buffer.insert_fragment(fragment);
+            }
+
+            // a repair fragment with a mismatched shard can't possibly be used for the recovery
+            let malformed = Fragment::try_new_repair(vec![42; 10], id, 10, 11).unwrap();
+            buffer.insert_fragment(malformed);
+            assert!(!buffer.is_complete);
+            assert!(buffer.repair_failed);
+            assert!(buffer.repair_fragments.is_empty());
+
+            // even if otherwise it would have been sufficient
+            buffer.insert_fragment(repair.pop().unwrap());
+            assert!(!buffer.is_complete);
+            assert!(buffer.repair_fragments.is_empty());
+
+            // but the set can still be completed with the data fragments
+            buffer.insert_fragment(last_fragment);
+            assert!(buffer.is_complete);
+            assert_eq!(buffer.reconstruct_set_data(), message);
+        }
+
+        #[test]
+        fn it_does_not_reconstruct_message_with_too_many_lost_fragments() {
+            let mut rng = thread_rng();
+
+            let mut message =
+                vec![0u8; 10 * unlinked_fragment_payload_max_len(AVAILABLE_PLAINTEXT_SIZE)];
+            rng.fill_bytes(&mut message);
+
+            let mut set =
+                crate::split_into_sets(&mut rand::rngs::OsRng, &message, AVAILABLE_PLAINTEXT_SIZE)
+                    .pop()
+                    .unwrap();
+            let repair = generate_repair_fragments(&set, 2, AVAILABLE_PLAINTEXT_SIZE).unwrap();
+            set.truncate(7);
+
+            let mut message_reconstructor = MessageReconstructor::default();
+            for fragment in set.into_iter().chain(repair) {
+                assert!(message_reconstructor
+                    .insert_new_fragment(fragment)
+                    .is_none())
+            }
+        }
+    }
 }
@@ -0,0 +1,130 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default length of a single sphinx key rotation.
+pub const DEFAULT_SPHINX_KEY_ROTATION_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Default amount of time a sphinx key is still accepted after its rotation has ended.
+pub const DEFAULT_SPHINX_KEY_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// Network-wide schedule of sphinx key rotations.
+///
+/// Rotations are aligned to the unix epoch, so that all nodes and clients using the same schedule
+/// agree on the rotation ids without any coordination. The key of rotation `n` is announced
+/// at the beginning of rotation `n - 1`, becomes the primary key at the beginning of rotation `n`
+/// and is still accepted for `grace_period` after rotation `n + 1` begins.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SphinxKeyRotationSchedule {
+    /// Length of a single key rotation.
+    #[serde(with = "humantime_serde")]
+    pub rotation_period: Duration,
+
+    /// Amount of time a key is still accepted for after the rotation it was used for has ended.
+    #[serde(with = "humantime_serde")]
+    pub grace_period: Duration,
+}
+
+impl Default for SphinxKeyRotationSchedule {
+    fn default() -> Self {
+        SphinxKeyRotationSchedule {
+            rotation_period: DEFAULT_SPHINX_KEY_ROTATION_PERIOD,
+            grace_period: DEFAULT_SPHINX_KEY_GRACE_PERIOD,
+        }
+    }
+}
+
+impl SphinxKeyRotationSchedule {
+    fn period_secs(&self) -> u64 {
+        // make sure we'd never divide by zero on a misconfigured schedule
+        self.rotation_period.as_secs().max(1)
+    }
+
+    /// Returns id of the rotation that is active at the provided time.
+    pub fn rotation_id(&self, at: SystemTime) -> u32 {
+        let since_epoch = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+        (since_epoch.as_secs() / self.period_secs()) as u32
+    }
+
+    /// Returns the time at which the provided rotation begins.
+    pub fn rotation_start(&self, rotation_id: u32) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(rotation_id as u64 * self.period_secs())
+    }
+
+    /// Returns the time at which the provided rotation ends, i.e. when the next one begins.
+    pub fn rotation_end(&self, rotation_id: u32) -> SystemTime {
+        self.rotation_start(rotation_id.saturating_add(1))
+    }
+
+    /// Returns the time after which the key of the provided rotation must no longer be used.
+    pub fn key_expiration(&self, rotation_id: u32) -> SystemTime {
+        self.rotation_end(rotation_id) + self.grace_period
+    }
+
+    /// Checks whether the key of the provided rotation is meant to be accepted at the given time,
+    /// i.e. whether it has already been announced and hasn't yet expired.
+    pub fn is_key_valid(&self, rotation_id: u32, at: SystemTime) -> bool {
+        let current = self.rotation_id(at);
+        rotation_id <= current.saturating_add(1) && at < self.key_expiration(rotation_id)
+    }
+
+    /// Returns the amount of time remaining until the next rotation begins.
+    pub fn until_next_rotation(&self, at: SystemTime) -> Duration {
+        self.rotation_end(self.rotation_id(at))
+            .duration_since(at)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule() -> SphinxKeyRotationSchedule {
+        SphinxKeyRotationSchedule {
+            rotation_period: Duration::from_secs(100),
+            grace_period: Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn rotation_boundaries() {
+        let schedule = schedule();
+        let at = UNIX_EPOCH + Duration::from_secs(1234);
+
+        assert_eq!(schedule.rotation_id(at), 12);
+        assert_eq!(
+            schedule.rotation_start(12),
+            UNIX_EPOCH + Duration::from_secs(1200)
+        );
+        assert_eq!(
+            schedule.rotation_end(12),
+            UNIX_EPOCH + Duration::from_secs(1300)
+        );
+        assert_eq!(
+            schedule.key_expiration(12),
+            UNIX_EPOCH + Duration::from_secs(1310)
+        );
+        assert_eq!(schedule.until_next_rotation(at), Duration::from_secs(66));
+    }
+
+    #[test]
+    fn key_validity() {
+        let schedule = schedule();
+        let at = UNIX_EPOCH + Duration::from_secs(1305);
+
+        // the previous key is still within its grace period
+        assert!(schedule.is_key_valid(12, at));
+        assert!(!schedule.is_key_valid(12, at + Duration::from_secs(5)));
+
+        // current and the announced next key
+        assert!(schedule.is_key_valid(13, at));
+        assert!(schedule.is_key_valid(14, at));
+
+        assert!(!schedule.is_key_valid(11, at));
+        assert!(!schedule.is_key_valid(15, at));
+    }
+}
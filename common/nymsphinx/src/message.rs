@@ -10,6 +10,7 @@ use nym_sphinx_anonymous_replies::requests::{
     InvalidReplyRequestError, RepliableMessage, RepliableMessageContent, ReplyMessage,
     ReplyMessageContent,
 };
+use nym_sphinx_chunking::fec::generate_repair_fragments;
 use nym_sphinx_chunking::fragment::Fragment;
 use nym_sphinx_params::{PacketSize, PacketType, ReplySurbKeyDigestAlgorithm};
 use rand::Rng;
@@ -278,6 +279,32 @@ impl PaddedMessage {
             .collect()
     }
 
+    /// Splits the padded message into [`Fragment`]s and appends the specified number of repair
+    /// fragments to each resulting set, so that the recipient could reconstruct it
+    /// even if some of its fragments got lost.
+    pub fn split_into_fragments_with_repair<R: Rng>(
+        self,
+        rng: &mut R,
+        plaintext_per_packet: usize,
+        repair_fragments_per_set: u8,
+    ) -> Vec<Fragment> {
+        chunking::split_into_sets(rng, &self.0, plaintext_per_packet)
+            .into_iter()
+            .flat_map(|fragment_set| {
+                let repair_fragments = generate_repair_fragments(
+                    &fragment_set,
+                    repair_fragments_per_set,
+                    plaintext_per_packet,
+                )
+                .unwrap_or_else(|err| {
+                    log::warn!("failed to generate repair fragments for the set: {err}");
+                    Vec::new()
+                });
+                fragment_set.into_iter().chain(repair_fragments)
+            })
+            .collect()
+    }
+
     // reverse of NymMessage::pad_to_full_packet_lengths
     pub fn remove_padding(self, num_mix_hops: u8) -> Result<NymMessage, NymMessageError> {
         // we are looking for first occurrence of 1 in the tail and we get its index
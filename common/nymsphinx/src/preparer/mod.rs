@@ -289,6 +289,23 @@ pub trait FragmentPreparer {
             .pad_to_full_packet_lengths(plaintext_per_packet)
             .split_into_fragments(self.rng(), plaintext_per_packet)
     }
+
+    fn pad_and_split_message_with_repair(
+        &mut self,
+        message: NymMessage,
+        packet_size: PacketSize,
+        repair_fragments_per_set: u8,
+    ) -> Vec<Fragment> {
+        let plaintext_per_packet = message.available_sphinx_plaintext_per_packet(packet_size);
+
+        message
+            .pad_to_full_packet_lengths(plaintext_per_packet)
+            .split_into_fragments_with_repair(
+                self.rng(),
+                plaintext_per_packet,
+                repair_fragments_per_set,
+            )
+    }
 }
 
 /// Prepares the message that is to be sent through the mix network by attaching
@@ -434,6 +451,20 @@ where
     ) -> Vec<Fragment> {
         <Self as FragmentPreparer>::pad_and_split_message(self, message, packet_size)
     }
+
+    pub fn pad_and_split_message_with_repair(
+        &mut self,
+        message: NymMessage,
+        packet_size: PacketSize,
+        repair_fragments_per_set: u8,
+    ) -> Vec<Fragment> {
+        <Self as FragmentPreparer>::pad_and_split_message_with_repair(
+            self,
+            message,
+            packet_size,
+            repair_fragments_per_set,
+        )
+    }
 }
 
 impl<R: CryptoRng + Rng> FragmentPreparer for MessagePreparer<R> {
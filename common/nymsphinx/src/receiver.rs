@@ -19,7 +19,7 @@ use nym_sphinx_params::{
 use thiserror::Error;
 
 // TODO: should this live in this file?
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReconstructedMessage {
     /// The actual plaintext message that was received.
     pub message: Vec<u8>,
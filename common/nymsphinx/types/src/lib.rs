@@ -7,9 +7,17 @@ pub use nym_outfox::{
     error::OutfoxError,
 };
 // re-exporting types and constants available in sphinx
+#[cfg(feature = "sphinx")]
+use curve25519_dalek::scalar::Scalar;
 #[cfg(feature = "outfox")]
 use nym_outfox::packet::{OutfoxPacket, OutfoxProcessedPacket};
 #[cfg(feature = "sphinx")]
+use sphinx_packet::{
+    constants::HEADER_INTEGRITY_MAC_SIZE,
+    header::{keys::RoutingKeys, mac::HeaderIntegrityMac},
+    SphinxPacket, SphinxPacketBuilder,
+};
+#[cfg(feature = "sphinx")]
 pub use sphinx_packet::{
     constants::{
         self, DESTINATION_ADDRESS_LENGTH, IDENTIFIER_LENGTH, MAX_PATH_LENGTH, NODE_ADDRESS_LENGTH,
@@ -23,8 +31,6 @@ pub use sphinx_packet::{
     surb::{SURBMaterial, SURB},
     Error as SphinxError, ProcessedPacket,
 };
-#[cfg(feature = "sphinx")]
-use sphinx_packet::{SphinxPacket, SphinxPacketBuilder};
 use std::{array::TryFromSliceError, fmt};
 use thiserror::Error;
 
@@ -40,6 +46,9 @@ pub enum NymPacketError {
 
     #[error("{0}")]
     FromSlice(#[from] TryFromSliceError),
+
+    #[error("no keys were provided for processing the packet")]
+    NoKeys,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -142,6 +151,42 @@ impl NymPacket {
         }
     }
 
+    /// Attempts to unwrap the packet with any of the provided keys, returning the processed packet
+    /// alongside the bytes of the key shared with the packet creator.
+    /// Only a single diffie-hellman is performed per attempted key and the packet is never copied.
+    #[cfg(feature = "sphinx")]
+    pub fn process_with_any_key<K: AsRef<PrivateKey>>(
+        self,
+        node_secret_keys: &[K],
+    ) -> Result<(NymProcessedPacket, [u8; 32]), NymPacketError> {
+        let Some((last_key, other_keys)) = node_secret_keys.split_last() else {
+            return Err(NymPacketError::NoKeys);
+        };
+
+        match self {
+            NymPacket::Sphinx(packet) => {
+                let (processed, shared_key) =
+                    process_sphinx_with_any_key(packet, last_key, other_keys)?;
+                Ok((NymProcessedPacket::Sphinx(processed), shared_key))
+            }
+            #[cfg(feature = "outfox")]
+            NymPacket::Outfox(mut packet) => {
+                // the packet is left intact if the layer fails to authenticate with the given key
+                let decoded = other_keys
+                    .iter()
+                    .find_map(|key| packet.decode_next_layer_with_shared_key(key.as_ref()).ok());
+                let (next_address, shared_key) = match decoded {
+                    Some(decoded) => decoded,
+                    None => packet.decode_next_layer_with_shared_key(last_key.as_ref())?,
+                };
+                Ok((
+                    NymProcessedPacket::Outfox(OutfoxProcessedPacket::new(packet, next_address)),
+                    shared_key,
+                ))
+            }
+        }
+    }
+
     #[cfg(feature = "sphinx")]
     pub fn process(
         self,
@@ -162,3 +207,43 @@ impl NymPacket {
         }
     }
 }
+
+#[cfg(feature = "sphinx")]
+fn process_sphinx_with_any_key<K: AsRef<PrivateKey>>(
+    packet: SphinxPacket,
+    last_key: &K,
+    other_keys: &[K],
+) -> Result<(ProcessedPacket, [u8; 32]), NymPacketError> {
+    let public_element = packet.shared_secret();
+    let derive_keys = |key: &K| {
+        let shared_key = key.as_ref().diffie_hellman(&public_element);
+        (shared_key, RoutingKeys::derive(shared_key))
+    };
+
+    // rather than attempting to fully process (and thus consume) the packet with each key,
+    // find the one the header integrity mac has been computed for
+    let mut derived = None;
+    if !other_keys.is_empty() {
+        // the mac is not directly exposed, but it's the leading part of the serialised routing info
+        let routing_info = packet.header.routing_info.to_bytes();
+        let (mac, encrypted_routing_info) = routing_info.split_at(HEADER_INTEGRITY_MAC_SIZE);
+        let mac = HeaderIntegrityMac::from_bytes(mac.try_into()?);
+        derived = other_keys
+            .iter()
+            .map(derive_keys)
+            .find(|(_, routing_keys)| {
+                mac.verify(
+                    routing_keys.header_integrity_hmac_key,
+                    encrypted_routing_info,
+                )
+            });
+    }
+    let (shared_key, routing_keys) = derived.unwrap_or_else(|| derive_keys(last_key));
+
+    // the blinded secret has to be provided upfront, even though it's only used by forward hops
+    let blinder = PrivateKey::from(Scalar::from_bytes_mod_order(routing_keys.blinding_factor));
+    let blinded_secret = blinder.diffie_hellman(&public_element);
+
+    let processed = packet.process_with_derived_keys(&Some(blinded_secret), &routing_keys)?;
+    Ok((processed, *shared_key.as_bytes()))
+}
@@ -0,0 +1,529 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optional encryption at rest of the stored keys.
+//!
+//! Encrypted keys are still stored as PEM files, but with their tag prefixed with `ENCRYPTED `
+//! and the contents replaced with the (json-encoded) key derivation parameters alongside
+//! the AES-256-GCM ciphertext of the original key, as produced by [`nym_store_cipher`].
+//! The encryption key is derived with Argon2, which is deliberately expensive, so all keys encrypted
+//! with the same passphrase by a single process share the (randomly generated) salt and the derived
+//! key is cached alongside the passphrase.
+
+use crate::traits::{PemStorableKey, PemStorableKeyPair};
+use crate::{decode_key, read_pem_file, store_key, write_pem, KeyPairPath};
+use nym_store_cipher::{Aes256Gcm, EncryptedData, KdfInfo, StoreCipher};
+use pem::Pem;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::fmt::{self, Debug, Formatter};
+use std::fs::{self, File};
+use std::io::{self, BufRead};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Name of the environment variable that can hold the passphrase used for the keys encryption.
+pub const KEYS_PASSPHRASE_ENV_VAR: &str = "NYM_KEYS_PASSPHRASE";
+
+/// Name of the environment variable that can point to a file containing the passphrase
+/// used for the keys encryption.
+pub const KEYS_PASSPHRASE_FILE_ENV_VAR: &str = "NYM_KEYS_PASSPHRASE_FILE";
+
+const ENCRYPTED_TAG_PREFIX: &str = "ENCRYPTED ";
+
+type Cipher = StoreCipher<Aes256Gcm>;
+
+/// Passphrase used for deriving the keys encryption key. It gets zeroized on drop.
+/// Any ciphers derived from it are shared between its clones.
+#[derive(Clone)]
+pub struct Passphrase(Arc<PassphraseInner>);
+
+struct PassphraseInner {
+    passphrase: Zeroizing<Vec<u8>>,
+    derived_ciphers: Mutex<Vec<(KdfInfo, Arc<Cipher>)>>,
+}
+
+impl Passphrase {
+    pub fn new(passphrase: impl Into<Vec<u8>>) -> Self {
+        Passphrase(Arc::new(PassphraseInner {
+            passphrase: Zeroizing::new(passphrase.into()),
+            derived_ciphers: Default::default(),
+        }))
+    }
+
+    fn derived_ciphers(&self) -> MutexGuard<'_, Vec<(KdfInfo, Arc<Cipher>)>> {
+        // the cache can't be left in an inconsistent state, so it's fine to ignore the poisoning
+        self.0
+            .derived_ciphers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn derive_cipher(
+        &self,
+        ciphers: &mut Vec<(KdfInfo, Arc<Cipher>)>,
+        kdf_info: KdfInfo,
+    ) -> io::Result<Arc<Cipher>> {
+        let cipher =
+            Arc::new(Cipher::new(self.as_ref(), kdf_info.clone()).map_err(cipher_failure)?);
+        ciphers.push((kdf_info, Arc::clone(&cipher)));
+        Ok(cipher)
+    }
+
+    /// Returns the cipher for the provided key derivation parameters, deriving it only if they haven't been used before.
+    fn cipher(&self, kdf_info: &KdfInfo) -> io::Result<Arc<Cipher>> {
+        let mut ciphers = self.derived_ciphers();
+        if let Some((_, cipher)) = ciphers.iter().find(|(info, _)| info == kdf_info) {
+            return Ok(Arc::clone(cipher));
+        }
+        self.derive_cipher(&mut ciphers, kdf_info.clone())
+    }
+
+    /// Returns the cipher, alongside its key derivation parameters, to be used for encrypting new keys.
+    fn encryption_cipher(&self) -> io::Result<(KdfInfo, Arc<Cipher>)> {
+        let mut ciphers = self.derived_ciphers();
+        if let Some((kdf_info, cipher)) = ciphers.first() {
+            return Ok((kdf_info.clone(), Arc::clone(cipher)));
+        }
+
+        let kdf_info = KdfInfo::new_with_default_settings().map_err(cipher_failure)?;
+        let cipher = self.derive_cipher(&mut ciphers, kdf_info.clone())?;
+        Ok((kdf_info, cipher))
+    }
+}
+
+impl Debug for Passphrase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Passphrase(<redacted>)")
+    }
+}
+
+impl AsRef<[u8]> for Passphrase {
+    fn as_ref(&self) -> &[u8] {
+        &self.0.passphrase
+    }
+}
+
+/// Location the keys passphrase should be read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PassphraseSource {
+    /// Read the passphrase from the environment variable of the provided name.
+    Env(String),
+
+    /// Read the passphrase from the provided file.
+    File(PathBuf),
+
+    /// Read the passphrase from the first line of the standard input.
+    Stdin,
+}
+
+impl PassphraseSource {
+    /// Returns the passphrase source specified via the default environment variables, if any.
+    /// The passphrase file takes precedence over the passphrase set directly.
+    pub fn from_default_env() -> Option<Self> {
+        if let Some(path) = std::env::var_os(KEYS_PASSPHRASE_FILE_ENV_VAR) {
+            return Some(PassphraseSource::File(path.into()));
+        }
+        if std::env::var_os(KEYS_PASSPHRASE_ENV_VAR).is_some() {
+            return Some(PassphraseSource::Env(KEYS_PASSPHRASE_ENV_VAR.to_string()));
+        }
+        None
+    }
+
+    pub fn read(&self) -> io::Result<Passphrase> {
+        let raw = Zeroizing::new(match self {
+            PassphraseSource::Env(var) => std::env::var(var).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("could not read the passphrase from the '{var}' environment variable: {err}"),
+                )
+            })?,
+            PassphraseSource::File(path) => std::fs::read_to_string(path)?,
+            PassphraseSource::Stdin => {
+                let mut line = String::new();
+                io::stdin().lock().read_line(&mut line)?;
+                line
+            }
+        });
+
+        // don't treat the trailing newline (as left by most editors or `echo`) as part of the passphrase
+        let passphrase = raw.trim_end_matches(['\r', '\n']);
+        if passphrase.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the provided keys passphrase is empty",
+            ));
+        }
+        Ok(Passphrase::new(passphrase.as_bytes()))
+    }
+}
+
+/// Command line arguments for specifying the source of the keys passphrase.
+#[cfg_attr(feature = "cli", derive(clap::Args))]
+#[derive(Debug, Clone, Default)]
+pub struct KeysPassphraseArgs {
+    /// Name of the environment variable containing the passphrase used for encrypting the keys.
+    /// If no passphrase source is specified, `NYM_KEYS_PASSPHRASE_FILE` and `NYM_KEYS_PASSPHRASE`
+    /// environment variables are going to be checked instead.
+    #[cfg_attr(feature = "cli", clap(long, group = "keys_passphrase"))]
+    pub keys_passphrase_env: Option<String>,
+
+    /// Path to the file containing the passphrase used for encrypting the keys.
+    #[cfg_attr(feature = "cli", clap(long, group = "keys_passphrase"))]
+    pub keys_passphrase_file: Option<PathBuf>,
+
+    /// Read the passphrase used for encrypting the keys from the standard input.
+    #[cfg_attr(feature = "cli", clap(long, group = "keys_passphrase"))]
+    pub keys_passphrase_stdin: bool,
+}
+
+impl KeysPassphraseArgs {
+    pub fn source(&self) -> Option<PassphraseSource> {
+        if let Some(var) = &self.keys_passphrase_env {
+            return Some(PassphraseSource::Env(var.clone()));
+        }
+        if let Some(path) = &self.keys_passphrase_file {
+            return Some(PassphraseSource::File(path.clone()));
+        }
+        if self.keys_passphrase_stdin {
+            return Some(PassphraseSource::Stdin);
+        }
+        PassphraseSource::from_default_env()
+    }
+
+    /// Reads the passphrase from the specified source, if any, prompting for it if it's meant to be read from stdin.
+    pub fn read(&self) -> io::Result<Option<Passphrase>> {
+        let Some(source) = self.source() else {
+            return Ok(None);
+        };
+        if source == PassphraseSource::Stdin {
+            eprintln!("enter the keys passphrase:");
+        }
+        source.read().map(Some)
+    }
+}
+
+/// Reads the passphrase from the source specified via the default environment variables, if any.
+/// The passphrase is only read once per process, so that any keys derived from it could be reused.
+pub fn default_passphrase() -> io::Result<Option<Passphrase>> {
+    static DEFAULT_PASSPHRASE: OnceLock<Option<Passphrase>> = OnceLock::new();
+
+    if let Some(passphrase) = DEFAULT_PASSPHRASE.get() {
+        return Ok(passphrase.clone());
+    }
+    let passphrase = PassphraseSource::from_default_env()
+        .map(|source| source.read())
+        .transpose()?;
+    Ok(DEFAULT_PASSPHRASE.get_or_init(|| passphrase).clone())
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedPemContents {
+    kdf_info: KdfInfo,
+    data: EncryptedData,
+}
+
+fn cipher_failure(err: nym_store_cipher::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+fn is_encrypted_pem(pem: &Pem) -> bool {
+    pem.tag.starts_with(ENCRYPTED_TAG_PREFIX)
+}
+
+fn encrypt_pem(pem: Pem, passphrase: &Passphrase) -> io::Result<Pem> {
+    let (kdf_info, cipher) = passphrase.encryption_cipher()?;
+
+    // note: the plaintext gets encrypted in place, so we're not leaving any copies of it behind
+    let data = cipher.encrypt_data(pem.contents).map_err(cipher_failure)?;
+    let contents = serde_json::to_vec(&EncryptedPemContents { kdf_info, data })
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    Ok(Pem {
+        tag: format!("{ENCRYPTED_TAG_PREFIX}{}", pem.tag),
+        contents,
+    })
+}
+
+fn decrypt_pem(pem: &Pem, passphrase: &Passphrase) -> io::Result<Pem> {
+    let Some(tag) = pem.tag.strip_prefix(ENCRYPTED_TAG_PREFIX) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("'{}' is not an encrypted pem tag", pem.tag),
+        ));
+    };
+
+    let encrypted: EncryptedPemContents = serde_json::from_slice(&pem.contents)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    let cipher = passphrase.cipher(&encrypted.kdf_info)?;
+    let contents = cipher.decrypt_data(encrypted.data).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "failed to decrypt the key. the provided passphrase is most likely invalid",
+        )
+    })?;
+
+    Ok(Pem {
+        tag: tag.to_string(),
+        contents,
+    })
+}
+
+/// Replaces the file at the provided path by writing the pem into a temporary file in the same directory
+/// and renaming it afterwards, so that the key would never be left partially written.
+fn replace_pem_file(path: &Path, pem: &Pem) -> io::Result<()> {
+    let Some(file_name) = path.file_name() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("'{}' is not a valid key file path", path.display()),
+        ));
+    };
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let res = write_pem(&temp_path, pem)
+        .and_then(|_| File::open(&temp_path)?.sync_all())
+        .and_then(|_| fs::rename(&temp_path, path));
+    if res.is_err() {
+        // don't leave the (potentially plaintext) key behind
+        let _ = fs::remove_file(&temp_path);
+    }
+    res
+}
+
+/// Checks whether the key stored at the provided path has been encrypted.
+pub fn is_encrypted<P: AsRef<Path>>(path: P) -> io::Result<bool> {
+    Ok(is_encrypted_pem(&read_pem_file(path)?))
+}
+
+/// Loads the key stored at the provided path decrypting it with the passphrase if required.
+/// Note that plaintext keys are always loaded, even if the passphrase has been provided.
+pub fn load_key_with_passphrase<T, P>(path: P, passphrase: Option<&Passphrase>) -> io::Result<T>
+where
+    T: PemStorableKey,
+    P: AsRef<Path>,
+{
+    let key_pem = read_pem_file(path)?;
+    if !is_encrypted_pem(&key_pem) {
+        return decode_key(&key_pem);
+    }
+
+    let Some(passphrase) = passphrase else {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "the key is encrypted, but no passphrase has been provided",
+        ));
+    };
+
+    let mut decrypted = decrypt_pem(&key_pem, passphrase)?;
+    let key = decode_key(&decrypted);
+    decrypted.contents.zeroize();
+    key
+}
+
+/// Stores the key at the provided path encrypting it with the passphrase, if one has been provided.
+pub fn store_key_with_passphrase<T, P>(
+    key: &T,
+    path: P,
+    passphrase: Option<&Passphrase>,
+) -> io::Result<()>
+where
+    T: PemStorableKey,
+    P: AsRef<Path>,
+{
+    let Some(passphrase) = passphrase else {
+        return store_key(key, path);
+    };
+
+    let pem = Pem {
+        tag: T::pem_type().to_string(),
+        contents: key.to_bytes(),
+    };
+    write_pem(path, &encrypt_pem(pem, passphrase)?)
+}
+
+pub fn load_keypair_with_passphrase<T>(
+    paths: &KeyPairPath,
+    passphrase: Option<&Passphrase>,
+) -> io::Result<T>
+where
+    T: PemStorableKeyPair,
+{
+    let private: T::PrivatePemKey = load_key_with_passphrase(&paths.private_key_path, passphrase)?;
+    let public: T::PublicPemKey = load_key_with_passphrase(&paths.public_key_path, passphrase)?;
+    Ok(T::from_keys(private, public))
+}
+
+/// Stores the keypair at the provided paths. If the passphrase has been provided,
+/// the private key gets encrypted. The public key is always stored in plaintext.
+pub fn store_keypair_with_passphrase<T>(
+    keypair: &T,
+    paths: &KeyPairPath,
+    passphrase: Option<&Passphrase>,
+) -> io::Result<()>
+where
+    T: PemStorableKeyPair,
+{
+    store_key(keypair.public_key(), &paths.public_key_path)?;
+    store_key_with_passphrase(keypair.private_key(), &paths.private_key_path, passphrase)
+}
+
+/// Encrypts the existing plaintext key stored at the provided path.
+/// Returns `false` if the key has already been encrypted before.
+pub fn encrypt_key_file<P: AsRef<Path>>(path: P, passphrase: &Passphrase) -> io::Result<bool> {
+    let key_pem = read_pem_file(path.as_ref())?;
+    if is_encrypted_pem(&key_pem) {
+        return Ok(false);
+    }
+
+    replace_pem_file(path.as_ref(), &encrypt_pem(key_pem, passphrase)?)?;
+    Ok(true)
+}
+
+/// Decrypts the existing key stored at the provided path, so that it would be stored in plaintext again.
+/// Returns `false` if the key hasn't been encrypted.
+pub fn decrypt_key_file<P: AsRef<Path>>(path: P, passphrase: &Passphrase) -> io::Result<bool> {
+    let key_pem = read_pem_file(path.as_ref())?;
+    if !is_encrypted_pem(&key_pem) {
+        return Ok(false);
+    }
+
+    let mut decrypted = decrypt_pem(&key_pem, passphrase)?;
+    let res = replace_pem_file(path.as_ref(), &decrypted);
+    decrypted.contents.zeroize();
+    res.map(|_| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct DummyKey(Vec<u8>);
+
+    impl PemStorableKey for DummyKey {
+        type Error = io::Error;
+
+        fn pem_type() -> &'static str {
+            "DUMMY KEY"
+        }
+
+        fn to_bytes(&self) -> Vec<u8> {
+            self.0.clone()
+        }
+
+        fn from_bytes(bytes: &[u8]) -> Result<Self, Self::Error> {
+            Ok(DummyKey(bytes.to_vec()))
+        }
+    }
+
+    fn dummy_key() -> DummyKey {
+        DummyKey(b"totally-secret-key-bytes".to_vec())
+    }
+
+    fn dir_entries(dir: &Path) -> Vec<OsString> {
+        let mut entries = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn encrypted_key_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key");
+        let passphrase = Passphrase::new("hunter2");
+
+        store_key_with_passphrase(&dummy_key(), &path, Some(&passphrase)).unwrap();
+        assert!(is_encrypted(&path).unwrap());
+        assert!(!fs::read_to_string(&path)
+            .unwrap()
+            .contains("totally-secret"));
+
+        let loaded: DummyKey = load_key_with_passphrase(&path, Some(&passphrase)).unwrap();
+        assert_eq!(loaded, dummy_key());
+
+        let err = load_key_with_passphrase::<DummyKey, _>(&path, None).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key");
+
+        store_key_with_passphrase(&dummy_key(), &path, Some(&Passphrase::new("hunter2"))).unwrap();
+        let err = load_key_with_passphrase::<DummyKey, _>(&path, Some(&Passphrase::new("hunter3")))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        let err = decrypt_key_file(&path, &Passphrase::new("hunter3")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(is_encrypted(&path).unwrap());
+    }
+
+    #[test]
+    fn plaintext_keys_are_loaded_regardless_of_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key");
+
+        store_key_with_passphrase(&dummy_key(), &path, None).unwrap();
+        assert!(!is_encrypted(&path).unwrap());
+
+        let loaded: DummyKey = load_key_with_passphrase(&path, None).unwrap();
+        assert_eq!(loaded, dummy_key());
+        let loaded: DummyKey =
+            load_key_with_passphrase(&path, Some(&Passphrase::new("hunter2"))).unwrap();
+        assert_eq!(loaded, dummy_key());
+    }
+
+    #[test]
+    fn encrypting_and_decrypting_existing_key_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("key");
+        let passphrase = Passphrase::new("hunter2");
+        store_key_with_passphrase(&dummy_key(), &path, None).unwrap();
+
+        assert!(encrypt_key_file(&path, &passphrase).unwrap());
+        assert!(!encrypt_key_file(&path, &passphrase).unwrap());
+        assert!(is_encrypted(&path).unwrap());
+        // no temporary files are left behind
+        assert_eq!(dir_entries(dir.path()), vec![OsString::from("key")]);
+
+        assert!(decrypt_key_file(&path, &passphrase).unwrap());
+        assert!(!decrypt_key_file(&path, &passphrase).unwrap());
+        assert!(!is_encrypted(&path).unwrap());
+        assert_eq!(dir_entries(dir.path()), vec![OsString::from("key")]);
+
+        let loaded: DummyKey = load_key_with_passphrase(&path, None).unwrap();
+        assert_eq!(loaded, dummy_key());
+    }
+
+    #[test]
+    fn encryption_key_is_derived_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let passphrase = Passphrase::new("hunter2");
+
+        for name in ["first", "second"] {
+            let path = dir.path().join(name);
+            store_key_with_passphrase(&dummy_key(), &path, Some(&passphrase)).unwrap();
+            let loaded: DummyKey =
+                load_key_with_passphrase(&path, Some(&passphrase.clone())).unwrap();
+            assert_eq!(loaded, dummy_key());
+        }
+        assert_eq!(passphrase.derived_ciphers().len(), 1);
+
+        // keys encrypted by another process (i.e. with a different salt) can still be decrypted
+        let other_path = dir.path().join("other");
+        store_key_with_passphrase(&dummy_key(), &other_path, Some(&Passphrase::new("hunter2")))
+            .unwrap();
+        let loaded: DummyKey = load_key_with_passphrase(&other_path, Some(&passphrase)).unwrap();
+        assert_eq!(loaded, dummy_key());
+        assert_eq!(passphrase.derived_ciphers().len(), 2);
+    }
+}
@@ -7,8 +7,17 @@ use std::fs::File;
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "encryption")]
+pub mod encryption;
 pub mod traits;
 
+#[cfg(feature = "encryption")]
+pub use encryption::{
+    decrypt_key_file, default_passphrase, encrypt_key_file, is_encrypted, load_key_with_passphrase,
+    load_keypair_with_passphrase, store_key_with_passphrase, store_keypair_with_passphrase,
+    KeysPassphraseArgs, Passphrase, PassphraseSource,
+};
+
 #[derive(Debug)]
 pub struct KeyPairPath {
     pub private_key_path: PathBuf,
@@ -47,7 +56,18 @@ where
     P: AsRef<Path>,
 {
     let key_pem = read_pem_file(path)?;
+    decode_key(&key_pem)
+}
 
+pub fn store_key<T, P>(key: &T, path: P) -> io::Result<()>
+where
+    T: PemStorableKey,
+    P: AsRef<Path>,
+{
+    write_pem_file(path, key.to_bytes(), T::pem_type())
+}
+
+fn decode_key<T: PemStorableKey>(key_pem: &Pem) -> io::Result<T> {
     if T::pem_type() != key_pem.tag {
         return Err(io::Error::new(
             io::ErrorKind::Other,
@@ -59,20 +79,8 @@ where
         ));
     }
 
-    let key = match T::from_bytes(&key_pem.contents) {
-        Ok(key) => key,
-        Err(err) => return Err(io::Error::new(io::ErrorKind::InvalidData, err.to_string())),
-    };
-
-    Ok(key)
-}
-
-pub fn store_key<T, P>(key: &T, path: P) -> io::Result<()>
-where
-    T: PemStorableKey,
-    P: AsRef<Path>,
-{
-    write_pem_file(path, key.to_bytes(), T::pem_type())
+    T::from_bytes(&key_pem.contents)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
 }
 
 fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<Pem> {
@@ -83,15 +91,19 @@ fn read_pem_file<P: AsRef<Path>>(filepath: P) -> io::Result<Pem> {
 }
 
 fn write_pem_file<P: AsRef<Path>>(filepath: P, data: Vec<u8>, tag: &str) -> io::Result<()> {
-    // ensure the whole directory structure exists
-    if let Some(parent_dir) = filepath.as_ref().parent() {
-        std::fs::create_dir_all(parent_dir)?;
-    }
     let pem = Pem {
         tag: tag.to_string(),
         contents: data,
     };
-    let key = pem::encode(&pem);
+    write_pem(filepath, &pem)
+}
+
+fn write_pem<P: AsRef<Path>>(filepath: P, pem: &Pem) -> io::Result<()> {
+    // ensure the whole directory structure exists
+    if let Some(parent_dir) = filepath.as_ref().parent() {
+        std::fs::create_dir_all(parent_dir)?;
+    }
+    let key = pem::encode(pem);
 
     let mut file = File::create(filepath.as_ref())?;
     file.write_all(key.as_bytes())?;
@@ -124,6 +124,9 @@ pub struct Socks5 {
     #[serde(default)]
     pub provider_interface_version: ProviderInterfaceVersion,
 
+    /// The version of the socks5 protocol this client is going to use for its regular connections.
+    /// Note that the UDP ASSOCIATE and BIND commands do not use it and instead rely on
+    /// the version advertised by the network requester itself.
     #[serde(default)]
     pub socks5_protocol_version: Socks5ProtocolVersion,
 
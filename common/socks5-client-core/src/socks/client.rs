@@ -1,21 +1,25 @@
 #![forbid(unsafe_code)]
 
 use super::authentication::{AuthenticationMethods, Authenticator, User};
+use super::provider_version::{self, ProviderVersionReceiver, PROVIDER_VERSION_TIMEOUT};
 use super::request::{SocksCommand, SocksRequest};
 use super::types::{ResponseCodeV4, ResponseCodeV5, SocksProxyError};
+use super::udp::{encode_socket_address, SocksDatagram, MAX_UDP_DATAGRAM_SIZE};
 use super::{SocksVersion, RESERVED, SOCKS4_VERSION, SOCKS5_VERSION};
 use crate::config;
 use futures::channel::mpsc;
 use futures::task::{Context, Poll};
+use futures::StreamExt;
 use log::*;
 use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
 use nym_service_providers_common::interface::{ProviderInterfaceVersion, RequestVersion};
 use nym_socks5_proxy_helpers::connection_controller::{
-    ConnectionReceiver, ControllerCommand, ControllerSender,
+    BindResponseReceiver, ConnectionReceiver, ControllerCommand, ControllerSender, DatagramReceiver,
 };
 use nym_socks5_proxy_helpers::proxy_runner::ProxyRunner;
 use nym_socks5_requests::{
-    ConnectionId, RemoteAddress, Socks5ProtocolVersion, Socks5ProviderRequest, Socks5Request,
+    BindStatus, ConnectionId, Datagram, RemoteAddress, Socks5ProtocolVersion,
+    Socks5ProviderRequest, Socks5Request,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::params::PacketSize;
@@ -27,8 +31,20 @@ use rand::RngCore;
 use std::io;
 use std::net::SocketAddr;
 use std::pin::Pin;
+use std::time::Duration;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::time::{sleep, timeout, Instant};
+
+// Network requesters expire datagram sessions that have been idle for a while,
+// so we have to periodically remind them about our session's existence.
+// This should be smaller than the idle timeout used by the network requester.
+const DATAGRAM_SESSION_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+// Network requesters give up on their bind listeners on their own and let us know about it,
+// so this is only a safeguard against the responses getting lost.
+// This should be larger than the accept timeout used by the network requester.
+const BIND_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
 #[pin_project(project = StateProject)]
 enum StreamState {
@@ -69,6 +85,17 @@ impl StreamState {
         }
     }
 
+    /// Returns the local address that this stream is bound to.
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            StreamState::RunningProxy => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "stream is being used to run the proxy",
+            )),
+            StreamState::Available(ref stream) => stream.local_addr(),
+        }
+    }
+
     async fn shutdown(&mut self) -> io::Result<()> {
         // shutdown should only be called if proxy is not being run. If it is, there's some bug
         // somewhere
@@ -182,6 +209,7 @@ pub(crate) struct SocksClient {
     input_sender: InputMessageSender,
     connection_id: ConnectionId,
     service_provider: Recipient,
+    provider_version: ProviderVersionReceiver,
     self_address: Recipient,
     started_proxy: bool,
     lane_queue_lengths: LaneQueueLengths,
@@ -212,6 +240,7 @@ impl SocksClient {
         input_sender: InputMessageSender,
         service_provider: &Recipient,
         controller_sender: ControllerSender,
+        provider_version: ProviderVersionReceiver,
         self_address: &Recipient,
         lane_queue_lengths: LaneQueueLengths,
         mut shutdown_listener: TaskClient,
@@ -232,6 +261,7 @@ impl SocksClient {
             authenticator,
             input_sender,
             service_provider: *service_provider,
+            provider_version,
             self_address: *self_address,
             started_proxy: false,
             lane_queue_lengths,
@@ -396,7 +426,16 @@ impl SocksClient {
     async fn run_proxy(&mut self, conn_receiver: ConnectionReceiver, remote_proxy_target: String) {
         self.send_connect_to_mixnet(remote_proxy_target.clone())
             .await;
+        self.proxy_stream(conn_receiver, remote_proxy_target).await
+    }
 
+    /// Proxies the local stream through the mixnet, assuming the remote side of the connection
+    /// has already been established.
+    async fn proxy_stream(
+        &mut self,
+        conn_receiver: ConnectionReceiver,
+        remote_proxy_target: String,
+    ) {
         let stream = self.stream.run_proxy();
         let peer_addr = match stream.peer_addr() {
             Ok(peer_addr) => peer_addr,
@@ -459,6 +498,309 @@ impl SocksClient {
         self.stream.finish_proxy(stream)
     }
 
+    async fn send_request_to_mixnet(
+        &mut self,
+        interface_version: ProviderInterfaceVersion,
+        request: Socks5Request,
+        reply_surbs: u32,
+    ) {
+        let msg = Socks5ProviderRequest::new_provider_data(interface_version, request);
+        let lane = TransmissionLane::ConnectionId(self.connection_id);
+
+        let input_message = if self.config.use_surbs_for_responses {
+            InputMessage::new_anonymous(
+                self.service_provider,
+                msg.into_bytes(),
+                reply_surbs,
+                lane,
+                self.packet_type,
+            )
+        } else {
+            InputMessage::new_regular(
+                self.service_provider,
+                msg.into_bytes(),
+                lane,
+                self.packet_type,
+            )
+        };
+        self.input_sender
+            .send(input_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+    }
+
+    async fn send_open_datagram_session_to_mixnet(
+        &mut self,
+        request_version: &RequestVersion<Socks5Request>,
+    ) {
+        let return_address = if self.config.use_surbs_for_responses {
+            None
+        } else {
+            Some(self.self_address)
+        };
+        let req = Socks5Request::new_open_datagram_session(
+            request_version.provider_protocol,
+            self.connection_id,
+            return_address,
+        );
+        self.send_request_to_mixnet(
+            request_version.provider_interface,
+            req,
+            self.config.connection_start_surbs,
+        )
+        .await
+    }
+
+    async fn send_datagram_to_mixnet(
+        &mut self,
+        request_version: &RequestVersion<Socks5Request>,
+        datagram: Datagram,
+    ) {
+        let req = Socks5Request::new_send_datagram(request_version.provider_protocol, datagram);
+        self.send_request_to_mixnet(
+            request_version.provider_interface,
+            req,
+            self.config.per_request_surbs,
+        )
+        .await
+    }
+
+    async fn send_close_datagram_session_to_mixnet(
+        &mut self,
+        request_version: &RequestVersion<Socks5Request>,
+    ) {
+        let req = Socks5Request::new_close_datagram_session(
+            request_version.provider_protocol,
+            self.connection_id,
+        );
+        self.send_request_to_mixnet(request_version.provider_interface, req, 0)
+            .await
+    }
+
+    async fn send_bind_to_mixnet(
+        &mut self,
+        request_version: &RequestVersion<Socks5Request>,
+        expected_peer: RemoteAddress,
+    ) {
+        let return_address = if self.config.use_surbs_for_responses {
+            None
+        } else {
+            Some(self.self_address)
+        };
+        let req = Socks5Request::new_bind(
+            request_version.provider_protocol,
+            self.connection_id,
+            expected_peer,
+            return_address,
+        );
+        self.send_request_to_mixnet(
+            request_version.provider_interface,
+            req,
+            self.config.connection_start_surbs,
+        )
+        .await
+    }
+
+    /// Returns the request version to use for the UDP ASSOCIATE and BIND commands,
+    /// or fails if the network requester is too old to support them.
+    async fn extended_request_version(
+        &mut self,
+    ) -> Result<RequestVersion<Socks5Request>, SocksProxyError> {
+        let provider_version = *self.provider_version.borrow();
+        let provider_version = match provider_version {
+            Some(version) => Some(version),
+            None => {
+                // we might have lost the initial response, so ask again
+                provider_version::send_provider_version_query(
+                    &self.input_sender,
+                    self.service_provider,
+                    self.packet_type,
+                )
+                .await;
+                match timeout(
+                    PROVIDER_VERSION_TIMEOUT,
+                    self.provider_version.wait_for(Option::is_some),
+                )
+                .await
+                {
+                    Ok(Ok(version)) => *version,
+                    _ => None,
+                }
+            }
+        };
+
+        let Some(provider_version) = provider_version else {
+            warn!("could not learn the version of the network requester");
+            return Err(ResponseCodeV5::CommandNotSupported.into());
+        };
+        provider_version::extended_request_version(provider_version).ok_or_else(|| {
+            warn!("the network requester ({provider_version}) is too old to support this command");
+            ResponseCodeV5::CommandNotSupported.into()
+        })
+    }
+
+    /// Waits for the remote listener to accept the inbound connection, sending both of the
+    /// BIND replies to the client along the way. Returns the address of the connected peer.
+    async fn wait_for_bind(
+        &mut self,
+        mut bind_receiver: BindResponseReceiver,
+    ) -> Result<SocketAddr, SocksProxyError> {
+        let timeout = sleep(BIND_RESPONSE_TIMEOUT);
+        tokio::pin!(timeout);
+
+        // the responses might get reordered by the mixnet, so we might learn about
+        // the accepted connection before we even know where the listener is
+        let mut listening = false;
+        let mut accepted = None;
+        let mut shutdown = self.shutdown_listener.clone();
+
+        loop {
+            let response = tokio::select! {
+                _ = shutdown.recv() => {
+                    log::trace!("SocksClient: Received shutdown while waiting for bind");
+                    return Err(SocksProxyError::BindTimeout);
+                }
+                _ = &mut timeout => return Err(SocksProxyError::BindTimeout),
+                response = bind_receiver.next() => response,
+            };
+            let Some(response) = response else {
+                return Err(SocksProxyError::BindFailure {
+                    message: "the bind listener channel got closed".to_string(),
+                });
+            };
+
+            match response.status {
+                BindStatus::Listening { address } => {
+                    let address = parse_bind_address(&address)?;
+                    self.acknowledge_socks5_with_address(address).await?;
+                    listening = true;
+                }
+                BindStatus::Accepted { peer } => accepted = Some(parse_bind_address(&peer)?),
+                BindStatus::Failed { message } => {
+                    return Err(SocksProxyError::BindFailure { message })
+                }
+            }
+
+            if listening {
+                if let Some(peer) = accepted {
+                    self.acknowledge_socks5_with_address(peer).await?;
+                    return Ok(peer);
+                }
+            }
+        }
+    }
+
+    async fn bind_udp_relay(&self) -> Result<UdpSocket, SocksProxyError> {
+        // bind the relay on the same interface the client has connected to
+        let local_ip = self
+            .stream
+            .local_addr()
+            .map_err(|source| SocksProxyError::UdpRelayBindFailure { source })?
+            .ip();
+        UdpSocket::bind(SocketAddr::new(local_ip, 0))
+            .await
+            .map_err(|source| SocksProxyError::UdpRelayBindFailure { source })
+    }
+
+    /// Relays datagrams between the local UDP socket and the mixnet for as long as
+    /// the TCP connection that requested the association stays open.
+    async fn run_udp_relay(
+        &mut self,
+        request_version: RequestVersion<Socks5Request>,
+        relay_socket: UdpSocket,
+        mut mix_receiver: DatagramReceiver,
+    ) {
+        let client_ip = match self.stream.peer_addr() {
+            Ok(peer_addr) => peer_addr.ip(),
+            Err(err) => {
+                log::error!("Unable to extract the remote peer address: {err}");
+                return;
+            }
+        };
+
+        self.send_open_datagram_session_to_mixnet(&request_version)
+            .await;
+        let mut last_refresh = Instant::now();
+
+        // the address the client is sending its datagrams from,
+        // we only learn it once the first datagram arrives
+        let mut client_address = None;
+        let mut control_buf = [0u8; 1];
+        let mut buf = vec![0u8; MAX_UDP_DATAGRAM_SIZE];
+        let mut shutdown = self.shutdown_listener.clone();
+
+        loop {
+            tokio::select! {
+                _ = shutdown.recv() => {
+                    log::trace!("UDP relay: Received shutdown");
+                    break;
+                }
+                read = self.stream.read(&mut control_buf) => match read {
+                    Ok(0) | Err(_) => {
+                        debug!("control connection of UDP relay {} got closed", self.connection_id);
+                        break;
+                    }
+                    // the client is not supposed to send anything else on the control connection
+                    Ok(_) => {}
+                },
+                received = relay_socket.recv_from(&mut buf) => {
+                    let (len, from) = match received {
+                        Ok(received) => received,
+                        Err(err) => {
+                            warn!("failed to receive on the UDP relay socket: {err}");
+                            break;
+                        }
+                    };
+                    if from.ip() != client_ip {
+                        debug!("dropping datagram from an unexpected source {from}");
+                        continue;
+                    }
+                    client_address = Some(from);
+
+                    let datagram = match SocksDatagram::try_from_bytes(&buf[..len]) {
+                        Ok(datagram) => datagram,
+                        Err(err) => {
+                            warn!("{err}");
+                            continue;
+                        }
+                    };
+                    // we don't support fragmentation, and as per RFC1928 we should just drop those
+                    if datagram.frag != 0 {
+                        debug!("dropping fragmented datagram");
+                        continue;
+                    }
+
+                    if last_refresh.elapsed() >= DATAGRAM_SESSION_REFRESH_INTERVAL {
+                        self.send_open_datagram_session_to_mixnet(&request_version)
+                            .await;
+                        last_refresh = Instant::now();
+                    }
+                    let SocksDatagram { address, data, .. } = datagram;
+                    let datagram = Datagram::new(self.connection_id, address, data);
+                    self.send_datagram_to_mixnet(&request_version, datagram)
+                        .await;
+                }
+                datagram = mix_receiver.next() => {
+                    let Some(datagram) = datagram else {
+                        log::trace!("UDP relay: Stopping since channel closed");
+                        break;
+                    };
+                    let Some(client_address) = client_address else {
+                        debug!("received a datagram before learning the client address");
+                        continue;
+                    };
+                    let bytes = SocksDatagram::new(datagram.address, datagram.data).into_bytes();
+                    if let Err(err) = relay_socket.send_to(&bytes, client_address).await {
+                        warn!("failed to send datagram to {client_address}: {err}");
+                    }
+                }
+            }
+        }
+
+        self.send_close_datagram_session_to_mixnet(&request_version)
+            .await;
+    }
+
     /// Handles a client request.
     async fn handle_request(&mut self) -> Result<(), SocksProxyError> {
         debug!("Handling CONNECT Command");
@@ -475,12 +817,12 @@ impl SocksClient {
 
         let remote_address = request.address_string();
 
-        // setup for receiving from the mixnet
-        let (mix_sender, mix_receiver) = mpsc::unbounded();
-
         match request.command {
             // Use the Proxy to connect to the specified addr/port
             SocksCommand::Connect => {
+                // setup for receiving from the mixnet
+                let (mix_sender, mix_receiver) = mpsc::unbounded();
+
                 trace!("Connecting to: {:?}", remote_address.clone());
                 match version {
                     SocksVersion::V4 => self.acknowledge_socks4().await,
@@ -507,8 +849,84 @@ impl SocksClient {
                 );
             }
 
-            SocksCommand::Bind => return Err(SocksProxyError::BindNotSupported), // not handled
-            SocksCommand::UdpAssociate => return Err(SocksProxyError::UdpNotSupported),
+            // Let the service provider listen for an inbound connection on our behalf
+            SocksCommand::Bind => {
+                // unlike in SOCKS5, the SOCKS4 replies do not carry the bound address
+                if version != &SocksVersion::V5 {
+                    return Err(ResponseCodeV5::CommandNotSupported.into());
+                }
+                let request_version = self.extended_request_version().await?;
+
+                // setup for receiving from the mixnet
+                let (mix_sender, mix_receiver) = mpsc::unbounded();
+                let (bind_sender, bind_receiver) = mpsc::unbounded();
+
+                // the connection has to be registered straight away as the data might
+                // arrive before we learn about the accepted connection
+                self.started_proxy = true;
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::Insert {
+                        connection_id: self.connection_id,
+                        connection_sender: mix_sender,
+                    })
+                    .unwrap();
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::InsertBindListener {
+                        connection_id: self.connection_id,
+                        bind_sender,
+                    })
+                    .unwrap();
+
+                trace!("Binding for: {:?}", remote_address.clone());
+                self.send_bind_to_mixnet(&request_version, remote_address)
+                    .await;
+                let peer = self.wait_for_bind(bind_receiver).await?;
+
+                info!(
+                    "Starting proxy for inbound connection from {peer} (id: {})",
+                    self.connection_id
+                );
+                self.proxy_stream(mix_receiver, peer.to_string()).await;
+                info!(
+                    "Proxy for inbound connection from {peer} is finished (id: {})",
+                    self.connection_id
+                );
+            }
+            SocksCommand::UdpAssociate => {
+                // there's no UDP support in SOCKS4
+                if version != &SocksVersion::V5 {
+                    return Err(ResponseCodeV5::CommandNotSupported.into());
+                }
+                let request_version = self.extended_request_version().await?;
+
+                let relay_socket = self.bind_udp_relay().await?;
+                let relay_address = relay_socket
+                    .local_addr()
+                    .map_err(|source| SocksProxyError::UdpRelayBindFailure { source })?;
+                self.acknowledge_socks5_with_address(relay_address).await?;
+
+                // setup for receiving from the mixnet
+                let (datagram_sender, datagram_receiver) = mpsc::unbounded();
+
+                self.started_proxy = true;
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::InsertDatagramSession {
+                        session_id: self.connection_id,
+                        datagram_sender,
+                    })
+                    .unwrap();
+
+                info!(
+                    "Starting UDP relay on {relay_address} (id: {})",
+                    self.connection_id
+                );
+                self.run_udp_relay(request_version, relay_socket, datagram_receiver)
+                    .await;
+                info!(
+                    "UDP relay on {relay_address} is finished (id: {})",
+                    self.connection_id
+                );
+            }
         };
 
         Ok(())
@@ -534,6 +952,22 @@ impl SocksClient {
             .unwrap();
     }
 
+    /// Writes a Socks5 header back to the requesting client's TCP stream,
+    /// with the bound address set to the provided value.
+    async fn acknowledge_socks5_with_address(
+        &mut self,
+        address: SocketAddr,
+    ) -> Result<(), SocksProxyError> {
+        let response: Vec<u8> = [SOCKS5_VERSION, ResponseCodeV5::Success as u8, RESERVED]
+            .into_iter()
+            .chain(encode_socket_address(address))
+            .collect();
+        self.stream
+            .write_all(&response)
+            .await
+            .map_err(|source| SocksProxyError::SocketWriteError { source })
+    }
+
     /// Writes a Socks4 header back to the requesting client's TCP stream,
     async fn acknowledge_socks4(&mut self) {
         self.stream
@@ -693,3 +1127,174 @@ impl SocksClient {
         Ok(methods)
     }
 }
+
+fn parse_bind_address(address: &str) -> Result<SocketAddr, SocksProxyError> {
+    address.parse().map_err(|_| SocksProxyError::BindFailure {
+        message: format!("{address} is not a valid socket address"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::socks::provider_version::{parse_provider_version, ProviderVersionSender};
+    use nym_client_core::client::inbound_messages::InputMessageReceiver;
+    use nym_service_providers_common::interface::{
+        ControlRequest, RequestContent, ServiceProviderRequest, SupportedVersions,
+    };
+    use nym_socks5_requests::{Socks5RequestContent, Socks5Response, Socks5ResponseContent};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    const PROVIDER: &str = "CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f";
+
+    struct TestSetup {
+        socks_stream: TcpStream,
+        client: JoinHandle<Result<(), SocksProxyError>>,
+        input_receiver: InputMessageReceiver,
+        controller_receiver: mpsc::UnboundedReceiver<ControllerCommand>,
+        provider_version: ProviderVersionSender,
+    }
+
+    impl TestSetup {
+        async fn new() -> Self {
+            // the default configuration still uses the legacy protocol version
+            let socks5_config = config::Socks5::new(PROVIDER);
+            let client_config = Config::new(
+                PacketSize::default(),
+                socks5_config.provider_interface_version,
+                socks5_config.socks5_protocol_version,
+                socks5_config.send_anonymously,
+                socks5_config.socks5_debug,
+            );
+
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let socks_stream = TcpStream::connect(listener.local_addr().unwrap())
+                .await
+                .unwrap();
+            let (stream, _) = listener.accept().await.unwrap();
+
+            let (input_sender, input_receiver) = tokio::sync::mpsc::channel(16);
+            let (controller_sender, controller_receiver) = mpsc::unbounded();
+            let (provider_version, provider_version_receiver) =
+                provider_version::provider_version_channel();
+            let provider = socks5_config.get_provider_mix_address();
+
+            let mut client = SocksClient::new(
+                client_config,
+                stream,
+                Authenticator::new(vec![AuthenticationMethods::NoAuth as u8], Vec::new()),
+                input_sender,
+                &provider,
+                controller_sender,
+                provider_version_receiver,
+                &provider,
+                LaneQueueLengths::new(),
+                TaskClient::dummy(),
+                None,
+            );
+
+            TestSetup {
+                socks_stream,
+                client: tokio::spawn(async move { client.run().await }),
+                input_receiver,
+                controller_receiver,
+                provider_version,
+            }
+        }
+
+        async fn request_udp_associate(&mut self) {
+            self.socks_stream
+                .write_all(&[SOCKS5_VERSION, 1, AuthenticationMethods::NoAuth as u8])
+                .await
+                .unwrap();
+            let mut auth_reply = [0u8; 2];
+            self.socks_stream.read_exact(&mut auth_reply).await.unwrap();
+            assert_eq!(
+                auth_reply,
+                [SOCKS5_VERSION, AuthenticationMethods::NoAuth as u8]
+            );
+
+            // UDP ASSOCIATE for 0.0.0.0:0
+            self.socks_stream
+                .write_all(&[SOCKS5_VERSION, 0x03, RESERVED, 0x01, 0, 0, 0, 0, 0, 0])
+                .await
+                .unwrap();
+        }
+
+        fn learn_provider_version(&self, version: Socks5ProtocolVersion) {
+            // that's what the network requester would have sent back
+            let versions = SupportedVersions {
+                interface_version: ProviderInterfaceVersion::new_current().to_string(),
+                provider_version: version.to_string(),
+            };
+            self.provider_version
+                .send_replace(parse_provider_version(&versions));
+        }
+
+        async fn next_request(&mut self) -> Socks5ProviderRequest {
+            let data = match self.input_receiver.recv().await.unwrap() {
+                InputMessage::Anonymous { data, .. } | InputMessage::Regular { data, .. } => data,
+                other => panic!("unexpected input message: {other:?}"),
+            };
+            // parse it the same way the network requester would have
+            Socks5ProviderRequest::try_from_bytes(&data).unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn default_config_client_can_open_datagram_session() {
+        let mut setup = TestSetup::new().await;
+        setup.request_udp_associate().await;
+
+        // we don't know anything about the network requester yet
+        let query = setup.next_request().await;
+        assert!(matches!(
+            query.content,
+            RequestContent::Control(ControlRequest::SupportedRequestVersions)
+        ));
+        setup.learn_provider_version(Socks5Request::max_supported_version());
+
+        let mut reply = [0u8; 10];
+        setup.socks_stream.read_exact(&mut reply).await.unwrap();
+        assert_eq!(reply[..2], [SOCKS5_VERSION, ResponseCodeV5::Success as u8]);
+        assert!(matches!(
+            setup.controller_receiver.next().await,
+            Some(ControllerCommand::InsertDatagramSession { .. })
+        ));
+
+        let request = setup.next_request().await;
+        let RequestContent::ProviderData(request) = request.content else {
+            panic!("expected a provider request")
+        };
+        let Socks5RequestContent::OpenDatagramSession(open_request) = request.content else {
+            panic!("expected a request to open datagram session")
+        };
+
+        // and the responses sent back for this session don't get dropped for compatibility reasons
+        let datagram = Datagram::new(open_request.session_id, "1.1.1.1:53".to_string(), vec![42]);
+        let response = Socks5Response::new_datagram(request.protocol_version, datagram);
+        assert!(matches!(
+            response.content,
+            Socks5ResponseContent::Datagram(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn udp_associate_is_not_supported_by_old_network_requesters() {
+        let mut setup = TestSetup::new().await;
+        setup.learn_provider_version(Socks5ProtocolVersion::new_versioned(3));
+        setup.request_udp_associate().await;
+
+        let res = (&mut setup.client).await.unwrap();
+        assert!(matches!(
+            res,
+            Err(SocksProxyError::Socks5ResponseFailure(
+                ResponseCodeV5::CommandNotSupported
+            ))
+        ));
+
+        // we already knew the version and haven't sent anything into the mixnet
+        assert!(setup.input_receiver.try_recv().is_err());
+    }
+}
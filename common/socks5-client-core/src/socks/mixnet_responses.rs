@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::Socks5ClientCoreError;
+use crate::socks::provider_version::{self, ProviderVersionSender};
 use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
@@ -11,7 +12,9 @@ use nym_client_core::client::received_buffer::{
 };
 use nym_service_providers_common::interface::{ControlResponse, ResponseContent};
 use nym_socks5_proxy_helpers::connection_controller::{ControllerCommand, ControllerSender};
-use nym_socks5_requests::{Socks5ProviderResponse, Socks5Response, Socks5ResponseContent};
+use nym_socks5_requests::{
+    ConnectionError, Socks5ProviderResponse, Socks5Response, Socks5ResponseContent,
+};
 use nym_sphinx::receiver::ReconstructedMessage;
 use nym_task::TaskClient;
 
@@ -19,6 +22,7 @@ pub(crate) struct MixnetResponseListener {
     buffer_requester: ReceivedBufferRequestSender,
     mix_response_receiver: ReconstructedMessagesReceiver,
     controller_sender: ControllerSender,
+    provider_version: ProviderVersionSender,
     shutdown: TaskClient,
 }
 
@@ -41,6 +45,7 @@ impl MixnetResponseListener {
     pub(crate) fn new(
         buffer_requester: ReceivedBufferRequestSender,
         controller_sender: ControllerSender,
+        provider_version: ProviderVersionSender,
         shutdown: TaskClient,
     ) -> Self {
         let (mix_response_sender, mix_response_receiver) = mpsc::unbounded();
@@ -52,6 +57,7 @@ impl MixnetResponseListener {
             buffer_requester,
             mix_response_receiver,
             controller_sender,
+            provider_version,
             shutdown,
         }
     }
@@ -60,6 +66,17 @@ impl MixnetResponseListener {
         &self,
         control_response: ControlResponse,
     ) -> Result<(), Socks5ClientCoreError> {
+        if let ControlResponse::SupportedRequestVersions(versions) = control_response {
+            match provider_version::parse_provider_version(&versions) {
+                Some(version) => {
+                    debug!("the network requester supports {version} socks5 protocol version");
+                    self.provider_version.send_replace(Some(version));
+                }
+                None => warn!("received malformed supported versions response: {versions:?}"),
+            }
+            return Ok(());
+        }
+
         error!("received a control response which we don't know how to handle yet!");
         error!("got: {:?}", control_response);
 
@@ -80,12 +97,31 @@ impl MixnetResponseListener {
                 );
                 Err(err_response.into())
             }
+            Socks5ResponseContent::LimitExceeded(limit_exceeded) => {
+                error!(
+                    "Network requester refused connection id {}: {}",
+                    limit_exceeded.connection_id, limit_exceeded.limit
+                );
+                Err(ConnectionError::from(limit_exceeded).into())
+            }
             Socks5ResponseContent::NetworkData { content } => {
                 self.controller_sender
                     .unbounded_send(ControllerCommand::new_send(content))
                     .unwrap();
                 Ok(())
             }
+            Socks5ResponseContent::Datagram(datagram) => {
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::new_send_datagram(datagram))
+                    .unwrap();
+                Ok(())
+            }
+            Socks5ResponseContent::Bind(bind_response) => {
+                self.controller_sender
+                    .unbounded_send(ControllerCommand::new_send_bind_response(bind_response))
+                    .unwrap();
+                Ok(())
+            }
             Socks5ResponseContent::Query(response) => {
                 error!("received a query response which we don't know how to handle yet!");
                 error!("got: {:?}", response);
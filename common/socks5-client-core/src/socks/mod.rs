@@ -5,9 +5,11 @@ use self::types::SocksProxyError;
 pub mod authentication;
 pub(crate) mod client;
 pub(crate) mod mixnet_responses;
+mod provider_version;
 mod request;
 pub mod server;
 pub mod types;
+mod udp;
 pub mod utils;
 
 /// Version of socks
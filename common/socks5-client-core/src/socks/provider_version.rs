@@ -0,0 +1,131 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! The UDP ASSOCIATE and BIND commands rely on responses that only exist since version 4
+//! of the socks5 protocol, but network requesters running older binaries reject any request
+//! with a version they don't know about. Thus, rather than relying on the configured version,
+//! we ask the network requester which version it supports and only use the newer one
+//! for those commands if it's actually going to be understood.
+
+use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
+use nym_service_providers_common::interface::{
+    ControlRequest, ProviderInterfaceVersion, RequestVersion, SupportedVersions,
+};
+use nym_socks5_requests::{Socks5ProtocolVersion, Socks5ProviderRequest, Socks5Request};
+use nym_sphinx::addressing::clients::Recipient;
+use nym_sphinx::params::PacketType;
+use nym_task::connections::TransmissionLane;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Maximum amount of time we're going to wait for the network requester
+/// to tell us about its supported version before giving up on the request.
+pub(crate) const PROVIDER_VERSION_TIMEOUT: Duration = Duration::from_secs(20);
+
+// control responses can only be sent back using reply SURBs
+const PROVIDER_VERSION_QUERY_SURBS: u32 = 1;
+
+pub(crate) type ProviderVersionSender = watch::Sender<Option<Socks5ProtocolVersion>>;
+pub(crate) type ProviderVersionReceiver = watch::Receiver<Option<Socks5ProtocolVersion>>;
+
+pub(crate) fn provider_version_channel() -> (ProviderVersionSender, ProviderVersionReceiver) {
+    watch::channel(None)
+}
+
+/// Asks the network requester about the versions of the interface it supports.
+/// Note that network requesters that predate versioned requests are not going to respond at all.
+pub(crate) async fn send_provider_version_query(
+    input_sender: &InputMessageSender,
+    service_provider: Recipient,
+    packet_type: Option<PacketType>,
+) {
+    let request = Socks5ProviderRequest::new_control(
+        ProviderInterfaceVersion::new_current(),
+        ControlRequest::SupportedRequestVersions,
+    );
+    let input_message = InputMessage::new_anonymous(
+        service_provider,
+        request.into_bytes(),
+        PROVIDER_VERSION_QUERY_SURBS,
+        TransmissionLane::General,
+        packet_type,
+    );
+    input_sender
+        .send(input_message)
+        .await
+        .expect("InputMessageReceiver has stopped receiving!");
+}
+
+/// Extracts the socks5 protocol version out of the network requester's response.
+pub(crate) fn parse_provider_version(
+    versions: &SupportedVersions,
+) -> Option<Socks5ProtocolVersion> {
+    // the versions are sent using their `Display` implementation, i.e. as json
+    serde_json::from_str(&versions.provider_version).ok()
+}
+
+/// Returns the request version that should be used for the requests expecting the `Datagram`
+/// or `Bind` responses, as long as the network requester is able to handle them.
+pub(crate) fn extended_request_version(
+    provider_version: Socks5ProtocolVersion,
+) -> Option<RequestVersion<Socks5Request>> {
+    let protocol_version = provider_version.min(Socks5ProtocolVersion::new_current());
+    if !protocol_version.supports_extended_responses() {
+        return None;
+    }
+
+    // versioned socks5 requests can't be sent using the legacy interface
+    Some(RequestVersion::new(
+        ProviderInterfaceVersion::new_current(),
+        protocol_version,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_service_providers_common::interface::ServiceProviderRequest;
+    use nym_socks5_requests::EXTENDED_RESPONSES_VERSION;
+
+    #[test]
+    fn parsing_supported_versions() {
+        // that's what the network requesters put in their responses
+        let versions = SupportedVersions {
+            interface_version: ProviderInterfaceVersion::new_current().to_string(),
+            provider_version: Socks5Request::max_supported_version().to_string(),
+        };
+        assert_eq!(
+            parse_provider_version(&versions),
+            Some(Socks5ProtocolVersion::new_current())
+        );
+
+        let versions = SupportedVersions {
+            interface_version: ProviderInterfaceVersion::new_current().to_string(),
+            provider_version: "foomp".to_string(),
+        };
+        assert!(parse_provider_version(&versions).is_none());
+    }
+
+    #[test]
+    fn extended_requests_are_only_sent_to_supporting_providers() {
+        assert!(extended_request_version(Socks5ProtocolVersion::new_legacy()).is_none());
+        assert!(extended_request_version(Socks5ProtocolVersion::new_versioned(3)).is_none());
+
+        let version = extended_request_version(Socks5ProtocolVersion::new_versioned(
+            EXTENDED_RESPONSES_VERSION,
+        ))
+        .unwrap();
+        assert!(!version.provider_interface.is_legacy());
+        assert_eq!(
+            version.provider_protocol,
+            Socks5ProtocolVersion::new_versioned(EXTENDED_RESPONSES_VERSION)
+        );
+
+        // we never use a version we don't know about ourselves
+        let version = extended_request_version(Socks5ProtocolVersion::new_versioned(42)).unwrap();
+        assert_eq!(
+            version.provider_protocol,
+            Socks5ProtocolVersion::new_current()
+        );
+    }
+}
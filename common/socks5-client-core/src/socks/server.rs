@@ -2,6 +2,7 @@ use crate::error::Socks5ClientCoreError;
 
 use super::{
     authentication::Authenticator, client::SocksClient, mixnet_responses::MixnetResponseListener,
+    provider_version,
 };
 use crate::socks::client;
 use log::*;
@@ -79,19 +80,25 @@ impl NymSocksServer {
         });
 
         // listener for mix messages
+        let (provider_version_sender, provider_version_receiver) =
+            provider_version::provider_version_channel();
         let mut mixnet_response_listener = MixnetResponseListener::new(
             buffer_requester,
             controller_sender.clone(),
+            provider_version_sender,
             self.shutdown.clone(),
         );
         tokio::spawn(async move {
             mixnet_response_listener.run().await;
         });
 
-        // TODO:, if required, there should be another task here responsible for control requests.
-        // it should get `input_sender` to send actual requests into the mixnet
-        // and some channel that connects it from `MixnetResponseListener` to receive
-        // any control responses
+        // learn the network requester's version before anyone needs it
+        provider_version::send_provider_version_query(
+            &input_sender,
+            self.service_provider,
+            Some(self.packet_type),
+        )
+        .await;
 
         loop {
             tokio::select! {
@@ -103,6 +110,7 @@ impl NymSocksServer {
                         input_sender.clone(),
                         &self.service_provider,
                         controller_sender.clone(),
+                        provider_version_receiver.clone(),
                         &self.self_address,
                         self.lane_queue_lengths.clone(),
                         self.shutdown.clone(),
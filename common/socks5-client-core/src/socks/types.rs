@@ -84,11 +84,20 @@ pub enum SocksProxyError {
         source: Socks5RequestError,
     },
 
-    #[error("SOCKS5 UDP not (yet) supported")]
-    UdpNotSupported,
+    #[error("failed to bind the UDP relay socket: {source}")]
+    UdpRelayBindFailure {
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("received a malformed SOCKS5 UDP datagram")]
+    MalformedUdpDatagram,
+
+    #[error("the service provider failed to bind the requested listener: {message}")]
+    BindFailure { message: String },
 
-    #[error("SOCKS5 BIND not (yet) supported")]
-    BindNotSupported,
+    #[error("timed out while waiting for the inbound connection of the bind request")]
+    BindTimeout,
 }
 
 /// DST.addr variant types
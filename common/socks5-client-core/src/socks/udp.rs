@@ -0,0 +1,153 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use super::types::{AddrType, SocksProxyError};
+use super::RESERVED;
+use nym_socks5_requests::RemoteAddress;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// Maximum size of a datagram that can be received on the local UDP relay socket.
+pub(crate) const MAX_UDP_DATAGRAM_SIZE: usize = u16::MAX as usize;
+
+/// A UDP datagram exchanged with the local client as described in RFC 1928, section 7:
+///
+/// +----+------+------+----------+----------+----------+
+/// |RSV | FRAG | ATYP | DST.ADDR | DST.PORT |   DATA   |
+/// +----+------+------+----------+----------+----------+
+/// | 2  |  1   |  1   | Variable |    2     | Variable |
+/// +----+------+------+----------+----------+----------+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct SocksDatagram {
+    pub frag: u8,
+    pub address: RemoteAddress,
+    pub data: Vec<u8>,
+}
+
+impl SocksDatagram {
+    pub(crate) fn new(address: RemoteAddress, data: Vec<u8>) -> Self {
+        SocksDatagram {
+            frag: 0,
+            address,
+            data,
+        }
+    }
+
+    pub(crate) fn try_from_bytes(b: &[u8]) -> Result<Self, SocksProxyError> {
+        if b.len() < 4 {
+            return Err(SocksProxyError::MalformedUdpDatagram);
+        }
+        let frag = b[2];
+        let addr_type =
+            AddrType::from(b[3] as usize).ok_or(SocksProxyError::MalformedUdpDatagram)?;
+
+        let b = &b[4..];
+        let (host, b) = match addr_type {
+            AddrType::V4 => {
+                let octets: [u8; 4] = b
+                    .get(..4)
+                    .ok_or(SocksProxyError::MalformedUdpDatagram)?
+                    .try_into()
+                    .unwrap();
+                (Ipv4Addr::from(octets).to_string(), &b[4..])
+            }
+            AddrType::V6 => {
+                let octets: [u8; 16] = b
+                    .get(..16)
+                    .ok_or(SocksProxyError::MalformedUdpDatagram)?
+                    .try_into()
+                    .unwrap();
+                (format!("[{}]", Ipv6Addr::from(octets)), &b[16..])
+            }
+            AddrType::Domain => {
+                let len = *b.first().ok_or(SocksProxyError::MalformedUdpDatagram)? as usize;
+                let domain = b
+                    .get(1..1 + len)
+                    .ok_or(SocksProxyError::MalformedUdpDatagram)?;
+                (String::from_utf8_lossy(domain).to_string(), &b[1 + len..])
+            }
+        };
+
+        if b.len() < 2 {
+            return Err(SocksProxyError::MalformedUdpDatagram);
+        }
+        let port = u16::from_be_bytes([b[0], b[1]]);
+
+        Ok(SocksDatagram {
+            frag,
+            address: format!("{host}:{port}"),
+            data: b[2..].to_vec(),
+        })
+    }
+
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        let header = [RESERVED, RESERVED, self.frag];
+        let address = encode_address(&self.address);
+        header.into_iter().chain(address).chain(self.data).collect()
+    }
+}
+
+/// Encodes the provided address as ATYP || ADDR || PORT.
+pub(crate) fn encode_socket_address(address: SocketAddr) -> Vec<u8> {
+    let ip = match address.ip() {
+        IpAddr::V4(ip) => std::iter::once(AddrType::V4 as u8)
+            .chain(ip.octets())
+            .collect::<Vec<_>>(),
+        IpAddr::V6(ip) => std::iter::once(AddrType::V6 as u8)
+            .chain(ip.octets())
+            .collect::<Vec<_>>(),
+    };
+    ip.into_iter().chain(address.port().to_be_bytes()).collect()
+}
+
+fn encode_address(address: &RemoteAddress) -> Vec<u8> {
+    if let Ok(socket_address) = address.parse() {
+        return encode_socket_address(socket_address);
+    }
+
+    // if it's not a valid socket address, it must have been a domain
+    let (domain, port) = address.rsplit_once(':').unwrap_or((address, "0"));
+    let port = port.parse::<u16>().unwrap_or_default();
+    let domain = domain.as_bytes();
+    let domain = &domain[..domain.len().min(u8::MAX as usize)];
+
+    std::iter::once(AddrType::Domain as u8)
+        .chain(std::iter::once(domain.len() as u8))
+        .chain(domain.iter().copied())
+        .chain(port.to_be_bytes())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_client_datagrams() {
+        let ipv4 = [0, 0, 0, 1, 1, 1, 1, 1, 0, 53, 42, 42];
+        assert_eq!(
+            SocksDatagram::try_from_bytes(&ipv4).unwrap(),
+            SocksDatagram::new("1.1.1.1:53".to_string(), vec![42, 42])
+        );
+
+        let domain = [0, 0, 0, 3, 7, 102, 111, 111, 46, 99, 111, 109, 1, 187, 42];
+        assert_eq!(
+            SocksDatagram::try_from_bytes(&domain).unwrap(),
+            SocksDatagram::new("foo.com:443".to_string(), vec![42])
+        );
+
+        let fragmented = [0, 0, 1, 1, 1, 1, 1, 1, 0, 53];
+        assert_eq!(SocksDatagram::try_from_bytes(&fragmented).unwrap().frag, 1);
+
+        let truncated = [0, 0, 0, 1, 1, 1];
+        assert!(SocksDatagram::try_from_bytes(&truncated).is_err());
+    }
+
+    #[test]
+    fn datagrams_serde() {
+        for address in ["1.1.1.1:53", "[2606:4700:4700::1111]:53", "foo.com:443"] {
+            let datagram = SocksDatagram::new(address.to_string(), vec![1, 2, 3]);
+            let bytes = SocksDatagram::new(address.to_string(), vec![1, 2, 3]).into_bytes();
+            assert_eq!(datagram, SocksDatagram::try_from_bytes(&bytes).unwrap());
+        }
+    }
+}
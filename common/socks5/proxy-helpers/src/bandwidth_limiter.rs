@@ -0,0 +1,114 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// Limits the number of bytes per second that can go through all the proxies sharing the limiter
+/// (in either direction). Bursts of up to a second worth of traffic are allowed.
+#[derive(Clone, Debug)]
+pub struct BandwidthLimiter {
+    bucket: Arc<Mutex<TokenBucket>>,
+}
+
+impl BandwidthLimiter {
+    pub fn new(bytes_per_second: u64) -> Self {
+        BandwidthLimiter {
+            bucket: Arc::new(Mutex::new(TokenBucket::new(
+                bytes_per_second,
+                Instant::now(),
+            ))),
+        }
+    }
+
+    /// Waits until transferring `amount` bytes would no longer exceed the limit.
+    pub async fn consume(&self, amount: usize) {
+        let delay = self
+            .bucket
+            .lock()
+            .expect("bandwidth limiter lock got poisoned")
+            .reserve(amount as u64, Instant::now());
+
+        if !delay.is_zero() {
+            sleep(delay).await
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    bytes_per_second: f64,
+
+    // note: it might become negative if the reservations exceed the available bandwidth,
+    // in which case the subsequent reservations have to wait for the debt to be paid off first
+    available: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_second: u64, now: Instant) -> Self {
+        TokenBucket {
+            bytes_per_second: bytes_per_second.max(1) as f64,
+            available: bytes_per_second as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.available = (self.available + elapsed.as_secs_f64() * self.bytes_per_second)
+            .min(self.bytes_per_second);
+        self.last_refill = now;
+    }
+
+    /// Reserves the specified amount of bytes and returns the time the caller has to wait
+    /// before it's allowed to actually send them.
+    fn reserve(&mut self, amount: u64, now: Instant) -> Duration {
+        self.refill(now);
+        self.available -= amount as f64;
+
+        if self.available >= 0. {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available / self.bytes_per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservations_within_limit_do_not_wait() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(1000, now);
+
+        assert!(bucket.reserve(600, now).is_zero());
+        assert!(bucket.reserve(400, now).is_zero());
+    }
+
+    #[test]
+    fn reservations_over_limit_wait_for_refill() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(1000, now);
+
+        assert!(bucket.reserve(1000, now).is_zero());
+        assert_eq!(bucket.reserve(500, now), Duration::from_millis(500));
+
+        // the debt has to be paid off before anything else can go through
+        let later = now + Duration::from_millis(500);
+        assert_eq!(bucket.reserve(1000, later), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn unused_bandwidth_does_not_accumulate_beyond_a_second() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(1000, now);
+
+        let later = now + Duration::from_secs(10);
+        assert!(bucket.reserve(1000, later).is_zero());
+        assert_eq!(bucket.reserve(100, later), Duration::from_millis(100));
+    }
+}
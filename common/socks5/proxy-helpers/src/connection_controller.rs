@@ -5,7 +5,7 @@ use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
 use nym_ordered_buffer::{OrderedMessageBuffer, ReadContiguousData};
-use nym_socks5_requests::{ConnectionId, SocketData};
+use nym_socks5_requests::{BindResponse, ConnectionId, Datagram, SocketData};
 use nym_task::connections::{ConnectionCommand, ConnectionCommandSender};
 use nym_task::TaskClient;
 use std::collections::{HashMap, HashSet};
@@ -28,6 +28,21 @@ pub type ConnectionSender = mpsc::UnboundedSender<ConnectionMessage>;
 /// Receiver part of the [`ConnectionSender`]
 pub type ConnectionReceiver = mpsc::UnboundedReceiver<ConnectionMessage>;
 
+/// Channel responsible for sending datagrams that were received from mix network into particular
+/// datagram session. Unlike [`ConnectionSender`], datagrams are forwarded as they are received,
+/// without any ordering.
+pub type DatagramSender = mpsc::UnboundedSender<Datagram>;
+
+/// Receiver part of the [`DatagramSender`]
+pub type DatagramReceiver = mpsc::UnboundedReceiver<Datagram>;
+
+/// Channel responsible for notifying a pending bind about the progress of its listener
+/// on the remote side.
+pub type BindResponseSender = mpsc::UnboundedSender<BindResponse>;
+
+/// Receiver part of the [`BindResponseSender`]
+pub type BindResponseReceiver = mpsc::UnboundedReceiver<BindResponse>;
+
 pub type ControllerSender = mpsc::UnboundedSender<ControllerCommand>;
 pub type ControllerReceiver = mpsc::UnboundedReceiver<ControllerCommand>;
 
@@ -36,18 +51,40 @@ pub enum ControllerCommand {
         connection_id: ConnectionId,
         connection_sender: ConnectionSender,
     },
+    InsertDatagramSession {
+        session_id: ConnectionId,
+        datagram_sender: DatagramSender,
+    },
+    InsertBindListener {
+        connection_id: ConnectionId,
+        bind_sender: BindResponseSender,
+    },
     Remove {
         connection_id: ConnectionId,
     },
     Send {
         data: SocketData,
     },
+    SendDatagram {
+        datagram: Datagram,
+    },
+    SendBindResponse {
+        response: BindResponse,
+    },
 }
 
 impl ControllerCommand {
     pub fn new_send(data: SocketData) -> Self {
         ControllerCommand::Send { data }
     }
+
+    pub fn new_send_datagram(datagram: Datagram) -> Self {
+        ControllerCommand::SendDatagram { datagram }
+    }
+
+    pub fn new_send_bind_response(response: BindResponse) -> Self {
+        ControllerCommand::SendBindResponse { response }
+    }
 }
 
 struct ActiveConnection {
@@ -82,6 +119,8 @@ pub enum BroadcastActiveConnections {
 /// proxy.
 pub struct Controller {
     active_connections: HashMap<ConnectionId, ActiveConnection>,
+    datagram_sessions: HashMap<ConnectionId, DatagramSender>,
+    bind_listeners: HashMap<ConnectionId, BindResponseSender>,
     receiver: ControllerReceiver,
 
     // TODO: this will need to be either completely removed (from code) or periodically cleaned
@@ -110,6 +149,8 @@ impl Controller {
         (
             Controller {
                 active_connections: HashMap::new(),
+                datagram_sessions: HashMap::new(),
+                bind_listeners: HashMap::new(),
                 receiver,
                 recently_closed: HashSet::new(),
                 client_connection_tx,
@@ -140,9 +181,32 @@ impl Controller {
         }
     }
 
+    fn insert_datagram_session(
+        &mut self,
+        session_id: ConnectionId,
+        datagram_sender: DatagramSender,
+    ) {
+        if self
+            .datagram_sessions
+            .insert(session_id, datagram_sender)
+            .is_some()
+        {
+            error!("Received a duplicate datagram session {session_id}!")
+        }
+    }
+
+    fn insert_bind_listener(&mut self, conn_id: ConnectionId, bind_sender: BindResponseSender) {
+        if self.bind_listeners.insert(conn_id, bind_sender).is_some() {
+            error!("Received a duplicate bind listener for {conn_id}!")
+        }
+    }
+
     fn remove_connection(&mut self, conn_id: ConnectionId) {
         debug!("Removing {conn_id} from controller");
-        if self.active_connections.remove(&conn_id).is_none() {
+        self.bind_listeners.remove(&conn_id);
+        if self.active_connections.remove(&conn_id).is_none()
+            && self.datagram_sessions.remove(&conn_id).is_none()
+        {
             error!("tried to remove non-existing connection with id: {conn_id}",)
         }
         self.recently_closed.insert(conn_id);
@@ -209,6 +273,34 @@ impl Controller {
         }
     }
 
+    fn send_to_datagram_session(&mut self, datagram: Datagram) {
+        let session_id = datagram.session_id;
+        let Some(datagram_sender) = self.datagram_sessions.get(&session_id) else {
+            // datagrams are unreliable by design, so there's no point in buffering them
+            debug!(
+                "Received a datagram for unknown session {session_id} ({} bytes were dropped)",
+                datagram.data.len()
+            );
+            return;
+        };
+
+        if let Err(err) = datagram_sender.unbounded_send(datagram) {
+            error!("failed to send on the datagram session channel: {err}");
+        }
+    }
+
+    fn send_to_bind_listener(&mut self, response: BindResponse) {
+        let conn_id = response.connection_id;
+        let Some(bind_sender) = self.bind_listeners.get(&conn_id) else {
+            debug!("Received a bind response for unknown connection {conn_id}");
+            return;
+        };
+
+        if let Err(err) = bind_sender.unbounded_send(response) {
+            error!("failed to send on the bind listener channel: {err}");
+        }
+    }
+
     pub async fn run(&mut self) {
         loop {
             tokio::select! {
@@ -216,9 +308,21 @@ impl Controller {
                     Some(ControllerCommand::Send{data}) => {
                         self.send_to_connection(data)
                     }
+                    Some(ControllerCommand::SendDatagram{datagram}) => {
+                        self.send_to_datagram_session(datagram)
+                    }
+                    Some(ControllerCommand::SendBindResponse{response}) => {
+                        self.send_to_bind_listener(response)
+                    }
                     Some(ControllerCommand::Insert{connection_id, connection_sender}) => {
                         self.insert_connection(connection_id, connection_sender)
                     }
+                    Some(ControllerCommand::InsertDatagramSession{session_id, datagram_sender}) => {
+                        self.insert_datagram_session(session_id, datagram_sender)
+                    }
+                    Some(ControllerCommand::InsertBindListener{connection_id, bind_sender}) => {
+                        self.insert_bind_listener(connection_id, bind_sender)
+                    }
                     Some(ControllerCommand::Remove{ connection_id }) => self.remove_connection(connection_id),
                     None => {
                         log::trace!("SOCKS5 Controller: Stopping since channel closed");
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod available_reader;
+pub mod bandwidth_limiter;
 pub mod connection_controller;
 pub mod ordered_sender;
 pub mod proxy_runner;
@@ -3,6 +3,7 @@
 
 use super::SHUTDOWN_TIMEOUT;
 use crate::available_reader::AvailableReader;
+use crate::bandwidth_limiter::BandwidthLimiter;
 use crate::ordered_sender::OrderedMessageSender;
 use crate::proxy_runner::KEEPALIVE_INTERVAL;
 use futures::FutureExt;
@@ -74,6 +75,7 @@ async fn wait_for_lane(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run_inbound<F, S>(
     mut reader: OwnedReadHalf,
     mut message_sender: OrderedMessageSender<F, S>,
@@ -81,6 +83,7 @@ pub(super) async fn run_inbound<F, S>(
     available_plaintext_per_mix_packet: usize,
     shutdown_notify: Arc<Notify>,
     lane_queue_lengths: Option<LaneQueueLengths>,
+    bandwidth_limiter: Option<BandwidthLimiter>,
     mut shutdown_listener: TaskClient,
 ) -> OwnedReadHalf
 where
@@ -151,6 +154,17 @@ where
             read_data = wait_until_lane_almost_empty(&lane_queue_lengths, connection_id)
                 .then(|_| available_reader.next()), if !we_are_closed =>
             {
+                if let (Some(limiter), Some(Ok(data))) = (&bandwidth_limiter, &read_data) {
+                    // waiting for the bandwidth might take a while, so don't hold up the shutdown
+                    select! {
+                        _ = limiter.consume(data.len()) => {}
+                        _ = shutdown_listener.recv() => {
+                            log::trace!("ProxyRunner inbound: Received shutdown while waiting for bandwidth");
+                            break;
+                        }
+                    }
+                }
+
                 let processed = message_sender.process_data(read_data);
                 let is_done = processed.is_done;
 
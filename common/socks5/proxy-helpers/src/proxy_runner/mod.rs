@@ -1,6 +1,7 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::bandwidth_limiter::BandwidthLimiter;
 use crate::connection_controller::ConnectionReceiver;
 use crate::ordered_sender::OrderedMessageSender;
 use nym_socks5_requests::{ConnectionId, SocketData};
@@ -56,6 +57,8 @@ pub struct ProxyRunner<S> {
 
     available_plaintext_per_mix_packet: usize,
 
+    bandwidth_limiter: Option<BandwidthLimiter>,
+
     // Listens to shutdown commands from higher up
     shutdown_listener: TaskClient,
 }
@@ -85,10 +88,18 @@ where
             connection_id,
             lane_queue_lengths,
             available_plaintext_per_mix_packet,
+            bandwidth_limiter: None,
             shutdown_listener,
         }
     }
 
+    /// Limits the throughput of this proxy, in both directions, with the provided (potentially shared) limiter.
+    #[must_use]
+    pub fn with_bandwidth_limiter(mut self, bandwidth_limiter: BandwidthLimiter) -> Self {
+        self.bandwidth_limiter = Some(bandwidth_limiter);
+        self
+    }
+
     // The `adapter_fn` is used to transform whatever was read into appropriate
     // request/response as required by entity running particular side of the proxy.
     pub async fn run<F>(mut self, adapter_fn: F) -> Self
@@ -113,6 +124,7 @@ where
             self.available_plaintext_per_mix_packet,
             Arc::clone(&shutdown_notify),
             self.lane_queue_lengths.clone(),
+            self.bandwidth_limiter.clone(),
             self.shutdown_listener.clone(),
         );
 
@@ -123,6 +135,7 @@ where
             self.mix_receiver.take().unwrap(),
             self.connection_id,
             shutdown_notify,
+            self.bandwidth_limiter.clone(),
             self.shutdown_listener.clone(),
         );
 
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::SHUTDOWN_TIMEOUT;
+use crate::bandwidth_limiter::BandwidthLimiter;
 use crate::connection_controller::{ConnectionMessage, ConnectionReceiver};
 use futures::FutureExt;
 use futures::StreamExt;
@@ -44,6 +45,7 @@ async fn deal_with_message(
     false
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn run_outbound(
     mut writer: OwnedWriteHalf,
     local_destination_address: String, // addresses are provided for better logging
@@ -51,6 +53,7 @@ pub(super) async fn run_outbound(
     mut mix_receiver: ConnectionReceiver,
     connection_id: ConnectionId,
     shutdown_notify: Arc<Notify>,
+    bandwidth_limiter: Option<BandwidthLimiter>,
     mut shutdown_listener: TaskClient,
 ) -> (OwnedWriteHalf, ConnectionReceiver) {
     let shutdown_future = shutdown_notify.notified().then(|_| sleep(SHUTDOWN_TIMEOUT));
@@ -62,6 +65,16 @@ pub(super) async fn run_outbound(
         select! {
             connection_message = &mut mix_receiver.next() => {
                 if let Some(connection_message) = connection_message {
+                    if let Some(limiter) = &bandwidth_limiter {
+                        // waiting for the bandwidth might take a while, so don't hold up the shutdown
+                        select! {
+                            _ = limiter.consume(connection_message.payload.len()) => {}
+                            _ = shutdown_listener.recv() => {
+                                log::trace!("ProxyRunner outbound: Received shutdown while waiting for bandwidth");
+                                break;
+                            }
+                        }
+                    }
                     if deal_with_message(connection_message, &mut writer, &local_destination_address, &remote_source_address, connection_id).await {
                         break;
                     }
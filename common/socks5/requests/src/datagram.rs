@@ -0,0 +1,115 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ConnectionId, RemoteAddress};
+use std::mem;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MalformedDatagramError {
+    #[error("not enough bytes to recover the datagram session id")]
+    SessionIdTooShort,
+
+    #[error("not enough bytes to recover the length of the address")]
+    AddressLengthTooShort,
+
+    #[error("not enough bytes to recover the address")]
+    AddressTooShort,
+}
+
+/// A single UDP datagram exchanged as part of a datagram session.
+/// In requests the address specifies the destination of the datagram,
+/// while in responses it specifies its source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Datagram {
+    pub session_id: ConnectionId,
+    pub address: RemoteAddress,
+    pub data: Vec<u8>,
+}
+
+impl Datagram {
+    const SESSION_ID_LEN: usize = mem::size_of::<ConnectionId>();
+
+    pub fn new(session_id: ConnectionId, address: RemoteAddress, data: Vec<u8>) -> Self {
+        Datagram {
+            session_id,
+            address,
+            data,
+        }
+    }
+
+    pub fn try_from_bytes(b: &[u8]) -> Result<Datagram, MalformedDatagramError> {
+        if b.len() < Self::SESSION_ID_LEN {
+            return Err(MalformedDatagramError::SessionIdTooShort);
+        }
+        // the unwrap here is fine as we just ensured we have enough bytes
+        let session_id = ConnectionId::from_be_bytes(b[..Self::SESSION_ID_LEN].try_into().unwrap());
+
+        let b = &b[Self::SESSION_ID_LEN..];
+        if b.len() < 2 {
+            return Err(MalformedDatagramError::AddressLengthTooShort);
+        }
+        let address_length = u16::from_be_bytes([b[0], b[1]]) as usize;
+        if b.len() < 2 + address_length {
+            return Err(MalformedDatagramError::AddressTooShort);
+        }
+        let address = String::from_utf8_lossy(&b[2..2 + address_length]).to_string();
+        let data = b[2 + address_length..].to_vec();
+
+        Ok(Datagram {
+            session_id,
+            address,
+            data,
+        })
+    }
+
+    // the serialization of the datagram looks as follows:
+    // SESSION_ID (8B) || ADDR_LEN (2B) || ADDR || DATA
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.into_bytes_iter().collect()
+    }
+
+    pub fn into_bytes_iter(self) -> impl Iterator<Item = u8> {
+        let address_bytes = self.address.into_bytes();
+        let address_bytes_len = address_bytes.len() as u16;
+
+        self.session_id
+            .to_be_bytes()
+            .into_iter()
+            .chain(address_bytes_len.to_be_bytes())
+            .chain(address_bytes)
+            .chain(self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datagram_serde() {
+        let datagram = Datagram::new(42, "1.1.1.1:53".to_string(), vec![1, 2, 3]);
+        let bytes = datagram.clone().into_bytes();
+        assert_eq!(datagram, Datagram::try_from_bytes(&bytes).unwrap());
+
+        let empty = Datagram::new(42, "1.1.1.1:53".to_string(), Vec::new());
+        let bytes = empty.clone().into_bytes();
+        assert_eq!(empty, Datagram::try_from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn datagram_deserialization_errors() {
+        assert_eq!(
+            Datagram::try_from_bytes(&[1, 2, 3]).unwrap_err(),
+            MalformedDatagramError::SessionIdTooShort
+        );
+        assert_eq!(
+            Datagram::try_from_bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 0]).unwrap_err(),
+            MalformedDatagramError::AddressLengthTooShort
+        );
+        assert_eq!(
+            Datagram::try_from_bytes(&[1, 2, 3, 4, 5, 6, 7, 8, 0, 4, 102]).unwrap_err(),
+            MalformedDatagramError::AddressTooShort
+        );
+    }
+}
@@ -6,10 +6,12 @@ use nym_service_providers_common::interface::ServiceProviderMessagingError;
 use std::mem;
 use thiserror::Error;
 
+pub use datagram::*;
 pub use request::*;
 pub use response::*;
 pub use version::*;
 
+pub mod datagram;
 pub mod request;
 pub mod response;
 pub mod version;
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    make_bincode_serializer, InsufficientSocketDataError, SocketData, Socks5ProtocolVersion,
-    Socks5RequestError, Socks5Response,
+    make_bincode_serializer, Datagram, InsufficientSocketDataError, MalformedDatagramError,
+    SocketData, Socks5ProtocolVersion, Socks5RequestError, Socks5Response,
 };
 use nym_service_providers_common::interface::{Serializable, ServiceProviderRequest};
 use nym_sphinx_addressing::clients::{Recipient, RecipientFormattingError};
@@ -22,6 +22,10 @@ pub enum RequestFlag {
     Connect = 0,
     Send = 1,
     Query = 2,
+    OpenDatagramSession = 3,
+    SendDatagram = 4,
+    CloseDatagramSession = 5,
+    Bind = 6,
 }
 
 impl TryFrom<u8> for RequestFlag {
@@ -32,6 +36,12 @@ impl TryFrom<u8> for RequestFlag {
             _ if value == (RequestFlag::Connect as u8) => Ok(Self::Connect),
             _ if value == (RequestFlag::Send as u8) => Ok(Self::Send),
             _ if value == (RequestFlag::Query as u8) => Ok(Self::Query),
+            _ if value == (RequestFlag::OpenDatagramSession as u8) => Ok(Self::OpenDatagramSession),
+            _ if value == (RequestFlag::SendDatagram as u8) => Ok(Self::SendDatagram),
+            _ if value == (RequestFlag::CloseDatagramSession as u8) => {
+                Ok(Self::CloseDatagramSession)
+            }
+            _ if value == (RequestFlag::Bind as u8) => Ok(Self::Bind),
             value => Err(RequestDeserializationError::UnknownRequestFlag { value }),
         }
     }
@@ -68,6 +78,9 @@ pub enum RequestDeserializationError {
 
     #[error(transparent)]
     InvalidSocketData(#[from] InsufficientSocketDataError),
+
+    #[error(transparent)]
+    MalformedDatagram(#[from] MalformedDatagramError),
 }
 
 impl RequestDeserializationError {
@@ -102,6 +115,45 @@ pub struct SendRequest {
     pub data: SocketData,
 }
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct OpenDatagramSessionRequest {
+    pub session_id: ConnectionId,
+    pub return_address: Option<Recipient>,
+}
+
+impl Debug for OpenDatagramSessionRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpenDatagramSessionRequest")
+            .field("session_id", &self.session_id)
+            .field(
+                "return_address",
+                &self.return_address.map(|r| r.to_string()),
+            )
+            .finish()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct BindRequest {
+    pub conn_id: ConnectionId,
+    /// Address of the host that is expected to connect to the listening socket.
+    pub expected_peer: RemoteAddress,
+    pub return_address: Option<Recipient>,
+}
+
+impl Debug for BindRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BindRequest")
+            .field("conn_id", &self.conn_id)
+            .field("expected_peer", &self.expected_peer)
+            .field(
+                "return_address",
+                &self.return_address.map(|r| r.to_string()),
+            )
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum QueryRequest {
@@ -214,6 +266,49 @@ impl Socks5Request {
             content: Socks5RequestContent::Query(query),
         }
     }
+
+    pub fn new_open_datagram_session(
+        protocol_version: Socks5ProtocolVersion,
+        session_id: ConnectionId,
+        return_address: Option<Recipient>,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::new_open_datagram_session(session_id, return_address),
+        }
+    }
+
+    pub fn new_send_datagram(
+        protocol_version: Socks5ProtocolVersion,
+        datagram: Datagram,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::SendDatagram(datagram),
+        }
+    }
+
+    pub fn new_close_datagram_session(
+        protocol_version: Socks5ProtocolVersion,
+        session_id: ConnectionId,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::CloseDatagramSession { session_id },
+        }
+    }
+
+    pub fn new_bind(
+        protocol_version: Socks5ProtocolVersion,
+        conn_id: ConnectionId,
+        expected_peer: RemoteAddress,
+        return_address: Option<Recipient>,
+    ) -> Socks5Request {
+        Socks5Request {
+            protocol_version,
+            content: Socks5RequestContent::new_bind(conn_id, expected_peer, return_address),
+        }
+    }
 }
 
 /// A request from a SOCKS5 client that a Nym Socks5 service provider should
@@ -229,6 +324,23 @@ pub enum Socks5RequestContent {
     Send(SendRequest),
 
     Query(QueryRequest),
+
+    /// Start a new UDP session on behalf of the client.
+    /// All datagrams received on this session should come back to the specified `Recipient`.
+    /// Repeating the request for an already existing session keeps it alive.
+    OpenDatagramSession(Box<OpenDatagramSessionRequest>),
+
+    /// Send a single datagram to the specified address using an existing UDP session.
+    SendDatagram(Datagram),
+
+    /// Close an existing UDP session.
+    CloseDatagramSession {
+        session_id: ConnectionId,
+    },
+
+    /// Start listening for a single inbound TCP connection from the specified peer.
+    /// Once accepted, the connection is proxied the same way as the ones created with `Connect`.
+    Bind(Box<BindRequest>),
 }
 
 impl Socks5RequestContent {
@@ -250,6 +362,30 @@ impl Socks5RequestContent {
         Socks5RequestContent::Send(SendRequest { data })
     }
 
+    /// Construct a new Request::OpenDatagramSession instance
+    pub fn new_open_datagram_session(
+        session_id: ConnectionId,
+        return_address: Option<Recipient>,
+    ) -> Socks5RequestContent {
+        Socks5RequestContent::OpenDatagramSession(Box::new(OpenDatagramSessionRequest {
+            session_id,
+            return_address,
+        }))
+    }
+
+    /// Construct a new Request::Bind instance
+    pub fn new_bind(
+        conn_id: ConnectionId,
+        expected_peer: RemoteAddress,
+        return_address: Option<Recipient>,
+    ) -> Socks5RequestContent {
+        Socks5RequestContent::Bind(Box::new(BindRequest {
+            conn_id,
+            expected_peer,
+            return_address,
+        }))
+    }
+
     /// Deserialize the request type, connection id, destination address and port,
     /// and the request body from bytes.
     ///
@@ -271,6 +407,18 @@ impl Socks5RequestContent {
     // send:
     // RequestFlag::Send || CONN_ID || LOCAL_CLOSED || DATA
     // where DATA: SEQ || TRUE_DATA
+    //
+    // open datagram session:
+    // RequestFlag::OpenDatagramSession || SESSION_ID || <RETURN_ADDR>
+    //
+    // send datagram:
+    // RequestFlag::SendDatagram || SESSION_ID || ADDR_LEN || ADDR || DATA
+    //
+    // close datagram session:
+    // RequestFlag::CloseDatagramSession || SESSION_ID
+    //
+    // bind:
+    // RequestFlag::Bind || CONN_ID || PEER_LEN || PEER || <RETURN_ADDR>
 
     pub fn try_from_bytes(b: &[u8]) -> Result<Socks5RequestContent, RequestDeserializationError> {
         // each request needs to at least contain flag and ConnectionId
@@ -280,49 +428,7 @@ impl Socks5RequestContent {
 
         match RequestFlag::try_from(b[0])? {
             RequestFlag::Connect => {
-                if b.len() < 9 {
-                    return Err(RequestDeserializationError::ConnectionIdTooShort);
-                }
-                let conn_id = u64::from_be_bytes([b[1], b[2], b[3], b[4], b[5], b[6], b[7], b[8]]);
-
-                let connect_request_bytes = &b[9..];
-
-                // we need to be able to read at least 2 bytes that specify address length
-                if connect_request_bytes.len() < 2 {
-                    return Err(RequestDeserializationError::AddressLengthTooShort);
-                }
-
-                let address_length =
-                    u16::from_be_bytes([connect_request_bytes[0], connect_request_bytes[1]])
-                        as usize;
-
-                if connect_request_bytes.len() < 2 + address_length {
-                    return Err(RequestDeserializationError::AddressTooShort);
-                }
-
-                let address_start = 2;
-                let address_end = address_start + address_length;
-                let address_bytes = &connect_request_bytes[address_start..address_end];
-                let remote_address = String::from_utf8_lossy(address_bytes).to_string();
-
-                // just a temporary reference to mid-slice for ease of use
-                let recipient_data_bytes = &connect_request_bytes[address_end..];
-
-                let return_address = if recipient_data_bytes.is_empty() {
-                    None
-                } else {
-                    if recipient_data_bytes.len() != Recipient::LEN {
-                        return Err(RequestDeserializationError::ReturnAddressTooShort);
-                    }
-
-                    let mut return_bytes = [0u8; Recipient::LEN];
-                    return_bytes.copy_from_slice(&recipient_data_bytes[..Recipient::LEN]);
-                    Some(
-                        Recipient::try_from_bytes(return_bytes)
-                            .map_err(RequestDeserializationError::MalformedReturnAddress)?,
-                    )
-                };
-
+                let (conn_id, remote_address, return_address) = parse_connection_target(&b[1..])?;
                 Ok(Socks5RequestContent::new_connect(
                     conn_id,
                     remote_address,
@@ -337,6 +443,29 @@ impl Socks5RequestContent {
                 let query = make_bincode_serializer().deserialize(&b[1..])?;
                 Ok(Socks5RequestContent::Query(query))
             }
+            RequestFlag::OpenDatagramSession => {
+                let session_id = parse_session_id(&b[1..])?;
+                let return_address = parse_optional_return_address(&b[9..])?;
+                Ok(Socks5RequestContent::new_open_datagram_session(
+                    session_id,
+                    return_address,
+                ))
+            }
+            RequestFlag::SendDatagram => Ok(Socks5RequestContent::SendDatagram(
+                Datagram::try_from_bytes(&b[1..])?,
+            )),
+            RequestFlag::CloseDatagramSession => {
+                let session_id = parse_session_id(&b[1..])?;
+                Ok(Socks5RequestContent::CloseDatagramSession { session_id })
+            }
+            RequestFlag::Bind => {
+                let (conn_id, expected_peer, return_address) = parse_connection_target(&b[1..])?;
+                Ok(Socks5RequestContent::new_bind(
+                    conn_id,
+                    expected_peer,
+                    return_address,
+                ))
+            }
         }
     }
 
@@ -346,21 +475,12 @@ impl Socks5RequestContent {
     pub fn into_bytes(self) -> Vec<u8> {
         match self {
             // connect is: CONN_FLAG || CONN_ID || REMOTE_LEN || REMOTE || RETURN
-            Socks5RequestContent::Connect(req) => {
-                let remote_address_bytes = req.remote_addr.into_bytes();
-                let remote_address_bytes_len = remote_address_bytes.len() as u16;
-
-                let iter = std::iter::once(RequestFlag::Connect as u8)
-                    .chain(req.conn_id.to_be_bytes())
-                    .chain(remote_address_bytes_len.to_be_bytes())
-                    .chain(remote_address_bytes);
-
-                if let Some(return_address) = req.return_address {
-                    iter.chain(return_address.to_bytes()).collect()
-                } else {
-                    iter.collect()
-                }
-            }
+            Socks5RequestContent::Connect(req) => connection_target_bytes(
+                RequestFlag::Connect,
+                req.conn_id,
+                req.remote_addr,
+                req.return_address,
+            ),
             Socks5RequestContent::Send(req) => std::iter::once(RequestFlag::Send as u8)
                 .chain(req.data.into_request_bytes_iter())
                 .collect(),
@@ -377,10 +497,115 @@ impl Socks5RequestContent {
                     .chain(query_bytes)
                     .collect()
             }
+            // open is: OPEN_FLAG || SESSION_ID || RETURN
+            Socks5RequestContent::OpenDatagramSession(req) => {
+                let iter = std::iter::once(RequestFlag::OpenDatagramSession as u8)
+                    .chain(req.session_id.to_be_bytes());
+
+                if let Some(return_address) = req.return_address {
+                    iter.chain(return_address.to_bytes()).collect()
+                } else {
+                    iter.collect()
+                }
+            }
+            Socks5RequestContent::SendDatagram(datagram) => {
+                std::iter::once(RequestFlag::SendDatagram as u8)
+                    .chain(datagram.into_bytes_iter())
+                    .collect()
+            }
+            Socks5RequestContent::CloseDatagramSession { session_id } => {
+                std::iter::once(RequestFlag::CloseDatagramSession as u8)
+                    .chain(session_id.to_be_bytes())
+                    .collect()
+            }
+            // bind is: BIND_FLAG || CONN_ID || PEER_LEN || PEER || RETURN
+            Socks5RequestContent::Bind(req) => connection_target_bytes(
+                RequestFlag::Bind,
+                req.conn_id,
+                req.expected_peer,
+                req.return_address,
+            ),
         }
     }
 }
 
+fn connection_target_bytes(
+    flag: RequestFlag,
+    conn_id: ConnectionId,
+    remote_addr: RemoteAddress,
+    return_address: Option<Recipient>,
+) -> Vec<u8> {
+    let remote_address_bytes = remote_addr.into_bytes();
+    let remote_address_bytes_len = remote_address_bytes.len() as u16;
+
+    let iter = std::iter::once(flag as u8)
+        .chain(conn_id.to_be_bytes())
+        .chain(remote_address_bytes_len.to_be_bytes())
+        .chain(remote_address_bytes);
+
+    if let Some(return_address) = return_address {
+        iter.chain(return_address.to_bytes()).collect()
+    } else {
+        iter.collect()
+    }
+}
+
+// CONN_ID || ADDR_LEN || ADDR || <RETURN_ADDR>
+fn parse_connection_target(
+    b: &[u8],
+) -> Result<(ConnectionId, RemoteAddress, Option<Recipient>), RequestDeserializationError> {
+    let conn_id = parse_session_id(b)?;
+    let b = &b[8..];
+
+    // we need to be able to read at least 2 bytes that specify address length
+    if b.len() < 2 {
+        return Err(RequestDeserializationError::AddressLengthTooShort);
+    }
+
+    let address_length = u16::from_be_bytes([b[0], b[1]]) as usize;
+
+    if b.len() < 2 + address_length {
+        return Err(RequestDeserializationError::AddressTooShort);
+    }
+
+    let address_start = 2;
+    let address_end = address_start + address_length;
+    let address_bytes = &b[address_start..address_end];
+    let remote_address = String::from_utf8_lossy(address_bytes).to_string();
+
+    // just a temporary reference to mid-slice for ease of use
+    let recipient_data_bytes = &b[address_end..];
+    let return_address = parse_optional_return_address(recipient_data_bytes)?;
+
+    Ok((conn_id, remote_address, return_address))
+}
+
+fn parse_session_id(b: &[u8]) -> Result<ConnectionId, RequestDeserializationError> {
+    if b.len() < 8 {
+        return Err(RequestDeserializationError::ConnectionIdTooShort);
+    }
+    Ok(ConnectionId::from_be_bytes([
+        b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7],
+    ]))
+}
+
+fn parse_optional_return_address(
+    b: &[u8],
+) -> Result<Option<Recipient>, RequestDeserializationError> {
+    if b.is_empty() {
+        return Ok(None);
+    }
+    if b.len() != Recipient::LEN {
+        return Err(RequestDeserializationError::ReturnAddressTooShort);
+    }
+
+    let mut return_bytes = [0u8; Recipient::LEN];
+    return_bytes.copy_from_slice(&b[..Recipient::LEN]);
+    Recipient::try_from_bytes(return_bytes)
+        .map(Some)
+        .map_err(RequestDeserializationError::MalformedReturnAddress)
+}
+
 #[cfg(test)]
 mod request_deserialization_tests {
     use super::*;
@@ -694,6 +919,85 @@ mod request_deserialization_tests {
         }
     }
 
+    #[cfg(test)]
+    mod datagram_sessions {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let recipient = Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap();
+
+            let requests = vec![
+                Socks5RequestContent::new_open_datagram_session(42, None),
+                Socks5RequestContent::new_open_datagram_session(42, Some(recipient)),
+                Socks5RequestContent::SendDatagram(Datagram::new(
+                    42,
+                    "1.1.1.1:53".to_string(),
+                    vec![1, 2, 3],
+                )),
+                Socks5RequestContent::CloseDatagramSession { session_id: 42 },
+            ];
+
+            for request in requests {
+                let bytes = request.clone().into_bytes();
+                assert_eq!(
+                    request,
+                    Socks5RequestContent::try_from_bytes(&bytes).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn returns_error_when_session_id_too_short() {
+            let request_bytes = [RequestFlag::CloseDatagramSession as u8, 1, 2, 3].to_vec();
+            match Socks5RequestContent::try_from_bytes(&request_bytes).unwrap_err() {
+                RequestDeserializationError::ConnectionIdTooShort => {}
+                _ => unreachable!(),
+            }
+
+            let request_bytes = [RequestFlag::SendDatagram as u8, 1, 2, 3].to_vec();
+            match Socks5RequestContent::try_from_bytes(&request_bytes).unwrap_err() {
+                RequestDeserializationError::MalformedDatagram(
+                    MalformedDatagramError::SessionIdTooShort,
+                ) => {}
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod binding {
+        use super::*;
+
+        #[test]
+        fn serialize_there_and_back() {
+            let recipient = Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap();
+
+            let requests = vec![
+                Socks5RequestContent::new_bind(42, "ftp.foo.com:21".to_string(), None),
+                Socks5RequestContent::new_bind(42, "1.2.3.4:0".to_string(), Some(recipient)),
+            ];
+
+            for request in requests {
+                let bytes = request.clone().into_bytes();
+                assert_eq!(
+                    request,
+                    Socks5RequestContent::try_from_bytes(&bytes).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn returns_error_when_address_is_too_short() {
+            let request_bytes =
+                [RequestFlag::Bind as u8, 1, 2, 3, 4, 5, 6, 7, 8, 0, 3, 102].to_vec();
+            match Socks5RequestContent::try_from_bytes(&request_bytes).unwrap_err() {
+                RequestDeserializationError::AddressTooShort => {}
+                _ => unreachable!(),
+            }
+        }
+    }
+
     #[cfg(test)]
     mod serialize_query_request {
         use super::*;
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    make_bincode_serializer, ConnectionId, InsufficientSocketDataError, SocketData,
-    Socks5ProtocolVersion, Socks5RequestError,
+    make_bincode_serializer, ConnectionId, Datagram, InsufficientSocketDataError,
+    MalformedDatagramError, SocketData, Socks5ProtocolVersion, Socks5RequestError,
 };
 use nym_exit_policy::ExitPolicy;
 use nym_service_providers_common::interface::{Serializable, ServiceProviderResponse};
@@ -20,6 +20,9 @@ pub enum ResponseFlag {
     NetworkData = 1,
     ConnectionError = 2,
     Query = 3,
+    Datagram = 4,
+    Bind = 5,
+    LimitExceeded = 6,
 }
 
 impl TryFrom<u8> for ResponseFlag {
@@ -30,6 +33,9 @@ impl TryFrom<u8> for ResponseFlag {
             _ if value == (ResponseFlag::NetworkData as u8) => Ok(Self::NetworkData),
             _ if value == (ResponseFlag::ConnectionError as u8) => Ok(Self::ConnectionError),
             _ if value == (ResponseFlag::Query as u8) => Ok(Self::Query),
+            _ if value == (ResponseFlag::Datagram as u8) => Ok(Self::Datagram),
+            _ if value == (ResponseFlag::Bind as u8) => Ok(Self::Bind),
+            _ if value == (ResponseFlag::LimitExceeded as u8) => Ok(Self::LimitExceeded),
             value => Err(ResponseDeserializationError::UnknownResponseFlag { value }),
         }
     }
@@ -63,6 +69,15 @@ pub enum ResponseDeserializationError {
         #[from]
         source: bincode::Error,
     },
+
+    #[error(transparent)]
+    MalformedDatagram(#[from] MalformedDatagramError),
+
+    #[error("failed to deserialize bind response: {source}")]
+    BindDeserializationError { source: bincode::Error },
+
+    #[error("failed to deserialize limit exceeded response: {source}")]
+    LimitExceededDeserializationError { source: bincode::Error },
 }
 
 #[derive(Debug, Clone)]
@@ -113,13 +128,15 @@ impl Serializable for Socks5Response {
 impl ServiceProviderResponse for Socks5Response {}
 
 impl Socks5Response {
+    /// Creates a new response for a client using the provided protocol version.
+    /// Any content that such client wouldn't be able to deserialize is replaced by a connection error.
     pub fn new(
         protocol_version: Socks5ProtocolVersion,
         content: Socks5ResponseContent,
     ) -> Socks5Response {
         Socks5Response {
             protocol_version,
-            content,
+            content: content.into_compatible(protocol_version),
         }
     }
 
@@ -157,6 +174,35 @@ impl Socks5Response {
         }
     }
 
+    pub fn new_datagram(
+        protocol_version: Socks5ProtocolVersion,
+        datagram: Datagram,
+    ) -> Socks5Response {
+        Socks5Response::new(protocol_version, Socks5ResponseContent::Datagram(datagram))
+    }
+
+    pub fn new_bind(
+        protocol_version: Socks5ProtocolVersion,
+        connection_id: ConnectionId,
+        status: BindStatus,
+    ) -> Socks5Response {
+        Socks5Response::new(
+            protocol_version,
+            Socks5ResponseContent::Bind(BindResponse::new(connection_id, status)),
+        )
+    }
+
+    pub fn new_limit_exceeded(
+        protocol_version: Socks5ProtocolVersion,
+        connection_id: ConnectionId,
+        limit: ExceededLimit,
+    ) -> Socks5Response {
+        Socks5Response::new(
+            protocol_version,
+            Socks5ResponseContent::LimitExceeded(LimitExceededResponse::new(connection_id, limit)),
+        )
+    }
+
     pub fn new_query_error<S: Into<String>>(
         protocol_version: Socks5ProtocolVersion,
         message: S,
@@ -175,6 +221,9 @@ pub enum Socks5ResponseContent {
     NetworkData { content: SocketData },
     ConnectionError(ConnectionError),
     Query(QueryResponse),
+    Datagram(Datagram),
+    Bind(BindResponse),
+    LimitExceeded(LimitExceededResponse),
 }
 
 impl Socks5ResponseContent {
@@ -196,6 +245,33 @@ impl Socks5ResponseContent {
         Socks5ResponseContent::ConnectionError(ConnectionError::new(connection_id, error_message))
     }
 
+    /// Replaces the responses that were introduced in [`EXTENDED_RESPONSES_VERSION`](crate::EXTENDED_RESPONSES_VERSION) with
+    /// an equivalent [`ConnectionError`] if the client uses an older version of the interface.
+    pub fn into_compatible(self, protocol_version: Socks5ProtocolVersion) -> Socks5ResponseContent {
+        if protocol_version.supports_extended_responses() {
+            return self;
+        }
+
+        match self {
+            Socks5ResponseContent::Datagram(datagram) => {
+                Socks5ResponseContent::ConnectionError(ConnectionError::new(
+                    datagram.session_id,
+                    "datagram sessions are not supported by this client version".to_string(),
+                ))
+            }
+            Socks5ResponseContent::Bind(bind) => {
+                Socks5ResponseContent::ConnectionError(ConnectionError::new(
+                    bind.connection_id,
+                    "binding is not supported by this client version".to_string(),
+                ))
+            }
+            Socks5ResponseContent::LimitExceeded(limit_exceeded) => {
+                Socks5ResponseContent::ConnectionError(limit_exceeded.into())
+            }
+            other => other,
+        }
+    }
+
     pub fn into_bytes(self) -> Vec<u8> {
         match self {
             Socks5ResponseContent::NetworkData { content } => {
@@ -220,6 +296,38 @@ impl Socks5ResponseContent {
                     .chain(query_bytes)
                     .collect()
             }
+            Socks5ResponseContent::Datagram(datagram) => {
+                std::iter::once(ResponseFlag::Datagram as u8)
+                    .chain(datagram.into_bytes_iter())
+                    .collect()
+            }
+            Socks5ResponseContent::Bind(bind) => {
+                use bincode::Options;
+                let bind_bytes: Vec<u8> = make_bincode_serializer()
+                    .serialize(&bind)
+                    .tap_err(|err| {
+                        log::error!("Failed to serialize bind response: {:?}: {err}", bind);
+                    })
+                    .unwrap_or_default();
+                std::iter::once(ResponseFlag::Bind as u8)
+                    .chain(bind_bytes)
+                    .collect()
+            }
+            Socks5ResponseContent::LimitExceeded(limit_exceeded) => {
+                use bincode::Options;
+                let limit_exceeded_bytes: Vec<u8> = make_bincode_serializer()
+                    .serialize(&limit_exceeded)
+                    .tap_err(|err| {
+                        log::error!(
+                            "Failed to serialize limit exceeded response: {:?}: {err}",
+                            limit_exceeded
+                        );
+                    })
+                    .unwrap_or_default();
+                std::iter::once(ResponseFlag::LimitExceeded as u8)
+                    .chain(limit_exceeded_bytes)
+                    .collect()
+            }
         }
     }
 
@@ -243,6 +351,30 @@ impl Socks5ResponseContent {
                 let query = make_bincode_serializer().deserialize(&b[1..])?;
                 Ok(Socks5ResponseContent::Query(query))
             }
+            ResponseFlag::Datagram => Ok(Socks5ResponseContent::Datagram(
+                Datagram::try_from_bytes(&b[1..])?,
+            )),
+            ResponseFlag::Bind => {
+                use bincode::Options;
+                let bind = make_bincode_serializer()
+                    .deserialize(&b[1..])
+                    .map_err(
+                        |source| ResponseDeserializationError::BindDeserializationError { source },
+                    )?;
+                Ok(Socks5ResponseContent::Bind(bind))
+            }
+            ResponseFlag::LimitExceeded => {
+                use bincode::Options;
+                let limit_exceeded =
+                    make_bincode_serializer()
+                        .deserialize(&b[1..])
+                        .map_err(|source| {
+                            ResponseDeserializationError::LimitExceededDeserializationError {
+                                source,
+                            }
+                        })?;
+                Ok(Socks5ResponseContent::LimitExceeded(limit_exceeded))
+            }
         }
     }
 
@@ -308,6 +440,68 @@ impl ConnectionError {
     }
 }
 
+/// Progress of a `Bind` request, as reported by the service provider.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BindResponse {
+    pub connection_id: ConnectionId,
+    pub status: BindStatus,
+}
+
+impl BindResponse {
+    pub fn new(connection_id: ConnectionId, status: BindStatus) -> Self {
+        BindResponse {
+            connection_id,
+            status,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum BindStatus {
+    /// The service provider is listening for the inbound connection on the specified address.
+    Listening { address: String },
+
+    /// The inbound connection from the specified peer has been accepted.
+    /// Any further data is exchanged as with regular connections.
+    Accepted { peer: String },
+
+    /// The bind request could not be fulfilled.
+    Failed { message: String },
+}
+
+/// Response to a request that has been refused, since the client has exceeded one of the limits
+/// imposed by the service provider.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LimitExceededResponse {
+    pub connection_id: ConnectionId,
+    pub limit: ExceededLimit,
+}
+
+impl LimitExceededResponse {
+    pub fn new(connection_id: ConnectionId, limit: ExceededLimit) -> Self {
+        LimitExceededResponse {
+            connection_id,
+            limit,
+        }
+    }
+}
+
+// clients that don't care about the exact reason can treat it as any other connection error
+impl From<LimitExceededResponse> for ConnectionError {
+    fn from(value: LimitExceededResponse) -> Self {
+        ConnectionError::new(value.connection_id, value.limit.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Error)]
+pub enum ExceededLimit {
+    #[error("the client has reached the maximum of {maximum} concurrent connections")]
+    ConcurrentConnections { maximum: u32 },
+
+    #[error("the client has reached the maximum of {maximum} new connections per minute")]
+    ConnectionsPerMinute { maximum: u32 },
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum QueryResponse {
@@ -326,6 +520,7 @@ pub enum QueryResponse {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::EXTENDED_RESPONSES_VERSION;
 
     #[cfg(test)]
     mod connection_error_response_serde_tests {
@@ -371,6 +566,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn datagram_response_serde() {
+        let datagram =
+            Socks5ResponseContent::Datagram(Datagram::new(42, "1.1.1.1:53".to_string(), vec![1]));
+        let bytes = datagram.clone().into_bytes();
+        assert_eq!(
+            datagram,
+            Socks5ResponseContent::try_from_bytes(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn bind_response_serde() {
+        let statuses = [
+            BindStatus::Listening {
+                address: "1.2.3.4:5678".to_string(),
+            },
+            BindStatus::Accepted {
+                peer: "5.6.7.8:21".to_string(),
+            },
+            BindStatus::Failed {
+                message: "timed out".to_string(),
+            },
+        ];
+
+        for status in statuses {
+            let bind = Socks5ResponseContent::Bind(BindResponse::new(42, status));
+            let bytes = bind.clone().into_bytes();
+            assert_eq!(bind, Socks5ResponseContent::try_from_bytes(&bytes).unwrap());
+        }
+    }
+
+    #[test]
+    fn limit_exceeded_response_serde() {
+        let limits = [
+            ExceededLimit::ConcurrentConnections { maximum: 64 },
+            ExceededLimit::ConnectionsPerMinute { maximum: 256 },
+        ];
+
+        for limit in limits {
+            let response =
+                Socks5ResponseContent::LimitExceeded(LimitExceededResponse::new(42, limit));
+            let bytes = response.clone().into_bytes();
+            assert_eq!(
+                response,
+                Socks5ResponseContent::try_from_bytes(&bytes).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn extended_responses_are_only_sent_to_new_clients() {
+        let old_client = Socks5ProtocolVersion::new_versioned(3);
+        let new_client = Socks5ProtocolVersion::new_versioned(EXTENDED_RESPONSES_VERSION);
+        let limit = ExceededLimit::ConcurrentConnections { maximum: 64 };
+
+        let response = Socks5Response::new_limit_exceeded(new_client, 42, limit);
+        assert_eq!(
+            response.content,
+            Socks5ResponseContent::LimitExceeded(LimitExceededResponse::new(42, limit))
+        );
+
+        let response = Socks5Response::new_limit_exceeded(old_client, 42, limit);
+        assert_eq!(
+            response.content,
+            Socks5ResponseContent::ConnectionError(ConnectionError::new(42, limit.to_string()))
+        );
+
+        let datagram = Datagram::new(7, "1.1.1.1:53".to_string(), vec![1]);
+        let response = Socks5Response::new_datagram(Socks5ProtocolVersion::Legacy, datagram);
+        assert!(matches!(
+            response.content,
+            Socks5ResponseContent::ConnectionError(ConnectionError {
+                connection_id: 7,
+                ..
+            })
+        ));
+
+        let status = BindStatus::Failed {
+            message: "timed out".to_string(),
+        };
+        let response = Socks5Response::new_bind(old_client, 9, status);
+        assert!(matches!(
+            response.content,
+            Socks5ResponseContent::ConnectionError(ConnectionError {
+                connection_id: 9,
+                ..
+            })
+        ));
+
+        // and the old clients can still deserialize whatever they receive
+        let bytes = Socks5Response::new_limit_exceeded(old_client, 42, limit).into_bytes();
+        let deserialized = Socks5Response::try_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.protocol_version, old_client);
+        assert!(matches!(
+            deserialized.content,
+            Socks5ResponseContent::ConnectionError(_)
+        ));
+    }
+
     #[cfg(test)]
     mod serialize_query_response {
         use super::*;
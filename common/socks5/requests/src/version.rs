@@ -12,10 +12,25 @@ pub const INITIAL_INTERFACE_VERSION: u8 = 3;
 /// Defines the current version of the communication interface between socks5 clients and
 /// network requesters (socks5).
 /// It has to be incremented for any breaking change.
-pub const INTERFACE_VERSION: u8 = 3;
+pub const INTERFACE_VERSION: u8 = 4;
+
+/// The first version of the interface whose clients understand the `Datagram`, `Bind`
+/// and `LimitExceeded` responses.
+pub const EXTENDED_RESPONSES_VERSION: u8 = 4;
 
 define_simple_version!(
     Socks5ProtocolVersion,
     INITIAL_INTERFACE_VERSION,
     INTERFACE_VERSION
 );
+
+impl Socks5ProtocolVersion {
+    /// Whether a client using this version of the interface is able to deserialize
+    /// the `Datagram`, `Bind` and `LimitExceeded` responses.
+    pub const fn supports_extended_responses(&self) -> bool {
+        match self.as_u8() {
+            Some(version) => version >= EXTENDED_RESPONSES_VERSION,
+            None => false,
+        }
+    }
+}
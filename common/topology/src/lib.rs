@@ -5,6 +5,7 @@
 // clippy::to_string_trait_impl is not on stable as of 1.77
 
 use crate::filter::VersionFilterable;
+use crate::route_selection::RouteSelectionWeights;
 pub use error::NymTopologyError;
 use log::{debug, warn};
 use nym_mixnet_contract_common::mixnode::MixNodeDetails;
@@ -30,6 +31,7 @@ pub mod filter;
 pub mod gateway;
 pub mod mix;
 pub mod random_route_provider;
+pub mod route_selection;
 
 #[cfg(feature = "provider-trait")]
 pub mod provider_trait;
@@ -120,11 +122,18 @@ pub type MixLayer = u8;
 pub struct NymTopology {
     mixes: BTreeMap<MixLayer, Vec<mix::Node>>,
     gateways: Vec<gateway::Node>,
+
+    // if specified, mixnodes are not chosen uniformly at random when constructing routes
+    route_selection_weights: Option<RouteSelectionWeights>,
 }
 
 impl NymTopology {
     pub fn new(mixes: BTreeMap<MixLayer, Vec<mix::Node>>, gateways: Vec<gateway::Node>) -> Self {
-        NymTopology { mixes, gateways }
+        NymTopology {
+            mixes,
+            gateways,
+            route_selection_weights: None,
+        }
     }
 
     pub fn new_unordered(unordered_mixes: Vec<mix::Node>, gateways: Vec<gateway::Node>) -> Self {
@@ -135,7 +144,7 @@ impl NymTopology {
             layer_entry.push(node)
         }
 
-        NymTopology { mixes, gateways }
+        NymTopology::new(mixes, gateways)
     }
 
     pub fn from_unordered<MI, GI, M, G>(unordered_mixes: MI, unordered_gateways: GI) -> Self
@@ -255,6 +264,15 @@ impl NymTopology {
         self.gateways = gateways
     }
 
+    pub fn route_selection_weights(&self) -> Option<&RouteSelectionWeights> {
+        self.route_selection_weights.as_ref()
+    }
+
+    /// Makes the route construction prefer some mixnodes over the others according to the provided weights.
+    pub fn set_route_selection_weights(&mut self, weights: RouteSelectionWeights) {
+        self.route_selection_weights = Some(weights)
+    }
+
     pub fn random_gateway<R>(&self, rng: &mut R) -> Result<&gateway::Node, NymTopologyError>
     where
         R: Rng + CryptoRng,
@@ -281,6 +299,7 @@ impl NymTopology {
             });
         }
         let mut route = Vec::with_capacity(num_mix_hops as usize);
+        let mut previous = None;
 
         // there is no "layer 0"
         for layer in 1..=num_mix_hops {
@@ -292,9 +311,13 @@ impl NymTopology {
 
             // choose a random mix from the above list
             // this can return a 'None' only if slice is empty
-            let random_mix = layer_mixes
-                .choose(rng)
-                .ok_or(NymTopologyError::EmptyMixLayer { layer })?;
+            let random_mix = match &self.route_selection_weights {
+                Some(weights) => weights.choose(rng, previous, layer_mixes),
+                None => layer_mixes.choose(rng),
+            }
+            .ok_or(NymTopologyError::EmptyMixLayer { layer })?;
+
+            previous = Some(random_mix.mix_id);
             route.push(random_mix.into());
         }
 
@@ -416,6 +439,7 @@ impl NymTopology {
         NymTopology {
             mixes: self.mixes.filter_by_version(expected_mix_version),
             gateways: self.gateways.clone(),
+            route_selection_weights: self.route_selection_weights.clone(),
         }
     }
 }
@@ -0,0 +1,185 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mix;
+use nym_mixnet_contract_common::MixId;
+use rand::distributions::WeightedIndex;
+use rand::prelude::{Distribution, SliceRandom};
+use rand::Rng;
+use std::collections::HashMap;
+use std::time::Duration;
+
+// don't let (possibly bogus) near-zero latencies completely dominate the selection
+const MINIMUM_CONSIDERED_LATENCY: Duration = Duration::from_millis(1);
+
+/// Biases the choice of mixnodes on each layer of a route towards nodes with lower latency
+/// and higher performance.
+///
+/// Whenever possible, the latency of the specific link between the previously chosen node and the candidate
+/// is used. Otherwise, the overall latency of the candidate is used instead.
+/// To preserve a reasonable anonymity set, the most preferred node on any layer is never going to be more than
+/// `maximum_selection_bias` times more likely to be chosen than the least preferred one.
+#[derive(Debug, Clone)]
+pub struct RouteSelectionWeights {
+    maximum_selection_bias: f64,
+
+    link_latencies: HashMap<(MixId, MixId), Duration>,
+    node_latencies: HashMap<MixId, Duration>,
+
+    // performance in the [0, 1] range
+    node_performance: HashMap<MixId, f64>,
+}
+
+impl RouteSelectionWeights {
+    pub fn new(maximum_selection_bias: f64) -> Self {
+        RouteSelectionWeights {
+            maximum_selection_bias: maximum_selection_bias.max(1.),
+            link_latencies: HashMap::new(),
+            node_latencies: HashMap::new(),
+            node_performance: HashMap::new(),
+        }
+    }
+
+    fn link_key(first: MixId, second: MixId) -> (MixId, MixId) {
+        (first.min(second), first.max(second))
+    }
+
+    pub fn set_link_latency(&mut self, first: MixId, second: MixId, latency: Duration) {
+        self.link_latencies
+            .insert(Self::link_key(first, second), latency);
+    }
+
+    pub fn set_node_latency(&mut self, node: MixId, latency: Duration) {
+        self.node_latencies.insert(node, latency);
+    }
+
+    pub fn set_node_performance(&mut self, node: MixId, performance: f64) {
+        self.node_performance
+            .insert(node, performance.clamp(0., 1.));
+    }
+
+    fn latency(&self, previous: Option<MixId>, node: MixId) -> Option<Duration> {
+        previous
+            .and_then(|previous| {
+                self.link_latencies
+                    .get(&Self::link_key(previous, node))
+                    .copied()
+            })
+            .or_else(|| self.node_latencies.get(&node).copied())
+            .map(|latency| latency.max(MINIMUM_CONSIDERED_LATENCY))
+    }
+
+    fn layer_weights(&self, previous: Option<MixId>, candidates: &[mix::Node]) -> Vec<f64> {
+        let latencies = candidates
+            .iter()
+            .map(|node| self.latency(previous, node.mix_id))
+            .collect::<Vec<_>>();
+
+        // nodes we know nothing about are treated as if they had the median latency of the layer
+        let mut known = latencies.iter().flatten().copied().collect::<Vec<_>>();
+        known.sort_unstable();
+        let fallback = known
+            .get(known.len() / 2)
+            .copied()
+            .unwrap_or(MINIMUM_CONSIDERED_LATENCY);
+
+        let raw = candidates
+            .iter()
+            .zip(latencies)
+            .map(|(node, latency)| {
+                let performance = self
+                    .node_performance
+                    .get(&node.mix_id)
+                    .copied()
+                    .unwrap_or(1.);
+                performance / latency.unwrap_or(fallback).as_secs_f64()
+            })
+            .collect::<Vec<_>>();
+
+        let best = raw.iter().copied().fold(0., f64::max);
+        let floor = best / self.maximum_selection_bias;
+        raw.into_iter().map(|weight| weight.max(floor)).collect()
+    }
+
+    /// Chooses a node out of the provided candidates, given the node chosen on the previous layer (if any).
+    pub fn choose<'a, R>(
+        &self,
+        rng: &mut R,
+        previous: Option<MixId>,
+        candidates: &'a [mix::Node],
+    ) -> Option<&'a mix::Node>
+    where
+        R: Rng + ?Sized,
+    {
+        match WeightedIndex::new(self.layer_weights(previous, candidates)) {
+            Ok(distribution) => candidates.get(distribution.sample(rng)),
+            // either there are no candidates or all of them have 0 performance
+            Err(_) => candidates.choose(rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_crypto::asymmetric::{encryption, identity};
+    use nym_mixnet_contract_common::Layer;
+
+    fn node(mix_id: MixId) -> mix::Node {
+        mix::Node {
+            mix_id,
+            owner: None,
+            host: "3.3.3.3".parse().unwrap(),
+            mix_host: "3.3.3.3:1789".parse().unwrap(),
+            identity_key: identity::PublicKey::from_base58_string(
+                "3ebjp1Fb9hdcS1AR6AZihgeJiMHkB5jjJUsvqNnfQwU7",
+            )
+            .unwrap(),
+            sphinx_key: encryption::PublicKey::from_base58_string(
+                "C7cown6dYCLZpLiMFC1PaBmhvLvmJmLDJGeRTbPD45bX",
+            )
+            .unwrap(),
+            layer: Layer::Two,
+            version: "1.1.0".into(),
+        }
+    }
+
+    #[test]
+    fn link_latency_takes_precedence_over_node_latency() {
+        let mut weights = RouteSelectionWeights::new(100.);
+        weights.set_node_latency(2, Duration::from_millis(10));
+        weights.set_node_latency(3, Duration::from_millis(20));
+        weights.set_link_latency(3, 1, Duration::from_millis(5));
+
+        let candidates = [node(2), node(3)];
+        let without_link = weights.layer_weights(None, &candidates);
+        assert!(without_link[0] > without_link[1]);
+
+        let with_link = weights.layer_weights(Some(1), &candidates);
+        assert!(with_link[0] < with_link[1]);
+    }
+
+    #[test]
+    fn selection_bias_is_capped() {
+        let mut weights = RouteSelectionWeights::new(4.);
+        weights.set_node_latency(1, Duration::from_millis(1));
+        weights.set_node_latency(2, Duration::from_millis(1000));
+        weights.set_node_latency(3, Duration::from_millis(2));
+        weights.set_node_performance(3, 0.);
+
+        let layer = weights.layer_weights(None, &[node(1), node(2), node(3)]);
+        assert_eq!(layer[0] / layer[1], 4.);
+        assert_eq!(layer[0] / layer[2], 4.);
+    }
+
+    #[test]
+    fn unknown_nodes_get_median_latency() {
+        let mut weights = RouteSelectionWeights::new(100.);
+        weights.set_node_latency(1, Duration::from_millis(10));
+        weights.set_node_latency(2, Duration::from_millis(20));
+        weights.set_node_latency(3, Duration::from_millis(40));
+
+        let layer = weights.layer_weights(None, &[node(1), node(2), node(3), node(4)]);
+        assert_eq!(layer[1], layer[3]);
+    }
+}
@@ -123,6 +123,8 @@ impl From<DebugWasm> for ConfigDebug {
             acknowledgements: debug.acknowledgements.into(),
             topology: debug.topology.into(),
             reply_surbs: debug.reply_surbs.into(),
+            message_reconstruction: Default::default(),
+            sphinx_key_rotation: Default::default(),
         }
     }
 }
@@ -195,6 +197,7 @@ impl From<TrafficWasm> for ConfigTraffic {
             primary_packet_size: PacketSize::RegularPacket,
             secondary_packet_size: use_extended_packet_size,
             packet_type,
+            repair_fragments_per_set: 0,
         }
     }
 }
@@ -281,6 +284,7 @@ impl From<GatewayConnectionWasm> for ConfigGatewayConnection {
             gateway_response_timeout: Duration::from_millis(
                 gateway_connection.gateway_response_timeout_ms as u64,
             ),
+            ..Default::default()
         }
     }
 }
@@ -313,6 +317,11 @@ pub struct AcknowledgementsWasm {
     /// it is assumed it was lost and retransmission of the data packet happens.
     /// In an ideal network with 0 latency, this value would have been 0.
     pub ack_wait_addition_ms: u32,
+
+    /// Maximum number of times a fragment of a message sent with an explicit tracking id
+    /// is going to be retransmitted before the message is reported as failed.
+    /// Note that messages without tracking ids are retransmitted until they're acknowledged.
+    pub maximum_tracked_message_retransmissions: u32,
 }
 
 impl Default for AcknowledgementsWasm {
@@ -327,6 +336,8 @@ impl From<AcknowledgementsWasm> for ConfigAcknowledgements {
             average_ack_delay: Duration::from_millis(acknowledgements.average_ack_delay_ms as u64),
             ack_wait_multiplier: acknowledgements.ack_wait_multiplier,
             ack_wait_addition: Duration::from_millis(acknowledgements.ack_wait_addition_ms as u64),
+            maximum_tracked_message_retransmissions: acknowledgements
+                .maximum_tracked_message_retransmissions,
         }
     }
 }
@@ -337,6 +348,8 @@ impl From<ConfigAcknowledgements> for AcknowledgementsWasm {
             average_ack_delay_ms: acknowledgements.average_ack_delay.as_millis() as u32,
             ack_wait_multiplier: acknowledgements.ack_wait_multiplier,
             ack_wait_addition_ms: acknowledgements.ack_wait_addition.as_millis() as u32,
+            maximum_tracked_message_retransmissions: acknowledgements
+                .maximum_tracked_message_retransmissions,
         }
     }
 }
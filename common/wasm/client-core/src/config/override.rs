@@ -201,6 +201,12 @@ pub struct AcknowledgementsWasmOverride {
     /// In an ideal network with 0 latency, this value would have been 0.
     #[tsify(optional)]
     pub ack_wait_addition_ms: Option<u32>,
+
+    /// Maximum number of times a fragment of a message sent with an explicit tracking id
+    /// is going to be retransmitted before the message is reported as failed.
+    /// Note that messages without tracking ids are retransmitted until they're acknowledged.
+    #[tsify(optional)]
+    pub maximum_tracked_message_retransmissions: Option<u32>,
 }
 
 impl From<AcknowledgementsWasmOverride> for AcknowledgementsWasm {
@@ -215,6 +221,9 @@ impl From<AcknowledgementsWasmOverride> for AcknowledgementsWasm {
             ack_wait_addition_ms: value
                 .ack_wait_addition_ms
                 .unwrap_or(def.ack_wait_addition_ms),
+            maximum_tracked_message_retransmissions: value
+                .maximum_tracked_message_retransmissions
+                .unwrap_or(def.maximum_tracked_message_retransmissions),
         }
     }
 }
@@ -2,6 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+pub const DEFAULT_PEER_TIMEOUT: Duration = Duration::from_secs(60 * 60); // 1 hour
+pub const DEFAULT_PEER_TIMEOUT_CHECK: Duration = Duration::from_secs(60); // 1 minute
+
+// 1GB
+pub const DEFAULT_PEER_BANDWIDTH_ALLOWANCE: i64 = 1024 * 1024 * 1024;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct Config {
@@ -20,4 +27,17 @@ pub struct Config {
     /// The prefix denoting the maximum number of the clients that can be connected via Wireguard.
     /// The maximum value for IPv4 is 32 and for IPv6 is 128
     pub private_network_prefix: u8,
+
+    /// Duration since the last handshake (or since registration, if no handshake happened)
+    /// after which the peer is considered stale and gets removed.
+    /// default: 1 hour
+    pub peer_timeout: Duration,
+
+    /// Specifies how often the peers are checked for staleness and their bandwidth usage is metered.
+    /// default: 1 minute
+    pub peer_timeout_check: Duration,
+
+    /// Bandwidth, in bytes, allocated to each newly registered peer.
+    /// default: 1GB
+    pub peer_bandwidth_allowance: i64,
 }
@@ -6,7 +6,9 @@ pub mod error;
 pub mod public_key;
 pub mod registration;
 
-pub use config::Config;
+pub use config::{
+    Config, DEFAULT_PEER_BANDWIDTH_ALLOWANCE, DEFAULT_PEER_TIMEOUT, DEFAULT_PEER_TIMEOUT_CHECK,
+};
 pub use error::Error;
 pub use public_key::PeerPublicKey;
 pub use registration::{
@@ -4,16 +4,21 @@
 // #![warn(clippy::unwrap_used)]
 
 use dashmap::DashMap;
-use defguard_wireguard_rs::{host::Peer, key::Key, net::IpAddrMask, WGApi};
+use defguard_wireguard_rs::WGApi;
 use nym_crypto::asymmetric::encryption::KeyPair;
-use nym_wireguard_types::{Config, Error, GatewayClient, GatewayClientRegistry};
+use nym_wireguard_types::{Config, Error, GatewayClient, GatewayClientRegistry, PeerPublicKey};
 use peer_controller::PeerControlMessage;
+use peer_storage::{PeerStorage, PeerStorageError, StoredPeer};
 use std::sync::Arc;
-use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::sync::{
+    mpsc::{self, UnboundedReceiver},
+    oneshot,
+};
 
 const WG_TUN_NAME: &str = "nymwg";
 
 pub mod peer_controller;
+pub mod peer_storage;
 
 pub struct WgApiWrapper {
     inner: WGApi,
@@ -72,18 +77,50 @@ impl WireguardGatewayData {
     }
 
     pub fn add_peer(&self, client: &GatewayClient) -> Result<(), Error> {
-        let mut peer = Peer::new(Key::new(client.pub_key.to_bytes()));
-        peer.allowed_ips
-            .push(IpAddrMask::new(client.private_ip, 32));
-        let msg = PeerControlMessage::AddPeer(peer);
+        let msg = PeerControlMessage::AddPeer(client.clone());
         self.peer_tx.send(msg).map_err(|_| Error::PeerModifyStopped)
     }
 
     pub fn remove_peer(&self, client: &GatewayClient) -> Result<(), Error> {
-        let key = Key::new(client.pub_key().to_bytes());
-        let msg = PeerControlMessage::RemovePeer(key);
+        let msg = PeerControlMessage::RemovePeer(client.pub_key());
         self.peer_tx.send(msg).map_err(|_| Error::PeerModifyStopped)
     }
+
+    /// Retrieves the remaining bandwidth, in bytes, of the particular peer.
+    /// Returns `None` if the peer is not registered.
+    pub async fn query_bandwidth(&self, pub_key: PeerPublicKey) -> Result<Option<i64>, Error> {
+        let (response_tx, response_rx) = oneshot::channel();
+        let msg = PeerControlMessage::QueryBandwidth {
+            pub_key,
+            response_tx,
+        };
+        self.peer_tx
+            .send(msg)
+            .map_err(|_| Error::PeerModifyStopped)?;
+        response_rx.await.map_err(|_| Error::PeerModifyStopped)
+    }
+}
+
+/// Loads all the persisted peers that still have some bandwidth left into the client registry,
+/// so that they would be configured once the wireguard interface gets created.
+/// This has to happen before the authenticator starts handing out private IPs.
+pub async fn restore_peers(
+    wireguard_data: &WireguardGatewayData,
+    storage: &dyn PeerStorage,
+) -> Result<Vec<StoredPeer>, PeerStorageError> {
+    let peers = storage.load_peers().await?;
+    let mut restored = 0;
+    for peer in peers.iter().filter(|peer| peer.available_bandwidth > 0) {
+        wireguard_data
+            .client_registry()
+            .insert(peer.client.pub_key, peer.client.clone());
+        restored += 1;
+    }
+    log::info!(
+        "restored {restored} wireguard peers ({} known in total)",
+        peers.len()
+    );
+    Ok(peers)
 }
 
 pub struct WireguardData {
@@ -96,19 +133,22 @@ pub struct WireguardData {
 pub async fn start_wireguard(
     task_client: nym_task::TaskClient,
     wireguard_data: WireguardData,
+    peer_storage: Arc<dyn PeerStorage>,
+    restored_peers: Vec<StoredPeer>,
 ) -> Result<std::sync::Arc<WgApiWrapper>, Box<dyn std::error::Error + Send + Sync + 'static>> {
     use base64::{prelude::BASE64_STANDARD, Engine};
-    use defguard_wireguard_rs::{InterfaceConfiguration, WireguardInterfaceApi};
+    use defguard_wireguard_rs::{
+        host::Peer, key::Key, net::IpAddrMask, InterfaceConfiguration, WireguardInterfaceApi,
+    };
     use ip_network::IpNetwork;
     use peer_controller::PeerController;
 
-    let mut peers = vec![];
-    for peer_client in wireguard_data.inner.client_registry().iter() {
-        let mut peer = Peer::new(Key::new(peer_client.pub_key.to_bytes()));
-        let peer_ip_mask = IpAddrMask::new(peer_client.private_ip, 32);
-        peer.set_allowed_ips(vec![peer_ip_mask]);
-        peers.push(peer);
-    }
+    let peers = wireguard_data
+        .inner
+        .client_registry()
+        .iter()
+        .map(|peer_client| peer_controller::to_peer(&peer_client))
+        .collect();
 
     let ifname = String::from(WG_TUN_NAME);
     let wg_api = defguard_wireguard_rs::WGApi::new(ifname.clone(), false)?;
@@ -135,7 +175,14 @@ pub async fn start_wireguard(
     wg_api.configure_peer_routing(&[catch_all_peer])?;
 
     let wg_api = std::sync::Arc::new(WgApiWrapper::new(wg_api));
-    let mut controller = PeerController::new(wg_api.clone(), wireguard_data.peer_rx);
+    let mut controller = PeerController::new(
+        wireguard_data.inner.config(),
+        peer_storage,
+        restored_peers,
+        Arc::clone(wireguard_data.inner.client_registry()),
+        wg_api.clone(),
+        wireguard_data.peer_rx,
+    );
     tokio::spawn(async move { controller.run(task_client).await });
 
     Ok(wg_api)
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
+    collections::HashMap,
     sync::Arc,
     time::{Duration, SystemTime},
 };
@@ -9,22 +10,105 @@ use std::{
 use defguard_wireguard_rs::{
     host::{Host, Peer},
     key::Key,
-    WGApi, WireguardInterfaceApi,
+    net::IpAddrMask,
+    WireguardInterfaceApi,
 };
-use tokio::sync::mpsc;
+use nym_wireguard_types::{Config, GatewayClient, GatewayClientRegistry, PeerPublicKey};
+use tokio::sync::{mpsc, oneshot};
 use tokio_stream::{wrappers::IntervalStream, StreamExt};
 
+use crate::peer_storage::{PeerStorage, StoredPeer};
 use crate::WgApiWrapper;
 
-const DEFAULT_PEER_TIMEOUT: Duration = Duration::from_secs(60 * 60); // 1 hour
-const DEFAULT_PEER_TIMEOUT_CHECK: Duration = Duration::from_secs(60); // 1 minute
-
 pub enum PeerControlMessage {
-    AddPeer(Peer),
-    RemovePeer(Key),
+    AddPeer(GatewayClient),
+    RemovePeer(PeerPublicKey),
+    QueryBandwidth {
+        pub_key: PeerPublicKey,
+        response_tx: oneshot::Sender<Option<i64>>,
+    },
+}
+
+pub(crate) fn to_peer(client: &GatewayClient) -> Peer {
+    let mut peer = Peer::new(Key::new(client.pub_key.to_bytes()));
+    peer.set_allowed_ips(vec![IpAddrMask::new(client.private_ip, 32)]);
+    peer
+}
+
+/// Bandwidth accounting of a single peer based on the traffic counters of the wireguard interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PeerBandwidth {
+    available: i64,
+    registered_at: SystemTime,
+    last_rx_bytes: u64,
+    last_tx_bytes: u64,
+}
+
+impl PeerBandwidth {
+    fn new(available: i64) -> Self {
+        PeerBandwidth {
+            available,
+            registered_at: SystemTime::now(),
+            last_rx_bytes: 0,
+            last_tx_bytes: 0,
+        }
+    }
+
+    /// Updates the state with the latest counters read from the interface
+    /// and returns the number of bytes used since the previous reading.
+    fn meter(&mut self, rx_bytes: u64, tx_bytes: u64) -> u64 {
+        // the counters get reset whenever the peer is (re)configured on the interface
+        let rx_delta = rx_bytes.checked_sub(self.last_rx_bytes).unwrap_or(rx_bytes);
+        let tx_delta = tx_bytes.checked_sub(self.last_tx_bytes).unwrap_or(tx_bytes);
+        self.last_rx_bytes = rx_bytes;
+        self.last_tx_bytes = tx_bytes;
+
+        let used = rx_delta.saturating_add(tx_delta);
+        self.available = self
+            .available
+            .saturating_sub(i64::try_from(used).unwrap_or(i64::MAX));
+        used
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.available <= 0
+    }
+
+    fn is_stale(
+        &self,
+        last_handshake: Option<SystemTime>,
+        now: SystemTime,
+        timeout: Duration,
+    ) -> bool {
+        // peers that have never completed a handshake are timed out from the moment of their registration
+        let last_activity = last_handshake.unwrap_or(self.registered_at);
+        now.duration_since(last_activity)
+            .map(|elapsed| elapsed > timeout)
+            .unwrap_or(false)
+    }
+}
+
+/// Peers that are already known (e.g. ones that have previously timed out) keep whatever is left
+/// of their allowance, only new peers get seeded with the full amount.
+fn bandwidth_on_registration(
+    known_peers: &HashMap<PeerPublicKey, PeerBandwidth>,
+    pub_key: &PeerPublicKey,
+    allowance: i64,
+) -> PeerBandwidth {
+    let available = known_peers
+        .get(pub_key)
+        .map(|bandwidth| bandwidth.available)
+        .unwrap_or(allowance);
+    PeerBandwidth::new(available)
 }
 
 pub struct PeerController {
+    storage: Arc<dyn PeerStorage>,
+    client_registry: Arc<GatewayClientRegistry>,
+    bandwidth: HashMap<PeerPublicKey, PeerBandwidth>,
+    peer_timeout: Duration,
+    peer_bandwidth_allowance: i64,
+
     peer_rx: mpsc::UnboundedReceiver<PeerControlMessage>,
     wg_api: Arc<WgApiWrapper>,
     timeout_check_interval: IntervalStream,
@@ -32,33 +116,110 @@ pub struct PeerController {
 
 impl PeerController {
     pub fn new(
+        config: Config,
+        storage: Arc<dyn PeerStorage>,
+        restored_peers: Vec<StoredPeer>,
+        client_registry: Arc<GatewayClientRegistry>,
         wg_api: Arc<WgApiWrapper>,
         peer_rx: mpsc::UnboundedReceiver<PeerControlMessage>,
     ) -> Self {
         let timeout_check_interval = tokio_stream::wrappers::IntervalStream::new(
-            tokio::time::interval(DEFAULT_PEER_TIMEOUT_CHECK),
+            tokio::time::interval(config.peer_timeout_check),
         );
+        let bandwidth = restored_peers
+            .into_iter()
+            .map(|peer| {
+                (
+                    peer.client.pub_key,
+                    PeerBandwidth::new(peer.available_bandwidth),
+                )
+            })
+            .collect();
+
         PeerController {
+            storage,
+            client_registry,
+            bandwidth,
+            peer_timeout: config.peer_timeout,
+            peer_bandwidth_allowance: config.peer_bandwidth_allowance,
             wg_api,
             peer_rx,
             timeout_check_interval,
         }
     }
 
-    fn remove_stale_peers(wg_api: &WGApi, host: Host) {
-        let current_timestamp = SystemTime::now();
-        for (key, peer) in host.peers.iter() {
-            if let Some(timestamp) = peer.last_handshake {
-                if let Ok(duration_since_handshake) = current_timestamp.duration_since(timestamp) {
-                    if duration_since_handshake > DEFAULT_PEER_TIMEOUT {
-                        if let Err(e) = wg_api.remove_peer(key) {
-                            log::error!("Could not remove stale peer: {:?}", e);
-                        } else {
-                            log::debug!("Removed stale peer {:?}", key);
-                        }
-                    }
+    async fn add_peer(&mut self, client: GatewayClient) {
+        if let Err(e) = self.wg_api.inner.configure_peer(&to_peer(&client)) {
+            log::error!("Could not configure peer: {:?}", e);
+            return;
+        }
+
+        let bandwidth = bandwidth_on_registration(
+            &self.bandwidth,
+            &client.pub_key,
+            self.peer_bandwidth_allowance,
+        );
+        let stored = StoredPeer {
+            client,
+            available_bandwidth: bandwidth.available,
+        };
+        if let Err(e) = self.storage.insert_peer(&stored).await {
+            log::error!("Could not persist peer {}: {e}", stored.client.pub_key);
+        }
+        self.bandwidth.insert(stored.client.pub_key, bandwidth);
+    }
+
+    /// Removes the peer from the interface, but keeps track of its remaining bandwidth,
+    /// so that registering again wouldn't grant it a fresh allowance.
+    fn deactivate_peer(&mut self, pub_key: &PeerPublicKey) {
+        if let Err(e) = self.wg_api.inner.remove_peer(&Key::new(pub_key.to_bytes())) {
+            log::error!("Could not remove peer: {:?}", e);
+        }
+        // the peer will have to register again
+        self.client_registry.remove(pub_key);
+    }
+
+    async fn remove_peer(&mut self, pub_key: &PeerPublicKey) {
+        if let Err(e) = self.wg_api.inner.remove_peer(&Key::new(pub_key.to_bytes())) {
+            log::error!("Could not remove peer: {:?}", e);
+        }
+        if let Err(e) = self.storage.remove_peer(pub_key).await {
+            log::error!("Could not remove persisted peer {pub_key}: {e}");
+        }
+        self.bandwidth.remove(pub_key);
+    }
+
+    async fn update_peers(&mut self, host: Host) {
+        let now = SystemTime::now();
+        let mut to_remove = Vec::new();
+
+        for (pub_key, bandwidth) in self.bandwidth.iter_mut() {
+            let Some(peer) = host.peers.get(&Key::new(pub_key.to_bytes())) else {
+                continue;
+            };
+
+            if bandwidth.meter(peer.rx_bytes, peer.tx_bytes) > 0 {
+                if let Err(e) = self
+                    .storage
+                    .set_available_bandwidth(pub_key, bandwidth.available)
+                    .await
+                {
+                    log::error!("Could not update bandwidth of peer {pub_key}: {e}");
                 }
             }
+
+            if bandwidth.is_exhausted() {
+                log::debug!("Peer {pub_key} has used up its bandwidth allowance");
+                to_remove.push(*pub_key);
+            } else if bandwidth.is_stale(peer.last_handshake, now, self.peer_timeout) {
+                log::debug!("Peer {pub_key} has timed out");
+                to_remove.push(*pub_key);
+            }
+        }
+
+        for pub_key in to_remove {
+            self.deactivate_peer(&pub_key);
+            log::debug!("Removed peer {pub_key}");
         }
     }
 
@@ -67,7 +228,7 @@ impl PeerController {
             tokio::select! {
                 _ = self.timeout_check_interval.next() => {
                     match self.wg_api.inner.read_interface_data() {
-                        Ok(host) => Self::remove_stale_peers(&self.wg_api.inner, host),
+                        Ok(host) => self.update_peers(host).await,
                         Err(e) => { log::error!("Could not read peer data: {:?}", e); },
                     }
                 }
@@ -77,15 +238,15 @@ impl PeerController {
                 }
                 msg = self.peer_rx.recv() => {
                     match msg {
-                        Some(PeerControlMessage::AddPeer(peer)) => {
-                            if let Err(e) = self.wg_api.inner.configure_peer(&peer) {
-                                log::error!("Could not configure peer: {:?}", e);
-                            }
+                        Some(PeerControlMessage::AddPeer(client)) => {
+                            self.add_peer(client).await;
                         }
-                        Some(PeerControlMessage::RemovePeer(peer_pubkey)) => {
-                            if let Err(e) = self.wg_api.inner.remove_peer(&peer_pubkey) {
-                                log::error!("Could not remove peer: {:?}", e);
-                            }
+                        Some(PeerControlMessage::RemovePeer(pub_key)) => {
+                            self.remove_peer(&pub_key).await;
+                        }
+                        Some(PeerControlMessage::QueryBandwidth { pub_key, response_tx }) => {
+                            let available = self.bandwidth.get(&pub_key).map(|b| b.available);
+                            response_tx.send(available).ok();
                         }
                         None => {
                             log::trace!("PeerController [main loop]: stopping since channel closed");
@@ -98,3 +259,71 @@ impl PeerController {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metering_bandwidth() {
+        let mut bandwidth = PeerBandwidth::new(1000);
+        assert_eq!(bandwidth.meter(100, 50), 150);
+        assert_eq!(bandwidth.available, 850);
+
+        // only the difference since the last reading is counted
+        assert_eq!(bandwidth.meter(300, 50), 200);
+        assert_eq!(bandwidth.available, 650);
+        assert_eq!(bandwidth.meter(300, 50), 0);
+        assert_eq!(bandwidth.available, 650);
+
+        // the counters got reset, i.e. the peer got reconfigured
+        assert_eq!(bandwidth.meter(10, 20), 30);
+        assert_eq!(bandwidth.available, 620);
+        assert!(!bandwidth.is_exhausted());
+
+        bandwidth.meter(1000, 20);
+        assert!(bandwidth.is_exhausted());
+    }
+
+    #[test]
+    fn registering_again_retains_remaining_bandwidth() {
+        let allowance = 1000;
+        let known_key = PeerPublicKey::new([1u8; 32].into());
+        let new_key = PeerPublicKey::new([2u8; 32].into());
+
+        let mut used = PeerBandwidth::new(allowance);
+        used.meter(300, 100);
+        let known_peers = HashMap::from([(known_key, used)]);
+
+        let bandwidth = bandwidth_on_registration(&known_peers, &known_key, allowance);
+        assert_eq!(bandwidth.available, 600);
+        // the interface counters start from scratch for the reconfigured peer
+        assert_eq!(bandwidth.last_rx_bytes, 0);
+        assert_eq!(bandwidth.last_tx_bytes, 0);
+
+        let bandwidth = bandwidth_on_registration(&known_peers, &new_key, allowance);
+        assert_eq!(bandwidth.available, allowance);
+    }
+
+    #[test]
+    fn stale_peers() {
+        let timeout = Duration::from_secs(60);
+        let bandwidth = PeerBandwidth::new(1000);
+        let registered = bandwidth.registered_at;
+
+        assert!(!bandwidth.is_stale(None, registered + Duration::from_secs(30), timeout));
+        assert!(bandwidth.is_stale(None, registered + Duration::from_secs(90), timeout));
+
+        let handshake = registered + Duration::from_secs(60);
+        assert!(!bandwidth.is_stale(
+            Some(handshake),
+            registered + Duration::from_secs(90),
+            timeout
+        ));
+        assert!(bandwidth.is_stale(
+            Some(handshake),
+            registered + Duration::from_secs(150),
+            timeout
+        ));
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use nym_wireguard_types::{GatewayClient, PeerPublicKey};
+
+pub type PeerStorageError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// Registered wireguard peer alongside its remaining bandwidth allowance.
+#[derive(Debug, Clone)]
+pub struct StoredPeer {
+    pub client: GatewayClient,
+
+    /// Remaining bandwidth, in bytes, that the peer is allowed to use.
+    pub available_bandwidth: i64,
+}
+
+/// Persistent registry of the wireguard peers, so that they could be restored upon restarting the node.
+#[async_trait]
+pub trait PeerStorage: Send + Sync {
+    /// Retrieves all the known peers, including the ones that are no longer active.
+    async fn load_peers(&self) -> Result<Vec<StoredPeer>, PeerStorageError>;
+
+    /// Inserts the provided peer into the storage.
+    /// If the peer has previously existed, its data is updated, but it retains its remaining bandwidth.
+    async fn insert_peer(&self, peer: &StoredPeer) -> Result<(), PeerStorageError>;
+
+    /// Removes the peer with the provided public key from the storage.
+    async fn remove_peer(&self, pub_key: &PeerPublicKey) -> Result<(), PeerStorageError>;
+
+    /// Sets the remaining bandwidth of the particular peer to the provided amount.
+    async fn set_available_bandwidth(
+        &self,
+        pub_key: &PeerPublicKey,
+        amount: i64,
+    ) -> Result<(), PeerStorageError>;
+}
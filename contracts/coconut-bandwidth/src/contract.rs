@@ -54,6 +54,9 @@ pub fn execute(
         ExecuteMsg::SpendCredential { data } => {
             transactions::spend_credential(deps, env, info, data)
         }
+        ExecuteMsg::SpendCredentialsBatch { data } => {
+            transactions::spend_credentials_batch(deps, env, info, data)
+        }
         ExecuteMsg::ReleaseFunds { funds } => transactions::release_funds(deps, env, info, funds),
     }
 }
@@ -29,6 +29,12 @@ pub enum ContractError {
     #[error("Credential already spent or in process of spending")]
     DuplicateBlindedSerialNumber,
 
+    #[error("The batch of credentials to spend is empty")]
+    EmptyCredentialsBatch,
+
+    #[error("The batch contains {got} credentials while at most {max} are allowed")]
+    CredentialsBatchTooBig { got: usize, max: usize },
+
     #[error(transparent)]
     Admin(#[from] AdminError),
 }
@@ -3,7 +3,7 @@
 
 use cosmwasm_std::{Addr, Coin};
 use nym_coconut_bandwidth_contract_common::spend_credential::{
-    SpendCredential, SpendCredentialData,
+    BatchedSpendCredential, SpendCredential, SpendCredentialData, SpendCredentialsBatchData,
 };
 
 pub const TEST_MIX_DENOM: &str = "unym";
@@ -23,3 +23,17 @@ pub fn spend_credential_data_fixture(blinded_serial_number: &str) -> SpendCreden
         "gateway_owner_addr".to_string(),
     )
 }
+
+pub fn spend_credentials_batch_data_fixture(
+    blinded_serial_numbers: &[&str],
+) -> SpendCredentialsBatchData {
+    SpendCredentialsBatchData::new(
+        blinded_serial_numbers
+            .iter()
+            .map(|serial| {
+                BatchedSpendCredential::new(Coin::new(100, TEST_MIX_DENOM), serial.to_string())
+            })
+            .collect(),
+        "gateway_owner_addr".to_string(),
+    )
+}
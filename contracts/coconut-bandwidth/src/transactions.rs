@@ -1,10 +1,12 @@
 // Copyright 2021 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use cosmwasm_std::{BankMsg, Coin, DepsMut, Env, Event, MessageInfo, Response};
+use cosmwasm_std::{BankMsg, Coin, DepsMut, Env, Event, MessageInfo, Response, StdError, Uint128};
 use nym_coconut_bandwidth_contract_common::spend_credential::{
-    to_cosmos_msg, SpendCredential, SpendCredentialData,
+    to_batch_cosmos_msg, to_cosmos_msg, SpendCredential, SpendCredentialData,
+    SpendCredentialsBatchData, MAX_SPEND_CREDENTIALS_BATCH_SIZE,
 };
+use std::collections::HashSet;
 
 use crate::error::ContractError;
 use crate::state::{ADMIN, CONFIG};
@@ -79,6 +81,69 @@ pub(crate) fn spend_credential(
     Ok(Response::new().add_message(msg))
 }
 
+pub(crate) fn spend_credentials_batch(
+    deps: DepsMut<'_>,
+    env: Env,
+    _info: MessageInfo,
+    data: SpendCredentialsBatchData,
+) -> Result<Response, ContractError> {
+    let credentials = data.credentials();
+    if credentials.is_empty() {
+        return Err(ContractError::EmptyCredentialsBatch);
+    }
+    if credentials.len() > MAX_SPEND_CREDENTIALS_BATCH_SIZE {
+        return Err(ContractError::CredentialsBatchTooBig {
+            got: credentials.len(),
+            max: MAX_SPEND_CREDENTIALS_BATCH_SIZE,
+        });
+    }
+
+    let cfg = CONFIG.load(deps.storage)?;
+    let gateway_cosmos_address = deps.api.addr_validate(data.gateway_cosmos_address())?;
+
+    // validate the entire batch before persisting anything
+    let mut seen = HashSet::with_capacity(credentials.len());
+    let mut total = Uint128::zero();
+    for credential in credentials {
+        if credential.funds().denom != cfg.mix_denom {
+            return Err(ContractError::WrongDenom {
+                mix_denom: cfg.mix_denom,
+            });
+        }
+        if !seen.insert(credential.blinded_serial_number())
+            || storage::spent_credentials().has(deps.storage, credential.blinded_serial_number())
+        {
+            return Err(ContractError::DuplicateBlindedSerialNumber);
+        }
+        total = total
+            .checked_add(credential.funds().amount)
+            .map_err(StdError::from)?;
+    }
+
+    let mut blinded_serial_numbers = Vec::with_capacity(credentials.len());
+    for credential in credentials {
+        storage::spent_credentials().save(
+            deps.storage,
+            credential.blinded_serial_number(),
+            &SpendCredential::new(
+                credential.funds().to_owned(),
+                credential.blinded_serial_number().to_owned(),
+                gateway_cosmos_address.clone(),
+            ),
+        )?;
+        blinded_serial_numbers.push(credential.blinded_serial_number().to_owned());
+    }
+
+    let msg = to_batch_cosmos_msg(
+        Coin::new(total.u128(), cfg.mix_denom),
+        &blinded_serial_numbers,
+        env.contract.address.into_string(),
+        cfg.multisig_addr.into_string(),
+    )?;
+
+    Ok(Response::new().add_message(msg))
+}
+
 pub(crate) fn release_funds(
     deps: DepsMut<'_>,
     env: Env,
@@ -111,12 +176,17 @@ pub(crate) fn release_funds(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::support::tests::fixtures::spend_credential_data_fixture;
+    use crate::support::tests::fixtures::{
+        spend_credential_data_fixture, spend_credentials_batch_data_fixture,
+    };
     use crate::support::tests::helpers::{self, MULTISIG_CONTRACT, POOL_CONTRACT};
     use cosmwasm_std::testing::{mock_env, mock_info};
     use cosmwasm_std::{from_binary, CosmosMsg, WasmMsg};
     use cw_controllers::AdminError;
     use nym_coconut_bandwidth_contract_common::msg::ExecuteMsg;
+    use nym_coconut_bandwidth_contract_common::spend_credential::{
+        serial_numbers_from_batch_proposal_description, BatchedSpendCredential,
+    };
     use nym_multisig_contract_common::msg::ExecuteMsg as MultisigExecuteMsg;
 
     #[test]
@@ -366,4 +436,125 @@ mod tests {
             ContractError::DuplicateBlindedSerialNumber
         );
     }
+
+    #[test]
+    fn valid_batch_spend() {
+        let mut deps = helpers::init_contract();
+        let env = mock_env();
+        let info = mock_info("requester", &[]);
+        let serials = ["serial1", "serial2", "serial3"];
+        let data = spend_credentials_batch_data_fixture(&serials);
+        let res = spend_credentials_batch(deps.as_mut(), env.clone(), info, data).unwrap();
+
+        for serial in serials {
+            assert!(storage::spent_credentials().has(&deps.storage, serial));
+        }
+
+        let CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr, msg, ..
+        }) = &res.messages[0].msg
+        else {
+            panic!("Wasm execute message not found");
+        };
+        assert_eq!(contract_addr, MULTISIG_CONTRACT);
+        let MultisigExecuteMsg::Propose {
+            description, msgs, ..
+        } = from_binary(msg).unwrap()
+        else {
+            panic!("Could not extract proposal from binary blob");
+        };
+        assert_eq!(
+            serial_numbers_from_batch_proposal_description(&description).unwrap(),
+            serials
+        );
+        let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &msgs[0] else {
+            panic!("Could not extract release funds message from proposal");
+        };
+        let release_funds_req: ExecuteMsg = from_binary(msg).unwrap();
+        assert_eq!(
+            release_funds_req,
+            ExecuteMsg::ReleaseFunds {
+                funds: Coin::new(300, crate::support::tests::fixtures::TEST_MIX_DENOM)
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_batch_spend_attempts() {
+        let mut deps = helpers::init_contract();
+        let env = mock_env();
+        let info = mock_info("requester", &[]);
+
+        let ret = spend_credentials_batch(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            spend_credentials_batch_data_fixture(&[]),
+        );
+        assert_eq!(ret.unwrap_err(), ContractError::EmptyCredentialsBatch);
+
+        let serials = (0..=MAX_SPEND_CREDENTIALS_BATCH_SIZE)
+            .map(|i| format!("serial{i}"))
+            .collect::<Vec<_>>();
+        let serials = serials.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let ret = spend_credentials_batch(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            spend_credentials_batch_data_fixture(&serials),
+        );
+        assert_eq!(
+            ret.unwrap_err(),
+            ContractError::CredentialsBatchTooBig {
+                got: MAX_SPEND_CREDENTIALS_BATCH_SIZE + 1,
+                max: MAX_SPEND_CREDENTIALS_BATCH_SIZE
+            }
+        );
+
+        let invalid_data = SpendCredentialsBatchData::new(
+            vec![BatchedSpendCredential::new(
+                Coin::new(1, "invalid_denom"),
+                "serial".to_string(),
+            )],
+            "gateway_owner_addr".to_string(),
+        );
+        let ret = spend_credentials_batch(deps.as_mut(), env.clone(), info.clone(), invalid_data);
+        assert_eq!(
+            ret.unwrap_err(),
+            ContractError::WrongDenom {
+                mix_denom: crate::support::tests::fixtures::TEST_MIX_DENOM.to_string()
+            }
+        );
+
+        let ret = spend_credentials_batch(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            spend_credentials_batch_data_fixture(&["serial", "serial"]),
+        );
+        assert_eq!(
+            ret.unwrap_err(),
+            ContractError::DuplicateBlindedSerialNumber
+        );
+
+        // a single already spent credential invalidates the whole batch
+        spend_credential(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            spend_credential_data_fixture("spent"),
+        )
+        .unwrap();
+        let ret = spend_credentials_batch(
+            deps.as_mut(),
+            env,
+            info,
+            spend_credentials_batch_data_fixture(&["fresh", "spent"]),
+        );
+        assert_eq!(
+            ret.unwrap_err(),
+            ContractError::DuplicateBlindedSerialNumber
+        );
+        assert!(!storage::spent_credentials().has(&deps.storage, "fresh"));
+    }
 }
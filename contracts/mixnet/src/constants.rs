@@ -35,6 +35,10 @@ pub const INTERVAL_EVENTS_MAX_RETRIEVAL_LIMIT: u32 = 250;
 pub const REWARDED_SET_DEFAULT_RETRIEVAL_LIMIT: u32 = 500;
 pub const REWARDED_SET_MAX_RETRIEVAL_LIMIT: u32 = 1000;
 
+// each compounding delegation has to be updated when its mixnode gets rewarded at the end of an interval,
+// so their number has to be bounded in order to not blow up the gas cost of the rewarding transaction
+pub const MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE: u32 = 100;
+
 pub const FAMILIES_DEFAULT_RETRIEVAL_LIMIT: u32 = 10;
 pub const FAMILIES_MAX_RETRIEVAL_LIMIT: u32 = 20;
 
@@ -74,6 +78,8 @@ pub const UNBONDED_MIXNODES_IDENTITY_IDX_NAMESPACE: &str = "umi";
 pub const REWARDING_PARAMS_KEY: &str = "rparams";
 pub const PENDING_REWARD_POOL_KEY: &str = "prp";
 pub const MIXNODES_REWARDING_PK_NAMESPACE: &str = "mnr";
+pub const COMPOUNDING_OPERATORS_NAMESPACE: &str = "cop";
+pub const COMPOUNDING_DELEGATIONS_NAMESPACE: &str = "cdl";
 
 pub const FAMILIES_INDEX_NAMESPACE: &str = "faml2";
 pub const FAMILIES_MAP_NAMESPACE: &str = "fam2";
@@ -346,6 +346,32 @@ pub fn execute(
                 deps, env, info, mix_id, delegate,
             )
         }
+        ExecuteMsg::RedelegateFromMixnode {
+            from_mix_id,
+            to_mix_id,
+            amount,
+        } => crate::delegations::transactions::try_redelegate_from_mixnode(
+            deps,
+            env,
+            info,
+            from_mix_id,
+            to_mix_id,
+            amount,
+        ),
+        ExecuteMsg::RedelegateFromMixnodeOnBehalf {
+            from_mix_id,
+            to_mix_id,
+            amount,
+            delegate,
+        } => crate::delegations::transactions::try_redelegate_from_mixnode_on_behalf(
+            deps,
+            env,
+            info,
+            from_mix_id,
+            to_mix_id,
+            amount,
+            delegate,
+        ),
 
         // reward-related
         ExecuteMsg::RewardMixnode {
@@ -367,6 +393,14 @@ pub fn execute(
                 deps, info, mix_id, owner,
             )
         }
+        ExecuteMsg::SetOperatorRewardCompounding { enabled } => {
+            crate::rewards::transactions::try_set_operator_reward_compounding(deps, info, enabled)
+        }
+        ExecuteMsg::SetDelegatorRewardCompounding { mix_id, enabled } => {
+            crate::rewards::transactions::try_set_delegator_reward_compounding(
+                deps, info, mix_id, enabled,
+            )
+        }
 
         // testing-only
         #[cfg(feature = "contract-testing")]
@@ -544,6 +578,12 @@ pub fn query(
         } => to_binary(&crate::rewards::queries::query_pending_delegator_reward(
             deps, address, mix_id, proxy,
         )?),
+        QueryMsg::GetOperatorRewardCompounding { mix_id } => to_binary(
+            &crate::rewards::queries::query_operator_reward_compounding(deps, mix_id)?,
+        ),
+        QueryMsg::GetDelegatorRewardCompounding { address, mix_id } => to_binary(
+            &crate::rewards::queries::query_delegator_reward_compounding(deps, address, mix_id)?,
+        ),
         QueryMsg::GetEstimatedCurrentEpochOperatorReward {
             mix_id,
             estimated_performance,
@@ -17,6 +17,7 @@ pub(crate) fn undelegate(
 
     rewards_storage::MIXNODE_REWARDING.save(store, delegation.mix_id, &mix_rewarding)?;
     storage::delegations().replace(store, delegation.storage_key(), None, Some(&delegation))?;
+    rewards_storage::COMPOUNDING_DELEGATIONS.remove(store, delegation.storage_key());
 
     Ok(tokens)
 }
@@ -11,7 +11,7 @@ use crate::support::helpers::{
 use cosmwasm_std::{Addr, Coin, DepsMut, Env, MessageInfo, Response};
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
-    new_pending_delegation_event, new_pending_undelegation_event,
+    new_pending_delegation_event, new_pending_redelegation_event, new_pending_undelegation_event,
 };
 use mixnet_contract_common::pending_events::PendingEpochEventKind;
 use mixnet_contract_common::{Delegation, MixId};
@@ -139,6 +139,101 @@ pub(crate) fn _try_remove_delegation_from_mixnode(
     Ok(Response::new().add_event(cosmos_event))
 }
 
+pub(crate) fn try_redelegate_from_mixnode(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+    amount: Coin,
+) -> Result<Response, MixnetContractError> {
+    _try_redelegate_from_mixnode(deps, env, from_mix_id, to_mix_id, amount, info.sender, None)
+}
+
+pub(crate) fn try_redelegate_from_mixnode_on_behalf(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+    amount: Coin,
+    delegate: String,
+) -> Result<Response, MixnetContractError> {
+    ensure_sent_by_vesting_contract(&info, deps.storage)?;
+
+    let delegate = deps.api.addr_validate(&delegate)?;
+    _try_redelegate_from_mixnode(
+        deps,
+        env,
+        from_mix_id,
+        to_mix_id,
+        amount,
+        delegate,
+        Some(info.sender),
+    )
+}
+
+pub(crate) fn _try_redelegate_from_mixnode(
+    deps: DepsMut<'_>,
+    env: Env,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+    amount: Coin,
+    delegate: Addr,
+    proxy: Option<Addr>,
+) -> Result<Response, MixnetContractError> {
+    // redelegation is only allowed if the epoch is currently not in the process of being advanced
+    ensure_epoch_in_progress_state(deps.storage)?;
+
+    if from_mix_id == to_mix_id {
+        return Err(MixnetContractError::RedelegationToSameMixnode {
+            mix_id: from_mix_id,
+        });
+    }
+
+    // check if the requested amount is non-zero and of the appropriate denomination.
+    // note: the minimum delegation requirement does not apply as the tokens have already been delegated before
+    let contract_state = mixnet_params_storage::CONTRACT_STATE.load(deps.storage)?;
+    let amount = validate_delegation_stake(vec![amount], None, contract_state.rewarding_denom)?;
+
+    // see if the delegation even exists
+    let storage_key = Delegation::generate_storage_key(from_mix_id, &delegate, proxy.as_ref());
+    if storage::delegations()
+        .may_load(deps.storage, storage_key)?
+        .is_none()
+    {
+        return Err(MixnetContractError::NoMixnodeDelegationFound {
+            mix_id: from_mix_id,
+            address: delegate.into_string(),
+            proxy: proxy.map(Addr::into_string),
+        });
+    }
+
+    // check if the target node actually exists and is still bonded
+    match mixnodes_storage::mixnode_bonds().may_load(deps.storage, to_mix_id)? {
+        None => return Err(MixnetContractError::MixNodeBondNotFound { mix_id: to_mix_id }),
+        Some(bond) if bond.is_unbonding => {
+            return Err(MixnetContractError::MixnodeIsUnbonding { mix_id: to_mix_id })
+        }
+        _ => (),
+    }
+
+    // push the event onto the queue and wait for it to be picked up at the end of the epoch
+    let cosmos_event =
+        new_pending_redelegation_event(&delegate, &proxy, &amount, from_mix_id, to_mix_id);
+
+    let epoch_event = PendingEpochEventKind::Redelegate {
+        owner: delegate,
+        from_mix_id,
+        to_mix_id,
+        amount,
+        proxy,
+    };
+    interval_storage::push_new_epoch_event(deps.storage, &env, epoch_event)?;
+
+    Ok(Response::new().add_event(cosmos_event))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -609,4 +704,279 @@ mod tests {
             )
         }
     }
+
+    #[cfg(test)]
+    mod redelegating_from_mixnode {
+        use super::*;
+        use crate::mixnodes::transactions::try_remove_mixnode;
+        use crate::support::tests::fixtures::TEST_COIN_DENOM;
+        use crate::support::tests::test_helpers::TestSetup;
+        use cosmwasm_std::coin;
+        use cosmwasm_std::testing::mock_info;
+        use mixnet_contract_common::{EpochState, EpochStatus};
+
+        #[test]
+        fn cant_be_performed_if_epoch_transition_is_in_progress() {
+            let bad_states = vec![
+                EpochState::Rewarding {
+                    last_rewarded: 0,
+                    final_node_id: 0,
+                },
+                EpochState::ReconcilingEvents,
+                EpochState::AdvancingEpoch,
+            ];
+
+            for bad_state in bad_states {
+                let mut test = TestSetup::new();
+                let from_mix_id = test.add_dummy_mixnode("owner1", None);
+                let to_mix_id = test.add_dummy_mixnode("owner2", None);
+                test.add_immediate_delegation("foomp", 1000u32, from_mix_id);
+
+                let mut status = EpochStatus::new(test.rewarding_validator().sender);
+                status.state = bad_state;
+                interval_storage::save_current_epoch_status(test.deps_mut().storage, &status)
+                    .unwrap();
+
+                let env = test.env();
+                let res = try_redelegate_from_mixnode(
+                    test.deps_mut(),
+                    env,
+                    mock_info("foomp", &[]),
+                    from_mix_id,
+                    to_mix_id,
+                    coin(1000, TEST_COIN_DENOM),
+                );
+                assert!(matches!(
+                    res,
+                    Err(MixnetContractError::EpochAdvancementInProgress { .. })
+                ));
+            }
+        }
+
+        #[test]
+        fn cant_be_done_towards_the_same_mixnode() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+            test.add_immediate_delegation(owner, 10000u32, mix_id);
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                mix_id,
+                mix_id,
+                coin(1000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::RedelegationToSameMixnode { mix_id })
+            )
+        }
+
+        #[test]
+        fn must_contain_non_zero_amount_of_valid_coins() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+            test.add_immediate_delegation(owner, 10000u32, from_mix_id);
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env.clone(),
+                mock_info(owner, &[]),
+                from_mix_id,
+                to_mix_id,
+                coin(0, TEST_COIN_DENOM),
+            );
+            assert_eq!(res, Err(MixnetContractError::EmptyDelegation));
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                from_mix_id,
+                to_mix_id,
+                coin(1000, "some-weird-coin"),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::WrongDenom {
+                    received: "some-weird-coin".to_string(),
+                    expected: TEST_COIN_DENOM.to_string()
+                })
+            );
+        }
+
+        #[test]
+        fn cannot_be_performed_if_delegation_doesnt_exist() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                from_mix_id,
+                to_mix_id,
+                coin(1000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::NoMixnodeDelegationFound {
+                    mix_id: from_mix_id,
+                    address: owner.to_string(),
+                    proxy: None
+                })
+            )
+        }
+
+        #[test]
+        fn can_only_be_done_towards_fully_bonded_mixnode() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from_mix_id = test.add_dummy_mixnode("mix-owner", None);
+            let mix_id_unbonding = test.add_dummy_mixnode("mix-owner-unbonding", None);
+            let mix_id_unbonded = test.add_dummy_mixnode("mix-owner-unbonded", None);
+            test.add_immediate_delegation(owner, 10000u32, from_mix_id);
+
+            try_remove_mixnode(
+                test.deps_mut(),
+                env.clone(),
+                mock_info("mix-owner-unbonded", &[]),
+            )
+            .unwrap();
+            test.execute_all_pending_events();
+            try_remove_mixnode(
+                test.deps_mut(),
+                env.clone(),
+                mock_info("mix-owner-unbonding", &[]),
+            )
+            .unwrap();
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env.clone(),
+                mock_info(owner, &[]),
+                from_mix_id,
+                mix_id_unbonding,
+                coin(1000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::MixnodeIsUnbonding {
+                    mix_id: mix_id_unbonding
+                })
+            );
+
+            let res = try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env,
+                mock_info(owner, &[]),
+                from_mix_id,
+                mix_id_unbonded,
+                coin(1000, TEST_COIN_DENOM),
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::MixNodeBondNotFound {
+                    mix_id: mix_id_unbonded
+                })
+            );
+        }
+
+        #[test]
+        fn correctly_pushes_appropriate_epoch_event() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+            let owner = "delegator";
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+            test.add_immediate_delegation(owner, 10000u32, from_mix_id);
+            test.add_immediate_delegation_with_legal_proxy(owner, 10000u32, from_mix_id);
+
+            let amount = coin(1000, TEST_COIN_DENOM);
+            let vesting_sender = mock_info(test.vesting_contract().as_str(), &[]);
+            try_redelegate_from_mixnode(
+                test.deps_mut(),
+                env.clone(),
+                mock_info(owner, &[]),
+                from_mix_id,
+                to_mix_id,
+                amount.clone(),
+            )
+            .unwrap();
+            try_redelegate_from_mixnode_on_behalf(
+                test.deps_mut(),
+                env,
+                vesting_sender,
+                from_mix_id,
+                to_mix_id,
+                amount.clone(),
+                owner.into(),
+            )
+            .unwrap();
+
+            let events = test.pending_epoch_events();
+            assert_eq!(
+                events[0].kind,
+                PendingEpochEventKind::Redelegate {
+                    owner: Addr::unchecked(owner),
+                    from_mix_id,
+                    to_mix_id,
+                    amount: amount.clone(),
+                    proxy: None
+                }
+            );
+            assert_eq!(
+                events[1].kind,
+                PendingEpochEventKind::Redelegate {
+                    owner: Addr::unchecked(owner),
+                    from_mix_id,
+                    to_mix_id,
+                    amount,
+                    proxy: Some(test.vesting_contract())
+                }
+            );
+        }
+
+        #[test]
+        fn fails_for_illegal_proxy() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+
+            let illegal_proxy = Addr::unchecked("not-vesting-contract");
+            let vesting_contract = test.vesting_contract();
+
+            let owner = "delegator";
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+
+            let res = try_redelegate_from_mixnode_on_behalf(
+                test.deps_mut(),
+                env,
+                mock_info(illegal_proxy.as_ref(), &[]),
+                from_mix_id,
+                to_mix_id,
+                coin(1000, TEST_COIN_DENOM),
+                owner.into(),
+            )
+            .unwrap_err();
+
+            assert_eq!(
+                res,
+                MixnetContractError::SenderIsNotVestingContract {
+                    received: illegal_proxy,
+                    vesting_contract
+                }
+            )
+        }
+    }
 }
@@ -1,15 +1,16 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use cosmwasm_std::{Addr, Coin, DepsMut, Env, Response};
+use cosmwasm_std::{Addr, Coin, DepsMut, Env, Response, Storage};
 
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
     new_active_set_update_event, new_delegation_event, new_delegation_on_unbonded_node_event,
     new_mixnode_cost_params_update_event, new_mixnode_unbonding_event, new_pledge_decrease_event,
-    new_pledge_increase_event, new_rewarding_params_update_event, new_undelegation_event,
+    new_pledge_increase_event, new_redelegation_event, new_redelegation_on_unbonded_node_event,
+    new_rewarding_params_update_event, new_undelegation_event,
 };
-use mixnet_contract_common::mixnode::MixNodeCostParams;
+use mixnet_contract_common::mixnode::{MixNodeCostParams, MixNodeRewarding};
 use mixnet_contract_common::pending_events::{
     PendingEpochEventData, PendingEpochEventKind, PendingIntervalEventData,
     PendingIntervalEventKind,
@@ -75,9 +76,38 @@ pub(crate) fn delegate(
         }
     };
 
-    let new_delegation_amount = amount.clone();
-    let mut mix_rewarding = mixnode_details.rewarding_details;
+    let cosmos_event = new_delegation_event(
+        created_at,
+        &owner,
+        &proxy,
+        &amount,
+        mix_id,
+        mixnode_details.rewarding_details.total_unit_reward,
+    );
+
+    increase_delegation(
+        deps.storage,
+        env,
+        owner,
+        mix_id,
+        amount,
+        proxy,
+        mixnode_details.rewarding_details,
+    )?;
 
+    Ok(Response::new().add_event(cosmos_event))
+}
+
+// adds the specified amount to the delegation towards the provided mixnode, creating it if it didn't exist before
+fn increase_delegation(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: Addr,
+    mix_id: MixId,
+    amount: Coin,
+    proxy: Option<Addr>,
+    mut mix_rewarding: MixNodeRewarding,
+) -> Result<(), MixnetContractError> {
     // the delegation_amount might get increased if there's already a pre-existing delegation on this mixnode
     // (in that case we just create a fresh delegation with the sum of both)
     let mut stored_delegation_amount = amount;
@@ -86,7 +116,7 @@ pub(crate) fn delegate(
     // with the sum of both
     let storage_key = Delegation::generate_storage_key(mix_id, &owner, proxy.as_ref());
     let old_delegation = if let Some(existing_delegation) =
-        delegations_storage::delegations().may_load(deps.storage, storage_key.clone())?
+        delegations_storage::delegations().may_load(storage, storage_key.clone())?
     {
         // completely remove the delegation from the node
         let og_with_reward = mix_rewarding.undelegate(&existing_delegation)?;
@@ -103,15 +133,6 @@ pub(crate) fn delegate(
     // add the amount we're intending to delegate (whether it's fresh or we're adding to the existing one)
     mix_rewarding.add_base_delegation(stored_delegation_amount.amount)?;
 
-    let cosmos_event = new_delegation_event(
-        created_at,
-        &owner,
-        &proxy,
-        &new_delegation_amount,
-        mix_id,
-        mix_rewarding.total_unit_reward,
-    );
-
     let delegation = Delegation::new(
         owner,
         mix_id,
@@ -123,14 +144,14 @@ pub(crate) fn delegate(
 
     // save on reading since `.save()` would have attempted to read old data that we already have on hand
     delegations_storage::delegations().replace(
-        deps.storage,
+        storage,
         storage_key,
         Some(&delegation),
         old_delegation.as_ref(),
     )?;
-    rewards_storage::MIXNODE_REWARDING.save(deps.storage, mix_id, &mix_rewarding)?;
+    rewards_storage::MIXNODE_REWARDING.save(storage, mix_id, &mix_rewarding)?;
 
-    Ok(Response::new().add_event(cosmos_event))
+    Ok(())
 }
 
 pub(crate) fn undelegate(
@@ -171,6 +192,123 @@ pub(crate) fn undelegate(
     Ok(response)
 }
 
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn redelegate(
+    deps: DepsMut<'_>,
+    env: &Env,
+    created_at: BlockHeight,
+    owner: Addr,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+    amount: Coin,
+    proxy: Option<Addr>,
+) -> Result<Response, MixnetContractError> {
+    // see if the delegation still exists (the user might have decided to undelegate
+    // or move all of their tokens elsewhere within the same epoch)
+    let source_key = Delegation::generate_storage_key(from_mix_id, &owner, proxy.as_ref());
+    let source_delegation =
+        match delegations_storage::delegations().may_load(deps.storage, source_key.clone())? {
+            None => return Ok(Response::default()),
+            Some(delegation) => delegation,
+        };
+
+    // check if the target node still exists. if it doesn't (or it's in the process of unbonding),
+    // we leave the tokens where they were. unlike with a fresh delegation, there's nothing to return.
+    let target_details = match get_mixnode_details_by_id(deps.storage, to_mix_id)? {
+        Some(details)
+            if details.rewarding_details.still_bonded()
+                && !details.bond_information.is_unbonding =>
+        {
+            details
+        }
+        _ => {
+            return Ok(
+                Response::new().add_event(new_redelegation_on_unbonded_node_event(
+                    &owner,
+                    &proxy,
+                    from_mix_id,
+                    to_mix_id,
+                )),
+            )
+        }
+    };
+
+    let mut source_rewarding = rewards_storage::MIXNODE_REWARDING.may_load(deps.storage, from_mix_id)?.ok_or(MixnetContractError::inconsistent_state(
+        "mixnode rewarding got removed from the storage whilst there's still an existing delegation",
+    ))?;
+
+    // completely remove the delegation (alongside all of its rewards) from the source node
+    let source_with_reward = source_rewarding.undelegate(&source_delegation)?;
+
+    // and move as much as was requested
+    let moved_amount = amount.amount.min(source_with_reward.amount);
+    let remaining_amount = source_with_reward.amount - moved_amount;
+    let moved = Coin::new(moved_amount.u128(), &source_with_reward.denom);
+
+    if remaining_amount.is_zero() {
+        delegations_storage::delegations().replace(
+            deps.storage,
+            source_key.clone(),
+            None,
+            Some(&source_delegation),
+        )?;
+        rewards_storage::COMPOUNDING_DELEGATIONS.remove(deps.storage, source_key);
+    } else {
+        // whatever is left on the source node becomes a fresh delegation
+        // (all the earned rewards have already been included in it)
+        source_rewarding.add_base_delegation(remaining_amount)?;
+        let remaining_delegation = Delegation::new(
+            owner.clone(),
+            from_mix_id,
+            source_rewarding.total_unit_reward,
+            Coin::new(remaining_amount.u128(), &source_with_reward.denom),
+            env.block.height,
+            proxy.clone(),
+        );
+        delegations_storage::delegations().replace(
+            deps.storage,
+            source_key,
+            Some(&remaining_delegation),
+            Some(&source_delegation),
+        )?;
+    }
+    rewards_storage::MIXNODE_REWARDING.save(deps.storage, from_mix_id, &source_rewarding)?;
+
+    let cosmos_event = new_redelegation_event(
+        created_at,
+        &owner,
+        &proxy,
+        &moved,
+        from_mix_id,
+        to_mix_id,
+        target_details.rewarding_details.total_unit_reward,
+    );
+
+    increase_delegation(
+        deps.storage,
+        env,
+        owner.clone(),
+        to_mix_id,
+        moved.clone(),
+        proxy.clone(),
+        target_details.rewarding_details,
+    )?;
+
+    let response = Response::new()
+        .add_event(cosmos_event)
+        .maybe_add_track_vesting_redelegation_message(
+            deps.storage,
+            proxy,
+            owner.into_string(),
+            from_mix_id,
+            to_mix_id,
+            moved,
+            remaining_amount.is_zero(),
+        )?;
+
+    Ok(response)
+}
+
 pub(crate) fn unbond_mixnode(
     deps: DepsMut<'_>,
     env: &Env,
@@ -356,6 +494,22 @@ impl ContractExecutableEvent for PendingEpochEventData {
                 mix_id,
                 proxy,
             } => undelegate(deps, self.created_at, owner, mix_id, proxy),
+            PendingEpochEventKind::Redelegate {
+                owner,
+                from_mix_id,
+                to_mix_id,
+                amount,
+                proxy,
+            } => redelegate(
+                deps,
+                env,
+                self.created_at,
+                owner,
+                from_mix_id,
+                to_mix_id,
+                amount,
+                proxy,
+            ),
             PendingEpochEventKind::PledgeMore { mix_id, amount } => {
                 increase_pledge(deps, self.created_at, mix_id, amount)
             }
@@ -1112,6 +1266,296 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod redelegating {
+        use cosmwasm_std::testing::mock_info;
+        use cosmwasm_std::{coin, to_binary, CosmosMsg, WasmMsg};
+
+        use mixnet_contract_common::rewarding::helpers::truncate_reward_amount;
+
+        use crate::mixnodes::transactions::try_remove_mixnode;
+        use crate::support::tests::fixtures::TEST_COIN_DENOM;
+
+        use super::*;
+
+        // returns the ids of the source and the target nodes, alongside the total value of
+        // the delegation made towards the source (including the earned rewards)
+        fn setup_rewarded_delegation(test: &mut TestSetup, owner: &str) -> (MixId, MixId, u128) {
+            let from_mix_id =
+                test.add_dummy_mixnode("mix-owner1", Some(100_000_000_000u128.into()));
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", Some(100_000_000_000u128.into()));
+
+            let delegation = 120_000_000u128;
+            test.add_immediate_delegation(owner, delegation, from_mix_id);
+
+            test.force_change_rewarded_set(vec![from_mix_id, to_mix_id]);
+            test.skip_to_next_epoch_end();
+            let dist1 = test.reward_with_distribution_with_state_bypass(
+                from_mix_id,
+                test_helpers::performance(100.0),
+            );
+            test.skip_to_next_epoch_end();
+            let dist2 = test.reward_with_distribution_with_state_bypass(
+                from_mix_id,
+                test_helpers::performance(100.0),
+            );
+
+            let reward = truncate_reward_amount(dist1.delegates + dist2.delegates);
+            (from_mix_id, to_mix_id, delegation + reward.u128())
+        }
+
+        #[test]
+        fn doesnt_do_anything_if_delegation_doesnt_exist() {
+            let mut test = TestSetup::new();
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+            let env = test.env();
+
+            let res = redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked("delegator"),
+                from_mix_id,
+                to_mix_id,
+                coin(100_000_000, TEST_COIN_DENOM),
+                None,
+            )
+            .unwrap();
+            assert!(res.messages.is_empty());
+            assert!(res.events.is_empty());
+            assert_eq!(test.mix_rewarding(to_mix_id).unique_delegations, 0);
+        }
+
+        #[test]
+        fn leaves_the_delegation_untouched_if_target_is_unbonding_or_unbonded() {
+            let mut test = TestSetup::new();
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let unbonding_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+            let unbonded_mix_id = test.add_dummy_mixnode("mix-owner3", None);
+
+            let owner = "delegator";
+            let delegation_coin = coin(120_000_000, TEST_COIN_DENOM);
+            test.add_immediate_delegation(owner, delegation_coin.amount, from_mix_id);
+
+            let env = test.env();
+            try_remove_mixnode(test.deps_mut(), env.clone(), mock_info("mix-owner3", &[])).unwrap();
+            test.execute_all_pending_events();
+            try_remove_mixnode(test.deps_mut(), env.clone(), mock_info("mix-owner2", &[])).unwrap();
+
+            for target in [unbonding_mix_id, unbonded_mix_id] {
+                let res = redelegate(
+                    test.deps_mut(),
+                    &env,
+                    123,
+                    Addr::unchecked(owner),
+                    from_mix_id,
+                    target,
+                    delegation_coin.clone(),
+                    None,
+                )
+                .unwrap();
+
+                // no tokens are moved anywhere
+                assert!(res.messages.is_empty());
+                let delegation = test.delegation(from_mix_id, owner, &None);
+                assert_eq!(delegation.amount, delegation_coin);
+            }
+        }
+
+        #[test]
+        fn moves_entire_delegation_with_earned_rewards_if_amount_exceeds_its_value() {
+            let mut test = TestSetup::new();
+            let owner = "delegator";
+            let (from_mix_id, to_mix_id, total) = setup_rewarded_delegation(&mut test, owner);
+            let env = test.env();
+
+            let res = redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from_mix_id,
+                to_mix_id,
+                coin(1_000_000_000_000, TEST_COIN_DENOM),
+                None,
+            )
+            .unwrap();
+
+            // the tokens never leave the contract
+            assert!(res.messages.is_empty());
+
+            let storage_key =
+                Delegation::generate_storage_key(from_mix_id, &Addr::unchecked(owner), None);
+            assert!(delegations_storage::delegations()
+                .may_load(test.deps().storage, storage_key)
+                .unwrap()
+                .is_none());
+            let source_rewarding = test.mix_rewarding(from_mix_id);
+            assert!(source_rewarding.delegates.is_zero());
+            assert_eq!(source_rewarding.unique_delegations, 0);
+
+            let delegation = test.delegation(to_mix_id, owner, &None);
+            assert_eq!(delegation.amount.amount.u128(), total);
+            let target_rewarding = test.mix_rewarding(to_mix_id);
+            assert_eq!(target_rewarding.unique_delegations, 1);
+            assert_eq!(
+                target_rewarding.delegates,
+                Decimal::from_atomics(total, 0).unwrap()
+            );
+        }
+
+        #[test]
+        fn moves_only_the_requested_amount_and_keeps_the_rest_delegated() {
+            let mut test = TestSetup::new();
+            let owner = "delegator";
+            let (from_mix_id, to_mix_id, total) = setup_rewarded_delegation(&mut test, owner);
+            let env = test.env();
+
+            let moved = 50_000_000u128;
+            redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from_mix_id,
+                to_mix_id,
+                coin(moved, TEST_COIN_DENOM),
+                None,
+            )
+            .unwrap();
+
+            // all the earned rewards stay with the remaining delegation
+            let remaining = test.delegation(from_mix_id, owner, &None);
+            assert_eq!(remaining.amount.amount.u128(), total - moved);
+            let source_rewarding = test.mix_rewarding(from_mix_id);
+            assert_eq!(source_rewarding.unique_delegations, 1);
+            assert_eq!(
+                remaining.cumulative_reward_ratio,
+                source_rewarding.total_unit_reward
+            );
+
+            let delegation = test.delegation(to_mix_id, owner, &None);
+            assert_eq!(delegation.amount.amount.u128(), moved);
+            assert_eq!(test.mix_rewarding(to_mix_id).unique_delegations, 1);
+        }
+
+        #[test]
+        fn if_target_delegation_already_exists_a_fresh_one_with_sum_of_both_is_created() {
+            let mut test = TestSetup::new();
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+
+            let owner = "delegator";
+            test.add_immediate_delegation(owner, 120_000_000u128, from_mix_id);
+            test.add_immediate_delegation(owner, 100_000_000u128, to_mix_id);
+
+            let env = test.env();
+            redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from_mix_id,
+                to_mix_id,
+                coin(20_000_000, TEST_COIN_DENOM),
+                None,
+            )
+            .unwrap();
+
+            let source = test.delegation(from_mix_id, owner, &None);
+            assert_eq!(source.amount, coin(100_000_000, TEST_COIN_DENOM));
+            let target = test.delegation(to_mix_id, owner, &None);
+            assert_eq!(target.amount, coin(120_000_000, TEST_COIN_DENOM));
+            assert_eq!(test.mix_rewarding(to_mix_id).unique_delegations, 1);
+        }
+
+        #[test]
+        fn attaches_vesting_contract_track_message() {
+            let mut test = TestSetup::new();
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+
+            let owner = "delegator";
+            let vesting_contract = test.vesting_contract();
+            test.add_immediate_delegation_with_legal_proxy(owner, 120_000_000u128, from_mix_id);
+
+            let env = test.env();
+            let res = redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from_mix_id,
+                to_mix_id,
+                coin(1_000_000_000, TEST_COIN_DENOM),
+                Some(vesting_contract.clone()),
+            )
+            .unwrap();
+
+            assert_eq!(res.messages.len(), 1);
+            let CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+            }) = &res.messages[0].msg
+            else {
+                panic!("unexpected message")
+            };
+            assert_eq!(contract_addr, vesting_contract.as_str());
+            let expected_msg = to_binary(&VestingContractExecuteMsg::TrackRedelegation {
+                owner: owner.to_string(),
+                from_mix_id,
+                to_mix_id,
+                amount: coin(120_000_000, TEST_COIN_DENOM),
+                moved_entire_delegation: true,
+            })
+            .unwrap();
+            assert_eq!(&expected_msg, msg);
+            assert!(funds.is_empty());
+
+            let delegation = test.delegation(to_mix_id, owner, &Some(vesting_contract));
+            assert_eq!(delegation.amount, coin(120_000_000, TEST_COIN_DENOM));
+        }
+
+        #[test]
+        fn returns_error_for_illegal_proxy() {
+            let mut test = TestSetup::new();
+            let from_mix_id = test.add_dummy_mixnode("mix-owner1", None);
+            let to_mix_id = test.add_dummy_mixnode("mix-owner2", None);
+
+            let owner = "delegator";
+            let vesting_contract = test.vesting_contract();
+            let dummy_proxy = Addr::unchecked("not-vesting-contract");
+            test.add_immediate_delegation_with_illegal_proxy(
+                owner,
+                120_000_000u128,
+                from_mix_id,
+                dummy_proxy.clone(),
+            );
+
+            let env = test.env();
+            let res = redelegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked(owner),
+                from_mix_id,
+                to_mix_id,
+                coin(1_000_000_000, TEST_COIN_DENOM),
+                Some(dummy_proxy.clone()),
+            )
+            .unwrap_err();
+            assert_eq!(
+                res,
+                MixnetContractError::ProxyIsNotVestingContract {
+                    received: dummy_proxy,
+                    vesting_contract,
+                }
+            );
+        }
+    }
+
     #[cfg(test)]
     mod mixnode_unbonding {
         use cosmwasm_std::{coin, to_binary, CosmosMsg, Uint128, WasmMsg};
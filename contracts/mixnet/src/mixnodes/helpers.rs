@@ -140,6 +140,7 @@ pub(crate) fn cleanup_post_unbond_mixnode_storage(
         None,
         Some(&current_details.bond_information),
     )?;
+    rewards_storage::COMPOUNDING_OPERATORS.remove(storage, mix_id);
 
     // if there are no pending delegations to return, we can also
     // purge all information regarding rewarding parameters
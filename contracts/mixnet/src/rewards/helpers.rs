@@ -4,8 +4,12 @@
 use super::storage;
 use crate::delegations::storage as delegations_storage;
 use crate::interval::storage as interval_storage;
-use cosmwasm_std::{Coin, Storage};
+use crate::mixnodes::storage as mixnodes_storage;
+use cosmwasm_std::{Coin, Event, Order, StdResult, Storage};
 use mixnet_contract_common::error::MixnetContractError;
+use mixnet_contract_common::events::{
+    new_compound_delegator_reward_event, new_compound_operator_reward_event,
+};
 use mixnet_contract_common::helpers::IntoBaseDecimal;
 use mixnet_contract_common::mixnode::{MixNodeDetails, MixNodeRewarding};
 use mixnet_contract_common::{Delegation, EpochState, EpochStatus, MixId};
@@ -86,6 +90,84 @@ pub(crate) fn withdraw_delegator_reward(
     Ok(reward)
 }
 
+/// Adds the rewards accrued by the operator and by all the delegators that have opted into automatic compounding
+/// to their respective stakes. Note that the updated `mix_rewarding` is NOT saved and it's up to the caller to do so.
+///
+/// This does not change the yield: pending rewards are already part of the stake the node gets rewarded for,
+/// so they earn rewards in the same way regardless of this flag. Compounding only moves them into
+/// the pledge (or the delegation) itself, i.e. it's equivalent to withdrawing and immediately re-staking them,
+/// minus the sub-unym dust that gets truncated away.
+pub(crate) fn compound_rewards(
+    store: &mut dyn Storage,
+    mix_id: MixId,
+    mix_rewarding: &mut MixNodeRewarding,
+) -> Result<Vec<Event>, MixnetContractError> {
+    let mut events = Vec::new();
+
+    if storage::COMPOUNDING_OPERATORS.has(store, mix_id) {
+        // note: operator's reward is already included in the `operator` field of the rewarding details,
+        // so it's sufficient to just update the pledge
+        let bond = mixnodes_storage::mixnode_bonds().load(store, mix_id)?;
+        let reward = mix_rewarding.pending_operator_reward(&bond.original_pledge);
+        if !reward.amount.is_zero() {
+            let mut updated_bond = bond.clone();
+            updated_bond.original_pledge.amount += reward.amount;
+            mixnodes_storage::mixnode_bonds().replace(
+                store,
+                mix_id,
+                Some(&updated_bond),
+                Some(&bond),
+            )?;
+            events.push(new_compound_operator_reward_event(
+                &bond.owner,
+                reward,
+                mix_id,
+            ));
+        }
+    }
+
+    let compounding_delegations = storage::COMPOUNDING_DELEGATIONS
+        .prefix(mix_id)
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for owner_proxy_subkey in compounding_delegations {
+        let storage_key = Delegation::generate_storage_key_with_subkey(mix_id, owner_proxy_subkey);
+        let Some(delegation) =
+            delegations_storage::delegations().may_load(store, storage_key.clone())?
+        else {
+            // the delegation no longer exists, so there's no point in keeping the flag around
+            storage::COMPOUNDING_DELEGATIONS.remove(store, storage_key);
+            continue;
+        };
+
+        let reward = mix_rewarding.pending_delegator_reward(&delegation)?;
+        if reward.amount.is_zero() {
+            continue;
+        }
+
+        // that's equivalent to withdrawing the reward and immediately delegating it again
+        let mut updated_delegation = delegation.clone();
+        mix_rewarding.withdraw_delegator_reward(&mut updated_delegation)?;
+        mix_rewarding.increase_delegates_uint128(reward.amount)?;
+        updated_delegation.amount.amount += reward.amount;
+
+        delegations_storage::delegations().replace(
+            store,
+            storage_key,
+            Some(&updated_delegation),
+            Some(&delegation),
+        )?;
+        events.push(new_compound_delegator_reward_event(
+            &delegation.owner,
+            reward,
+            mix_id,
+        ));
+    }
+
+    Ok(events)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
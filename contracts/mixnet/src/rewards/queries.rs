@@ -12,7 +12,7 @@ use mixnet_contract_common::mixnode::MixNodeDetails;
 use mixnet_contract_common::reward_params::{NodeRewardParams, Performance, RewardingParams};
 use mixnet_contract_common::rewarding::helpers::truncate_reward;
 use mixnet_contract_common::rewarding::{
-    EstimatedCurrentEpochRewardResponse, PendingRewardResponse,
+    EstimatedCurrentEpochRewardResponse, PendingRewardResponse, RewardCompoundingResponse,
 };
 use mixnet_contract_common::{Delegation, MixId};
 
@@ -91,6 +91,30 @@ pub fn query_pending_delegator_reward(
     })
 }
 
+pub fn query_operator_reward_compounding(
+    deps: Deps,
+    mix_id: MixId,
+) -> StdResult<RewardCompoundingResponse> {
+    Ok(RewardCompoundingResponse {
+        mix_id,
+        compounding_enabled: storage::COMPOUNDING_OPERATORS.has(deps.storage, mix_id),
+    })
+}
+
+pub fn query_delegator_reward_compounding(
+    deps: Deps,
+    owner: String,
+    mix_id: MixId,
+) -> StdResult<RewardCompoundingResponse> {
+    let owner_address = deps.api.addr_validate(&owner)?;
+    let storage_key = Delegation::generate_storage_key(mix_id, &owner_address, None);
+
+    Ok(RewardCompoundingResponse {
+        mix_id,
+        compounding_enabled: storage::COMPOUNDING_DELEGATIONS.has(deps.storage, storage_key),
+    })
+}
+
 fn zero_reward(
     original_stake: Coin,
     current_value: Decimal,
@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::constants::{
+    COMPOUNDING_DELEGATIONS_NAMESPACE, COMPOUNDING_OPERATORS_NAMESPACE,
     MIXNODES_REWARDING_PK_NAMESPACE, PENDING_REWARD_POOL_KEY, REWARDING_PARAMS_KEY,
 };
 use crate::rewards::models::RewardPoolChange;
-use cosmwasm_std::{Decimal, StdResult, Storage};
+use cosmwasm_std::{Decimal, Empty, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
+use mixnet_contract_common::delegation::OwnerProxySubKey;
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::mixnode::MixNodeRewarding;
 use mixnet_contract_common::reward_params::RewardingParams;
@@ -20,6 +22,15 @@ pub(crate) const PENDING_REWARD_POOL_CHANGE: Item<'_, RewardPoolChange> =
 pub const MIXNODE_REWARDING: Map<MixId, MixNodeRewarding> =
     Map::new(MIXNODES_REWARDING_PK_NAMESPACE);
 
+// mixnodes whose operators have opted into having their rewards automatically added to their pledge
+pub(crate) const COMPOUNDING_OPERATORS: Map<MixId, Empty> =
+    Map::new(COMPOUNDING_OPERATORS_NAMESPACE);
+
+// delegations (keyed the same way as in the delegations storage) whose owners have opted into
+// having their rewards automatically added to the delegated amount
+pub(crate) const COMPOUNDING_DELEGATIONS: Map<(MixId, OwnerProxySubKey), Empty> =
+    Map::new(COMPOUNDING_DELEGATIONS_NAMESPACE);
+
 pub fn reward_accounting(
     storage: &mut dyn Storage,
     amount: Decimal,
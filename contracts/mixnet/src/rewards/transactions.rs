@@ -1,23 +1,28 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use cosmwasm_std::{wasm_execute, Addr, DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{
+    wasm_execute, Addr, DepsMut, Empty, Env, Event, MessageInfo, Order, Response, Storage,
+};
 
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
-    new_active_set_update_event, new_mix_rewarding_event,
-    new_not_found_mix_operator_rewarding_event, new_pending_active_set_update_event,
+    new_active_set_update_event, new_delegator_reward_compounding_update_event,
+    new_mix_rewarding_event, new_not_found_mix_operator_rewarding_event,
+    new_operator_reward_compounding_update_event, new_pending_active_set_update_event,
     new_pending_rewarding_params_update_event, new_rewarding_params_update_event,
     new_withdraw_delegator_reward_event, new_withdraw_operator_reward_event,
     new_zero_uptime_mix_operator_rewarding_event,
 };
+use mixnet_contract_common::mixnode::MixNodeRewarding;
 use mixnet_contract_common::pending_events::{PendingEpochEventKind, PendingIntervalEventKind};
 use mixnet_contract_common::reward_params::{
     IntervalRewardingParamsUpdate, NodeRewardParams, Performance,
 };
-use mixnet_contract_common::{Delegation, EpochState, MixId};
+use mixnet_contract_common::{Delegation, EpochState, Interval, MixId};
 use vesting_contract_common::messages::ExecuteMsg as VestingContractExecuteMsg;
 
+use crate::constants::MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE;
 use crate::delegations::storage as delegations_storage;
 use crate::interval::storage as interval_storage;
 use crate::interval::storage::{push_new_epoch_event, push_new_interval_event};
@@ -103,12 +108,14 @@ pub(crate) fn try_reward_mixnode(
     // however, we still need to update last_rewarded_epoch field
     if node_performance.is_zero() {
         mix_rewarding.last_rewarded_epoch = absolute_epoch_id;
+        let compounding_events =
+            maybe_compound_rewards(deps.storage, &interval, mix_id, &mut mix_rewarding)?;
         storage::MIXNODE_REWARDING.save(deps.storage, mix_id, &mix_rewarding)?;
-        return Ok(
-            Response::new().add_event(new_zero_uptime_mix_operator_rewarding_event(
+        return Ok(Response::new()
+            .add_event(new_zero_uptime_mix_operator_rewarding_event(
                 interval, mix_id,
-            )),
-        );
+            ))
+            .add_events(compounding_events));
     }
 
     let rewarding_params = storage::REWARDING_PARAMS.load(deps.storage)?;
@@ -122,18 +129,36 @@ pub(crate) fn try_reward_mixnode(
         interval.epochs_in_interval(),
     );
     mix_rewarding.distribute_rewards(reward_distribution, absolute_epoch_id);
+    let compounding_events =
+        maybe_compound_rewards(deps.storage, &interval, mix_id, &mut mix_rewarding)?;
 
     // persist changes happened to the storage
     storage::MIXNODE_REWARDING.save(deps.storage, mix_id, &mix_rewarding)?;
     storage::reward_accounting(deps.storage, node_reward)?;
 
-    Ok(Response::new().add_event(new_mix_rewarding_event(
-        interval,
-        mix_id,
-        reward_distribution,
-        prior_delegates,
-        prior_unit_reward,
-    )))
+    Ok(Response::new()
+        .add_event(new_mix_rewarding_event(
+            interval,
+            mix_id,
+            reward_distribution,
+            prior_delegates,
+            prior_unit_reward,
+        ))
+        .add_events(compounding_events))
+}
+
+// rewards are only ever compounded when the node is being rewarded for the final epoch of an interval
+fn maybe_compound_rewards(
+    store: &mut dyn Storage,
+    interval: &Interval,
+    mix_id: MixId,
+    mix_rewarding: &mut MixNodeRewarding,
+) -> Result<Vec<Event>, MixnetContractError> {
+    if interval.is_current_epoch_last_in_interval() {
+        helpers::compound_rewards(store, mix_id, mix_rewarding)
+    } else {
+        Ok(Vec::new())
+    }
 }
 
 pub(crate) fn try_withdraw_operator_reward(
@@ -287,6 +312,91 @@ pub(crate) fn _try_withdraw_delegator_reward(
     )))
 }
 
+pub(crate) fn try_set_operator_reward_compounding(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    enabled: bool,
+) -> Result<Response, MixnetContractError> {
+    let owner = info.sender;
+    let mix_details = get_mixnode_details_by_owner(deps.storage, owner.clone())?.ok_or(
+        MixnetContractError::NoAssociatedMixNodeBond {
+            owner: owner.clone(),
+        },
+    )?;
+    let mix_id = mix_details.mix_id();
+    ensure_bonded(&mix_details.bond_information)?;
+
+    if enabled {
+        // the vesting contract wouldn't have known anything about the increased pledge
+        if mix_details.bond_information.proxy.is_some() {
+            return Err(MixnetContractError::CompoundingVestingPledge { mix_id });
+        }
+        storage::COMPOUNDING_OPERATORS.save(deps.storage, mix_id, &Empty {})?;
+    } else {
+        storage::COMPOUNDING_OPERATORS.remove(deps.storage, mix_id);
+    }
+
+    Ok(
+        Response::new().add_event(new_operator_reward_compounding_update_event(
+            &owner, mix_id, enabled,
+        )),
+    )
+}
+
+pub(crate) fn try_set_delegator_reward_compounding(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    mix_id: MixId,
+    enabled: bool,
+) -> Result<Response, MixnetContractError> {
+    let owner = info.sender;
+
+    // note: only delegations made with liquid tokens can be compounded
+    let storage_key = Delegation::generate_storage_key(mix_id, &owner, None);
+
+    if enabled {
+        if !delegations_storage::delegations().has(deps.storage, storage_key.clone()) {
+            return Err(MixnetContractError::NoMixnodeDelegationFound {
+                mix_id,
+                address: owner.into_string(),
+                proxy: None,
+            });
+        }
+
+        // there's no point in compounding rewards of a node that's going away
+        match mixnodes_storage::mixnode_bonds().may_load(deps.storage, mix_id)? {
+            Some(mix_bond) if mix_bond.is_unbonding => {
+                return Err(MixnetContractError::MixnodeIsUnbonding { mix_id });
+            }
+            None => return Err(MixnetContractError::MixnodeHasUnbonded { mix_id }),
+            _ => (),
+        };
+
+        if !storage::COMPOUNDING_DELEGATIONS.has(deps.storage, storage_key.clone()) {
+            let compounding = storage::COMPOUNDING_DELEGATIONS
+                .prefix(mix_id)
+                .keys(deps.storage, None, None, Order::Ascending)
+                .take(MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE as usize)
+                .count();
+            if compounding >= MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE as usize {
+                return Err(MixnetContractError::TooManyCompoundingDelegations {
+                    mix_id,
+                    limit: MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE,
+                });
+            }
+            storage::COMPOUNDING_DELEGATIONS.save(deps.storage, storage_key, &Empty {})?;
+        }
+    } else {
+        storage::COMPOUNDING_DELEGATIONS.remove(deps.storage, storage_key);
+    }
+
+    Ok(
+        Response::new().add_event(new_delegator_reward_compounding_update_event(
+            &owner, mix_id, enabled,
+        )),
+    )
+}
+
 pub(crate) fn try_update_active_set_size(
     deps: DepsMut<'_>,
     env: Env,
@@ -1946,6 +2056,370 @@ pub mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod reward_compounding {
+        use cosmwasm_std::{Decimal, Uint128};
+
+        use mixnet_contract_common::helpers::IntoBaseDecimal;
+        use mixnet_contract_common::rewarding::helpers::truncate_reward_amount;
+
+        use crate::constants::MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE;
+        use crate::interval::pending_events;
+        use crate::rewards::queries::{
+            query_delegator_reward_compounding, query_operator_reward_compounding,
+        };
+        use crate::support::tests::test_helpers::{assert_decimals, performance, TestSetup};
+
+        use super::*;
+
+        // shortens the current interval so that its end could be reached within few epochs
+        fn set_epochs_in_interval(test: &mut TestSetup, epochs_in_interval: u32) {
+            let mut interval = test.current_interval();
+            interval.force_change_epochs_in_interval(epochs_in_interval);
+            interval_storage::save_interval(test.deps_mut().storage, &interval).unwrap();
+        }
+
+        fn operator_compounding(test: &TestSetup, mix_id: MixId) -> bool {
+            query_operator_reward_compounding(test.deps(), mix_id)
+                .unwrap()
+                .compounding_enabled
+        }
+
+        fn delegator_compounding(test: &TestSetup, delegator: &str, mix_id: MixId) -> bool {
+            query_delegator_reward_compounding(test.deps(), delegator.to_string(), mix_id)
+                .unwrap()
+                .compounding_enabled
+        }
+
+        #[test]
+        fn setting_operator_compounding() {
+            let mut test = TestSetup::new();
+            let owner = "mix-owner";
+            let sender = mock_info(owner, &[]);
+
+            let res = try_set_operator_reward_compounding(test.deps_mut(), sender.clone(), true);
+            assert_eq!(
+                res,
+                Err(MixnetContractError::NoAssociatedMixNodeBond {
+                    owner: Addr::unchecked(owner)
+                })
+            );
+
+            let mix_id = test.add_dummy_mixnode(owner, None);
+            assert!(!operator_compounding(&test, mix_id));
+
+            try_set_operator_reward_compounding(test.deps_mut(), sender.clone(), true).unwrap();
+            assert!(operator_compounding(&test, mix_id));
+
+            // setting it again is a no-op
+            try_set_operator_reward_compounding(test.deps_mut(), sender.clone(), true).unwrap();
+            assert!(operator_compounding(&test, mix_id));
+
+            try_set_operator_reward_compounding(test.deps_mut(), sender.clone(), false).unwrap();
+            assert!(!operator_compounding(&test, mix_id));
+
+            test.start_unbonding_mixnode(mix_id);
+            let res = try_set_operator_reward_compounding(test.deps_mut(), sender, true);
+            assert_eq!(res, Err(MixnetContractError::MixnodeIsUnbonding { mix_id }));
+        }
+
+        #[test]
+        fn operator_compounding_is_not_allowed_for_vesting_pledge() {
+            let mut test = TestSetup::new();
+            let owner = "mix-owner";
+            let mix_id = test.add_dummy_mixnode_with_legal_proxy(owner, None);
+
+            let res =
+                try_set_operator_reward_compounding(test.deps_mut(), mock_info(owner, &[]), true);
+            assert_eq!(
+                res,
+                Err(MixnetContractError::CompoundingVestingPledge { mix_id })
+            );
+            assert!(!operator_compounding(&test, mix_id));
+        }
+
+        #[test]
+        fn setting_delegator_compounding() {
+            let mut test = TestSetup::new();
+            let delegator = "delegator";
+            let sender = mock_info(delegator, &[]);
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+
+            let res =
+                try_set_delegator_reward_compounding(test.deps_mut(), sender.clone(), mix_id, true);
+            assert_eq!(
+                res,
+                Err(MixnetContractError::NoMixnodeDelegationFound {
+                    mix_id,
+                    address: delegator.to_string(),
+                    proxy: None,
+                })
+            );
+
+            test.add_immediate_delegation(delegator, 100_000_000u128, mix_id);
+            assert!(!delegator_compounding(&test, delegator, mix_id));
+
+            try_set_delegator_reward_compounding(test.deps_mut(), sender.clone(), mix_id, true)
+                .unwrap();
+            assert!(delegator_compounding(&test, delegator, mix_id));
+
+            try_set_delegator_reward_compounding(test.deps_mut(), sender.clone(), mix_id, false)
+                .unwrap();
+            assert!(!delegator_compounding(&test, delegator, mix_id));
+
+            test.start_unbonding_mixnode(mix_id);
+            let res = try_set_delegator_reward_compounding(test.deps_mut(), sender, mix_id, true);
+            assert_eq!(res, Err(MixnetContractError::MixnodeIsUnbonding { mix_id }));
+        }
+
+        #[test]
+        fn number_of_compounding_delegations_per_node_is_limited() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+
+            for i in 0..=MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE {
+                test.add_immediate_delegation(&format!("delegator{i}"), 100_000_000u128, mix_id);
+            }
+            for i in 0..MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE {
+                try_set_delegator_reward_compounding(
+                    test.deps_mut(),
+                    mock_info(&format!("delegator{i}"), &[]),
+                    mix_id,
+                    true,
+                )
+                .unwrap();
+            }
+
+            let last = format!("delegator{MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE}");
+            let res = try_set_delegator_reward_compounding(
+                test.deps_mut(),
+                mock_info(&last, &[]),
+                mix_id,
+                true,
+            );
+            assert_eq!(
+                res,
+                Err(MixnetContractError::TooManyCompoundingDelegations {
+                    mix_id,
+                    limit: MAX_COMPOUNDING_DELEGATIONS_PER_MIXNODE
+                })
+            );
+
+            // delegations that are already compounding are not affected
+            try_set_delegator_reward_compounding(
+                test.deps_mut(),
+                mock_info("delegator0", &[]),
+                mix_id,
+                true,
+            )
+            .unwrap();
+
+            // and once a slot frees up, another delegation can start compounding
+            try_set_delegator_reward_compounding(
+                test.deps_mut(),
+                mock_info("delegator0", &[]),
+                mix_id,
+                false,
+            )
+            .unwrap();
+            try_set_delegator_reward_compounding(
+                test.deps_mut(),
+                mock_info(&last, &[]),
+                mix_id,
+                true,
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn rewards_are_compounded_at_the_end_of_interval() {
+            let mut test = TestSetup::new();
+            set_epochs_in_interval(&mut test, 3);
+
+            let owner = "mix-owner";
+            let compounding_delegator = "compounding-delegator";
+            let regular_delegator = "regular-delegator";
+
+            let pledge = Uint128::new(1_000_000_000_000);
+            let delegation = Uint128::new(500_000_000_000);
+            let mix_id = test.add_dummy_mixnode(owner, Some(pledge));
+            test.add_immediate_delegation(compounding_delegator, delegation, mix_id);
+            test.add_immediate_delegation(regular_delegator, delegation, mix_id);
+
+            try_set_operator_reward_compounding(test.deps_mut(), mock_info(owner, &[]), true)
+                .unwrap();
+            try_set_delegator_reward_compounding(
+                test.deps_mut(),
+                mock_info(compounding_delegator, &[]),
+                mix_id,
+                true,
+            )
+            .unwrap();
+
+            test.force_change_rewarded_set(vec![mix_id]);
+
+            // rewards are not compounded in the middle of an interval
+            test.skip_to_next_epoch_end();
+            assert!(!test.current_interval().is_current_epoch_last_in_interval());
+            test.reward_with_distribution_with_state_bypass(mix_id, performance(100.0));
+
+            assert_eq!(test.mix_bond(mix_id).original_pledge.amount, pledge);
+            assert_eq!(
+                test.delegation(mix_id, compounding_delegator, &None)
+                    .amount
+                    .amount,
+                delegation
+            );
+            assert!(!test.pending_operator_reward(mix_id).is_zero());
+            assert!(!test
+                .pending_delegator_reward(compounding_delegator, mix_id)
+                .is_zero());
+
+            // but they are once the final epoch of the interval gets rewarded
+            test.skip_to_next_epoch_end();
+            assert!(test.current_interval().is_current_epoch_last_in_interval());
+            test.reward_with_distribution_with_state_bypass(mix_id, performance(100.0));
+
+            let mix_rewarding = test.mix_rewarding(mix_id);
+            let compounded_pledge = test.mix_bond(mix_id).original_pledge.amount;
+            assert!(compounded_pledge > pledge);
+            assert_eq!(
+                compounded_pledge,
+                truncate_reward_amount(mix_rewarding.operator)
+            );
+            // (only the truncated dust is left behind)
+            assert!(test.pending_operator_reward(mix_id) < Decimal::one());
+
+            // both delegators have earned exactly the same, but only one of them had it added to the stake
+            let regular_reward = test.pending_delegator_reward(regular_delegator, mix_id);
+            let compounded = test.delegation(mix_id, compounding_delegator, &None);
+            assert_eq!(
+                compounded.amount.amount,
+                delegation + truncate_reward_amount(regular_reward)
+            );
+            assert_eq!(
+                compounded.cumulative_reward_ratio,
+                mix_rewarding.full_reward_ratio()
+            );
+            assert_eq!(
+                test.pending_delegator_reward(compounding_delegator, mix_id),
+                Decimal::zero()
+            );
+            assert_eq!(
+                test.delegation(mix_id, regular_delegator, &None)
+                    .amount
+                    .amount,
+                delegation
+            );
+
+            // the node is still accounted for correctly, i.e. the delegates contain both delegations
+            // alongside the pending reward of the regular delegator (minus the truncated dust)
+            assert_decimals(
+                mix_rewarding.delegates,
+                compounded.dec_amount().unwrap()
+                    + delegation.into_base_decimal().unwrap()
+                    + regular_reward,
+            );
+
+            // compounding doesn't change the yield though, since the pending reward of the regular delegator
+            // was already earning rewards too. from now on both stakes keep growing by the same factor every epoch
+            // (the compounding one is only smaller by the truncated dust)
+            let mut compounding_stake = compounded.dec_amount().unwrap();
+            let mut compounding_pending = Decimal::zero();
+            let mut regular_stake = delegation.into_base_decimal().unwrap() + regular_reward;
+            let mut regular_pending = regular_reward;
+
+            // (stay within the next interval so that nothing gets compounded again in the meantime)
+            for _ in 0..2 {
+                test.skip_to_next_epoch_end();
+                assert!(!test.current_interval().is_current_epoch_last_in_interval());
+                test.reward_with_distribution_with_state_bypass(mix_id, performance(100.0));
+
+                let compounding_reward = test
+                    .pending_delegator_reward(compounding_delegator, mix_id)
+                    - compounding_pending;
+                let regular_reward =
+                    test.pending_delegator_reward(regular_delegator, mix_id) - regular_pending;
+                assert!(!compounding_reward.is_zero());
+                assert!(regular_reward >= compounding_reward);
+
+                assert_decimals(
+                    compounding_reward / compounding_stake,
+                    regular_reward / regular_stake,
+                );
+
+                compounding_pending += compounding_reward;
+                compounding_stake += compounding_reward;
+                regular_pending += regular_reward;
+                regular_stake += regular_reward;
+            }
+        }
+
+        #[test]
+        fn rewards_are_compounded_even_with_zero_performance_in_final_epoch() {
+            let mut test = TestSetup::new();
+            set_epochs_in_interval(&mut test, 3);
+
+            let owner = "mix-owner";
+            let pledge = Uint128::new(1_000_000_000_000);
+            let mix_id = test.add_dummy_mixnode(owner, Some(pledge));
+            try_set_operator_reward_compounding(test.deps_mut(), mock_info(owner, &[]), true)
+                .unwrap();
+
+            test.force_change_rewarded_set(vec![mix_id]);
+
+            // earn something in an epoch that is not the final one
+            test.skip_to_next_epoch_end();
+            assert!(!test.current_interval().is_current_epoch_last_in_interval());
+            test.reward_with_distribution_with_state_bypass(mix_id, performance(100.0));
+            let pending = test.pending_operator_reward(mix_id);
+
+            test.skip_to_next_epoch_end();
+            assert!(test.current_interval().is_current_epoch_last_in_interval());
+            test.start_epoch_transition();
+            let env = test.env();
+            let sender = test.rewarding_validator();
+            try_reward_mixnode(test.deps_mut(), env, sender, mix_id, performance(0.0)).unwrap();
+
+            assert_eq!(
+                test.mix_bond(mix_id).original_pledge.amount,
+                pledge + truncate_reward_amount(pending)
+            );
+        }
+
+        #[test]
+        fn compounding_flag_is_removed_upon_undelegation() {
+            let mut test = TestSetup::new();
+            let delegator = "delegator";
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+            test.add_immediate_delegation(delegator, 100_000_000u128, mix_id);
+
+            try_set_delegator_reward_compounding(
+                test.deps_mut(),
+                mock_info(delegator, &[]),
+                mix_id,
+                true,
+            )
+            .unwrap();
+            assert!(delegator_compounding(&test, delegator, mix_id));
+
+            let env = test.env();
+            pending_events::undelegate(
+                test.deps_mut(),
+                env.block.height,
+                Addr::unchecked(delegator),
+                mix_id,
+                None,
+            )
+            .unwrap();
+            assert!(!delegator_compounding(&test, delegator, mix_id));
+
+            // so a new delegation wouldn't start compounding on its own
+            test.add_immediate_delegation(delegator, 100_000_000u128, mix_id);
+            assert!(!delegator_compounding(&test, delegator, mix_id));
+        }
+    }
+
     #[cfg(test)]
     mod updating_active_set {
         use mixnet_contract_common::EpochStatus;
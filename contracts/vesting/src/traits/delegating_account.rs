@@ -23,6 +23,14 @@ pub trait DelegatingAccount {
         storage: &dyn Storage,
     ) -> Result<Response, VestingContractError>;
 
+    fn try_redelegate_from_mixnode(
+        &self,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        storage: &dyn Storage,
+    ) -> Result<Response, VestingContractError>;
+
     // track_delegation performs internal vesting accounting necessary when
     // delegating from a vesting account. It accepts the current block height, the
     // delegation amount and balance of all coins whose denomination exists in
@@ -44,4 +52,16 @@ pub trait DelegatingAccount {
         amount: Coin,
         storage: &mut dyn Storage,
     ) -> Result<(), VestingContractError>;
+
+    // track_redelegation performs internal vesting accounting necessary when
+    // (part of) a delegation of a vesting account got moved to a different mixnode.
+    fn track_redelegation(
+        &self,
+        block_timestamp_secs: u64,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        moved_entire_delegation: bool,
+        storage: &mut dyn Storage,
+    ) -> Result<(), VestingContractError>;
 }
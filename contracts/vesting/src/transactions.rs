@@ -19,7 +19,8 @@ use vesting_contract_common::events::{
     new_ownership_transfer_event, new_periodic_vesting_account_event,
     new_staking_address_update_event, new_track_gateway_unbond_event,
     new_track_mixnode_pledge_decrease_event, new_track_mixnode_unbond_event,
-    new_track_reward_event, new_track_undelegation_event, new_vested_coins_withdraw_event,
+    new_track_redelegation_event, new_track_reward_event, new_track_undelegation_event,
+    new_vested_coins_withdraw_event,
 };
 use vesting_contract_common::{Account, PledgeCap, VestingContractError, VestingSpecification};
 
@@ -376,6 +377,33 @@ pub fn try_track_undelegation(
     Ok(Response::new().add_event(new_track_undelegation_event()))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn try_track_redelegation(
+    address: &str,
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+    amount: Coin,
+    moved_entire_delegation: bool,
+    info: MessageInfo,
+    env: Env,
+    deps: DepsMut<'_>,
+) -> Result<Response, VestingContractError> {
+    if info.sender != MIXNET_CONTRACT_ADDRESS.load(deps.storage)? {
+        return Err(VestingContractError::NotMixnetContract(info.sender));
+    }
+    let account = account_from_address(address, deps.storage, deps.api)?;
+
+    account.track_redelegation(
+        env.block.time.seconds(),
+        from_mix_id,
+        to_mix_id,
+        amount,
+        moved_entire_delegation,
+        deps.storage,
+    )?;
+    Ok(Response::new().add_event(new_track_redelegation_event()))
+}
+
 /// Delegate to mixnode, sends [mixnet_contract_common::ExecuteMsg::DelegateToMixnodeOnBehalf] to [crate::storage::MIXNET_CONTRACT_ADDRESS]..
 pub fn try_delegate_to_mixnode(
     mix_id: MixId,
@@ -449,6 +477,31 @@ pub fn try_undelegate_from_mixnode(
     account.try_undelegate_from_mixnode(mix_id, deps.storage)
 }
 
+/// Moves delegation between mixnodes, sends [mixnet_contract_common::ExecuteMsg::RedelegateFromMixnodeOnBehalf] to [crate::storage::MIXNET_CONTRACT_ADDRESS].
+pub fn try_redelegate_from_mixnode(
+    from_mix_id: MixId,
+    to_mix_id: MixId,
+    amount: Coin,
+    on_behalf_of: Option<String>,
+    info: MessageInfo,
+    deps: DepsMut<'_>,
+) -> Result<Response, VestingContractError> {
+    let mix_denom = MIX_DENOM.load(deps.storage)?;
+    let amount = validate_funds(&[amount], mix_denom)?;
+
+    let account = match on_behalf_of {
+        Some(account_owner) => {
+            let account = account_from_address(&account_owner, deps.storage, deps.api)?;
+            ensure_staking_permission(&info.sender, &account)?;
+            account
+        }
+        // you're the owner, you can do what you want
+        None => account_from_address(info.sender.as_str(), deps.storage, deps.api)?,
+    };
+
+    account.try_redelegate_from_mixnode(from_mix_id, to_mix_id, amount, deps.storage)
+}
+
 /// Creates a new periodic vesting account, and deposits funds to vest into the contract.
 ///
 /// Callable by ADMIN only, see [instantiate].
@@ -7,7 +7,7 @@ use cosmwasm_std::{wasm_execute, Coin, Env, Response, Storage, Uint128};
 use mixnet_contract_common::ExecuteMsg as MixnetExecuteMsg;
 use mixnet_contract_common::MixId;
 use vesting_contract_common::events::{
-    new_vesting_delegation_event, new_vesting_undelegation_event,
+    new_vesting_delegation_event, new_vesting_redelegation_event, new_vesting_undelegation_event,
 };
 use vesting_contract_common::VestingContractError;
 
@@ -96,6 +96,46 @@ impl DelegatingAccount for Account {
             .add_event(new_vesting_undelegation_event()))
     }
 
+    fn try_redelegate_from_mixnode(
+        &self,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        storage: &dyn Storage,
+    ) -> Result<Response, VestingContractError> {
+        if !self.any_delegation_for_mix(from_mix_id, storage) {
+            return Err(VestingContractError::NoSuchDelegation(
+                self.owner_address(),
+                from_mix_id,
+            ));
+        }
+
+        // the moved tokens are going to be tracked as a new delegation towards the target
+        let num_subdelegations = self.num_subdelegations_for_mix(to_mix_id, storage);
+        if num_subdelegations >= MAX_PER_MIX_DELEGATIONS {
+            return Err(VestingContractError::TooManyDelegations {
+                address: self.owner_address.clone(),
+                acc_id: self.storage_key(),
+                mix_id: to_mix_id,
+                num: num_subdelegations,
+                cap: MAX_PER_MIX_DELEGATIONS,
+            });
+        }
+
+        let msg = MixnetExecuteMsg::RedelegateFromMixnodeOnBehalf {
+            from_mix_id,
+            to_mix_id,
+            amount,
+            delegate: self.owner_address().into_string(),
+        };
+        let redelegate_from_mixnode =
+            wasm_execute(MIXNET_CONTRACT_ADDRESS.load(storage)?, &msg, vec![])?;
+
+        Ok(Response::new()
+            .add_message(redelegate_from_mixnode)
+            .add_event(new_vesting_redelegation_event()))
+    }
+
     fn track_delegation(
         &self,
         block_timestamp_secs: u64,
@@ -125,4 +165,40 @@ impl DelegatingAccount for Account {
         self.save_balance(new_balance, storage)?;
         Ok(())
     }
+
+    fn track_redelegation(
+        &self,
+        block_timestamp_secs: u64,
+        from_mix_id: MixId,
+        to_mix_id: MixId,
+        amount: Coin,
+        moved_entire_delegation: bool,
+        storage: &mut dyn Storage,
+    ) -> Result<(), VestingContractError> {
+        // note: the balance of the account does not change, we only have to move the
+        // delegated tokens between the mixnodes. since the moved amount might include rewards
+        // that we don't keep track of, it's capped by the originally delegated amount
+        let delegated = self.total_delegations_for_mix(from_mix_id, storage)?;
+        let moved = if moved_entire_delegation {
+            delegated
+        } else {
+            amount.amount.min(delegated)
+        };
+
+        self.remove_delegations_for_mix(from_mix_id, storage)?;
+        if !moved_entire_delegation {
+            // the delegation still exists, even if it only consists of the rewards now
+            save_delegation(
+                (self.storage_key(), from_mix_id, block_timestamp_secs),
+                delegated - moved,
+                storage,
+            )?;
+        }
+        save_delegation(
+            (self.storage_key(), to_mix_id, block_timestamp_secs),
+            moved,
+            storage,
+        )?;
+        Ok(())
+    }
 }
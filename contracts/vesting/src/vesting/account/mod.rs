@@ -162,7 +162,6 @@ pub(crate) trait StorableVestingAccountExt: VestingAccount {
         storage: &mut dyn Storage,
     ) -> Result<(), VestingContractError>;
 
-    #[allow(dead_code)]
     fn total_delegations_for_mix(
         &self,
         mix_id: MixId,
@@ -625,6 +625,80 @@ mod tests {
         assert_eq!(Uint128::new(90_000_000_000), total_delegations);
     }
 
+    #[test]
+    fn test_redelegations() {
+        let mut deps = init_contract();
+        let env = mock_env();
+        let account = vesting_account_new_fixture(&mut deps.storage, &env);
+
+        let delegation = coin(90_000_000_000, TEST_COIN_DENOM);
+        account
+            .try_delegate_to_mixnode(1, delegation, &env, &mut deps.storage)
+            .unwrap();
+        let balance = account.load_balance(&deps.storage).unwrap();
+
+        // can't move a delegation that doesn't exist
+        let err = account
+            .try_redelegate_from_mixnode(2, 3, coin(1_000_000_000, TEST_COIN_DENOM), &deps.storage)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            VestingContractError::NoSuchDelegation(account.owner_address(), 2)
+        );
+
+        let ok = account.try_redelegate_from_mixnode(
+            1,
+            2,
+            coin(30_000_000_000, TEST_COIN_DENOM),
+            &deps.storage,
+        );
+        assert!(ok.is_ok());
+
+        // part of the delegation got moved
+        account
+            .track_redelegation(
+                env.block.time.seconds(),
+                1,
+                2,
+                coin(30_000_000_000, TEST_COIN_DENOM),
+                false,
+                &mut deps.storage,
+            )
+            .unwrap();
+        assert_eq!(
+            account.total_delegations_for_mix(1, &deps.storage).unwrap(),
+            Uint128::new(60_000_000_000)
+        );
+        assert_eq!(
+            account.total_delegations_for_mix(2, &deps.storage).unwrap(),
+            Uint128::new(30_000_000_000)
+        );
+
+        // the rest of it, alongside some rewards, got moved. rewards are not tracked
+        account
+            .track_redelegation(
+                env.block.time.seconds(),
+                1,
+                2,
+                coin(65_000_000_000, TEST_COIN_DENOM),
+                true,
+                &mut deps.storage,
+            )
+            .unwrap();
+        assert!(!account.any_delegation_for_mix(1, &deps.storage));
+        assert_eq!(
+            account.total_delegations_for_mix(2, &deps.storage).unwrap(),
+            Uint128::new(90_000_000_000)
+        );
+
+        // the balance is not affected
+        assert_eq!(account.load_balance(&deps.storage).unwrap(), balance);
+        assert_eq!(
+            account.total_delegations(&deps.storage).unwrap(),
+            Uint128::new(90_000_000_000)
+        );
+    }
+
     #[test]
     fn test_mixnode_bonds() {
         let mut deps = init_contract();
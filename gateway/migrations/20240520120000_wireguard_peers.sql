@@ -0,0 +1,12 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+CREATE TABLE wireguard_peer
+(
+    public_key          TEXT    NOT NULL PRIMARY KEY UNIQUE,
+    private_ip          TEXT    NOT NULL,
+    mac                 TEXT    NOT NULL,
+    available_bandwidth INTEGER NOT NULL
+);
@@ -0,0 +1,14 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- unix timestamp of when the message has been stored
+ALTER TABLE message_store
+ADD COLUMN inserted_at INTEGER NOT NULL DEFAULT 0;
+
+-- we don't know when the existing messages have been received, so assume it was just now
+UPDATE message_store
+SET inserted_at = CAST(strftime('%s', 'now') AS INTEGER);
+
+CREATE INDEX `message_store_timestamp_index` ON `message_store` (`inserted_at`);
@@ -0,0 +1,17 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- bandwidth vouchers that have been accepted from the clients, but whose funds haven't yet been redeemed on chain
+CREATE TABLE pending_voucher_redemption
+(
+    blinded_serial_number_bs58 TEXT    NOT NULL PRIMARY KEY,
+    epoch_id                   INTEGER NOT NULL,
+
+    -- json-encoded spending data of the credential as required by the nym-apis for its verification
+    credential_data            TEXT    NOT NULL,
+
+    -- unix timestamp of when the voucher has been accepted
+    inserted_at                INTEGER NOT NULL
+);
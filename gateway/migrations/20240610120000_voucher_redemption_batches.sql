@@ -0,0 +1,22 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- batches of vouchers whose funds are being redeemed on chain. they're created before the redemption
+-- transaction is broadcast, so that the redemption could be resumed if any of the subsequent steps fails
+CREATE TABLE voucher_redemption_batch
+(
+    id          INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+
+    -- hash of the transaction that has submitted the batch to the bandwidth contract, once it has been included in a block
+    tx_hash     TEXT,
+
+    -- id of the multisig proposal releasing the funds of the batch, once it has been resolved
+    proposal_id INTEGER,
+
+    created_at  INTEGER NOT NULL
+);
+
+ALTER TABLE pending_voucher_redemption
+    ADD COLUMN batch_id INTEGER REFERENCES voucher_redemption_batch (id);
@@ -19,6 +19,7 @@ use nym_network_defaults::mainnet;
 use nym_network_defaults::var_names::NYXD;
 use nym_network_defaults::var_names::{BECH32_PREFIX, NYM_API};
 
+use nym_network_requester::error::ClientCoreError;
 use nym_network_requester::{
     generate_new_client_keys, set_active_gateway, setup_fs_gateways_storage, setup_gateway,
     GatewaySetup, OnDiskKeys,
@@ -160,7 +161,11 @@ pub(crate) async fn initialise_local_network_requester(
     let mut nr_cfg = nym_network_requester::Config::new(&nr_id).with_data_directory(nr_data_dir);
     nr_cfg = override_network_requester_config(nr_cfg, Some(opts));
 
-    let key_store = OnDiskKeys::new(nr_cfg.storage_paths.common_paths.keys.clone());
+    let key_store = OnDiskKeys::new(nr_cfg.storage_paths.common_paths.keys.clone())
+        .with_passphrase_from_env()
+        .map_err(|source| ClientCoreError::KeyStoreError {
+            source: Box::new(source),
+        })?;
     let details_store =
         setup_fs_gateways_storage(&nr_cfg.storage_paths.common_paths.gateway_registrations).await?;
 
@@ -221,7 +226,11 @@ pub(crate) async fn initialise_local_ip_packet_router(
     let mut ip_cfg = nym_ip_packet_router::Config::new(&ip_id).with_data_directory(ip_data_dir);
     ip_cfg = override_ip_packet_router_config(ip_cfg, Some(opts));
 
-    let key_store = OnDiskKeys::new(ip_cfg.storage_paths.common_paths.keys.clone());
+    let key_store = OnDiskKeys::new(ip_cfg.storage_paths.common_paths.keys.clone())
+        .with_passphrase_from_env()
+        .map_err(|source| ClientCoreError::KeyStoreError {
+            source: Box::new(source),
+        })?;
     let details_store =
         setup_fs_gateways_storage(&ip_cfg.storage_paths.common_paths.gateway_registrations).await?;
 
@@ -11,6 +11,7 @@ use nym_config::{
     must_get_home, read_config_from_toml_file, save_formatted_config_to_file, NymConfigTemplate,
     DEFAULT_CONFIG_DIR, DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_mixnode_common::packet_processor::replay_detection::ReplayProtectionConfig;
 use nym_network_defaults::{mainnet, DEFAULT_NYM_NODE_HTTP_PORT};
 use serde::{Deserialize, Serialize};
 use std::io;
@@ -43,6 +44,17 @@ const DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE: usize = 2000;
 const DEFAULT_STORED_MESSAGE_FILENAME_LENGTH: u16 = 16;
 const DEFAULT_MESSAGE_RETRIEVAL_LIMIT: i64 = 100;
 
+const DEFAULT_STORED_MESSAGES_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60); // 1 week
+const DEFAULT_STORED_MESSAGES_PRUNING_INTERVAL: Duration = Duration::from_secs(60 * 60); // 1 hour
+const DEFAULT_MAX_STORED_MESSAGES_PER_CLIENT: i64 = 10_000;
+const DEFAULT_MAX_STORED_BYTES_PER_CLIENT: i64 = 100 * 1024 * 1024; // 100MB
+
+const DEFAULT_SPENT_CREDENTIALS_FILTER_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const DEFAULT_SPENT_CREDENTIALS_SUBMISSION_INTERVAL: Duration = Duration::from_secs(30);
+
+const DEFAULT_VOUCHER_REDEMPTION_INTERVAL: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_VOUCHER_REDEMPTION_MAXIMUM_BATCH_SIZE: usize = 100;
+
 const DEFAULT_CLIENT_BANDWIDTH_MAX_FLUSHING_RATE: Duration = Duration::from_millis(5);
 const DEFAULT_CLIENT_BANDWIDTH_MAX_DELTA_FLUSHING_AMOUNT: i64 = 512 * 1024; // 512kB
 
@@ -464,6 +476,18 @@ pub struct Debug {
     // existing nodes whilst everyone else is upgrading and getting the code for handling the new field.
     // It shall be disabled in the subsequent releases.
     pub use_legacy_framed_packet_version: bool,
+
+    /// Settings for detecting and rejecting replayed sphinx packets.
+    pub replay_protection: ReplayProtectionConfig,
+
+    /// Limits imposed on messages stored for offline clients.
+    pub inbox: InboxConfig,
+
+    /// Settings for sharing information about spent credentials with other gateways (through the nym-apis).
+    pub spent_credentials: SpentCredentialsConfig,
+
+    /// Settings for redeeming the funds of the accepted bandwidth vouchers.
+    pub voucher_redemption: VoucherRedemptionConfig,
 }
 
 impl Default for Debug {
@@ -480,6 +504,81 @@ impl Default for Debug {
             client_bandwidth_max_delta_flushing_amount:
                 DEFAULT_CLIENT_BANDWIDTH_MAX_DELTA_FLUSHING_AMOUNT,
             use_legacy_framed_packet_version: false,
+            replay_protection: Default::default(),
+            inbox: Default::default(),
+            spent_credentials: Default::default(),
+            voucher_redemption: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct InboxConfig {
+    /// Maximum amount of time a message can be kept for an offline client before it gets removed.
+    #[serde(with = "humantime_serde")]
+    pub max_message_age: Duration,
+
+    /// Specifies how often the expired messages are removed from the storage.
+    #[serde(with = "humantime_serde")]
+    pub pruning_interval: Duration,
+
+    /// Maximum number of messages that can be stored for a single offline client.
+    pub max_messages_per_client: i64,
+
+    /// Maximum total size, in bytes, of messages that can be stored for a single offline client.
+    pub max_bytes_per_client: i64,
+}
+
+impl Default for InboxConfig {
+    fn default() -> Self {
+        InboxConfig {
+            max_message_age: DEFAULT_STORED_MESSAGES_MAX_AGE,
+            pruning_interval: DEFAULT_STORED_MESSAGES_PRUNING_INTERVAL,
+            max_messages_per_client: DEFAULT_MAX_STORED_MESSAGES_PER_CLIENT,
+            max_bytes_per_client: DEFAULT_MAX_STORED_BYTES_PER_CLIENT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct SpentCredentialsConfig {
+    /// Specifies how often the filters of credentials spent at other gateways are retrieved from the nym-apis.
+    #[serde(with = "humantime_serde")]
+    pub filter_refresh_interval: Duration,
+
+    /// Specifies how often the credentials spent at this gateway are reported to the nym-apis.
+    #[serde(with = "humantime_serde")]
+    pub submission_interval: Duration,
+}
+
+impl Default for SpentCredentialsConfig {
+    fn default() -> Self {
+        SpentCredentialsConfig {
+            filter_refresh_interval: DEFAULT_SPENT_CREDENTIALS_FILTER_REFRESH_INTERVAL,
+            submission_interval: DEFAULT_SPENT_CREDENTIALS_SUBMISSION_INTERVAL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct VoucherRedemptionConfig {
+    /// Specifies how often the funds of the accepted bandwidth vouchers are redeemed.
+    #[serde(with = "humantime_serde")]
+    pub redemption_interval: Duration,
+
+    /// Maximum number of vouchers redeemed with a single multisig proposal.
+    /// Note that it can't exceed the limit imposed by the bandwidth contract.
+    pub maximum_batch_size: usize,
+}
+
+impl Default for VoucherRedemptionConfig {
+    fn default() -> Self {
+        VoucherRedemptionConfig {
+            redemption_interval: DEFAULT_VOUCHER_REDEMPTION_INTERVAL,
+            maximum_batch_size: DEFAULT_VOUCHER_REDEMPTION_MAXIMUM_BATCH_SIZE,
         }
     }
 }
@@ -4,13 +4,12 @@
 use crate::config::Config;
 use crate::error::GatewayError;
 use crate::helpers::load_public_key;
-use log::{debug, error, warn};
+use log::{debug, error};
 use nym_bin_common::bin_info_owned;
 use nym_crypto::asymmetric::{encryption, identity};
-use nym_network_requester::RequestFilter;
 use nym_node_http_api::api::api_requests;
-use nym_node_http_api::api::api_requests::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use nym_node_http_api::api::api_requests::SignedHostInformation;
+use nym_node_http_api::state::exit_policy::SharedExitPolicy;
 use nym_node_http_api::NymNodeHttpError;
 use nym_sphinx::addressing::clients::Recipient;
 use nym_task::TaskClient;
@@ -43,6 +42,7 @@ fn load_host_details(
             ed25519_identity: identity_keypair.public_key().to_base58_string(),
             x25519_sphinx: sphinx_key.to_base58_string(),
             x25519_noise: "".to_string(),
+            sphinx_key_rotation: None,
         },
     };
 
@@ -134,7 +134,7 @@ fn load_ip_packet_router_details(
 pub(crate) struct HttpApiBuilder<'a> {
     gateway_config: &'a Config,
     network_requester_config: Option<&'a nym_network_requester::Config>,
-    exit_policy: Option<UsedExitPolicy>,
+    exit_policy: Option<SharedExitPolicy>,
     ip_packet_router_config: Option<&'a nym_ip_packet_router::Config>,
 
     identity_keypair: &'a identity::KeyPair,
@@ -168,44 +168,8 @@ impl<'a> HttpApiBuilder<'a> {
     }
 
     #[must_use]
-    pub(crate) fn with_maybe_network_request_filter(
-        mut self,
-        request_filter: Option<RequestFilter>,
-    ) -> Self {
-        let Some(request_filter) = request_filter else {
-            warn!("no valid request filter has been passed. no changes will be made");
-            return self;
-        };
-
-        // we can cheat here a bit since we're not refreshing the exit policy
-        // thus:
-        // - we can ignore the Arc pointer and clone the inner value
-        // - we can set the last refresh time to the current time
-        //
-        // once we start refreshing it, we'll have to change it, but at that point
-        // the allow list will be probably be completely removed and thus the pointer management
-        // will be much easier
-
-        let upstream = request_filter.current_exit_policy_filter().upstream();
-
-        // if there's no upstream (i.e. open proxy), we couldn't have possibly updated it : )
-        let last_updated = if upstream.is_some() {
-            #[allow(clippy::expect_used)]
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .expect("system clock is set to before the unix epoch")
-                .as_secs()
-        } else {
-            0
-        };
-
-        self.exit_policy = Some(UsedExitPolicy {
-            enabled: true,
-            upstream_source: upstream.map(|u| u.to_string()).unwrap_or_default(),
-            last_updated,
-            policy: Some(request_filter.current_exit_policy_filter().policy().clone()),
-        });
-
+    pub(crate) fn with_maybe_exit_policy(mut self, exit_policy: Option<SharedExitPolicy>) -> Self {
+        self.exit_policy = exit_policy;
         self
     }
 
@@ -245,7 +209,7 @@ impl<'a> HttpApiBuilder<'a> {
             )?);
 
             if let Some(exit_policy) = self.exit_policy {
-                config = config.with_used_exit_policy(exit_policy)
+                config = config.with_shared_exit_policy(exit_policy)
             }
         }
 
@@ -67,6 +67,9 @@ pub enum RequestHandlingError {
     #[error("the provided bandwidth credential has already been spent before at this gateway")]
     BandwidthCredentialAlreadySpent,
 
+    #[error("the provided bandwidth credential has already been spent before at another gateway")]
+    BandwidthCredentialSpentElsewhere,
+
     #[error("This gateway is only accepting coconut credentials for bandwidth")]
     OnlyCoconutCredentials,
 
@@ -347,20 +350,40 @@ where
             }
         }
 
+        // make sure the credential hasn't been accepted by any other gateway either
+        let epoch_id = credential.data.epoch_id;
+        let bs58_serial_number = serial_number.to_bs58();
+        let spent_elsewhere = self
+            .inner
+            .shared_state
+            .coconut_verifier
+            .is_spent_elsewhere(epoch_id, &bs58_serial_number)
+            .await?;
+        if spent_elsewhere {
+            trace!("the credential has already been spent at another gateway");
+            return Err(RequestHandlingError::BandwidthCredentialSpentElsewhere);
+        }
+
         match credential.data.typ {
             CredentialType::Voucher => {
-                trace!("the credential is a bandwidth voucher. attempting to release the funds");
-                let api_clients = self
+                // the funds are no longer released immediately, so make sure the voucher hasn't been redeemed
+                // before, as otherwise we'd only find out about it when submitting the redemption batch
+                let redeemed = self
                     .inner
                     .shared_state
                     .coconut_verifier
-                    .api_clients(credential.data.epoch_id)
+                    .is_redeemed_on_chain(&bs58_serial_number)
                     .await?;
+                if redeemed {
+                    trace!("the voucher has already been redeemed on chain");
+                    return Err(RequestHandlingError::BandwidthCredentialSpentElsewhere);
+                }
 
+                // the funds are going to be released alongside other vouchers in the next redemption batch
+                trace!("the credential is a bandwidth voucher. scheduling it for redemption");
                 self.inner
-                    .shared_state
-                    .coconut_verifier
-                    .release_bandwidth_voucher_funds(&api_clients, credential)
+                    .storage
+                    .insert_pending_voucher_redemption(&credential.data)
                     .await?;
             }
             CredentialType::FreePass => {
@@ -387,6 +410,13 @@ where
             )
             .await?;
 
+        // and let the other gateways know about it
+        self.inner
+            .shared_state
+            .coconut_verifier
+            .note_spent_credential(epoch_id, bs58_serial_number)
+            .await;
+
         trace!("increasing client bandwidth");
         self.increase_bandwidth(bandwidth).await?;
         // set free pass expiration
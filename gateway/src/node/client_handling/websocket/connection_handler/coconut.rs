@@ -3,23 +3,35 @@
 
 use super::authenticated::RequestHandlingError;
 use log::*;
-use nym_credentials_interface::VerificationKey;
-use nym_gateway_requests::models::CredentialSpendingRequest;
+use nym_api_requests::coconut::models::MAX_SPENT_CREDENTIALS_BATCH_SIZE;
+use nym_api_requests::coconut::spent_credentials::is_tracked_spent_credentials_epoch;
+use nym_api_requests::coconut::{SpentCredentialsBatch, SpentCredentialsFilter};
+use nym_coconut_bandwidth_contract_common::spend_credential::{
+    batch_proposal_description, BatchedSpendCredential,
+};
+use nym_credentials_interface::{CredentialSpendingData, VerificationKey};
+use nym_crypto::asymmetric::identity;
 use nym_validator_client::coconut::all_coconut_api_clients;
 use nym_validator_client::nym_api::EpochId;
-use nym_validator_client::nyxd::contract_traits::{MultisigQueryClient, NymContractsProvider};
-use nym_validator_client::nyxd::AccountId;
+use nym_validator_client::nyxd::contract_traits::{
+    CoconutBandwidthQueryClient, MultisigQueryClient, NymContractsProvider,
+};
+use nym_validator_client::nyxd::cw3::Status;
+use nym_validator_client::nyxd::{AccountId, CosmWasmClient, Hash};
 use nym_validator_client::{
     nyxd::{
         contract_traits::{CoconutBandwidthSigningClient, DkgQueryClient, MultisigSigningClient},
-        cosmwasm_client::logs::{find_attribute, BANDWIDTH_PROPOSAL_ID},
+        cosmwasm_client::logs::BANDWIDTH_PROPOSAL_ID,
         Coin,
     },
     CoconutApiClient, DirectSigningHttpRpcNyxdClient,
 };
 use std::collections::HashMap;
+use std::mem;
 use std::ops::Deref;
-use tokio::sync::{RwLock, RwLockReadGuard};
+use tokio::sync::{Mutex, RwLock, RwLockReadGuard};
+
+const PROPOSALS_PAGE_LIMIT: u32 = 100;
 
 pub(crate) struct CoconutVerifier {
     address: AccountId,
@@ -31,6 +43,13 @@ pub(crate) struct CoconutVerifier {
     // keys never change during epochs
     master_keys: RwLock<HashMap<EpochId, VerificationKey>>,
     mix_denom_base: String,
+
+    // union of the filters of credentials, spent at any gateway, as retrieved from all the api clients
+    // (only the most recent epochs are kept, see `SPENT_CREDENTIALS_FILTER_EPOCHS`)
+    spent_credentials: RwLock<HashMap<EpochId, SpentCredentialsFilter>>,
+
+    // serial numbers of credentials spent at this gateway that haven't yet been reported to the api clients
+    pending_spent_credentials: Mutex<HashMap<EpochId, Vec<String>>>,
 }
 
 impl CoconutVerifier {
@@ -63,6 +82,8 @@ impl CoconutVerifier {
                 api_clients: Default::default(),
                 master_keys: Default::default(),
                 mix_denom_base,
+                spent_credentials: Default::default(),
+                pending_spent_credentials: Default::default(),
             });
         }
 
@@ -79,6 +100,8 @@ impl CoconutVerifier {
                 api_clients: Default::default(),
                 master_keys: Default::default(),
                 mix_denom_base,
+                spent_credentials: Default::default(),
+                pending_spent_credentials: Default::default(),
             });
         };
 
@@ -114,6 +137,8 @@ impl CoconutVerifier {
             api_clients: RwLock::new(api_clients),
             master_keys: RwLock::new(master_keys),
             mix_denom_base,
+            spent_credentials: Default::default(),
+            pending_spent_credentials: Default::default(),
         })
     }
 
@@ -202,36 +227,223 @@ impl CoconutVerifier {
         Ok(all_coconut_api_clients(self.nyxd_client.read().await.deref(), epoch_id).await?)
     }
 
-    pub async fn release_bandwidth_voucher_funds(
+    /// Checks whether the credential with the provided serial number has already been spent at another gateway.
+    ///
+    /// The local filters might yield false positives, so any hit is confirmed with the api clients
+    /// before the credential gets rejected.
+    pub async fn is_spent_elsewhere(
         &self,
-        api_clients: &[CoconutApiClient],
-        credential: CredentialSpendingRequest,
-    ) -> Result<(), RequestHandlingError> {
-        if !credential.data.typ.is_voucher() {
-            unimplemented!()
+        epoch_id: EpochId,
+        blinded_serial_number_bs58: &str,
+    ) -> Result<bool, RequestHandlingError> {
+        let maybe_spent = self
+            .spent_credentials
+            .read()
+            .await
+            .get(&epoch_id)
+            .map(|filter| filter.contains(blinded_serial_number_bs58))
+            .unwrap_or_default();
+        if !maybe_spent {
+            return Ok(false);
         }
 
-        // safety: the voucher funds are released after the credential has already been verified locally
-        // and the underlying bandwidth value has been extracted, so the below MUST succeed
-        let voucher_amount = credential.unchecked_voucher_value() as u128;
+        trace!("{blinded_serial_number_bs58} might have been spent before. confirming with the api clients");
+        for client in self.api_clients(epoch_id).await?.iter() {
+            match client
+                .api_client
+                .spent_credential_status(blinded_serial_number_bs58)
+                .await
+            {
+                Ok(status) if status.spent => return Ok(true),
+                Ok(_) => {}
+                Err(err) => {
+                    let client_url = client.api_client.nym_api.current_url();
+                    warn!("failed to check the credential status with {client_url}: {err}")
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Schedules the serial number of the credential spent at this gateway to be reported to the api clients.
+    pub async fn note_spent_credential(
+        &self,
+        epoch_id: EpochId,
+        blinded_serial_number_bs58: String,
+    ) {
+        self.pending_spent_credentials
+            .lock()
+            .await
+            .entry(epoch_id)
+            .or_default()
+            .push(blinded_serial_number_bs58)
+    }
+
+    /// Retrieves the latest filters of spent credentials, for all the recent epochs we know about, from the api clients.
+    /// The filters of the epochs that are no longer tracked are dropped.
+    pub async fn refresh_spent_credentials_filters(&self) {
+        let current_epoch_id = match self.nyxd_client.read().await.get_current_epoch().await {
+            Ok(epoch) => epoch.epoch_id,
+            Err(err) => {
+                warn!("failed to query for the current DKG epoch - won't refresh the spent credentials filters: {err}");
+                return;
+            }
+        };
 
-        let blinded_serial_number = credential
-            .data
-            .verify_credential_request
-            .blinded_serial_number_bs58();
+        self.spent_credentials
+            .write()
+            .await
+            .retain(|epoch_id, _| is_tracked_spent_credentials_epoch(*epoch_id, current_epoch_id));
+
+        let epochs = self
+            .api_clients
+            .read()
+            .await
+            .keys()
+            .copied()
+            .filter(|epoch_id| is_tracked_spent_credentials_epoch(*epoch_id, current_epoch_id))
+            .collect::<Vec<_>>();
+
+        for epoch_id in epochs {
+            let Ok(api_clients) = self.api_clients(epoch_id).await else {
+                continue;
+            };
+
+            // note: filters only ever grow within an epoch, so we can safely merge the new ones into the existing one,
+            // without losing information from the apis that might be temporarily unreachable
+            let mut refreshed = self
+                .spent_credentials
+                .read()
+                .await
+                .get(&epoch_id)
+                .cloned()
+                .unwrap_or_default();
+
+            for client in api_clients.iter() {
+                let client_url = client.api_client.nym_api.current_url();
+                match client.api_client.spent_credentials_filter(epoch_id).await {
+                    Ok(response) => {
+                        if !refreshed.merge(&response.filter) {
+                            warn!("{client_url} has returned an incompatible spent credentials filter for epoch {epoch_id}")
+                        }
+                    }
+                    Err(err) => {
+                        debug!("failed to retrieve spent credentials filter for epoch {epoch_id} from {client_url}: {err}")
+                    }
+                }
+            }
+
+            self.spent_credentials
+                .write()
+                .await
+                .insert(epoch_id, refreshed);
+        }
+    }
+
+    /// Reports all credentials spent at this gateway since the last submission to the api clients.
+    pub async fn submit_spent_credentials(&self, identity: &identity::KeyPair) {
+        let pending = mem::take(&mut *self.pending_spent_credentials.lock().await);
+
+        for (epoch_id, serials) in pending {
+            let Ok(api_clients) = self.api_clients(epoch_id).await else {
+                warn!("could not obtain the api clients for epoch {epoch_id} - {} spent credentials won't be reported", serials.len());
+                continue;
+            };
+
+            for chunk in serials.chunks(MAX_SPENT_CREDENTIALS_BATCH_SIZE) {
+                let batch = SpentCredentialsBatch::new_signed(identity, epoch_id, chunk.to_vec());
+
+                // the other gateways use the union of all filters, so it's enough if any api has received the batch
+                let mut submitted = false;
+                for client in api_clients.iter() {
+                    match client.api_client.submit_spent_credentials(&batch).await {
+                        Ok(_) => submitted = true,
+                        Err(err) => {
+                            let client_url = client.api_client.nym_api.current_url();
+                            debug!("failed to submit spent credentials to {client_url}: {err}")
+                        }
+                    }
+                }
+
+                if !submitted {
+                    warn!(
+                        "failed to submit {} spent credentials to any api. we'll try again later",
+                        chunk.len()
+                    );
+                    self.pending_spent_credentials
+                        .lock()
+                        .await
+                        .entry(epoch_id)
+                        .or_default()
+                        .extend_from_slice(chunk)
+                }
+            }
+        }
+    }
+
+    /// Checks whether the voucher with the provided serial number has already been submitted to the bandwidth contract.
+    pub async fn is_redeemed_on_chain(
+        &self,
+        blinded_serial_number_bs58: &str,
+    ) -> Result<bool, RequestHandlingError> {
+        Ok(self
+            .nyxd_client
+            .read()
+            .await
+            .get_spent_credential(blinded_serial_number_bs58.to_string())
+            .await?
+            .spend_credential
+            .is_some())
+    }
+
+    /// Submits the batch of accepted bandwidth vouchers to the bandwidth contract, which creates
+    /// a single multisig proposal releasing all of their funds.
+    /// Returns the hash of the submission transaction.
+    pub async fn submit_vouchers_batch(
+        &self,
+        credentials: &[CredentialSpendingData],
+    ) -> Result<Hash, RequestHandlingError> {
+        let mut entries = Vec::with_capacity(credentials.len());
+        for credential in credentials {
+            // the value has been extracted and validated when the voucher has been accepted
+            let voucher_amount = credential
+                .get_bandwidth_attribute()
+                .and_then(|value| value.parse::<u128>().ok())
+                .ok_or_else(|| {
+                    RequestHandlingError::InvalidBandwidthCredential(String::from(
+                        "the voucher does not have a valid bandwidth value",
+                    ))
+                })?;
+
+            entries.push(BatchedSpendCredential::new(
+                Coin::new(voucher_amount, &self.mix_denom_base).into(),
+                credential
+                    .verify_credential_request
+                    .blinded_serial_number_bs58(),
+            ));
+        }
 
         let res = self
             .nyxd_client
             .write()
             .await
-            .spend_credential(
-                Coin::new(voucher_amount, &self.mix_denom_base),
-                blinded_serial_number,
-                self.address.to_string(),
-                None,
-            )
+            .spend_credentials_batch(entries, self.address.to_string(), None)
             .await?;
-        let proposal_id = find_attribute(&res.logs, "wasm", BANDWIDTH_PROPOSAL_ID)
+        Ok(res.transaction_hash)
+    }
+
+    /// Retrieves the id of the multisig proposal created by the provided batch submission transaction.
+    pub async fn batch_proposal_id(&self, tx_hash: Hash) -> Result<u64, RequestHandlingError> {
+        let tx = self.nyxd_client.read().await.get_tx(tx_hash).await?;
+
+        // the proposal id is emitted by the multisig contract, whose event is not necessarily the first wasm one
+        tx.tx_result
+            .events
+            .iter()
+            .filter(|event| event.kind == "wasm")
+            .flat_map(|event| event.attributes.iter())
+            .find(|attribute| attribute.key == BANDWIDTH_PROPOSAL_ID)
             .ok_or(RequestHandlingError::ProposalIdError {
                 reason: String::from("proposal id not found"),
             })?
@@ -239,38 +451,103 @@ impl CoconutVerifier {
             .parse::<u64>()
             .map_err(|_| RequestHandlingError::ProposalIdError {
                 reason: String::from("proposal id could not be parsed to u64"),
-            })?;
+            })
+    }
 
+    /// Attempts to find the multisig proposal releasing the funds of the batch of vouchers with the provided serial numbers,
+    /// for example if the hash of its submission transaction has been lost.
+    pub async fn find_batch_proposal(
+        &self,
+        blinded_serial_numbers: &[String],
+    ) -> Result<Option<u64>, RequestHandlingError> {
+        let description = batch_proposal_description(blinded_serial_numbers);
+        let client = self.nyxd_client.read().await;
+
+        // the batch would have been submitted recently, so start looking from the newest proposals
+        let mut start_before = None;
+        loop {
+            let proposals = client
+                .reverse_proposals(start_before, Some(PROPOSALS_PAGE_LIMIT))
+                .await?
+                .proposals;
+            if let Some(proposal) = proposals.iter().find(|p| p.description == description) {
+                return Ok(Some(proposal.id));
+            }
+
+            match proposals.last() {
+                Some(last) if proposals.len() == PROPOSALS_PAGE_LIMIT as usize => {
+                    start_before = Some(last.id)
+                }
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    /// Gets the multisig proposal releasing the funds of the provided batch of vouchers voted on by the api clients
+    /// and executes it.
+    pub async fn finalise_vouchers_batch(
+        &self,
+        api_clients: &[CoconutApiClient],
+        proposal_id: u64,
+        credentials: Vec<CredentialSpendingData>,
+    ) -> Result<(), RequestHandlingError> {
         let proposal = self
             .nyxd_client
             .read()
             .await
             .query_proposal(proposal_id)
             .await?;
-        if !credential.matches_blinded_serial_number(&proposal.description)? {
+        let serial_numbers = credentials
+            .iter()
+            .map(|credential| {
+                credential
+                    .verify_credential_request
+                    .blinded_serial_number_bs58()
+            })
+            .collect::<Vec<_>>();
+        if proposal.description != batch_proposal_description(&serial_numbers) {
             return Err(RequestHandlingError::ProposalIdError {
-                reason: String::from("proposal has different serial number"),
+                reason: String::from("proposal has different serial numbers"),
             });
         }
 
-        let req = nym_api_requests::coconut::VerifyCredentialBody::new(
-            credential.data,
-            proposal_id,
-            self.address.clone(),
-        );
-        for client in api_clients {
-            let ret = client.api_client.verify_bandwidth_credential(&req).await;
-            let client_url = client.api_client.nym_api.current_url();
-            match ret {
-                Ok(res) => {
-                    if !res.verification_result {
-                        warn!("Validator at {client_url} didn't accept the credential. It will probably vote No on the spending proposal");
+        match proposal.status {
+            Status::Executed => {
+                debug!("proposal {proposal_id} has already been executed");
+                return Ok(());
+            }
+            Status::Rejected => {
+                warn!(
+                    "proposal {proposal_id} has been rejected. funds of {} vouchers won't be redeemed",
+                    credentials.len()
+                );
+                return Ok(());
+            }
+            Status::Pending | Status::Open => {
+                let req = nym_api_requests::coconut::VerifyCredentialsBatchBody::new(
+                    credentials,
+                    proposal_id,
+                    self.address.clone(),
+                );
+                for client in api_clients {
+                    let ret = client
+                        .api_client
+                        .verify_bandwidth_credentials_batch(&req)
+                        .await;
+                    let client_url = client.api_client.nym_api.current_url();
+                    match ret {
+                        Ok(res) => {
+                            if !res.verification_result {
+                                warn!("Validator at {client_url} didn't accept the batch of credentials. It will probably vote No on the spending proposal");
+                            }
+                        }
+                        Err(err) => {
+                            warn!("Validator at {client_url} could not be reached. There might be a problem with the coconut endpoint: {err}");
+                        }
                     }
                 }
-                Err(err) => {
-                    warn!("Validator at {client_url} could not be reached. There might be a problem with the coconut endpoint: {err}");
-                }
             }
+            Status::Passed => {}
         }
 
         self.nyxd_client
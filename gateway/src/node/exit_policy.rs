@@ -0,0 +1,82 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use log::trace;
+use nym_network_requester::{ActiveExitPolicy, RequestFilter};
+use nym_node_http_api::api::api_requests::v1::network_requester::exit_policy::models::UsedExitPolicy;
+use nym_node_http_api::state::exit_policy::SharedExitPolicy;
+use nym_task::TaskClient;
+
+/// Propagates any changes to the exit policy of the embedded network requester to the http api.
+pub(crate) struct ExitPolicyUpdater {
+    request_filter: RequestFilter,
+    exit_policy: SharedExitPolicy,
+    shutdown: TaskClient,
+}
+
+impl ExitPolicyUpdater {
+    pub(crate) fn new(
+        request_filter: RequestFilter,
+        exit_policy: SharedExitPolicy,
+        shutdown: TaskClient,
+    ) -> Self {
+        ExitPolicyUpdater {
+            request_filter,
+            exit_policy,
+            shutdown,
+        }
+    }
+
+    fn used_exit_policy(&self, active: &ActiveExitPolicy) -> UsedExitPolicy {
+        let policy_filter = self.request_filter.current_exit_policy_filter();
+        let source = policy_filter.source();
+
+        // if there's no upstream (i.e. open proxy), we couldn't have possibly updated it : )
+        let last_updated = if source.is_some() {
+            active.last_updated().unix_timestamp() as u64
+        } else {
+            0
+        };
+
+        UsedExitPolicy {
+            enabled: true,
+            upstream_source: source.map(|s| s.to_string()).unwrap_or_default(),
+            last_updated,
+            policy_hash: Some(active.hash().to_string()),
+            policy: Some(active.policy().clone()),
+        }
+    }
+
+    async fn run(mut self) {
+        let mut updates = self.request_filter.current_exit_policy_filter().subscribe();
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("ExitPolicyUpdater: Received shutdown");
+                }
+                changed = updates.changed() => {
+                    if changed.is_err() {
+                        trace!("ExitPolicyUpdater: the exit policy filter has been dropped");
+                        break;
+                    }
+                    let active = updates.borrow_and_update().clone();
+                    self.exit_policy.update(self.used_exit_policy(&active)).await;
+                }
+            }
+        }
+        self.shutdown.disarm();
+        trace!("ExitPolicyUpdater: Exiting");
+    }
+
+    pub(crate) async fn start(self) {
+        // make sure the http api never exposes the placeholder policy
+        let current = self.request_filter.current_exit_policy_filter().current();
+        self.exit_policy
+            .update(self.used_exit_policy(&current))
+            .await;
+
+        tokio::spawn(self.run());
+    }
+}
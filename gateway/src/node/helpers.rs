@@ -71,18 +71,23 @@ pub(crate) async fn initialise_main_storage(
     let path = &config.storage_paths.clients_storage;
     let retrieval_limit = config.debug.message_retrieval_limit;
 
-    Ok(PersistentStorage::init(path, retrieval_limit).await?)
+    Ok(PersistentStorage::init(path, retrieval_limit, config.debug.inbox).await?)
 }
 
 pub fn load_keypair<T: PemStorableKeyPair>(
     paths: KeyPairPath,
     name: impl Into<String>,
 ) -> Result<T, GatewayError> {
-    nym_pemstore::load_keypair(&paths).map_err(|err| GatewayError::KeyPairLoadFailure {
-        keys: name.into(),
-        paths,
-        err,
-    })
+    // the private keys might have been encrypted at rest with a passphrase specified via the environment
+    nym_pemstore::default_passphrase()
+        .and_then(|passphrase| {
+            nym_pemstore::load_keypair_with_passphrase(&paths, passphrase.as_ref())
+        })
+        .map_err(|err| GatewayError::KeyPairLoadFailure {
+            keys: name.into(),
+            paths,
+            err,
+        })
 }
 
 /// Loads Sphinx keys stored on disk
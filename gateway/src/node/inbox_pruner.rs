@@ -0,0 +1,69 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::InboxConfig;
+use crate::node::storage::Storage;
+use log::{debug, error, info, trace};
+use nym_task::TaskClient;
+use time::OffsetDateTime;
+use tokio::time::interval;
+
+/// Periodically removes messages that have been stored for offline clients for longer than allowed.
+pub(crate) struct InboxPruner<St> {
+    storage: St,
+    config: InboxConfig,
+    shutdown: TaskClient,
+}
+
+impl<St> InboxPruner<St>
+where
+    St: Storage + 'static,
+{
+    pub(crate) fn new(storage: St, config: InboxConfig, shutdown: TaskClient) -> Self {
+        InboxPruner {
+            storage,
+            config,
+            shutdown,
+        }
+    }
+
+    async fn prune_expired_messages(&self) {
+        let cutoff = OffsetDateTime::now_utc() - self.config.max_message_age;
+        match self.storage.remove_messages_older_than(cutoff).await {
+            Ok(0) => trace!("there were no expired messages to remove"),
+            Ok(removed) => debug!("removed {removed} expired messages"),
+            Err(err) => error!("failed to remove expired messages: {err}"),
+        }
+
+        let metrics = self.storage.inbox_metrics();
+        let (rejected, expired) = (metrics.rejected_messages(), metrics.expired_messages());
+        if rejected > 0 || expired > 0 {
+            info!(
+                "offline client inboxes: {expired} messages expired and {rejected} messages ({} bytes) rejected due to exceeded quotas so far",
+                metrics.rejected_bytes()
+            );
+        }
+    }
+
+    async fn run(mut self) {
+        // the first tick completes immediately, so any messages that expired while the gateway was offline get removed on startup
+        let mut pruning_interval = interval(self.config.pruning_interval);
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("InboxPruner: Received shutdown");
+                }
+                _ = pruning_interval.tick() => {
+                    self.prune_expired_messages().await;
+                }
+            }
+        }
+        trace!("InboxPruner: Exiting");
+    }
+
+    pub(crate) fn start(self) {
+        tokio::spawn(self.run());
+    }
+}
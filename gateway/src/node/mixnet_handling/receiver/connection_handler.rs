@@ -3,13 +3,16 @@
 
 use crate::node::client_handling::active_clients::ActiveClientsStore;
 use crate::node::client_handling::websocket::message_receiver::MixMessageSender;
-use crate::node::mixnet_handling::receiver::packet_processing::PacketProcessor;
+use crate::node::mixnet_handling::receiver::packet_processing::{
+    GatewayProcessingError, PacketProcessor,
+};
 use crate::node::storage::error::StorageError;
 use crate::node::storage::Storage;
 use futures::channel::mpsc::SendError;
 use futures::StreamExt;
 use log::*;
 use nym_mixnet_client::forwarder::MixForwardingSender;
+use nym_mixnode_common::packet_processor::error::MixProcessingError;
 use nym_mixnode_common::packet_processor::processor::ProcessedFinalHop;
 use nym_sphinx::forwarding::packet::MixPacket;
 use nym_sphinx::framing::codec::NymCodec;
@@ -159,13 +162,20 @@ impl<St: Storage> ConnectionHandler<St> {
         // we failed to push message directly to the client - it's probably offline.
         // we should store it on the disk instead.
         match self.try_push_message_to_client(client_address, message) {
-            Err(unsent_plaintext) => match self
-                .store_processed_packet_payload(client_address, unsent_plaintext)
-                .await
-            {
-                Err(err) => error!("Failed to store client data - {err}"),
-                Ok(_) => trace!("Stored packet for {client_address}"),
-            },
+            Err(unsent_plaintext) => {
+                let message_size = unsent_plaintext.len();
+                match self
+                    .store_processed_packet_payload(client_address, unsent_plaintext)
+                    .await
+                {
+                    Err(err @ StorageError::InboxQuotaExceeded { .. }) => {
+                        self.storage.inbox_metrics().record_rejected(message_size);
+                        debug!("Dropping packet for {client_address} - {err}")
+                    }
+                    Err(err) => error!("Failed to store client data - {err}"),
+                    Ok(_) => trace!("Stored packet for {client_address}"),
+                }
+            }
             Ok(_) => trace!("Pushed received packet to {client_address}"),
         }
 
@@ -179,14 +189,15 @@ impl<St: Storage> ConnectionHandler<St> {
         &mut self,
         framed_sphinx_packet: FramedNymPacket,
     ) -> Result<(), CriticalPacketProcessingError> {
-        //
-        // TODO: here be replay attack detection - it will require similar key cache to the one in
-        // packet processor for vpn packets,
-        // question: can it also be per connection vs global?
-        //
-
+        // note: replay detection is performed (and counted) by the packet processor itself
         let processed_final_hop = match self.packet_processor.process_received(framed_sphinx_packet)
         {
+            Err(GatewayProcessingError::PacketProcessingError(
+                MixProcessingError::ReplayedPacket,
+            )) => {
+                debug!("rejected a replayed sphinx packet");
+                return Ok(());
+            }
             Err(err) => {
                 debug!("We failed to process received sphinx packet - {err}");
                 return Ok(());
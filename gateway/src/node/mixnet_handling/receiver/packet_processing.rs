@@ -1,10 +1,11 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use nym_crypto::asymmetric::encryption;
 use nym_mixnode_common::packet_processor::error::MixProcessingError;
 pub use nym_mixnode_common::packet_processor::processor::MixProcessingResult;
 use nym_mixnode_common::packet_processor::processor::{ProcessedFinalHop, SphinxPacketProcessor};
+use nym_mixnode_common::packet_processor::replay_detection::ReplayDetector;
+use nym_mixnode_common::packet_processor::sphinx_keys::ActiveSphinxKeys;
 use nym_sphinx::framing::packet::FramedNymPacket;
 use thiserror::Error;
 
@@ -24,10 +25,16 @@ pub struct PacketProcessor {
 }
 
 impl PacketProcessor {
-    pub(crate) fn new(encryption_key: &encryption::PrivateKey) -> Self {
-        PacketProcessor {
-            inner_processor: SphinxPacketProcessor::new(encryption_key.into()),
+    pub(crate) fn new(
+        sphinx_keys: ActiveSphinxKeys,
+        replay_detector: Option<ReplayDetector>,
+    ) -> Self {
+        let mut inner_processor = SphinxPacketProcessor::new_with_rotating_keys(sphinx_keys);
+        if let Some(replay_detector) = replay_detector {
+            inner_processor = inner_processor.with_replay_detection(replay_detector)
         }
+
+        PacketProcessor { inner_processor }
     }
 
     pub(crate) fn process_received(
@@ -13,14 +13,23 @@ use crate::node::client_handling::active_clients::ActiveClientsStore;
 use crate::node::client_handling::embedded_clients::{LocalEmbeddedClientHandle, MessageRouter};
 use crate::node::client_handling::websocket;
 use crate::node::client_handling::websocket::connection_handler::coconut::CoconutVerifier;
+use crate::node::exit_policy::ExitPolicyUpdater;
 use crate::node::helpers::{initialise_main_storage, load_network_requester_config};
+use crate::node::inbox_pruner::InboxPruner;
 use crate::node::mixnet_handling::receiver::connection_handler::ConnectionHandler;
+use crate::node::spent_credentials_sync::SpentCredentialsSync;
+use crate::node::voucher_redemption::VoucherRedeemer;
 use futures::channel::{mpsc, oneshot};
 use log::*;
 use nym_crypto::asymmetric::{encryption, identity};
 use nym_mixnet_client::forwarder::{MixForwardingSender, PacketForwarder};
+use nym_mixnode_common::packet_processor::replay_detection::{
+    ReplayDetector, ReplayDetectorRotator,
+};
+use nym_mixnode_common::packet_processor::sphinx_keys::ActiveSphinxKeys;
 use nym_network_defaults::NymNetworkDetails;
 use nym_network_requester::{LocalGateway, NRServiceProviderBuilder, RequestFilter};
+use nym_node_http_api::state::exit_policy::SharedExitPolicy;
 use nym_task::{TaskClient, TaskHandle, TaskManager};
 use nym_types::gateway::GatewayNodeDetailsResponse;
 use nym_validator_client::nyxd::{Coin, CosmWasmClient};
@@ -32,11 +41,15 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 pub(crate) mod client_handling;
+pub(crate) mod exit_policy;
 pub(crate) mod helpers;
+pub(crate) mod inbox_pruner;
 pub(crate) mod mixnet_handling;
+pub(crate) mod spent_credentials_sync;
 pub(crate) mod storage;
+pub(crate) mod voucher_redemption;
 
-pub use storage::{InMemStorage, PersistentStorage, Storage};
+pub use storage::{InMemStorage, InboxMetrics, PersistentStorage, Storage};
 
 // TODO: should this struct live here?
 struct StartedNetworkRequester {
@@ -143,11 +156,17 @@ pub struct Gateway<St = PersistentStorage> {
     /// x25519 keypair used for Diffie-Hellman. Currently only used for sphinx key derivation.
     sphinx_keypair: Arc<encryption::KeyPair>,
 
+    /// Rotating sphinx keys used for processing packets. If not set, the static `sphinx_keypair` is used instead.
+    active_sphinx_keys: Option<ActiveSphinxKeys>,
+
     storage: St,
 
     #[cfg(all(feature = "wireguard", target_os = "linux"))]
     wireguard_data: Option<nym_wireguard::WireguardData>,
 
+    /// Exit policy of the embedded network requester as exposed by the http api.
+    exit_policy: SharedExitPolicy,
+
     run_http_server: bool,
     task_client: Option<TaskClient>,
 }
@@ -164,12 +183,14 @@ impl<St> Gateway<St> {
             storage,
             identity_keypair: Arc::new(load_identity_keys(&config)?),
             sphinx_keypair: Arc::new(helpers::load_sphinx_keys(&config)?),
+            active_sphinx_keys: None,
             config,
             network_requester_opts,
             ip_packet_router_opts,
             authenticator_opts: None,
             #[cfg(all(feature = "wireguard", target_os = "linux"))]
             wireguard_data: None,
+            exit_policy: Default::default(),
             run_http_server: true,
             task_client: None,
         })
@@ -191,9 +212,11 @@ impl<St> Gateway<St> {
             authenticator_opts,
             identity_keypair,
             sphinx_keypair,
+            active_sphinx_keys: None,
             storage,
             #[cfg(all(feature = "wireguard", target_os = "linux"))]
             wireguard_data: None,
+            exit_policy: Default::default(),
             run_http_server: true,
             task_client: None,
         }
@@ -203,10 +226,18 @@ impl<St> Gateway<St> {
         self.run_http_server = false
     }
 
+    pub fn set_shared_exit_policy(&mut self, exit_policy: SharedExitPolicy) {
+        self.exit_policy = exit_policy
+    }
+
     pub fn set_task_client(&mut self, task_client: TaskClient) {
         self.task_client = Some(task_client)
     }
 
+    pub fn set_active_sphinx_keys(&mut self, sphinx_keys: ActiveSphinxKeys) {
+        self.active_sphinx_keys = Some(sphinx_keys)
+    }
+
     #[cfg(all(feature = "wireguard", target_os = "linux"))]
     pub fn set_wireguard_data(&mut self, wireguard_data: nym_wireguard::WireguardData) {
         self.wireguard_data = Some(wireguard_data)
@@ -217,6 +248,24 @@ impl<St> Gateway<St> {
         crate::helpers::node_details(&self.config).await
     }
 
+    fn start_replay_detector(&self, shutdown: TaskClient) -> Option<ReplayDetector> {
+        let replay_protection = &self.config.debug.replay_protection;
+        if replay_protection.unsafe_disabled {
+            warn!("replay protection has been explicitly disabled");
+            return None;
+        }
+
+        let replay_detector = ReplayDetector::new(replay_protection);
+        ReplayDetectorRotator::new(
+            replay_detector.clone(),
+            replay_protection.rotation_interval,
+            shutdown,
+        )
+        .start();
+
+        Some(replay_detector)
+    }
+
     fn start_mix_socket_listener(
         &self,
         ack_sender: MixForwardingSender,
@@ -227,8 +276,12 @@ impl<St> Gateway<St> {
     {
         info!("Starting mix socket listener...");
 
-        let packet_processor =
-            mixnet_handling::PacketProcessor::new(self.sphinx_keypair.private_key());
+        let replay_detector = self.start_replay_detector(shutdown.fork("ReplayDetectorRotator"));
+        let sphinx_keys = self
+            .active_sphinx_keys
+            .clone()
+            .unwrap_or_else(|| ActiveSphinxKeys::new(0, self.sphinx_keypair.private_key().into()));
+        let packet_processor = mixnet_handling::PacketProcessor::new(sphinx_keys, replay_detector);
 
         let connection_handler = ConnectionHandler::new(
             packet_processor,
@@ -250,7 +303,10 @@ impl<St> Gateway<St> {
         &mut self,
         forwarding_channel: MixForwardingSender,
         shutdown: TaskClient,
-    ) -> Result<StartedAuthenticator, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> Result<StartedAuthenticator, Box<dyn std::error::Error + Send + Sync>>
+    where
+        St: Storage + Clone + 'static,
+    {
         let opts = self
             .authenticator_opts
             .as_ref()
@@ -265,6 +321,11 @@ impl<St> Gateway<St> {
         );
 
         if let Some(wireguard_data) = self.wireguard_data.take() {
+            // the peers have to be restored before the authenticator starts assigning private IPs
+            let peer_storage = Arc::new(storage::WireguardPeerStorage::new(self.storage.clone()));
+            let restored_peers =
+                nym_wireguard::restore_peers(&wireguard_data.inner, peer_storage.as_ref()).await?;
+
             let (on_start_tx, on_start_rx) = oneshot::channel();
             let mut authenticator_server = nym_authenticator::Authenticator::new(
                 opts.config.clone(),
@@ -299,7 +360,13 @@ impl<St> Gateway<St> {
             MessageRouter::new(auth_mix_receiver, packet_router)
                 .start_with_shutdown(router_shutdown);
 
-            let wg_api = nym_wireguard::start_wireguard(shutdown, wireguard_data).await?;
+            let wg_api = nym_wireguard::start_wireguard(
+                shutdown,
+                wireguard_data,
+                peer_storage,
+                restored_peers,
+            )
+            .await?;
 
             Ok(StartedAuthenticator {
                 wg_api,
@@ -573,11 +640,35 @@ impl<St> Gateway<St> {
             }
         }
 
-        let coconut_verifier =
-            CoconutVerifier::new(nyxd_client, self.config.gateway.only_coconut_credentials).await?;
+        let coconut_verifier = Arc::new(
+            CoconutVerifier::new(nyxd_client, self.config.gateway.only_coconut_credentials).await?,
+        );
+
+        SpentCredentialsSync::new(
+            coconut_verifier.clone(),
+            self.identity_keypair.clone(),
+            self.config.debug.spent_credentials,
+            shutdown.fork("SpentCredentialsSync"),
+        )
+        .start();
+
+        VoucherRedeemer::new(
+            coconut_verifier.clone(),
+            self.storage.clone(),
+            self.config.debug.voucher_redemption,
+            shutdown.fork("VoucherRedeemer"),
+        )
+        .start();
 
         let mix_forwarding_channel = self.start_packet_forwarder(shutdown.fork("PacketForwarder"));
 
+        InboxPruner::new(
+            self.storage.clone(),
+            self.config.debug.inbox,
+            shutdown.fork("InboxPruner"),
+        )
+        .start();
+
         let active_clients_store = ActiveClientsStore::new();
         self.start_mix_socket_listener(
             mix_forwarding_channel.clone(),
@@ -589,10 +680,10 @@ impl<St> Gateway<St> {
             mix_forwarding_channel.clone(),
             active_clients_store.clone(),
             shutdown.fork("websocket::Listener"),
-            Arc::new(coconut_verifier),
+            coconut_verifier,
         );
 
-        let nr_request_filter = if self.config.network_requester.enabled {
+        let nr_exit_policy = if self.config.network_requester.enabled {
             let embedded_nr = self
                 .start_network_requester(
                     mix_forwarding_channel.clone(),
@@ -601,7 +692,15 @@ impl<St> Gateway<St> {
                 .await?;
             // insert information about embedded NR to the active clients store
             active_clients_store.insert_embedded(embedded_nr.handle);
-            Some(embedded_nr.used_request_filter)
+
+            ExitPolicyUpdater::new(
+                embedded_nr.used_request_filter,
+                self.exit_policy.clone(),
+                shutdown.fork("ExitPolicyUpdater"),
+            )
+            .start()
+            .await;
+            Some(self.exit_policy.clone())
         } else {
             info!("embedded network requester is disabled");
             None
@@ -636,7 +735,7 @@ impl<St> Gateway<St> {
                 self.sphinx_keypair.clone(),
             )
             .with_maybe_network_requester(self.network_requester_opts.as_ref().map(|o| &o.config))
-            .with_maybe_network_request_filter(nr_request_filter)
+            .with_maybe_exit_policy(nr_exit_policy)
             .with_maybe_ip_packet_router(self.ip_packet_router_opts.as_ref().map(|o| &o.config))
             .start(shutdown.fork("http-api"))?;
         }
@@ -0,0 +1,65 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::SpentCredentialsConfig;
+use crate::node::client_handling::websocket::connection_handler::coconut::CoconutVerifier;
+use log::trace;
+use nym_crypto::asymmetric::identity;
+use nym_task::TaskClient;
+use std::sync::Arc;
+use tokio::time::interval;
+
+/// Periodically exchanges information about spent credentials with the nym-apis, i.e. it retrieves
+/// the filters of credentials spent at other gateways and reports the ones spent at this gateway.
+pub(crate) struct SpentCredentialsSync {
+    coconut_verifier: Arc<CoconutVerifier>,
+    identity_keypair: Arc<identity::KeyPair>,
+    config: SpentCredentialsConfig,
+    shutdown: TaskClient,
+}
+
+impl SpentCredentialsSync {
+    pub(crate) fn new(
+        coconut_verifier: Arc<CoconutVerifier>,
+        identity_keypair: Arc<identity::KeyPair>,
+        config: SpentCredentialsConfig,
+        shutdown: TaskClient,
+    ) -> Self {
+        SpentCredentialsSync {
+            coconut_verifier,
+            identity_keypair,
+            config,
+            shutdown,
+        }
+    }
+
+    async fn run(mut self) {
+        let mut refresh_interval = interval(self.config.filter_refresh_interval);
+        let mut submission_interval = interval(self.config.submission_interval);
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("SpentCredentialsSync: Received shutdown");
+                }
+                _ = refresh_interval.tick() => {
+                    self.coconut_verifier.refresh_spent_credentials_filters().await;
+                }
+                _ = submission_interval.tick() => {
+                    self.coconut_verifier.submit_spent_credentials(&self.identity_keypair).await;
+                }
+            }
+        }
+
+        // don't lose the credentials spent since the last submission
+        self.coconut_verifier
+            .submit_spent_credentials(&self.identity_keypair)
+            .await;
+        trace!("SpentCredentialsSync: Exiting");
+    }
+
+    pub(crate) fn start(self) {
+        tokio::spawn(self.run());
+    }
+}
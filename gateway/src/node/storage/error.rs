@@ -10,4 +10,30 @@ pub enum StorageError {
 
     #[error("Failed to perform database migration: {0}")]
     MigrationError(#[from] sqlx::migrate::MigrateError),
+
+    #[error("the inbox of {client} is full: it already holds {stored_messages} messages ({stored_bytes} bytes)")]
+    InboxQuotaExceeded {
+        client: String,
+        stored_messages: i64,
+        stored_bytes: i64,
+    },
+
+    #[error("the stored wireguard peer {public_key} is malformed: {reason}")]
+    MalformedWireguardPeer { public_key: String, reason: String },
+
+    #[error(
+        "failed to (de)serialize the spending data of credential {blinded_serial_number}: {source}"
+    )]
+    CredentialDataSerializationFailure {
+        blinded_serial_number: String,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("the stored credential {blinded_serial_number} has been issued in epoch {credential_epoch}, but it has been stored under epoch {stored_epoch}")]
+    InconsistentCredentialEpoch {
+        blinded_serial_number: String,
+        stored_epoch: i64,
+        credential_epoch: u64,
+    },
 }
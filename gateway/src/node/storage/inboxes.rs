@@ -1,7 +1,44 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::node::storage::models::StoredMessage;
+use crate::node::storage::models::{InboxUsage, StoredMessage};
+use std::sync::atomic::{AtomicU64, Ordering};
+use time::OffsetDateTime;
+
+/// Counters of messages that were dropped instead of being delivered to offline clients.
+#[derive(Debug, Default)]
+pub struct InboxMetrics {
+    rejected_messages: AtomicU64,
+    rejected_bytes: AtomicU64,
+    expired_messages: AtomicU64,
+}
+
+impl InboxMetrics {
+    pub(crate) fn record_rejected(&self, bytes: usize) {
+        self.rejected_messages.fetch_add(1, Ordering::Relaxed);
+        self.rejected_bytes
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_expired(&self, messages: u64) {
+        self.expired_messages.fetch_add(messages, Ordering::Relaxed);
+    }
+
+    /// Number of messages that were not stored due to the client exceeding its inbox quota.
+    pub fn rejected_messages(&self) -> u64 {
+        self.rejected_messages.load(Ordering::Relaxed)
+    }
+
+    /// Total size of messages that were not stored due to the client exceeding its inbox quota.
+    pub fn rejected_bytes(&self) -> u64 {
+        self.rejected_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Number of messages that were removed due to exceeding their maximum age.
+    pub fn expired_messages(&self) -> u64 {
+        self.expired_messages.load(Ordering::Relaxed)
+    }
+}
 
 #[derive(Clone)]
 pub(crate) struct InboxManager {
@@ -41,16 +78,42 @@ impl InboxManager {
         client_address_bs58: &str,
         content: Vec<u8>,
     ) -> Result<(), sqlx::Error> {
+        let inserted_at = OffsetDateTime::now_utc().unix_timestamp();
         sqlx::query!(
-            "INSERT INTO message_store(client_address_bs58, content) VALUES (?, ?)",
+            "INSERT INTO message_store(client_address_bs58, content, inserted_at) VALUES (?, ?, ?)",
             client_address_bs58,
             content,
+            inserted_at,
         )
         .execute(&self.connection_pool)
         .await?;
         Ok(())
     }
 
+    /// Retrieves the number and the total size of messages stored for the particular client.
+    ///
+    /// # Arguments
+    ///
+    /// * `client_address_bs58`: base58-encoded address of the client
+    pub(crate) async fn get_inbox_usage(
+        &self,
+        client_address_bs58: &str,
+    ) -> Result<InboxUsage, sqlx::Error> {
+        sqlx::query_as!(
+            InboxUsage,
+            r#"
+                SELECT
+                    COUNT(*) as "messages!: i64",
+                    COALESCE(SUM(LENGTH(content)), 0) as "bytes!: i64"
+                FROM message_store
+                WHERE client_address_bs58 = ?
+            "#,
+            client_address_bs58
+        )
+        .fetch_one(&self.connection_pool)
+        .await
+    }
+
     /// Retrieves messages stored for the particular client specified by the provided address.
     ///
     /// It also respects the specified retrieval limit. If there are more messages stored than allowed
@@ -133,4 +196,22 @@ impl InboxManager {
             .await?;
         Ok(())
     }
+
+    /// Removes all messages that have been stored before the provided cutoff.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff`: the insertion time before which all messages get removed
+    ///
+    /// returns the number of removed messages.
+    pub(crate) async fn remove_messages_older_than(
+        &self,
+        cutoff: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error> {
+        let cutoff = cutoff.unix_timestamp();
+        let res = sqlx::query!("DELETE FROM message_store WHERE inserted_at < ?", cutoff)
+            .execute(&self.connection_pool)
+            .await?;
+        Ok(res.rows_affected())
+    }
 }
@@ -1,25 +1,41 @@
 // Copyright 2020 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::config::InboxConfig;
 use crate::node::storage::bandwidth::BandwidthManager;
 use crate::node::storage::error::StorageError;
 use crate::node::storage::inboxes::InboxManager;
-use crate::node::storage::models::{PersistedBandwidth, PersistedSharedKeys, StoredMessage};
+use crate::node::storage::models::{
+    InboxUsage, PersistedBandwidth, PersistedSharedKeys, PersistedWireguardPeer, StoredMessage,
+};
 use crate::node::storage::shared_keys::SharedKeysManager;
+use crate::node::storage::voucher_redemptions::VoucherRedemptionManager;
+use crate::node::storage::wireguard_peers::WireguardPeerManager;
 use async_trait::async_trait;
 use log::{debug, error};
-use nym_credentials_interface::{Base58, BlindedSerialNumber};
+use nym_credentials_interface::{Base58, BlindedSerialNumber, CredentialSpendingData};
 use nym_gateway_requests::registration::handshake::SharedKeys;
 use nym_sphinx::DestinationAddressBytes;
+#[cfg(all(feature = "wireguard", target_os = "linux"))]
+use nym_wireguard::peer_storage::{PeerStorage, PeerStorageError, StoredPeer};
+use nym_wireguard_types::{GatewayClient, PeerPublicKey};
 use sqlx::ConnectOptions;
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 use time::OffsetDateTime;
+use tokio::sync::Mutex;
 
 mod bandwidth;
 pub(crate) mod error;
 mod inboxes;
 mod models;
 mod shared_keys;
+mod voucher_redemptions;
+mod wireguard_peers;
+
+pub use inboxes::InboxMetrics;
+pub(crate) use models::VoucherRedemptionBatch;
 
 #[async_trait]
 pub trait Storage: Send + Sync {
@@ -60,6 +76,7 @@ pub trait Storage: Send + Sync {
     ) -> Result<(), StorageError>;
 
     /// Inserts new message to the storage for an offline client for future retrieval.
+    /// Returns [`StorageError::InboxQuotaExceeded`] if the client has already used up its inbox quota.
     ///
     /// # Arguments
     ///
@@ -93,6 +110,18 @@ pub trait Storage: Send + Sync {
     /// * `ids`: ids of the messages to remove
     async fn remove_messages(&self, ids: Vec<i64>) -> Result<(), StorageError>;
 
+    /// Removes all messages that have been stored before the provided cutoff.
+    /// Returns the number of removed messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `cutoff`: the insertion time before which all messages get removed
+    async fn remove_messages_older_than(&self, cutoff: OffsetDateTime)
+        -> Result<u64, StorageError>;
+
+    /// Metrics on messages that were dropped instead of being delivered to offline clients.
+    fn inbox_metrics(&self) -> &InboxMetrics;
+
     /// Creates a new bandwidth entry for the particular client.
     ///
     /// # Arguments
@@ -169,14 +198,158 @@ pub trait Storage: Send + Sync {
         &self,
         blinded_serial_number: &BlindedSerialNumber,
     ) -> Result<bool, StorageError>;
+
+    /// Inserts the accepted bandwidth voucher into the storage so that its funds could be redeemed
+    /// (alongside other vouchers) at a later point.
+    ///
+    /// # Arguments
+    ///
+    /// * `credential`: the spending data of the voucher
+    async fn insert_pending_voucher_redemption(
+        &self,
+        credential: &CredentialSpendingData,
+    ) -> Result<(), StorageError>;
+
+    /// Retrieves the oldest accepted bandwidth vouchers that haven't yet been assigned to any redemption batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit`: maximum number of vouchers to retrieve
+    async fn get_pending_voucher_redemptions(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<CredentialSpendingData>, StorageError>;
+
+    /// Removes the bandwidth vouchers, whose funds can't be redeemed, from the storage.
+    ///
+    /// # Arguments
+    ///
+    /// * `blinded_serial_numbers`: bs58-encoded blinded serial numbers of the vouchers
+    async fn remove_pending_voucher_redemptions(
+        &self,
+        blinded_serial_numbers: &[String],
+    ) -> Result<(), StorageError>;
+
+    /// Assigns the provided bandwidth vouchers to a new redemption batch and returns its id.
+    ///
+    /// # Arguments
+    ///
+    /// * `blinded_serial_numbers`: bs58-encoded blinded serial numbers of the vouchers included in the batch
+    async fn create_voucher_redemption_batch(
+        &self,
+        blinded_serial_numbers: &[String],
+    ) -> Result<i64, StorageError>;
+
+    /// Retrieves all the voucher redemption batches that haven't yet been completed.
+    async fn get_voucher_redemption_batches(
+        &self,
+    ) -> Result<Vec<VoucherRedemptionBatch>, StorageError>;
+
+    /// Retrieves the bandwidth vouchers included in the specified redemption batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    async fn get_batched_voucher_redemptions(
+        &self,
+        batch_id: i64,
+    ) -> Result<Vec<CredentialSpendingData>, StorageError>;
+
+    /// Sets the hash of the transaction that has submitted the redemption batch to the bandwidth contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    /// * `tx_hash`: hash of the submission transaction
+    async fn set_voucher_redemption_batch_tx_hash(
+        &self,
+        batch_id: i64,
+        tx_hash: &str,
+    ) -> Result<(), StorageError>;
+
+    /// Sets the id of the multisig proposal releasing the funds of the redemption batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    /// * `proposal_id`: id of the multisig proposal
+    async fn set_voucher_redemption_batch_proposal_id(
+        &self,
+        batch_id: i64,
+        proposal_id: u64,
+    ) -> Result<(), StorageError>;
+
+    /// Removes the completed redemption batch, alongside all of its vouchers, from the storage.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    async fn remove_voucher_redemption_batch(&self, batch_id: i64) -> Result<(), StorageError>;
+
+    /// Inserts provided wireguard peer into the storage.
+    /// If the peer previously existed, its data is updated, but its available bandwidth is retained.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer`: the registered wireguard peer
+    /// * `available_bandwidth`: bandwidth allowance of a new peer
+    async fn insert_wireguard_peer(
+        &self,
+        peer: &GatewayClient,
+        available_bandwidth: i64,
+    ) -> Result<(), StorageError>;
+
+    /// Retrieves all the stored wireguard peers.
+    async fn get_all_wireguard_peers(&self) -> Result<Vec<PersistedWireguardPeer>, StorageError>;
+
+    /// Removes the wireguard peer from the storage.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key`: public key of the peer
+    async fn remove_wireguard_peer(&self, public_key: &PeerPublicKey) -> Result<(), StorageError>;
+
+    /// Sets available bandwidth of the particular wireguard peer to the provided amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key`: public key of the peer
+    /// * `amount`: the updated peer bandwidth amount.
+    async fn set_wireguard_peer_bandwidth(
+        &self,
+        public_key: &PeerPublicKey,
+        amount: i64,
+    ) -> Result<(), StorageError>;
+}
+
+fn ensure_within_inbox_quota(
+    inbox_config: &InboxConfig,
+    usage: InboxUsage,
+    client_address_bs58: &str,
+    message: &[u8],
+) -> Result<(), StorageError> {
+    if usage.messages >= inbox_config.max_messages_per_client
+        || usage.bytes + message.len() as i64 > inbox_config.max_bytes_per_client
+    {
+        return Err(StorageError::InboxQuotaExceeded {
+            client: client_address_bs58.to_string(),
+            stored_messages: usage.messages,
+            stored_bytes: usage.bytes,
+        });
+    }
+    Ok(())
 }
 
 // note that clone here is fine as upon cloning the same underlying pool will be used
 #[derive(Clone)]
 pub struct PersistentStorage {
+    inbox_config: InboxConfig,
+    inbox_metrics: Arc<InboxMetrics>,
     shared_key_manager: SharedKeysManager,
     inbox_manager: InboxManager,
     bandwidth_manager: BandwidthManager,
+    voucher_redemption_manager: VoucherRedemptionManager,
+    wireguard_peer_manager: WireguardPeerManager,
 }
 
 impl PersistentStorage {
@@ -186,9 +359,11 @@ impl PersistentStorage {
     ///
     /// * `database_path`: path to the database.
     /// * `message_retrieval_limit`: maximum number of stored client messages that can be retrieved at once.
+    /// * `inbox_config`: limits imposed on messages stored for offline clients.
     pub async fn init<P: AsRef<Path> + Send>(
         database_path: P,
         message_retrieval_limit: i64,
+        inbox_config: InboxConfig,
     ) -> Result<Self, StorageError> {
         debug!(
             "Attempting to connect to database {:?}",
@@ -220,9 +395,13 @@ impl PersistentStorage {
 
         // the cloning here are cheap as connection pool is stored behind an Arc
         Ok(PersistentStorage {
+            inbox_config,
+            inbox_metrics: Default::default(),
             shared_key_manager: SharedKeysManager::new(connection_pool.clone()),
             inbox_manager: InboxManager::new(connection_pool.clone(), message_retrieval_limit),
-            bandwidth_manager: BandwidthManager::new(connection_pool),
+            bandwidth_manager: BandwidthManager::new(connection_pool.clone()),
+            voucher_redemption_manager: VoucherRedemptionManager::new(connection_pool.clone()),
+            wireguard_peer_manager: WireguardPeerManager::new(connection_pool),
         })
     }
 }
@@ -271,8 +450,18 @@ impl Storage for PersistentStorage {
         client_address: DestinationAddressBytes,
         message: Vec<u8>,
     ) -> Result<(), StorageError> {
+        let client_address_bs58 = client_address.as_base58_string();
+
+        // note: this is not atomic with the insertion, so under concurrent writes the quota
+        // might get exceeded by few messages, which is fine
+        let usage = self
+            .inbox_manager
+            .get_inbox_usage(&client_address_bs58)
+            .await?;
+        ensure_within_inbox_quota(&self.inbox_config, usage, &client_address_bs58, &message)?;
+
         self.inbox_manager
-            .insert_message(&client_address.as_base58_string(), message)
+            .insert_message(&client_address_bs58, message)
             .await?;
         Ok(())
     }
@@ -296,6 +485,22 @@ impl Storage for PersistentStorage {
         Ok(())
     }
 
+    async fn remove_messages_older_than(
+        &self,
+        cutoff: OffsetDateTime,
+    ) -> Result<u64, StorageError> {
+        let removed = self
+            .inbox_manager
+            .remove_messages_older_than(cutoff)
+            .await?;
+        self.inbox_metrics.record_expired(removed);
+        Ok(removed)
+    }
+
+    fn inbox_metrics(&self) -> &InboxMetrics {
+        &self.inbox_metrics
+    }
+
     async fn create_bandwidth_entry(
         &self,
         client_address: DestinationAddressBytes,
@@ -375,11 +580,193 @@ impl Storage for PersistentStorage {
 
         Ok(cred.is_some())
     }
+
+    async fn insert_pending_voucher_redemption(
+        &self,
+        credential: &CredentialSpendingData,
+    ) -> Result<(), StorageError> {
+        let blinded_serial_number = credential
+            .verify_credential_request
+            .blinded_serial_number_bs58();
+        let credential_data = serde_json::to_string(credential).map_err(|source| {
+            StorageError::CredentialDataSerializationFailure {
+                blinded_serial_number: blinded_serial_number.clone(),
+                source,
+            }
+        })?;
+
+        self.voucher_redemption_manager
+            .insert_pending_redemption(
+                &blinded_serial_number,
+                credential.epoch_id as i64,
+                &credential_data,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_pending_voucher_redemptions(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<CredentialSpendingData>, StorageError> {
+        self.voucher_redemption_manager
+            .get_unbatched_redemptions(limit)
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+
+    async fn remove_pending_voucher_redemptions(
+        &self,
+        blinded_serial_numbers: &[String],
+    ) -> Result<(), StorageError> {
+        self.voucher_redemption_manager
+            .remove_pending_redemptions(blinded_serial_numbers)
+            .await?;
+        Ok(())
+    }
+
+    async fn create_voucher_redemption_batch(
+        &self,
+        blinded_serial_numbers: &[String],
+    ) -> Result<i64, StorageError> {
+        Ok(self
+            .voucher_redemption_manager
+            .create_batch(blinded_serial_numbers)
+            .await?)
+    }
+
+    async fn get_voucher_redemption_batches(
+        &self,
+    ) -> Result<Vec<VoucherRedemptionBatch>, StorageError> {
+        Ok(self.voucher_redemption_manager.get_batches().await?)
+    }
+
+    async fn get_batched_voucher_redemptions(
+        &self,
+        batch_id: i64,
+    ) -> Result<Vec<CredentialSpendingData>, StorageError> {
+        self.voucher_redemption_manager
+            .get_batched_redemptions(batch_id)
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+
+    async fn set_voucher_redemption_batch_tx_hash(
+        &self,
+        batch_id: i64,
+        tx_hash: &str,
+    ) -> Result<(), StorageError> {
+        self.voucher_redemption_manager
+            .set_batch_tx_hash(batch_id, tx_hash)
+            .await?;
+        Ok(())
+    }
+
+    async fn set_voucher_redemption_batch_proposal_id(
+        &self,
+        batch_id: i64,
+        proposal_id: u64,
+    ) -> Result<(), StorageError> {
+        self.voucher_redemption_manager
+            .set_batch_proposal_id(batch_id, proposal_id as i64)
+            .await?;
+        Ok(())
+    }
+
+    async fn remove_voucher_redemption_batch(&self, batch_id: i64) -> Result<(), StorageError> {
+        self.voucher_redemption_manager
+            .remove_batch(batch_id)
+            .await?;
+        Ok(())
+    }
+
+    async fn insert_wireguard_peer(
+        &self,
+        peer: &GatewayClient,
+        available_bandwidth: i64,
+    ) -> Result<(), StorageError> {
+        self.wireguard_peer_manager
+            .insert_peer(PersistedWireguardPeer::new(peer, available_bandwidth))
+            .await?;
+        Ok(())
+    }
+
+    async fn get_all_wireguard_peers(&self) -> Result<Vec<PersistedWireguardPeer>, StorageError> {
+        Ok(self.wireguard_peer_manager.get_all_peers().await?)
+    }
+
+    async fn remove_wireguard_peer(&self, public_key: &PeerPublicKey) -> Result<(), StorageError> {
+        self.wireguard_peer_manager
+            .remove_peer(&public_key.to_string())
+            .await?;
+        Ok(())
+    }
+
+    async fn set_wireguard_peer_bandwidth(
+        &self,
+        public_key: &PeerPublicKey,
+        amount: i64,
+    ) -> Result<(), StorageError> {
+        self.wireguard_peer_manager
+            .set_available_bandwidth(&public_key.to_string(), amount)
+            .await?;
+        Ok(())
+    }
 }
 
 /// In-memory implementation of `Storage`. The intention is primarily in testing environments.
-#[derive(Clone)]
-pub struct InMemStorage;
+// note: the `Storage` trait is only implemented for the tests, so outside them nothing reads the state
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Clone, Default)]
+pub struct InMemStorage {
+    inbox_config: InboxConfig,
+    inbox_metrics: Arc<InboxMetrics>,
+    inner: Arc<Mutex<InMemStorageInner>>,
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+#[derive(Default)]
+struct InMemStorageInner {
+    next_message_id: i64,
+    messages: Vec<InMemMessage>,
+    next_voucher_batch_id: i64,
+    pending_vouchers: Vec<InMemPendingVoucher>,
+    voucher_batches: Vec<VoucherRedemptionBatch>,
+    wireguard_peers: HashMap<String, PersistedWireguardPeer>,
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+struct InMemPendingVoucher {
+    blinded_serial_number_bs58: String,
+    credential: CredentialSpendingData,
+    batch_id: Option<i64>,
+}
+
+#[cfg_attr(not(test), allow(dead_code))]
+struct InMemMessage {
+    id: i64,
+    client_address_bs58: String,
+    content: Vec<u8>,
+    inserted_at: OffsetDateTime,
+}
+
+impl InMemStorage {
+    /// Creates new empty instance of the `InMemStorage`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inbox_config`: limits imposed on messages stored for offline clients.
+    pub fn new(inbox_config: InboxConfig) -> Self {
+        InMemStorage {
+            inbox_config,
+            ..Default::default()
+        }
+    }
+}
 
 //#[cfg(test)]
 //impl InMemStorage {
@@ -416,22 +803,84 @@ impl Storage for InMemStorage {
 
     async fn store_message(
         &self,
-        _client_address: DestinationAddressBytes,
-        _message: Vec<u8>,
+        client_address: DestinationAddressBytes,
+        message: Vec<u8>,
     ) -> Result<(), StorageError> {
-        todo!()
+        let client_address_bs58 = client_address.as_base58_string();
+        let mut guard = self.inner.lock().await;
+
+        let usage = guard
+            .messages
+            .iter()
+            .filter(|stored| stored.client_address_bs58 == client_address_bs58)
+            .fold(InboxUsage::default(), |usage, stored| InboxUsage {
+                messages: usage.messages + 1,
+                bytes: usage.bytes + stored.content.len() as i64,
+            });
+        ensure_within_inbox_quota(&self.inbox_config, usage, &client_address_bs58, &message)?;
+
+        guard.next_message_id += 1;
+        let id = guard.next_message_id;
+        guard.messages.push(InMemMessage {
+            id,
+            client_address_bs58,
+            content: message,
+            inserted_at: OffsetDateTime::now_utc(),
+        });
+        Ok(())
     }
 
     async fn retrieve_messages(
         &self,
-        _client_address: DestinationAddressBytes,
-        _start_after: Option<i64>,
+        client_address: DestinationAddressBytes,
+        start_after: Option<i64>,
     ) -> Result<(Vec<StoredMessage>, Option<i64>), StorageError> {
-        todo!()
+        let client_address_bs58 = client_address.as_base58_string();
+        let start_after = start_after.unwrap_or_default();
+
+        // there's no retrieval limit, so everything is always returned at once
+        let messages = self
+            .inner
+            .lock()
+            .await
+            .messages
+            .iter()
+            .filter(|stored| {
+                stored.client_address_bs58 == client_address_bs58 && stored.id > start_after
+            })
+            .map(|stored| StoredMessage {
+                id: stored.id,
+                client_address_bs58: stored.client_address_bs58.clone(),
+                content: stored.content.clone(),
+            })
+            .collect();
+        Ok((messages, None))
     }
 
-    async fn remove_messages(&self, _ids: Vec<i64>) -> Result<(), StorageError> {
-        todo!()
+    async fn remove_messages(&self, ids: Vec<i64>) -> Result<(), StorageError> {
+        self.inner
+            .lock()
+            .await
+            .messages
+            .retain(|stored| !ids.contains(&stored.id));
+        Ok(())
+    }
+
+    async fn remove_messages_older_than(
+        &self,
+        cutoff: OffsetDateTime,
+    ) -> Result<u64, StorageError> {
+        let mut guard = self.inner.lock().await;
+        let before = guard.messages.len();
+        guard.messages.retain(|stored| stored.inserted_at >= cutoff);
+        let removed = (before - guard.messages.len()) as u64;
+
+        self.inbox_metrics.record_expired(removed);
+        Ok(removed)
+    }
+
+    fn inbox_metrics(&self) -> &InboxMetrics {
+        &self.inbox_metrics
     }
 
     async fn create_bandwidth_entry(
@@ -486,4 +935,350 @@ impl Storage for InMemStorage {
     ) -> Result<bool, StorageError> {
         todo!()
     }
+
+    async fn insert_pending_voucher_redemption(
+        &self,
+        credential: &CredentialSpendingData,
+    ) -> Result<(), StorageError> {
+        self.inner
+            .lock()
+            .await
+            .pending_vouchers
+            .push(InMemPendingVoucher {
+                blinded_serial_number_bs58: credential
+                    .verify_credential_request
+                    .blinded_serial_number_bs58(),
+                credential: credential.clone(),
+                batch_id: None,
+            });
+        Ok(())
+    }
+
+    async fn get_pending_voucher_redemptions(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<CredentialSpendingData>, StorageError> {
+        let guard = self.inner.lock().await;
+        let mut pending = guard
+            .pending_vouchers
+            .iter()
+            .filter(|voucher| voucher.batch_id.is_none())
+            .map(|voucher| voucher.credential.clone())
+            .collect::<Vec<_>>();
+
+        // the sort is stable, so within the same epoch the vouchers are still ordered by their insertion
+        pending.sort_by_key(|credential| credential.epoch_id);
+        pending.truncate(limit as usize);
+        Ok(pending)
+    }
+
+    async fn remove_pending_voucher_redemptions(
+        &self,
+        blinded_serial_numbers: &[String],
+    ) -> Result<(), StorageError> {
+        self.inner.lock().await.pending_vouchers.retain(|voucher| {
+            !blinded_serial_numbers.contains(&voucher.blinded_serial_number_bs58)
+        });
+        Ok(())
+    }
+
+    async fn create_voucher_redemption_batch(
+        &self,
+        blinded_serial_numbers: &[String],
+    ) -> Result<i64, StorageError> {
+        let mut guard = self.inner.lock().await;
+        guard.next_voucher_batch_id += 1;
+        let batch_id = guard.next_voucher_batch_id;
+
+        for voucher in guard.pending_vouchers.iter_mut() {
+            if blinded_serial_numbers.contains(&voucher.blinded_serial_number_bs58) {
+                voucher.batch_id = Some(batch_id)
+            }
+        }
+        guard.voucher_batches.push(VoucherRedemptionBatch {
+            id: batch_id,
+            tx_hash: None,
+            proposal_id: None,
+        });
+        Ok(batch_id)
+    }
+
+    async fn get_voucher_redemption_batches(
+        &self,
+    ) -> Result<Vec<VoucherRedemptionBatch>, StorageError> {
+        Ok(self.inner.lock().await.voucher_batches.clone())
+    }
+
+    async fn get_batched_voucher_redemptions(
+        &self,
+        batch_id: i64,
+    ) -> Result<Vec<CredentialSpendingData>, StorageError> {
+        Ok(self
+            .inner
+            .lock()
+            .await
+            .pending_vouchers
+            .iter()
+            .filter(|voucher| voucher.batch_id == Some(batch_id))
+            .map(|voucher| voucher.credential.clone())
+            .collect())
+    }
+
+    async fn set_voucher_redemption_batch_tx_hash(
+        &self,
+        batch_id: i64,
+        tx_hash: &str,
+    ) -> Result<(), StorageError> {
+        let mut guard = self.inner.lock().await;
+        if let Some(batch) = guard.voucher_batches.iter_mut().find(|b| b.id == batch_id) {
+            batch.tx_hash = Some(tx_hash.to_string())
+        }
+        Ok(())
+    }
+
+    async fn set_voucher_redemption_batch_proposal_id(
+        &self,
+        batch_id: i64,
+        proposal_id: u64,
+    ) -> Result<(), StorageError> {
+        let mut guard = self.inner.lock().await;
+        if let Some(batch) = guard.voucher_batches.iter_mut().find(|b| b.id == batch_id) {
+            batch.proposal_id = Some(proposal_id as i64)
+        }
+        Ok(())
+    }
+
+    async fn remove_voucher_redemption_batch(&self, batch_id: i64) -> Result<(), StorageError> {
+        let mut guard = self.inner.lock().await;
+        guard
+            .pending_vouchers
+            .retain(|voucher| voucher.batch_id != Some(batch_id));
+        guard.voucher_batches.retain(|batch| batch.id != batch_id);
+        Ok(())
+    }
+
+    async fn insert_wireguard_peer(
+        &self,
+        peer: &GatewayClient,
+        available_bandwidth: i64,
+    ) -> Result<(), StorageError> {
+        let mut new = PersistedWireguardPeer::new(peer, available_bandwidth);
+        let mut guard = self.inner.lock().await;
+        if let Some(existing) = guard.wireguard_peers.get(&new.public_key) {
+            new.available_bandwidth = existing.available_bandwidth;
+        }
+        guard.wireguard_peers.insert(new.public_key.clone(), new);
+        Ok(())
+    }
+
+    async fn get_all_wireguard_peers(&self) -> Result<Vec<PersistedWireguardPeer>, StorageError> {
+        Ok(self
+            .inner
+            .lock()
+            .await
+            .wireguard_peers
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    async fn remove_wireguard_peer(&self, public_key: &PeerPublicKey) -> Result<(), StorageError> {
+        self.inner
+            .lock()
+            .await
+            .wireguard_peers
+            .remove(&public_key.to_string());
+        Ok(())
+    }
+
+    async fn set_wireguard_peer_bandwidth(
+        &self,
+        public_key: &PeerPublicKey,
+        amount: i64,
+    ) -> Result<(), StorageError> {
+        if let Some(peer) = self
+            .inner
+            .lock()
+            .await
+            .wireguard_peers
+            .get_mut(&public_key.to_string())
+        {
+            peer.available_bandwidth = amount;
+        }
+        Ok(())
+    }
+}
+
+/// Exposes the wireguard peers stored by the gateway to the wireguard peer controller.
+#[cfg(all(feature = "wireguard", target_os = "linux"))]
+pub(crate) struct WireguardPeerStorage<St>(St);
+
+#[cfg(all(feature = "wireguard", target_os = "linux"))]
+impl<St> WireguardPeerStorage<St> {
+    pub(crate) fn new(storage: St) -> Self {
+        WireguardPeerStorage(storage)
+    }
+}
+
+#[cfg(all(feature = "wireguard", target_os = "linux"))]
+#[async_trait]
+impl<St: Storage> PeerStorage for WireguardPeerStorage<St> {
+    async fn load_peers(&self) -> Result<Vec<StoredPeer>, PeerStorageError> {
+        let mut peers = Vec::new();
+        for peer in self.0.get_all_wireguard_peers().await? {
+            let available_bandwidth = peer.available_bandwidth;
+            peers.push(StoredPeer {
+                client: peer.try_into()?,
+                available_bandwidth,
+            })
+        }
+        Ok(peers)
+    }
+
+    async fn insert_peer(&self, peer: &StoredPeer) -> Result<(), PeerStorageError> {
+        Ok(self
+            .0
+            .insert_wireguard_peer(&peer.client, peer.available_bandwidth)
+            .await?)
+    }
+
+    async fn remove_peer(&self, pub_key: &PeerPublicKey) -> Result<(), PeerStorageError> {
+        Ok(self.0.remove_wireguard_peer(pub_key).await?)
+    }
+
+    async fn set_available_bandwidth(
+        &self,
+        pub_key: &PeerPublicKey,
+        amount: i64,
+    ) -> Result<(), PeerStorageError> {
+        Ok(self.0.set_wireguard_peer_bandwidth(pub_key, amount).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_wireguard_types::ClientMac;
+
+    fn wireguard_peer(key: u8) -> GatewayClient {
+        GatewayClient {
+            pub_key: PeerPublicKey::new([key; 32].into()),
+            private_ip: format!("10.1.0.{key}").parse().unwrap(),
+            mac: ClientMac::new(vec![key; 32]),
+        }
+    }
+
+    fn client_address(byte: u8) -> DestinationAddressBytes {
+        DestinationAddressBytes::from_bytes([byte; 32])
+    }
+
+    fn inbox_config() -> InboxConfig {
+        InboxConfig {
+            max_messages_per_client: 3,
+            max_bytes_per_client: 100,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn inbox_message_quota_is_enforced_per_client() {
+        let storage = InMemStorage::new(inbox_config());
+        let client = client_address(1);
+
+        for _ in 0..3 {
+            storage.store_message(client, vec![0; 10]).await.unwrap();
+        }
+        let err = storage
+            .store_message(client, vec![0; 10])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            StorageError::InboxQuotaExceeded {
+                stored_messages: 3,
+                stored_bytes: 30,
+                ..
+            }
+        ));
+
+        // other clients are not affected
+        storage
+            .store_message(client_address(2), vec![0; 10])
+            .await
+            .unwrap();
+
+        // retrieving (and removing) the messages frees up the quota
+        let (messages, _) = storage.retrieve_messages(client, None).await.unwrap();
+        assert_eq!(messages.len(), 3);
+        storage
+            .remove_messages(messages.iter().map(|message| message.id).collect())
+            .await
+            .unwrap();
+        storage.store_message(client, vec![0; 10]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn inbox_byte_quota_is_enforced() {
+        let storage = InMemStorage::new(inbox_config());
+        let client = client_address(1);
+
+        storage.store_message(client, vec![0; 60]).await.unwrap();
+        assert!(matches!(
+            storage.store_message(client, vec![0; 41]).await,
+            Err(StorageError::InboxQuotaExceeded { .. })
+        ));
+        // filling the quota exactly is fine
+        storage.store_message(client, vec![0; 40]).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn pruning_removes_only_expired_messages() {
+        let storage = InMemStorage::new(inbox_config());
+        let client = client_address(1);
+
+        storage.store_message(client, vec![1]).await.unwrap();
+        storage.store_message(client, vec![2]).await.unwrap();
+        let cutoff = OffsetDateTime::now_utc() + time::Duration::nanoseconds(1);
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        storage.store_message(client, vec![3]).await.unwrap();
+
+        assert_eq!(storage.remove_messages_older_than(cutoff).await.unwrap(), 2);
+        assert_eq!(storage.inbox_metrics().expired_messages(), 2);
+
+        let (messages, _) = storage.retrieve_messages(client, None).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].content, vec![3]);
+
+        assert_eq!(storage.remove_messages_older_than(cutoff).await.unwrap(), 0);
+        assert_eq!(storage.inbox_metrics().expired_messages(), 2);
+    }
+
+    #[tokio::test]
+    async fn in_mem_wireguard_peers_retain_bandwidth_on_reinsertion() {
+        let storage = InMemStorage::default();
+        let peer = wireguard_peer(1);
+
+        storage.insert_wireguard_peer(&peer, 1000).await.unwrap();
+        storage
+            .set_wireguard_peer_bandwidth(&peer.pub_key, 400)
+            .await
+            .unwrap();
+        storage.insert_wireguard_peer(&peer, 1000).await.unwrap();
+        storage
+            .insert_wireguard_peer(&wireguard_peer(2), 1000)
+            .await
+            .unwrap();
+
+        let mut peers = storage.get_all_wireguard_peers().await.unwrap();
+        peers.sort_by_key(|peer| peer.available_bandwidth);
+        assert_eq!(peers.len(), 2);
+        assert_eq!(peers[0].public_key, peer.pub_key.to_string());
+        assert_eq!(peers[0].available_bandwidth, 400);
+        assert_eq!(peers[1].available_bandwidth, 1000);
+
+        storage.remove_wireguard_peer(&peer.pub_key).await.unwrap();
+        let peers = storage.get_all_wireguard_peers().await.unwrap();
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].available_bandwidth, 1000);
+    }
 }
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::node::client_handling::websocket::connection_handler::AvailableBandwidth;
+use crate::node::storage::error::StorageError;
+use nym_credentials_interface::CredentialSpendingData;
+use nym_wireguard_types::GatewayClient;
 use sqlx::FromRow;
+use std::net::AddrParseError;
 use time::OffsetDateTime;
 
 pub struct PersistedSharedKeys {
@@ -17,6 +21,12 @@ pub struct StoredMessage {
     pub(crate) content: Vec<u8>,
 }
 
+#[derive(Debug, Clone, Copy, Default, FromRow)]
+pub struct InboxUsage {
+    pub(crate) messages: i64,
+    pub(crate) bytes: i64,
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct PersistedBandwidth {
     #[allow(dead_code)]
@@ -52,3 +62,83 @@ pub struct SpentCredential {
     #[allow(dead_code)]
     pub(crate) client_address_bs58: String,
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PersistedWireguardPeer {
+    pub(crate) public_key: String,
+    pub(crate) private_ip: String,
+    pub(crate) mac: String,
+    pub(crate) available_bandwidth: i64,
+}
+
+impl PersistedWireguardPeer {
+    pub(crate) fn new(client: &GatewayClient, available_bandwidth: i64) -> Self {
+        PersistedWireguardPeer {
+            public_key: client.pub_key.to_string(),
+            private_ip: client.private_ip.to_string(),
+            mac: client.mac.to_string(),
+            available_bandwidth,
+        }
+    }
+}
+
+impl TryFrom<PersistedWireguardPeer> for GatewayClient {
+    type Error = StorageError;
+
+    fn try_from(value: PersistedWireguardPeer) -> Result<Self, Self::Error> {
+        let malformed = |reason: String| StorageError::MalformedWireguardPeer {
+            public_key: value.public_key.clone(),
+            reason,
+        };
+
+        Ok(GatewayClient {
+            pub_key: value
+                .public_key
+                .parse()
+                .map_err(|err: nym_wireguard_types::Error| malformed(err.to_string()))?,
+            private_ip: value
+                .private_ip
+                .parse()
+                .map_err(|err: AddrParseError| malformed(err.to_string()))?,
+            mac: value
+                .mac
+                .parse()
+                .map_err(|err: nym_wireguard_types::Error| malformed(err.to_string()))?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct PendingVoucherRedemption {
+    pub(crate) blinded_serial_number_bs58: String,
+    pub(crate) epoch_id: i64,
+    pub(crate) credential_data: String,
+}
+
+impl TryFrom<PendingVoucherRedemption> for CredentialSpendingData {
+    type Error = StorageError;
+
+    fn try_from(value: PendingVoucherRedemption) -> Result<Self, Self::Error> {
+        let credential: CredentialSpendingData = serde_json::from_str(&value.credential_data)
+            .map_err(|source| StorageError::CredentialDataSerializationFailure {
+                blinded_serial_number: value.blinded_serial_number_bs58.clone(),
+                source,
+            })?;
+
+        if credential.epoch_id as i64 != value.epoch_id {
+            return Err(StorageError::InconsistentCredentialEpoch {
+                blinded_serial_number: value.blinded_serial_number_bs58,
+                stored_epoch: value.epoch_id,
+                credential_epoch: credential.epoch_id,
+            });
+        }
+        Ok(credential)
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct VoucherRedemptionBatch {
+    pub(crate) id: i64,
+    pub(crate) tx_hash: Option<String>,
+    pub(crate) proposal_id: Option<i64>,
+}
@@ -0,0 +1,231 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node::storage::models::{PendingVoucherRedemption, VoucherRedemptionBatch};
+use time::OffsetDateTime;
+
+#[derive(Clone)]
+pub(crate) struct VoucherRedemptionManager {
+    connection_pool: sqlx::SqlitePool,
+}
+
+impl VoucherRedemptionManager {
+    /// Creates new instance of the `VoucherRedemptionManager` with the provided sqlite connection pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_pool`: database connection pool to use.
+    pub(crate) fn new(connection_pool: sqlx::SqlitePool) -> Self {
+        VoucherRedemptionManager { connection_pool }
+    }
+
+    /// Inserts the accepted voucher into the database so that its funds could be redeemed later.
+    ///
+    /// # Arguments
+    ///
+    /// * `blinded_serial_number_bs58`: the unique blinded serial number embedded in the credential
+    /// * `epoch_id`: the (DKG) epoch id under which the credential has been issued
+    /// * `credential_data`: json-encoded spending data of the credential
+    pub(crate) async fn insert_pending_redemption(
+        &self,
+        blinded_serial_number_bs58: &str,
+        epoch_id: i64,
+        credential_data: &str,
+    ) -> Result<(), sqlx::Error> {
+        let inserted_at = OffsetDateTime::now_utc().unix_timestamp();
+        sqlx::query!(
+            r#"
+                INSERT INTO pending_voucher_redemption(blinded_serial_number_bs58, epoch_id, credential_data, inserted_at)
+                VALUES (?, ?, ?, ?)
+            "#,
+            blinded_serial_number_bs58,
+            epoch_id,
+            credential_data,
+            inserted_at
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Retrieves the oldest vouchers that haven't yet been assigned to any redemption batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit`: maximum number of vouchers to retrieve.
+    pub(crate) async fn get_unbatched_redemptions(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<PendingVoucherRedemption>, sqlx::Error> {
+        sqlx::query_as!(
+            PendingVoucherRedemption,
+            r#"
+                SELECT
+                    blinded_serial_number_bs58 as "blinded_serial_number_bs58!",
+                    epoch_id as "epoch_id!",
+                    credential_data as "credential_data!"
+                FROM pending_voucher_redemption
+                WHERE batch_id IS NULL
+                ORDER BY epoch_id ASC, inserted_at ASC
+                LIMIT ?
+            "#,
+            limit
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    /// Removes the vouchers with the provided serial numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `blinded_serial_numbers_bs58`: the blinded serial numbers of the vouchers to remove
+    pub(crate) async fn remove_pending_redemptions(
+        &self,
+        blinded_serial_numbers_bs58: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.connection_pool.begin().await?;
+        for serial_number in blinded_serial_numbers_bs58 {
+            sqlx::query!(
+                "DELETE FROM pending_voucher_redemption WHERE blinded_serial_number_bs58 = ?",
+                serial_number
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    /// Creates a new redemption batch out of the vouchers with the provided serial numbers.
+    ///
+    /// # Arguments
+    ///
+    /// * `blinded_serial_numbers_bs58`: the blinded serial numbers of the vouchers included in the batch
+    pub(crate) async fn create_batch(
+        &self,
+        blinded_serial_numbers_bs58: &[String],
+    ) -> Result<i64, sqlx::Error> {
+        let created_at = OffsetDateTime::now_utc().unix_timestamp();
+        let mut tx = self.connection_pool.begin().await?;
+        let batch_id = sqlx::query!(
+            "INSERT INTO voucher_redemption_batch(created_at) VALUES (?)",
+            created_at
+        )
+        .execute(&mut tx)
+        .await?
+        .last_insert_rowid();
+
+        for serial_number in blinded_serial_numbers_bs58 {
+            sqlx::query!(
+                "UPDATE pending_voucher_redemption SET batch_id = ? WHERE blinded_serial_number_bs58 = ?",
+                batch_id,
+                serial_number
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(batch_id)
+    }
+
+    /// Retrieves all the redemption batches that haven't yet been completed.
+    pub(crate) async fn get_batches(&self) -> Result<Vec<VoucherRedemptionBatch>, sqlx::Error> {
+        sqlx::query_as!(
+            VoucherRedemptionBatch,
+            r#"
+                SELECT id as "id!", tx_hash, proposal_id
+                FROM voucher_redemption_batch
+                ORDER BY id ASC
+            "#
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    /// Retrieves the vouchers included in the specified redemption batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    pub(crate) async fn get_batched_redemptions(
+        &self,
+        batch_id: i64,
+    ) -> Result<Vec<PendingVoucherRedemption>, sqlx::Error> {
+        sqlx::query_as!(
+            PendingVoucherRedemption,
+            r#"
+                SELECT blinded_serial_number_bs58, epoch_id, credential_data
+                FROM pending_voucher_redemption
+                WHERE batch_id = ?
+                ORDER BY inserted_at ASC
+            "#,
+            batch_id
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    /// Sets the hash of the transaction that has submitted the redemption batch to the chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    /// * `tx_hash`: hash of the submission transaction
+    pub(crate) async fn set_batch_tx_hash(
+        &self,
+        batch_id: i64,
+        tx_hash: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE voucher_redemption_batch SET tx_hash = ? WHERE id = ?",
+            tx_hash,
+            batch_id
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Sets the id of the multisig proposal releasing the funds of the redemption batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    /// * `proposal_id`: id of the multisig proposal
+    pub(crate) async fn set_batch_proposal_id(
+        &self,
+        batch_id: i64,
+        proposal_id: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE voucher_redemption_batch SET proposal_id = ? WHERE id = ?",
+            proposal_id,
+            batch_id
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Removes the completed redemption batch alongside all of its vouchers.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_id`: id of the redemption batch
+    pub(crate) async fn remove_batch(&self, batch_id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.connection_pool.begin().await?;
+        sqlx::query!(
+            "DELETE FROM pending_voucher_redemption WHERE batch_id = ?",
+            batch_id
+        )
+        .execute(&mut tx)
+        .await?;
+        sqlx::query!(
+            "DELETE FROM voucher_redemption_batch WHERE id = ?",
+            batch_id
+        )
+        .execute(&mut tx)
+        .await?;
+        tx.commit().await
+    }
+}
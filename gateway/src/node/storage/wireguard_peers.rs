@@ -0,0 +1,89 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node::storage::models::PersistedWireguardPeer;
+
+#[derive(Clone)]
+pub(crate) struct WireguardPeerManager {
+    connection_pool: sqlx::SqlitePool,
+}
+
+impl WireguardPeerManager {
+    /// Creates new instance of the `WireguardPeerManager` with the provided sqlite connection pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_pool`: database connection pool to use.
+    pub(crate) fn new(connection_pool: sqlx::SqlitePool) -> Self {
+        WireguardPeerManager { connection_pool }
+    }
+
+    /// Inserts provided wireguard peer into the database.
+    /// If the peer previously existed, its data is updated, but its available bandwidth is retained.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer`: peer information to store.
+    pub(crate) async fn insert_peer(
+        &self,
+        peer: PersistedWireguardPeer,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO wireguard_peer(public_key, private_ip, mac, available_bandwidth)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT(public_key) DO UPDATE SET private_ip = excluded.private_ip, mac = excluded.mac
+            "#,
+            peer.public_key,
+            peer.private_ip,
+            peer.mac,
+            peer.available_bandwidth,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Retrieves all the stored wireguard peers.
+    pub(crate) async fn get_all_peers(&self) -> Result<Vec<PersistedWireguardPeer>, sqlx::Error> {
+        sqlx::query_as!(PersistedWireguardPeer, "SELECT * FROM wireguard_peer")
+            .fetch_all(&self.connection_pool)
+            .await
+    }
+
+    /// Removes from the database the wireguard peer with the provided public key.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key`: base64-encoded x25519 public key of the peer.
+    pub(crate) async fn remove_peer(&self, public_key: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM wireguard_peer WHERE public_key = ?",
+            public_key
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Sets available bandwidth of the particular peer to the provided amount.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key`: base64-encoded x25519 public key of the peer.
+    /// * `amount`: the updated peer bandwidth amount.
+    pub(crate) async fn set_available_bandwidth(
+        &self,
+        public_key: &str,
+        amount: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE wireguard_peer SET available_bandwidth = ? WHERE public_key = ?",
+            amount,
+            public_key
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+}
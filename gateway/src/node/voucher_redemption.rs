@@ -0,0 +1,318 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::VoucherRedemptionConfig;
+use crate::node::client_handling::websocket::connection_handler::authenticated::RequestHandlingError;
+use crate::node::client_handling::websocket::connection_handler::coconut::CoconutVerifier;
+use crate::node::storage::{Storage, VoucherRedemptionBatch};
+use log::{debug, error, info, trace, warn};
+use nym_coconut_bandwidth_contract_common::spend_credential::MAX_SPEND_CREDENTIALS_BATCH_SIZE;
+use nym_credentials_interface::CredentialSpendingData;
+use nym_task::TaskClient;
+use nym_validator_client::nym_api::EpochId;
+use nym_validator_client::nyxd::Hash;
+use std::collections::BTreeMap;
+use std::mem;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::time::interval;
+
+/// Periodically redeems the funds of the accepted bandwidth vouchers in batches,
+/// so that a single multisig proposal would cover many of them.
+pub(crate) struct VoucherRedeemer<St> {
+    coconut_verifier: Arc<CoconutVerifier>,
+    storage: St,
+    config: VoucherRedemptionConfig,
+    shutdown: TaskClient,
+}
+
+impl<St> VoucherRedeemer<St>
+where
+    St: Storage + 'static,
+{
+    pub(crate) fn new(
+        coconut_verifier: Arc<CoconutVerifier>,
+        storage: St,
+        config: VoucherRedemptionConfig,
+        shutdown: TaskClient,
+    ) -> Self {
+        VoucherRedeemer {
+            coconut_verifier,
+            storage,
+            config,
+            shutdown,
+        }
+    }
+
+    fn batch_size(&self) -> usize {
+        self.config
+            .maximum_batch_size
+            .clamp(1, MAX_SPEND_CREDENTIALS_BATCH_SIZE)
+    }
+
+    /// Drops the vouchers that have already been submitted to the bandwidth contract (for example by another gateway),
+    /// as otherwise the whole batch would have been rejected.
+    async fn drop_unredeemable(
+        &self,
+        credentials: Vec<CredentialSpendingData>,
+    ) -> Result<Vec<CredentialSpendingData>, RequestHandlingError> {
+        let mut redeemable = Vec::with_capacity(credentials.len());
+        let mut unredeemable = Vec::new();
+        for credential in credentials {
+            let serial_number = credential
+                .verify_credential_request
+                .blinded_serial_number_bs58();
+            if self
+                .coconut_verifier
+                .is_redeemed_on_chain(&serial_number)
+                .await?
+            {
+                warn!("voucher {serial_number} has already been submitted to the bandwidth contract. its funds won't be redeemed");
+                unredeemable.push(serial_number);
+            } else {
+                redeemable.push(credential)
+            }
+        }
+
+        if !unredeemable.is_empty() {
+            self.storage
+                .remove_pending_voucher_redemptions(&unredeemable)
+                .await?;
+        }
+        Ok(redeemable)
+    }
+
+    async fn submit_batch(
+        &self,
+        batch_id: i64,
+        credentials: &[CredentialSpendingData],
+    ) -> Result<Hash, RequestHandlingError> {
+        let tx_hash = self
+            .coconut_verifier
+            .submit_vouchers_batch(credentials)
+            .await?;
+        self.storage
+            .set_voucher_redemption_batch_tx_hash(batch_id, &tx_hash.to_string())
+            .await?;
+        Ok(tx_hash)
+    }
+
+    /// Resolves the id of the multisig proposal releasing the funds of the batch,
+    /// (re)submitting the batch to the bandwidth contract if it has never made it to the chain.
+    async fn resolve_proposal_id(
+        &self,
+        batch: &VoucherRedemptionBatch,
+        credentials: &mut Vec<CredentialSpendingData>,
+    ) -> Result<u64, RequestHandlingError> {
+        if let Some(proposal_id) = batch.proposal_id {
+            return Ok(proposal_id as u64);
+        }
+
+        let tx_hash = match &batch.tx_hash {
+            Some(tx_hash) => {
+                Hash::from_str(tx_hash).map_err(|err| RequestHandlingError::ProposalIdError {
+                    reason: format!("the stored transaction hash is malformed: {err}"),
+                })?
+            }
+            None => {
+                // we don't know whether the previous submission attempt has made it to the chain
+                if let Some(proposal_id) = self
+                    .coconut_verifier
+                    .find_batch_proposal(&serial_numbers(credentials))
+                    .await?
+                {
+                    self.storage
+                        .set_voucher_redemption_batch_proposal_id(batch.id, proposal_id)
+                        .await?;
+                    return Ok(proposal_id);
+                }
+
+                *credentials = self.drop_unredeemable(mem::take(credentials)).await?;
+                if credentials.is_empty() {
+                    return Err(RequestHandlingError::ProposalIdError {
+                        reason: String::from("none of the vouchers in the batch are redeemable"),
+                    });
+                }
+                self.submit_batch(batch.id, credentials).await?
+            }
+        };
+
+        let proposal_id = self.coconut_verifier.batch_proposal_id(tx_hash).await?;
+        self.storage
+            .set_voucher_redemption_batch_proposal_id(batch.id, proposal_id)
+            .await?;
+        Ok(proposal_id)
+    }
+
+    /// Attempts to complete the redemption of the batch, resuming from the last step that has succeeded.
+    /// The batch is only removed from the storage once it no longer needs any action from this gateway.
+    async fn complete_batch(
+        &self,
+        batch: VoucherRedemptionBatch,
+        mut credentials: Vec<CredentialSpendingData>,
+    ) -> Result<(), RequestHandlingError> {
+        let Some(epoch_id) = credentials.first().map(|credential| credential.epoch_id) else {
+            self.storage
+                .remove_voucher_redemption_batch(batch.id)
+                .await?;
+            return Ok(());
+        };
+
+        let proposal_id = match self.resolve_proposal_id(&batch, &mut credentials).await {
+            Ok(proposal_id) => proposal_id,
+            Err(err) if credentials.is_empty() => {
+                debug!("abandoning redemption batch {}: {err}", batch.id);
+                self.storage
+                    .remove_voucher_redemption_batch(batch.id)
+                    .await?;
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
+
+        let api_clients = self.coconut_verifier.api_clients(epoch_id).await?;
+        self.coconut_verifier
+            .finalise_vouchers_batch(&api_clients, proposal_id, credentials)
+            .await?;
+        self.storage
+            .remove_voucher_redemption_batch(batch.id)
+            .await?;
+        Ok(())
+    }
+
+    async fn redeem_new_batch(
+        &self,
+        credentials: Vec<CredentialSpendingData>,
+    ) -> Result<(), RequestHandlingError> {
+        let credentials = self.drop_unredeemable(credentials).await?;
+        if credentials.is_empty() {
+            return Ok(());
+        }
+
+        // persist the batch before submitting it, so that we could resume the redemption
+        // even if we fail to retrieve the resulting proposal
+        let batch_id = self
+            .storage
+            .create_voucher_redemption_batch(&serial_numbers(&credentials))
+            .await?;
+        let tx_hash = self.submit_batch(batch_id, &credentials).await?;
+
+        let batch = VoucherRedemptionBatch {
+            id: batch_id,
+            tx_hash: Some(tx_hash.to_string()),
+            proposal_id: None,
+        };
+        self.complete_batch(batch, credentials).await
+    }
+
+    async fn resume_batches(&self) {
+        let batches = match self.storage.get_voucher_redemption_batches().await {
+            Ok(batches) => batches,
+            Err(err) => {
+                error!("failed to retrieve the voucher redemption batches: {err}");
+                return;
+            }
+        };
+
+        for batch in batches {
+            let batch_id = batch.id;
+            let credentials = match self.storage.get_batched_voucher_redemptions(batch_id).await {
+                Ok(credentials) => credentials,
+                Err(err) => {
+                    error!("failed to retrieve the vouchers of redemption batch {batch_id}: {err}");
+                    continue;
+                }
+            };
+
+            debug!(
+                "resuming the redemption of batch {batch_id} with {} vouchers",
+                credentials.len()
+            );
+            if let Err(err) = self.complete_batch(batch, credentials).await {
+                warn!("failed to complete the redemption of batch {batch_id}: {err}. we'll try again later")
+            }
+        }
+    }
+
+    async fn redeem_pending_vouchers(&self) {
+        // finish whatever we have started before, so that none of the submitted vouchers would get lost
+        self.resume_batches().await;
+
+        let batch_size = self.batch_size();
+
+        // don't attempt to process everything at once if we have accumulated a huge backlog
+        let pending = match self
+            .storage
+            .get_pending_voucher_redemptions(batch_size as i64 * 10)
+            .await
+        {
+            Ok(pending) => pending,
+            Err(err) => {
+                error!("failed to retrieve the pending voucher redemptions: {err}");
+                return;
+            }
+        };
+
+        if pending.is_empty() {
+            trace!("there are no vouchers to redeem");
+            return;
+        }
+        info!("attempting to redeem {} bandwidth vouchers", pending.len());
+
+        // credentials issued in different epochs have to be verified by different sets of apis
+        let mut per_epoch: BTreeMap<EpochId, Vec<CredentialSpendingData>> = BTreeMap::new();
+        for credential in pending {
+            per_epoch
+                .entry(credential.epoch_id)
+                .or_default()
+                .push(credential)
+        }
+
+        for (epoch_id, credentials) in per_epoch {
+            for batch in credentials.chunks(batch_size) {
+                debug!(
+                    "redeeming a batch of {} vouchers from epoch {epoch_id}",
+                    batch.len()
+                );
+                if let Err(err) = self.redeem_new_batch(batch.to_vec()).await {
+                    warn!(
+                        "failed to redeem a batch of {} vouchers: {err}. we'll try again later",
+                        batch.len()
+                    );
+                }
+            }
+        }
+    }
+
+    async fn run(mut self) {
+        let mut redemption_interval = interval(self.config.redemption_interval);
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("VoucherRedeemer: Received shutdown");
+                }
+                _ = redemption_interval.tick() => {
+                    self.redeem_pending_vouchers().await;
+                }
+            }
+        }
+        trace!("VoucherRedeemer: Exiting");
+    }
+
+    pub(crate) fn start(self) {
+        tokio::spawn(self.run());
+    }
+}
+
+fn serial_numbers(credentials: &[CredentialSpendingData]) -> Vec<String> {
+    credentials
+        .iter()
+        .map(|credential| {
+            credential
+                .verify_credential_request
+                .blinded_serial_number_bs58()
+        })
+        .collect()
+}
@@ -14,6 +14,7 @@ use nym_config::{
     serde_helpers::de_maybe_stringified, NymConfigTemplate, DEFAULT_CONFIG_DIR,
     DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_mixnode_common::packet_processor::replay_detection::ReplayProtectionConfig;
 use serde::{Deserialize, Serialize};
 use std::io;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -391,6 +392,9 @@ pub struct Debug {
     // existing nodes whilst everyone else is upgrading and getting the code for handling the new field.
     // It shall be disabled in the subsequent releases.
     pub use_legacy_framed_packet_version: bool,
+
+    /// Settings for detecting and rejecting replayed sphinx packets.
+    pub replay_protection: ReplayProtectionConfig,
 }
 
 impl Default for Debug {
@@ -403,6 +407,7 @@ impl Default for Debug {
             initial_connection_timeout: DEFAULT_INITIAL_CONNECTION_TIMEOUT,
             maximum_connection_buffer_size: DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE,
             use_legacy_framed_packet_version: false,
+            replay_protection: Default::default(),
         }
     }
 }
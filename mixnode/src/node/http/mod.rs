@@ -27,6 +27,7 @@ fn load_host_details(
             ed25519_identity: identity_keypair.public_key().to_base58_string(),
             x25519_sphinx: sphinx_key.to_base58_string(),
             x25519_noise: "".to_string(),
+            sphinx_key_rotation: None,
         },
     };
 
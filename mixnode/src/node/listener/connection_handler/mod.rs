@@ -51,13 +51,7 @@ impl ConnectionHandler {
     }
 
     fn handle_received_packet(&self, framed_sphinx_packet: FramedNymPacket) {
-        //
-        // TODO: here be replay attack detection - it will require similar key cache to the one in
-        // packet processor for vpn packets,
-        // question: can it also be per connection vs global?
-        //
-
-        // all processing such, key caching, etc. was done.
+        // all processing such as unwrapping and replay detection was done.
         // however, if it was a forward hop, we still need to delay it
         nanos!("handle_received_packet", {
             match self.packet_processor.process_received(framed_sphinx_packet) {
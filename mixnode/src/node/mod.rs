@@ -13,6 +13,10 @@ use crate::node::packet_delayforwarder::{DelayForwarder, PacketDelayForwardSende
 use log::{error, info, warn};
 use nym_bin_common::output_format::OutputFormat;
 use nym_crypto::asymmetric::{encryption, identity};
+use nym_mixnode_common::packet_processor::replay_detection::{
+    ReplayDetector, ReplayDetectorRotator,
+};
+use nym_mixnode_common::packet_processor::sphinx_keys::ActiveSphinxKeys;
 use nym_mixnode_common::verloc;
 use nym_mixnode_common::verloc::VerlocMeasurer;
 use nym_node_http_api::state::metrics::{SharedMixingStats, SharedVerlocStats};
@@ -37,6 +41,9 @@ pub struct MixNode {
     identity_keypair: Arc<identity::KeyPair>,
     sphinx_keypair: Arc<encryption::KeyPair>,
 
+    /// Rotating sphinx keys used for processing packets. If not set, the static `sphinx_keypair` is used instead.
+    active_sphinx_keys: Option<ActiveSphinxKeys>,
+
     run_http_server: bool,
     task_client: Option<TaskClient>,
     mixing_stats: Option<SharedMixingStats>,
@@ -50,6 +57,7 @@ impl MixNode {
             descriptor: Self::load_node_description(&config),
             identity_keypair: Arc::new(load_identity_keys(&config)?),
             sphinx_keypair: Arc::new(load_sphinx_keys(&config)?),
+            active_sphinx_keys: None,
             config,
             task_client: None,
             mixing_stats: None,
@@ -70,6 +78,7 @@ impl MixNode {
             descriptor,
             identity_keypair,
             sphinx_keypair,
+            active_sphinx_keys: None,
             mixing_stats: None,
             verloc_stats: None,
         }
@@ -83,6 +92,10 @@ impl MixNode {
         self.task_client = Some(task_client)
     }
 
+    pub fn set_active_sphinx_keys(&mut self, sphinx_keys: ActiveSphinxKeys) {
+        self.active_sphinx_keys = Some(sphinx_keys)
+    }
+
     pub fn set_mixing_stats(&mut self, mixing_stats: SharedMixingStats) {
         self.mixing_stats = Some(mixing_stats);
     }
@@ -143,16 +156,40 @@ impl MixNode {
         (mixing_stats, update_sender)
     }
 
+    fn start_replay_detector(&self, shutdown: TaskClient) -> Option<ReplayDetector> {
+        let replay_protection = &self.config.debug.replay_protection;
+        if replay_protection.unsafe_disabled {
+            warn!("replay protection has been explicitly disabled");
+            return None;
+        }
+
+        info!("Starting replay detector...");
+        let replay_detector = ReplayDetector::new(replay_protection);
+        ReplayDetectorRotator::new(
+            replay_detector.clone(),
+            replay_protection.rotation_interval,
+            shutdown,
+        )
+        .start();
+
+        Some(replay_detector)
+    }
+
     fn start_socket_listener(
         &self,
         node_stats_update_sender: node_statistics::UpdateSender,
         delay_forwarding_channel: PacketDelayForwardSender,
+        replay_detector: Option<ReplayDetector>,
         shutdown: TaskClient,
     ) {
         info!("Starting socket listener...");
 
+        let sphinx_keys = self
+            .active_sphinx_keys
+            .clone()
+            .unwrap_or_else(|| ActiveSphinxKeys::new(0, self.sphinx_keypair.private_key().into()));
         let packet_processor =
-            PacketProcessor::new(self.sphinx_keypair.private_key(), node_stats_update_sender);
+            PacketProcessor::new(sphinx_keys, replay_detector, node_stats_update_sender);
 
         let connection_handler = ConnectionHandler::new(packet_processor, delay_forwarding_channel);
 
@@ -277,9 +314,11 @@ impl MixNode {
             node_stats_update_sender.clone(),
             shutdown.fork("DelayForwarder"),
         );
+        let replay_detector = self.start_replay_detector(shutdown.fork("ReplayDetectorRotator"));
         self.start_socket_listener(
             node_stats_update_sender,
             delay_forwarding_channel,
+            replay_detector,
             shutdown.fork("Listener"),
         );
         let atomic_verloc_results = self.start_verloc_measurements(shutdown.fork("VerlocMeasurer"));
@@ -22,11 +22,23 @@ type PacketDataReceiver = mpsc::UnboundedReceiver<PacketEvent>;
 type PacketDataSender = mpsc::UnboundedSender<PacketEvent>;
 
 trait MixingStatsUpdateExt {
-    async fn update(&self, new_received: u64, new_sent: PacketsMap, new_dropped: PacketsMap);
+    async fn update(
+        &self,
+        new_received: u64,
+        new_replayed: u64,
+        new_sent: PacketsMap,
+        new_dropped: PacketsMap,
+    );
 }
 
 impl MixingStatsUpdateExt for SharedMixingStats {
-    async fn update(&self, new_received: u64, new_sent: PacketsMap, new_dropped: PacketsMap) {
+    async fn update(
+        &self,
+        new_received: u64,
+        new_replayed: u64,
+        new_sent: PacketsMap,
+        new_dropped: PacketsMap,
+    ) {
         let mut guard = self.write().await;
         let snapshot_time = OffsetDateTime::now_utc();
 
@@ -34,6 +46,7 @@ impl MixingStatsUpdateExt for SharedMixingStats {
         guard.update_time = snapshot_time;
 
         guard.packets_received_since_startup += new_received;
+        guard.packets_replayed_since_startup += new_replayed;
         for count in new_sent.values() {
             guard.packets_sent_since_startup_all += count;
         }
@@ -43,6 +56,7 @@ impl MixingStatsUpdateExt for SharedMixingStats {
         }
 
         inc_by!("packets_received_since_startup", new_received);
+        inc_by!("packets_replayed_since_startup", new_replayed);
         inc_by!(
             "packets_sent_since_startup_all",
             new_sent.values().sum::<u64>()
@@ -53,6 +67,7 @@ impl MixingStatsUpdateExt for SharedMixingStats {
         );
 
         guard.packets_received_since_last_update = new_received;
+        guard.packets_replayed_since_last_update = new_replayed;
         guard.packets_sent_since_last_update = new_sent;
         guard.packets_explicitly_dropped_since_last_update = new_dropped;
     }
@@ -61,6 +76,7 @@ impl MixingStatsUpdateExt for SharedMixingStats {
 pub(crate) enum PacketEvent {
     Sent(String),
     Received,
+    Replayed,
     Dropped(String),
 }
 
@@ -72,6 +88,7 @@ struct CurrentPacketData {
 #[derive(Debug)]
 struct PacketDataInner {
     received: AtomicU64,
+    replayed: AtomicU64,
     sent: Mutex<PacketsMap>,
     dropped: Mutex<PacketsMap>,
 }
@@ -81,6 +98,7 @@ impl CurrentPacketData {
         CurrentPacketData {
             inner: Arc::new(PacketDataInner {
                 received: AtomicU64::new(0),
+                replayed: AtomicU64::new(0),
                 sent: Mutex::new(HashMap::new()),
                 dropped: Mutex::new(HashMap::new()),
             }),
@@ -91,6 +109,10 @@ impl CurrentPacketData {
         self.inner.received.fetch_add(1, Ordering::SeqCst);
     }
 
+    fn increment_replayed(&self) {
+        self.inner.replayed.fetch_add(1, Ordering::SeqCst);
+    }
+
     async fn increment_sent(&self, destination: String) {
         let mut unlocked = self.inner.sent.lock().await;
         let receiver_count = unlocked.entry(destination).or_insert(0);
@@ -103,15 +125,16 @@ impl CurrentPacketData {
         *dropped_count += 1;
     }
 
-    async fn acquire_and_reset(&self) -> (u64, PacketsMap, PacketsMap) {
+    async fn acquire_and_reset(&self) -> (u64, u64, PacketsMap, PacketsMap) {
         let mut unlocked_sent = self.inner.sent.lock().await;
         let mut unlocked_dropped = self.inner.dropped.lock().await;
         let received = self.inner.received.swap(0, Ordering::SeqCst);
+        let replayed = self.inner.replayed.swap(0, Ordering::SeqCst);
 
         let sent = std::mem::take(unlocked_sent.deref_mut());
         let dropped = std::mem::take(unlocked_dropped.deref_mut());
 
-        (received, sent, dropped)
+        (received, replayed, sent, dropped)
     }
 }
 
@@ -142,6 +165,7 @@ impl UpdateHandler {
                 Some(packet_data) = self.update_receiver.next() => {
                     match packet_data {
                         PacketEvent::Received => self.current_data.increment_received(),
+                        PacketEvent::Replayed => self.current_data.increment_replayed(),
                         PacketEvent::Sent(destination) => {
                             self.current_data.increment_sent(destination).await
                         }
@@ -186,6 +210,12 @@ impl UpdateSender {
         self.0.unbounded_send(PacketEvent::Received).unwrap()
     }
 
+    pub(crate) fn report_replayed(&self) {
+        // in unbounded_send() failed it means that the receiver channel was disconnected
+        // and hence something weird must have happened without a way of recovering
+        self.0.unbounded_send(PacketEvent::Replayed).unwrap()
+    }
+
     pub(crate) fn report_dropped(&self, destination: String) {
         // in unbounded_send() failed it means that the receiver channel was disconnected
         // and hence something weird must have happened without a way of recovering
@@ -221,8 +251,11 @@ impl StatsUpdater {
 
     async fn update_stats(&self) {
         // grab new data since last update
-        let (received, sent, dropped) = self.current_packet_data.acquire_and_reset().await;
-        self.current_stats.update(received, sent, dropped).await;
+        let (received, replayed, sent, dropped) =
+            self.current_packet_data.acquire_and_reset().await;
+        self.current_stats
+            .update(received, replayed, sent, dropped)
+            .await;
     }
 
     async fn run(&mut self) {
@@ -282,6 +315,14 @@ impl PacketStatsConsoleLogger {
                     difference_secs,
                 );
             }
+            if stats.packets_replayed_since_startup > 0 {
+                info!(
+                    "Since startup rejected {} replayed packets! ({} in last {} seconds)",
+                    stats.packets_replayed_since_startup,
+                    stats.packets_replayed_since_last_update,
+                    difference_secs,
+                );
+            }
 
             debug!(
                 "Since startup received {} packets ({} in last {} seconds)",
@@ -306,6 +347,12 @@ impl PacketStatsConsoleLogger {
                     stats.packets_dropped_since_startup_all,
                 );
             }
+            if stats.packets_replayed_since_startup > 0 {
+                info!(
+                    "Since startup rejected {} replayed packets!",
+                    stats.packets_replayed_since_startup,
+                );
+            }
 
             debug!(
                 "Since startup received {} packets",
@@ -430,6 +477,7 @@ mod tests {
         assert_eq!(&stats.packets_sent_since_last_update.len(), &1);
         assert_eq!(&stats.packets_received_since_startup, &0);
         assert_eq!(&stats.packets_dropped_since_startup_all, &0);
-        assert_eq!(metrics!(), "# HELP nym_mixnode_packets_dropped_since_startup_all nym_mixnode_packets_dropped_since_startup_all\n# TYPE nym_mixnode_packets_dropped_since_startup_all counter\nnym_mixnode_packets_dropped_since_startup_all 0\n# HELP nym_mixnode_packets_received_since_startup nym_mixnode_packets_received_since_startup\n# TYPE nym_mixnode_packets_received_since_startup counter\nnym_mixnode_packets_received_since_startup 0\n# HELP nym_mixnode_packets_sent_since_startup_all nym_mixnode_packets_sent_since_startup_all\n# TYPE nym_mixnode_packets_sent_since_startup_all counter\nnym_mixnode_packets_sent_since_startup_all 2\n")
+        assert_eq!(&stats.packets_replayed_since_startup, &0);
+        assert_eq!(metrics!(), "# HELP nym_mixnode_packets_dropped_since_startup_all nym_mixnode_packets_dropped_since_startup_all\n# TYPE nym_mixnode_packets_dropped_since_startup_all counter\nnym_mixnode_packets_dropped_since_startup_all 0\n# HELP nym_mixnode_packets_received_since_startup nym_mixnode_packets_received_since_startup\n# TYPE nym_mixnode_packets_received_since_startup counter\nnym_mixnode_packets_received_since_startup 0\n# HELP nym_mixnode_packets_replayed_since_startup nym_mixnode_packets_replayed_since_startup\n# TYPE nym_mixnode_packets_replayed_since_startup counter\nnym_mixnode_packets_replayed_since_startup 0\n# HELP nym_mixnode_packets_sent_since_startup_all nym_mixnode_packets_sent_since_startup_all\n# TYPE nym_mixnode_packets_sent_since_startup_all counter\nnym_mixnode_packets_sent_since_startup_all 2\n")
     }
 }
@@ -0,0 +1,20 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- blinded serial numbers of credentials that have been reported as spent so that the gateways could
+-- reject credentials that have already been accepted by somebody else
+CREATE TABLE spent_credential
+(
+    blinded_serial_number_bs58 VARCHAR NOT NULL PRIMARY KEY,
+    epoch_id                   INTEGER NOT NULL,
+
+    -- identity of the gateway (or the cosmos address of the spender of a voucher) that has reported the credential
+    reporter                   VARCHAR NOT NULL,
+
+    -- unix timestamp of when we have first learned about the spending
+    reported_at                INTEGER NOT NULL
+);
+
+CREATE INDEX spent_credential_epoch_id_index ON spent_credential (epoch_id);
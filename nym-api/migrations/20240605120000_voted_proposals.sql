@@ -0,0 +1,15 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- multisig proposals for releasing the funds of spent vouchers that we have already voted on,
+-- so that we wouldn't have to re-verify all the credentials if the same proposal got submitted again
+CREATE TABLE voted_proposal
+(
+    proposal_id INTEGER NOT NULL PRIMARY KEY,
+    vote_yes    BOOLEAN NOT NULL,
+
+    -- unix timestamp of when we have cast the vote
+    voted_at    INTEGER NOT NULL
+);
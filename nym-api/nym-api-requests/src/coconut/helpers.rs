@@ -31,3 +31,25 @@ pub fn issued_credential_plaintext(
         )
         .collect()
 }
+
+// recomputes plaintext on the batch of spent credentials the gateway has used for signing
+pub fn spent_credentials_batch_plaintext(
+    epoch_id: u64,
+    blinded_serial_numbers: &[String],
+) -> Vec<u8> {
+    epoch_id
+        .to_be_bytes()
+        .into_iter()
+        .chain(
+            blinded_serial_numbers
+                .iter()
+                // include the lengths so that the serial numbers couldn't be re-split differently
+                .flat_map(|serial| {
+                    (serial.len() as u32)
+                        .to_be_bytes()
+                        .into_iter()
+                        .chain(serial.as_bytes().iter().copied())
+                }),
+        )
+        .collect()
+}
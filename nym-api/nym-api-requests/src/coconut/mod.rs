@@ -3,8 +3,12 @@
 
 pub mod helpers;
 pub mod models;
+pub mod spent_credentials;
 
 pub use models::{
     BlindSignRequestBody, BlindedSignatureResponse, CredentialsRequestBody, FreePassRequest,
+    SpentCredentialStatusResponse, SpentCredentialsBatch, SpentCredentialsFilterResponse,
     VerificationKeyResponse, VerifyCredentialBody, VerifyCredentialResponse,
+    VerifyCredentialsBatchBody,
 };
+pub use spent_credentials::SpentCredentialsFilter;
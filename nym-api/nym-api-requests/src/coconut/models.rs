@@ -1,7 +1,8 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::coconut::helpers::issued_credential_plaintext;
+use crate::coconut::helpers::{issued_credential_plaintext, spent_credentials_batch_plaintext};
+use crate::coconut::spent_credentials::SpentCredentialsFilter;
 use cosmrs::AccountId;
 use nym_credentials_interface::{
     hash_to_scalar, Attribute, BlindSignRequest, BlindedSignature, Bytable, CoconutError,
@@ -38,6 +39,33 @@ impl VerifyCredentialBody {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct VerifyCredentialsBatchBody {
+    /// The cryptographic material required for spending the underlying credentials,
+    /// in the same order as their serial numbers are listed in the proposal.
+    pub credentials: Vec<CredentialSpendingData>,
+
+    /// Multisig proposal for releasing funds for the provided batch of bandwidth credentials
+    pub proposal_id: u64,
+
+    /// Cosmos address of the spender of the credentials
+    pub gateway_cosmos_addr: AccountId,
+}
+
+impl VerifyCredentialsBatchBody {
+    pub fn new(
+        credentials: Vec<CredentialSpendingData>,
+        proposal_id: u64,
+        gateway_cosmos_addr: AccountId,
+    ) -> VerifyCredentialsBatchBody {
+        VerifyCredentialsBatchBody {
+            credentials,
+            proposal_id,
+            gateway_cosmos_addr,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct VerifyCredentialResponse {
     pub verification_result: bool,
@@ -285,3 +313,66 @@ impl IssuedCredential {
         )
     }
 }
+
+/// Maximum number of serial numbers that can be submitted in a single [`SpentCredentialsBatch`].
+pub const MAX_SPENT_CREDENTIALS_BATCH_SIZE: usize = 1000;
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpentCredentialsFilterResponse {
+    pub epoch_id: u64,
+    pub filter: SpentCredentialsFilter,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpentCredentialStatusResponse {
+    pub spent: bool,
+}
+
+/// Blinded serial numbers of credentials that have recently been spent at the particular gateway.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SpentCredentialsBatch {
+    /// Identity of the gateway that has accepted the credentials.
+    #[serde(with = "identity::serde_helpers::bs58_pubkey")]
+    pub gateway_identity: identity::PublicKey,
+
+    /// The (coconut) epoch in which the credentials have been issued.
+    pub epoch_id: u64,
+
+    pub blinded_serial_numbers: Vec<String>,
+
+    /// Signature on the epoch id and all the serial numbers made with the gateway's identity key.
+    pub signature: identity::Signature,
+}
+
+impl SpentCredentialsBatch {
+    pub fn new_signed(
+        gateway_identity: &identity::KeyPair,
+        epoch_id: u64,
+        blinded_serial_numbers: Vec<String>,
+    ) -> Self {
+        let signature = gateway_identity
+            .private_key()
+            .sign(spent_credentials_batch_plaintext(
+                epoch_id,
+                &blinded_serial_numbers,
+            ));
+
+        SpentCredentialsBatch {
+            gateway_identity: *gateway_identity.public_key(),
+            epoch_id,
+            blinded_serial_numbers,
+            signature,
+        }
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        let plaintext =
+            spent_credentials_batch_plaintext(self.epoch_id, &self.blinded_serial_numbers);
+        self.gateway_identity
+            .verify(plaintext, &self.signature)
+            .is_ok()
+    }
+}
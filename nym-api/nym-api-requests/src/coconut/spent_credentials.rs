@@ -0,0 +1,174 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use base64::engine::general_purpose;
+use base64::Engine;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+// with the default parameters, the filter takes 128kB and the false positive rate
+// stays below 0.1% until roughly 70'000 serial numbers have been inserted
+pub const DEFAULT_SPENT_CREDENTIALS_FILTER_BITS: u32 = 1 << 20;
+pub const DEFAULT_SPENT_CREDENTIALS_FILTER_HASHES: u32 = 7;
+
+/// Number of the most recent DKG epochs, including the current one, for which the filters
+/// of spent credentials are maintained. Credentials issued in older epochs are no longer tracked.
+pub const SPENT_CREDENTIALS_FILTER_EPOCHS: u64 = 3;
+
+/// Checks whether the filter of credentials issued in `epoch_id` is still maintained
+/// given the current DKG epoch.
+pub fn is_tracked_spent_credentials_epoch(epoch_id: u64, current_epoch_id: u64) -> bool {
+    epoch_id <= current_epoch_id && current_epoch_id - epoch_id < SPENT_CREDENTIALS_FILTER_EPOCHS
+}
+
+/// Compact, probabilistic, set of blinded serial numbers of credentials that have been spent.
+///
+/// It never yields false negatives, i.e. if a serial number has been inserted, [`Self::contains`]
+/// will always return `true`. However, it might return `true` for serial numbers that have never been inserted,
+/// so any hit should be treated only as an indication that the credential **might** have been spent.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpentCredentialsFilter {
+    num_hashes: u32,
+
+    #[serde(with = "base64_bytes")]
+    bits: Vec<u8>,
+}
+
+impl Default for SpentCredentialsFilter {
+    fn default() -> Self {
+        SpentCredentialsFilter::new(
+            DEFAULT_SPENT_CREDENTIALS_FILTER_BITS,
+            DEFAULT_SPENT_CREDENTIALS_FILTER_HASHES,
+        )
+    }
+}
+
+impl SpentCredentialsFilter {
+    pub fn new(num_bits: u32, num_hashes: u32) -> Self {
+        // make sure we always have at least a single byte and a single hash function to work with
+        let num_bytes = num_bits.div_ceil(8).max(1) as usize;
+        SpentCredentialsFilter {
+            num_hashes: num_hashes.max(1),
+            bits: vec![0; num_bytes],
+        }
+    }
+
+    fn num_bits(&self) -> u64 {
+        self.bits.len() as u64 * 8
+    }
+
+    // uses the standard double hashing construction, i.e. h_i(x) = h1(x) + i * h2(x)
+    fn bit_indices(&self, bs58_serial_number: &str) -> impl Iterator<Item = usize> {
+        let digest = Sha256::digest(bs58_serial_number.as_bytes());
+        let mut first = [0u8; 8];
+        let mut second = [0u8; 8];
+        first.copy_from_slice(&digest[..8]);
+        second.copy_from_slice(&digest[8..16]);
+
+        // make sure the step is odd so that we'd never get the same index repeated
+        let h1 = u64::from_le_bytes(first);
+        let h2 = u64::from_le_bytes(second) | 1;
+
+        let num_bits = self.num_bits();
+        (0..self.num_hashes as u64)
+            .map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % num_bits) as usize)
+    }
+
+    /// Marks the provided serial number as spent.
+    pub fn insert(&mut self, bs58_serial_number: &str) {
+        for index in self.bit_indices(bs58_serial_number).collect::<Vec<_>>() {
+            self.bits[index / 8] |= 1 << (index % 8);
+        }
+    }
+
+    /// Checks whether the provided serial number might have been spent.
+    pub fn contains(&self, bs58_serial_number: &str) -> bool {
+        self.bit_indices(bs58_serial_number)
+            .all(|index| self.bits[index / 8] & (1 << (index % 8)) != 0)
+    }
+
+    /// Checks whether the other filter has been constructed with the same parameters,
+    /// so that the two could be merged.
+    pub fn is_compatible(&self, other: &SpentCredentialsFilter) -> bool {
+        self.num_hashes == other.num_hashes && self.bits.len() == other.bits.len()
+    }
+
+    /// Adds all serial numbers from the other filter into this one.
+    /// Returns `false`, without modifying anything, if the filters are not compatible.
+    pub fn merge(&mut self, other: &SpentCredentialsFilter) -> bool {
+        if !self.is_compatible(other) {
+            return false;
+        }
+        for (byte, other_byte) in self.bits.iter_mut().zip(&other.bits) {
+            *byte |= other_byte
+        }
+        true
+    }
+}
+
+mod base64_bytes {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserted_serials_are_always_found() {
+        let mut filter = SpentCredentialsFilter::default();
+        let serials = (0..1000).map(|i| format!("serial{i}")).collect::<Vec<_>>();
+        for serial in &serials {
+            filter.insert(serial)
+        }
+        assert!(serials.iter().all(|serial| filter.contains(serial)));
+        assert!(!filter.contains("never-inserted"));
+    }
+
+    #[test]
+    fn merging_filters() {
+        let mut first = SpentCredentialsFilter::default();
+        let mut second = SpentCredentialsFilter::default();
+        first.insert("foo");
+        second.insert("bar");
+
+        assert!(first.merge(&second));
+        assert!(first.contains("foo"));
+        assert!(first.contains("bar"));
+
+        let incompatible = SpentCredentialsFilter::new(1024, 3);
+        assert!(!first.merge(&incompatible));
+    }
+
+    #[test]
+    fn only_recent_epochs_are_tracked() {
+        assert!(is_tracked_spent_credentials_epoch(10, 10));
+        assert!(is_tracked_spent_credentials_epoch(8, 10));
+        assert!(!is_tracked_spent_credentials_epoch(7, 10));
+        assert!(!is_tracked_spent_credentials_epoch(11, 10));
+        assert!(is_tracked_spent_credentials_epoch(0, 0));
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let mut filter = SpentCredentialsFilter::new(2048, 4);
+        filter.insert("foo");
+
+        let serialised = serde_json::to_string(&filter).unwrap();
+        let deserialised: SpentCredentialsFilter = serde_json::from_str(&serialised).unwrap();
+        assert_eq!(filter, deserialised);
+    }
+}
@@ -390,13 +390,43 @@ impl From<nym_node_requests::api::v1::node::models::HostInformation> for HostInf
 pub struct HostKeys {
     pub ed25519: String,
     pub x25519: String,
+
+    /// Base58-encoded x25519 public key of this node used for the noise protocol, if it was announced.
+    #[serde(default)]
+    pub x25519_noise: Option<String>,
+
+    /// Id of the sphinx key rotation the `x25519` key belongs to, if the node rotates its keys.
+    #[serde(default)]
+    pub current_rotation_id: Option<u32>,
+
+    /// The pre-announced sphinx key of the following key rotation, if the node rotates its keys.
+    #[serde(default)]
+    pub next_x25519: Option<String>,
 }
 
 impl From<nym_node_requests::api::v1::node::models::HostKeys> for HostKeys {
     fn from(value: nym_node_requests::api::v1::node::models::HostKeys) -> Self {
+        let (current_rotation_id, next_x25519) = match value.sphinx_key_rotation {
+            Some(rotation) => (
+                Some(rotation.current_rotation_id),
+                Some(rotation.next_x25519_sphinx),
+            ),
+            None => (None, None),
+        };
+
+        // older nodes do not expose their noise keys
+        let x25519_noise = if value.x25519_noise.is_empty() {
+            None
+        } else {
+            Some(value.x25519_noise)
+        };
+
         HostKeys {
             ed25519: value.ed25519_identity,
             x25519: value.x25519_sphinx,
+            x25519_noise,
+            current_rotation_id,
+            next_x25519,
         }
     }
 }
@@ -666,10 +696,42 @@ pub struct AuthenticatorDetails {
     pub address: String,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ApiHealthResponse {
     pub status: ApiStatus,
     pub uptime: u64,
+
+    /// Information on how fresh the data held by each of the caches is.
+    #[serde(default)]
+    pub caches: Vec<CacheAge>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CacheAge {
+    pub name: String,
+
+    /// Time of the last successful refresh of the cache. `None` if it hasn't been initialised yet.
+    pub refreshed_at: Option<OffsetDateTimeJsonSchemaWrapper>,
+
+    /// Number of seconds that have passed since the last successful refresh of the cache.
+    pub age_secs: Option<u64>,
+}
+
+impl CacheAge {
+    pub fn new(name: impl Into<String>, refreshed_at: Option<OffsetDateTime>) -> Self {
+        let age_secs = refreshed_at.map(|refreshed_at| {
+            // in case of any clock weirdness, don't go negative
+            (OffsetDateTime::now_utc() - refreshed_at)
+                .whole_seconds()
+                .max(0) as u64
+        });
+
+        CacheAge {
+            name: name.into(),
+            refreshed_at: refreshed_at.map(Into::into),
+            age_secs,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, schemars::JsonSchema)]
@@ -683,8 +745,15 @@ impl ApiHealthResponse {
         ApiHealthResponse {
             status: ApiStatus::Up,
             uptime: uptime.as_secs(),
+            caches: Vec::new(),
         }
     }
+
+    #[must_use]
+    pub fn with_caches(mut self, caches: Vec<CacheAge>) -> Self {
+        self.caches = caches;
+        self
+    }
 }
 
 impl ApiStatus {
@@ -2,10 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::models::{
-    GatewayBondAnnotated, MixNodeBondAnnotated, NymNodeDescription, OffsetDateTimeJsonSchemaWrapper,
+    AuthenticatorDetails, GatewayBondAnnotated, IpPacketRouterDetails, MixNodeBondAnnotated,
+    NetworkRequesterDetails, NodePerformance, NymNodeDescription, OffsetDateTimeJsonSchemaWrapper,
+    WebSockets,
 };
+use crate::pagination::PaginatedResponse;
+use nym_mixnet_contract_common::mixnode::MixNodeDetails;
 use nym_mixnet_contract_common::reward_params::Performance;
-use nym_mixnet_contract_common::MixId;
+use nym_mixnet_contract_common::{GatewayBond, MixId};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 
@@ -15,6 +19,12 @@ pub struct CachedNodesResponse<T> {
     pub nodes: Vec<T>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PaginatedCachedNodesResponse<T> {
+    pub refreshed_at: OffsetDateTimeJsonSchemaWrapper,
+    pub nodes: PaginatedResponse<T>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "request-parsing", derive(rocket::form::FromFormField))]
@@ -69,7 +79,15 @@ pub struct SkimmedNode {
 
     // TODO: to be deprecated in favour of well-known hardcoded port for everyone
     pub mix_port: u16,
+
+    /// The sphinx key clients should use for constructing packets. For nodes rotating their keys
+    /// it's the key of the current rotation, otherwise it's the key the node has been bonded with.
     pub x25519_sphinx_pubkey: String,
+
+    /// Details of the sphinx key rotation, if the node has announced its rotated keys.
+    #[serde(default)]
+    pub sphinx_key_rotation: Option<SphinxKeyRotationInformation>,
+
     pub role: NodeRole,
     pub entry: Option<BasicEntryInformation>,
 
@@ -77,7 +95,44 @@ pub struct SkimmedNode {
     pub performance: Performance,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SphinxKeyRotationInformation {
+    /// Id of the key rotation the `x25519_sphinx_pubkey` belongs to.
+    pub current_rotation_id: u32,
+
+    /// The pre-announced sphinx key the node is going to use once the next key rotation begins.
+    pub next_x25519_sphinx_pubkey: String,
+}
+
 impl SkimmedNode {
+    /// Replaces the bonded sphinx key with the rotated keys the node has announced, if any.
+    fn use_described_sphinx_keys(&mut self, description: &NymNodeDescription) {
+        let keys = &description.host_information.keys;
+        let (Some(current_rotation_id), Some(next_x25519)) =
+            (keys.current_rotation_id, &keys.next_x25519)
+        else {
+            // the node doesn't rotate its keys, so the bonded key is the one to use
+            return;
+        };
+
+        self.x25519_sphinx_pubkey.clone_from(&keys.x25519);
+        self.sphinx_key_rotation = Some(SphinxKeyRotationInformation {
+            current_rotation_id,
+            next_x25519_sphinx_pubkey: next_x25519.clone(),
+        });
+    }
+
+    pub fn from_described_mixnode(
+        annotated: &MixNodeBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        let mut base: SkimmedNode = annotated.into();
+        if let Some(description) = description {
+            base.use_described_sphinx_keys(description);
+        }
+        base
+    }
+
     pub fn from_described_gateway(
         annotated: &GatewayBondAnnotated,
         description: Option<&NymNodeDescription>,
@@ -100,6 +155,7 @@ impl SkimmedNode {
             base.ip_addresses
                 .clone_from(&description.host_information.ip_address)
         }
+        base.use_described_sphinx_keys(description);
 
         base
     }
@@ -113,6 +169,7 @@ impl<'a> From<&'a MixNodeBondAnnotated> for SkimmedNode {
             ip_addresses: value.ip_addresses.clone(),
             mix_port: value.mix_node().mix_port,
             x25519_sphinx_pubkey: value.mix_node().sphinx_key.clone(),
+            sphinx_key_rotation: None,
             role: NodeRole::Mixnode {
                 layer: value.mixnode_details.bond_information.layer.into(),
             },
@@ -130,6 +187,7 @@ impl<'a> From<&'a GatewayBondAnnotated> for SkimmedNode {
             ed25519_identity_pubkey: value.gateway_bond.identity().clone(),
             mix_port: value.gateway_bond.gateway.mix_port,
             x25519_sphinx_pubkey: value.gateway_bond.gateway.sphinx_key.clone(),
+            sphinx_key_rotation: None,
             role: NodeRole::EntryGateway,
             entry: Some(BasicEntryInformation {
                 hostname: None,
@@ -141,19 +199,151 @@ impl<'a> From<&'a GatewayBondAnnotated> for SkimmedNode {
     }
 }
 
+// the interfaces through which clients can interact with the node
+#[derive(Clone, Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClientInterfaces {
+    pub mixnet_websockets: Option<WebSockets>,
+    pub network_requester: Option<NetworkRequesterDetails>,
+    pub ip_packet_router: Option<IpPacketRouterDetails>,
+    pub authenticator: Option<AuthenticatorDetails>,
+}
+
+impl ClientInterfaces {
+    fn from_described_gateway(
+        annotated: &GatewayBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        let Some(description) = description else {
+            return ClientInterfaces {
+                mixnet_websockets: Some(WebSockets {
+                    ws_port: annotated.gateway_bond.gateway.clients_port,
+                    wss_port: None,
+                }),
+                ..Default::default()
+            };
+        };
+
+        ClientInterfaces {
+            mixnet_websockets: Some(description.mixnet_websockets.clone()),
+            network_requester: description.network_requester.clone(),
+            ip_packet_router: description.ip_packet_router.clone(),
+            authenticator: description.authenticator.clone(),
+        }
+    }
+}
+
 // an intermediate variant that exposes additional data such as noise keys but without
 // the full fat of the self-described data
 #[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct SemiSkimmedNode {
     pub basic: SkimmedNode,
-    pub x25519_noise_pubkey: String,
+    pub x25519_noise_pubkey: Option<String>,
+    pub node_performance: NodePerformance,
+    pub client_interfaces: ClientInterfaces,
     // pub location:
 }
 
+impl SemiSkimmedNode {
+    pub fn from_described_gateway(
+        annotated: &GatewayBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        SemiSkimmedNode {
+            basic: SkimmedNode::from_described_gateway(annotated, description),
+            x25519_noise_pubkey: description
+                .and_then(|d| d.host_information.keys.x25519_noise.clone()),
+            node_performance: annotated.node_performance.clone(),
+            client_interfaces: ClientInterfaces::from_described_gateway(annotated, description),
+        }
+    }
+
+    pub fn from_described_mixnode(
+        annotated: &MixNodeBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        SemiSkimmedNode {
+            basic: SkimmedNode::from_described_mixnode(annotated, description),
+            x25519_noise_pubkey: description
+                .and_then(|d| d.host_information.keys.x25519_noise.clone()),
+            node_performance: annotated.node_performance.clone(),
+            // mixnodes do not expose anything to the clients
+            client_interfaces: Default::default(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct FullFatNode {
     pub expanded: SemiSkimmedNode,
 
+    // only one of those is going to be set depending on the type of the bonded node
+    pub mixnode_details: Option<MixNodeDetails>,
+    pub gateway_bond: Option<GatewayBond>,
+    pub blacklisted: bool,
+
     // kinda temporary for now to make as few changes as possible for now
     pub self_described: Option<NymNodeDescription>,
 }
+
+impl FullFatNode {
+    pub fn from_described_gateway(
+        annotated: &GatewayBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        FullFatNode {
+            expanded: SemiSkimmedNode::from_described_gateway(annotated, description),
+            mixnode_details: None,
+            gateway_bond: Some(annotated.gateway_bond.clone()),
+            blacklisted: annotated.blacklisted,
+            self_described: description.cloned(),
+        }
+    }
+
+    pub fn from_described_mixnode(
+        annotated: &MixNodeBondAnnotated,
+        description: Option<&NymNodeDescription>,
+    ) -> Self {
+        FullFatNode {
+            expanded: SemiSkimmedNode::from_described_mixnode(annotated, description),
+            mixnode_details: Some(annotated.mixnode_details.clone()),
+            gateway_bond: None,
+            blacklisted: annotated.blacklisted,
+            self_described: description.cloned(),
+        }
+    }
+}
+
+/// Round-trip times between mixnodes, as measured by their verloc, aggregated over the whole network.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NetworkLatency {
+    pub links: Vec<LinkLatency>,
+    pub nodes: Vec<NodeLatency>,
+}
+
+/// Latency of the link between two nodes. The measurements are symmetric, so the order of the nodes
+/// is irrelevant.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct LinkLatency {
+    pub first_node_identity: String,
+    pub second_node_identity: String,
+
+    /// Mean round-trip time of the echo packets sent between the nodes (in either direction), in microseconds.
+    pub mean_rtt_micros: u64,
+}
+
+/// Overall latency of the links of particular node, for use whenever the latency of a specific link is unknown.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NodeLatency {
+    pub node_identity: String,
+
+    /// Median of the mean round-trip times of all measured links of this node, in microseconds.
+    pub median_rtt_micros: u64,
+
+    pub measured_links: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CachedNetworkLatencyResponse {
+    pub refreshed_at: OffsetDateTimeJsonSchemaWrapper,
+    pub latency: NetworkLatency,
+}
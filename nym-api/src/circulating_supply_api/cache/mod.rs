@@ -9,10 +9,14 @@ use nym_validator_client::nyxd::Coin;
 use rocket::fairing::AdHoc;
 use std::ops::Deref;
 use std::{
-    sync::{atomic::AtomicBool, Arc},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 use thiserror::Error;
+use ::time::OffsetDateTime;
 use tokio::sync::RwLock;
 use tokio::time;
 
@@ -67,6 +71,14 @@ impl CirculatingSupplyCache {
         }
     }
 
+    /// Returns the time of the last successful refresh of the cache, if it has been initialised.
+    pub(crate) async fn last_refreshed(&self) -> Option<OffsetDateTime> {
+        if !self.initialised.load(Ordering::Relaxed) {
+            return None;
+        }
+        Some(self.data.read().await.circulating_supply.timestamp())
+    }
+
     pub(crate) fn stage(mix_denom: String) -> AdHoc {
         AdHoc::on_ignite("Circulating Supply Cache Stage", |rocket| async {
             rocket.manage(Self::new(mix_denom))
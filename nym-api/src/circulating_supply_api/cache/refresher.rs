@@ -5,17 +5,22 @@ use super::CirculatingSupplyCache;
 use crate::circulating_supply_api::cache::CirculatingSupplyCacheError;
 use crate::support::nyxd::Client;
 use nym_contracts_common::truncate_decimal;
+use nym_mixnet_contract_common::EpochId;
 use nym_task::TaskClient;
 use nym_validator_client::nyxd::Coin;
 use std::collections::HashSet;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
+use tokio::sync::watch;
 use tokio::time;
 
 pub(crate) struct CirculatingSupplyCacheRefresher {
     nyxd_client: Client,
     cache: CirculatingSupplyCache,
     caching_interval: Duration,
+
+    // the mixmining reserve changes whenever the epoch rewards get distributed
+    epoch_listener: watch::Receiver<EpochId>,
 }
 
 impl CirculatingSupplyCacheRefresher {
@@ -23,35 +28,25 @@ impl CirculatingSupplyCacheRefresher {
         nyxd_client: Client,
         cache: CirculatingSupplyCache,
         caching_interval: Duration,
+        epoch_listener: watch::Receiver<EpochId>,
     ) -> Self {
         CirculatingSupplyCacheRefresher {
             nyxd_client,
             cache,
             caching_interval,
+            epoch_listener,
         }
     }
 
-    pub(crate) async fn run(&self, mut shutdown: TaskClient) {
+    pub(crate) async fn run(&mut self, mut shutdown: TaskClient) {
         let mut interval = time::interval(self.caching_interval);
         while !shutdown.is_shutdown() {
             tokio::select! {
-                _ = interval.tick() => {
-                    tokio::select! {
-                        biased;
-                        _ = shutdown.recv() => {
-                            trace!("CirculatingSupplyCacheRefresher: Received shutdown");
-                        }
-                        ret = self.refresh() => {
-                            if let Err(err) = ret {
-                                error!("Failed to refresh circulating supply cache - {err}");
-                            } else {
-                                // relaxed memory ordering is fine here. worst case scenario network monitor
-                                // will just have to wait for an additional backoff to see the change.
-                                // And so this will not really incur any performance penalties by setting it every loop iteration
-                                self.cache.initialised.store(true, Ordering::Relaxed)
-                            }
-                        }
-                    }
+                _ = interval.tick() => self.refresh_cache(&mut shutdown).await,
+                Ok(_) = self.epoch_listener.changed() => {
+                    debug!("epoch change detected - refreshing the circulating supply cache");
+                    self.refresh_cache(&mut shutdown).await;
+                    interval.reset();
                 }
                 _ = shutdown.recv() => {
                     trace!("CirculatingSupplyCacheRefresher: Received shutdown");
@@ -60,6 +55,25 @@ impl CirculatingSupplyCacheRefresher {
         }
     }
 
+    async fn refresh_cache(&self, shutdown: &mut TaskClient) {
+        tokio::select! {
+            biased;
+            _ = shutdown.recv() => {
+                trace!("CirculatingSupplyCacheRefresher: Received shutdown");
+            }
+            ret = self.refresh() => {
+                if let Err(err) = ret {
+                    error!("Failed to refresh circulating supply cache - {err}");
+                } else {
+                    // relaxed memory ordering is fine here. worst case scenario network monitor
+                    // will just have to wait for an additional backoff to see the change.
+                    // And so this will not really incur any performance penalties by setting it every loop iteration
+                    self.cache.initialised.store(true, Ordering::Relaxed)
+                }
+            }
+        }
+    }
+
     async fn get_mixmining_reserve(
         &self,
         mix_denom: &str,
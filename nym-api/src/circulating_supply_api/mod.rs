@@ -1,6 +1,7 @@
 // Copyright 2022-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use nym_mixnet_contract_common::EpochId;
 use nym_task::TaskManager;
 use okapi::openapi3::OpenApi;
 use rocket::Route;
@@ -27,13 +28,15 @@ pub(crate) fn start_cache_refresh(
     config: &config::CirculatingSupplyCacher,
     nyxd_client: nyxd::Client,
     circulating_supply_cache: &cache::CirculatingSupplyCache,
+    epoch_listener: tokio::sync::watch::Receiver<EpochId>,
     shutdown: &TaskManager,
 ) {
     if config.enabled {
-        let refresher = CirculatingSupplyCacheRefresher::new(
+        let mut refresher = CirculatingSupplyCacheRefresher::new(
             nyxd_client,
             circulating_supply_cache.to_owned(),
             config.debug.caching_interval,
+            epoch_listener,
         );
         let shutdown_listener = shutdown.subscribe();
         tokio::spawn(async move { refresher.run(shutdown_listener).await });
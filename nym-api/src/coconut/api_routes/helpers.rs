@@ -3,9 +3,30 @@
 
 use crate::coconut::error::Result;
 use crate::coconut::storage::models::IssuedCredential;
+use crate::nym_contract_cache::cache::NymContractCache;
 use nym_api_requests::coconut::models::IssuedCredentialBody;
 use nym_api_requests::coconut::models::IssuedCredentialsResponse;
+use rocket::request::{FromRequest, Outcome};
+use rocket::Request;
 use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+/// Request guard retrieving the contract cache if it's managed by the rocket instance.
+// note: we can't just use `Option<&State<NymContractCache>>` as rocket would refuse to launch
+// if the cache wasn't managed, which is the case when the coconut routes are launched
+// on their own (i.e. in our tests)
+pub(crate) struct OptionalContractCache<'r>(pub(crate) Option<&'r NymContractCache>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for OptionalContractCache<'r> {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(OptionalContractCache(
+            request.rocket().state::<NymContractCache>(),
+        ))
+    }
+}
 
 pub(crate) fn build_credentials_response(
     raw: Vec<IssuedCredential>,
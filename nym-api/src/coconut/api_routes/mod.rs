@@ -1,6 +1,8 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
 use k256::ecdsa::signature::Verifier;
@@ -12,22 +14,26 @@ use time::OffsetDateTime;
 
 use nym_api_requests::coconut::models::{
     CredentialsRequestBody, EpochCredentialsResponse, FreePassNonceResponse, FreePassRequest,
-    IssuedCredentialResponse, IssuedCredentialsResponse,
+    IssuedCredentialResponse, IssuedCredentialsResponse, MAX_SPENT_CREDENTIALS_BATCH_SIZE,
 };
 use nym_api_requests::coconut::{
-    BlindSignRequestBody, BlindedSignatureResponse, VerifyCredentialBody, VerifyCredentialResponse,
+    BlindSignRequestBody, BlindedSignatureResponse, SpentCredentialStatusResponse,
+    SpentCredentialsBatch, SpentCredentialsFilterResponse, VerifyCredentialBody,
+    VerifyCredentialResponse, VerifyCredentialsBatchBody,
 };
 use nym_coconut_bandwidth_contract_common::spend_credential::{
-    funds_from_cosmos_msgs, SpendCredentialStatus,
+    funds_from_cosmos_msgs, serial_numbers_from_batch_proposal_description, SpendCredentialStatus,
+    MAX_SPEND_CREDENTIALS_BATCH_SIZE,
 };
 use nym_coconut_dkg_common::types::EpochId;
 use nym_credentials::coconut::bandwidth::freepass::MAX_FREE_PASS_VALIDITY;
 use nym_credentials::coconut::bandwidth::{
-    bandwidth_credential_params, CredentialType, IssuanceBandwidthCredential,
+    bandwidth_credential_params, CredentialSpendingData, CredentialType,
+    IssuanceBandwidthCredential,
 };
 use nym_validator_client::nyxd::Coin;
 
-use crate::coconut::api_routes::helpers::build_credentials_response;
+use crate::coconut::api_routes::helpers::{build_credentials_response, OptionalContractCache};
 use crate::coconut::error::{CoconutError, Result};
 use crate::coconut::helpers::{accepted_vote_err, blind_sign};
 use crate::coconut::state::State;
@@ -262,6 +268,38 @@ pub async fn post_blind_sign(
     Ok(Json(BlindedSignatureResponse { blinded_signature }))
 }
 
+fn voucher_value(credential_data: &CredentialSpendingData) -> Result<u64> {
+    if !credential_data.typ.is_voucher() {
+        return Err(CoconutError::NotABandwidthVoucher {
+            typ: credential_data.typ,
+        });
+    }
+
+    credential_data
+        .get_bandwidth_attribute()
+        .ok_or(CoconutError::MissingBandwidthValue)?
+        .parse()
+        .map_err(|source| CoconutError::VoucherValueParsingFailure { source })
+}
+
+async fn ensure_spending_in_progress(state: &State, blinded_serial_number: String) -> Result<()> {
+    let credential_status = state
+        .client
+        .get_spent_credential(blinded_serial_number)
+        .await?
+        .spend_credential
+        .ok_or(CoconutError::InvalidCredentialStatus {
+            status: String::from("Inexistent"),
+        })?
+        .status();
+    if credential_status != SpendCredentialStatus::InProgress {
+        return Err(CoconutError::InvalidCredentialStatus {
+            status: format!("{:?}", credential_status),
+        });
+    }
+    Ok(())
+}
+
 #[post("/verify-bandwidth-credential", data = "<verify_credential_body>")]
 pub async fn verify_bandwidth_credential(
     verify_credential_body: Json<VerifyCredentialBody>,
@@ -272,17 +310,7 @@ pub async fn verify_bandwidth_credential(
     let epoch_id = credential_data.epoch_id;
     let theta = &credential_data.verify_credential_request;
 
-    let voucher_value: u64 = if credential_data.typ.is_voucher() {
-        credential_data
-            .get_bandwidth_attribute()
-            .ok_or(CoconutError::MissingBandwidthValue)?
-            .parse()
-            .map_err(|source| CoconutError::VoucherValueParsingFailure { source })?
-    } else {
-        return Err(CoconutError::NotABandwidthVoucher {
-            typ: credential_data.typ,
-        });
-    };
+    let voucher_value = voucher_value(credential_data)?;
 
     // TODO: introduce a check to make sure we haven't already voted for this proposal to prevent DDOS
 
@@ -299,20 +327,7 @@ pub async fn verify_bandwidth_credential(
             reason: String::from("action is not to release funds"),
         })?;
     // Credential has not been spent before, and is on its way of being spent
-    let credential_status = state
-        .client
-        .get_spent_credential(theta.blinded_serial_number_bs58())
-        .await?
-        .spend_credential
-        .ok_or(CoconutError::InvalidCredentialStatus {
-            status: String::from("Inexistent"),
-        })?
-        .status();
-    if credential_status != SpendCredentialStatus::InProgress {
-        return Err(CoconutError::InvalidCredentialStatus {
-            status: format!("{:?}", credential_status),
-        });
-    }
+    ensure_spending_in_progress(state, theta.blinded_serial_number_bs58()).await?;
     let verification_key = state.verification_key(epoch_id).await?;
     let params = bandwidth_credential_params();
     let mut vote_yes = credential_data.verify(params, &verification_key);
@@ -327,6 +342,146 @@ pub async fn verify_bandwidth_credential(
         .await;
     accepted_vote_err(ret)?;
 
+    if vote_yes {
+        // let other gateways know about the credential without having to wait for them to report it
+        if let Err(err) = state
+            .record_spent_credentials(
+                epoch_id,
+                vec![theta.blinded_serial_number_bs58()],
+                verify_credential_body.gateway_cosmos_addr.to_string(),
+            )
+            .await
+        {
+            warn!("failed to record the spent credential: {err}")
+        }
+    }
+
+    Ok(Json(VerifyCredentialResponse::new(vote_yes)))
+}
+
+#[post(
+    "/verify-bandwidth-credentials-batch",
+    data = "<verify_credentials_body>"
+)]
+pub async fn verify_bandwidth_credentials_batch(
+    verify_credentials_body: Json<VerifyCredentialsBatchBody>,
+    state: &RocketState<State>,
+) -> Result<Json<VerifyCredentialResponse>> {
+    let proposal_id = verify_credentials_body.proposal_id;
+    let credentials = &verify_credentials_body.credentials;
+
+    if credentials.is_empty() || credentials.len() > MAX_SPEND_CREDENTIALS_BATCH_SIZE {
+        return Err(CoconutError::InvalidCredentialsBatchSize {
+            got: credentials.len(),
+            max: MAX_SPEND_CREDENTIALS_BATCH_SIZE,
+        });
+    }
+
+    // don't go through the whole (expensive) verification again if somebody resubmits the same proposal
+    if let Some(vote_yes) = state.storage.get_proposal_vote(proposal_id).await? {
+        debug!("we have already voted on proposal {proposal_id}");
+        return Ok(Json(VerifyCredentialResponse::new(vote_yes)));
+    }
+
+    let proposal = state.client.get_proposal(proposal_id).await?;
+
+    // Proposal description contains the blinded serial numbers of all the credentials in the batch
+    let serial_numbers = serial_numbers_from_batch_proposal_description(&proposal.description)
+        .ok_or(CoconutError::IncorrectProposal {
+            reason: String::from("description does not contain a batch of blinded serial numbers"),
+        })?;
+    if serial_numbers.len() != credentials.len() {
+        return Err(CoconutError::IncorrectProposal {
+            reason: format!(
+                "the proposal contains {} blinded serial numbers while {} credentials were provided",
+                serial_numbers.len(),
+                credentials.len()
+            ),
+        });
+    }
+    for (credential, serial_number) in credentials.iter().zip(&serial_numbers) {
+        if !credential
+            .verify_credential_request
+            .has_blinded_serial_number(serial_number)?
+        {
+            return Err(CoconutError::IncorrectProposal {
+                reason: String::from("incorrect blinded serial number in description"),
+            });
+        }
+    }
+    let proposed_release_funds =
+        funds_from_cosmos_msgs(proposal.msgs).ok_or(CoconutError::IncorrectProposal {
+            reason: String::from("action is not to release funds"),
+        })?;
+
+    let params = bandwidth_credential_params();
+    let mut verification_keys = HashMap::new();
+    let mut seen_serial_numbers = HashSet::new();
+    let mut total_value: u128 = 0;
+
+    // a single invalid credential invalidates the whole batch
+    let mut vote_yes = true;
+    for (credential, serial_number) in credentials.iter().zip(&serial_numbers) {
+        total_value += voucher_value(credential)? as u128;
+
+        // Credentials have not been spent before, and are on their way of being spent
+        ensure_spending_in_progress(state, serial_number.clone()).await?;
+
+        vote_yes &= seen_serial_numbers.insert(serial_number);
+        if !vote_yes {
+            break;
+        }
+
+        let verification_key = match verification_keys.entry(credential.epoch_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                entry.insert(state.verification_key(credential.epoch_id).await?)
+            }
+        };
+
+        vote_yes &= credential.verify(params, verification_key);
+        if !vote_yes {
+            break;
+        }
+    }
+
+    vote_yes &=
+        Coin::from(proposed_release_funds) == Coin::new(total_value, state.mix_denom.clone());
+
+    // Vote yes or no on the proposal based on the verification result
+    let ret = state
+        .client
+        .vote_proposal(proposal_id, vote_yes, None)
+        .await;
+    accepted_vote_err(ret)?;
+    state
+        .storage
+        .insert_proposal_vote(proposal_id, vote_yes)
+        .await?;
+
+    if vote_yes {
+        // let other gateways know about the credentials without having to wait for them to report them
+        let mut per_epoch: HashMap<EpochId, Vec<String>> = HashMap::new();
+        for (credential, serial_number) in credentials.iter().zip(serial_numbers) {
+            per_epoch
+                .entry(credential.epoch_id)
+                .or_default()
+                .push(serial_number);
+        }
+        for (epoch_id, serial_numbers) in per_epoch {
+            if let Err(err) = state
+                .record_spent_credentials(
+                    epoch_id,
+                    serial_numbers,
+                    verify_credentials_body.gateway_cosmos_addr.to_string(),
+                )
+                .await
+            {
+                warn!("failed to record the spent credentials: {err}")
+            }
+        }
+    }
+
     Ok(Json(VerifyCredentialResponse::new(vote_yes)))
 }
 
@@ -391,3 +546,67 @@ pub async fn issued_credentials(
 
     build_credentials_response(credentials).map(Json)
 }
+
+#[get("/spent-credentials-filter/<epoch>")]
+pub async fn spent_credentials_filter(
+    epoch: EpochId,
+    state: &RocketState<State>,
+) -> Result<Json<SpentCredentialsFilterResponse>> {
+    let filter = state.spent_credentials_filter(epoch).await?;
+
+    Ok(Json(SpentCredentialsFilterResponse {
+        epoch_id: epoch,
+        filter,
+    }))
+}
+
+#[get("/spent-credential/<blinded_serial_number>")]
+pub async fn spent_credential(
+    blinded_serial_number: &str,
+    state: &RocketState<State>,
+) -> Result<Json<SpentCredentialStatusResponse>> {
+    let spent = state
+        .storage
+        .is_credential_spent(blinded_serial_number)
+        .await?;
+
+    Ok(Json(SpentCredentialStatusResponse { spent }))
+}
+
+#[post("/spent-credentials", data = "<batch>")]
+pub async fn submit_spent_credentials(
+    batch: Json<SpentCredentialsBatch>,
+    state: &RocketState<State>,
+    contract_cache: OptionalContractCache<'_>,
+) -> Result<Json<()>> {
+    let batch = batch.into_inner();
+
+    if batch.blinded_serial_numbers.len() > MAX_SPENT_CREDENTIALS_BATCH_SIZE {
+        return Err(CoconutError::TooManySpentCredentials {
+            got: batch.blinded_serial_numbers.len(),
+            max: MAX_SPENT_CREDENTIALS_BATCH_SIZE,
+        });
+    }
+
+    if !batch.verify_signature() {
+        return Err(CoconutError::InvalidSpentCredentialsSignature);
+    }
+
+    // only accept reports from the gateways that are actually bonded to prevent anyone from spamming us
+    let identity = batch.gateway_identity.to_base58_string();
+    let contract_cache = contract_cache.0.ok_or(CoconutError::UnavailableGateways)?;
+    let is_bonded = contract_cache
+        .gateways_all()
+        .await
+        .iter()
+        .any(|bond| bond.gateway.identity_key == identity);
+    if !is_bonded {
+        return Err(CoconutError::UnbondedGateway { identity });
+    }
+
+    state
+        .record_spent_credentials(batch.epoch_id, batch.blinded_serial_numbers, identity)
+        .await?;
+
+    Ok(Json(()))
+}
@@ -231,15 +231,40 @@ pub enum CoconutError {
         dealing_index: DealingIndex,
         chunk_index: ChunkIndex,
     },
+
+    #[error("the signature on the submitted spent credentials is invalid")]
+    InvalidSpentCredentialsSignature,
+
+    #[error("received {got} spent credentials in a single batch while at most {max} are allowed")]
+    TooManySpentCredentials { got: usize, max: usize },
+
+    #[error("gateway {identity} is not bonded")]
+    UnbondedGateway { identity: String },
+
+    #[error("the list of bonded gateways is currently not available")]
+    UnavailableGateways,
+
+    #[error("received a batch of {got} credentials to verify while it must contain between 1 and {max} of them")]
+    InvalidCredentialsBatchSize { got: usize, max: usize },
+
+    #[error("spent credentials are not tracked for epoch {epoch_id} (the current epoch is {current_epoch_id})")]
+    UntrackedSpentCredentialsEpoch {
+        epoch_id: EpochId,
+        current_epoch_id: EpochId,
+    },
 }
 
 impl<'r, 'o: 'r> Responder<'r, 'o> for CoconutError {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'o> {
+        let status = match self {
+            CoconutError::UntrackedSpentCredentialsEpoch { .. } => Status::NotFound,
+            _ => Status::BadRequest,
+        };
         let err_msg = self.to_string();
         Response::build()
             .header(ContentType::Plain)
             .sized_body(err_msg.len(), Cursor::new(err_msg))
-            .status(Status::BadRequest)
+            .status(status)
             .ok()
     }
 }
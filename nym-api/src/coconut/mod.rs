@@ -56,9 +56,13 @@ where
                 api_routes::post_free_pass,
                 api_routes::post_blind_sign,
                 api_routes::verify_bandwidth_credential,
+                api_routes::verify_bandwidth_credentials_batch,
                 api_routes::epoch_credentials,
                 api_routes::issued_credential,
                 api_routes::issued_credentials,
+                api_routes::spent_credentials_filter,
+                api_routes::spent_credential,
+                api_routes::submit_spent_credentials,
             ],
         )
     })
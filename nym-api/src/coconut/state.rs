@@ -9,13 +9,15 @@ use crate::coconut::keys::KeyPair;
 use crate::coconut::storage::CoconutStorageExt;
 use crate::support::storage::NymApiStorage;
 use nym_api_requests::coconut::helpers::issued_credential_plaintext;
-use nym_api_requests::coconut::BlindSignRequestBody;
+use nym_api_requests::coconut::spent_credentials::is_tracked_spent_credentials_epoch;
+use nym_api_requests::coconut::{BlindSignRequestBody, SpentCredentialsFilter};
 use nym_coconut::{BlindedSignature, VerificationKey};
 use nym_coconut_dkg_common::types::EpochId;
 use nym_crypto::asymmetric::identity;
 use nym_validator_client::nyxd::{AccountId, Hash, TxResponse};
 use rand::rngs::OsRng;
 use rand::RngCore;
+use std::collections::HashMap;
 use std::sync::Arc;
 use time::{Duration, OffsetDateTime};
 use tokio::sync::{OnceCell, RwLock};
@@ -32,6 +34,10 @@ pub struct State {
     pub(crate) storage: NymApiStorage,
     pub(crate) freepass_nonce: Arc<RwLock<[u8; 16]>>,
     pub(crate) authorised_freepass_requester: Arc<RwLock<AuthorisedFreepassRequester>>,
+
+    // lazily populated filters of credentials spent in particular epochs
+    // (only the most recent epochs are kept, see `SPENT_CREDENTIALS_FILTER_EPOCHS`)
+    pub(crate) spent_credentials: RwLock<HashMap<EpochId, SpentCredentialsFilter>>,
 }
 
 const FREEPASS_REQUESTER_TTL: Duration = Duration::hours(1);
@@ -81,6 +87,7 @@ impl State {
             storage,
             freepass_nonce: Arc::new(RwLock::new(nonce)),
             authorised_freepass_requester: Arc::new(Default::default()),
+            spent_credentials: Default::default(),
         }
     }
 
@@ -221,6 +228,69 @@ impl State {
         Ok(())
     }
 
+    /// Returns the filter of all credentials issued in the provided epoch that are known to have been spent.
+    /// Only the filters of the current and a bounded number of past DKG epochs are available.
+    pub async fn spent_credentials_filter(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<SpentCredentialsFilter> {
+        let current_epoch_id = self.comm_channel.current_epoch().await?;
+        if !is_tracked_spent_credentials_epoch(epoch_id, current_epoch_id) {
+            return Err(CoconutError::UntrackedSpentCredentialsEpoch {
+                epoch_id,
+                current_epoch_id,
+            });
+        }
+
+        if let Some(filter) = self.spent_credentials.read().await.get(&epoch_id) {
+            return Ok(filter.clone());
+        }
+
+        let mut guard = self.spent_credentials.write().await;
+        // somebody might have built the filter while we were waiting for the lock
+        if let Some(filter) = guard.get(&epoch_id) {
+            return Ok(filter.clone());
+        }
+
+        let mut filter = SpentCredentialsFilter::default();
+        for serial in self.storage.get_spent_credentials(epoch_id).await? {
+            filter.insert(&serial)
+        }
+
+        // get rid of the filters of the epochs that are no longer tracked
+        guard.retain(|epoch, _| is_tracked_spent_credentials_epoch(*epoch, current_epoch_id));
+        guard.insert(epoch_id, filter.clone());
+        Ok(filter)
+    }
+
+    /// Persists the information about the provided spent credentials and updates the associated filter.
+    pub async fn record_spent_credentials(
+        &self,
+        epoch_id: EpochId,
+        bs58_serial_numbers: Vec<String>,
+        reporter: String,
+    ) -> Result<()> {
+        let new_serials = self
+            .storage
+            .insert_spent_credentials(epoch_id, bs58_serial_numbers, reporter)
+            .await?;
+        if new_serials.is_empty() {
+            return Ok(());
+        }
+        debug!(
+            "recorded {} new spent credentials for epoch {epoch_id}",
+            new_serials.len()
+        );
+
+        // if the filter hasn't been built yet, it will include the new entries when it's (lazily) loaded
+        if let Some(filter) = self.spent_credentials.write().await.get_mut(&epoch_id) {
+            for serial in &new_serials {
+                filter.insert(serial)
+            }
+        }
+        Ok(())
+    }
+
     pub async fn verification_key(&self, epoch_id: EpochId) -> Result<VerificationKey> {
         self.comm_channel
             .aggregated_verification_key(epoch_id)
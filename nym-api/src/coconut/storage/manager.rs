@@ -95,6 +95,59 @@ pub trait CoconutStorageManagerExt {
     ) -> Result<Vec<IssuedCredential>, sqlx::Error>;
 
     async fn increment_issued_freepasses(&self) -> Result<(), sqlx::Error>;
+
+    /// Stores the provided blinded serial numbers of spent credentials, ignoring the ones that already existed.
+    /// Returns the serial numbers that have not been seen before.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch_id`: Id of the (coconut) epoch in which the credentials have been issued.
+    /// * `bs58_serial_numbers`: the blinded serial numbers of the spent credentials.
+    /// * `reporter`: identity of the party that has reported the spending.
+    /// * `reported_at`: unix timestamp of the report.
+    async fn insert_spent_credentials(
+        &self,
+        epoch_id: u32,
+        bs58_serial_numbers: Vec<String>,
+        reporter: String,
+        reported_at: i64,
+    ) -> Result<Vec<String>, sqlx::Error>;
+
+    /// Retrieves blinded serial numbers of all credentials, issued in the provided epoch,
+    /// that have been reported as spent.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch_id`: Id of the (coconut) epoch in question.
+    async fn get_spent_credentials(&self, epoch_id: u32) -> Result<Vec<String>, sqlx::Error>;
+
+    /// Checks whether the credential with the provided blinded serial number has been reported as spent.
+    ///
+    /// # Arguments
+    ///
+    /// * `bs58_serial_number`: the blinded serial number of the credential.
+    async fn is_credential_spent(&self, bs58_serial_number: &str) -> Result<bool, sqlx::Error>;
+
+    /// Retrieves the vote we have cast on the provided proposal, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `proposal_id`: id of the multisig proposal.
+    async fn get_proposal_vote(&self, proposal_id: i64) -> Result<Option<bool>, sqlx::Error>;
+
+    /// Records the vote we have cast on the provided proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `proposal_id`: id of the multisig proposal.
+    /// * `vote_yes`: whether we have voted in favour of the proposal.
+    /// * `voted_at`: unix timestamp of the vote.
+    async fn insert_proposal_vote(
+        &self,
+        proposal_id: i64,
+        vote_yes: bool,
+        voted_at: i64,
+    ) -> Result<(), sqlx::Error>;
 }
 
 #[async_trait]
@@ -360,6 +413,125 @@ impl CoconutStorageManagerExt for StorageManager {
             .await?;
         Ok(())
     }
+
+    /// Stores the provided blinded serial numbers of spent credentials, ignoring the ones that already existed.
+    /// Returns the serial numbers that have not been seen before.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch_id`: Id of the (coconut) epoch in which the credentials have been issued.
+    /// * `bs58_serial_numbers`: the blinded serial numbers of the spent credentials.
+    /// * `reporter`: identity of the party that has reported the spending.
+    /// * `reported_at`: unix timestamp of the report.
+    async fn insert_spent_credentials(
+        &self,
+        epoch_id: u32,
+        bs58_serial_numbers: Vec<String>,
+        reporter: String,
+        reported_at: i64,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let mut tx = self.connection_pool.begin().await?;
+
+        let mut new_serials = Vec::new();
+        for serial in bs58_serial_numbers {
+            let inserted = sqlx::query!(
+                r#"
+                    INSERT OR IGNORE INTO spent_credential
+                    (blinded_serial_number_bs58, epoch_id, reporter, reported_at)
+                    VALUES (?, ?, ?, ?)
+                "#,
+                serial,
+                epoch_id,
+                reporter,
+                reported_at
+            )
+            .execute(&mut tx)
+            .await?
+            .rows_affected();
+
+            if inserted != 0 {
+                new_serials.push(serial)
+            }
+        }
+
+        tx.commit().await?;
+        Ok(new_serials)
+    }
+
+    /// Retrieves blinded serial numbers of all credentials, issued in the provided epoch,
+    /// that have been reported as spent.
+    ///
+    /// # Arguments
+    ///
+    /// * `epoch_id`: Id of the (coconut) epoch in question.
+    async fn get_spent_credentials(&self, epoch_id: u32) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query!(
+            "SELECT blinded_serial_number_bs58 FROM spent_credential WHERE epoch_id = ?",
+            epoch_id
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+        .map(|records| {
+            records
+                .into_iter()
+                .map(|record| record.blinded_serial_number_bs58)
+                .collect()
+        })
+    }
+
+    /// Checks whether the credential with the provided blinded serial number has been reported as spent.
+    ///
+    /// # Arguments
+    ///
+    /// * `bs58_serial_number`: the blinded serial number of the credential.
+    async fn is_credential_spent(&self, bs58_serial_number: &str) -> Result<bool, sqlx::Error> {
+        sqlx::query!(
+            "SELECT EXISTS (SELECT 1 FROM spent_credential WHERE blinded_serial_number_bs58 = ?) AS 'exists'",
+            bs58_serial_number
+        )
+        .fetch_one(&self.connection_pool)
+        .await
+        .map(|result| result.exists == 1)
+    }
+
+    /// Retrieves the vote we have cast on the provided proposal, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `proposal_id`: id of the multisig proposal.
+    async fn get_proposal_vote(&self, proposal_id: i64) -> Result<Option<bool>, sqlx::Error> {
+        sqlx::query!(
+            "SELECT vote_yes FROM voted_proposal WHERE proposal_id = ?",
+            proposal_id
+        )
+        .fetch_optional(&self.connection_pool)
+        .await
+        .map(|record| record.map(|record| record.vote_yes))
+    }
+
+    /// Records the vote we have cast on the provided proposal.
+    ///
+    /// # Arguments
+    ///
+    /// * `proposal_id`: id of the multisig proposal.
+    /// * `vote_yes`: whether we have voted in favour of the proposal.
+    /// * `voted_at`: unix timestamp of the vote.
+    async fn insert_proposal_vote(
+        &self,
+        proposal_id: i64,
+        vote_yes: bool,
+        voted_at: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "INSERT OR REPLACE INTO voted_proposal (proposal_id, vote_yes, voted_at) VALUES (?, ?, ?)",
+            proposal_id,
+            vote_yes,
+            voted_at
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Error)]
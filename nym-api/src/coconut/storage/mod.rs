@@ -10,12 +10,18 @@ use nym_coconut::{Base58, BlindedSignature};
 use nym_coconut_dkg_common::types::EpochId;
 use nym_crypto::asymmetric::identity;
 use nym_validator_client::nyxd::Hash;
+use time::OffsetDateTime;
 
 pub(crate) mod manager;
 pub(crate) mod models;
 
 const DEFAULT_CREDENTIALS_PAGE_LIMIT: u32 = 100;
 
+// the spent credentials are stored alongside 32-bit epoch ids
+fn storable_epoch_id(epoch_id: EpochId) -> Result<u32, NymApiStorageError> {
+    u32::try_from(epoch_id).map_err(|_| NymApiStorageError::EpochIdOutOfRange { epoch_id })
+}
+
 #[async_trait]
 pub trait CoconutStorageExt {
     async fn get_epoch_credentials(
@@ -66,6 +72,32 @@ pub trait CoconutStorageExt {
     ) -> Result<Vec<IssuedCredential>, NymApiStorageError>;
 
     async fn increment_issued_freepasses(&self) -> Result<(), NymApiStorageError>;
+
+    async fn insert_spent_credentials(
+        &self,
+        epoch_id: EpochId,
+        bs58_serial_numbers: Vec<String>,
+        reporter: String,
+    ) -> Result<Vec<String>, NymApiStorageError>;
+
+    async fn get_spent_credentials(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<Vec<String>, NymApiStorageError>;
+
+    async fn is_credential_spent(
+        &self,
+        bs58_serial_number: &str,
+    ) -> Result<bool, NymApiStorageError>;
+
+    async fn get_proposal_vote(&self, proposal_id: u64)
+        -> Result<Option<bool>, NymApiStorageError>;
+
+    async fn insert_proposal_vote(
+        &self,
+        proposal_id: u64,
+        vote_yes: bool,
+    ) -> Result<(), NymApiStorageError>;
 }
 
 #[async_trait]
@@ -170,4 +202,60 @@ impl CoconutStorageExt for NymApiStorage {
     async fn increment_issued_freepasses(&self) -> Result<(), NymApiStorageError> {
         Ok(self.manager.increment_issued_freepasses().await?)
     }
+
+    async fn insert_spent_credentials(
+        &self,
+        epoch_id: EpochId,
+        bs58_serial_numbers: Vec<String>,
+        reporter: String,
+    ) -> Result<Vec<String>, NymApiStorageError> {
+        Ok(self
+            .manager
+            .insert_spent_credentials(
+                storable_epoch_id(epoch_id)?,
+                bs58_serial_numbers,
+                reporter,
+                OffsetDateTime::now_utc().unix_timestamp(),
+            )
+            .await?)
+    }
+
+    async fn get_spent_credentials(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<Vec<String>, NymApiStorageError> {
+        Ok(self
+            .manager
+            .get_spent_credentials(storable_epoch_id(epoch_id)?)
+            .await?)
+    }
+
+    async fn is_credential_spent(
+        &self,
+        bs58_serial_number: &str,
+    ) -> Result<bool, NymApiStorageError> {
+        Ok(self.manager.is_credential_spent(bs58_serial_number).await?)
+    }
+
+    async fn get_proposal_vote(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Option<bool>, NymApiStorageError> {
+        Ok(self.manager.get_proposal_vote(proposal_id as i64).await?)
+    }
+
+    async fn insert_proposal_vote(
+        &self,
+        proposal_id: u64,
+        vote_yes: bool,
+    ) -> Result<(), NymApiStorageError> {
+        Ok(self
+            .manager
+            .insert_proposal_vote(
+                proposal_id as i64,
+                vote_yes,
+                OffsetDateTime::now_utc().unix_timestamp(),
+            )
+            .await?)
+    }
 }
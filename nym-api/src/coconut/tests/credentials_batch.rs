@@ -0,0 +1,279 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::coconut::error::CoconutError;
+use crate::coconut::storage::CoconutStorageExt;
+use crate::coconut::tests::{voucher_fixture, TestFixture, TEST_REWARDING_VALIDATOR_ADDRESS};
+use cosmwasm_std::{coin, to_binary, Addr, CosmosMsg, Decimal, WasmMsg};
+use cw3::{Proposal, Votes};
+use nym_api_requests::coconut::{VerifyCredentialResponse, VerifyCredentialsBatchBody};
+use nym_coconut::{blind_sign, hash_to_scalar, keygen, SecretKey, VerificationKey};
+use nym_coconut_bandwidth_contract_common::msg::ExecuteMsg;
+use nym_coconut_bandwidth_contract_common::spend_credential::{
+    batch_proposal_description, SpendCredential, SpendCredentialResponse,
+};
+use nym_credentials::coconut::bandwidth::{bandwidth_credential_params, CredentialSpendingData};
+use nym_validator_client::nym_api::routes::{
+    API_VERSION, BANDWIDTH, COCONUT_ROUTES, COCONUT_VERIFY_BANDWIDTH_CREDENTIALS_BATCH,
+};
+use nym_validator_client::nyxd::AccountId;
+use rocket::http::Status;
+use std::str::FromStr;
+
+fn spending_fixture(amount: u128) -> CredentialSpendingData {
+    // note: the key is different from the one used by the api, so the credential won't verify
+    let key_pair = keygen(bandwidth_credential_params());
+    signed_spending_fixture(key_pair.secret_key(), key_pair.verification_key(), amount)
+}
+
+fn signed_spending_fixture(
+    secret_key: &SecretKey,
+    verification_key: &VerificationKey,
+    amount: u128,
+) -> CredentialSpendingData {
+    let params = bandwidth_credential_params();
+    let issuance = voucher_fixture(coin(amount, "unym"), None);
+    let sig_req = issuance.prepare_for_signing();
+    let pub_attrs_hashed = sig_req
+        .public_attributes_plain
+        .iter()
+        .map(hash_to_scalar)
+        .collect::<Vec<_>>();
+    let pub_attrs = pub_attrs_hashed.iter().collect::<Vec<_>>();
+    let blind_sig =
+        blind_sign(params, secret_key, &sig_req.blind_sign_request, &pub_attrs).unwrap();
+    let sig = blind_sig.unblind(verification_key, &sig_req.pedersen_commitments_openings);
+
+    issuance
+        .into_issued_credential(sig, 1)
+        .prepare_for_spending(verification_key)
+        .unwrap()
+}
+
+fn serial(credential: &CredentialSpendingData) -> String {
+    credential
+        .verify_credential_request
+        .blinded_serial_number_bs58()
+}
+
+impl TestFixture {
+    fn add_batch_proposal(&self, proposal_id: u64, description: String, total: u128) {
+        let msg = ExecuteMsg::ReleaseFunds {
+            funds: coin(total, "unym"),
+        };
+        let mut chain = self.chain_state.lock().unwrap();
+        let proposal = Proposal {
+            title: String::new(),
+            description,
+            msgs: vec![CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: String::new(),
+                msg: to_binary(&msg).unwrap(),
+                funds: vec![],
+            })],
+            status: cw3::Status::Open,
+            expires: cw_utils::Expiration::Never {},
+            threshold: cw_utils::Threshold::AbsolutePercentage {
+                percentage: Decimal::from_ratio(2u32, 3u32),
+            },
+            total_weight: chain.total_group_weight(),
+            votes: Votes::yes(0),
+            proposer: Addr::unchecked("proposer"),
+            deposit: None,
+            start_height: 0,
+        };
+        chain
+            .multisig_contract
+            .proposals
+            .insert(proposal_id, proposal);
+    }
+
+    fn add_in_progress_spending(&self, credential: &CredentialSpendingData, amount: u128) {
+        self.chain_state
+            .lock()
+            .unwrap()
+            .bandwidth_contract
+            .spent_credentials
+            .insert(
+                serial(credential),
+                SpendCredentialResponse::new(Some(SpendCredential::new(
+                    coin(amount, "unym"),
+                    serial(credential),
+                    Addr::unchecked("gateway"),
+                ))),
+            );
+    }
+
+    async fn verify_batch(
+        &self,
+        body: &VerifyCredentialsBatchBody,
+    ) -> Result<VerifyCredentialResponse, String> {
+        let response = self
+            .rocket
+            .post(format!(
+                "/{API_VERSION}/{COCONUT_ROUTES}/{BANDWIDTH}/{COCONUT_VERIFY_BANDWIDTH_CREDENTIALS_BATCH}"
+            ))
+            .json(body)
+            .dispatch()
+            .await;
+        let status = response.status();
+        let body = response.into_string().await.unwrap();
+        if status == Status::Ok {
+            Ok(serde_json::from_str(&body).unwrap())
+        } else {
+            Err(body)
+        }
+    }
+}
+
+#[tokio::test]
+async fn verification_of_credentials_batch() {
+    let test_fixture = TestFixture::new().await;
+    test_fixture
+        .chain_state
+        .lock()
+        .unwrap()
+        .add_member(TEST_REWARDING_VALIDATOR_ADDRESS, 100);
+    let gateway_cosmos_addr = AccountId::from_str(TEST_REWARDING_VALIDATOR_ADDRESS).unwrap();
+
+    let proposal_id = 42;
+    let empty = VerifyCredentialsBatchBody::new(vec![], proposal_id, gateway_cosmos_addr.clone());
+    assert_eq!(
+        test_fixture.verify_batch(&empty).await.unwrap_err(),
+        CoconutError::InvalidCredentialsBatchSize { got: 0, max: 100 }.to_string()
+    );
+
+    let credentials = vec![spending_fixture(100), spending_fixture(200)];
+    let serials = credentials.iter().map(serial).collect::<Vec<_>>();
+    let req =
+        VerifyCredentialsBatchBody::new(credentials.clone(), proposal_id, gateway_cosmos_addr);
+
+    assert_eq!(
+        test_fixture.verify_batch(&req).await.unwrap_err(),
+        CoconutError::IncorrectProposal {
+            reason: "proposal not found".to_string()
+        }
+        .to_string()
+    );
+
+    // proposal for a single credential rather than a batch
+    test_fixture.add_batch_proposal(proposal_id, serials[0].clone(), 300);
+    assert!(test_fixture.verify_batch(&req).await.is_err());
+
+    // proposal with only a subset of the credentials
+    test_fixture.add_batch_proposal(proposal_id, batch_proposal_description(&serials[..1]), 300);
+    assert!(test_fixture.verify_batch(&req).await.is_err());
+
+    // credentials not (yet) spent in the bandwidth contract
+    test_fixture.add_batch_proposal(proposal_id, batch_proposal_description(&serials), 300);
+    assert!(test_fixture.verify_batch(&req).await.is_err());
+    assert!(test_fixture
+        .storage
+        .get_proposal_vote(proposal_id)
+        .await
+        .unwrap()
+        .is_none());
+
+    // the credentials do not verify, so the whole batch gets rejected
+    for (credential, amount) in credentials.iter().zip([100, 200]) {
+        test_fixture.add_in_progress_spending(credential, amount)
+    }
+    let res = test_fixture.verify_batch(&req).await.unwrap();
+    assert!(!res.verification_result);
+    assert_eq!(
+        cw3::Status::Rejected,
+        test_fixture
+            .chain_state
+            .lock()
+            .unwrap()
+            .multisig_contract
+            .proposals
+            .get(&proposal_id)
+            .unwrap()
+            .status
+    );
+    assert_eq!(
+        test_fixture
+            .storage
+            .get_proposal_vote(proposal_id)
+            .await
+            .unwrap(),
+        Some(false)
+    );
+
+    // and we don't go through the verification again for the same proposal
+    test_fixture
+        .chain_state
+        .lock()
+        .unwrap()
+        .multisig_contract
+        .proposals
+        .remove(&proposal_id);
+    let res = test_fixture.verify_batch(&req).await.unwrap();
+    assert!(!res.verification_result);
+}
+
+#[tokio::test]
+async fn valid_credentials_batch_gets_accepted() {
+    let test_fixture = TestFixture::new().await;
+    test_fixture
+        .chain_state
+        .lock()
+        .unwrap()
+        .add_member(TEST_REWARDING_VALIDATOR_ADDRESS, 100);
+    let gateway_cosmos_addr = AccountId::from_str(TEST_REWARDING_VALIDATOR_ADDRESS).unwrap();
+
+    // the credentials are signed with the api's own key, so they verify correctly
+    let amounts = [100, 200, 300];
+    let credentials = amounts
+        .iter()
+        .map(|amount| {
+            signed_spending_fixture(
+                &test_fixture.coconut_secret_key,
+                &test_fixture.coconut_verification_key,
+                *amount,
+            )
+        })
+        .collect::<Vec<_>>();
+    let serials = credentials.iter().map(serial).collect::<Vec<_>>();
+    for (credential, amount) in credentials.iter().zip(amounts) {
+        test_fixture.add_in_progress_spending(credential, amount)
+    }
+
+    let proposal_id = 42;
+    let req = VerifyCredentialsBatchBody::new(credentials, proposal_id, gateway_cosmos_addr);
+
+    // the proposed amount has to match the total value of the credentials
+    test_fixture.add_batch_proposal(proposal_id, batch_proposal_description(&serials), 500);
+    let res = test_fixture.verify_batch(&req).await.unwrap();
+    assert!(!res.verification_result);
+
+    let proposal_id = 43;
+    let req = VerifyCredentialsBatchBody::new(
+        req.credentials.clone(),
+        proposal_id,
+        req.gateway_cosmos_addr.clone(),
+    );
+    test_fixture.add_batch_proposal(proposal_id, batch_proposal_description(&serials), 600);
+    let res = test_fixture.verify_batch(&req).await.unwrap();
+    assert!(res.verification_result);
+    assert_eq!(
+        cw3::Status::Passed,
+        test_fixture
+            .chain_state
+            .lock()
+            .unwrap()
+            .multisig_contract
+            .proposals
+            .get(&proposal_id)
+            .unwrap()
+            .status
+    );
+    assert_eq!(
+        test_fixture
+            .storage
+            .get_proposal_vote(proposal_id)
+            .await
+            .unwrap(),
+        Some(true)
+    );
+}
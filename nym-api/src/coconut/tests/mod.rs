@@ -60,9 +60,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use tempfile::{tempdir, TempDir};
 
+mod credentials_batch;
 pub(crate) mod fixtures;
 pub(crate) mod helpers;
 mod issued_credentials;
+mod spent_credentials;
 
 const TEST_COIN_DENOM: &str = "unym";
 const TEST_REWARDING_VALIDATOR_ADDRESS: &str = "n19lc9u84cz0yz3fww5283nucc9yvr8gsjmgeul0";
@@ -1335,6 +1337,10 @@ struct TestFixture {
     chain_state: SharedFakeChain,
     epoch: Arc<AtomicU64>,
 
+    // copy of the coconut keys used by the api, so that the tests could issue valid credentials
+    coconut_secret_key: nym_coconut::SecretKey,
+    coconut_verification_key: nym_coconut::VerificationKey,
+
     _tmp_dir: TempDir,
 }
 
@@ -1343,6 +1349,9 @@ impl TestFixture {
         let mut rng = crate::coconut::tests::fixtures::test_rng([69u8; 32]);
         let params = Parameters::new(4).unwrap();
         let coconut_keypair = nym_coconut::ttp_keygen(&params, 1, 1).unwrap().remove(0);
+        let coconut_secret_key =
+            nym_coconut::SecretKey::from_bytes(&coconut_keypair.secret_key().to_bytes()).unwrap();
+        let coconut_verification_key = coconut_keypair.verification_key().clone();
         let identity = identity::KeyPair::new(&mut rng);
         let epoch = Arc::new(AtomicU64::new(1));
         let comm_channel =
@@ -1386,6 +1395,8 @@ impl TestFixture {
             storage,
             chain_state,
             epoch,
+            coconut_secret_key,
+            coconut_verification_key,
             _tmp_dir: tmp_dir,
         }
     }
@@ -0,0 +1,133 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::coconut::state::State;
+use crate::coconut::tests::TestFixture;
+use nym_api_requests::coconut::spent_credentials::SPENT_CREDENTIALS_FILTER_EPOCHS;
+use nym_api_requests::coconut::{
+    SpentCredentialStatusResponse, SpentCredentialsBatch, SpentCredentialsFilterResponse,
+};
+use nym_crypto::asymmetric::identity;
+use nym_validator_client::nym_api::routes::{API_VERSION, BANDWIDTH, COCONUT_ROUTES};
+use rocket::http::Status;
+
+async fn get_filter_status(test_fixture: &TestFixture, epoch: u64) -> Status {
+    test_fixture
+        .rocket
+        .get(format!(
+            "/{API_VERSION}/{COCONUT_ROUTES}/{BANDWIDTH}/spent-credentials-filter/{epoch}"
+        ))
+        .dispatch()
+        .await
+        .status()
+}
+
+async fn get_filter(test_fixture: &TestFixture, epoch: u64) -> SpentCredentialsFilterResponse {
+    let response = test_fixture
+        .rocket
+        .get(format!(
+            "/{API_VERSION}/{COCONUT_ROUTES}/{BANDWIDTH}/spent-credentials-filter/{epoch}"
+        ))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    serde_json::from_str(&response.into_string().await.unwrap()).unwrap()
+}
+
+async fn is_spent(test_fixture: &TestFixture, serial: &str) -> bool {
+    let response = test_fixture
+        .rocket
+        .get(format!(
+            "/{API_VERSION}/{COCONUT_ROUTES}/{BANDWIDTH}/spent-credential/{serial}"
+        ))
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::Ok);
+    let parsed: SpentCredentialStatusResponse =
+        serde_json::from_str(&response.into_string().await.unwrap()).unwrap();
+    parsed.spent
+}
+
+#[tokio::test]
+async fn spent_credentials_filter() {
+    let test_fixture = TestFixture::new().await;
+
+    let initial = get_filter(&test_fixture, 1).await;
+    assert_eq!(initial.epoch_id, 1);
+    assert!(!initial.filter.contains("foo"));
+    assert!(!is_spent(&test_fixture, "foo").await);
+
+    let state = test_fixture.rocket.rocket().state::<State>().unwrap();
+    state
+        .record_spent_credentials(1, vec!["foo".to_string()], "gateway".to_string())
+        .await
+        .unwrap();
+
+    // the already built filter gets updated
+    let updated = get_filter(&test_fixture, 1).await;
+    assert!(updated.filter.contains("foo"));
+    assert!(is_spent(&test_fixture, "foo").await);
+    assert!(!is_spent(&test_fixture, "bar").await);
+
+    // and other epochs are not affected
+    test_fixture.set_epoch(2);
+    state
+        .record_spent_credentials(2, vec!["bar".to_string()], "gateway".to_string())
+        .await
+        .unwrap();
+    let other_epoch = get_filter(&test_fixture, 2).await;
+    assert!(other_epoch.filter.contains("bar"));
+    assert!(!get_filter(&test_fixture, 1).await.filter.contains("bar"));
+}
+
+#[tokio::test]
+async fn spent_credentials_filters_are_only_kept_for_recent_epochs() {
+    let test_fixture = TestFixture::new().await;
+    let state = test_fixture.rocket.rocket().state::<State>().unwrap();
+
+    // epochs that haven't started yet are not served (nor stored)
+    assert_eq!(get_filter_status(&test_fixture, 2).await, Status::NotFound);
+    assert_eq!(
+        get_filter_status(&test_fixture, u64::MAX).await,
+        Status::NotFound
+    );
+    assert!(state.spent_credentials.read().await.is_empty());
+
+    get_filter(&test_fixture, 1).await;
+    assert!(state.spent_credentials.read().await.contains_key(&1));
+
+    // once the epoch gets old enough, its filter is no longer available and gets evicted
+    let current = 1 + SPENT_CREDENTIALS_FILTER_EPOCHS;
+    test_fixture.set_epoch(current);
+    assert_eq!(get_filter_status(&test_fixture, 1).await, Status::NotFound);
+    get_filter(&test_fixture, current).await;
+
+    let guard = state.spent_credentials.read().await;
+    assert!(!guard.contains_key(&1));
+    assert!(guard.contains_key(&current));
+    assert_eq!(guard.len(), 1);
+}
+
+#[tokio::test]
+async fn spent_credentials_submission_requires_valid_signature() {
+    let test_fixture = TestFixture::new().await;
+    let mut rng = crate::coconut::tests::fixtures::test_rng([42u8; 32]);
+    let gateway_identity = identity::KeyPair::new(&mut rng);
+
+    let route = format!("/{API_VERSION}/{COCONUT_ROUTES}/{BANDWIDTH}/spent-credentials");
+
+    let mut batch = SpentCredentialsBatch::new_signed(&gateway_identity, 1, vec!["foo".into()]);
+    assert!(batch.verify_signature());
+
+    batch.blinded_serial_numbers.push("bar".into());
+    assert!(!batch.verify_signature());
+
+    let response = test_fixture
+        .rocket
+        .post(&route)
+        .json(&batch)
+        .dispatch()
+        .await;
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(!is_spent(&test_fixture, "foo").await);
+}
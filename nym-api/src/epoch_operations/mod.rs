@@ -14,6 +14,7 @@
 
 use crate::node_status_api::ONE_DAY;
 use crate::nym_contract_cache::cache::NymContractCache;
+use crate::support::caching::refresher::RefreshRequester;
 use crate::support::nyxd::Client;
 use crate::support::storage::NymApiStorage;
 use error::RewardingError;
@@ -34,6 +35,7 @@ mod transition_beginning;
 pub struct RewardedSetUpdater {
     nyxd_client: Client,
     nym_contract_cache: NymContractCache,
+    nym_contract_cache_refresher: RefreshRequester,
     storage: NymApiStorage,
 }
 
@@ -47,11 +49,13 @@ impl RewardedSetUpdater {
     pub(crate) fn new(
         nyxd_client: Client,
         nym_contract_cache: NymContractCache,
+        nym_contract_cache_refresher: RefreshRequester,
         storage: NymApiStorage,
     ) -> Self {
         RewardedSetUpdater {
             nyxd_client,
             nym_contract_cache,
+            nym_contract_cache_refresher,
             storage,
         }
     }
@@ -141,6 +145,9 @@ impl RewardedSetUpdater {
         self.update_rewarded_set_and_advance_epoch(interval, &all_mixnodes)
             .await?;
 
+        // don't wait for the next scheduled refresh for the caches to pick up the new epoch
+        self.nym_contract_cache_refresher.request_cache_refresh();
+
         log::info!("Purging old node statuses from the storage...");
         let cutoff = (epoch_end - 2 * ONE_DAY).unix_timestamp();
         self.storage.purge_old_statuses(cutoff).await?;
@@ -267,12 +274,14 @@ impl RewardedSetUpdater {
     pub(crate) fn start(
         nyxd_client: Client,
         nym_contract_cache: &NymContractCache,
+        nym_contract_cache_refresher: RefreshRequester,
         storage: &NymApiStorage,
         shutdown: &TaskManager,
     ) {
         let mut rewarded_set_updater = RewardedSetUpdater::new(
             nyxd_client,
             nym_contract_cache.to_owned(),
+            nym_contract_cache_refresher,
             storage.to_owned(),
         );
         let shutdown_listener = shutdown.subscribe();
@@ -21,6 +21,7 @@ use circulating_supply_api::cache::CirculatingSupplyCache;
 use clap::Parser;
 use coconut::dkg::controller::DkgController;
 use node_status_api::NodeStatusCache;
+use nym_api_requests::nym_nodes::NetworkLatency;
 use nym_bin_common::logging::setup_logging;
 use nym_config::defaults::NymNetworkDetails;
 use nym_contract_cache::cache::NymContractCache;
@@ -35,6 +36,7 @@ mod epoch_operations;
 pub(crate) mod network;
 mod network_monitor;
 pub(crate) mod node_describe_cache;
+pub(crate) mod node_latency_cache;
 pub(crate) mod node_status_api;
 pub(crate) mod nym_contract_cache;
 pub(crate) mod nym_nodes;
@@ -109,6 +111,15 @@ async fn start_nym_api_tasks(config: Config) -> anyhow::Result<ShutdownHandles>
     let circulating_supply_cache_state = rocket.state::<CirculatingSupplyCache>().unwrap();
     let maybe_storage = rocket.state::<NymApiStorage>();
     let described_nodes_state = rocket.state::<SharedCache<DescribedNodes>>().unwrap();
+    let network_latency_state = rocket.state::<SharedCache<NetworkLatency>>().unwrap();
+
+    // start all the caches first
+    let nym_contract_cache_listeners = nym_contract_cache::start_refresher(
+        &config.node_status_api,
+        nym_contract_cache_state,
+        nyxd_client.clone(),
+        &shutdown,
+    );
 
     // start note describe cache refresher
     // we should be doing the below, but can't due to our current startup structure
@@ -120,28 +131,31 @@ async fn start_nym_api_tasks(config: Config) -> anyhow::Result<ShutdownHandles>
         described_nodes_state.to_owned(),
     )
     .named("node-self-described-data-refresher")
+    // new epoch means possibly new nodes to describe
+    .with_refresh_trigger(nym_contract_cache_listeners.epoch_changes.clone())
     .start(shutdown.subscribe_named("node-self-described-data-refresher"));
 
-    // start all the caches first
-    let nym_contract_cache_listener = nym_contract_cache::start_refresher(
-        &config.node_status_api,
-        nym_contract_cache_state,
-        nyxd_client.clone(),
-        &shutdown,
-    );
+    node_latency_cache::new_refresher_with_initial_value(
+        &config.topology_cacher,
+        nym_contract_cache_state.clone(),
+        network_latency_state.to_owned(),
+    )
+    .named("node-latency-refresher")
+    .start(shutdown.subscribe_named("node-latency-refresher"));
 
     node_status_api::start_cache_refresh(
         &config.node_status_api,
         nym_contract_cache_state,
         node_status_cache_state,
         maybe_storage,
-        nym_contract_cache_listener,
+        nym_contract_cache_listeners.cache_updates,
         &shutdown,
     );
     circulating_supply_api::start_cache_refresh(
         &config.circulating_supply_cacher,
         nyxd_client.clone(),
         circulating_supply_cache_state,
+        nym_contract_cache_listeners.epoch_changes,
         &shutdown,
     );
 
@@ -180,7 +194,13 @@ async fn start_nym_api_tasks(config: Config) -> anyhow::Result<ShutdownHandles>
         // start 'rewarding' if its enabled
         if config.rewarding.enabled {
             epoch_operations::ensure_rewarding_permission(&nyxd_client).await?;
-            RewardedSetUpdater::start(nyxd_client, nym_contract_cache_state, storage, &shutdown);
+            RewardedSetUpdater::start(
+                nyxd_client,
+                nym_contract_cache_state,
+                nym_contract_cache_listeners.refresh_requester,
+                storage,
+                &shutdown,
+            );
         }
     }
 
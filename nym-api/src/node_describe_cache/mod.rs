@@ -76,7 +76,7 @@ impl NodeDescriptionProvider {
     }
 }
 
-async fn try_get_client(
+pub(crate) async fn try_get_client(
     host: &str,
     identity_key: &IdentityKey,
     port: Option<u16>,
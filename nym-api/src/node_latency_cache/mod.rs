@@ -0,0 +1,287 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node_describe_cache::{try_get_client, NodeDescribeCacheError};
+use crate::nym_contract_cache::cache::NymContractCache;
+use crate::support::caching::cache::{SharedCache, UninitialisedCache};
+use crate::support::caching::refresher::{CacheItemProvider, CacheRefresher};
+use crate::support::config;
+use futures::{stream, StreamExt};
+use nym_api_requests::nym_nodes::{LinkLatency, NetworkLatency, NodeLatency};
+use nym_contracts_common::IdentityKey;
+use nym_node_requests::api::client::{NymNodeApiClientError, NymNodeApiClientExt};
+use nym_node_requests::api::v1::metrics::models::{VerlocNodeResult, VerlocResult};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum NodeLatencyCacheError {
+    #[error("contract cache hasn't been initialised")]
+    UninitialisedContractCache {
+        #[from]
+        source: UninitialisedCache,
+    },
+
+    #[error(transparent)]
+    UnreachableNode(#[from] NodeDescribeCacheError),
+
+    #[error("failed to query verloc results of node '{node}': {source}")]
+    ApiFailure {
+        node: IdentityKey,
+
+        #[source]
+        source: NymNodeApiClientError,
+    },
+
+    #[error("node '{node}' hasn't finished any verloc measurements yet")]
+    NoMeasurements { node: IdentityKey },
+}
+
+struct NodeData {
+    host: String,
+    identity_key: IdentityKey,
+    http_api_port: Option<u16>,
+}
+
+pub struct NodeLatencyProvider {
+    contract_cache: NymContractCache,
+
+    batch_size: usize,
+}
+
+impl NodeLatencyProvider {
+    pub(crate) fn new(contract_cache: NymContractCache) -> NodeLatencyProvider {
+        NodeLatencyProvider {
+            contract_cache,
+            batch_size: config::DEFAULT_NODE_DESCRIBE_BATCH_SIZE,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+async fn try_get_verloc_results(
+    node: NodeData,
+) -> Result<(IdentityKey, Vec<VerlocNodeResult>), NodeLatencyCacheError> {
+    let client = try_get_client(&node.host, &node.identity_key, node.http_api_port).await?;
+
+    let stats =
+        client
+            .get_verloc_stats()
+            .await
+            .map_err(|err| NodeLatencyCacheError::ApiFailure {
+                node: node.identity_key.clone(),
+                source: err,
+            })?;
+
+    // prefer the latest run, unless it's still in progress
+    let results = match (stats.current, stats.previous) {
+        (VerlocResult::Data(current), _) if current.run_finished() => current.results,
+        (_, VerlocResult::Data(previous)) => previous.results,
+        _ => {
+            return Err(NodeLatencyCacheError::NoMeasurements {
+                node: node.identity_key,
+            })
+        }
+    };
+
+    Ok((node.identity_key, results))
+}
+
+fn mean_micros(rtts: &[Duration]) -> u64 {
+    if rtts.is_empty() {
+        return 0;
+    }
+    (rtts.iter().sum::<Duration>() / rtts.len() as u32).as_micros() as u64
+}
+
+/// Combines the verloc results reported by the individual nodes into per-link and per-node latencies.
+fn aggregate_measurements(reported: HashMap<IdentityKey, Vec<VerlocNodeResult>>) -> NetworkLatency {
+    // the same link might have been measured by the nodes on both of its ends
+    let mut link_rtts: BTreeMap<(IdentityKey, IdentityKey), Vec<Duration>> = BTreeMap::new();
+    for (reporter, results) in reported {
+        for result in results {
+            let Some(measurement) = result.latest_measurement else {
+                continue;
+            };
+            let target = result.node_identity.to_base58_string();
+            if target == reporter {
+                continue;
+            }
+
+            let link = if reporter < target {
+                (reporter.clone(), target)
+            } else {
+                (target, reporter.clone())
+            };
+            link_rtts.entry(link).or_default().push(measurement.mean);
+        }
+    }
+
+    let links = link_rtts
+        .into_iter()
+        .map(|((first, second), rtts)| LinkLatency {
+            first_node_identity: first,
+            second_node_identity: second,
+            mean_rtt_micros: mean_micros(&rtts),
+        })
+        .collect::<Vec<_>>();
+
+    let mut node_rtts: BTreeMap<&str, Vec<u64>> = BTreeMap::new();
+    for link in &links {
+        node_rtts
+            .entry(&link.first_node_identity)
+            .or_default()
+            .push(link.mean_rtt_micros);
+        node_rtts
+            .entry(&link.second_node_identity)
+            .or_default()
+            .push(link.mean_rtt_micros);
+    }
+
+    let nodes = node_rtts
+        .into_iter()
+        .map(|(identity, mut rtts)| {
+            rtts.sort_unstable();
+            NodeLatency {
+                node_identity: identity.to_string(),
+                median_rtt_micros: rtts[rtts.len() / 2],
+                measured_links: rtts.len() as u32,
+            }
+        })
+        .collect();
+
+    NetworkLatency { links, nodes }
+}
+
+#[async_trait]
+impl CacheItemProvider for NodeLatencyProvider {
+    type Item = NetworkLatency;
+    type Error = NodeLatencyCacheError;
+
+    async fn wait_until_ready(&self) {
+        self.contract_cache.wait_for_initial_values().await
+    }
+
+    async fn try_refresh(&self) -> Result<Self::Item, Self::Error> {
+        let mixnodes = self
+            .contract_cache
+            .mixnodes_all()
+            .await
+            .into_iter()
+            .map(|full| NodeData {
+                host: full.bond_information.mix_node.host,
+                identity_key: full.bond_information.mix_node.identity_key,
+                http_api_port: Some(full.bond_information.mix_node.http_api_port),
+            })
+            .collect::<Vec<_>>();
+
+        if mixnodes.is_empty() {
+            return Ok(NetworkLatency::default());
+        }
+
+        let reported = stream::iter(mixnodes.into_iter().map(try_get_verloc_results))
+            .buffer_unordered(self.batch_size)
+            .filter_map(|res| async move {
+                match res {
+                    Ok(results) => Some(results),
+                    Err(err) => {
+                        debug!("failed to obtain node verloc results: {err}");
+                        None
+                    }
+                }
+            })
+            .collect::<HashMap<_, _>>()
+            .await;
+
+        Ok(aggregate_measurements(reported))
+    }
+}
+
+pub(crate) fn new_refresher_with_initial_value(
+    config: &config::TopologyCacher,
+    contract_cache: NymContractCache,
+    initial: SharedCache<NetworkLatency>,
+) -> CacheRefresher<NetworkLatency, NodeLatencyCacheError> {
+    CacheRefresher::new_with_initial_value(
+        Box::new(
+            NodeLatencyProvider::new(contract_cache)
+                .with_batch_size(config.debug.node_describe_batch_size),
+        ),
+        config.debug.node_latency_caching_interval,
+        initial,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_crypto::asymmetric::identity;
+    use nym_node_requests::api::v1::metrics::models::VerlocMeasurement;
+    use rand_chacha::rand_core::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    fn identity(seed: u8) -> identity::PublicKey {
+        let mut rng = ChaCha20Rng::from_seed([seed; 32]);
+        *identity::KeyPair::new(&mut rng).public_key()
+    }
+
+    fn result(node: identity::PublicKey, mean_millis: Option<u64>) -> VerlocNodeResult {
+        VerlocNodeResult::new(
+            node,
+            mean_millis.map(|mean| VerlocMeasurement::new(&[Duration::from_millis(mean)])),
+        )
+    }
+
+    #[test]
+    fn links_measured_from_both_ends_are_averaged() {
+        let a = identity(1);
+        let b = identity(2);
+        let c = identity(3);
+
+        let reported = HashMap::from([
+            (
+                a.to_base58_string(),
+                vec![result(b, Some(10)), result(c, Some(40)), result(a, Some(1))],
+            ),
+            (
+                b.to_base58_string(),
+                vec![result(a, Some(20)), result(c, None)],
+            ),
+        ]);
+
+        let latency = aggregate_measurements(reported);
+        assert_eq!(latency.links.len(), 2);
+
+        let ab = latency
+            .links
+            .iter()
+            .find(|link| {
+                let ends = [&link.first_node_identity, &link.second_node_identity];
+                ends.contains(&&a.to_base58_string()) && ends.contains(&&b.to_base58_string())
+            })
+            .unwrap();
+        assert_eq!(ab.mean_rtt_micros, 15_000);
+
+        let node_a = latency
+            .nodes
+            .iter()
+            .find(|node| node.node_identity == a.to_base58_string())
+            .unwrap();
+        assert_eq!(node_a.measured_links, 2);
+        assert_eq!(node_a.median_rtt_micros, 40_000);
+
+        let node_c = latency
+            .nodes
+            .iter()
+            .find(|node| node.node_identity == c.to_base58_string())
+            .unwrap();
+        assert_eq!(node_c.measured_links, 1);
+        assert_eq!(node_c.median_rtt_micros, 40_000);
+    }
+}
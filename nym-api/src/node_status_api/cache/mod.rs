@@ -11,6 +11,7 @@ use rocket::fairing::AdHoc;
 use std::collections::HashMap;
 use std::{sync::Arc, time::Duration};
 use thiserror::Error;
+use ::time::OffsetDateTime;
 use tokio::sync::RwLockReadGuard;
 use tokio::{sync::RwLock, time};
 
@@ -162,6 +163,18 @@ impl NodeStatusCache {
         gateways.get(gateway_id).cloned()
     }
 
+    /// Returns the time of the last update of the cache, if it has been initialised.
+    pub(crate) async fn last_refreshed(&self) -> Option<OffsetDateTime> {
+        let timestamp = self.get(|c| &c.mixnodes_annotated).await?.timestamp();
+
+        // uninitialised caches are timestamped with the unix epoch
+        if timestamp == OffsetDateTime::UNIX_EPOCH {
+            None
+        } else {
+            Some(timestamp)
+        }
+    }
+
     pub(crate) async fn inclusion_probabilities(&self) -> Option<Cache<InclusionProbabilities>> {
         self.get_owned(|c| c.inclusion_probabilities.clone_cache())
             .await
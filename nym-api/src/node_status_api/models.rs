@@ -414,6 +414,9 @@ pub enum NymApiStorageError {
     #[error("experienced internal storage error")]
     DatabaseInconsistency { reason: String },
 
+    #[error("epoch {epoch_id} is outside the range that can be stored")]
+    EpochIdOutOfRange { epoch_id: u64 },
+
     // this one would never be returned to users since it's only possible on startup
     #[error("failed to perform startup SQL migration - {0}")]
     StartupMigrationFailure(#[from] sqlx::migrate::MigrateError),
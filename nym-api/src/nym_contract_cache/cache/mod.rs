@@ -18,6 +18,7 @@ use std::{
     },
     time::Duration,
 };
+use ::time::OffsetDateTime;
 use tokio::sync::RwLock;
 use tokio::time;
 
@@ -305,6 +306,15 @@ impl NymContractCache {
         self.initialised.load(Ordering::Relaxed)
     }
 
+    /// Returns the time of the last successful refresh of the cache, if it has been initialised.
+    pub(crate) async fn last_refreshed(&self) -> Option<OffsetDateTime> {
+        if !self.initialised() {
+            return None;
+        }
+        // all the fields are updated at the same time
+        Some(self.inner.read().await.mixnodes.timestamp())
+    }
+
     pub(crate) async fn wait_for_initial_values(&self) {
         let initialisation_backoff = Duration::from_secs(5);
         loop {
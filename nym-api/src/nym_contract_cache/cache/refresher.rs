@@ -4,9 +4,10 @@
 use super::NymContractCache;
 use crate::nym_contract_cache::cache::data::{CachedContractInfo, CachedContractsInfo};
 use crate::nyxd::Client;
+use crate::support::caching::refresher::RefreshRequester;
 use crate::support::caching::CacheNotification;
 use anyhow::Result;
-use nym_mixnet_contract_common::{MixId, MixNodeDetails, RewardedSetNodeStatus};
+use nym_mixnet_contract_common::{EpochId, MixId, MixNodeDetails, RewardedSetNodeStatus};
 use nym_task::TaskClient;
 use nym_validator_client::nyxd::contract_traits::{
     MixnetQueryClient, NymContractsProvider, VestingQueryClient,
@@ -22,6 +23,11 @@ pub struct NymContractCacheRefresher {
 
     // Notify listeners that the cache has been updated
     update_notifier: watch::Sender<CacheNotification>,
+
+    // Notify listeners that a new epoch has started (and thus the rewarded set might have changed)
+    epoch_notifier: watch::Sender<EpochId>,
+
+    refresh_requester: RefreshRequester,
 }
 
 impl NymContractCacheRefresher {
@@ -31,11 +37,14 @@ impl NymContractCacheRefresher {
         cache: NymContractCache,
     ) -> Self {
         let (tx, _) = watch::channel(CacheNotification::Start);
+        let (epoch_tx, _) = watch::channel(0);
         NymContractCacheRefresher {
             nyxd_client,
             cache,
             caching_interval,
             update_notifier: tx,
+            epoch_notifier: epoch_tx,
+            refresh_requester: Default::default(),
         }
     }
 
@@ -43,6 +52,15 @@ impl NymContractCacheRefresher {
         self.update_notifier.subscribe()
     }
 
+    /// Returns a receiver holding the current absolute epoch id that gets notified whenever it changes.
+    pub fn subscribe_epoch_changes(&self) -> watch::Receiver<EpochId> {
+        self.epoch_notifier.subscribe()
+    }
+
+    pub(crate) fn refresh_requester(&self) -> RefreshRequester {
+        self.refresh_requester.clone()
+    }
+
     async fn get_nym_contracts_info(&self) -> Result<CachedContractsInfo> {
         use crate::query_guard;
 
@@ -108,6 +126,7 @@ impl NymContractCacheRefresher {
     async fn refresh(&self) -> Result<()> {
         let rewarding_params = self.nyxd_client.get_current_rewarding_parameters().await?;
         let current_interval = self.nyxd_client.get_current_interval().await?.interval;
+        let current_epoch = current_interval.current_epoch_absolute_id();
 
         let mixnodes = self.nyxd_client.get_mixnodes().await?;
         let gateways = self.nyxd_client.get_gateways().await?;
@@ -144,6 +163,18 @@ impl NymContractCacheRefresher {
             warn!("Failed to notify validator cache refresh: {err}");
         }
 
+        // the rewarded set is always updated alongside advancing the epoch,
+        // so this is also the signal for anything that depends on it
+        self.epoch_notifier.send_if_modified(|epoch| {
+            if *epoch != current_epoch {
+                info!("observed epoch change from {} to {current_epoch}", *epoch);
+                *epoch = current_epoch;
+                true
+            } else {
+                false
+            }
+        });
+
         Ok(())
     }
 
@@ -178,23 +209,11 @@ impl NymContractCacheRefresher {
         let mut interval = time::interval(self.caching_interval);
         while !shutdown.is_shutdown() {
             tokio::select! {
-                _ = interval.tick() => {
-                    tokio::select! {
-                        biased;
-                        _ = shutdown.recv() => {
-                            trace!("ValidatorCacheRefresher: Received shutdown");
-                        }
-                        ret = self.refresh() => {
-                            if let Err(err) = ret {
-                                error!("Failed to refresh validator cache - {err}");
-                            } else {
-                                // relaxed memory ordering is fine here. worst case scenario network monitor
-                                // will just have to wait for an additional backoff to see the change.
-                                // And so this will not really incur any performance penalties by setting it every loop iteration
-                                self.cache.initialised.store(true, Ordering::Relaxed)
-                            }
-                        }
-                    }
+                _ = interval.tick() => self.refresh_cache(&mut shutdown).await,
+                _ = self.refresh_requester.requested() => {
+                    debug!("received an on-demand validator cache refresh request");
+                    self.refresh_cache(&mut shutdown).await;
+                    interval.reset();
                 }
                 _ = shutdown.recv() => {
                     trace!("ValidatorCacheRefresher: Received shutdown");
@@ -202,4 +221,26 @@ impl NymContractCacheRefresher {
             }
         }
     }
+
+    async fn refresh_cache(&self, shutdown: &mut TaskClient) {
+        // any requests made before we started refreshing are going to be covered by this refresh
+        self.refresh_requester.clear();
+
+        tokio::select! {
+            biased;
+            _ = shutdown.recv() => {
+                trace!("ValidatorCacheRefresher: Received shutdown");
+            }
+            ret = self.refresh() => {
+                if let Err(err) = ret {
+                    error!("Failed to refresh validator cache - {err}");
+                } else {
+                    // relaxed memory ordering is fine here. worst case scenario network monitor
+                    // will just have to wait for an additional backoff to see the change.
+                    // And so this will not really incur any performance penalties by setting it every loop iteration
+                    self.cache.initialised.store(true, Ordering::Relaxed)
+                }
+            }
+        }
+    }
 }
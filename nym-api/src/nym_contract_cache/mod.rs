@@ -2,12 +2,16 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::nym_contract_cache::cache::NymContractCache;
-use crate::support::{self, config, nyxd};
+use crate::support::caching::refresher::RefreshRequester;
+use crate::support::caching::CacheNotification;
+use crate::support::{config, nyxd};
+use nym_mixnet_contract_common::EpochId;
 use nym_task::TaskManager;
 use okapi::openapi3::OpenApi;
 use rocket::Route;
 use rocket_okapi::openapi_get_routes_spec;
 use rocket_okapi::settings::OpenApiSettings;
+use tokio::sync::watch;
 
 use self::cache::refresher::NymContractCacheRefresher;
 
@@ -30,20 +34,36 @@ pub(crate) fn nym_contract_cache_routes(settings: &OpenApiSettings) -> (Vec<Rout
     ]
 }
 
+/// Handles allowing other tasks to react to the changes of the nym contract cache
+/// (and, by extension, of the chain state) or to request its immediate refresh.
+pub(crate) struct NymContractCacheListeners {
+    /// Notified every time the cache gets refreshed.
+    pub(crate) cache_updates: watch::Receiver<CacheNotification>,
+
+    /// Notified every time a new epoch starts, which is also when the rewarded set gets updated.
+    pub(crate) epoch_changes: watch::Receiver<EpochId>,
+
+    pub(crate) refresh_requester: RefreshRequester,
+}
+
 pub(crate) fn start_refresher(
     config: &config::NodeStatusAPI,
     nym_contract_cache_state: &NymContractCache,
     nyxd_client: nyxd::Client,
     shutdown: &TaskManager,
-) -> tokio::sync::watch::Receiver<support::caching::CacheNotification> {
+) -> NymContractCacheListeners {
     let nym_contract_cache_refresher = NymContractCacheRefresher::new(
         nyxd_client,
         config.debug.caching_interval,
         nym_contract_cache_state.to_owned(),
     );
-    let nym_contract_cache_listener = nym_contract_cache_refresher.subscribe();
+    let listeners = NymContractCacheListeners {
+        cache_updates: nym_contract_cache_refresher.subscribe(),
+        epoch_changes: nym_contract_cache_refresher.subscribe_epoch_changes(),
+        refresh_requester: nym_contract_cache_refresher.refresh_requester(),
+    };
     let shutdown_listener = shutdown.subscribe();
     tokio::spawn(async move { nym_contract_cache_refresher.run(shutdown_listener).await });
 
-    nym_contract_cache_listener
+    listeners
 }
@@ -28,5 +28,6 @@ pub(crate) fn nym_node_routes_next(settings: &OpenApiSettings) -> (Vec<Route>, O
         unstable_routes::mixnodes_basic,
         unstable_routes::mixnodes_expanded,
         unstable_routes::mixnodes_detailed,
+        unstable_routes::mixnodes_latency,
     ]
 }
@@ -5,16 +5,19 @@ use crate::node_describe_cache::DescribedNodes;
 use crate::node_status_api::models::ErrorResponse;
 use crate::node_status_api::NodeStatusCache;
 use crate::support::caching::cache::SharedCache;
+use crate::support::http::helpers::PaginationRequest;
+use nym_api_requests::models::{GatewayBondAnnotated, MixNodeBondAnnotated, NymNodeDescription};
 use nym_api_requests::nym_nodes::{
-    CachedNodesResponse, FullFatNode, NodeRoleQueryParam, SemiSkimmedNode, SkimmedNode,
+    CachedNetworkLatencyResponse, CachedNodesResponse, FullFatNode, NetworkLatency,
+    NodeRoleQueryParam, PaginatedCachedNodesResponse, SemiSkimmedNode, SkimmedNode,
 };
+use nym_api_requests::pagination::{PaginatedResponse, Pagination};
 use nym_bin_common::version_checker;
 use rocket::http::Status;
 use rocket::serde::json::Json;
 use rocket::State;
 use rocket_okapi::openapi;
 use std::cmp::min;
-use std::ops::Deref;
 
 /*
    routes:
@@ -32,6 +35,9 @@ use std::ops::Deref;
 
 */
 
+/// Returns the basic information of the nodes of the specified role.
+/// Exit gateways are the gateways that have announced both the network requester and the ip packet router.
+/// The role has to be specified, since mixnodes and gateways are still served from separate caches.
 #[openapi(tag = "Unstable Nym Nodes")]
 #[get("/skimmed?<role>&<semver_compatibility>")]
 pub async fn nodes_basic(
@@ -40,63 +46,91 @@ pub async fn nodes_basic(
     role: Option<NodeRoleQueryParam>,
     semver_compatibility: Option<String>,
 ) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
-    if let Some(role) = role {
-        match role {
-            NodeRoleQueryParam::ActiveMixnode => {
-                return mixnodes_basic(status_cache, semver_compatibility).await
-            }
-            NodeRoleQueryParam::EntryGateway => {
-                return gateways_basic(status_cache, describe_cache, semver_compatibility).await
-            }
-            _ => {}
+    match role.ok_or_else(missing_role)? {
+        NodeRoleQueryParam::ActiveMixnode => {
+            mixnodes_basic(status_cache, describe_cache, semver_compatibility).await
+        }
+        NodeRoleQueryParam::EntryGateway => {
+            basic_gateways(status_cache, describe_cache, semver_compatibility, false).await
+        }
+        NodeRoleQueryParam::ExitGateway => {
+            basic_gateways(status_cache, describe_cache, semver_compatibility, true).await
         }
     }
-
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
 }
 
+/// Returns the expanded information of the nodes of the specified role.
+/// See [`nodes_basic`] for the details of the role filtering.
 #[openapi(tag = "Unstable Nym Nodes")]
-#[get("/semi-skimmed?<role>&<semver_compatibility>")]
+#[get("/semi-skimmed?<role>&<semver_compatibility>&<pagination..>")]
 pub async fn nodes_expanded(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     role: Option<NodeRoleQueryParam>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
-    if let Some(role) = role {
-        match role {
-            NodeRoleQueryParam::ActiveMixnode => {
-                return mixnodes_expanded(cache, semver_compatibility).await
-            }
-            NodeRoleQueryParam::EntryGateway => {
-                return gateways_expanded(cache, semver_compatibility).await
-            }
-            _ => {}
+    pagination: PaginationRequest,
+) -> Result<Json<PaginatedCachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
+    let exit_only = match role.ok_or_else(missing_role)? {
+        NodeRoleQueryParam::ActiveMixnode => {
+            return mixnodes_expanded(
+                status_cache,
+                describe_cache,
+                semver_compatibility,
+                pagination,
+            )
+            .await
         }
-    }
+        NodeRoleQueryParam::EntryGateway => false,
+        NodeRoleQueryParam::ExitGateway => true,
+    };
 
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
+    described_gateways(
+        status_cache,
+        describe_cache,
+        semver_compatibility,
+        pagination,
+        exit_only,
+        SemiSkimmedNode::from_described_gateway,
+    )
+    .await
+    .map(Json)
 }
 
+/// Returns the detailed information of the nodes of the specified role.
+/// See [`nodes_basic`] for the details of the role filtering.
 #[openapi(tag = "Unstable Nym Nodes")]
-#[get("/full-fat?<role>&<semver_compatibility>")]
+#[get("/full-fat?<role>&<semver_compatibility>&<pagination..>")]
 pub async fn nodes_detailed(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     role: Option<NodeRoleQueryParam>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<FullFatNode>>, ErrorResponse> {
-    if let Some(role) = role {
-        match role {
-            NodeRoleQueryParam::ActiveMixnode => {
-                return mixnodes_detailed(cache, semver_compatibility).await
-            }
-            NodeRoleQueryParam::EntryGateway => {
-                return gateways_detailed(cache, semver_compatibility).await
-            }
-            _ => {}
+    pagination: PaginationRequest,
+) -> Result<Json<PaginatedCachedNodesResponse<FullFatNode>>, ErrorResponse> {
+    let exit_only = match role.ok_or_else(missing_role)? {
+        NodeRoleQueryParam::ActiveMixnode => {
+            return mixnodes_detailed(
+                status_cache,
+                describe_cache,
+                semver_compatibility,
+                pagination,
+            )
+            .await
         }
-    }
+        NodeRoleQueryParam::EntryGateway => false,
+        NodeRoleQueryParam::ExitGateway => true,
+    };
 
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
+    described_gateways(
+        status_cache,
+        describe_cache,
+        semver_compatibility,
+        pagination,
+        exit_only,
+        FullFatNode::from_described_gateway,
+    )
+    .await
+    .map(Json)
 }
 
 #[openapi(tag = "Unstable Nym Nodes")]
@@ -106,94 +140,73 @@ pub async fn gateways_basic(
     describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
 ) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
-    let gateways_cache = status_cache
-        .gateways_cache()
-        .await
-        .ok_or(ErrorResponse::new(
-            "could not obtain gateways cache",
-            Status::InternalServerError,
-        ))?;
-
-    if gateways_cache.is_empty() {
-        return Ok(Json(CachedNodesResponse {
-            refreshed_at: gateways_cache.timestamp().into(),
-            nodes: vec![],
-        }));
-    }
-
-    // if the self describe cache is unavailable don't try to use self-describe data
-    let Ok(self_descriptions) = describe_cache.get().await else {
-        return Ok(Json(CachedNodesResponse {
-            refreshed_at: gateways_cache.timestamp().into(),
-            nodes: gateways_cache.values().map(Into::into).collect(),
-        }));
-    };
-
-    let refreshed_at = min(gateways_cache.timestamp(), self_descriptions.timestamp());
-
-    // the same comment holds as with `get_gateways_described`.
-    // this is inefficient and will have to get refactored with directory v3
-    Ok(Json(CachedNodesResponse {
-        refreshed_at: refreshed_at.into(),
-        nodes: gateways_cache
-            .values()
-            .filter(|annotated_bond| {
-                if let Some(semver_compatibility) = semver_compatibility.as_ref() {
-                    version_checker::is_minor_version_compatible(
-                        &annotated_bond.gateway_bond.gateway.version,
-                        semver_compatibility,
-                    )
-                } else {
-                    true
-                }
-            })
-            .map(|annotated_bond| {
-                SkimmedNode::from_described_gateway(
-                    annotated_bond,
-                    self_descriptions.deref().get(annotated_bond.identity()),
-                )
-            })
-            .collect(),
-    }))
+    basic_gateways(status_cache, describe_cache, semver_compatibility, false).await
 }
 
 #[openapi(tag = "Unstable Nym Nodes")]
-#[get("/gateways/semi-skimmed?<semver_compatibility>")]
+#[get("/gateways/semi-skimmed?<semver_compatibility>&<pagination..>")]
 pub async fn gateways_expanded(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
-    let _ = cache;
-    let _ = semver_compatibility;
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
+    pagination: PaginationRequest,
+) -> Result<Json<PaginatedCachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
+    described_gateways(
+        status_cache,
+        describe_cache,
+        semver_compatibility,
+        pagination,
+        false,
+        SemiSkimmedNode::from_described_gateway,
+    )
+    .await
+    .map(Json)
 }
 
 #[openapi(tag = "Unstable Nym Nodes")]
-#[get("/gateways/full-fat?<semver_compatibility>")]
+#[get("/gateways/full-fat?<semver_compatibility>&<pagination..>")]
 pub async fn gateways_detailed(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<FullFatNode>>, ErrorResponse> {
-    let _ = cache;
-    let _ = semver_compatibility;
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
+    pagination: PaginationRequest,
+) -> Result<Json<PaginatedCachedNodesResponse<FullFatNode>>, ErrorResponse> {
+    described_gateways(
+        status_cache,
+        describe_cache,
+        semver_compatibility,
+        pagination,
+        false,
+        FullFatNode::from_described_gateway,
+    )
+    .await
+    .map(Json)
 }
 
 #[openapi(tag = "Unstable Nym Nodes")]
 #[get("/mixnodes/skimmed?<semver_compatibility>")]
 pub async fn mixnodes_basic(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
 ) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
-    let mixnodes_cache = cache
+    let mixnodes_cache = status_cache
         .active_mixnodes_cache()
         .await
         .ok_or(ErrorResponse::new(
             "could not obtain mixnodes cache",
             Status::InternalServerError,
         ))?;
+
+    // if the self describe cache is unavailable fallback to the bonded sphinx keys
+    let self_descriptions = describe_cache.get().await.ok();
+    let refreshed_at = match &self_descriptions {
+        Some(self_descriptions) => min(mixnodes_cache.timestamp(), self_descriptions.timestamp()),
+        None => mixnodes_cache.timestamp(),
+    };
+
     Ok(Json(CachedNodesResponse {
-        refreshed_at: mixnodes_cache.timestamp().into(),
+        refreshed_at: refreshed_at.into(),
         nodes: mixnodes_cache
             .iter()
             .filter(|annotated_bond| {
@@ -210,29 +223,337 @@ pub async fn mixnodes_basic(
                     true
                 }
             })
-            .map(Into::into)
+            .map(|annotated_bond| {
+                let description = self_descriptions
+                    .as_ref()
+                    .and_then(|descriptions| descriptions.get(annotated_bond.identity_key()));
+                SkimmedNode::from_described_mixnode(annotated_bond, description)
+            })
             .collect(),
     }))
 }
 
 #[openapi(tag = "Unstable Nym Nodes")]
-#[get("/mixnodes/semi-skimmed?<semver_compatibility>")]
+#[get("/mixnodes/semi-skimmed?<semver_compatibility>&<pagination..>")]
 pub async fn mixnodes_expanded(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
-    let _ = cache;
-    let _ = semver_compatibility;
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
+    pagination: PaginationRequest,
+) -> Result<Json<PaginatedCachedNodesResponse<SemiSkimmedNode>>, ErrorResponse> {
+    described_mixnodes(
+        status_cache,
+        describe_cache,
+        semver_compatibility,
+        pagination,
+        SemiSkimmedNode::from_described_mixnode,
+    )
+    .await
+    .map(Json)
 }
 
 #[openapi(tag = "Unstable Nym Nodes")]
-#[get("/mixnodes/full-fat?<semver_compatibility>")]
+#[get("/mixnodes/full-fat?<semver_compatibility>&<pagination..>")]
 pub async fn mixnodes_detailed(
-    cache: &State<NodeStatusCache>,
+    status_cache: &State<NodeStatusCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
+    semver_compatibility: Option<String>,
+    pagination: PaginationRequest,
+) -> Result<Json<PaginatedCachedNodesResponse<FullFatNode>>, ErrorResponse> {
+    described_mixnodes(
+        status_cache,
+        describe_cache,
+        semver_compatibility,
+        pagination,
+        FullFatNode::from_described_mixnode,
+    )
+    .await
+    .map(Json)
+}
+
+#[openapi(tag = "Unstable Nym Nodes")]
+#[get("/mixnodes/latency")]
+pub async fn mixnodes_latency(
+    latency_cache: &State<SharedCache<NetworkLatency>>,
+) -> Result<Json<CachedNetworkLatencyResponse>, ErrorResponse> {
+    let latency = latency_cache.get().await.map_err(|_| {
+        ErrorResponse::new(
+            "network latency data is not yet available",
+            Status::ServiceUnavailable,
+        )
+    })?;
+
+    Ok(Json(CachedNetworkLatencyResponse {
+        refreshed_at: latency.timestamp().into(),
+        latency: (**latency).clone(),
+    }))
+}
+
+const DEFAULT_NODES_PAGE_SIZE: u32 = 100;
+const MAX_NODES_PAGE_SIZE: u32 = 1000;
+
+fn paginate<N, T, F>(nodes: Vec<N>, pagination: PaginationRequest, f: F) -> PaginatedResponse<T>
+where
+    F: Fn(N) -> T,
+{
+    // we're paging from page 0, the same way as with the test results
+    let page = pagination.page.unwrap_or_default();
+    let per_page = pagination
+        .per_page
+        .unwrap_or(DEFAULT_NODES_PAGE_SIZE)
+        .clamp(1, MAX_NODES_PAGE_SIZE) as usize;
+
+    let total = nodes.len();
+    let data = nodes
+        .into_iter()
+        .skip((page as usize).saturating_mul(per_page))
+        .take(per_page)
+        .map(f)
+        .collect::<Vec<_>>();
+
+    PaginatedResponse {
+        pagination: Pagination {
+            total,
+            page,
+            size: data.len(),
+        },
+        data,
+    }
+}
+
+fn missing_role() -> ErrorResponse {
+    ErrorResponse::new(
+        "the node role has to be specified (active-mixnode, entry-gateway or exit-gateway)",
+        Status::BadRequest,
+    )
+}
+
+/// Gateways are considered to be exit gateways if they run both of the exit service providers.
+fn is_exit_gateway(description: Option<&NymNodeDescription>) -> bool {
+    description
+        .map(|description| {
+            description.network_requester.is_some() && description.ip_packet_router.is_some()
+        })
+        .unwrap_or_default()
+}
+
+fn is_semver_compatible(version: &str, semver_compatibility: Option<&str>) -> bool {
+    if let Some(semver_compatibility) = semver_compatibility {
+        version_checker::is_minor_version_compatible(version, semver_compatibility)
+    } else {
+        true
+    }
+}
+
+async fn basic_gateways(
+    status_cache: &NodeStatusCache,
+    describe_cache: &SharedCache<DescribedNodes>,
+    semver_compatibility: Option<String>,
+    exit_only: bool,
+) -> Result<Json<CachedNodesResponse<SkimmedNode>>, ErrorResponse> {
+    let gateways_cache = status_cache
+        .gateways_cache()
+        .await
+        .ok_or(ErrorResponse::new(
+            "could not obtain gateways cache",
+            Status::InternalServerError,
+        ))?;
+
+    // if the self describe cache is unavailable don't try to use self-describe data
+    // (which also means we can't tell which gateways are the exit ones)
+    let self_descriptions = describe_cache.get().await.ok();
+    let refreshed_at = match &self_descriptions {
+        Some(self_descriptions) => min(gateways_cache.timestamp(), self_descriptions.timestamp()),
+        None => gateways_cache.timestamp(),
+    };
+
+    // the same comment holds as with `get_gateways_described`.
+    // this is inefficient and will have to get refactored with directory v3
+    Ok(Json(CachedNodesResponse {
+        refreshed_at: refreshed_at.into(),
+        nodes: gateways_cache
+            .values()
+            .filter(|annotated_bond| {
+                is_semver_compatible(
+                    &annotated_bond.gateway_bond.gateway.version,
+                    semver_compatibility.as_deref(),
+                )
+            })
+            .filter_map(|annotated_bond| {
+                let description = self_descriptions
+                    .as_ref()
+                    .and_then(|descriptions| descriptions.get(annotated_bond.identity()));
+                if exit_only && !is_exit_gateway(description) {
+                    return None;
+                }
+                Some(SkimmedNode::from_described_gateway(
+                    annotated_bond,
+                    description,
+                ))
+            })
+            .collect(),
+    }))
+}
+
+async fn described_gateways<T, F>(
+    status_cache: &NodeStatusCache,
+    describe_cache: &SharedCache<DescribedNodes>,
+    semver_compatibility: Option<String>,
+    pagination: PaginationRequest,
+    exit_only: bool,
+    f: F,
+) -> Result<PaginatedCachedNodesResponse<T>, ErrorResponse>
+where
+    F: Fn(&GatewayBondAnnotated, Option<&NymNodeDescription>) -> T,
+{
+    let gateways_cache = status_cache
+        .gateways_cache()
+        .await
+        .ok_or(ErrorResponse::new(
+            "could not obtain gateways cache",
+            Status::InternalServerError,
+        ))?;
+
+    // if the self describe cache is unavailable don't try to use self-describe data
+    let self_descriptions = describe_cache.get().await.ok();
+    let refreshed_at = match &self_descriptions {
+        Some(self_descriptions) => min(gateways_cache.timestamp(), self_descriptions.timestamp()),
+        None => gateways_cache.timestamp(),
+    };
+
+    // the underlying cache is a map, so make sure the ordering is stable between the pages
+    let mut gateways = gateways_cache
+        .values()
+        .filter(|annotated_bond| {
+            is_semver_compatible(
+                &annotated_bond.gateway_bond.gateway.version,
+                semver_compatibility.as_deref(),
+            )
+        })
+        .map(|annotated_bond| {
+            let description = self_descriptions
+                .as_ref()
+                .and_then(|descriptions| descriptions.get(annotated_bond.identity()));
+            (annotated_bond, description)
+        })
+        .filter(|(_, description)| !exit_only || is_exit_gateway(*description))
+        .collect::<Vec<_>>();
+    gateways.sort_by(|(a, _), (b, _)| a.identity().cmp(b.identity()));
+
+    Ok(PaginatedCachedNodesResponse {
+        refreshed_at: refreshed_at.into(),
+        nodes: paginate(gateways, pagination, |(annotated_bond, description)| {
+            f(annotated_bond, description)
+        }),
+    })
+}
+
+async fn described_mixnodes<T, F>(
+    status_cache: &NodeStatusCache,
+    describe_cache: &SharedCache<DescribedNodes>,
     semver_compatibility: Option<String>,
-) -> Result<Json<CachedNodesResponse<FullFatNode>>, ErrorResponse> {
-    let _ = cache;
-    let _ = semver_compatibility;
-    Err(ErrorResponse::new("unimplemented", Status::NotImplemented))
+    pagination: PaginationRequest,
+    f: F,
+) -> Result<PaginatedCachedNodesResponse<T>, ErrorResponse>
+where
+    F: Fn(&MixNodeBondAnnotated, Option<&NymNodeDescription>) -> T,
+{
+    let mixnodes_cache = status_cache
+        .active_mixnodes_cache()
+        .await
+        .ok_or(ErrorResponse::new(
+            "could not obtain mixnodes cache",
+            Status::InternalServerError,
+        ))?;
+
+    // if the self describe cache is unavailable don't try to use self-describe data
+    let self_descriptions = describe_cache.get().await.ok();
+    let refreshed_at = match &self_descriptions {
+        Some(self_descriptions) => min(mixnodes_cache.timestamp(), self_descriptions.timestamp()),
+        None => mixnodes_cache.timestamp(),
+    };
+
+    let mut mixnodes = mixnodes_cache
+        .iter()
+        .filter(|annotated_bond| {
+            is_semver_compatible(
+                &annotated_bond.mix_node().version,
+                semver_compatibility.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    mixnodes.sort_by_key(|annotated_bond| annotated_bond.mix_id());
+
+    Ok(PaginatedCachedNodesResponse {
+        refreshed_at: refreshed_at.into(),
+        nodes: paginate(mixnodes, pagination, |annotated_bond| {
+            let description = self_descriptions
+                .as_ref()
+                .and_then(|descriptions| descriptions.get(annotated_bond.identity_key()));
+            f(annotated_bond, description)
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pagination(page: Option<u32>, per_page: Option<u32>) -> PaginationRequest {
+        PaginationRequest { page, per_page }
+    }
+
+    #[test]
+    fn paginate_uses_default_page_size() {
+        let nodes = (0..250).collect::<Vec<u32>>();
+        let res = paginate(nodes, pagination(None, None), |n| n);
+
+        assert_eq!(res.pagination.total, 250);
+        assert_eq!(res.pagination.page, 0);
+        assert_eq!(res.pagination.size, DEFAULT_NODES_PAGE_SIZE as usize);
+        assert_eq!(res.data, (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn paginate_clamps_page_size() {
+        let nodes = (0..2500).collect::<Vec<u32>>();
+        let res = paginate(nodes.clone(), pagination(Some(1), Some(5000)), |n| n);
+        assert_eq!(res.pagination.size, MAX_NODES_PAGE_SIZE as usize);
+        assert_eq!(res.data, (1000..2000).collect::<Vec<_>>());
+
+        // empty pages are not allowed either
+        let res = paginate(nodes, pagination(Some(3), Some(0)), |n| n);
+        assert_eq!(res.pagination.size, 1);
+        assert_eq!(res.data, vec![3]);
+    }
+
+    #[test]
+    fn paginate_returns_partial_last_page() {
+        let nodes = (0..250).collect::<Vec<u32>>();
+        let res = paginate(nodes.clone(), pagination(Some(2), None), |n| n * 2);
+        assert_eq!(res.pagination.total, 250);
+        assert_eq!(res.pagination.page, 2);
+        assert_eq!(res.pagination.size, 50);
+        assert_eq!(res.data, (200..250).map(|n| n * 2).collect::<Vec<_>>());
+
+        // going past the last page yields no data, but still reports the total
+        let res = paginate(nodes, pagination(Some(3), None), |n| n);
+        assert_eq!(res.pagination.total, 250);
+        assert_eq!(res.pagination.size, 0);
+        assert!(res.data.is_empty());
+    }
+
+    #[test]
+    fn semver_compatibility() {
+        // everything is compatible if no requirement is specified
+        assert!(is_semver_compatible("1.1.0", None));
+        assert!(is_semver_compatible("not-a-version", None));
+
+        assert!(is_semver_compatible("1.1.0", Some("1.1.5")));
+        assert!(is_semver_compatible("1.1.7", Some("1.1.0")));
+        assert!(!is_semver_compatible("1.2.0", Some("1.1.0")));
+        assert!(!is_semver_compatible("2.1.0", Some("1.1.0")));
+        assert!(!is_semver_compatible("not-a-version", Some("1.1.0")));
+        assert!(!is_semver_compatible("1.1.0", Some("not-a-version")));
+    }
 }
@@ -1,9 +1,14 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::circulating_supply_api::cache::CirculatingSupplyCache;
+use crate::node_describe_cache::DescribedNodes;
 use crate::node_status_api::models::ErrorResponse;
+use crate::node_status_api::NodeStatusCache;
+use crate::nym_contract_cache::cache::NymContractCache;
 use crate::status::ApiStatusState;
-use nym_api_requests::models::{ApiHealthResponse, SignerInformationResponse};
+use crate::support::caching::cache::SharedCache;
+use nym_api_requests::models::{ApiHealthResponse, CacheAge, SignerInformationResponse};
 use nym_bin_common::build_information::BinaryBuildInformationOwned;
 use nym_coconut::Base58;
 use rocket::http::Status;
@@ -13,9 +18,33 @@ use rocket_okapi::openapi;
 
 #[openapi(tag = "Api Status")]
 #[get("/health")]
-pub(crate) async fn health(state: &State<ApiStatusState>) -> Json<ApiHealthResponse> {
+pub(crate) async fn health(
+    state: &State<ApiStatusState>,
+    contract_cache: &State<NymContractCache>,
+    node_status_cache: &State<NodeStatusCache>,
+    circulating_supply_cache: &State<CirculatingSupplyCache>,
+    describe_cache: &State<SharedCache<DescribedNodes>>,
+) -> Json<ApiHealthResponse> {
     let uptime = state.startup_time.elapsed();
-    let health = ApiHealthResponse::new_healthy(uptime);
+
+    let caches = vec![
+        CacheAge::new("nym-contract", contract_cache.last_refreshed().await),
+        CacheAge::new("node-status", node_status_cache.last_refreshed().await),
+        CacheAge::new(
+            "circulating-supply",
+            circulating_supply_cache.last_refreshed().await,
+        ),
+        CacheAge::new(
+            "node-self-described",
+            describe_cache
+                .get()
+                .await
+                .ok()
+                .map(|cache| cache.timestamp()),
+        ),
+    ];
+
+    let health = ApiHealthResponse::new_healthy(uptime).with_caches(caches);
     Json(health)
 }
 
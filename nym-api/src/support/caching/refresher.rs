@@ -2,18 +2,30 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::support::caching::cache::SharedCache;
+use futures::future::{pending, select_all};
+use futures::FutureExt;
 use nym_task::TaskClient;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::interval;
+use tokio::sync::{watch, Notify};
+use tokio::time::{interval, sleep};
+
+// how long we wait after receiving a refresh trigger before actually refreshing the cache,
+// so that a burst of related events (say, an epoch change alongside a rewarded set update)
+// would only result in a single refresh
+pub(crate) const DEFAULT_COALESCING_WINDOW: Duration = Duration::from_secs(2);
+
+type BoxedRefreshTrigger = Box<dyn RefreshTriggerTrait + Send + Sync>;
 
 pub struct CacheRefresher<T, E> {
     name: String,
     refreshing_interval: Duration,
+    coalescing_window: Duration,
 
     // TODO: the Send + Sync bounds are only required for the `start` method. could we maybe make it less restrictive?
     provider: Box<dyn CacheItemProvider<Error = E, Item = T> + Send + Sync>,
     shared_cache: SharedCache<T>,
-    // triggers: Vec<Box<dyn RefreshTriggerTrait>>,
+    triggers: Vec<BoxedRefreshTrigger>,
 }
 
 #[async_trait]
@@ -26,27 +38,79 @@ pub trait CacheItemProvider {
     async fn try_refresh(&self) -> Result<Self::Item, Self::Error>;
 }
 
-// pub struct TriggerFailure;
-//
-// #[async_trait]
-// pub trait RefreshTriggerTrait {
-//     async fn triggerred(&mut self) -> Result<(), TriggerFailure>;
-// }
-//
-// // TODO: how to get rid of `T: Send + Sync`? it really doesn't need to be Send + Sync
-// // since it's wrapped in Shared<T> internally anyway
-// #[async_trait]
-// impl<T> RefreshTriggerTrait for watch::Receiver<T>
-// where
-//     T: Send + Sync,
-// {
-//     async fn triggerred(&mut self) -> Result<(), TriggerFailure> {
-//         self.changed().await.map_err(|err| {
-//             error!("failed to process refresh trigger: {err}");
-//             TriggerFailure
-//         })
-//     }
-// }
+#[derive(Debug)]
+pub struct TriggerFailure;
+
+/// An event source, such as an update of another cache or a chain event, that causes the cache to get refreshed
+/// outside its regular refreshing interval.
+#[async_trait]
+pub trait RefreshTriggerTrait {
+    /// Resolves once the cache should get refreshed.
+    async fn triggered(&mut self) -> Result<(), TriggerFailure>;
+
+    /// Marks all pending events as seen so that they wouldn't cause an additional refresh.
+    fn clear(&mut self) {}
+}
+
+// TODO: how to get rid of `T: Send + Sync`? it really doesn't need to be Send + Sync
+// since it's wrapped in Shared<T> internally anyway
+#[async_trait]
+impl<T> RefreshTriggerTrait for watch::Receiver<T>
+where
+    T: Send + Sync,
+{
+    async fn triggered(&mut self) -> Result<(), TriggerFailure> {
+        self.changed().await.map_err(|err| {
+            error!("failed to process refresh trigger: {err}");
+            TriggerFailure
+        })
+    }
+
+    fn clear(&mut self) {
+        self.borrow_and_update();
+    }
+}
+
+/// Handle allowing to request an immediate refresh of the associated cache.
+/// All requests made before the refresh begins are coalesced into a single one.
+#[derive(Clone, Default)]
+pub(crate) struct RefreshRequester(Arc<Notify>);
+
+impl RefreshRequester {
+    pub(crate) fn request_cache_refresh(&self) {
+        self.0.notify_one()
+    }
+
+    pub(crate) async fn requested(&self) {
+        self.0.notified().await
+    }
+
+    /// Removes the pending request, if any.
+    pub(crate) fn clear(&self) {
+        // if there's a stored permit, the first poll is going to consume it
+        let _ = self.0.notified().now_or_never();
+    }
+}
+
+// resolves once any of the triggers fires.
+// the triggers that failed (i.e. their sources got dropped) are removed so that they wouldn't keep firing
+async fn wait_for_trigger(triggers: &mut Vec<BoxedRefreshTrigger>) {
+    loop {
+        if triggers.is_empty() {
+            return pending().await;
+        }
+
+        let (res, index, _) =
+            select_all(triggers.iter_mut().map(|trigger| trigger.triggered())).await;
+        match res {
+            Ok(()) => return,
+            Err(TriggerFailure) => {
+                warn!("removing a failed refresh trigger");
+                triggers.remove(index);
+            }
+        }
+    }
+}
 
 impl<T, E> CacheRefresher<T, E>
 where
@@ -56,12 +120,7 @@ where
         item_provider: Box<dyn CacheItemProvider<Error = E, Item = T> + Send + Sync>,
         refreshing_interval: Duration,
     ) -> Self {
-        CacheRefresher {
-            name: "GenericCacheRefresher".to_string(),
-            refreshing_interval,
-            provider: item_provider,
-            shared_cache: SharedCache::new(),
-        }
+        Self::new_with_initial_value(item_provider, refreshing_interval, SharedCache::new())
     }
 
     pub(crate) fn new_with_initial_value(
@@ -72,8 +131,10 @@ where
         CacheRefresher {
             name: "GenericCacheRefresher".to_string(),
             refreshing_interval,
+            coalescing_window: DEFAULT_COALESCING_WINDOW,
             provider: item_provider,
             shared_cache,
+            triggers: Vec::new(),
         }
     }
 
@@ -83,25 +144,43 @@ where
         self
     }
 
+    /// Makes the cache get refreshed whenever the provided trigger fires,
+    /// for example whenever a cache it depends on gets updated.
+    #[must_use]
+    pub(crate) fn with_refresh_trigger<R>(mut self, trigger: R) -> Self
+    where
+        R: RefreshTriggerTrait + Send + Sync + 'static,
+    {
+        self.triggers.push(Box::new(trigger));
+        self
+    }
+
     #[allow(dead_code)]
     pub(crate) fn get_shared_cache(&self) -> SharedCache<T> {
         self.shared_cache.clone()
     }
 
+    fn clear_pending_triggers(&mut self) {
+        for trigger in &mut self.triggers {
+            trigger.clear()
+        }
+    }
+
     async fn do_refresh_cache(&self) {
         match self.provider.try_refresh().await {
-            Ok(updated_items) => {
-                self.shared_cache.update(updated_items).await;
-            }
+            Ok(updated_items) => self.shared_cache.update(updated_items).await,
             Err(err) => {
                 error!("{}: failed to refresh the cache: {err}", self.name)
             }
         }
     }
 
-    pub async fn refresh(&self, task_client: &mut TaskClient) {
+    pub async fn refresh(&mut self, task_client: &mut TaskClient) {
         log::info!("{}: refreshing cache state", self.name);
 
+        // whatever has triggered the refresh so far is going to be covered by this one
+        self.clear_pending_triggers();
+
         tokio::select! {
             biased;
             _ = task_client.recv() => {
@@ -111,9 +190,26 @@ where
         }
     }
 
-    pub async fn run(&self, mut task_client: TaskClient) {
+    async fn refresh_on_trigger(&mut self, task_client: &mut TaskClient) {
+        // give any related events a chance to arrive before refreshing
+        tokio::select! {
+            biased;
+            _ = task_client.recv() => {
+                log::trace!("{}: Received shutdown while waiting for more refresh triggers", self.name);
+                return
+            }
+            _ = sleep(self.coalescing_window) => (),
+        }
+
+        self.refresh(task_client).await
+    }
+
+    pub async fn run(&mut self, mut task_client: TaskClient) {
         self.provider.wait_until_ready().await;
 
+        // anything that happened while we were waiting is going to be covered by the initial refresh
+        self.clear_pending_triggers();
+
         let mut refresh_interval = interval(self.refreshing_interval);
         while !task_client.is_shutdown() {
             tokio::select! {
@@ -121,12 +217,17 @@ where
                 _ = task_client.recv() => {
                     log::trace!("{}: Received shutdown", self.name)
                 }
+                _ = wait_for_trigger(&mut self.triggers) => {
+                    log::debug!("{}: received a refresh trigger", self.name);
+                    self.refresh_on_trigger(&mut task_client).await;
+                    refresh_interval.reset();
+                }
                 _ = refresh_interval.tick() => self.refresh(&mut task_client).await,
             }
         }
     }
 
-    pub fn start(self, task_client: TaskClient)
+    pub fn start(mut self, task_client: TaskClient)
     where
         T: Send + Sync + 'static,
         E: Send + Sync + 'static,
@@ -134,3 +235,61 @@ where
         tokio::spawn(async move { self.run(task_client).await });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("this never happens")]
+    struct NeverError;
+
+    struct CountingProvider(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl CacheItemProvider for CountingProvider {
+        type Item = usize;
+        type Error = NeverError;
+
+        async fn try_refresh(&self) -> Result<Self::Item, Self::Error> {
+            Ok(self.0.fetch_add(1, Ordering::SeqCst) + 1)
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn bursts_of_refresh_triggers_are_coalesced() {
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        let (trigger_tx, trigger_rx) = watch::channel(0u32);
+        let (other_trigger_tx, other_trigger_rx) = watch::channel(0u32);
+
+        let mut refresher = CacheRefresher::new(
+            Box::new(CountingProvider(refreshes.clone())),
+            Duration::from_secs(60 * 60),
+        )
+        .with_refresh_trigger(trigger_rx)
+        .with_refresh_trigger(other_trigger_rx);
+        refresher.coalescing_window = Duration::from_millis(100);
+        let cache = refresher.get_shared_cache();
+        refresher.start(TaskClient::dummy());
+
+        // the initial refresh
+        sleep(Duration::from_millis(50)).await;
+        assert_eq!(refreshes.load(Ordering::SeqCst), 1);
+
+        for i in 1..=5 {
+            trigger_tx.send_replace(i);
+        }
+        other_trigger_tx.send_replace(1);
+        other_trigger_tx.send_replace(2);
+
+        sleep(Duration::from_millis(300)).await;
+        assert_eq!(refreshes.load(Ordering::SeqCst), 2);
+        assert_eq!(**cache.get().await.unwrap(), 2);
+
+        // and the trigger keeps working afterwards
+        trigger_tx.send_replace(42);
+        sleep(Duration::from_millis(300)).await;
+        assert_eq!(refreshes.load(Ordering::SeqCst), 3);
+    }
+}
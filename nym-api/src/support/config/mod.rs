@@ -56,6 +56,9 @@ const DEFAULT_CIRCULATING_SUPPLY_CACHE_INTERVAL: Duration = Duration::from_secs(
 pub(crate) const DEFAULT_NODE_DESCRIBE_CACHE_INTERVAL: Duration = Duration::from_secs(4500);
 pub(crate) const DEFAULT_NODE_DESCRIBE_BATCH_SIZE: usize = 50;
 
+// nodes only run their verloc measurements every 12h, so there's no point in querying them more often
+const DEFAULT_NODE_LATENCY_CACHE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
 const DEFAULT_MONITOR_THRESHOLD: u8 = 60;
 const DEFAULT_MIN_MIXNODE_RELIABILITY: u8 = 50;
 const DEFAULT_MIN_GATEWAY_RELIABILITY: u8 = 20;
@@ -414,6 +417,9 @@ pub struct TopologyCacherDebug {
     pub node_describe_caching_interval: Duration,
 
     pub node_describe_batch_size: usize,
+
+    #[serde(with = "humantime_serde")]
+    pub node_latency_caching_interval: Duration,
 }
 
 impl Default for TopologyCacherDebug {
@@ -422,6 +428,7 @@ impl Default for TopologyCacherDebug {
             caching_interval: DEFAULT_TOPOLOGY_CACHE_INTERVAL,
             node_describe_caching_interval: DEFAULT_NODE_DESCRIBE_CACHE_INTERVAL,
             node_describe_batch_size: DEFAULT_NODE_DESCRIBE_BATCH_SIZE,
+            node_latency_caching_interval: DEFAULT_NODE_LATENCY_CACHE_INTERVAL,
         }
     }
 }
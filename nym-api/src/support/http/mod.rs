@@ -17,6 +17,7 @@ use crate::support::config::Config;
 use crate::support::{nyxd, storage};
 use crate::{circulating_supply_api, nym_contract_cache, nym_nodes::nym_node_routes};
 use anyhow::{bail, Result};
+use nym_api_requests::nym_nodes::NetworkLatency;
 use nym_crypto::asymmetric::identity;
 use nym_validator_client::nyxd::Coin;
 use rocket::http::Method;
@@ -60,6 +61,7 @@ pub(crate) async fn setup_rocket(
     let rocket = rocket
         .manage(network_details)
         .manage(SharedCache::<DescribedNodes>::new())
+        .manage(SharedCache::<NetworkLatency>::new())
         .mount("/swagger", make_swagger_ui(&openapi::get_docs()))
         .attach(setup_cors()?)
         .attach(NymContractCache::stage())
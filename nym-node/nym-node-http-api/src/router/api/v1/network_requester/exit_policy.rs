@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::api::{FormattedResponse, OutputParams};
+use crate::state::exit_policy::SharedExitPolicy;
 use axum::extract::Query;
 use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 
@@ -20,11 +21,11 @@ use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExit
     params(OutputParams)
 )]
 pub(crate) async fn node_exit_policy(
-    policy: UsedExitPolicy,
+    policy: SharedExitPolicy,
     Query(output): Query<OutputParams>,
 ) -> ExitPolicyResponse {
     let output = output.output.unwrap_or_default();
-    output.to_response(policy)
+    output.to_response(policy.read().await.clone())
 }
 
 pub type ExitPolicyResponse = FormattedResponse<UsedExitPolicy>;
@@ -2,9 +2,9 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::api::v1::network_requester::exit_policy::node_exit_policy;
+use crate::state::exit_policy::SharedExitPolicy;
 use axum::routing::get;
 use axum::Router;
-use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use nym_node_requests::api::v1::network_requester::models;
 use nym_node_requests::routes::api::v1::network_requester;
 
@@ -14,7 +14,7 @@ pub mod root;
 #[derive(Debug, Clone, Default)]
 pub struct Config {
     pub details: Option<models::NetworkRequester>,
-    pub exit_policy: Option<UsedExitPolicy>,
+    pub exit_policy: SharedExitPolicy,
 }
 
 pub(crate) fn routes<S: Send + Sync + 'static + Clone>(config: Config) -> Router<S> {
@@ -29,7 +29,7 @@ pub(crate) fn routes<S: Send + Sync + 'static + Clone>(config: Config) -> Router
         .route(
             network_requester::EXIT_POLICY,
             get({
-                let policy = config.exit_policy.unwrap_or_default();
+                let policy = config.exit_policy;
                 move |query| node_exit_policy(policy, query)
             }),
         )
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::api::{FormattedResponse, OutputParams};
+use crate::state::host_information::SharedHostInformation;
 use axum::extract::Query;
 use nym_node_requests::api::v1::node::models::SignedHostInformation;
 
@@ -20,11 +21,11 @@ use nym_node_requests::api::v1::node::models::SignedHostInformation;
     params(OutputParams)
 )]
 pub(crate) async fn host_information(
-    host_information: SignedHostInformation,
+    host_information: SharedHostInformation,
     Query(output): Query<OutputParams>,
 ) -> HostInformationResponse {
     let output = output.output.unwrap_or_default();
-    output.to_response(host_information)
+    output.to_response(host_information.read().await.clone())
 }
 
 pub type HostInformationResponse = FormattedResponse<SignedHostInformation>;
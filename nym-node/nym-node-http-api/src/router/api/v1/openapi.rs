@@ -43,6 +43,7 @@ use utoipa_swagger_ui::SwaggerUi;
             api_requests::v1::node::models::SignedHostInformation,
             api_requests::v1::node::models::HostInformation,
             api_requests::v1::node::models::HostKeys,
+            api_requests::v1::node::models::SphinxKeyRotation,
             api_requests::v1::node::models::NodeRoles,
             api_requests::v1::node::models::HostSystem,
             api_requests::v1::node::models::Hardware,
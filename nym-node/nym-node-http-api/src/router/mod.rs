@@ -3,6 +3,8 @@
 
 use crate::error::NymNodeHttpError;
 use crate::middleware::logging;
+use crate::state::exit_policy::SharedExitPolicy;
+use crate::state::host_information::SharedHostInformation;
 use crate::state::AppState;
 use crate::NymNodeHTTPServer;
 use axum::response::Redirect;
@@ -43,7 +45,7 @@ impl Config {
                 v1_config: api::v1::Config {
                     node: api::v1::node::Config {
                         build_information,
-                        host_information,
+                        host_information: SharedHostInformation::new(host_information),
                         system_info: None,
                         roles: Default::default(),
                         description: Default::default(),
@@ -78,6 +80,12 @@ impl Config {
         self
     }
 
+    #[must_use]
+    pub fn with_shared_host_information(mut self, host_information: SharedHostInformation) -> Self {
+        self.api.v1_config.node.host_information = host_information;
+        self
+    }
+
     #[must_use]
     pub fn with_system_info(mut self, info: HostSystem) -> Self {
         self.api.v1_config.node.system_info = Some(info);
@@ -133,8 +141,13 @@ impl Config {
     }
 
     #[must_use]
-    pub fn with_used_exit_policy(mut self, exit_policy: UsedExitPolicy) -> Self {
-        self.api.v1_config.network_requester.exit_policy = Some(exit_policy);
+    pub fn with_used_exit_policy(self, exit_policy: UsedExitPolicy) -> Self {
+        self.with_shared_exit_policy(SharedExitPolicy::new(exit_policy))
+    }
+
+    #[must_use]
+    pub fn with_shared_exit_policy(mut self, exit_policy: SharedExitPolicy) -> Self {
+        self.api.v1_config.network_requester.exit_policy = exit_policy;
         self
     }
 
@@ -0,0 +1,28 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use nym_node_requests::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard};
+
+/// Exit policy exposed by the http api that can be updated whenever the underlying policy gets refreshed.
+#[derive(Clone, Debug, Default)]
+pub struct SharedExitPolicy {
+    inner: Arc<RwLock<UsedExitPolicy>>,
+}
+
+impl SharedExitPolicy {
+    pub fn new(exit_policy: UsedExitPolicy) -> SharedExitPolicy {
+        SharedExitPolicy {
+            inner: Arc::new(RwLock::new(exit_policy)),
+        }
+    }
+
+    pub async fn read(&self) -> RwLockReadGuard<'_, UsedExitPolicy> {
+        self.inner.read().await
+    }
+
+    pub async fn update(&self, exit_policy: UsedExitPolicy) {
+        *self.inner.write().await = exit_policy
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use nym_node_requests::api::SignedHostInformation;
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard};
+
+/// Signed host information exposed by the http api that can be updated whenever any of the announced keys change,
+/// for example on sphinx key rotation.
+#[derive(Clone, Debug)]
+pub struct SharedHostInformation {
+    inner: Arc<RwLock<SignedHostInformation>>,
+}
+
+impl SharedHostInformation {
+    pub fn new(host_information: SignedHostInformation) -> SharedHostInformation {
+        SharedHostInformation {
+            inner: Arc::new(RwLock::new(host_information)),
+        }
+    }
+
+    pub async fn read(&self) -> RwLockReadGuard<'_, SignedHostInformation> {
+        self.inner.read().await
+    }
+
+    pub async fn update(&self, host_information: SignedHostInformation) {
+        *self.inner.write().await = host_information
+    }
+}
@@ -51,7 +51,9 @@ pub struct MixingStatsState {
     pub packets_received_since_startup: u64,
     pub packets_sent_since_startup_all: u64,
     pub packets_dropped_since_startup_all: u64,
+    pub packets_replayed_since_startup: u64,
     pub packets_received_since_last_update: u64,
+    pub packets_replayed_since_last_update: u64,
 
     // note: sent does not imply forwarded. We don't know if it was delivered successfully
     pub packets_sent_since_last_update: PacketsMap,
@@ -68,12 +70,14 @@ impl MixingStatsState {
             received_since_startup: self.packets_received_since_startup,
             sent_since_startup: self.packets_sent_since_startup_all,
             dropped_since_startup: self.packets_dropped_since_startup_all,
+            replayed_since_startup: self.packets_replayed_since_startup,
             received_since_last_update: self.packets_received_since_last_update,
             sent_since_last_update: self.packets_sent_since_last_update.values().sum(),
             dropped_since_last_update: self
                 .packets_explicitly_dropped_since_last_update
                 .values()
                 .sum(),
+            replayed_since_last_update: self.packets_replayed_since_last_update,
         }
     }
 }
@@ -86,7 +90,9 @@ impl Default for MixingStatsState {
             packets_received_since_startup: 0,
             packets_sent_since_startup_all: 0,
             packets_dropped_since_startup_all: 0,
+            packets_replayed_since_startup: 0,
             packets_received_since_last_update: 0,
+            packets_replayed_since_last_update: 0,
             packets_sent_since_last_update: Default::default(),
             packets_explicitly_dropped_since_last_update: Default::default(),
         }
@@ -4,6 +4,8 @@
 use crate::state::metrics::{MetricsAppState, SharedMixingStats, SharedVerlocStats};
 use tokio::time::Instant;
 
+pub mod exit_policy;
+pub mod host_information;
 pub mod metrics;
 
 #[derive(Debug, Clone)]
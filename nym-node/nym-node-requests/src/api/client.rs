@@ -12,6 +12,7 @@ use nym_http_api_client::{ApiClient, HttpClientError};
 use crate::api::v1::authenticator::models::Authenticator;
 use crate::api::v1::health::models::NodeHealth;
 use crate::api::v1::ip_packet_router::models::IpPacketRouter;
+use crate::api::v1::metrics::models::VerlocStats;
 use crate::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use crate::api::v1::network_requester::models::NetworkRequester;
 pub use nym_http_api_client::Client;
@@ -69,6 +70,11 @@ pub trait NymNodeApiClientExt: ApiClient {
         self.get_json_from(routes::api::v1::authenticator_absolute())
             .await
     }
+
+    async fn get_verloc_stats(&self) -> Result<VerlocStats, NymNodeApiClientError> {
+        self.get_json_from(routes::api::v1::metrics::verloc_absolute())
+            .await
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
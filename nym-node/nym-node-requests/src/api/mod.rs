@@ -113,6 +113,7 @@ mod tests {
                 ed25519_identity: ed22519.public_key().to_base58_string(),
                 x25519_sphinx: x25519_sphinx.public_key().to_base58_string(),
                 x25519_noise: "".to_string(),
+                sphinx_key_rotation: None,
             },
         };
 
@@ -143,6 +144,7 @@ mod tests {
                 ed25519_identity: legacy_info.keys.ed25519.clone(),
                 x25519_sphinx: legacy_info.keys.x25519.clone(),
                 x25519_noise: "".to_string(),
+                sphinx_key_rotation: None,
             },
         };
 
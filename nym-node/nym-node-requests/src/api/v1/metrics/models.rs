@@ -26,6 +26,10 @@ pub struct MixingStats {
     // we know for sure we dropped those packets
     pub dropped_since_startup: u64,
 
+    // packets that were rejected as they have already been processed before
+    #[serde(default)]
+    pub replayed_since_startup: u64,
+
     pub received_since_last_update: u64,
 
     // note: sent does not imply forwarded. We don't know if it was delivered successfully
@@ -33,6 +37,10 @@ pub struct MixingStats {
 
     // we know for sure we dropped those packets
     pub dropped_since_last_update: u64,
+
+    // packets that were rejected as they have already been processed before
+    #[serde(default)]
+    pub replayed_since_last_update: u64,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -133,19 +141,19 @@ impl Ord for VerlocNodeResult {
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VerlocMeasurement {
     /// Minimum RTT duration it took to receive an echo packet.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub minimum: Duration,
 
     /// Average RTT duration it took to receive the echo packets.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub mean: Duration,
 
     /// Maximum RTT duration it took to receive an echo packet.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub maximum: Duration,
 
     /// The standard deviation of the RTT duration it took to receive the echo packets.
-    #[serde(serialize_with = "humantime_serde::serialize")]
+    #[serde(with = "humantime_serde")]
     pub standard_deviation: Duration,
 }
 
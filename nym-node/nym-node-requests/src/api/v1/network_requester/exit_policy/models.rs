@@ -25,6 +25,10 @@ pub struct UsedExitPolicy {
     #[cfg_attr(feature = "openapi", schema(example = 1697731611))]
     pub last_updated: u64,
 
+    /// Hex-encoded sha256 digest of the currently used exit policy.
+    #[serde(default)]
+    pub policy_hash: Option<String>,
+
     /// The actual policy used by this node.
     // `ExitPolicy` is a type alias for `AddressPolicy`,
     // but it seems utoipa is too stupid to realise it by itself
@@ -38,6 +42,7 @@ impl Default for UsedExitPolicy {
             enabled: false,
             upstream_source: "".to_string(),
             last_updated: 0,
+            policy_hash: None,
             policy: None,
         }
     }
@@ -65,6 +65,21 @@ pub struct HostKeys {
     /// Base58-encoded x25519 public key of this node used for the noise protocol.
     #[serde(default)]
     pub x25519_noise: String,
+
+    /// Details of the sphinx key rotation, if this node has it enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sphinx_key_rotation: Option<SphinxKeyRotation>,
+}
+
+#[derive(Clone, Default, Debug, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SphinxKeyRotation {
+    /// Id of the key rotation the current `x25519_sphinx` key belongs to.
+    pub current_rotation_id: u32,
+
+    /// Base58-encoded x25519 public key of this node that is going to be used for sphinx/outfox packet creation
+    /// once the next key rotation begins. The node already accepts packets created with it.
+    pub next_x25519_sphinx: String,
 }
 
 impl From<HostKeys> for LegacyHostKeys {
@@ -0,0 +1,102 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::helpers::ConfigArgs;
+use crate::node::sphinx_key_rotation::SphinxKeyStore;
+use nym_node::config::upgrade_helpers::try_load_current_config;
+use nym_node::config::Config;
+use nym_node::error::NymNodeError;
+use nym_pemstore::KeysPassphraseArgs;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+#[derive(Debug, clap::Args)]
+pub(crate) struct Args {
+    #[clap(flatten)]
+    config: ConfigArgs,
+
+    /// Decrypt the existing private keys, so that they would be stored in plaintext again,
+    /// instead of encrypting them.
+    #[clap(long)]
+    decrypt: bool,
+
+    #[clap(flatten)]
+    keys_passphrase: KeysPassphraseArgs,
+}
+
+/// Returns paths to all private keys of this node (and its embedded clients) that currently exist on disk.
+fn existing_private_key_files(config: &Config) -> Result<BTreeSet<PathBuf>, NymNodeError> {
+    let keys = &config.storage_paths.keys;
+    let exit_paths = &config.exit_gateway.storage_paths;
+
+    let mut files = vec![
+        keys.private_ed25519_identity_key_file.clone(),
+        keys.private_x25519_sphinx_key_file.clone(),
+        keys.private_x25519_noise_key_file.clone(),
+        config
+            .wireguard
+            .storage_paths
+            .private_diffie_hellman_key_file
+            .clone(),
+    ];
+
+    for client_paths in [
+        exit_paths.network_requester.to_common_client_paths(),
+        exit_paths.ip_packet_router.to_common_client_paths(),
+        exit_paths.authenticator.to_common_client_paths(),
+        config
+            .entry_gateway
+            .storage_paths
+            .authenticator
+            .to_common_client_paths(),
+    ] {
+        files.push(client_paths.keys.private_identity_key_file);
+        files.push(client_paths.keys.private_encryption_key_file);
+        files.push(client_paths.keys.ack_key_file);
+    }
+
+    let rotation_store = SphinxKeyStore::new(keys.sphinx_key_rotation_directory());
+    for rotation_id in rotation_store.stored_rotation_ids()? {
+        files.push(rotation_store.key_paths(rotation_id).private_key_path)
+    }
+
+    Ok(files.into_iter().filter(|path| path.exists()).collect())
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymNodeError> {
+    let config = try_load_current_config(args.config.config_path()).await?;
+
+    let passphrase = args
+        .keys_passphrase
+        .read()
+        .map_err(|source| NymNodeError::KeysPassphraseReadFailure { source })?
+        .ok_or(NymNodeError::MissingKeysPassphrase)?;
+
+    let mut changed = 0;
+    for path in existing_private_key_files(&config)? {
+        let res = if args.decrypt {
+            nym_pemstore::decrypt_key_file(&path, &passphrase)
+        } else {
+            nym_pemstore::encrypt_key_file(&path, &passphrase)
+        };
+
+        match res {
+            Ok(true) => {
+                debug!("changed encryption of {}", path.display());
+                changed += 1
+            }
+            Ok(false) => debug!("{} didn't require any changes", path.display()),
+            Err(source) => return Err(NymNodeError::KeyEncryptionChangeFailure { path, source }),
+        }
+    }
+
+    if args.decrypt {
+        info!("decrypted {changed} private keys");
+    } else {
+        info!("encrypted {changed} private keys");
+        eprintln!("make sure to provide the passphrase via the 'NYM_KEYS_PASSPHRASE' or 'NYM_KEYS_PASSPHRASE_FILE' environment variables when running the node");
+    }
+
+    Ok(())
+}
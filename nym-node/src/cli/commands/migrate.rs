@@ -411,6 +411,9 @@ async fn migrate_gateway(mut args: Args) -> Result<(), NymNodeError> {
                 announce_wss_port: cfg.gateway.clients_wss_port,
                 debug: config::entry_gateway::Debug {
                     message_retrieval_limit: cfg.debug.message_retrieval_limit,
+                    inbox: cfg.debug.inbox,
+                    spent_credentials: cfg.debug.spent_credentials,
+                    voucher_redemption: cfg.debug.voucher_redemption,
                 },
             },
         ))
@@ -1,7 +1,9 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::cli::commands::{bonding_information, build_info, migrate, node_details, run, sign};
+use crate::cli::commands::{
+    bonding_information, build_info, keys_encryption, migrate, node_details, run, sign,
+};
 use crate::env::vars::{NYMNODE_CONFIG_ENV_FILE_ARG, NYMNODE_NO_BANNER_ARG};
 use clap::{Parser, Subcommand};
 use nym_bin_common::bin_info;
@@ -50,6 +52,7 @@ impl Cli {
             Commands::Run(args) => run::execute(*args).await,
             Commands::Migrate(args) => migrate::execute(*args).await,
             Commands::Sign(args) => sign::execute(args).await,
+            Commands::KeysEncryption(args) => keys_encryption::execute(args).await,
         }
     }
 }
@@ -73,6 +76,9 @@ pub(crate) enum Commands {
 
     /// Use identity key of this node to sign provided message.
     Sign(sign::Args),
+
+    /// Encrypt the existing private keys of this node with a passphrase or decrypt them again.
+    KeysEncryption(keys_encryption::Args),
 }
 
 #[cfg(test)]
@@ -51,6 +51,18 @@ pub struct EntryGatewayConfig {
 pub struct Debug {
     /// Number of messages from offline client that can be pulled at once (i.e. with a single SQL query) from the storage.
     pub message_retrieval_limit: i64,
+
+    /// Limits imposed on messages stored for offline clients.
+    #[serde(default)]
+    pub inbox: nym_gateway::config::InboxConfig,
+
+    /// Settings for sharing information about spent credentials with other gateways (through the nym-apis).
+    #[serde(default)]
+    pub spent_credentials: nym_gateway::config::SpentCredentialsConfig,
+
+    /// Settings for redeeming the funds of the accepted bandwidth vouchers.
+    #[serde(default)]
+    pub voucher_redemption: nym_gateway::config::VoucherRedemptionConfig,
 }
 
 impl Debug {
@@ -61,6 +73,9 @@ impl Default for Debug {
     fn default() -> Self {
         Debug {
             message_retrieval_limit: Self::DEFAULT_MESSAGE_RETRIEVAL_LIMIT,
+            inbox: Default::default(),
+            spent_credentials: Default::default(),
+            voucher_redemption: Default::default(),
         }
     }
 }
@@ -110,6 +125,7 @@ pub fn ephemeral_entry_gateway_config(
             announced_port: config.wireguard.announced_port,
             private_network_prefix: config.wireguard.private_network_prefix,
             storage_paths: config.wireguard.storage_paths.clone(),
+            debug: config.wireguard.debug,
         },
         custom_mixnet_path: None,
     };
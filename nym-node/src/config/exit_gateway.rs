@@ -201,6 +201,7 @@ pub fn ephemeral_exit_gateway_config(
                 upstream_exit_policy_url: Some(
                     config.exit_gateway.upstream_exit_policy_url.clone(),
                 ),
+                ..Default::default()
             },
             storage_paths: nym_ip_packet_router::config::IpPacketRouterPaths {
                 common_paths: config
@@ -255,6 +256,7 @@ pub fn ephemeral_exit_gateway_config(
             announced_port: config.wireguard.announced_port,
             private_network_prefix: config.wireguard.private_network_prefix,
             storage_paths: config.wireguard.storage_paths.clone(),
+            debug: config.wireguard.debug,
         },
         custom_mixnet_path: None,
     };
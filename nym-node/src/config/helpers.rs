@@ -80,7 +80,11 @@ pub fn ephemeral_gateway_config(
             initial_connection_timeout: config.mixnet.debug.initial_connection_timeout,
             maximum_connection_buffer_size: config.mixnet.debug.maximum_connection_buffer_size,
             message_retrieval_limit: config.entry_gateway.debug.message_retrieval_limit,
+            inbox: config.entry_gateway.debug.inbox,
+            spent_credentials: config.entry_gateway.debug.spent_credentials,
+            voucher_redemption: config.entry_gateway.debug.voucher_redemption,
             use_legacy_framed_packet_version: false,
+            replay_protection: config.mixnet.debug.replay_protection,
             ..Default::default()
         },
     ))
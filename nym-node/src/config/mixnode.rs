@@ -209,6 +209,7 @@ pub fn ephemeral_mixnode_config(
             initial_connection_timeout: config.mixnet.debug.initial_connection_timeout,
             maximum_connection_buffer_size: config.mixnet.debug.maximum_connection_buffer_size,
             use_legacy_framed_packet_version: false,
+            replay_protection: config.mixnet.debug.replay_protection,
         },
     ))
 }
@@ -17,6 +17,8 @@ use nym_config::{
     must_get_home, parse_urls, read_config_from_toml_file, save_formatted_config_to_file,
     NymConfigTemplate, DEFAULT_CONFIG_DIR, DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_mixnode_common::packet_processor::replay_detection::ReplayProtectionConfig;
+use nym_sphinx_params::SphinxKeyRotationSchedule;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fmt::{Display, Formatter};
@@ -449,6 +451,25 @@ pub struct MixnetDebug {
 
     /// Specifies whether this node should **NOT** use noise protocol in the connections (currently not implemented)
     pub unsafe_disable_noise: bool,
+
+    /// Settings for detecting and rejecting replayed sphinx packets.
+    pub replay_protection: ReplayProtectionConfig,
+
+    /// Settings for periodic rotation of the sphinx keys.
+    pub sphinx_key_rotation: SphinxKeyRotation,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct SphinxKeyRotation {
+    /// Specifies whether this node should periodically rotate its sphinx keys.
+    /// Note that the bonded key remains valid, so that clients unaware of the rotation could still
+    /// route packets through this node.
+    pub enabled: bool,
+
+    /// Schedule of the key rotations. It should match the one used by the rest of the network.
+    pub schedule: SphinxKeyRotationSchedule,
 }
 
 impl MixnetDebug {
@@ -467,6 +488,8 @@ impl Default for MixnetDebug {
             maximum_connection_buffer_size: Self::DEFAULT_MAXIMUM_CONNECTION_BUFFER_SIZE,
             // to be changed by @SW once the implementation is there
             unsafe_disable_noise: true,
+            replay_protection: Default::default(),
+            sphinx_key_rotation: Default::default(),
         }
     }
 }
@@ -523,6 +546,36 @@ pub struct Wireguard {
 
     /// Paths for wireguard keys, client registries, etc.
     pub storage_paths: persistence::WireguardPaths,
+
+    #[serde(default)]
+    pub debug: WireguardDebug,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+#[serde(deny_unknown_fields)]
+pub struct WireguardDebug {
+    /// Duration since the last handshake (or since registration, if no handshake happened)
+    /// after which the peer is considered stale and gets removed.
+    #[serde(with = "humantime_serde")]
+    pub peer_timeout: Duration,
+
+    /// Specifies how often the peers are checked for staleness and their bandwidth usage is metered.
+    #[serde(with = "humantime_serde")]
+    pub peer_timeout_check: Duration,
+
+    /// Bandwidth, in bytes, allocated to each newly registered peer.
+    pub peer_bandwidth_allowance: i64,
+}
+
+impl Default for WireguardDebug {
+    fn default() -> Self {
+        WireguardDebug {
+            peer_timeout: nym_wireguard_types::DEFAULT_PEER_TIMEOUT,
+            peer_timeout_check: nym_wireguard_types::DEFAULT_PEER_TIMEOUT_CHECK,
+            peer_bandwidth_allowance: nym_wireguard_types::DEFAULT_PEER_BANDWIDTH_ALLOWANCE,
+        }
+    }
 }
 
 impl Wireguard {
@@ -537,6 +590,7 @@ impl Wireguard {
             announced_port: DEFAULT_WIREGUARD_PORT,
             private_network_prefix: DEFAULT_WIREGUARD_PREFIX,
             storage_paths: persistence::WireguardPaths::new(data_dir),
+            debug: Default::default(),
         }
     }
 }
@@ -548,6 +602,9 @@ impl From<Wireguard> for nym_wireguard_types::Config {
             private_ip: value.private_ip,
             announced_port: value.announced_port,
             private_network_prefix: value.private_network_prefix,
+            peer_timeout: value.debug.peer_timeout,
+            peer_timeout_check: value.debug.peer_timeout_check,
+            peer_bandwidth_allowance: value.debug.peer_bandwidth_allowance,
         }
     }
 }
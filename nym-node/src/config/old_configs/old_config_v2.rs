@@ -838,6 +838,8 @@ pub async fn try_upgrade_config_v2<P: AsRef<Path>>(
                 initial_connection_timeout: old_cfg.mixnet.debug.initial_connection_timeout,
                 maximum_connection_buffer_size: old_cfg.mixnet.debug.maximum_connection_buffer_size,
                 unsafe_disable_noise: old_cfg.mixnet.debug.unsafe_disable_noise,
+                replay_protection: Default::default(),
+                sphinx_key_rotation: Default::default(),
             },
         },
         storage_paths: NymNodePaths {
@@ -893,6 +895,7 @@ pub async fn try_upgrade_config_v2<P: AsRef<Path>>(
                     .storage_paths
                     .public_diffie_hellman_key_file,
             },
+            debug: Default::default(),
         },
         mixnode: MixnodeConfig {
             storage_paths: MixnodePaths {},
@@ -925,6 +928,9 @@ pub async fn try_upgrade_config_v2<P: AsRef<Path>>(
             announce_wss_port: old_cfg.entry_gateway.announce_wss_port,
             debug: EntryGatewayConfigDebug {
                 message_retrieval_limit: old_cfg.entry_gateway.debug.message_retrieval_limit,
+                inbox: Default::default(),
+                spent_credentials: Default::default(),
+                voucher_redemption: Default::default(),
             },
         },
         exit_gateway: ExitGatewayConfig {
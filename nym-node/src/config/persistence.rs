@@ -16,6 +16,7 @@ pub const DEFAULT_ED25519_PRIVATE_IDENTITY_KEY_FILENAME: &str = "ed25519_identit
 pub const DEFAULT_ED25519_PUBLIC_IDENTITY_KEY_FILENAME: &str = "ed25519_identity.pub";
 pub const DEFAULT_X25519_PRIVATE_SPHINX_KEY_FILENAME: &str = "x25519_sphinx";
 pub const DEFAULT_X25519_PUBLIC_SPHINX_KEY_FILENAME: &str = "x25519_sphinx.pub";
+pub const DEFAULT_SPHINX_KEY_ROTATION_DIR: &str = "sphinx_key_rotation";
 pub const DEFAULT_X25519_PRIVATE_NOISE_KEY_FILENAME: &str = "x25519_noise";
 pub const DEFAULT_X25519_PUBLIC_NOISE_KEY_FILENAME: &str = "x25519_noise.pub";
 pub const DEFAULT_NYMNODE_DESCRIPTION_FILENAME: &str = "description.toml";
@@ -128,6 +129,15 @@ impl KeysPaths {
         )
     }
 
+    /// Directory containing the sphinx keys of the individual rotations, if the key rotation is enabled.
+    /// It's placed alongside the static sphinx key.
+    pub fn sphinx_key_rotation_directory(&self) -> PathBuf {
+        self.private_x25519_sphinx_key_file
+            .parent()
+            .map(|dir| dir.join(DEFAULT_SPHINX_KEY_ROTATION_DIR))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SPHINX_KEY_ROTATION_DIR))
+    }
+
     pub fn x25519_noise_storage_paths(&self) -> nym_pemstore::KeyPairPath {
         nym_pemstore::KeyPairPath::new(
             &self.private_x25519_noise_key_file,
@@ -123,6 +123,29 @@ pub enum NymNodeError {
     #[error(transparent)]
     KeyFailure(#[from] KeyIOFailure),
 
+    #[error("failed to access the sphinx key store at '{}': {source}", path.display())]
+    SphinxKeyStoreFailure {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to read the keys passphrase: {source}")]
+    KeysPassphraseReadFailure {
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("no passphrase for the keys encryption has been provided")]
+    MissingKeysPassphrase,
+
+    #[error("failed to change encryption of the key stored at '{}': {source}", path.display())]
+    KeyEncryptionChangeFailure {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
     #[error("could not initialise nym-node as '--{name}' has not been specified which is required for a first time setup. (config section: {section})")]
     MissingInitArg { section: String, name: String },
 
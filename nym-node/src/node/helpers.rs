@@ -68,15 +68,22 @@ impl Display for DisplayDetails {
     }
 }
 
+// note: all the private keys are encrypted at rest if the passphrase has been specified via
+// the `NYM_KEYS_PASSPHRASE` or `NYM_KEYS_PASSPHRASE_FILE` environment variables
+
 pub(crate) fn load_keypair<T: PemStorableKeyPair>(
     paths: KeyPairPath,
     name: impl Into<String>,
 ) -> Result<T, KeyIOFailure> {
-    nym_pemstore::load_keypair(&paths).map_err(|err| KeyIOFailure::KeyPairLoadFailure {
-        keys: name.into(),
-        paths,
-        err,
-    })
+    nym_pemstore::default_passphrase()
+        .and_then(|passphrase| {
+            nym_pemstore::load_keypair_with_passphrase(&paths, passphrase.as_ref())
+        })
+        .map_err(|err| KeyIOFailure::KeyPairLoadFailure {
+            keys: name.into(),
+            paths,
+            err,
+        })
 }
 
 pub(crate) fn store_keypair<T: PemStorableKeyPair>(
@@ -84,11 +91,15 @@ pub(crate) fn store_keypair<T: PemStorableKeyPair>(
     paths: KeyPairPath,
     name: impl Into<String>,
 ) -> Result<(), KeyIOFailure> {
-    nym_pemstore::store_keypair(keys, &paths).map_err(|err| KeyIOFailure::KeyPairStoreFailure {
-        keys: name.into(),
-        paths,
-        err,
-    })
+    nym_pemstore::default_passphrase()
+        .and_then(|passphrase| {
+            nym_pemstore::store_keypair_with_passphrase(keys, &paths, passphrase.as_ref())
+        })
+        .map_err(|err| KeyIOFailure::KeyPairStoreFailure {
+            keys: name.into(),
+            paths,
+            err,
+        })
 }
 
 pub(crate) fn load_key<T, P>(path: P, name: impl Into<String>) -> Result<T, KeyIOFailure>
@@ -96,11 +107,15 @@ where
     T: PemStorableKey,
     P: AsRef<Path>,
 {
-    nym_pemstore::load_key(path.as_ref()).map_err(|err| KeyIOFailure::KeyLoadFailure {
-        key: name.into(),
-        path: path.as_ref().to_path_buf(),
-        err,
-    })
+    nym_pemstore::default_passphrase()
+        .and_then(|passphrase| {
+            nym_pemstore::load_key_with_passphrase(path.as_ref(), passphrase.as_ref())
+        })
+        .map_err(|err| KeyIOFailure::KeyLoadFailure {
+            key: name.into(),
+            path: path.as_ref().to_path_buf(),
+            err,
+        })
 }
 
 pub(crate) fn store_key<T, P>(key: &T, path: P, name: impl Into<String>) -> Result<(), KeyIOFailure>
@@ -108,11 +123,15 @@ where
     T: PemStorableKey,
     P: AsRef<Path>,
 {
-    nym_pemstore::store_key(key, path.as_ref()).map_err(|err| KeyIOFailure::KeyStoreFailure {
-        key: name.into(),
-        path: path.as_ref().to_path_buf(),
-        err,
-    })
+    nym_pemstore::default_passphrase()
+        .and_then(|passphrase| {
+            nym_pemstore::store_key_with_passphrase(key, path.as_ref(), passphrase.as_ref())
+        })
+        .map_err(|err| KeyIOFailure::KeyStoreFailure {
+            key: name.into(),
+            path: path.as_ref().to_path_buf(),
+            err,
+        })
 }
 
 pub(crate) fn load_ed25519_identity_keypair(
@@ -15,6 +15,7 @@ pub(crate) fn sign_host_details(
     x22519_sphinx: &x25519::PublicKey,
     x25519_noise: &x25519::PublicKey,
     ed22519_identity: &ed25519::KeyPair,
+    sphinx_key_rotation: Option<api_requests::v1::node::models::SphinxKeyRotation>,
 ) -> Result<api_requests::v1::node::models::SignedHostInformation, NymNodeError> {
     let x25519_noise = if config.mixnet.debug.unsafe_disable_noise {
         String::new()
@@ -29,6 +30,7 @@ pub(crate) fn sign_host_details(
             ed25519_identity: ed22519_identity.public_key().to_base58_string(),
             x25519_sphinx: x22519_sphinx.to_base58_string(),
             x25519_noise,
+            sphinx_key_rotation,
         },
     };
 
@@ -8,10 +8,12 @@ use crate::node::helpers::{
     store_x25519_sphinx_keypair, DisplayDetails,
 };
 use crate::node::http::{sign_host_details, system_info::get_system_info};
+use crate::node::sphinx_key_rotation::{SphinxKeyRotation, SphinxKeyRotator};
 use nym_bin_common::bin_info_owned;
 use nym_crypto::asymmetric::{ed25519, x25519};
 use nym_gateway::Gateway;
 use nym_mixnode::MixNode;
+use nym_mixnode_common::packet_processor::sphinx_keys::ActiveSphinxKeys;
 use nym_network_requester::{
     set_active_gateway, setup_fs_gateways_storage, store_gateway_details, CustomGatewayDetails,
     GatewayDetails, GatewayRegistration,
@@ -26,6 +28,9 @@ use nym_node::config::{
 use nym_node::error::{EntryGatewayError, ExitGatewayError, MixnodeError, NymNodeError};
 use nym_node_http_api::api::api_requests;
 use nym_node_http_api::api::api_requests::v1::node::models::NodeDescription;
+use nym_node_http_api::api::api_requests::SignedHostInformation;
+use nym_node_http_api::state::exit_policy::SharedExitPolicy;
+use nym_node_http_api::state::host_information::SharedHostInformation;
 use nym_node_http_api::state::metrics::{SharedMixingStats, SharedVerlocStats};
 use nym_node_http_api::state::AppState;
 use nym_node_http_api::{NymNodeHTTPServer, NymNodeRouter};
@@ -47,6 +52,7 @@ pub mod bonding_information;
 pub mod description;
 pub mod helpers;
 pub(crate) mod http;
+pub(crate) mod sphinx_key_rotation;
 
 pub struct MixnodeData {
     mixing_stats: SharedMixingStats,
@@ -90,6 +96,7 @@ impl EntryGatewayData {
             client_storage: nym_gateway::node::PersistentStorage::init(
                 &config.storage_paths.clients_storage,
                 config.debug.message_retrieval_limit,
+                config.debug.inbox,
             )
             .await
             .map_err(nym_gateway::GatewayError::from)?,
@@ -108,6 +115,9 @@ pub struct ExitGatewayData {
     ipr_ed25519: ed25519::PublicKey,
     ipr_x25519: x25519::PublicKey,
 
+    // exit policy used by the embedded network requester, updated whenever it gets refreshed
+    exit_policy: SharedExitPolicy,
+
     auth_ed25519: ed25519::PublicKey,
     auth_x25519: x25519::PublicKey,
 }
@@ -251,6 +261,15 @@ impl ExitGatewayData {
             "authenticator x25519",
         )?;
 
+        // placeholder until the embedded network requester retrieves the actual policy
+        let exit_policy = SharedExitPolicy::new(
+            api_requests::v1::network_requester::exit_policy::models::UsedExitPolicy {
+                enabled: true,
+                upstream_source: config.upstream_exit_policy_url.to_string(),
+                ..Default::default()
+            },
+        );
+
         Ok(ExitGatewayData {
             nr_ed25519,
             nr_x25519,
@@ -258,6 +277,7 @@ impl ExitGatewayData {
             ipr_x25519,
             auth_ed25519,
             auth_x25519,
+            exit_policy,
         })
     }
 }
@@ -323,6 +343,13 @@ pub(crate) struct NymNode {
     ed25519_identity_keys: Arc<ed25519::KeyPair>,
     x25519_sphinx_keys: Arc<x25519::KeyPair>,
 
+    /// Rotating sphinx keys, if enabled. Note that `x25519_sphinx_keys` always corresponds to the bonded key
+    /// which remains valid alongside the rotated ones. It's moved into the rotator task once the node starts.
+    sphinx_key_rotation: Option<SphinxKeyRotation>,
+
+    /// Handle to the rotating sphinx keys used by the packet processors.
+    active_sphinx_keys: Option<ActiveSphinxKeys>,
+
     // to be used when noise is integrated
     #[allow(dead_code)]
     x25519_noise_keys: Arc<x25519::KeyPair>,
@@ -445,13 +472,32 @@ impl NymNode {
 
     pub(crate) async fn new(config: Config) -> Result<Self, NymNodeError> {
         let wireguard_data = WireguardData::new(&config.wireguard)?;
+
+        let x25519_sphinx_keys = Arc::new(load_x25519_sphinx_keypair(
+            config.storage_paths.keys.x25519_sphinx_storage_paths(),
+        )?);
+
+        let key_rotation_cfg = config.mixnet.debug.sphinx_key_rotation;
+        let sphinx_key_rotation = if key_rotation_cfg.enabled {
+            Some(SphinxKeyRotation::load(
+                config.storage_paths.keys.sphinx_key_rotation_directory(),
+                key_rotation_cfg.schedule,
+                &x25519_sphinx_keys,
+            )?)
+        } else {
+            None
+        };
+        let active_sphinx_keys = sphinx_key_rotation
+            .as_ref()
+            .map(|rotation| rotation.active_keys());
+
         Ok(NymNode {
             ed25519_identity_keys: Arc::new(load_ed25519_identity_keypair(
                 config.storage_paths.keys.ed25519_identity_storage_paths(),
             )?),
-            x25519_sphinx_keys: Arc::new(load_x25519_sphinx_keypair(
-                config.storage_paths.keys.x25519_sphinx_storage_paths(),
-            )?),
+            x25519_sphinx_keys,
+            sphinx_key_rotation,
+            active_sphinx_keys,
             x25519_noise_keys: Arc::new(load_x25519_noise_keypair(
                 config.storage_paths.keys.x25519_noise_storage_paths(),
             )?),
@@ -545,6 +591,9 @@ impl NymNode {
         mixnode.set_task_client(task_client);
         mixnode.set_mixing_stats(self.mixnode.mixing_stats.clone());
         mixnode.set_verloc_stats(self.verloc_stats.clone());
+        if let Some(sphinx_keys) = &self.active_sphinx_keys {
+            mixnode.set_active_sphinx_keys(sphinx_keys.clone());
+        }
 
         tokio::spawn(async move {
             if let Err(err) = mixnode.run().await {
@@ -570,6 +619,9 @@ impl NymNode {
         );
         entry_gateway.disable_http_server();
         entry_gateway.set_task_client(task_client);
+        if let Some(sphinx_keys) = &self.active_sphinx_keys {
+            entry_gateway.set_active_sphinx_keys(sphinx_keys.clone());
+        }
         #[cfg(all(feature = "wireguard", target_os = "linux"))]
         entry_gateway.set_wireguard_data(self.wireguard.into());
 
@@ -597,7 +649,11 @@ impl NymNode {
             self.entry_gateway.client_storage.clone(),
         );
         exit_gateway.disable_http_server();
+        exit_gateway.set_shared_exit_policy(self.exit_gateway.exit_policy.clone());
         exit_gateway.set_task_client(task_client);
+        if let Some(sphinx_keys) = &self.active_sphinx_keys {
+            exit_gateway.set_active_sphinx_keys(sphinx_keys.clone());
+        }
         #[cfg(all(feature = "wireguard", target_os = "linux"))]
         exit_gateway.set_wireguard_data(self.wireguard.into());
 
@@ -609,14 +665,28 @@ impl NymNode {
         Ok(())
     }
 
-    pub(crate) async fn build_http_server(&self) -> Result<NymNodeHTTPServer, NymNodeError> {
-        let host_details = sign_host_details(
+    fn sign_host_details(&self) -> Result<SignedHostInformation, NymNodeError> {
+        // with the rotation enabled, announce the current rotated key rather than the bonded one
+        let sphinx_keys = match &self.sphinx_key_rotation {
+            Some(rotation) => rotation.current_keypair(),
+            None => self.x25519_sphinx_keys.clone(),
+        };
+
+        sign_host_details(
             &self.config,
-            self.x25519_sphinx_keys.public_key(),
+            sphinx_keys.public_key(),
             self.x25519_noise_keys.public_key(),
             &self.ed25519_identity_keys,
-        )?;
+            self.sphinx_key_rotation
+                .as_ref()
+                .and_then(|rotation| rotation.announced_rotation()),
+        )
+    }
 
+    pub(crate) async fn build_http_server(
+        &self,
+        host_information: SharedHostInformation,
+    ) -> Result<NymNodeHTTPServer, NymNodeError> {
         let auxiliary_details = api_requests::v1::node::models::AuxiliaryDetails {
             location: self.config.host.location,
             accepted_operator_terms_and_conditions: self.accepted_operator_terms_and_conditions,
@@ -669,27 +739,16 @@ impl NymNode {
             address: self.exit_authenticator_address().to_string(),
         };
 
-        let exit_policy_details =
-            api_requests::v1::network_requester::exit_policy::models::UsedExitPolicy {
-                enabled: true,
-                upstream_source: self
-                    .config
-                    .exit_gateway
-                    .upstream_exit_policy_url
-                    .to_string(),
-                last_updated: 0,
-                // TODO: this will require some refactoring to actually retrieve the data from the embedded providers
-                policy: None,
-            };
-
-        let mut config = nym_node_http_api::Config::new(bin_info_owned!(), host_details)
+        let host_info = host_information.read().await.clone();
+        let mut config = nym_node_http_api::Config::new(bin_info_owned!(), host_info)
+            .with_shared_host_information(host_information)
             .with_landing_page_assets(self.config.http.landing_page_assets_path.as_ref())
             .with_mixnode_details(mixnode_details)
             .with_gateway_details(gateway_details)
             .with_network_requester_details(nr_details)
             .with_ip_packet_router_details(ipr_details)
             .with_authenticator_details(auth_details)
-            .with_used_exit_policy(exit_policy_details)
+            .with_shared_exit_policy(self.exit_gateway.exit_policy.clone())
             .with_description(self.description.clone())
             .with_auxiliary_details(auxiliary_details);
 
@@ -719,10 +778,37 @@ impl NymNode {
             .await?)
     }
 
-    pub(crate) async fn run(self) -> Result<(), NymNodeError> {
+    fn start_sphinx_key_rotator(
+        &mut self,
+        host_information: SharedHostInformation,
+        task_client: TaskClient,
+    ) {
+        let Some(rotation) = self.sphinx_key_rotation.take() else {
+            return;
+        };
+
+        info!("starting the sphinx key rotator");
+        SphinxKeyRotator::new(
+            rotation,
+            self.config.clone(),
+            *self.x25519_noise_keys.public_key(),
+            self.ed25519_identity_keys.clone(),
+            host_information,
+            task_client,
+        )
+        .start();
+    }
+
+    pub(crate) async fn run(mut self) -> Result<(), NymNodeError> {
         let mut task_manager = TaskManager::default().named("NymNode");
+        let host_information = SharedHostInformation::new(self.sign_host_details()?);
+        self.start_sphinx_key_rotator(
+            host_information.clone(),
+            task_manager.subscribe_named("sphinx-key-rotator"),
+        );
+
         let http_server = self
-            .build_http_server()
+            .build_http_server(host_information)
             .await?
             .with_task_client(task_manager.subscribe_named("http-server"));
         let bind_address = self.config.http.bind_address;
@@ -0,0 +1,474 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node::helpers::{load_x25519_sphinx_keypair, store_x25519_sphinx_keypair};
+use crate::node::http::sign_host_details;
+use nym_crypto::asymmetric::{ed25519, x25519};
+use nym_mixnode_common::packet_processor::sphinx_keys::ActiveSphinxKeys;
+use nym_node::config::Config;
+use nym_node::error::NymNodeError;
+use nym_node_http_api::api::api_requests::v1::node::models::SphinxKeyRotation as AnnouncedRotation;
+use nym_node_http_api::state::host_information::SharedHostInformation;
+use nym_pemstore::KeyPairPath;
+use nym_sphinx_params::SphinxKeyRotationSchedule;
+use nym_task::TaskClient;
+use rand::rngs::OsRng;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tracing::{debug, error, info, trace, warn};
+
+const SPHINX_KEY_FILENAME_PREFIX: &str = "x25519_sphinx_";
+const PUBLIC_KEY_EXTENSION: &str = "pub";
+
+/// On-disk storage of the sphinx keys of the individual rotations.
+pub(crate) struct SphinxKeyStore {
+    directory: PathBuf,
+}
+
+impl SphinxKeyStore {
+    pub(crate) fn new<P: AsRef<Path>>(directory: P) -> Self {
+        SphinxKeyStore {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    pub(crate) fn key_paths(&self, rotation_id: u32) -> KeyPairPath {
+        let private = self
+            .directory
+            .join(format!("{SPHINX_KEY_FILENAME_PREFIX}{rotation_id}"));
+        let public = private.with_extension(PUBLIC_KEY_EXTENSION);
+        KeyPairPath::new(private, public)
+    }
+
+    fn io_failure(&self, source: io::Error) -> NymNodeError {
+        NymNodeError::SphinxKeyStoreFailure {
+            path: self.directory.clone(),
+            source,
+        }
+    }
+
+    /// Loads the key of the provided rotation or generates (and persists) a fresh one if it doesn't exist yet.
+    fn load_or_generate(&self, rotation_id: u32) -> Result<x25519::KeyPair, NymNodeError> {
+        let paths = self.key_paths(rotation_id);
+        if paths.private_key_path.exists() {
+            return load_x25519_sphinx_keypair(paths);
+        }
+
+        debug!("generating new sphinx key for rotation {rotation_id}");
+        let keys = x25519::KeyPair::new(&mut OsRng);
+        store_x25519_sphinx_keypair(&keys, paths)?;
+        Ok(keys)
+    }
+
+    /// Returns ids of all rotations that have their keys currently stored on disk.
+    pub(crate) fn stored_rotation_ids(&self) -> Result<Vec<u32>, NymNodeError> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(self.io_failure(err)),
+        };
+
+        let mut ids = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|err| self.io_failure(err))?;
+            let name = entry.file_name();
+            let Some(id) = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(SPHINX_KEY_FILENAME_PREFIX))
+                .and_then(|id| id.parse().ok())
+            else {
+                continue;
+            };
+            ids.push(id)
+        }
+        Ok(ids)
+    }
+
+    /// Removes the keys of the provided rotation making sure the private key gets overwritten beforehand
+    /// so that it couldn't be recovered from the disk.
+    fn securely_remove(&self, rotation_id: u32) -> Result<(), NymNodeError> {
+        let paths = self.key_paths(rotation_id);
+        overwrite_and_remove(&paths.private_key_path).map_err(|err| self.io_failure(err))?;
+        match fs::remove_file(&paths.public_key_path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(self.io_failure(err)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn overwrite_and_remove(path: &Path) -> io::Result<()> {
+    let len = match fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    let mut file = fs::OpenOptions::new().write(true).open(path)?;
+    file.write_all(&vec![0u8; len as usize])?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::remove_file(path)
+}
+
+/// Sphinx keys of this node following the configured rotation schedule.
+pub(crate) struct SphinxKeyRotation {
+    schedule: SphinxKeyRotationSchedule,
+    store: SphinxKeyStore,
+    keys: BTreeMap<u32, Arc<x25519::KeyPair>>,
+    active_keys: ActiveSphinxKeys,
+}
+
+impl SphinxKeyRotation {
+    /// Loads all still valid keys from the store, generating the current and the next key if needed.
+    /// The bonded key remains valid alongside the rotated keys, as that's the key clients learn
+    /// about unless they obtain the node's self-described data.
+    pub(crate) fn load<P: AsRef<Path>>(
+        store_directory: P,
+        schedule: SphinxKeyRotationSchedule,
+        bonded_keys: &x25519::KeyPair,
+    ) -> Result<Self, NymNodeError> {
+        Self::load_at(store_directory, schedule, bonded_keys, SystemTime::now())
+    }
+
+    fn load_at<P: AsRef<Path>>(
+        store_directory: P,
+        schedule: SphinxKeyRotationSchedule,
+        bonded_keys: &x25519::KeyPair,
+        now: SystemTime,
+    ) -> Result<Self, NymNodeError> {
+        let store = SphinxKeyStore::new(store_directory);
+        let current = schedule.rotation_id(now);
+
+        let current_keys = Arc::new(store.load_or_generate(current)?);
+        let active_keys = ActiveSphinxKeys::new(current, current_keys.private_key().into());
+        active_keys.set_bonded_key(bonded_keys.private_key().into());
+        let mut keys = BTreeMap::new();
+        keys.insert(current, current_keys);
+
+        let mut rotation = SphinxKeyRotation {
+            schedule,
+            store,
+            keys,
+            active_keys,
+        };
+
+        // restore keys of the previous rotations that are still within their grace period
+        for id in rotation.store.stored_rotation_ids()? {
+            if id < current && schedule.is_key_valid(id, now) {
+                let keypair = load_x25519_sphinx_keypair(rotation.store.key_paths(id))?;
+                rotation.insert_key(id, keypair);
+            }
+        }
+
+        rotation.update(now)?;
+        Ok(rotation)
+    }
+
+    fn insert_key(&mut self, rotation_id: u32, keypair: x25519::KeyPair) {
+        self.active_keys
+            .insert(rotation_id, keypair.private_key().into());
+        self.keys.insert(rotation_id, Arc::new(keypair));
+    }
+
+    /// Brings the keys in line with the schedule at the provided time, i.e. it makes sure the key
+    /// of the current rotation is used as the primary one, the next key has already been generated
+    /// and that all expired keys got removed. Returns whether the primary key has changed.
+    fn update(&mut self, now: SystemTime) -> Result<bool, NymNodeError> {
+        let current = self.schedule.rotation_id(now);
+        let previous_primary = self.active_keys.primary_rotation_id();
+
+        for id in [current, current.saturating_add(1)] {
+            if !self.keys.contains_key(&id) {
+                let keypair = self.store.load_or_generate(id)?;
+                self.insert_key(id, keypair);
+            }
+        }
+        self.active_keys.set_primary(current);
+
+        let expired = self
+            .store
+            .stored_rotation_ids()?
+            .into_iter()
+            .chain(self.keys.keys().copied())
+            .filter(|id| !self.schedule.is_key_valid(*id, now))
+            .collect::<Vec<_>>();
+        for id in expired {
+            if self.keys.remove(&id).is_some() {
+                self.active_keys.remove(id);
+                info!("sphinx key of rotation {id} has expired");
+            }
+            self.store.securely_remove(id)?;
+        }
+
+        Ok(previous_primary != current)
+    }
+
+    /// Returns the amount of time until the keys have to be updated again,
+    /// i.e. until either the next rotation begins or the previous key expires.
+    fn until_next_update(&self, now: SystemTime) -> Duration {
+        let current = self.schedule.rotation_id(now);
+        let mut next_update = self.schedule.rotation_end(current);
+
+        let previous_expiration = self.schedule.key_expiration(current.saturating_sub(1));
+        if previous_expiration > now && previous_expiration < next_update {
+            next_update = previous_expiration
+        }
+        next_update.duration_since(now).unwrap_or_default()
+    }
+
+    /// Handle to the keys used by the packet processors.
+    pub(crate) fn active_keys(&self) -> ActiveSphinxKeys {
+        self.active_keys.clone()
+    }
+
+    /// Returns the keypair of the current primary key.
+    pub(crate) fn current_keypair(&self) -> Arc<x25519::KeyPair> {
+        let primary = self.active_keys.primary_rotation_id();
+        // SAFETY: the primary key is always inserted into the map alongside the active keys
+        #[allow(clippy::expect_used)]
+        self.keys
+            .get(&primary)
+            .cloned()
+            .expect("the primary sphinx key is missing")
+    }
+
+    /// Returns the rotation details to be announced alongside the current key.
+    pub(crate) fn announced_rotation(&self) -> Option<AnnouncedRotation> {
+        let primary = self.active_keys.primary_rotation_id();
+        let next = self.keys.get(&primary.saturating_add(1))?;
+        Some(AnnouncedRotation {
+            current_rotation_id: primary,
+            next_x25519_sphinx: next.public_key().to_base58_string(),
+        })
+    }
+}
+
+/// Task responsible for rotating the sphinx keys according to the schedule
+/// and updating the host information announced through the http api.
+pub(crate) struct SphinxKeyRotator {
+    rotation: SphinxKeyRotation,
+    config: Config,
+    x25519_noise: x25519::PublicKey,
+    ed25519_identity: Arc<ed25519::KeyPair>,
+    host_information: SharedHostInformation,
+    shutdown: TaskClient,
+}
+
+impl SphinxKeyRotator {
+    pub(crate) fn new(
+        rotation: SphinxKeyRotation,
+        config: Config,
+        x25519_noise: x25519::PublicKey,
+        ed25519_identity: Arc<ed25519::KeyPair>,
+        host_information: SharedHostInformation,
+        shutdown: TaskClient,
+    ) -> Self {
+        SphinxKeyRotator {
+            rotation,
+            config,
+            x25519_noise,
+            ed25519_identity,
+            host_information,
+            shutdown,
+        }
+    }
+
+    async fn update_keys(&mut self) {
+        let primary_changed = match self.rotation.update(SystemTime::now()) {
+            Ok(primary_changed) => primary_changed,
+            Err(err) => {
+                error!("failed to update the sphinx keys: {err}");
+                return;
+            }
+        };
+
+        if !primary_changed {
+            return;
+        }
+
+        info!(
+            "rotated sphinx key. the current rotation is {}",
+            self.rotation.active_keys.primary_rotation_id()
+        );
+        match sign_host_details(
+            &self.config,
+            self.rotation.current_keypair().public_key(),
+            &self.x25519_noise,
+            &self.ed25519_identity,
+            self.rotation.announced_rotation(),
+        ) {
+            Ok(signed) => self.host_information.update(signed).await,
+            Err(err) => warn!("failed to sign the updated host information: {err}"),
+        }
+    }
+
+    async fn run(mut self) {
+        while !self.shutdown.is_shutdown() {
+            // make sure we'd never end up in a busy loop if the clock misbehaves
+            let wait = self
+                .rotation
+                .until_next_update(SystemTime::now())
+                .max(Duration::from_secs(1));
+
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("SphinxKeyRotator: Received shutdown");
+                }
+                _ = tokio::time::sleep(wait) => {
+                    self.update_keys().await;
+                }
+            }
+        }
+        trace!("SphinxKeyRotator: Exiting");
+    }
+
+    pub(crate) fn start(self) {
+        tokio::spawn(self.run());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn schedule() -> SphinxKeyRotationSchedule {
+        SphinxKeyRotationSchedule {
+            rotation_period: Duration::from_secs(100),
+            grace_period: Duration::from_secs(10),
+        }
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn public_key(rotation: &SphinxKeyRotation, rotation_id: u32) -> Option<x25519::PublicKey> {
+        rotation
+            .keys
+            .get(&rotation_id)
+            .map(|keys| *keys.public_key())
+    }
+
+    #[test]
+    fn current_and_next_keys_are_available_upon_loading() {
+        let dir = tempfile::tempdir().unwrap();
+        let bonded = x25519::KeyPair::new(&mut OsRng);
+        let rotation =
+            SphinxKeyRotation::load_at(dir.path(), schedule(), &bonded, at(1250)).unwrap();
+
+        assert_eq!(rotation.active_keys().primary_rotation_id(), 12);
+        assert_eq!(rotation.active_keys().rotation_ids(), vec![12, 13]);
+        // current, next and the bonded key
+        assert_eq!(rotation.active_keys().keys().len(), 3);
+
+        let announced = rotation.announced_rotation().unwrap();
+        assert_eq!(announced.current_rotation_id, 12);
+        assert_eq!(
+            announced.next_x25519_sphinx,
+            public_key(&rotation, 13).unwrap().to_base58_string()
+        );
+        assert_eq!(
+            rotation.current_keypair().public_key(),
+            &public_key(&rotation, 12).unwrap()
+        );
+
+        // the keys survive restarts
+        let reloaded =
+            SphinxKeyRotation::load_at(dir.path(), schedule(), &bonded, at(1260)).unwrap();
+        assert_eq!(public_key(&reloaded, 12), public_key(&rotation, 12));
+        assert_eq!(public_key(&reloaded, 13), public_key(&rotation, 13));
+    }
+
+    #[test]
+    fn previous_key_is_accepted_within_the_grace_period() {
+        let dir = tempfile::tempdir().unwrap();
+        let bonded = x25519::KeyPair::new(&mut OsRng);
+        let mut rotation =
+            SphinxKeyRotation::load_at(dir.path(), schedule(), &bonded, at(1250)).unwrap();
+        let announced_next = public_key(&rotation, 13);
+        assert_eq!(
+            rotation.until_next_update(at(1250)),
+            Duration::from_secs(50)
+        );
+
+        // the next rotation begins: the announced key becomes the primary one
+        assert!(rotation.update(at(1300)).unwrap());
+        assert_eq!(rotation.active_keys().primary_rotation_id(), 13);
+        assert_eq!(public_key(&rotation, 13), announced_next);
+        assert_eq!(rotation.active_keys().rotation_ids(), vec![12, 13, 14]);
+        assert_eq!(
+            rotation.announced_rotation().unwrap().current_rotation_id,
+            13
+        );
+        // next update happens once the previous key expires
+        assert_eq!(
+            rotation.until_next_update(at(1300)),
+            Duration::from_secs(10)
+        );
+
+        // still within the grace period
+        assert!(!rotation.update(at(1305)).unwrap());
+        assert_eq!(rotation.active_keys().rotation_ids(), vec![12, 13, 14]);
+
+        // the grace period is over
+        assert!(!rotation.update(at(1310)).unwrap());
+        assert_eq!(rotation.active_keys().rotation_ids(), vec![13, 14]);
+        assert!(!rotation.store.key_paths(12).private_key_path.exists());
+        let mut stored = rotation.store.stored_rotation_ids().unwrap();
+        stored.sort();
+        assert_eq!(stored, vec![13, 14]);
+        assert_eq!(
+            rotation.until_next_update(at(1310)),
+            Duration::from_secs(90)
+        );
+    }
+
+    #[test]
+    fn bonded_key_remains_valid_across_rotations() {
+        let dir = tempfile::tempdir().unwrap();
+        let bonded = x25519::KeyPair::new(&mut OsRng);
+        let mut rotation =
+            SphinxKeyRotation::load_at(dir.path(), schedule(), &bonded, at(1250)).unwrap();
+
+        for secs in [1300, 1310, 1400, 1410, 1500] {
+            rotation.update(at(secs)).unwrap();
+        }
+        assert_eq!(rotation.active_keys().primary_rotation_id(), 15);
+        // (the sphinx keys are clamped, so compare the derived public keys rather than the raw bytes)
+        assert!(rotation.active_keys().keys().iter().any(|key| {
+            x25519::PrivateKey::from_bytes(&key.to_bytes())
+                .unwrap()
+                .public_key()
+                == *bonded.public_key()
+        }));
+
+        // and it's never announced as one of the rotated keys
+        let announced = rotation.announced_rotation().unwrap();
+        assert_ne!(
+            announced.next_x25519_sphinx,
+            bonded.public_key().to_base58_string()
+        );
+    }
+
+    #[test]
+    fn expired_keys_are_removed_when_loading() {
+        let dir = tempfile::tempdir().unwrap();
+        let bonded = x25519::KeyPair::new(&mut OsRng);
+        SphinxKeyRotation::load_at(dir.path(), schedule(), &bonded, at(1250)).unwrap();
+
+        // the node was offline for a while
+        let rotation =
+            SphinxKeyRotation::load_at(dir.path(), schedule(), &bonded, at(1650)).unwrap();
+        assert_eq!(rotation.active_keys().rotation_ids(), vec![16, 17]);
+        let mut stored = rotation.store.stored_rotation_ids().unwrap();
+        stored.sort();
+        assert_eq!(stored, vec![16, 17]);
+    }
+}
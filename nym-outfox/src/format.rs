@@ -271,6 +271,17 @@ impl MixStageParameters {
         buffer: &mut [u8],
         mix_secret_key: &[u8],
     ) -> Result<Vec<u8>, OutfoxError> {
+        self.decode_mix_layer_with_shared_key(buffer, mix_secret_key)
+            .map(|(routing_data, _)| routing_data)
+    }
+
+    /// Decodes the mix layer, returning the routing data alongside the key shared with the packet creator.
+    /// Note that the buffer is left intact if the header fails to authenticate.
+    pub fn decode_mix_layer_with_shared_key(
+        &self,
+        buffer: &mut [u8],
+        mix_secret_key: &[u8],
+    ) -> Result<(Vec<u8>, MontgomeryPoint), OutfoxError> {
         // Check the length of the incoming buffer is correct.
 
         let mix_secret_key = Scalar::from_bytes_mod_order(mix_secret_key.try_into()?);
@@ -306,7 +317,7 @@ impl MixStageParameters {
         // Do a round of LION on the payload
         lion_transform_decrypt(&mut buffer[self.payload_range()], &shared_key.0)?;
 
-        Ok(routing_data)
+        Ok((routing_data, shared_key))
     }
 }
 
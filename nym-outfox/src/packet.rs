@@ -194,11 +194,8 @@ impl OutfoxPacket {
             .all(|x| x == &0)
     }
 
-    pub fn decode_next_layer(
-        &mut self,
-        mix_secret_key: &PrivateKey,
-    ) -> Result<[u8; 32], OutfoxError> {
-        let mix_secret_key = mix_secret_key.to_bytes();
+    /// Determines the index of the mix layer that is going to get decoded next.
+    pub fn current_layer(&self) -> usize {
         let routing_lenght_by_stage = self
             .mix_params()
             .routing_information_length_by_stage
@@ -210,11 +207,33 @@ impl OutfoxPacket {
                 break;
             }
         }
-        self.decode_mix_layer(layer, &mix_secret_key)?;
+        layer
+    }
+
+    pub fn decode_next_layer(
+        &mut self,
+        mix_secret_key: &PrivateKey,
+    ) -> Result<[u8; 32], OutfoxError> {
+        self.decode_next_layer_with_shared_key(mix_secret_key)
+            .map(|(routing_address, _)| routing_address)
+    }
+
+    /// Decodes the next layer of the packet, returning the routing address alongside
+    /// the bytes of the key shared with the packet creator.
+    /// If the layer hasn't been created for the provided key, the packet is left intact.
+    pub fn decode_next_layer_with_shared_key(
+        &mut self,
+        mix_secret_key: &PrivateKey,
+    ) -> Result<([u8; 32], [u8; 32]), OutfoxError> {
+        let mix_secret_key = mix_secret_key.to_bytes();
+        let layer = self.current_layer();
+        let (range, params) = self.stage_params(layer);
+        let (_, shared_key) = params
+            .decode_mix_layer_with_shared_key(&mut self.payload_mut()[range], &mix_secret_key)?;
         self.update_routing_information(layer)?;
         let (range, stage_params) = self.mix_params().get_stage_params(layer);
         let routing_bytes = &self.payload()[range][stage_params.routing_data_range()];
         let routing_address: [u8; 32] = routing_bytes.try_into()?;
-        Ok(routing_address)
+        Ok((routing_address, shared_key.to_bytes()))
     }
 }
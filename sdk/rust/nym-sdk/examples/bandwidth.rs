@@ -30,7 +30,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Send a message throughout the mixnet to ourselves
     client
-        .send_plain_message(*our_address, "hello there")
+        .send_plain_message(our_address, "hello there")
         .await?;
 
     println!("Waiting for message");
@@ -20,7 +20,7 @@ async fn main() {
 
     // Send a message through the mixnet to ourselves
     client
-        .send_plain_message(*our_address, "hello there")
+        .send_plain_message(our_address, "hello there")
         .await
         .unwrap();
 
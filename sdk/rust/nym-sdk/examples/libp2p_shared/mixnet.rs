@@ -22,7 +22,7 @@ pub(crate) async fn initialize_mixnet(
     ),
     Error,
 > {
-    let recipient = *client.nym_address();
+    let recipient = client.nym_address();
 
     // a channel of inbound messages from the mixnet..
     // the transport reads from (listens) to the inbound_rx.
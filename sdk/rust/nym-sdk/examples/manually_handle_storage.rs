@@ -24,7 +24,7 @@ async fn main() {
 
     // Send important info up the pipe to a buddy
     client
-        .send_plain_message(*our_address, "hello there")
+        .send_plain_message(our_address, "hello there")
         .await
         .unwrap();
 
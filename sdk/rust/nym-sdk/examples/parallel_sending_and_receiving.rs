@@ -13,7 +13,7 @@ async fn main() {
     let mut client = mixnet::MixnetClient::connect_new().await.unwrap();
 
     // Be able to get our client address
-    let our_address = *client.nym_address();
+    let our_address = client.nym_address();
     println!("Our client nym address is: {our_address}");
 
     let sender = client.split_sender();
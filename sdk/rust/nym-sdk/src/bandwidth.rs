@@ -26,7 +26,7 @@
 //!     let our_address = client.nym_address();
 //!
 //!     // Send a message throughout the mixnet to ourselves
-//!     client.send_plain_message(*our_address, "hello there").await.unwrap();
+//!     client.send_plain_message(our_address, "hello there").await.unwrap();
 //!
 //!     println!("Waiting for message");
 //!     if let Some(received) = client.wait_for_messages().await {
@@ -94,6 +94,26 @@ pub enum Error {
     #[error("failed to send the provided message")]
     MessageSendingFailure,
 
+    #[error(transparent)]
+    ServiceMessagingError(
+        #[from] nym_service_providers_common::interface::ServiceProviderMessagingError,
+    ),
+
+    #[error("received a malformed response from the service: {message}")]
+    MalformedServiceResponse { message: String },
+
+    #[error("received an unexpected control response from the service: {response}")]
+    UnexpectedControlResponse { response: String },
+
+    #[error("the service failed to handle the request: {message}")]
+    ServiceError { message: String },
+
+    #[error("the service did not respond within {timeout:?}")]
+    ServiceResponseTimeout { timeout: std::time::Duration },
+
+    #[error("the mixnet client has shut down")]
+    MixnetClientShutdown,
+
     #[error("this operation is currently unsupported: {details}")]
     Unsupported { details: String },
 }
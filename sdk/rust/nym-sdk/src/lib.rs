@@ -1,11 +1,12 @@
 //! Rust SDK for the Nym platform
 //!
-//! The main component currently is [`mixnet`].
+//! The main component currently is [`mixnet`], with [`service`] allowing to run custom services on top of it.
 
 mod error;
 
 pub mod bandwidth;
 pub mod mixnet;
+pub mod service;
 
 pub use error::{Error, Result};
 pub use nym_client_core::client::mix_traffic::transceiver::*;
@@ -17,7 +17,7 @@
 //!     println!("Our client nym address is: {our_address}");
 //!
 //!     // Send a message throught the mixnet to ourselves
-//!     client.send_plain_message(*our_address, "hello there").await.unwrap();
+//!     client.send_plain_message(our_address, "hello there").await.unwrap();
 //!
 //!     println!("Waiting for message");
 //!     if let Some(received) = client.wait_for_messages().await {
@@ -50,6 +50,9 @@ pub use nym_client_core::{
             },
             Ephemeral, MixnetClientStorage, OnDiskPersistent,
         },
+        delivery_status::{
+            DeliveryStatus, DeliveryStatusEvent, DeliveryStatusReceiver, MessageTrackingId,
+        },
         inbound_messages::InputMessage,
         key_manager::{
             persistence::{InMemEphemeralKeys, KeyStore, OnDiskKeys},
@@ -71,7 +74,7 @@ pub use nym_network_defaults::NymNetworkDetails;
 pub use nym_socks5_client_core::config::Socks5;
 pub use nym_sphinx::{
     addressing::{
-        clients::{ClientIdentity, Recipient},
+        clients::{ClientIdentity, MultiHomedRecipient, Recipient},
         nodes::NodeIdentity,
     },
     anonymous_replies::requests::AnonymousSenderTag,
@@ -4,7 +4,7 @@
 use super::{connection_state::BuilderState, Config, StoragePaths};
 use crate::bandwidth::BandwidthAcquireClient;
 use crate::mixnet::socks5_client::Socks5MixnetClient;
-use crate::mixnet::{CredentialStorage, MixnetClient, Recipient};
+use crate::mixnet::{CredentialStorage, MixnetClient, NodeIdentity, Recipient};
 use crate::GatewayTransceiver;
 use crate::NymNetworkDetails;
 use crate::{Error, Result};
@@ -53,6 +53,7 @@ pub struct MixnetClientBuilder<S: MixnetClientStorage = Ephemeral> {
     wait_for_gateway: bool,
     custom_topology_provider: Option<Box<dyn TopologyProvider + Send + Sync>>,
     custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send + Sync>>,
+    standby_gateways: Vec<NodeIdentity>,
     custom_shutdown: Option<TaskClient>,
     force_tls: bool,
     user_agent: Option<UserAgent>,
@@ -94,6 +95,7 @@ impl MixnetClientBuilder<OnDiskPersistent> {
             gateway_endpoint_config_path: None,
             custom_shutdown: None,
             custom_gateway_transceiver: None,
+            standby_gateways: Vec::new(),
             force_tls: false,
             user_agent: None,
         })
@@ -121,6 +123,7 @@ where
             wait_for_gateway: false,
             custom_topology_provider: None,
             custom_gateway_transceiver: None,
+            standby_gateways: Vec::new(),
             custom_shutdown: None,
             force_tls: false,
             user_agent: None,
@@ -140,6 +143,7 @@ where
             wait_for_gateway: self.wait_for_gateway,
             custom_topology_provider: self.custom_topology_provider,
             custom_gateway_transceiver: self.custom_gateway_transceiver,
+            standby_gateways: self.standby_gateways,
             custom_shutdown: self.custom_shutdown,
             force_tls: self.force_tls,
             user_agent: self.user_agent,
@@ -254,6 +258,17 @@ where
         self
     }
 
+    /// Simultaneously connect to the specified gateways in addition to the primary one ("hot-standby"),
+    /// so that the client would remain reachable even if some of its gateways go down.
+    /// Senders can use the address returned by [`MixnetClient::multi_homed_address`] to pick a live gateway.
+    ///
+    /// Note that with enabled credentials mode, only the primary gateway is able to claim more bandwidth.
+    #[must_use]
+    pub fn with_standby_gateways(mut self, standby_gateways: Vec<NodeIdentity>) -> Self {
+        self.standby_gateways = standby_gateways;
+        self
+    }
+
     /// Use specified file for storing gateway configuration.
     pub fn gateway_endpoint_config_path<P: AsRef<Path>>(mut self, path: P) -> Self {
         self.gateway_endpoint_config_path = Some(path.as_ref().to_owned());
@@ -266,6 +281,7 @@ where
             DisconnectedMixnetClient::new(self.config, self.socks5_config, self.storage)?;
 
         client.custom_gateway_transceiver = self.custom_gateway_transceiver;
+        client.standby_gateways = self.standby_gateways;
         client.custom_topology_provider = self.custom_topology_provider;
         client.custom_shutdown = self.custom_shutdown;
         client.wireguard_mode = self.wireguard_mode;
@@ -309,6 +325,9 @@ where
     /// advanced usage of custom gateways
     custom_gateway_transceiver: Option<Box<dyn GatewayTransceiver + Send + Sync>>,
 
+    /// Additional gateways the client is going to be simultaneously connected to.
+    standby_gateways: Vec<NodeIdentity>,
+
     /// If the client connects via Wireguard tunnel to the gateway.
     wireguard_mode: bool,
 
@@ -367,6 +386,7 @@ where
             storage,
             custom_topology_provider: None,
             custom_gateway_transceiver: None,
+            standby_gateways: Vec::new(),
             wireguard_mode: false,
             wait_for_gateway: false,
             force_tls: false,
@@ -580,7 +600,8 @@ where
         let mut base_builder: BaseClientBuilder<_, _> =
             BaseClientBuilder::new(&base_config, self.storage, self.dkg_query_client)
                 .with_wait_for_gateway(self.wait_for_gateway)
-                .with_wireguard_connection(self.wireguard_mode);
+                .with_wireguard_connection(self.wireguard_mode)
+                .with_standby_gateways(self.standby_gateways);
 
         if let Some(user_agent) = self.user_agent {
             base_builder = base_builder.with_user_agent(user_agent);
@@ -680,7 +701,6 @@ where
         }
 
         Ok(Socks5MixnetClient {
-            nym_address,
             client_state,
             task_handle: started_client.task_handle,
             socks5_config,
@@ -711,7 +731,7 @@ where
         if self.socks5_config.is_some() {
             return Err(Error::Socks5Config { set: true });
         }
-        let (mut started_client, nym_address) = self.connect_to_mixnet_common().await?;
+        let (mut started_client, _) = self.connect_to_mixnet_common().await?;
         let client_input = started_client.client_input.register_producer();
         let mut client_output = started_client.client_output.register_consumer();
         let client_state = started_client.client_state;
@@ -720,7 +740,7 @@ where
         let reconstructed_receiver = client_output.register_receiver()?;
 
         Ok(MixnetClient::new(
-            nym_address,
+            started_client.multi_homed_address,
             identity_keys,
             client_input,
             client_output,
@@ -5,13 +5,15 @@ use async_trait::async_trait;
 use futures::{ready, Stream, StreamExt};
 use log::error;
 use nym_client_core::client::base_client::GatewayConnection;
+use nym_client_core::client::self_address::SelfAddress;
 use nym_client_core::client::{
     base_client::{ClientInput, ClientOutput, ClientState},
+    delivery_status::DeliveryStatusReceiver,
     inbound_messages::InputMessage,
     received_buffer::ReconstructedMessagesReceiver,
 };
 use nym_crypto::asymmetric::identity;
-use nym_sphinx::addressing::clients::Recipient;
+use nym_sphinx::addressing::clients::{MultiHomedRecipient, Recipient};
 use nym_sphinx::{params::PacketType, receiver::ReconstructedMessage};
 use nym_task::{
     connections::{ConnectionCommandSender, LaneQueueLengths},
@@ -24,8 +26,8 @@ use std::task::{Context, Poll};
 
 /// Client connected to the Nym mixnet.
 pub struct MixnetClient {
-    /// The nym address of this connected client.
-    pub(crate) nym_address: Recipient,
+    /// The address of this client going through all of its gateways, if it's connected to more than one.
+    pub(crate) multi_homed_address: Option<MultiHomedRecipient>,
 
     pub(crate) identity_keys: Arc<identity::KeyPair>,
 
@@ -56,7 +58,7 @@ pub struct MixnetClient {
 impl MixnetClient {
     #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
-        nym_address: Recipient,
+        multi_homed_address: Option<MultiHomedRecipient>,
         identity_keys: Arc<identity::KeyPair>,
         client_input: ClientInput,
         client_output: ClientOutput,
@@ -66,7 +68,7 @@ impl MixnetClient {
         packet_type: Option<PacketType>,
     ) -> Self {
         Self {
-            nym_address,
+            multi_homed_address,
             identity_keys,
             client_input,
             client_output,
@@ -99,10 +101,32 @@ impl MixnetClient {
             .await
     }
 
-    /// Get the nym address for this client, if it is available. The nym address is composed of the
+    /// Get the current nym address of this client. The nym address is composed of the
     /// client identity, the client encryption key, and the gateway identity.
-    pub fn nym_address(&self) -> &Recipient {
-        &self.nym_address
+    ///
+    /// Note that the address changes if the client fails over to a different gateway,
+    /// so it should not be cached for longer than necessary.
+    pub fn nym_address(&self) -> Recipient {
+        self.client_state.self_address.current()
+    }
+
+    /// Get a handle for observing the address of this client, for example to get notified
+    /// whenever it changes as a result of a gateway failover.
+    pub fn self_address(&self) -> SelfAddress {
+        self.client_state.self_address.clone()
+    }
+
+    /// Get the address of this client going through all of its gateways.
+    /// It is only available if the client has been configured with standby gateways.
+    pub fn multi_homed_address(&self) -> Option<&MultiHomedRecipient> {
+        self.multi_homed_address.as_ref()
+    }
+
+    /// Choose the address through which the provided multi-homed client is currently reachable,
+    /// i.e. the most preferred one going through a gateway that's present in the current network topology.
+    pub async fn select_live_recipient(&self, address: &MultiHomedRecipient) -> Option<Recipient> {
+        let topology = self.read_current_topology().await?;
+        address.select(|gateway| topology.get_gateway(gateway).is_some())
     }
 
     /// Sign a message with the client's private identity key.
@@ -162,6 +186,13 @@ impl MixnetClient {
         self.client_state.topology_accessor.release_manual_control()
     }
 
+    /// Get the stream of delivery status changes of messages sent with an explicit tracking id,
+    /// for example via [`MixnetMessageSender::send_tracked_message`].
+    /// Note that only the most recently obtained receiver is going to get any updates.
+    pub fn delivery_status_events(&mut self) -> Result<DeliveryStatusReceiver> {
+        Ok(self.client_output.register_delivery_status_receiver()?)
+    }
+
     /// Wait for messages from the mixnet
     pub async fn wait_for_messages(&mut self) -> Option<Vec<ReconstructedMessage>> {
         self.reconstructed_receiver.next().await
@@ -10,9 +10,6 @@ use crate::Result;
 
 /// Client connected to the Nym mixnet.
 pub struct Socks5MixnetClient {
-    /// The nym address of this connected client.
-    pub(crate) nym_address: Recipient,
-
     /// The current state of the client that is exposed to the user. This includes things like
     /// current message send queue length.
     pub(crate) client_state: ClientState,
@@ -48,10 +45,12 @@ impl Socks5MixnetClient {
             .await
     }
 
-    /// Get the nym address for this client, if it is available. The nym address is composed of the
+    /// Get the current nym address of this client. The nym address is composed of the
     /// client identity, the client encryption key, and the gateway identity.
-    pub fn nym_address(&self) -> &Recipient {
-        &self.nym_address
+    ///
+    /// Note that the address changes if the client fails over to a different gateway.
+    pub fn nym_address(&self) -> Recipient {
+        self.client_state.self_address.current()
     }
 
     /// Get the SOCKS5 proxy URL that a HTTP(S) client can connect to.
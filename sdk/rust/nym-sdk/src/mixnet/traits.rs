@@ -1,7 +1,7 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::mixnet::{AnonymousSenderTag, IncludedSurbs, Recipient};
+use crate::mixnet::{AnonymousSenderTag, IncludedSurbs, MessageTrackingId, Recipient};
 use crate::Result;
 use async_trait::async_trait;
 use nym_client_core::client::inbound_messages::InputMessage;
@@ -88,6 +88,55 @@ pub trait MixnetMessageSender {
         self.send(input_msg).await
     }
 
+    /// Sends bytes to the supplied Nym address, just like [`MixnetMessageSender::send_message`],
+    /// but any changes to the message's delivery status are going to be reported under the
+    /// provided `tracking_id`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use nym_sdk::mixnet::{self, MixnetMessageSender};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let address = "foobar";
+    ///     let recipient = mixnet::Recipient::try_from_base58_string(address).unwrap();
+    ///     let mut client = mixnet::MixnetClient::connect_new().await.unwrap();
+    ///     let mut statuses = client.delivery_status_events().unwrap();
+    ///     let surbs = mixnet::IncludedSurbs::default();
+    ///     let tracking_id = mixnet::MessageTrackingId::new(42);
+    ///     client.send_tracked_message(recipient, "hi", surbs, tracking_id).await.unwrap();
+    /// }
+    /// ```
+    async fn send_tracked_message<M>(
+        &self,
+        address: Recipient,
+        message: M,
+        surbs: IncludedSurbs,
+        tracking_id: MessageTrackingId,
+    ) -> Result<()>
+    where
+        M: AsRef<[u8]> + Send,
+    {
+        let lane = TransmissionLane::General;
+        let input_msg = match surbs {
+            IncludedSurbs::Amount(surbs) => InputMessage::new_anonymous(
+                address,
+                message.as_ref().to_vec(),
+                surbs,
+                lane,
+                self.packet_type(),
+            ),
+            IncludedSurbs::ExposeSelfAddress => InputMessage::new_regular(
+                address,
+                message.as_ref().to_vec(),
+                lane,
+                self.packet_type(),
+            ),
+        };
+        self.send(input_msg.with_tracking_id(tracking_id)).await
+    }
+
     /// Sends reply data to the supplied anonymous recipient.
     ///
     /// # Example
@@ -0,0 +1,69 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+//! The service component of the Rust SDK for the Nym platform
+//!
+//! Allows running custom services on the mixnet using the same versioned request framing
+//! as the network requester. The service only has to implement the [`RequestHandler`] for its own
+//! request type, while the control requests, concurrency and sending back the responses are handled by
+//! the [`MixnetService`]. The [`ServiceClient`] can then be used for talking to such service.
+//!
+//! # Basic example
+//!
+//! ```no_run
+//! use nym_sdk::mixnet::{self, AnonymousSenderTag};
+//! use nym_sdk::service::{
+//!     BinaryInformation, EmptyMessage, MixnetService, ProviderInterfaceVersion, RequestHandler,
+//! };
+//!
+//! struct EchoHandler;
+//!
+//! #[async_trait::async_trait]
+//! impl RequestHandler<EmptyMessage> for EchoHandler {
+//!     type Error = String;
+//!
+//!     async fn handle_request(
+//!         &self,
+//!         _sender: Option<AnonymousSenderTag>,
+//!         request: EmptyMessage,
+//!         _interface_version: ProviderInterfaceVersion,
+//!     ) -> Result<Option<EmptyMessage>, Self::Error> {
+//!         Ok(Some(request))
+//!     }
+//!
+//!     fn binary_info(&self) -> BinaryInformation {
+//!         BinaryInformation {
+//!             binary_name: "echo".to_string(),
+//!             build_information: nym_bin_common::bin_info_owned!(),
+//!         }
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = mixnet::MixnetClient::connect_new().await.unwrap();
+//!     let service = MixnetService::new(client, EchoHandler).with_max_concurrent_requests(16);
+//!     println!("the service is listening on {}", service.nym_address());
+//!
+//!     // handle requests until ctrl-c is pressed, then finish whatever is still in progress
+//!     service
+//!         .run_until(async {
+//!             tokio::signal::ctrl_c().await.ok();
+//!         })
+//!         .await;
+//! }
+//! ```
+
+mod client;
+mod framing;
+mod handler;
+mod runner;
+
+pub use client::{ServiceClient, DEFAULT_REPLY_SURBS, DEFAULT_RESPONSE_TIMEOUT};
+pub use framing::RequestId;
+pub use handler::RequestHandler;
+pub use nym_service_providers_common::interface::{
+    BinaryInformation, ControlRequest, ControlResponse, EmptyMessage, ProviderInterfaceVersion,
+    Serializable, ServiceProviderMessagingError, ServiceProviderRequest, ServiceProviderResponse,
+    SupportedVersions, Version,
+};
+pub use runner::{MixnetService, DEFAULT_MAX_CONCURRENT_REQUESTS};
@@ -0,0 +1,246 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mixnet::{IncludedSurbs, MixnetClient, MixnetMessageSender, Recipient};
+use crate::service::framing::{self, RequestId};
+use crate::{Error, Result};
+use futures::StreamExt;
+use log::{debug, warn};
+use nym_service_providers_common::interface::{
+    BinaryInformation, ControlRequest, ControlResponse, ProviderInterfaceVersion, Request,
+    Response, ResponseContent, ServiceProviderMessagingError, ServiceProviderRequest,
+    SupportedVersions,
+};
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+pub const DEFAULT_REPLY_SURBS: u32 = 10;
+pub const DEFAULT_RESPONSE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Typed client for a service running on the mixnet, such as one started with [`MixnetService`](crate::service::MixnetService).
+///
+/// Requests are sent anonymously with reply SURBs attached and are processed one at a time,
+/// i.e. every request waits for its response before the next one can be sent.
+/// Every request carries its own id and any received message that isn't the response to the current request,
+/// such as a late response to an earlier request that has timed out, is discarded.
+pub struct ServiceClient<T> {
+    client: MixnetClient,
+    provider: Recipient,
+    next_request_id: RequestId,
+    interface_version: ProviderInterfaceVersion,
+    reply_surbs: u32,
+    response_timeout: Duration,
+    _request: PhantomData<fn() -> T>,
+}
+
+impl<T> ServiceClient<T>
+where
+    T: ServiceProviderRequest,
+    <T as ServiceProviderRequest>::Error: Display,
+{
+    pub fn new(client: MixnetClient, provider: Recipient) -> Self {
+        ServiceClient {
+            client,
+            provider,
+            next_request_id: rand::random(),
+            interface_version: ProviderInterfaceVersion::new_current(),
+            reply_surbs: DEFAULT_REPLY_SURBS,
+            response_timeout: DEFAULT_RESPONSE_TIMEOUT,
+            _request: PhantomData,
+        }
+    }
+
+    /// Sets the number of reply SURBs attached to every request.
+    #[must_use]
+    pub fn with_reply_surbs(mut self, reply_surbs: u32) -> Self {
+        self.reply_surbs = reply_surbs;
+        self
+    }
+
+    /// Sets the maximum amount of time to wait for a response to a request.
+    #[must_use]
+    pub fn with_response_timeout(mut self, response_timeout: Duration) -> Self {
+        self.response_timeout = response_timeout;
+        self
+    }
+
+    /// The address of the service this client is talking to.
+    pub fn provider(&self) -> &Recipient {
+        &self.provider
+    }
+
+    /// Sends the provided request to the service and waits for its response.
+    pub async fn send_request(&mut self, request: T) -> Result<T::Response> {
+        let request = Request::new_provider_data(self.interface_version, request);
+        match self.exchange(request).await? {
+            ResponseContent::ProviderData(response) => Ok(response),
+            ResponseContent::Control(_) => {
+                Err(ServiceProviderMessagingError::UnexpectedControlResponse.into())
+            }
+        }
+    }
+
+    /// Sends the provided control request to the service and waits for its response.
+    pub async fn send_control_request(
+        &mut self,
+        request: ControlRequest,
+    ) -> Result<ControlResponse> {
+        let request = Request::new_control(self.interface_version, request);
+        match self.exchange(request).await? {
+            ResponseContent::Control(response) => Ok(response),
+            ResponseContent::ProviderData(_) => {
+                Err(ServiceProviderMessagingError::UnexpectedProviderDataResponse.into())
+            }
+        }
+    }
+
+    /// Checks whether the service is up and healthy.
+    pub async fn check_health(&mut self) -> Result<()> {
+        match self.send_control_request(ControlRequest::Health).await? {
+            ControlResponse::Health => Ok(()),
+            other => Err(Error::UnexpectedControlResponse {
+                response: format!("{other:?}"),
+            }),
+        }
+    }
+
+    /// Retrieves information about the binary running the service.
+    pub async fn binary_info(&mut self) -> Result<BinaryInformation> {
+        match self
+            .send_control_request(ControlRequest::BinaryInfo)
+            .await?
+        {
+            ControlResponse::BinaryInfo(info) => Ok(*info),
+            other => Err(Error::UnexpectedControlResponse {
+                response: format!("{other:?}"),
+            }),
+        }
+    }
+
+    /// Retrieves the request versions supported by the service.
+    pub async fn supported_versions(&mut self) -> Result<SupportedVersions> {
+        match self
+            .send_control_request(ControlRequest::SupportedRequestVersions)
+            .await?
+        {
+            ControlResponse::SupportedRequestVersions(versions) => Ok(versions),
+            other => Err(Error::UnexpectedControlResponse {
+                response: format!("{other:?}"),
+            }),
+        }
+    }
+
+    /// Disconnects the underlying mixnet client.
+    pub async fn disconnect(self) {
+        self.client.disconnect().await
+    }
+
+    async fn exchange(&mut self, request: Request<T>) -> Result<ResponseContent<T>> {
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+
+        self.client
+            .send_message(
+                self.provider,
+                framing::frame(request_id, request.into_bytes()),
+                IncludedSurbs::new(self.reply_surbs),
+            )
+            .await?;
+
+        let client = &mut self.client;
+        let wait_for_response = async move {
+            loop {
+                let received = client.next().await.ok_or(Error::MixnetClientShutdown)?;
+                if let Some(response) = parse_response::<T>(request_id, &received.message) {
+                    return response;
+                }
+            }
+        };
+
+        tokio::time::timeout(self.response_timeout, wait_for_response)
+            .await
+            .map_err(|_| Error::ServiceResponseTimeout {
+                timeout: self.response_timeout,
+            })?
+    }
+}
+
+/// Attempts to parse the received message as the response to the request with the provided id.
+/// Returns `None` if the message is not a response to that request and thus should be ignored.
+fn parse_response<T>(expected_id: RequestId, raw: &[u8]) -> Option<Result<ResponseContent<T>>>
+where
+    T: ServiceProviderRequest,
+    <T as ServiceProviderRequest>::Error: Display,
+{
+    let Some((request_id, raw_response)) = framing::split(raw) else {
+        warn!("received a message without a request id - ignoring it");
+        return None;
+    };
+    if request_id != expected_id {
+        debug!("received a response to request {request_id} while waiting for {expected_id} - ignoring it");
+        return None;
+    }
+
+    let response = match Response::<T>::try_from_bytes(raw_response) {
+        Ok(response) => response,
+        Err(err) => {
+            return Some(Err(Error::MalformedServiceResponse {
+                message: err.to_string(),
+            }))
+        }
+    };
+
+    match response.content {
+        ResponseContent::Control(ControlResponse::Error(err)) => Some(Err(Error::ServiceError {
+            message: err.message().to_string(),
+        })),
+        content => Some(Ok(content)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_service_providers_common::interface::{EmptyMessage, ErrorResponse};
+
+    fn framed_response(request_id: RequestId, content: ControlResponse) -> Vec<u8> {
+        let response =
+            Response::<EmptyMessage>::new_control(ProviderInterfaceVersion::new_current(), content);
+        framing::frame(request_id, response.into_bytes())
+    }
+
+    #[test]
+    fn responses_to_other_requests_are_ignored() {
+        let stale = framed_response(41, ControlResponse::Health);
+        assert!(parse_response::<EmptyMessage>(42, &stale).is_none());
+        assert!(parse_response::<EmptyMessage>(42, &[1, 2, 3]).is_none());
+
+        let current = framed_response(42, ControlResponse::Health);
+        assert!(matches!(
+            parse_response::<EmptyMessage>(42, &current),
+            Some(Ok(ResponseContent::Control(ControlResponse::Health)))
+        ));
+    }
+
+    #[test]
+    fn error_responses_are_surfaced_as_errors() {
+        let raw = framed_response(
+            1,
+            ControlResponse::Error(ErrorResponse::new("database is down")),
+        );
+        match parse_response::<EmptyMessage>(1, &raw) {
+            Some(Err(Error::ServiceError { message })) => assert_eq!(message, "database is down"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn malformed_response_with_matching_id_is_an_error() {
+        let raw = framing::frame(5, vec![0xFF, 0xFF]);
+        assert!(matches!(
+            parse_response::<EmptyMessage>(5, &raw),
+            Some(Err(Error::MalformedServiceResponse { .. }))
+        ));
+    }
+}
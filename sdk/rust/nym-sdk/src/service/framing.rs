@@ -0,0 +1,90 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Every request sent by the [`ServiceClient`](crate::service::ServiceClient) is prefixed with
+//! a request id that the [`MixnetService`](crate::service::MixnetService) copies onto its response,
+//! so that responses can be matched to their requests even if they arrive out of order or late.
+//! The remainder of the message is the regular versioned service provider [`Request`] or [`Response`].
+//!
+//! [`Request`]: nym_service_providers_common::interface::Request
+//! [`Response`]: nym_service_providers_common::interface::Response
+
+pub type RequestId = u64;
+
+const REQUEST_ID_LEN: usize = std::mem::size_of::<RequestId>();
+
+/// Prefixes the provided serialized request or response with its request id.
+pub(crate) fn frame(request_id: RequestId, payload: Vec<u8>) -> Vec<u8> {
+    request_id
+        .to_be_bytes()
+        .into_iter()
+        .chain(payload)
+        .collect()
+}
+
+/// Splits the received message into its request id and the serialized request or response.
+pub(crate) fn split(b: &[u8]) -> Option<(RequestId, &[u8])> {
+    if b.len() < REQUEST_ID_LEN {
+        return None;
+    }
+    let (id, payload) = b.split_at(REQUEST_ID_LEN);
+    // the unwrap is fine as we've just split off exactly REQUEST_ID_LEN bytes
+    Some((RequestId::from_be_bytes(id.try_into().unwrap()), payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_service_providers_common::interface::{
+        ControlRequest, ControlResponse, EmptyMessage, ProviderInterfaceVersion, Request,
+        RequestContent, Response, ResponseContent,
+    };
+
+    #[test]
+    fn request_framing_round_trip() {
+        let version = ProviderInterfaceVersion::new_current();
+
+        let control = Request::<EmptyMessage>::new_control(version, ControlRequest::BinaryInfo);
+        let framed = frame(42, control.into_bytes());
+        let (id, payload) = split(&framed).unwrap();
+        assert_eq!(id, 42);
+        let request = Request::<EmptyMessage>::try_from_bytes(payload).unwrap();
+        assert_eq!(request.interface_version, version);
+        assert!(matches!(
+            request.content,
+            RequestContent::Control(ControlRequest::BinaryInfo)
+        ));
+
+        let data = Request::<EmptyMessage>::new_provider_data(version, EmptyMessage);
+        let framed = frame(RequestId::MAX, data.into_bytes());
+        let (id, payload) = split(&framed).unwrap();
+        assert_eq!(id, RequestId::MAX);
+        let request = Request::<EmptyMessage>::try_from_bytes(payload).unwrap();
+        assert!(matches!(
+            request.content,
+            RequestContent::ProviderData(EmptyMessage)
+        ));
+    }
+
+    #[test]
+    fn response_framing_round_trip() {
+        let version = ProviderInterfaceVersion::new_current();
+        let response = Response::<EmptyMessage>::new_control(version, ControlResponse::Health);
+        let framed = frame(7, response.into_bytes());
+
+        let (id, payload) = split(&framed).unwrap();
+        assert_eq!(id, 7);
+        let response = Response::<EmptyMessage>::try_from_bytes(payload).unwrap();
+        assert!(matches!(
+            response.content,
+            ResponseContent::Control(ControlResponse::Health)
+        ));
+    }
+
+    #[test]
+    fn too_short_message_has_no_request_id() {
+        assert!(split(&[]).is_none());
+        assert!(split(&[1, 2, 3, 4, 5, 6, 7]).is_none());
+        assert_eq!(split(&[0, 0, 0, 0, 0, 0, 0, 1]), Some((1, &[][..])));
+    }
+}
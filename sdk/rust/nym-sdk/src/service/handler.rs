@@ -0,0 +1,40 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mixnet::AnonymousSenderTag;
+use async_trait::async_trait;
+use nym_service_providers_common::interface::{
+    BinaryInformation, ProviderInterfaceVersion, ServiceProviderRequest,
+};
+use std::fmt::Display;
+
+/// Application-specific logic of a service running on the mixnet.
+///
+/// The handler only has to deal with the provider data requests, i.e. requests of the custom type `T`.
+/// Control requests, request framing and sending back the responses are taken care of by the [`MixnetService`](crate::service::MixnetService).
+///
+/// Note that requests are handled concurrently, hence the handler only ever gets a shared reference to itself.
+#[async_trait]
+pub trait RequestHandler<T>: Sync
+where
+    T: ServiceProviderRequest + Send + 'static,
+{
+    type Error: Display;
+
+    /// Handles a single request of the custom type `T`.
+    /// If `Some` response is returned, it is going to be sent back to the `sender` using its reply SURBs.
+    async fn handle_request(
+        &self,
+        sender: Option<AnonymousSenderTag>,
+        request: T,
+        interface_version: ProviderInterfaceVersion,
+    ) -> Result<Option<T::Response>, Self::Error>;
+
+    /// Information about the binary running the service, used for responding to the `BinaryInfo` control request.
+    fn binary_info(&self) -> BinaryInformation;
+
+    /// Allows custom health checks before responding to the `Health` control request.
+    async fn check_health(&self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
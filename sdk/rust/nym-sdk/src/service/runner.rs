@@ -0,0 +1,255 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::mixnet::{
+    MixnetClient, MixnetClientSender, MixnetMessageSender, Recipient, ReconstructedMessage,
+};
+use crate::service::framing::{self, RequestId};
+use crate::service::handler::RequestHandler;
+use futures::StreamExt;
+use log::{debug, info, warn};
+use nym_service_providers_common::interface::{
+    ControlRequest, ControlResponse, ErrorResponse, ProviderInterfaceVersion, Request,
+    RequestContent, Response, ResponseContent, ServiceProviderRequest, SupportedVersions,
+};
+use std::fmt::Display;
+use std::future::Future;
+use std::marker::PhantomData;
+
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 32;
+
+/// Runs a [`RequestHandler`] on top of a connected [`MixnetClient`].
+///
+/// Every received message is deserialized as a request id followed by a versioned service provider [`Request`].
+/// Control requests are answered automatically, while provider data requests are passed to the handler,
+/// with up to `max_concurrent_requests` of them being handled at the same time.
+/// Any responses are sent back, tagged with the id of their request, to the senders using the reply SURBs
+/// they have attached to their requests. If a request is malformed or the handler fails, a control
+/// [`ControlResponse::Error`] is sent back instead.
+pub struct MixnetService<T, H> {
+    client: MixnetClient,
+    handler: H,
+    max_concurrent_requests: usize,
+    _request: PhantomData<fn() -> T>,
+}
+
+impl<T, H> MixnetService<T, H>
+where
+    T: ServiceProviderRequest + Send + 'static,
+    T::Response: Send,
+    <T as ServiceProviderRequest>::Error: Display,
+    H: RequestHandler<T>,
+{
+    pub fn new(client: MixnetClient, handler: H) -> Self {
+        MixnetService {
+            client,
+            handler,
+            max_concurrent_requests: DEFAULT_MAX_CONCURRENT_REQUESTS,
+            _request: PhantomData,
+        }
+    }
+
+    /// Sets the maximum number of requests that are going to be handled at the same time.
+    #[must_use]
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+        self
+    }
+
+    /// The nym address of this service that clients should send their requests to.
+    pub fn nym_address(&self) -> Recipient {
+        self.client.nym_address()
+    }
+
+    /// Runs the service until the underlying mixnet client shuts down.
+    pub async fn run(self) {
+        self.run_until(futures::future::pending()).await
+    }
+
+    /// Runs the service until either the underlying mixnet client shuts down or the provided `shutdown`
+    /// future resolves. In the latter case, no new requests are accepted, but all requests
+    /// that are already being handled are allowed to finish before the mixnet client gets disconnected.
+    pub async fn run_until<F>(self, shutdown: F)
+    where
+        F: Future<Output = ()>,
+    {
+        let MixnetService {
+            mut client,
+            handler,
+            max_concurrent_requests,
+            ..
+        } = self;
+
+        info!(
+            "starting the mixnet service on {} (handling up to {max_concurrent_requests} requests at once)",
+            client.nym_address()
+        );
+
+        let sender = client.split_sender();
+        (&mut client)
+            .take_until(shutdown)
+            .for_each_concurrent(max_concurrent_requests, |message| {
+                handle_message::<T, H>(&sender, &handler, message)
+            })
+            .await;
+
+        info!("the mixnet service has finished handling requests - disconnecting from the mixnet");
+        client.disconnect().await
+    }
+}
+
+async fn handle_message<T, H>(
+    sender: &MixnetClientSender,
+    handler: &H,
+    message: ReconstructedMessage,
+) where
+    T: ServiceProviderRequest + Send + 'static,
+    T::Response: Send,
+    <T as ServiceProviderRequest>::Error: Display,
+    H: RequestHandler<T>,
+{
+    let Some((request_id, raw_request)) = framing::split(&message.message) else {
+        warn!("received a message without a request id");
+        return;
+    };
+
+    let (interface_version, content) = match Request::<T>::try_from_bytes(raw_request) {
+        Ok(request) => {
+            let interface_version = request.interface_version;
+            let content = match request.content {
+                RequestContent::Control(control_request) => {
+                    handle_control_request::<T, H>(handler, control_request, interface_version)
+                        .await
+                        .map(|maybe_res| maybe_res.map(ResponseContent::Control))
+                }
+                RequestContent::ProviderData(provider_data_request) => handler
+                    .handle_request(message.sender_tag, provider_data_request, interface_version)
+                    .await
+                    .map(|maybe_res| maybe_res.map(ResponseContent::ProviderData)),
+            };
+            (interface_version, into_response_content::<T, _>(content))
+        }
+        Err(err) => {
+            warn!("received a malformed request {request_id}: {err}");
+            (
+                ProviderInterfaceVersion::new_current(),
+                Some(error_content(format!("malformed request: {err}"))),
+            )
+        }
+    };
+
+    let Some(content) = content else {
+        return;
+    };
+
+    send_response::<T>(sender, message, request_id, interface_version, content).await
+}
+
+async fn send_response<T>(
+    sender: &MixnetClientSender,
+    message: ReconstructedMessage,
+    request_id: RequestId,
+    interface_version: ProviderInterfaceVersion,
+    content: ResponseContent<T>,
+) where
+    T: ServiceProviderRequest,
+{
+    // FUTURE WORK: once requests can carry an explicit return address, use it if there are no surbs
+    let Some(sender_tag) = message.sender_tag else {
+        warn!("can't send the response back as the request didn't contain any reply SURBs");
+        return;
+    };
+
+    let response = Response::<T> {
+        interface_version,
+        content,
+    };
+    debug!("sending response to request {request_id} back to {sender_tag}");
+    let framed = framing::frame(request_id, response.into_bytes());
+    if let Err(err) = sender.send_reply(sender_tag, framed).await {
+        warn!("failed to send the response back to {sender_tag}: {err}")
+    }
+}
+
+fn error_content<T: ServiceProviderRequest>(message: String) -> ResponseContent<T> {
+    ResponseContent::Control(ControlResponse::Error(ErrorResponse::new(message)))
+}
+
+/// Turns the result of handling a request into the content of the response that should be sent back,
+/// so that the client gets told about the failure rather than waiting for a response until it times out.
+fn into_response_content<T, E>(
+    handled: Result<Option<ResponseContent<T>>, E>,
+) -> Option<ResponseContent<T>>
+where
+    T: ServiceProviderRequest,
+    E: Display,
+{
+    match handled {
+        Ok(content) => content,
+        Err(err) => {
+            warn!("failed to handle the request: {err}");
+            Some(error_content(err.to_string()))
+        }
+    }
+}
+
+async fn handle_control_request<T, H>(
+    handler: &H,
+    request: ControlRequest,
+    interface_version: ProviderInterfaceVersion,
+) -> Result<Option<ControlResponse>, H::Error>
+where
+    T: ServiceProviderRequest + Send + 'static,
+    H: RequestHandler<T>,
+{
+    if interface_version.is_legacy() {
+        // control requests didn't exist in the legacy version
+        return Ok(None);
+    }
+
+    let response = match request {
+        ControlRequest::Health => {
+            handler.check_health().await?;
+            ControlResponse::Health
+        }
+        ControlRequest::BinaryInfo => ControlResponse::BinaryInfo(Box::new(handler.binary_info())),
+        ControlRequest::SupportedRequestVersions => {
+            ControlResponse::SupportedRequestVersions(SupportedVersions {
+                interface_version: ProviderInterfaceVersion::new_current().to_string(),
+                provider_version: T::max_supported_version().to_string(),
+            })
+        }
+    };
+    Ok(Some(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_service_providers_common::interface::EmptyMessage;
+
+    #[test]
+    fn handler_errors_are_turned_into_error_responses() {
+        let handled: Result<Option<ResponseContent<EmptyMessage>>, _> = Err("database is down");
+        match into_response_content(handled) {
+            Some(ResponseContent::Control(ControlResponse::Error(err))) => {
+                assert_eq!(err.message(), "database is down")
+            }
+            other => panic!("unexpected response content: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn successful_handling_is_passed_through() {
+        let handled: Result<_, String> = Ok(Some(ResponseContent::<EmptyMessage>::ProviderData(
+            EmptyMessage,
+        )));
+        assert!(matches!(
+            into_response_content(handled),
+            Some(ResponseContent::ProviderData(EmptyMessage))
+        ));
+
+        let handled: Result<Option<ResponseContent<EmptyMessage>>, String> = Ok(None);
+        assert!(into_response_content(handled).is_none());
+    }
+}
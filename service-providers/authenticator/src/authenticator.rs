@@ -119,7 +119,7 @@ impl Authenticator {
         )
         .await?;
 
-        let self_address = *mixnet_client.nym_address();
+        let self_address = mixnet_client.nym_address();
 
         let private_ip_network = IpNetwork::new(
             self.config.authenticator.private_ip,
@@ -27,12 +27,16 @@ impl DummyHandler {
                 msg = self.peer_rx.recv() => {
                     if let Some(msg) = msg {
                         match msg {
-                            PeerControlMessage::AddPeer(peer) => {
-                                log::info!("[DUMMY] Adding peer {:?}", peer);
+                            PeerControlMessage::AddPeer(client) => {
+                                log::info!("[DUMMY] Adding peer {:?}", client);
                             }
                             PeerControlMessage::RemovePeer(key) => {
                                 log::info!("[DUMMY] Removing peer {:?}", key);
                             }
+                            PeerControlMessage::QueryBandwidth { pub_key, response_tx } => {
+                                log::info!("[DUMMY] Querying bandwidth of peer {:?}", pub_key);
+                                response_tx.send(None).ok();
+                            }
                         }
                     } else {
                         break;
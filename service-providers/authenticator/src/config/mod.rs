@@ -221,6 +221,9 @@ impl From<Authenticator> for nym_wireguard_types::Config {
             private_ip: value.private_ip,
             announced_port: value.announced_port,
             private_network_prefix: value.private_network_prefix,
+            peer_timeout: nym_wireguard_types::DEFAULT_PEER_TIMEOUT,
+            peer_timeout_check: nym_wireguard_types::DEFAULT_PEER_TIMEOUT_CHECK,
+            peer_bandwidth_allowance: nym_wireguard_types::DEFAULT_PEER_BANDWIDTH_ALLOWANCE,
         }
     }
 }
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::{
+    collections::HashSet,
+    net::IpAddr,
     sync::Arc,
     time::{Duration, SystemTime},
 };
@@ -11,8 +13,8 @@ use futures::StreamExt;
 use ipnetwork::IpNetwork;
 use nym_authenticator_requests::v1::{
     self,
-    request::{AuthenticatorRequest, AuthenticatorRequestData},
-    response::AuthenticatorResponse,
+    request::{AuthenticatorRequest, AuthenticatorRequestData, QueryBandwidthMessage},
+    response::{AuthenticatorResponse, RemainingBandwidthData},
 };
 use nym_sdk::mixnet::{InputMessage, MixnetMessageSender, Recipient, TransmissionLane};
 use nym_sphinx::receiver::ReconstructedMessage;
@@ -60,15 +62,26 @@ impl MixnetListener {
     ) -> Self {
         let timeout_check_interval =
             IntervalStream::new(tokio::time::interval(DEFAULT_REGISTRATION_TIMEOUT_CHECK));
+
+        // the IPs of any restored peers are already taken
+        let now = SystemTime::now();
+        let taken_ips = wireguard_gateway_data
+            .client_registry()
+            .iter()
+            .map(|client| client.private_ip)
+            .collect::<HashSet<_>>();
+        let free_private_network_ips = private_ip_network
+            .iter()
+            .map(|ip| (ip, taken_ips.contains(&ip).then_some(now)))
+            .collect();
+
         MixnetListener {
             config,
             mixnet_client,
             task_handle,
             registration_in_progres: Default::default(),
             wireguard_gateway_data,
-            free_private_network_ips: Arc::new(
-                private_ip_network.iter().map(|ip| (ip, None)).collect(),
-            ),
+            free_private_network_ips: Arc::new(free_private_network_ips),
             timeout_check_interval,
         }
     }
@@ -121,6 +134,29 @@ impl MixnetListener {
         Ok(())
     }
 
+    // peers can get removed by the peer controller (due to timeouts or exhausted bandwidth),
+    // so make sure their IPs could be assigned again
+    fn release_removed_peer_ips(&self) {
+        let used_ips = self
+            .wireguard_gateway_data
+            .client_registry()
+            .iter()
+            .map(|client| client.private_ip)
+            .chain(
+                self.registration_in_progres
+                    .iter()
+                    .map(|reg| reg.gateway_data.private_ip),
+            )
+            .collect::<HashSet<IpAddr>>();
+
+        for mut ip in self.free_private_network_ips.iter_mut() {
+            if ip.is_some() && !used_ips.contains(ip.key()) {
+                log::debug!("Releasing private IP {}", ip.key());
+                *ip = None;
+            }
+        }
+    }
+
     fn on_initial_request(
         &mut self,
         init_message: InitMessage,
@@ -221,6 +257,52 @@ impl MixnetListener {
         }
     }
 
+    async fn on_query_bandwidth_request(
+        &mut self,
+        query_message: QueryBandwidthMessage,
+        request_id: u64,
+        reply_to: Recipient,
+    ) -> AuthenticatorHandleResult {
+        let gateway_client = query_message.gateway_client;
+        if gateway_client
+            .verify(
+                self.wireguard_gateway_data.keypair().private_key(),
+                query_message.nonce,
+            )
+            .is_err()
+        {
+            return Err(AuthenticatorError::MacVerificationFailure);
+        }
+
+        let is_registered = self
+            .wireguard_gateway_data
+            .client_registry()
+            .get(&gateway_client.pub_key())
+            .map(|client| client.private_ip == gateway_client.private_ip)
+            .unwrap_or(false);
+        if !is_registered {
+            return Ok(AuthenticatorResponse::new_remaining_bandwidth(
+                None, reply_to, request_id,
+            ));
+        }
+
+        let available_bandwidth = self
+            .wireguard_gateway_data
+            .query_bandwidth(gateway_client.pub_key())
+            .await
+            .map_err(|err| {
+                AuthenticatorError::InternalError(format!("could not query peer bandwidth: {err}"))
+            })?;
+
+        Ok(AuthenticatorResponse::new_remaining_bandwidth(
+            available_bandwidth.map(|available_bandwidth| RemainingBandwidthData {
+                available_bandwidth,
+            }),
+            reply_to,
+            request_id,
+        ))
+    }
+
     async fn on_reconstructed_message(
         &mut self,
         reconstructed: ReconstructedMessage,
@@ -244,6 +326,10 @@ impl MixnetListener {
             AuthenticatorRequestData::Final(client) => {
                 self.on_final_request(client, request.request_id, request.reply_to)
             }
+            AuthenticatorRequestData::QueryBandwidth(query_message) => {
+                self.on_query_bandwidth_request(query_message, request.request_id, request.reply_to)
+                    .await
+            }
         }
     }
 
@@ -285,6 +371,7 @@ impl MixnetListener {
                     if let Err(e) = self.remove_stale_registrations() {
                         log::error!("Could not clear stale registrations. The registration process might get jammed soon - {:?}", e);
                     }
+                    self.release_removed_peer_ips();
                 }
                 msg = self.mixnet_client.next() => {
                     if let Some(msg) = msg {
@@ -86,6 +86,18 @@ pub struct ErrorResponse {
     message: String,
 }
 
+impl ErrorResponse {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        ErrorResponse {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub enum ControlResponse {
     Health,
@@ -1,7 +1,9 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-pub use control::{BinaryInformation, ControlRequest, ControlResponse, SupportedVersions};
+pub use control::{
+    BinaryInformation, ControlRequest, ControlResponse, ErrorResponse, SupportedVersions,
+};
 pub use request::{Request, RequestContent, ServiceProviderRequest};
 pub use response::{Response, ResponseContent, ServiceProviderResponse};
 pub use version::{ProviderInterfaceVersion, RequestVersion, Version};
@@ -59,12 +59,14 @@ pub(crate) async fn execute(args: &Sign) -> Result<(), IpPacketRouterError> {
         return Err(IpPacketRouterError::FailedLocalVersionCheck);
     }
 
-    let key_store = OnDiskKeys::new(config.storage_paths.common_paths.keys);
-    let identity_keypair = key_store.load_identity_keypair().map_err(|source| {
-        IpPacketRouterError::ClientCoreError(ClientCoreError::KeyStoreError {
-            source: Box::new(source),
-        })
-    })?;
+    let identity_keypair = OnDiskKeys::new(config.storage_paths.common_paths.keys)
+        .with_passphrase_from_env()
+        .and_then(|key_store| key_store.load_identity_keypair())
+        .map_err(|source| {
+            IpPacketRouterError::ClientCoreError(ClientCoreError::KeyStoreError {
+                source: Box::new(source),
+            })
+        })?;
 
     print_signed_contract_msg(
         identity_keypair.private_key(),
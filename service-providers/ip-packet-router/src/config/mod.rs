@@ -1,5 +1,6 @@
 pub use nym_client_core::config::Config as BaseClientConfig;
 
+use ipnetwork::{Ipv4Network, Ipv6Network};
 use nym_bin_common::logging::LoggingSettings;
 use nym_client_core::{cli_helpers::CliClientConfig, config::disk_persistence::CommonClientPaths};
 use nym_config::{
@@ -7,12 +8,14 @@ use nym_config::{
     serde_helpers::de_maybe_stringified, NymConfigTemplate, OptionalSet, DEFAULT_CONFIG_DIR,
     DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_network_requester::config::DEFAULT_EXIT_POLICY_UPDATE_INTERVAL;
 use nym_service_providers_common::DEFAULT_SERVICE_PROVIDERS_DIR;
 use serde::{Deserialize, Serialize};
 use std::{
     io,
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 use url::Url;
 
@@ -27,6 +30,9 @@ mod template;
 
 const DEFAULT_IP_PACKET_ROUTER_DIR: &str = "ip-packet-router";
 
+const DEFAULT_IPV4_CLIENT_NETWORK: &str = "10.0.0.0/16";
+const DEFAULT_IPV6_CLIENT_NETWORK: &str = "2001:db8:a160::/112";
+
 /// Derive default path to ip packet routers' config directory.
 /// It should get resolved to `$HOME/.nym/service-providers/ip-packet-router/<id>/config`
 pub fn default_config_directory<P: AsRef<Path>>(id: P) -> PathBuf {
@@ -193,6 +199,18 @@ pub struct IpPacketRouter {
     /// Specifies the url for an upstream source of the exit policy used by this node.
     #[serde(deserialize_with = "de_maybe_stringified")]
     pub upstream_exit_policy_url: Option<Url>,
+
+    /// Defines how often the exit policy should get re-fetched from its upstream source.
+    #[serde(with = "humantime_serde")]
+    pub exit_policy_update_interval: Duration,
+
+    /// IPv4 network from which addresses are assigned to the connected clients.
+    /// The first host address of the network is used by the TUN device itself.
+    pub ipv4_client_network: Ipv4Network,
+
+    /// IPv6 network from which addresses are assigned to the connected clients.
+    /// The first host address of the network is used by the TUN device itself.
+    pub ipv6_client_network: Ipv6Network,
 }
 
 impl Default for IpPacketRouter {
@@ -204,6 +222,13 @@ impl Default for IpPacketRouter {
                     .parse()
                     .expect("invalid default exit policy URL"),
             ),
+            exit_policy_update_interval: DEFAULT_EXIT_POLICY_UPDATE_INTERVAL,
+            ipv4_client_network: DEFAULT_IPV4_CLIENT_NETWORK
+                .parse()
+                .expect("invalid default IPv4 client network"),
+            ipv6_client_network: DEFAULT_IPV6_CLIENT_NETWORK
+                .parse()
+                .expect("invalid default IPv6 client network"),
         }
     }
 }
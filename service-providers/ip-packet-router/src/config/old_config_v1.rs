@@ -90,6 +90,7 @@ impl From<IpPacketRouterV1> for IpPacketRouter {
         IpPacketRouter {
             disable_poisson_rate: value.disable_poisson_rate,
             upstream_exit_policy_url: value.upstream_exit_policy_url,
+            ..Default::default()
         }
     }
 }
@@ -77,6 +77,12 @@ pub enum IpPacketRouterError {
     #[error("can't setup an exit policy without any upstream urls")]
     NoUpstreamExitPolicy,
 
+    #[error("failed to set up the exit policy: {source}")]
+    ExitPolicySetupFailure {
+        #[source]
+        source: nym_network_requester::error::NetworkRequesterError,
+    },
+
     #[error("no recipient in response packet")]
     NoRecipientInResponse,
 
@@ -141,22 +141,32 @@ impl IpPacketRouter {
         )
         .await?;
 
-        let self_address = *mixnet_client.nym_address();
+        let self_address = mixnet_client.nym_address();
+
+        let ip_pool = crate::util::ip_pool::IpPool::new(
+            self.config.ip_packet_router.ipv4_client_network,
+            self.config.ip_packet_router.ipv6_client_network,
+        );
+        if ip_pool.capacity() == 0 {
+            log::warn!("the configured client networks are too small to assign any addresses");
+        }
 
         // Create the TUN device that we interact with the rest of the world with
+        let tun_ips = ip_pool.tun_ips();
         let config = nym_tun::tun_device::TunDeviceConfig {
             base_name: crate::constants::TUN_BASE_NAME.to_string(),
-            ipv4: crate::constants::TUN_DEVICE_ADDRESS_V4,
-            netmaskv4: crate::constants::TUN_DEVICE_NETMASK_V4,
-            ipv6: crate::constants::TUN_DEVICE_ADDRESS_V6,
-            netmaskv6: crate::constants::TUN_DEVICE_NETMASK_V6.to_string(),
+            ipv4: tun_ips.ipv4,
+            netmaskv4: ip_pool.ipv4_network().mask(),
+            ipv6: tun_ips.ipv6,
+            netmaskv6: ip_pool.ipv6_network().prefix().to_string(),
         };
         let (tun_reader, tun_writer) =
             tokio::io::split(nym_tun::tun_device::TunDevice::new_device_only(config)?);
 
         // Channel used by the IpPacketRouter to signal connected and disconnected clients to the
         // TunListener
-        let (connected_clients, connected_clients_rx) = mixnet_listener::ConnectedClients::new();
+        let (connected_clients, connected_clients_rx) =
+            mixnet_listener::ConnectedClients::new(ip_pool);
 
         let tun_listener = tun_listener::TunListener {
             tun_reader,
@@ -166,7 +176,7 @@ impl IpPacketRouter {
         tun_listener.start();
 
         let request_filter = request_filter::RequestFilter::new(&self.config).await?;
-        request_filter.start_update_tasks().await;
+        request_filter.start_update_tasks(task_handle.get_handle().named("ExitPolicyRefresher"));
 
         let mixnet_listener = mixnet_listener::MixnetListener {
             _config: self.config,
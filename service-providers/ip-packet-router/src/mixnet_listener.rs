@@ -9,8 +9,8 @@ use nym_ip_packet_requests::{
     v6::{
         self,
         response::{
-            DynamicConnectFailureReason, InfoLevel, InfoResponseReply, IpPacketResponse,
-            StaticConnectFailureReason,
+            DisconnectFailureReason, DynamicConnectFailureReason, InfoLevel, InfoResponseReply,
+            IpPacketResponse, StaticConnectFailureReason,
         },
     },
     v7::{
@@ -39,9 +39,9 @@ use crate::{
     error::{IpPacketRouterError, Result},
     request_filter::{self},
     tun_listener,
-    util::generate_new_ip,
     util::{
         create_message::create_input_message,
+        ip_pool::IpPool,
         parse_ip::{parse_packet, ParsedPacket},
     },
 };
@@ -51,23 +51,31 @@ pub(crate) struct ConnectedClients {
     clients_ipv4_mapping: HashMap<Ipv4Addr, ConnectedClient>,
     clients_ipv6_mapping: HashMap<Ipv6Addr, ConnectedClient>,
 
+    // The pool of addresses we assign to the connecting clients
+    ip_pool: IpPool,
+
     // Notify the tun listener when a new client connects or disconnects
     tun_listener_connected_client_tx: tokio::sync::mpsc::UnboundedSender<ConnectedClientEvent>,
 }
 
 impl ConnectedClients {
-    pub(crate) fn new() -> (Self, tun_listener::ConnectedClientsListener) {
+    pub(crate) fn new(ip_pool: IpPool) -> (Self, tun_listener::ConnectedClientsListener) {
         let (connected_client_tx, connected_client_rx) = tokio::sync::mpsc::unbounded_channel();
         (
             Self {
                 clients_ipv4_mapping: Default::default(),
                 clients_ipv6_mapping: Default::default(),
+                ip_pool,
                 tun_listener_connected_client_tx: connected_client_tx,
             },
             tun_listener::ConnectedClientsListener::new(connected_client_rx),
         )
     }
 
+    fn is_ip_in_pool(&self, ips: &IpPair) -> bool {
+        self.ip_pool.contains(ips)
+    }
+
     fn is_ip_connected(&self, ips: &IpPair) -> bool {
         self.clients_ipv4_mapping.contains_key(&ips.ipv4)
             || self.clients_ipv6_mapping.contains_key(&ips.ipv6)
@@ -190,36 +198,46 @@ impl ConnectedClients {
         ret
     }
 
+    fn disconnect_client(&mut self, ips: IpPair) {
+        // Dropping the last reference to the client signals its handler to stop
+        self.clients_ipv4_mapping.remove(&ips.ipv4);
+        self.clients_ipv6_mapping.remove(&ips.ipv6);
+        self.tun_listener_connected_client_tx
+            .send(ConnectedClientEvent::Disconnect(DisconnectEvent(ips)))
+            .tap_err(|err| {
+                log::error!("Failed to send disconnect event: {err}");
+            })
+            .ok();
+    }
+
+    /// Disconnects the client associated with the provided nym address, returning its released addresses.
+    fn disconnect_nym_address(&mut self, nym_address: &Recipient) -> Option<IpPair> {
+        let ips = self.lookup_ip_from_nym_address(nym_address)?;
+        self.disconnect_client(ips);
+        Some(ips)
+    }
+
     fn disconnect_stopped_client_handlers(&mut self, stopped_clients: Vec<(IpPair, Recipient)>) {
-        for (ips, _) in &stopped_clients {
+        for (ips, _) in stopped_clients {
             log::info!("Disconnect stopped client: {ips}");
-            self.clients_ipv4_mapping.remove(&ips.ipv4);
-            self.clients_ipv6_mapping.remove(&ips.ipv6);
-            self.tun_listener_connected_client_tx
-                .send(ConnectedClientEvent::Disconnect(DisconnectEvent(*ips)))
-                .tap_err(|err| {
-                    log::error!("Failed to send disconnect event: {err}");
-                })
-                .ok();
+            self.disconnect_client(ips);
         }
     }
 
     fn disconnect_inactive_clients(&mut self, inactive_clients: Vec<(IpPair, Recipient)>) {
-        for (ips, _) in &inactive_clients {
+        for (ips, _) in inactive_clients {
             log::info!("Disconnect inactive client: {ips}");
-            self.clients_ipv4_mapping.remove(&ips.ipv4);
-            self.clients_ipv6_mapping.remove(&ips.ipv6);
-            self.tun_listener_connected_client_tx
-                .send(ConnectedClientEvent::Disconnect(DisconnectEvent(*ips)))
-                .tap_err(|err| {
-                    log::error!("Failed to send disconnect event: {err}");
-                })
-                .ok();
+            self.disconnect_client(ips);
         }
     }
 
-    fn find_new_ip(&self) -> Option<IpPair> {
-        generate_new_ip::find_new_ips(&self.clients_ipv4_mapping, &self.clients_ipv6_mapping)
+    fn find_new_ip(&mut self) -> Option<IpPair> {
+        let clients_ipv4_mapping = &self.clients_ipv4_mapping;
+        let clients_ipv6_mapping = &self.clients_ipv6_mapping;
+        self.ip_pool.allocate(|ips| {
+            clients_ipv4_mapping.contains_key(&ips.ipv4)
+                || clients_ipv6_mapping.contains_key(&ips.ipv6)
+        })
     }
 }
 
@@ -311,6 +329,18 @@ impl MixnetListener {
         let buffer_timeout = nym_ip_packet_requests::codec::BUFFER_TIMEOUT;
         // TODO: ignoring reply_to_avg_mix_delays for now
 
+        // Check that the IP belongs to the network we're routing for
+        if !self.connected_clients.is_ip_in_pool(&requested_ips) {
+            log::info!("Requested IP is outside of the client network");
+            return Ok(Some(IpPacketResponse::new_static_connect_failure(
+                request_id,
+                reply_to,
+                StaticConnectFailureReason::Other(
+                    "requested ip address is outside of the client network".to_string(),
+                ),
+            )));
+        }
+
         // Check that the IP is available in the set of connected clients
         let is_ip_taken = self.connected_clients.is_ip_connected(&requested_ips);
 
@@ -414,7 +444,7 @@ impl MixnetListener {
         }
 
         let Some(new_ips) = self.connected_clients.find_new_ip() else {
-            log::info!("No available IP address");
+            log::warn!("No available IP address, the client address pool is exhausted");
             return Ok(Some(IpPacketResponse::new_dynamic_connect_failure(
                 request_id,
                 reply_to,
@@ -445,9 +475,33 @@ impl MixnetListener {
         )))
     }
 
-    fn on_disconnect_request(&self, _disconnect_request: DisconnectRequest) -> PacketHandleResult {
-        log::info!("Received disconnect request: not implemented, dropping");
-        Ok(None)
+    fn on_disconnect_request(
+        &mut self,
+        disconnect_request: DisconnectRequest,
+    ) -> PacketHandleResult {
+        log::info!(
+            "Received disconnect request from {sender_address}",
+            sender_address = disconnect_request.reply_to
+        );
+
+        let request_id = disconnect_request.request_id;
+        let reply_to = disconnect_request.reply_to;
+
+        // TODO: same as with the connect requests, until we require the requests to be signed,
+        // anyone knowing the nym address of a client is able to disconnect it
+        let Some(ips) = self.connected_clients.disconnect_nym_address(&reply_to) else {
+            log::info!("Nym address is not connected");
+            return Ok(Some(IpPacketResponse::new_disconnect_failure(
+                request_id,
+                reply_to,
+                DisconnectFailureReason::RequestedNymAddressNotConnected,
+            )));
+        };
+
+        log::info!("Disconnected client: {ips}");
+        Ok(Some(IpPacketResponse::new_disconnect_success(
+            request_id, reply_to,
+        )))
     }
 
     async fn handle_packet(&mut self, ip_packet: &Bytes) -> PacketHandleResult {
@@ -729,3 +783,70 @@ pub(crate) struct ConnectEvent {
     pub(crate) ips: IpPair,
     pub(crate) forward_from_tun_tx: tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tun_listener::ConnectedClientsListener;
+
+    fn test_recipient() -> Recipient {
+        Recipient::try_from_base58_string("CytBseW6yFXUMzz4SGAKdNLGR7q3sJLLYxyBGvutNEQV.4QXYyEVc5fUDjmmi8PrHN9tdUFV4PCvSJE1278cHyvoe@4sBbL1ngf1vtNqykydQKTFh26sQCw888GpUqvPvyNB4f").unwrap()
+    }
+
+    #[tokio::test]
+    async fn disconnecting_client_releases_its_resources() {
+        let (connected_client_tx, mut connected_client_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut connected_clients = ConnectedClients {
+            clients_ipv4_mapping: Default::default(),
+            clients_ipv6_mapping: Default::default(),
+            ip_pool: IpPool::new(
+                "10.0.0.0/24".parse().unwrap(),
+                "fc00::/112".parse().unwrap(),
+            ),
+            tun_listener_connected_client_tx: connected_client_tx,
+        };
+
+        // the tun listener mirror is kept up to date via the emitted events
+        let (_, mirror_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut mirror = ConnectedClientsListener::new(mirror_rx);
+
+        let nym_address = test_recipient();
+        let ips = connected_clients.find_new_ip().unwrap();
+        let (forward_from_tun_tx, _forward_from_tun_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (close_tx, mut close_rx) = tokio::sync::oneshot::channel();
+        connected_clients.connect(
+            ips,
+            nym_address,
+            None,
+            forward_from_tun_tx,
+            close_tx,
+            tokio::spawn(async {}),
+        );
+        mirror.update(connected_client_rx.try_recv().unwrap());
+
+        assert!(connected_clients.is_nym_address_connected(&nym_address));
+        assert!(connected_clients.is_ip_connected(&ips));
+        assert!(mirror.get(&IpAddr::V4(ips.ipv4)).is_some());
+
+        assert_eq!(
+            connected_clients.disconnect_nym_address(&nym_address),
+            Some(ips)
+        );
+        assert!(!connected_clients.is_nym_address_connected(&nym_address));
+        assert!(!connected_clients.is_ip_connected(&ips));
+
+        // the client handler got told to stop
+        assert!(close_rx.try_recv().is_ok());
+
+        // and the tun listener no longer forwards packets to the client
+        mirror.update(connected_client_rx.try_recv().unwrap());
+        assert!(mirror.get(&IpAddr::V4(ips.ipv4)).is_none());
+        assert!(mirror.get(&IpAddr::V6(ips.ipv6)).is_none());
+
+        // the client can't be disconnected twice
+        assert!(connected_clients
+            .disconnect_nym_address(&nym_address)
+            .is_none());
+        assert!(connected_client_rx.try_recv().is_err());
+    }
+}
@@ -2,51 +2,60 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::error::IpPacketRouterError;
-use nym_exit_policy::client::get_exit_policy;
 use nym_exit_policy::ExitPolicy;
-use reqwest::IntoUrl;
+use nym_network_requester::request_filter::exit_policy::refresher::ExitPolicyRefresher;
+use nym_network_requester::request_filter::ExitPolicyRequestFilter as InnerPolicyFilter;
+use nym_network_requester::ActiveExitPolicy;
+use nym_task::TaskClient;
 use url::Url;
 
+// the policy management (including refreshing) is shared with the network requester
 pub struct ExitPolicyRequestFilter {
-    #[allow(unused)]
-    upstream: Option<Url>,
-    policy: ExitPolicy,
+    inner: Arc<InnerPolicyFilter>,
 }
 
 impl ExitPolicyRequestFilter {
-    pub(crate) async fn new_upstream(url: impl IntoUrl) -> Result<Self, IpPacketRouterError> {
-        let url = url
-            .into_url()
-            .map_err(|source| IpPacketRouterError::MalformedExitPolicyUpstreamUrl { source })?;
+    pub(crate) async fn new_upstream(url: Url) -> Result<Self, IpPacketRouterError> {
+        let inner = InnerPolicyFilter::new_upstream(url)
+            .await
+            .map_err(|source| IpPacketRouterError::ExitPolicySetupFailure { source })?;
 
         Ok(ExitPolicyRequestFilter {
-            upstream: Some(url.clone()),
-            policy: get_exit_policy(url).await?,
+            inner: Arc::new(inner),
         })
     }
 
     #[allow(unused)]
     pub(crate) fn new(policy: ExitPolicy) -> Self {
         ExitPolicyRequestFilter {
-            upstream: None,
-            policy,
+            inner: Arc::new(InnerPolicyFilter::new_from_policy(policy)),
         }
     }
 
     #[allow(unused)]
-    pub fn policy(&self) -> &ExitPolicy {
-        &self.policy
+    pub fn current(&self) -> Arc<ActiveExitPolicy> {
+        self.inner.current()
     }
 
     #[allow(unused)]
     pub fn upstream(&self) -> Option<&Url> {
-        self.upstream.as_ref()
+        self.inner.upstream()
+    }
+
+    pub(crate) fn start_refresher(&self, update_interval: Duration, shutdown: TaskClient) {
+        let refresher =
+            ExitPolicyRefresher::new(Arc::clone(&self.inner), update_interval, shutdown);
+        tokio::spawn(refresher.run());
     }
 
     pub(crate) async fn check(&self, addr: &SocketAddr) -> Result<bool, IpPacketRouterError> {
-        self.policy
+        self.inner
+            .current()
+            .policy()
             .allows_sockaddr(addr)
             .ok_or(IpPacketRouterError::AddressNotCoveredByExitPolicy { addr: *addr })
     }
@@ -5,7 +5,8 @@ use crate::config::Config;
 use crate::error::IpPacketRouterError;
 use crate::request_filter::exit_policy::ExitPolicyRequestFilter;
 use log::{info, warn};
-use std::{net::SocketAddr, sync::Arc};
+use nym_task::TaskClient;
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 pub mod exit_policy;
 
@@ -18,6 +19,7 @@ enum RequestFilterInner {
 #[derive(Clone)]
 pub struct RequestFilter {
     inner: Arc<RequestFilterInner>,
+    update_interval: Duration,
 }
 
 impl RequestFilter {
@@ -33,10 +35,10 @@ impl RequestFilter {
         }
     }
 
-    pub(crate) async fn start_update_tasks(&self) {
+    pub(crate) fn start_update_tasks(&self, shutdown: TaskClient) {
         match &*self.inner {
-            RequestFilterInner::ExitPolicy { .. } => {
-                // nothing to do for the exit policy (yet; we might add a refresher at some point)
+            RequestFilterInner::ExitPolicy { policy_filter } => {
+                policy_filter.start_refresher(self.update_interval, shutdown)
             }
         }
     }
@@ -50,6 +52,7 @@ impl RequestFilter {
         let policy_filter = ExitPolicyRequestFilter::new_upstream(upstream_url.clone()).await?;
         Ok(RequestFilter {
             inner: Arc::new(RequestFilterInner::ExitPolicy { policy_filter }),
+            update_interval: config.ip_packet_router.exit_policy_update_interval,
         })
     }
 
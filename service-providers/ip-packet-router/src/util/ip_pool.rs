@@ -0,0 +1,164 @@
+use ipnetwork::{Ipv4Network, Ipv6Network};
+use nym_ip_packet_requests::IpPair;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+// The first address of the networks is the network address itself
+// and the one right after it is assigned to the TUN device.
+const FIRST_CLIENT_OFFSET: u128 = 2;
+
+/// Pool of IP addresses handed out to the connected clients.
+///
+/// Both IPv4 and IPv6 addresses are allocated in pairs at the same offset within their respective
+/// networks, so the size of the pool is limited by the smaller of the two.
+#[derive(Debug, Clone)]
+pub(crate) struct IpPool {
+    ipv4_network: Ipv4Network,
+    ipv6_network: Ipv6Network,
+
+    // (exclusive) upper bound of the offsets that can be assigned to clients
+    end_offset: u128,
+
+    // offset from which we're going to start looking for the next free address,
+    // so that the recently released addresses are not immediately reused
+    next_offset: u128,
+}
+
+impl IpPool {
+    pub(crate) fn new(ipv4_network: Ipv4Network, ipv6_network: Ipv6Network) -> Self {
+        // exclude the broadcast address of the IPv4 network
+        let ipv4_end = (1u128 << (32 - ipv4_network.prefix())) - 1;
+        let ipv6_end = 1u128
+            .checked_shl(128 - ipv6_network.prefix() as u32)
+            .unwrap_or(u128::MAX);
+
+        IpPool {
+            ipv4_network,
+            ipv6_network,
+            end_offset: ipv4_end.min(ipv6_end).max(FIRST_CLIENT_OFFSET),
+            next_offset: FIRST_CLIENT_OFFSET,
+        }
+    }
+
+    fn ips_at(&self, offset: u128) -> IpPair {
+        let ipv4 = Ipv4Addr::from(u32::from(self.ipv4_network.network()) + offset as u32);
+        let ipv6 = Ipv6Addr::from(u128::from(self.ipv6_network.network()) + offset);
+        IpPair::new(ipv4, ipv6)
+    }
+
+    /// The number of addresses that can be assigned to clients.
+    pub(crate) fn capacity(&self) -> u128 {
+        self.end_offset - FIRST_CLIENT_OFFSET
+    }
+
+    pub(crate) fn ipv4_network(&self) -> Ipv4Network {
+        self.ipv4_network
+    }
+
+    pub(crate) fn ipv6_network(&self) -> Ipv6Network {
+        self.ipv6_network
+    }
+
+    /// The addresses assigned to the TUN device.
+    pub(crate) fn tun_ips(&self) -> IpPair {
+        self.ips_at(1)
+    }
+
+    /// Checks whether the provided addresses could have been assigned to a client.
+    pub(crate) fn contains(&self, ips: &IpPair) -> bool {
+        let client_range = FIRST_CLIENT_OFFSET..self.end_offset;
+        let ipv4_offset = u32::from(ips.ipv4).wrapping_sub(u32::from(self.ipv4_network.network()));
+        let ipv6_offset =
+            u128::from(ips.ipv6).wrapping_sub(u128::from(self.ipv6_network.network()));
+
+        client_range.contains(&(ipv4_offset as u128)) && client_range.contains(&ipv6_offset)
+    }
+
+    /// Finds the next pair of addresses that are not yet taken,
+    /// or returns `None` if the entire pool is exhausted.
+    pub(crate) fn allocate<F>(&mut self, is_taken: F) -> Option<IpPair>
+    where
+        F: Fn(&IpPair) -> bool,
+    {
+        let capacity = self.capacity();
+        for i in 0..capacity {
+            let offset =
+                FIRST_CLIENT_OFFSET + (self.next_offset - FIRST_CLIENT_OFFSET + i) % capacity;
+            let ips = self.ips_at(offset);
+            if !is_taken(&ips) {
+                self.next_offset = offset + 1;
+                if self.next_offset == self.end_offset {
+                    self.next_offset = FIRST_CLIENT_OFFSET;
+                }
+                return Some(ips);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn new_pool(ipv4: &str, ipv6: &str) -> IpPool {
+        IpPool::new(ipv4.parse().unwrap(), ipv6.parse().unwrap())
+    }
+
+    #[test]
+    fn hands_out_every_address_until_exhausted() {
+        let mut pool = new_pool("10.0.0.0/24", "fc00::/112");
+        assert_eq!(pool.capacity(), 253);
+
+        let mut allocated = HashSet::new();
+        while let Some(ips) = pool.allocate(|ips| allocated.contains(ips)) {
+            assert!(pool.contains(&ips));
+            assert_ne!(ips, pool.tun_ips());
+            allocated.insert(ips);
+        }
+        assert_eq!(allocated.len(), 253);
+        assert!(allocated.contains(&IpPair::new(
+            Ipv4Addr::new(10, 0, 0, 2),
+            "fc00::2".parse().unwrap()
+        )));
+        assert!(allocated.contains(&IpPair::new(
+            Ipv4Addr::new(10, 0, 0, 254),
+            "fc00::fe".parse().unwrap()
+        )));
+
+        // once an address is released, it can be allocated again
+        let released = IpPair::new(Ipv4Addr::new(10, 0, 0, 42), "fc00::2a".parse().unwrap());
+        allocated.remove(&released);
+        assert_eq!(pool.allocate(|ips| allocated.contains(ips)), Some(released));
+    }
+
+    #[test]
+    fn smaller_network_limits_the_capacity() {
+        let pool = new_pool("10.0.0.0/16", "fc00::/120");
+        assert_eq!(pool.capacity(), 254);
+
+        let pool = new_pool("10.0.0.0/30", "fc00::/64");
+        assert_eq!(pool.capacity(), 1);
+
+        let pool = new_pool("10.0.0.0/32", "fc00::/64");
+        assert_eq!(pool.capacity(), 0);
+    }
+
+    #[test]
+    fn checking_pool_membership() {
+        let pool = new_pool("10.0.0.0/16", "fc00::/112");
+        assert!(pool.contains(&IpPair::new(
+            Ipv4Addr::new(10, 0, 1, 2),
+            "fc00::102".parse().unwrap()
+        )));
+        assert!(!pool.contains(&pool.tun_ips()));
+        assert!(!pool.contains(&IpPair::new(
+            Ipv4Addr::new(10, 1, 0, 2),
+            "fc00::2".parse().unwrap()
+        )));
+        assert!(!pool.contains(&IpPair::new(
+            Ipv4Addr::new(10, 0, 255, 255),
+            "fc00::ffff".parse().unwrap()
+        )));
+    }
+}
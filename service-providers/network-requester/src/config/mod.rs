@@ -37,6 +37,17 @@ const DEFAULT_NETWORK_REQUESTERS_DIR: &str = "network-requester";
 
 pub const DEFAULT_STANDARD_LIST_UPDATE_INTERVAL: Duration = Duration::from_secs(30 * 60);
 
+pub const DEFAULT_EXIT_POLICY_UPDATE_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+pub const DEFAULT_DATAGRAM_SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+pub const DEFAULT_BIND_ACCEPT_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+pub const DEFAULT_MAXIMUM_BINDS_PER_CLIENT: usize = 4;
+
+pub const DEFAULT_MAXIMUM_CONNECTIONS_PER_CLIENT: usize = 64;
+pub const DEFAULT_MAXIMUM_NEW_CONNECTIONS_PER_CLIENT_PER_MINUTE: usize = 256;
+pub const DEFAULT_MAXIMUM_BYTES_PER_SECOND_PER_CLIENT: u64 = 0;
+
 /// Derive default path to network requester's config directory.
 /// It should get resolved to `$HOME/.nym/service-providers/network-requester/<id>/config`
 pub fn default_config_directory<P: AsRef<Path>>(id: P) -> PathBuf {
@@ -214,6 +225,7 @@ pub struct NetworkRequester {
     pub disable_poisson_rate: bool,
 
     /// Specifies the url for an upstream source of the exit policy used by this node.
+    /// A `file://` url can be used for pointing to a local policy file, which is going to be watched for changes.
     #[serde(deserialize_with = "de_maybe_stringified")]
     pub upstream_exit_policy_url: Option<Url>,
 }
@@ -239,12 +251,49 @@ pub struct Debug {
     /// Deprecated
     #[serde(with = "humantime_serde")]
     pub standard_list_update_interval: Duration,
+
+    /// Defines how often the exit policy should get re-fetched from its upstream source.
+    #[serde(with = "humantime_serde")]
+    pub exit_policy_update_interval: Duration,
+
+    /// Defines how long a UDP datagram session can remain idle before it's closed.
+    #[serde(with = "humantime_serde")]
+    pub datagram_session_idle_timeout: Duration,
+
+    /// Defines how long a listener created for a SOCKS5 BIND request waits for the inbound connection.
+    #[serde(with = "humantime_serde")]
+    pub bind_accept_timeout: Duration,
+
+    /// Defines the maximum number of SOCKS5 BIND listeners (and connections accepted on them)
+    /// a single client can have open at any given time.
+    pub maximum_binds_per_client: usize,
+
+    /// Defines the maximum number of outbound connections a single client can have open at any given time.
+    /// Clients are identified either by their explicit return address or their anonymous sender tag.
+    /// Note that anonymous clients get a new sender tag whenever they reconnect and with it a fresh quota,
+    /// so this limit (as well as the other per-client ones) can be trivially evaded by them.
+    pub maximum_connections_per_client: usize,
+
+    /// Defines the maximum number of new outbound connections a single client can open within a minute.
+    pub maximum_new_connections_per_client_per_minute: usize,
+
+    /// Defines the maximum number of bytes per second, summed over both directions, that can go through
+    /// all outbound connections of a single client. 0 means there's no limit.
+    pub maximum_bytes_per_second_per_client: u64,
 }
 
 impl Default for Debug {
     fn default() -> Self {
         Debug {
             standard_list_update_interval: DEFAULT_STANDARD_LIST_UPDATE_INTERVAL,
+            exit_policy_update_interval: DEFAULT_EXIT_POLICY_UPDATE_INTERVAL,
+            datagram_session_idle_timeout: DEFAULT_DATAGRAM_SESSION_IDLE_TIMEOUT,
+            bind_accept_timeout: DEFAULT_BIND_ACCEPT_TIMEOUT,
+            maximum_binds_per_client: DEFAULT_MAXIMUM_BINDS_PER_CLIENT,
+            maximum_connections_per_client: DEFAULT_MAXIMUM_CONNECTIONS_PER_CLIENT,
+            maximum_new_connections_per_client_per_minute:
+                DEFAULT_MAXIMUM_NEW_CONNECTIONS_PER_CLIENT_PER_MINUTE,
+            maximum_bytes_per_second_per_client: DEFAULT_MAXIMUM_BYTES_PER_SECOND_PER_CLIENT,
         }
     }
 }
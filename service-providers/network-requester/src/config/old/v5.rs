@@ -131,6 +131,7 @@ impl From<DebugV5> for Debug {
     fn from(value: DebugV5) -> Self {
         Debug {
             standard_list_update_interval: value.standard_list_update_interval,
+            ..Default::default()
         }
     }
 }
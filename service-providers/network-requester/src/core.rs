@@ -5,6 +5,9 @@ use crate::config::{BaseClientConfig, Config};
 use crate::error::NetworkRequesterError;
 use crate::reply::MixnetMessage;
 use crate::request_filter::RequestFilter;
+use crate::socks5::bind::{BindLimiter, BindListener};
+use crate::socks5::limits::{ConnectionLimiter, ConnectionLimits, ConnectionPermit};
+use crate::socks5::udp::{DatagramSession, DatagramSessionSender};
 use crate::{reply, socks5};
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
@@ -14,18 +17,21 @@ use nym_bin_common::bin_info_owned;
 use nym_client_core::client::mix_traffic::transceiver::GatewayTransceiver;
 use nym_client_core::config::disk_persistence::CommonClientPaths;
 use nym_client_core::HardcodedTopologyProvider;
+use nym_metrics::inc;
 use nym_network_defaults::NymNetworkDetails;
 use nym_sdk::mixnet::{MixnetMessageSender, TopologyProvider};
 use nym_service_providers_common::interface::{
     BinaryInformation, ProviderInterfaceVersion, Request, RequestVersion,
 };
 use nym_service_providers_common::ServiceProvider;
+use nym_socks5_proxy_helpers::bandwidth_limiter::BandwidthLimiter;
 use nym_socks5_proxy_helpers::connection_controller::{
     Controller, ControllerCommand, ControllerSender,
 };
 use nym_socks5_proxy_helpers::proxy_runner::{MixProxyReader, MixProxySender};
 use nym_socks5_requests::{
-    ConnectRequest, ConnectionId, QueryRequest, QueryResponse, SendRequest, SocketData,
+    BindRequest, BindStatus, ConnectRequest, ConnectionId, Datagram, ExceededLimit,
+    OpenDatagramSessionRequest, QueryRequest, QueryResponse, SendRequest, SocketData,
     Socks5ProtocolVersion, Socks5ProviderRequest, Socks5Request, Socks5RequestContent,
     Socks5Response,
 };
@@ -36,8 +42,10 @@ use nym_sphinx::receiver::ReconstructedMessage;
 use nym_task::connections::LaneQueueLengths;
 use nym_task::manager::TaskHandle;
 use nym_task::TaskClient;
+use std::collections::HashMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::net::lookup_host;
 
 // Since it's an atomic, it's safe to be kept static and shared across threads
 static ACTIVE_PROXIES: AtomicUsize = AtomicUsize::new(0);
@@ -78,6 +86,12 @@ pub struct NRServiceProvider {
     mixnet_client: nym_sdk::mixnet::MixnetClient,
     controller_sender: ControllerSender,
 
+    // unlike TCP connections, datagrams don't need any reordering, so we can forward them directly
+    datagram_sessions: HashMap<ConnectionId, DatagramSessionSender>,
+
+    bind_limiter: BindLimiter,
+    connection_limiter: ConnectionLimiter,
+
     mix_input_sender: MixProxySender<MixnetMessage>,
     shutdown: TaskHandle,
 }
@@ -141,6 +155,14 @@ impl ServiceProvider<Socks5Request> for NRServiceProvider {
             }
             Socks5RequestContent::Send(req) => self.handle_proxy_send(req),
             Socks5RequestContent::Query(query) => return self.handle_query(query),
+            Socks5RequestContent::OpenDatagramSession(req) => {
+                self.handle_open_datagram_session(request_version, sender, req)
+            }
+            Socks5RequestContent::SendDatagram(datagram) => self.handle_send_datagram(datagram),
+            Socks5RequestContent::CloseDatagramSession { session_id } => {
+                self.handle_close_datagram_session(session_id)
+            }
+            Socks5RequestContent::Bind(req) => self.handle_proxy_bind(request_version, sender, req),
         }
 
         Ok(None)
@@ -264,7 +286,7 @@ impl NRServiceProviderBuilder {
         });
 
         let mixnet_client_sender = mixnet_client.split_sender();
-        let self_address = *mixnet_client.nym_address();
+        let self_address = mixnet_client.nym_address();
         let packet_type = self.config.base.debug.traffic.packet_type;
 
         // start the listener for mix messages
@@ -278,12 +300,29 @@ impl NRServiceProviderBuilder {
         });
 
         let request_filter = RequestFilter::new(&self.config).await?;
+        request_filter.start_update_task(
+            self.config
+                .network_requester_debug
+                .exit_policy_update_interval,
+            shutdown.get_handle().named("ExitPolicyRefresher"),
+        );
+        let debug_config = self.config.network_requester_debug;
+        let bind_limiter = BindLimiter::new(debug_config.maximum_binds_per_client);
+        let connection_limiter = ConnectionLimiter::new(ConnectionLimits {
+            maximum_concurrent_connections: debug_config.maximum_connections_per_client,
+            maximum_connections_per_minute: debug_config
+                .maximum_new_connections_per_client_per_minute,
+            maximum_bytes_per_second: debug_config.maximum_bytes_per_second_per_client,
+        });
 
         let mut service_provider = NRServiceProvider {
             config: self.config,
             request_filter: request_filter.clone(),
             mixnet_client,
             controller_sender,
+            datagram_sessions: HashMap::new(),
+            bind_limiter,
+            connection_limiter,
             mix_input_sender,
             shutdown,
         };
@@ -378,9 +417,10 @@ impl NRServiceProvider {
         controller_sender: ControllerSender,
         mix_input_sender: MixProxySender<MixnetMessage>,
         lane_queue_lengths: LaneQueueLengths,
+        permit: ConnectionPermit,
         mut shutdown: TaskClient,
     ) {
-        let mut conn = match socks5::tcp::Connection::new(
+        let conn = match socks5::tcp::Connection::new(
             connection_id,
             remote_addr.clone(),
             return_address.clone(),
@@ -409,6 +449,37 @@ impl NRServiceProvider {
             }
         };
 
+        Self::run_proxy_connection(
+            conn,
+            remote_version,
+            connection_id,
+            remote_addr,
+            biggest_packet_size,
+            controller_sender,
+            mix_input_sender,
+            lane_queue_lengths,
+            permit.bandwidth_limiter(),
+            shutdown,
+        )
+        .await;
+
+        // the connection is closed now, so it no longer counts towards the client's limit
+        drop(permit)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run_proxy_connection(
+        mut conn: socks5::tcp::Connection,
+        remote_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        remote_addr: String,
+        biggest_packet_size: PacketSize,
+        controller_sender: ControllerSender,
+        mix_input_sender: MixProxySender<MixnetMessage>,
+        lane_queue_lengths: LaneQueueLengths,
+        bandwidth_limiter: Option<BandwidthLimiter>,
+        shutdown: TaskClient,
+    ) {
         // Connect implies it's a fresh connection - register it with our controller
         let (mix_sender, mix_receiver) = mpsc::unbounded();
         controller_sender
@@ -431,6 +502,7 @@ impl NRServiceProvider {
             mix_receiver,
             mix_input_sender,
             lane_queue_lengths,
+            bandwidth_limiter,
             shutdown,
         )
         .await;
@@ -469,6 +541,10 @@ impl NRServiceProvider {
             .secondary_packet_size
             .unwrap_or(traffic_config.primary_packet_size);
 
+        let permit = self
+            .connection_limiter
+            .try_acquire(return_address.client_identifier());
+
         let controller_sender_clone = self.controller_sender.clone();
         let mix_input_sender_clone = self.mix_input_sender.clone();
         let lane_queue_lengths_clone = self.mixnet_client.shared_lane_queue_lengths();
@@ -481,6 +557,34 @@ impl NRServiceProvider {
         // because we might have to resolve the underlying address and it can take some time
         // during which we don't want to block other incoming requests
         tokio::spawn(async move {
+            let permit = match permit {
+                Ok(permit) => permit,
+                Err(limit) => {
+                    log::info!("refusing connection {conn_id} to {remote_addr:?}: {limit}");
+                    match limit {
+                        ExceededLimit::ConcurrentConnections { .. } => {
+                            inc!("connections_refused_concurrent_limit")
+                        }
+                        ExceededLimit::ConnectionsPerMinute { .. } => {
+                            inc!("connections_refused_rate_limit")
+                        }
+                    }
+
+                    let refusal = MixnetMessage::new_limit_exceeded(
+                        return_address,
+                        remote_version,
+                        conn_id,
+                        limit,
+                    );
+                    mix_input_sender_clone
+                        .send(refusal)
+                        .await
+                        .expect("InputMessageReceiver has stopped receiving!");
+                    shutdown.mark_as_success();
+                    return;
+                }
+            };
+
             if !request_filter.check_address(&remote_addr).await {
                 let log_msg = format!("Domain {remote_addr:?} failed filter check");
                 log::info!("{log_msg}");
@@ -509,6 +613,157 @@ impl NRServiceProvider {
                 controller_sender_clone,
                 mix_input_sender_clone,
                 lane_queue_lengths_clone,
+                permit,
+                shutdown,
+            )
+            .await
+        });
+    }
+
+    fn handle_proxy_bind(
+        &self,
+        remote_version: RequestVersion<Socks5Request>,
+        sender_tag: Option<AnonymousSenderTag>,
+        bind_req: Box<BindRequest>,
+    ) {
+        let Some(return_address) = reply::MixnetAddress::new(bind_req.return_address, sender_tag)
+        else {
+            log::warn!("attempted to bind with no way of returning data back to the sender");
+            return;
+        };
+
+        let expected_peer = bind_req.expected_peer;
+        let conn_id = bind_req.conn_id;
+        let traffic_config = self.config.base.debug.traffic;
+        let packet_size = traffic_config
+            .secondary_packet_size
+            .unwrap_or(traffic_config.primary_packet_size);
+        let accept_timeout = self.config.network_requester_debug.bind_accept_timeout;
+
+        let permit = self
+            .bind_limiter
+            .try_acquire(return_address.client_identifier());
+
+        let controller_sender_clone = self.controller_sender.clone();
+        let mix_input_sender_clone = self.mix_input_sender.clone();
+        let lane_queue_lengths_clone = self.mixnet_client.shared_lane_queue_lengths();
+        let mut shutdown = self.shutdown.get_handle();
+        let request_filter = self.request_filter.clone();
+
+        tokio::spawn(async move {
+            let send_status = |status: BindStatus| {
+                let msg = MixnetMessage::new_bind_response(
+                    return_address.clone(),
+                    remote_version.clone(),
+                    conn_id,
+                    status,
+                );
+                let sender = mix_input_sender_clone.clone();
+                async move {
+                    sender
+                        .send(msg)
+                        .await
+                        .expect("InputMessageReceiver has stopped receiving!")
+                }
+            };
+
+            // there's no point in listening for anything if the client can't understand the responses
+            // (the failure itself is going to get downgraded to a plain connection error)
+            if !remote_version
+                .provider_protocol
+                .supports_extended_responses()
+            {
+                log::info!("refusing to bind {conn_id} for an outdated client");
+                send_status(BindStatus::Failed {
+                    message: "binding is not supported by this client version".to_string(),
+                })
+                .await;
+                shutdown.mark_as_success();
+                return;
+            }
+
+            // the permit has to be held for as long as the bind is in use
+            let Some(_permit) = permit else {
+                log::info!("rejecting bind {conn_id} as the client has too many open binds");
+                send_status(BindStatus::Failed {
+                    message: "too many open binds".to_string(),
+                })
+                .await;
+                shutdown.mark_as_success();
+                return;
+            };
+
+            // the exit policy applies to the host that is going to connect to us
+            if !request_filter.check_address(&expected_peer).await {
+                let log_msg = format!("Bind peer {expected_peer:?} failed filter check");
+                log::info!("{log_msg}");
+                send_status(BindStatus::Failed { message: log_msg }).await;
+                shutdown.mark_as_success();
+                return;
+            }
+
+            let listener = match lookup_host(&expected_peer).await.map(|mut a| a.next()) {
+                Ok(Some(peer)) => BindListener::new(conn_id, peer).await,
+                Ok(None) => Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("could not resolve {expected_peer}"),
+                )),
+                Err(err) => Err(err),
+            };
+            let listener = match listener {
+                Ok(listener) => listener,
+                Err(err) => {
+                    log::error!("failed to create bind listener for {expected_peer}: {err}");
+                    send_status(BindStatus::Failed {
+                        message: format!("failed to create listener: {err}"),
+                    })
+                    .await;
+                    shutdown.disarm();
+                    return;
+                }
+            };
+
+            let bound_address = listener.bound_address();
+            log::debug!("bind {conn_id} is listening on {bound_address}");
+            send_status(BindStatus::Listening {
+                address: bound_address.to_string(),
+            })
+            .await;
+
+            let (stream, peer) = match listener.accept(accept_timeout).await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    log::debug!("bind {conn_id} has not accepted any connections: {err}");
+                    send_status(BindStatus::Failed {
+                        message: err.to_string(),
+                    })
+                    .await;
+                    shutdown.mark_as_success();
+                    return;
+                }
+            };
+            send_status(BindStatus::Accepted {
+                peer: peer.to_string(),
+            })
+            .await;
+
+            let conn = socks5::tcp::Connection::new_established(
+                conn_id,
+                peer.to_string(),
+                stream,
+                return_address.clone(),
+            );
+            Self::run_proxy_connection(
+                conn,
+                remote_version,
+                conn_id,
+                peer.to_string(),
+                packet_size,
+                controller_sender_clone,
+                mix_input_sender_clone,
+                lane_queue_lengths_clone,
+                // connections accepted on binds are only limited by the number of binds
+                None,
                 shutdown,
             )
             .await
@@ -521,6 +776,125 @@ impl NRServiceProvider {
             .unwrap()
     }
 
+    fn handle_open_datagram_session(
+        &mut self,
+        remote_version: RequestVersion<Socks5Request>,
+        sender_tag: Option<AnonymousSenderTag>,
+        req: Box<OpenDatagramSessionRequest>,
+    ) {
+        let session_id = req.session_id;
+
+        // get rid of sessions that have already finished
+        self.datagram_sessions
+            .retain(|_, session| !session.is_closed());
+        if self.datagram_sessions.contains_key(&session_id) {
+            // the client is just letting us know the session is still in use
+            log::trace!("datagram session {session_id} is still alive");
+            return;
+        }
+
+        let Some(return_address) = reply::MixnetAddress::new(req.return_address, sender_tag) else {
+            log::warn!(
+                "attempted to open datagram session with no way of returning data back to the sender"
+            );
+            return;
+        };
+
+        let (session_sender, session_receiver) = mpsc::unbounded();
+        self.datagram_sessions.insert(session_id, session_sender);
+
+        let request_filter = self.request_filter.clone();
+        let idle_timeout = self
+            .config
+            .network_requester_debug
+            .datagram_session_idle_timeout;
+        let mix_input_sender = self.mix_input_sender.clone();
+        let connection_command_sender = self.mixnet_client.connection_command_sender();
+        let mut shutdown = self.shutdown.get_handle();
+
+        tokio::spawn(async move {
+            // there's no point in relaying any datagrams if the client can't understand them
+            if !remote_version
+                .provider_protocol
+                .supports_extended_responses()
+            {
+                log::info!("refusing to open datagram session {session_id} for an outdated client");
+                shutdown.mark_as_success();
+
+                let error_msg = MixnetMessage::new_connection_error(
+                    return_address,
+                    remote_version,
+                    session_id,
+                    "datagram sessions are not supported by this client version".to_string(),
+                );
+                mix_input_sender
+                    .send(error_msg)
+                    .await
+                    .expect("InputMessageReceiver has stopped receiving!");
+                return;
+            }
+
+            let session = match DatagramSession::new(
+                session_id,
+                return_address.clone(),
+                remote_version.clone(),
+                request_filter,
+                idle_timeout,
+            )
+            .await
+            {
+                Ok(session) => session,
+                Err(err) => {
+                    log::error!("failed to open datagram session {session_id}: {err}");
+                    shutdown.disarm();
+
+                    let error_msg = MixnetMessage::new_connection_error(
+                        return_address,
+                        remote_version,
+                        session_id,
+                        format!("failed to open datagram session: {err}"),
+                    );
+                    mix_input_sender
+                        .send(error_msg)
+                        .await
+                        .expect("InputMessageReceiver has stopped receiving!");
+                    return;
+                }
+            };
+
+            log::info!("Starting datagram session {session_id}");
+            session
+                .run(
+                    session_receiver,
+                    mix_input_sender,
+                    connection_command_sender,
+                    shutdown,
+                )
+                .await;
+            log::info!("Datagram session {session_id} is finished");
+        });
+    }
+
+    fn handle_send_datagram(&mut self, datagram: Datagram) {
+        let session_id = datagram.session_id;
+        let Some(session) = self.datagram_sessions.get(&session_id) else {
+            log::debug!("received a datagram for unknown session {session_id}");
+            return;
+        };
+
+        if session.unbounded_send(datagram).is_err() {
+            log::debug!("datagram session {session_id} has already finished");
+            self.datagram_sessions.remove(&session_id);
+        }
+    }
+
+    fn handle_close_datagram_session(&mut self, session_id: ConnectionId) {
+        // dropping the sender is going to stop the session
+        if self.datagram_sessions.remove(&session_id).is_none() {
+            log::debug!("attempted to close unknown datagram session {session_id}");
+        }
+    }
+
     fn handle_query(
         &self,
         query: QueryRequest,
@@ -541,10 +915,10 @@ impl NRServiceProvider {
                 let response = QueryResponse::ExitPolicy {
                     enabled: true,
                     upstream: exit_policy_filter
-                        .upstream()
-                        .map(|u| u.to_string())
+                        .source()
+                        .map(|source| source.to_string())
                         .unwrap_or_default(),
-                    policy: Some(exit_policy_filter.policy().clone()),
+                    policy: Some(exit_policy_filter.current().policy().clone()),
                 };
 
                 Socks5Response::new_query(protocol_version, response)
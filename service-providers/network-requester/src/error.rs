@@ -7,6 +7,7 @@ use nym_exit_policy::policy::PolicyError;
 use nym_id::NymIdError;
 use nym_socks5_requests::{RemoteAddress, Socks5RequestError};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
 #[derive(thiserror::Error, Debug)]
 pub enum NetworkRequesterError {
@@ -70,6 +71,13 @@ pub enum NetworkRequesterError {
     #[error("can't setup an exit policy without any upstream urls")]
     NoUpstreamExitPolicy,
 
+    #[error("failed to read the exit policy file at {}: {source}", path.display())]
+    ExitPolicyFileReadFailure {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     #[error(transparent)]
     ConfigUpgradeFailure(#[from] nym_client_core::config::ConfigUpgradeFailure),
 
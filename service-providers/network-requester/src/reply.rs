@@ -6,8 +6,9 @@ use nym_service_providers_common::interface::{
     ControlRequest, ControlResponse, ProviderInterfaceVersion, RequestVersion,
 };
 use nym_socks5_requests::{
-    ConnectionId, SocketData, Socks5ProviderRequest, Socks5ProviderResponse, Socks5Request,
-    Socks5RequestContent, Socks5Response, Socks5ResponseContent,
+    BindStatus, ConnectionId, Datagram, ExceededLimit, SocketData, Socks5ProviderRequest,
+    Socks5ProviderResponse, Socks5Request, Socks5RequestContent, Socks5Response,
+    Socks5ResponseContent,
 };
 use nym_sphinx::addressing::clients::Recipient;
 use nym_sphinx::anonymous_replies::requests::AnonymousSenderTag;
@@ -93,6 +94,33 @@ impl MixnetMessage {
         Self::new_provider_response(address, connection_id, msg)
     }
 
+    pub(crate) fn new_datagram_response(
+        address: MixnetAddress,
+        request_version: RequestVersion<Socks5Request>,
+        datagram: Datagram,
+    ) -> Self {
+        let session_id = datagram.session_id;
+        let res = Socks5Response::new_datagram(request_version.provider_protocol, datagram);
+        let msg =
+            Socks5ProviderResponse::new_provider_data(request_version.provider_interface, res);
+
+        Self::new_provider_response(address, session_id, msg)
+    }
+
+    pub(crate) fn new_bind_response(
+        address: MixnetAddress,
+        request_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        status: BindStatus,
+    ) -> Self {
+        let res =
+            Socks5Response::new_bind(request_version.provider_protocol, connection_id, status);
+        let msg =
+            Socks5ProviderResponse::new_provider_data(request_version.provider_interface, res);
+
+        Self::new_provider_response(address, connection_id, msg)
+    }
+
     #[allow(dead_code)]
     pub(crate) fn new_control_request<A: Into<MixnetAddress>>(
         address: A,
@@ -133,6 +161,23 @@ impl MixnetMessage {
         Self::new_provider_response(address, connection_id, msg)
     }
 
+    pub(crate) fn new_limit_exceeded(
+        address: MixnetAddress,
+        request_version: RequestVersion<Socks5Request>,
+        connection_id: ConnectionId,
+        limit: ExceededLimit,
+    ) -> Self {
+        let res = Socks5Response::new_limit_exceeded(
+            request_version.provider_protocol,
+            connection_id,
+            limit,
+        );
+        let msg =
+            Socks5ProviderResponse::new_provider_data(request_version.provider_interface, res);
+
+        Self::new_provider_response(address, connection_id, msg)
+    }
+
     // TODO: the naming is awful, but naming things is difficult...
     pub(crate) fn new_network_data_response_content(
         address: MixnetAddress,
@@ -179,6 +224,14 @@ impl MixnetAddress {
         None
     }
 
+    /// Returns a string uniquely identifying this address, used for applying per-client limits.
+    pub(crate) fn client_identifier(&self) -> String {
+        match self {
+            MixnetAddress::Known(recipient) => recipient.to_string(),
+            MixnetAddress::Anonymous(sender_tag) => sender_tag.to_base58_string(),
+        }
+    }
+
     pub(super) fn send_back_to(
         self,
         message: Vec<u8>,
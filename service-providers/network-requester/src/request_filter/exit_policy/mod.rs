@@ -3,17 +3,114 @@
 
 use crate::config::Config;
 use crate::error::NetworkRequesterError;
-use log::trace;
+use log::{debug, info, trace};
 use nym_exit_policy::client::get_exit_policy;
-use nym_exit_policy::ExitPolicy;
+use nym_exit_policy::{diff_exit_policies, format_exit_policy, parse_exit_policy, ExitPolicy};
 use nym_socks5_requests::RemoteAddress;
-use reqwest::IntoUrl;
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use time::OffsetDateTime;
 use tokio::net::lookup_host;
+use tokio::sync::watch;
 use url::Url;
 
-pub struct ExitPolicyRequestFilter {
-    upstream: Option<Url>,
+pub mod refresher;
+
+/// Location from which the exit policy gets (re)loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExitPolicySource {
+    /// Remote url that is periodically queried for the latest policy.
+    Upstream(Url),
+
+    /// Local file (specified with a `file://` url) that is watched for any changes.
+    LocalFile(PathBuf),
+}
+
+impl From<Url> for ExitPolicySource {
+    fn from(url: Url) -> Self {
+        if url.scheme() == "file" {
+            if let Ok(path) = url.to_file_path() {
+                return ExitPolicySource::LocalFile(path);
+            }
+        }
+        ExitPolicySource::Upstream(url)
+    }
+}
+
+impl Display for ExitPolicySource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExitPolicySource::Upstream(url) => url.fmt(f),
+            ExitPolicySource::LocalFile(path) => path.display().fmt(f),
+        }
+    }
+}
+
+impl ExitPolicySource {
+    async fn load(&self) -> Result<ExitPolicy, NetworkRequesterError> {
+        match self {
+            ExitPolicySource::Upstream(url) => Ok(get_exit_policy(url.clone()).await?),
+            ExitPolicySource::LocalFile(path) => {
+                let raw = tokio::fs::read_to_string(path).await.map_err(|source| {
+                    NetworkRequesterError::ExitPolicyFileReadFailure {
+                        path: path.clone(),
+                        source,
+                    }
+                })?;
+                Ok(parse_exit_policy(raw)?)
+            }
+        }
+    }
+}
+
+/// A particular version of the exit policy that is currently applied to all requests.
+#[derive(Debug)]
+pub struct ActiveExitPolicy {
     policy: ExitPolicy,
+    hash: String,
+    last_updated: OffsetDateTime,
+}
+
+impl ActiveExitPolicy {
+    fn new(policy: ExitPolicy) -> Self {
+        // hash the canonical representation so that the formatting of the source wouldn't matter
+        let hash = hex::encode(Sha256::digest(format_exit_policy(&policy)));
+
+        ActiveExitPolicy {
+            policy,
+            hash,
+            last_updated: OffsetDateTime::now_utc(),
+        }
+    }
+
+    pub fn policy(&self) -> &ExitPolicy {
+        &self.policy
+    }
+
+    /// Hex-encoded sha256 digest of the torrc representation of the policy.
+    // this is a false positive, this method is actually called when used as a library
+    // but clippy complains about it when building the binary
+    #[allow(unused)]
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Time at which the policy has last been successfully retrieved from its source.
+    #[allow(unused)]
+    pub fn last_updated(&self) -> OffsetDateTime {
+        self.last_updated
+    }
+}
+
+pub struct ExitPolicyRequestFilter {
+    source: Option<ExitPolicySource>,
+
+    // the channel is used as an atomically swappable pointer that can also notify
+    // any interested parties (like the http api) about the policy changes
+    active: watch::Sender<Arc<ActiveExitPolicy>>,
 }
 
 impl From<ExitPolicy> for ExitPolicyRequestFilter {
@@ -23,14 +120,14 @@ impl From<ExitPolicy> for ExitPolicyRequestFilter {
 }
 
 impl ExitPolicyRequestFilter {
-    pub(crate) async fn new_upstream(url: impl IntoUrl) -> Result<Self, NetworkRequesterError> {
-        let url = url
-            .into_url()
-            .map_err(|source| NetworkRequesterError::MalformedExitPolicyUpstreamUrl { source })?;
+    // note: this takes an `Url` rather than `impl IntoUrl` as reqwest rejects any `file://` urls
+    pub async fn new_upstream(url: Url) -> Result<Self, NetworkRequesterError> {
+        let source = ExitPolicySource::from(url);
+        let policy = source.load().await?;
 
         Ok(ExitPolicyRequestFilter {
-            upstream: Some(url.clone()),
-            policy: get_exit_policy(url).await?,
+            source: Some(source),
+            active: watch::Sender::new(Arc::new(ActiveExitPolicy::new(policy))),
         })
     }
 
@@ -50,17 +147,63 @@ impl ExitPolicyRequestFilter {
 
     pub fn new_from_policy(policy: ExitPolicy) -> Self {
         ExitPolicyRequestFilter {
-            upstream: None,
-            policy,
+            source: None,
+            active: watch::Sender::new(Arc::new(ActiveExitPolicy::new(policy))),
         }
     }
 
-    pub fn policy(&self) -> &ExitPolicy {
-        &self.policy
+    /// Returns the currently applied version of the exit policy.
+    pub fn current(&self) -> Arc<ActiveExitPolicy> {
+        Arc::clone(&self.active.borrow())
     }
 
+    /// Subscribes to any future changes to the exit policy.
+    #[allow(unused)]
+    pub fn subscribe(&self) -> watch::Receiver<Arc<ActiveExitPolicy>> {
+        self.active.subscribe()
+    }
+
+    pub fn source(&self) -> Option<&ExitPolicySource> {
+        self.source.as_ref()
+    }
+
+    #[allow(unused)]
     pub fn upstream(&self) -> Option<&Url> {
-        self.upstream.as_ref()
+        match self.source.as_ref()? {
+            ExitPolicySource::Upstream(url) => Some(url),
+            ExitPolicySource::LocalFile(_) => None,
+        }
+    }
+
+    /// Attempts to retrieve the latest version of the exit policy from its source.
+    /// On failure, the currently active policy remains unchanged.
+    pub(crate) async fn reload(&self) -> Result<(), NetworkRequesterError> {
+        let Some(source) = &self.source else {
+            return Ok(());
+        };
+
+        let policy = source.load().await?;
+        let diff = diff_exit_policies(self.current().policy(), &policy);
+
+        if diff.is_empty() {
+            debug!("the exit policy retrieved from {source} hasn't changed");
+        } else {
+            info!("the exit policy retrieved from {source} has changed");
+            for rule in &diff.removed {
+                info!("  - {rule}");
+            }
+            for rule in &diff.added {
+                info!("  + {rule}");
+            }
+            if diff.reordered {
+                info!("  (the order of the remaining rules has changed)");
+            }
+        }
+
+        let updated = ActiveExitPolicy::new(policy);
+        debug!("the active exit policy hash is now {}", updated.hash);
+        self.active.send_replace(Arc::new(updated));
+        Ok(())
     }
 
     pub(crate) async fn check(
@@ -86,18 +229,73 @@ impl ExitPolicyRequestFilter {
 
         trace!("{remote} has been resolved to {addrs:?}");
 
+        // make sure all addresses are checked against the same version of the policy
+        let active = self.current();
+
         // if the remote decided to give us an address that can resolve to multiple socket addresses,
         // they'd better make sure all of them are allowed by the exit policy.
         for addr in addrs {
-            if !self
-                .policy
-                .allows_sockaddr(&addr)
-                .ok_or(NetworkRequesterError::AddressNotCoveredByExitPolicy { addr })?
-            {
+            if !check_policy(active.policy(), &addr)? {
                 return Ok(false);
             }
         }
 
         Ok(true)
     }
+
+    pub(crate) fn check_socket_address(
+        &self,
+        addr: &SocketAddr,
+    ) -> Result<bool, NetworkRequesterError> {
+        check_policy(self.active.borrow().policy(), addr)
+    }
+}
+
+fn check_policy(policy: &ExitPolicy, addr: &SocketAddr) -> Result<bool, NetworkRequesterError> {
+    policy
+        .allows_sockaddr(addr)
+        .ok_or(NetworkRequesterError::AddressNotCoveredByExitPolicy { addr: *addr })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[tokio::test]
+    async fn reloading_local_policy() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "ExitPolicy accept *:80\nExitPolicy reject *:*").unwrap();
+
+        let url = Url::from_file_path(file.path()).unwrap();
+        let filter = ExitPolicyRequestFilter::new_upstream(url).await.unwrap();
+        assert_eq!(
+            filter.source(),
+            Some(&ExitPolicySource::LocalFile(file.path().to_path_buf()))
+        );
+
+        let allowed: SocketAddr = "1.1.1.1:80".parse().unwrap();
+        let later_allowed: SocketAddr = "1.1.1.1:443".parse().unwrap();
+        assert!(filter.check_socket_address(&allowed).unwrap());
+        assert!(!filter.check_socket_address(&later_allowed).unwrap());
+
+        let original_hash = filter.current().hash().to_string();
+        let updates = filter.subscribe();
+
+        // invalid policies are rejected and the last good one is kept
+        std::fs::write(file.path(), "ExitPolicy foomp *:443").unwrap();
+        assert!(filter.reload().await.is_err());
+        assert_eq!(filter.current().hash(), original_hash);
+        assert!(!updates.has_changed().unwrap());
+
+        std::fs::write(
+            file.path(),
+            "ExitPolicy accept *:80\nExitPolicy accept *:443\nExitPolicy reject *:*",
+        )
+        .unwrap();
+        filter.reload().await.unwrap();
+        assert!(updates.has_changed().unwrap());
+        assert_ne!(filter.current().hash(), original_hash);
+        assert!(filter.check_socket_address(&later_allowed).unwrap());
+    }
 }
@@ -0,0 +1,116 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::request_filter::exit_policy::{ExitPolicyRequestFilter, ExitPolicySource};
+use futures::channel::mpsc;
+use futures::{Stream, StreamExt};
+use log::{debug, trace, warn};
+use nym_async_file_watcher::{AsyncFileWatcher, FileWatcherEventReceiver};
+use nym_task::TaskClient;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant};
+
+/// Background task responsible for keeping the exit policy up to date with its source.
+pub struct ExitPolicyRefresher {
+    filter: Arc<ExitPolicyRequestFilter>,
+    update_interval: Duration,
+    shutdown: TaskClient,
+}
+
+impl ExitPolicyRefresher {
+    pub fn new(
+        filter: Arc<ExitPolicyRequestFilter>,
+        update_interval: Duration,
+        shutdown: TaskClient,
+    ) -> Self {
+        ExitPolicyRefresher {
+            filter,
+            update_interval,
+            shutdown,
+        }
+    }
+
+    async fn refresh(&self) {
+        if let Err(err) = self.filter.reload().await {
+            warn!("failed to refresh the exit policy: {err}. the last valid policy will remain in use")
+        }
+    }
+
+    fn start_file_watcher(path: &Path) -> Option<(FileWatcherEventReceiver, JoinHandle<()>)> {
+        let (events_sender, events_receiver) = mpsc::unbounded();
+        let mut watcher = match AsyncFileWatcher::new_file_changes_watcher(path, events_sender) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                warn!("failed to create the exit policy file watcher: {err}. the file will only be periodically reloaded");
+                return None;
+            }
+        };
+
+        let join_handle = tokio::spawn(async move {
+            if let Err(err) = watcher.watch().await {
+                warn!("the exit policy file watcher has failed: {err}")
+            }
+        });
+        Some((events_receiver, join_handle))
+    }
+
+    pub async fn run(mut self) {
+        let Some(source) = self.filter.source() else {
+            debug!("the exit policy does not have any source - there's nothing to refresh");
+            self.shutdown.disarm();
+            return;
+        };
+
+        // local files are watched for changes, but we also periodically reload them
+        // in case the watch has been lost, for example because the file got replaced
+        let (mut file_events, watcher_handle) = match source {
+            ExitPolicySource::LocalFile(path) => match Self::start_file_watcher(path) {
+                Some((events, handle)) => (Some(events), Some(handle)),
+                None => (None, None),
+            },
+            ExitPolicySource::Upstream(_) => (None, None),
+        };
+
+        let start = Instant::now() + self.update_interval;
+        let mut update_interval = interval_at(start, self.update_interval);
+
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.recv() => {
+                    trace!("ExitPolicyRefresher: Received shutdown");
+                }
+                _ = update_interval.tick() => {
+                    self.refresh().await;
+                }
+                event = next_event(&mut file_events) => {
+                    match event {
+                        Some(event) => {
+                            debug!("the exit policy file has changed: {:?}", event.kind);
+                            self.refresh().await;
+                        }
+                        None => {
+                            warn!("the exit policy file watcher has stopped. the file will only be periodically reloaded");
+                            file_events = None;
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(handle) = watcher_handle {
+            handle.abort()
+        }
+        trace!("ExitPolicyRefresher: Exiting");
+    }
+}
+
+async fn next_event<S: Stream + Unpin>(events: &mut Option<S>) -> Option<S::Item> {
+    match events {
+        Some(events) => events.next().await,
+        None => std::future::pending().await,
+    }
+}
@@ -3,14 +3,23 @@
 
 use crate::config::Config;
 use crate::error::NetworkRequesterError;
+use crate::request_filter::exit_policy::refresher::ExitPolicyRefresher;
 use log::warn;
 use nym_socks5_requests::RemoteAddress;
+use nym_task::TaskClient;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub mod exit_policy;
 
 pub use exit_policy::ExitPolicyRequestFilter;
 
+// this is a false positive, it's re-exported when used as a library
+// but the compiler complains about it when building the binary
+#[allow(unused)]
+pub use exit_policy::ActiveExitPolicy;
+
 #[derive(Clone)]
 pub struct RequestFilter {
     inner: Arc<ExitPolicyRequestFilter>,
@@ -27,10 +36,25 @@ impl RequestFilter {
         &self.inner
     }
 
+    pub(crate) fn start_update_task(&self, update_interval: Duration, shutdown: TaskClient) {
+        let refresher =
+            ExitPolicyRefresher::new(Arc::clone(&self.inner), update_interval, shutdown);
+        tokio::spawn(refresher.run());
+    }
+
     pub(crate) async fn check_address(&self, address: &RemoteAddress) -> bool {
         self.inner.check(address).await.unwrap_or_else(|err| {
             warn!("failed to validate '{address}' against the exit policy: {err}");
             false
         })
     }
+
+    pub(crate) fn check_socket_address(&self, address: &SocketAddr) -> bool {
+        self.inner
+            .check_socket_address(address)
+            .unwrap_or_else(|err| {
+                warn!("failed to validate '{address}' against the exit policy: {err}");
+                false
+            })
+    }
 }
@@ -0,0 +1,144 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use log::*;
+use nym_socks5_requests::ConnectionId;
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+
+/// A listener created on behalf of a SOCKS5 BIND request, which accepts a single
+/// inbound connection from the expected peer.
+pub(crate) struct BindListener {
+    id: ConnectionId,
+    listener: TcpListener,
+    expected_peer: IpAddr,
+    bound_address: SocketAddr,
+}
+
+impl BindListener {
+    pub(crate) async fn new(id: ConnectionId, expected_peer: SocketAddr) -> io::Result<Self> {
+        let unspecified: IpAddr = if expected_peer.is_ipv4() {
+            Ipv4Addr::UNSPECIFIED.into()
+        } else {
+            Ipv6Addr::UNSPECIFIED.into()
+        };
+        let listener = TcpListener::bind((unspecified, 0)).await?;
+        let port = listener.local_addr()?.port();
+
+        // figure out which of our addresses is used for reaching the peer, as that's the one
+        // it should be able to connect back to. note that connecting an UDP socket doesn't send
+        // anything over the wire, so the port we're using here is irrelevant
+        let probe = UdpSocket::bind((unspecified, 0)).await?;
+        probe.connect((expected_peer.ip(), 9)).await?;
+        let local_ip = probe.local_addr()?.ip();
+
+        Ok(BindListener {
+            id,
+            listener,
+            expected_peer: expected_peer.ip(),
+            bound_address: SocketAddr::new(local_ip, port),
+        })
+    }
+
+    pub(crate) fn bound_address(&self) -> SocketAddr {
+        self.bound_address
+    }
+
+    /// Waits for the expected peer to connect, rejecting connections from any other hosts.
+    pub(crate) async fn accept(self, timeout: Duration) -> io::Result<(TcpStream, SocketAddr)> {
+        let accept = async {
+            loop {
+                let (stream, peer) = self.listener.accept().await?;
+                if peer.ip() == self.expected_peer {
+                    return Ok((stream, peer));
+                }
+                debug!(
+                    "rejecting inbound connection from unexpected peer {peer} on bind listener {}",
+                    self.id
+                );
+            }
+        };
+
+        tokio::time::timeout(timeout, accept).await.map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no inbound connection has been received in time",
+            )
+        })?
+    }
+}
+
+/// Keeps track of the number of binds each client has currently got open.
+#[derive(Clone)]
+pub(crate) struct BindLimiter {
+    max_per_client: usize,
+    active: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl BindLimiter {
+    pub(crate) fn new(max_per_client: usize) -> Self {
+        BindLimiter {
+            max_per_client,
+            active: Default::default(),
+        }
+    }
+
+    /// Attempts to reserve a bind slot for the particular client.
+    /// The slot is released once the returned permit is dropped.
+    pub(crate) fn try_acquire(&self, client: String) -> Option<BindPermit> {
+        let mut active = self.active.lock().expect("bind limiter lock got poisoned");
+        let count = active.entry(client.clone()).or_default();
+        if *count >= self.max_per_client {
+            return None;
+        }
+        *count += 1;
+
+        Some(BindPermit {
+            client,
+            active: Arc::clone(&self.active),
+        })
+    }
+}
+
+pub(crate) struct BindPermit {
+    client: String,
+    active: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl Drop for BindPermit {
+    fn drop(&mut self) {
+        let Ok(mut active) = self.active.lock() else {
+            return;
+        };
+        if let Some(count) = active.get_mut(&self.client) {
+            *count -= 1;
+            if *count == 0 {
+                active.remove(&self.client);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bind_permits_are_limited_per_client() {
+        let limiter = BindLimiter::new(2);
+
+        let first = limiter.try_acquire("alice".to_string()).unwrap();
+        let _second = limiter.try_acquire("alice".to_string()).unwrap();
+        assert!(limiter.try_acquire("alice".to_string()).is_none());
+
+        // other clients are not affected
+        assert!(limiter.try_acquire("bob".to_string()).is_some());
+
+        drop(first);
+        assert!(limiter.try_acquire("alice".to_string()).is_some());
+    }
+}
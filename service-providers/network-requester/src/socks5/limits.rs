@@ -0,0 +1,270 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use nym_socks5_proxy_helpers::bandwidth_limiter::BandwidthLimiter;
+use nym_socks5_requests::ExceededLimit;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const CONNECTION_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ConnectionLimits {
+    pub(crate) maximum_concurrent_connections: usize,
+    pub(crate) maximum_connections_per_minute: usize,
+
+    // 0 means there's no limit
+    pub(crate) maximum_bytes_per_second: u64,
+}
+
+struct ClientConnections {
+    active: usize,
+
+    // times at which the connections were opened within the last `CONNECTION_RATE_WINDOW`
+    recently_opened: VecDeque<Instant>,
+
+    // shared between all connections of the client
+    bandwidth_limiter: Option<BandwidthLimiter>,
+}
+
+impl ClientConnections {
+    fn new(maximum_bytes_per_second: u64) -> Self {
+        ClientConnections {
+            active: 0,
+            recently_opened: VecDeque::new(),
+            bandwidth_limiter: (maximum_bytes_per_second != 0)
+                .then(|| BandwidthLimiter::new(maximum_bytes_per_second)),
+        }
+    }
+
+    fn forget_stale(&mut self, now: Instant) {
+        while let Some(opened) = self.recently_opened.front() {
+            if now.duration_since(*opened) < CONNECTION_RATE_WINDOW {
+                break;
+            }
+            self.recently_opened.pop_front();
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.active == 0 && self.recently_opened.is_empty()
+    }
+}
+
+struct TrackedClients {
+    clients: HashMap<String, ClientConnections>,
+
+    // idle clients are only removed periodically so that not every connection attempt
+    // would have to go through all the clients
+    last_pruned: Instant,
+}
+
+impl TrackedClients {
+    fn new(now: Instant) -> Self {
+        TrackedClients {
+            clients: HashMap::new(),
+            last_pruned: now,
+        }
+    }
+
+    fn maybe_prune(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_pruned) < CONNECTION_RATE_WINDOW {
+            return;
+        }
+        self.clients.retain(|_, connections| {
+            connections.forget_stale(now);
+            !connections.is_idle()
+        });
+        self.last_pruned = now;
+    }
+}
+
+/// Keeps track of the outbound connections opened on behalf of each client
+/// and enforces the per-client limits on them.
+///
+/// Note that the clients are identified by their return address. For anonymous clients that's their
+/// sender tag, which they can change at no cost by simply reconnecting. So the limits only really bind
+/// the clients that reveal their address or that keep using the same sender tag.
+#[derive(Clone)]
+pub(crate) struct ConnectionLimiter {
+    limits: ConnectionLimits,
+    clients: Arc<Mutex<TrackedClients>>,
+}
+
+impl ConnectionLimiter {
+    pub(crate) fn new(limits: ConnectionLimits) -> Self {
+        ConnectionLimiter {
+            limits,
+            clients: Arc::new(Mutex::new(TrackedClients::new(Instant::now()))),
+        }
+    }
+
+    /// Attempts to reserve a new connection for the particular client.
+    /// The connection is considered active until the returned permit is dropped.
+    pub(crate) fn try_acquire(&self, client: String) -> Result<ConnectionPermit, ExceededLimit> {
+        self.try_acquire_at(client, Instant::now())
+    }
+
+    fn try_acquire_at(
+        &self,
+        client: String,
+        now: Instant,
+    ) -> Result<ConnectionPermit, ExceededLimit> {
+        let mut tracked = self
+            .clients
+            .lock()
+            .expect("connection limiter lock got poisoned");
+
+        // get rid of clients we no longer have to remember anything about
+        tracked.maybe_prune(now);
+
+        let connections = tracked
+            .clients
+            .entry(client.clone())
+            .or_insert_with(|| ClientConnections::new(self.limits.maximum_bytes_per_second));
+        connections.forget_stale(now);
+
+        if connections.active >= self.limits.maximum_concurrent_connections {
+            return Err(ExceededLimit::ConcurrentConnections {
+                maximum: self.limits.maximum_concurrent_connections as u32,
+            });
+        }
+        if connections.recently_opened.len() >= self.limits.maximum_connections_per_minute {
+            return Err(ExceededLimit::ConnectionsPerMinute {
+                maximum: self.limits.maximum_connections_per_minute as u32,
+            });
+        }
+
+        connections.active += 1;
+        connections.recently_opened.push_back(now);
+
+        Ok(ConnectionPermit {
+            client,
+            bandwidth_limiter: connections.bandwidth_limiter.clone(),
+            clients: Arc::clone(&self.clients),
+        })
+    }
+}
+
+pub(crate) struct ConnectionPermit {
+    client: String,
+    bandwidth_limiter: Option<BandwidthLimiter>,
+    clients: Arc<Mutex<TrackedClients>>,
+}
+
+impl ConnectionPermit {
+    pub(crate) fn bandwidth_limiter(&self) -> Option<BandwidthLimiter> {
+        self.bandwidth_limiter.clone()
+    }
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        let Ok(mut tracked) = self.clients.lock() else {
+            return;
+        };
+        // note: we can't remove the entry even if there are no more active connections
+        // as we still have to remember when they were opened
+        if let Some(connections) = tracked.clients.get_mut(&self.client) {
+            connections.active = connections.active.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(concurrent: usize, per_minute: usize) -> ConnectionLimiter {
+        ConnectionLimiter::new(ConnectionLimits {
+            maximum_concurrent_connections: concurrent,
+            maximum_connections_per_minute: per_minute,
+            maximum_bytes_per_second: 0,
+        })
+    }
+
+    #[test]
+    fn concurrent_connections_are_limited_per_client() {
+        let limiter = limiter(2, 100);
+        let now = Instant::now();
+
+        let first = limiter.try_acquire_at("alice".to_string(), now).unwrap();
+        let _second = limiter.try_acquire_at("alice".to_string(), now).unwrap();
+        assert_eq!(
+            limiter.try_acquire_at("alice".to_string(), now).err(),
+            Some(ExceededLimit::ConcurrentConnections { maximum: 2 })
+        );
+
+        // other clients are not affected
+        assert!(limiter.try_acquire_at("bob".to_string(), now).is_ok());
+
+        drop(first);
+        assert!(limiter.try_acquire_at("alice".to_string(), now).is_ok());
+    }
+
+    #[test]
+    fn connection_rate_is_limited_per_client() {
+        let limiter = limiter(100, 2);
+        let now = Instant::now();
+
+        drop(limiter.try_acquire_at("alice".to_string(), now).unwrap());
+        drop(limiter.try_acquire_at("alice".to_string(), now).unwrap());
+
+        // closing the connections does not make any difference
+        assert_eq!(
+            limiter.try_acquire_at("alice".to_string(), now).err(),
+            Some(ExceededLimit::ConnectionsPerMinute { maximum: 2 })
+        );
+        assert!(limiter.try_acquire_at("bob".to_string(), now).is_ok());
+
+        let later = now + CONNECTION_RATE_WINDOW;
+        assert!(limiter.try_acquire_at("alice".to_string(), later).is_ok());
+    }
+
+    #[test]
+    fn idle_clients_are_forgotten() {
+        let limiter = limiter(100, 100);
+        let now = limiter.clients.lock().unwrap().last_pruned;
+
+        drop(limiter.try_acquire_at("alice".to_string(), now).unwrap());
+        let _bob = limiter
+            .try_acquire_at("bob".to_string(), now + CONNECTION_RATE_WINDOW)
+            .unwrap();
+
+        let tracked = limiter.clients.lock().unwrap();
+        assert!(!tracked.clients.contains_key("alice"));
+        assert!(tracked.clients.contains_key("bob"));
+    }
+
+    #[test]
+    fn idle_clients_are_only_pruned_periodically() {
+        let limiter = limiter(100, 100);
+        let now = limiter.clients.lock().unwrap().last_pruned;
+
+        drop(limiter.try_acquire_at("alice".to_string(), now).unwrap());
+
+        // alice is already idle, but it's not yet time to go through all the clients
+        let half_window = CONNECTION_RATE_WINDOW / 2;
+        drop(
+            limiter
+                .try_acquire_at("bob".to_string(), now + half_window)
+                .unwrap(),
+        );
+        assert!(limiter
+            .clients
+            .lock()
+            .unwrap()
+            .clients
+            .contains_key("alice"));
+
+        // but she's gone at the next pruning, unlike bob, who has opened a connection recently
+        let _carol = limiter
+            .try_acquire_at("carol".to_string(), now + CONNECTION_RATE_WINDOW)
+            .unwrap();
+        let tracked = limiter.clients.lock().unwrap();
+        assert!(!tracked.clients.contains_key("alice"));
+        assert!(tracked.clients.contains_key("bob"));
+        assert_eq!(tracked.last_pruned, now + CONNECTION_RATE_WINDOW);
+    }
+}
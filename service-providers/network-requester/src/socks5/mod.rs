@@ -1,4 +1,7 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+pub(super) mod bind;
+pub(super) mod limits;
 pub(super) mod tcp;
+pub(super) mod udp;
@@ -4,6 +4,7 @@
 use crate::reply;
 use crate::reply::MixnetMessage;
 use nym_service_providers_common::interface::RequestVersion;
+use nym_socks5_proxy_helpers::bandwidth_limiter::BandwidthLimiter;
 use nym_socks5_proxy_helpers::connection_controller::ConnectionReceiver;
 use nym_socks5_proxy_helpers::proxy_runner::{MixProxySender, ProxyRunner};
 use nym_socks5_requests::{ConnectionId, RemoteAddress, Socks5Request};
@@ -40,6 +41,22 @@ impl Connection {
         })
     }
 
+    /// Wraps an already established connection, such as one accepted on a bind listener.
+    pub(crate) fn new_established(
+        id: ConnectionId,
+        address: RemoteAddress,
+        conn: TcpStream,
+        return_address: reply::MixnetAddress,
+    ) -> Self {
+        Connection {
+            id,
+            address,
+            conn: Some(conn),
+            return_address,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn run_proxy(
         &mut self,
         remote_version: RequestVersion<Socks5Request>,
@@ -47,13 +64,14 @@ impl Connection {
         mix_receiver: ConnectionReceiver,
         mix_sender: MixProxySender<MixnetMessage>,
         lane_queue_lengths: LaneQueueLengths,
+        bandwidth_limiter: Option<BandwidthLimiter>,
         shutdown: TaskClient,
     ) {
         let stream = self.conn.take().unwrap();
         let remote_source_address = "???".to_string(); // we don't know ip address of requester
         let connection_id = self.id;
         let return_address = self.return_address.clone();
-        let (stream, _) = ProxyRunner::new(
+        let mut proxy_runner = ProxyRunner::new(
             stream,
             self.address.clone(),
             remote_source_address,
@@ -65,19 +83,24 @@ impl Connection {
             connection_id,
             Some(lane_queue_lengths),
             shutdown,
-        )
-        .run(move |socket_data| {
-            MixnetMessage::new_network_data_response_content(
-                return_address.clone(),
-                remote_version.clone(),
-                socket_data.header.seq,
-                socket_data.header.connection_id,
-                socket_data.data,
-                socket_data.header.local_socket_closed,
-            )
-        })
-        .await
-        .into_inner();
+        );
+        if let Some(bandwidth_limiter) = bandwidth_limiter {
+            proxy_runner = proxy_runner.with_bandwidth_limiter(bandwidth_limiter);
+        }
+
+        let (stream, _) = proxy_runner
+            .run(move |socket_data| {
+                MixnetMessage::new_network_data_response_content(
+                    return_address.clone(),
+                    remote_version.clone(),
+                    socket_data.header.seq,
+                    socket_data.header.connection_id,
+                    socket_data.data,
+                    socket_data.header.local_socket_closed,
+                )
+            })
+            .await
+            .into_inner();
         self.conn = Some(stream);
     }
 }
@@ -0,0 +1,225 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::reply;
+use crate::reply::MixnetMessage;
+use crate::request_filter::RequestFilter;
+use futures::channel::mpsc;
+use futures::StreamExt;
+use log::*;
+use nym_service_providers_common::interface::RequestVersion;
+use nym_socks5_proxy_helpers::proxy_runner::MixProxySender;
+use nym_socks5_requests::{ConnectionId, Datagram, RemoteAddress, Socks5Request};
+use nym_task::connections::{ConnectionCommand, ConnectionCommandSender};
+use nym_task::TaskClient;
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use tokio::net::{lookup_host, UdpSocket};
+use tokio::time::{sleep, Instant};
+
+const MAX_DATAGRAM_SIZE: usize = u16::MAX as usize;
+
+/// Channel used for forwarding datagrams received from the mixnet into particular session.
+pub(crate) type DatagramSessionSender = mpsc::UnboundedSender<Datagram>;
+
+/// Receiver part of the [`DatagramSessionSender`]
+pub(crate) type DatagramSessionReceiver = mpsc::UnboundedReceiver<Datagram>;
+
+/// An outbound UDP session of the Socks5 service provider, which sends datagrams
+/// on behalf of users and returns any responses through the mixnet.
+pub(crate) struct DatagramSession {
+    id: ConnectionId,
+    return_address: reply::MixnetAddress,
+    remote_version: RequestVersion<Socks5Request>,
+    request_filter: RequestFilter,
+    idle_timeout: Duration,
+
+    ipv4_socket: UdpSocket,
+    // not every host has got IPv6 connectivity
+    ipv6_socket: Option<UdpSocket>,
+
+    /// Cache of socket addresses the remote destinations have been resolved to,
+    /// so that we wouldn't need to perform a DNS lookup for every single datagram.
+    resolved_destinations: HashMap<RemoteAddress, SocketAddr>,
+
+    /// Addresses we have sent any datagrams to and thus we're accepting responses from.
+    contacted: HashSet<SocketAddr>,
+}
+
+impl DatagramSession {
+    pub(crate) async fn new(
+        id: ConnectionId,
+        return_address: reply::MixnetAddress,
+        remote_version: RequestVersion<Socks5Request>,
+        request_filter: RequestFilter,
+        idle_timeout: Duration,
+    ) -> io::Result<Self> {
+        let ipv4_socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+        let ipv6_socket = match UdpSocket::bind((Ipv6Addr::UNSPECIFIED, 0)).await {
+            Ok(socket) => Some(socket),
+            Err(err) => {
+                debug!("failed to bind IPv6 socket for datagram session {id}: {err}");
+                None
+            }
+        };
+
+        Ok(DatagramSession {
+            id,
+            return_address,
+            remote_version,
+            request_filter,
+            idle_timeout,
+            ipv4_socket,
+            ipv6_socket,
+            resolved_destinations: HashMap::new(),
+            contacted: HashSet::new(),
+        })
+    }
+
+    async fn resolve_destination(&mut self, remote: RemoteAddress) -> Option<SocketAddr> {
+        if let Some(resolved) = self.resolved_destinations.get(&remote) {
+            return Some(*resolved);
+        }
+
+        let resolved = match lookup_host(&remote).await {
+            Ok(mut addrs) => addrs.next()?,
+            Err(err) => {
+                debug!("failed to resolve {remote}: {err}");
+                return None;
+            }
+        };
+        self.resolved_destinations.insert(remote, resolved);
+        Some(resolved)
+    }
+
+    async fn send_to_remote(&mut self, datagram: Datagram) {
+        let Some(destination) = self.resolve_destination(datagram.address).await else {
+            return;
+        };
+
+        // every single datagram has to be checked against the exit policy
+        if !self.request_filter.check_socket_address(&destination) {
+            info!("Datagram destination {destination} failed filter check");
+            return;
+        }
+
+        let socket = if destination.is_ipv4() {
+            &self.ipv4_socket
+        } else if let Some(ipv6_socket) = &self.ipv6_socket {
+            ipv6_socket
+        } else {
+            debug!("can't send datagram to {destination} as IPv6 is not available");
+            return;
+        };
+
+        if let Err(err) = socket.send_to(&datagram.data, destination).await {
+            debug!("failed to send datagram to {destination}: {err}");
+            return;
+        }
+        self.contacted.insert(destination);
+    }
+
+    /// Attempts to forward the received datagram back to the client.
+    /// Returns a boolean indicating whether it was successful.
+    async fn send_to_mixnet(
+        &self,
+        received: io::Result<(usize, SocketAddr)>,
+        buf: &[u8],
+        mix_sender: &MixProxySender<MixnetMessage>,
+    ) -> bool {
+        let (len, source) = match received {
+            Ok(received) => received,
+            Err(err) => {
+                debug!("failed to receive datagram on session {}: {err}", self.id);
+                return false;
+            }
+        };
+
+        // only let through responses from the hosts we have previously contacted
+        if !self.contacted.contains(&source) {
+            trace!("dropping unsolicited datagram from {source}");
+            return false;
+        }
+
+        let datagram = Datagram::new(self.id, source.to_string(), buf[..len].to_vec());
+        let mixnet_message = MixnetMessage::new_datagram_response(
+            self.return_address.clone(),
+            self.remote_version.clone(),
+            datagram,
+        );
+        mix_sender
+            .send(mixnet_message)
+            .await
+            .expect("InputMessageReceiver has stopped receiving!");
+        true
+    }
+
+    pub(crate) async fn run(
+        mut self,
+        mut mix_receiver: DatagramSessionReceiver,
+        mix_sender: MixProxySender<MixnetMessage>,
+        connection_command_sender: ConnectionCommandSender,
+        mut shutdown: TaskClient,
+    ) {
+        let mut ipv4_buf = vec![0u8; MAX_DATAGRAM_SIZE];
+        let mut ipv6_buf = vec![0u8; MAX_DATAGRAM_SIZE];
+
+        let idle = sleep(self.idle_timeout);
+        tokio::pin!(idle);
+
+        loop {
+            tokio::select! {
+                biased;
+                _ = shutdown.recv() => {
+                    trace!("DatagramSession: Received shutdown");
+                    break;
+                }
+                _ = &mut idle => {
+                    debug!("datagram session {} has been idle for too long", self.id);
+                    break;
+                }
+                datagram = mix_receiver.next() => {
+                    let Some(datagram) = datagram else {
+                        trace!("DatagramSession: Stopping since channel closed");
+                        break;
+                    };
+                    self.send_to_remote(datagram).await;
+                    idle.as_mut().reset(Instant::now() + self.idle_timeout);
+                }
+                received = self.ipv4_socket.recv_from(&mut ipv4_buf) => {
+                    if self.send_to_mixnet(received, &ipv4_buf, &mix_sender).await {
+                        idle.as_mut().reset(Instant::now() + self.idle_timeout);
+                    }
+                }
+                received = recv_from_optional(self.ipv6_socket.as_ref(), &mut ipv6_buf) => {
+                    if self.send_to_mixnet(received, &ipv6_buf, &mix_sender).await {
+                        idle.as_mut().reset(Instant::now() + self.idle_timeout);
+                    }
+                }
+            }
+        }
+
+        // make sure to clean up the lane associated with this session
+        if let Err(err) =
+            connection_command_sender.unbounded_send(ConnectionCommand::Close(self.id))
+        {
+            debug!(
+                "failed to close the lane of datagram session {}: {err}",
+                self.id
+            );
+        }
+        shutdown.disarm();
+    }
+}
+
+async fn recv_from_optional(
+    socket: Option<&UdpSocket>,
+    buf: &mut [u8],
+) -> io::Result<(usize, SocketAddr)> {
+    match socket {
+        Some(socket) => socket.recv_from(buf).await,
+        None => std::future::pending().await,
+    }
+}
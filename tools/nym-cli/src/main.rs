@@ -110,7 +110,7 @@ async fn execute(cli: Cli) -> anyhow::Result<()> {
             validator::cosmwasm::execute(args, cosmwasm, &network_details).await?
         }
         Commands::Tx(transactions) => {
-            validator::transactions::execute(transactions, &network_details).await?
+            validator::transactions::execute(transactions, &network_details, mnemonic).await?
         }
         Commands::VestingSchedule(vesting) => {
             validator::vesting::execute(args, vesting, &network_details).await?
@@ -13,6 +13,9 @@ pub(crate) async fn execute(
         nym_cli_commands::validator::mixnet::delegators::rewards::MixnetDelegatorsRewardCommands::Claim(args) => {
             nym_cli_commands::validator::mixnet::delegators::rewards::claim_delegator_reward::claim_delegator_reward(args, create_signing_client(global_args, network_details)?).await
         }
+        nym_cli_commands::validator::mixnet::delegators::rewards::MixnetDelegatorsRewardCommands::Compounding(args) => {
+            nym_cli_commands::validator::mixnet::delegators::rewards::delegator_reward_compounding::delegator_reward_compounding(args, create_signing_client(global_args, network_details)?).await
+        }
         _ => unreachable!(),
     }
     Ok(())
@@ -319,8 +319,11 @@ async fn main() -> anyhow::Result<()> {
 
     text_println("Registering with gateway...", &args.output);
     let mut client = QueryClient::new(args.provider, args.gateway).await?;
-    let our_gateway = client.client.nym_address().gateway();
-    text_println(&format!("  gateway: {our_gateway}"), &args.output);
+    let our_address = client.client.nym_address();
+    text_println(
+        &format!("  gateway: {}", our_address.gateway()),
+        &args.output,
+    );
 
     text_println("Sending request(s)...", &args.output);
     if args.command == Commands::Ping {
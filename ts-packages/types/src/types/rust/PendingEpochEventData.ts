@@ -4,6 +4,8 @@ import type { DecCoin } from './DecCoin';
 export type PendingEpochEventData =
   | { Delegate: { owner: string; mix_id: number; amount: DecCoin; proxy: string | null } }
   | { Undelegate: { owner: string; mix_id: number; proxy: string | null } }
+  | { Redelegate: { owner: string; from_mix_id: number; to_mix_id: number; amount: DecCoin; proxy: string | null } }
   | { PledgeMore: { mix_id: number; amount: DecCoin } }
+  | { DecreasePledge: { mix_id: number; decrease_by: DecCoin } }
   | { UnbondMixnode: { mix_id: number } }
   | { UpdateActiveSetSize: { new_size: number } };
@@ -5,7 +5,7 @@ use crate::active_requests::ActiveRequests;
 use futures::channel::mpsc;
 use futures::StreamExt;
 use nym_service_providers_common::interface::ResponseContent;
-use nym_socks5_requests::{Socks5ProviderResponse, Socks5ResponseContent};
+use nym_socks5_requests::{ConnectionError, Socks5ProviderResponse, Socks5ResponseContent};
 use wasm_bindgen_futures::spawn_local;
 use wasm_client_core::client::base_client::ClientOutput;
 use wasm_client_core::client::received_buffer::{
@@ -64,12 +64,22 @@ impl RequestWriter {
                     Socks5ResponseContent::ConnectionError(err) => {
                         self.requests.reject(err.connection_id, err.into()).await;
                     }
+                    Socks5ResponseContent::LimitExceeded(limit_exceeded) => {
+                        let err = ConnectionError::from(limit_exceeded);
+                        self.requests.reject(err.connection_id, err.into()).await;
+                    }
                     Socks5ResponseContent::Query(query) => {
                         console_error!("received a provider query response even though we didn't send any queries! - {query:#?}")
                     }
                     Socks5ResponseContent::NetworkData { content } => {
                         self.requests.try_send_data_to_go(content).await;
                     }
+                    Socks5ResponseContent::Datagram(datagram) => {
+                        console_error!("received a datagram even though we never opened any datagram sessions! (session: {})", datagram.session_id)
+                    }
+                    Socks5ResponseContent::Bind(bind) => {
+                        console_error!("received a bind response even though we never sent any bind requests! (connection: {})", bind.connection_id)
+                    }
                 },
             },
         }